@@ -35,12 +35,7 @@ impl BenchContext {
     pub(crate) async fn new(bench_args: &BenchmarkArgs, rpc_url: String) -> eyre::Result<Self> {
         info!("Running benchmark using data from RPC URL: {}", rpc_url);
 
-        // Ensure that output directory is a directory
-        if let Some(output) = &bench_args.output {
-            if output.is_file() {
-                return Err(eyre::eyre!("Output path must be a directory"));
-            }
-        }
+        ensure_output_dir(bench_args)?;
 
         // set up alloy client for blocks
         let block_provider =
@@ -51,25 +46,7 @@ impl BenchContext {
         let mut benchmark_mode = BenchMode::new(bench_args.from, bench_args.to)?;
 
         // construct the authenticated provider
-        let auth_jwt = bench_args
-            .auth_jwtsecret
-            .clone()
-            .ok_or_else(|| eyre::eyre!("--jwtsecret must be provided for authenticated RPC"))?;
-
-        // fetch jwt from file
-        //
-        // the jwt is hex encoded so we will decode it after
-        let jwt = std::fs::read_to_string(auth_jwt)?;
-        let jwt = JwtSecret::from_hex(jwt)?;
-
-        // get engine url
-        let auth_url = Url::parse(&bench_args.engine_rpc_url)?;
-
-        // construct the authed transport
-        info!("Connecting to Engine RPC at {} for replay", auth_url);
-        let auth_transport = AuthenticatedTransportConnect::new(auth_url, jwt);
-        let client = ClientBuilder::default().connect_boxed(auth_transport).await?;
-        let auth_provider = RootProvider::<_, AnyNetwork>::new(client);
+        let auth_provider = auth_provider(bench_args).await?;
 
         let first_block = match benchmark_mode {
             BenchMode::Continuous => {
@@ -101,3 +78,39 @@ impl BenchContext {
         Ok(Self { auth_provider, block_provider, benchmark_mode, next_block })
     }
 }
+
+/// Ensures that the output path of the benchmark, if any, is a directory.
+pub(crate) fn ensure_output_dir(bench_args: &BenchmarkArgs) -> eyre::Result<()> {
+    if let Some(output) = &bench_args.output {
+        if output.is_file() {
+            return Err(eyre::eyre!("Output path must be a directory"));
+        }
+    }
+    Ok(())
+}
+
+/// Constructs the authenticated provider for engine API queries from the `--jwtsecret` and
+/// `--engine-rpc-url` of the [`BenchmarkArgs`].
+pub(crate) async fn auth_provider(
+    bench_args: &BenchmarkArgs,
+) -> eyre::Result<RootProvider<BoxTransport, AnyNetwork>> {
+    let auth_jwt = bench_args
+        .auth_jwtsecret
+        .clone()
+        .ok_or_else(|| eyre::eyre!("--jwtsecret must be provided for authenticated RPC"))?;
+
+    // fetch jwt from file
+    //
+    // the jwt is hex encoded so we will decode it after
+    let jwt = std::fs::read_to_string(auth_jwt)?;
+    let jwt = JwtSecret::from_hex(jwt)?;
+
+    // get engine url
+    let auth_url = Url::parse(&bench_args.engine_rpc_url)?;
+
+    // construct the authed transport
+    info!("Connecting to Engine RPC at {} for replay", auth_url);
+    let auth_transport = AuthenticatedTransportConnect::new(auth_url, jwt);
+    let client = ClientBuilder::default().connect_boxed(auth_transport).await?;
+    Ok(RootProvider::<_, AnyNetwork>::new(client))
+}
@@ -0,0 +1,276 @@
+//! A corpus of recorded payloads, that can be replayed without access to an RPC.
+//!
+//! A corpus is either a JSONL file of [`CorpusPayload`]s, as written by `reth-bench
+//! dump-payloads`, or a directory of engine API messages stored by a node running with
+//! `--debug.engine-api-store`.
+
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::{ExecutionPayload, ExecutionPayloadSidecar, ForkchoiceState};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+use tracing::{debug, warn};
+
+/// A recorded payload, with everything needed to replay it with `newPayload` and
+/// `forkchoiceUpdated`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CorpusPayload {
+    /// The execution payload sent with `newPayload`.
+    pub(crate) execution_payload: ExecutionPayload,
+    /// The versioned hashes of the blob transactions in the payload.
+    #[serde(default)]
+    pub(crate) versioned_hashes: Vec<B256>,
+    /// The parent beacon block root, required for V3 payloads.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) parent_beacon_block_root: Option<B256>,
+    /// The forkchoice state sent after the payload.
+    ///
+    /// If this is not set, the payload is made the head without a safe or finalized block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) forkchoice_state: Option<ForkchoiceState>,
+}
+
+impl CorpusPayload {
+    /// Returns the block number of the payload.
+    pub(crate) const fn block_number(&self) -> u64 {
+        self.execution_payload.as_v1().block_number
+    }
+
+    /// Returns the gas used by the payload.
+    pub(crate) const fn gas_used(&self) -> u64 {
+        self.execution_payload.as_v1().gas_used
+    }
+
+    /// Returns the forkchoice state to send after the payload.
+    pub(crate) fn forkchoice_state(&self) -> ForkchoiceState {
+        self.forkchoice_state.unwrap_or_else(|| ForkchoiceState {
+            head_block_hash: self.execution_payload.block_hash(),
+            safe_block_hash: B256::ZERO,
+            finalized_block_hash: B256::ZERO,
+        })
+    }
+}
+
+/// An engine API message stored by the node's engine message store.
+///
+/// Payload attributes of stored forkchoice updates are ignored, the benchmark does not build
+/// payloads.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum StoredEngineApiMessage {
+    ForkchoiceUpdated { state: ForkchoiceState },
+    NewPayload { payload: Box<ExecutionPayload>, sidecar: ExecutionPayloadSidecar },
+}
+
+/// Reads the corpus at the given path and returns its payloads in the range `from..=to`.
+///
+/// The returned payloads are ordered by block number and must form a contiguous chain, so that
+/// every replay of the corpus sends the same messages.
+pub(crate) fn read_corpus(
+    path: &Path,
+    from: Option<u64>,
+    to: Option<u64>,
+) -> eyre::Result<Vec<CorpusPayload>> {
+    let mut payloads =
+        if path.is_dir() { read_engine_api_store(path)? } else { read_payload_file(path)? };
+
+    payloads.retain(|payload| {
+        from.is_none_or(|from| payload.block_number() >= from) &&
+            to.is_none_or(|to| payload.block_number() <= to)
+    });
+    payloads.sort_by_key(CorpusPayload::block_number);
+
+    for window in payloads.windows(2) {
+        let (parent, payload) = (&window[0], &window[1]);
+        if payload.execution_payload.parent_hash() != parent.execution_payload.block_hash() {
+            return Err(eyre::eyre!(
+                "Payload corpus is not a contiguous chain, block {} is not a child of block {}",
+                payload.block_number(),
+                parent.block_number()
+            ))
+        }
+    }
+
+    if payloads.is_empty() {
+        return Err(eyre::eyre!("Payload corpus at {} has no payloads to replay", path.display()))
+    }
+
+    Ok(payloads)
+}
+
+/// Reads a JSONL file of [`CorpusPayload`]s.
+fn read_payload_file(path: &Path) -> eyre::Result<Vec<CorpusPayload>> {
+    let mut payloads = Vec::new();
+    for (index, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue
+        }
+        let payload = serde_json::from_str(&line).map_err(|err| {
+            eyre::eyre!("Invalid payload on line {} of {}: {err}", index + 1, path.display())
+        })?;
+        payloads.push(payload);
+    }
+    Ok(payloads)
+}
+
+/// Reads a directory of engine API messages, named `<timestamp>-<method>-<hash>.json`.
+///
+/// Payloads that were received more than once are only replayed once, and every payload is
+/// followed by the last forkchoice state that made it the head, if any.
+fn read_engine_api_store(path: &Path) -> eyre::Result<Vec<CorpusPayload>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let path = entry?.path();
+        let timestamp = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .filter(|name| name.ends_with(".json"))
+            .and_then(|name| name.split('-').next()?.parse::<u64>().ok());
+        match timestamp {
+            Some(timestamp) => files.push((timestamp, path)),
+            None => warn!(?path, "Skipping file that is not a stored engine API message"),
+        }
+    }
+    files.sort();
+
+    let mut payloads = Vec::new();
+    let mut forkchoice_states = HashMap::new();
+    for (_, path) in files {
+        debug!(?path, "Reading stored engine API message");
+        match serde_json::from_slice(&std::fs::read(&path)?)
+            .map_err(|err| eyre::eyre!("Invalid engine API message {}: {err}", path.display()))?
+        {
+            StoredEngineApiMessage::ForkchoiceUpdated { state } => {
+                forkchoice_states.insert(state.head_block_hash, state);
+            }
+            StoredEngineApiMessage::NewPayload { payload, sidecar } => {
+                payloads.push(CorpusPayload {
+                    versioned_hashes: sidecar.versioned_hashes().cloned().unwrap_or_default(),
+                    parent_beacon_block_root: sidecar.parent_beacon_block_root(),
+                    execution_payload: *payload,
+                    forkchoice_state: None,
+                });
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    payloads.retain(|payload| seen.insert(payload.execution_payload.block_hash()));
+    for payload in &mut payloads {
+        payload.forkchoice_state =
+            forkchoice_states.get(&payload.execution_payload.block_hash()).copied();
+    }
+
+    Ok(payloads)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bloom, Bytes, U256};
+    use alloy_rpc_types_engine::{CancunPayloadFields, ExecutionPayloadV1};
+    use std::io::Write;
+
+    fn payload(number: u64, parent_hash: B256) -> ExecutionPayload {
+        ExecutionPayload::V1(ExecutionPayloadV1 {
+            parent_hash,
+            fee_recipient: Default::default(),
+            state_root: Default::default(),
+            receipts_root: Default::default(),
+            logs_bloom: Bloom::default(),
+            prev_randao: Default::default(),
+            block_number: number,
+            gas_limit: 30_000_000,
+            gas_used: number * 1_000,
+            timestamp: number * 12,
+            extra_data: Bytes::default(),
+            base_fee_per_gas: U256::from(7),
+            block_hash: B256::with_last_byte(number as u8),
+            transactions: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn read_payload_file_in_range() {
+        let dir = std::env::temp_dir().join(format!("reth-bench-corpus-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("payloads.jsonl");
+
+        let mut file = File::create(&path).unwrap();
+        for number in [2, 1, 4] {
+            let payload = CorpusPayload {
+                execution_payload: payload(number, B256::with_last_byte(number as u8 - 1)),
+                versioned_hashes: Vec::new(),
+                parent_beacon_block_root: None,
+                forkchoice_state: None,
+            };
+            writeln!(file, "{}", serde_json::to_string(&payload).unwrap()).unwrap();
+        }
+        drop(file);
+
+        let payloads = read_corpus(&path, None, Some(3)).unwrap();
+        assert_eq!(payloads.iter().map(CorpusPayload::block_number).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(payloads[0].gas_used(), 1_000);
+        assert_eq!(payloads[1].forkchoice_state().head_block_hash, B256::with_last_byte(2));
+
+        let gap = read_corpus(&path, Some(2), None).map(|_| ()).unwrap_err();
+        assert!(gap.to_string().contains("not a contiguous chain"), "{gap}");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn read_stored_engine_api_messages() {
+        let dir = std::env::temp_dir().join(format!("reth-bench-store-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let first = payload(1, B256::ZERO);
+        let second = payload(2, first.block_hash());
+        let sidecar = ExecutionPayloadSidecar::v3(CancunPayloadFields::new(
+            B256::with_last_byte(0xbb),
+            vec![B256::with_last_byte(0xaa)],
+        ));
+        let state = ForkchoiceState {
+            head_block_hash: first.block_hash(),
+            safe_block_hash: B256::ZERO,
+            finalized_block_hash: B256::ZERO,
+        };
+        let messages = [
+            (
+                "1-new_payload-1.json",
+                serde_json::json!({ "newPayload": { "payload": first, "sidecar": sidecar } }),
+            ),
+            (
+                "2-fcu-1.json",
+                serde_json::json!({ "forkchoiceUpdated": { "state": state, "payload_attrs": null } }),
+            ),
+            (
+                "3-new_payload-2.json",
+                serde_json::json!({ "newPayload": { "payload": second, "sidecar": sidecar } }),
+            ),
+            // resent payload
+            (
+                "4-new_payload-2.json",
+                serde_json::json!({ "newPayload": { "payload": second, "sidecar": sidecar } }),
+            ),
+        ];
+        for (name, message) in messages {
+            std::fs::write(dir.join(name), serde_json::to_vec(&message).unwrap()).unwrap();
+        }
+
+        let payloads = read_corpus(&dir, None, None).unwrap();
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(payloads[0].forkchoice_state, Some(state));
+        assert_eq!(payloads[0].versioned_hashes, [B256::with_last_byte(0xaa)]);
+        assert_eq!(payloads[0].parent_beacon_block_root, Some(B256::with_last_byte(0xbb)));
+        assert_eq!(payloads[1].forkchoice_state, None);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
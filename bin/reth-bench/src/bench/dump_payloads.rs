@@ -0,0 +1,97 @@
+//! Runs the `reth bench dump-payloads` command, writing a range of blocks to a payload corpus that
+//! can be replayed with `reth bench replay-payloads`.
+
+use crate::bench::{corpus::CorpusPayload, new_payload_fcu::from_any_rpc_block};
+use alloy_primitives::B256;
+use alloy_provider::{network::AnyNetwork, Provider, ProviderBuilder, RootProvider};
+use alloy_rpc_types_engine::ForkchoiceState;
+use alloy_transport_http::Http;
+use clap::Parser;
+use reqwest::Client;
+use reth_cli_runner::CliContext;
+use reth_rpc_types_compat::engine::payload::block_to_payload;
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+use tracing::{debug, info};
+
+/// `reth benchmark dump-payloads` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The RPC url to use for getting data, e.g. a node running on the datadir of the blocks.
+    #[arg(long, value_name = "RPC_URL", verbatim_doc_comment)]
+    rpc_url: String,
+
+    /// The first block to dump.
+    #[arg(long, verbatim_doc_comment)]
+    from: u64,
+
+    /// The last block to dump.
+    #[arg(long, verbatim_doc_comment)]
+    to: u64,
+
+    /// The JSONL file the payloads are written to.
+    #[arg(long, short, value_name = "FILE", verbatim_doc_comment)]
+    output: PathBuf,
+}
+
+impl Command {
+    /// Execute `benchmark dump-payloads` command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        if self.from > self.to {
+            return Err(eyre::eyre!("--from must not be greater than --to"))
+        }
+
+        info!("Dumping blocks {}..={} from RPC URL: {}", self.from, self.to, self.rpc_url);
+        let block_provider =
+            ProviderBuilder::new().network::<AnyNetwork>().on_http(self.rpc_url.parse()?);
+
+        let mut writer = BufWriter::new(File::create(&self.output)?);
+        for block_number in self.from..=self.to {
+            let block = block_provider
+                .get_block_by_number(block_number.into(), true.into())
+                .await?
+                .ok_or_else(|| eyre::eyre!("Block {block_number} not found"))?;
+            let block = from_any_rpc_block(block);
+
+            // same safe and finalized distances as `new-payload-fcu`
+            let safe_block_hash = block_hash(&block_provider, block_number.saturating_sub(32));
+            let finalized_block_hash = block_hash(&block_provider, block_number.saturating_sub(64));
+            let (safe_block_hash, finalized_block_hash) =
+                tokio::try_join!(safe_block_hash, finalized_block_hash)?;
+
+            let payload = CorpusPayload {
+                versioned_hashes: block.body().blob_versioned_hashes_iter().copied().collect(),
+                parent_beacon_block_root: block.parent_beacon_block_root,
+                forkchoice_state: Some(ForkchoiceState {
+                    head_block_hash: block.hash(),
+                    safe_block_hash,
+                    finalized_block_hash,
+                }),
+                execution_payload: block_to_payload(block).0,
+            };
+
+            debug!(?block_number, "Writing payload");
+            serde_json::to_writer(&mut writer, &payload)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+
+        info!("Finished writing payloads to {:?}.", self.output);
+        Ok(())
+    }
+}
+
+/// Fetches the hash of the given block.
+async fn block_hash(
+    provider: &RootProvider<Http<Client>, AnyNetwork>,
+    block_number: u64,
+) -> eyre::Result<B256> {
+    let block = provider
+        .get_block_by_number(block_number.into(), false.into())
+        .await?
+        .ok_or_else(|| eyre::eyre!("Block {block_number} not found"))?;
+    Ok(block.header.hash)
+}
@@ -6,9 +6,13 @@ use reth_node_core::args::LogArgs;
 use reth_tracing::FileWorkerGuard;
 
 mod context;
+mod corpus;
+mod dump_payloads;
 mod new_payload_fcu;
 mod new_payload_only;
+mod node_metrics;
 mod output;
+mod replay_payloads;
 
 /// `reth bench` command
 #[derive(Debug, Parser)]
@@ -28,6 +32,13 @@ pub enum Subcommands {
 
     /// Benchmark which only calls subsequent `newPayload` calls.
     NewPayloadOnly(new_payload_only::Command),
+
+    /// Benchmark which replays a recorded payload corpus, calling `newPayload`, then
+    /// `forkchoiceUpdated`.
+    ReplayPayloads(replay_payloads::Command),
+
+    /// Writes a range of blocks to a payload corpus for `replay-payloads`.
+    DumpPayloads(dump_payloads::Command),
 }
 
 impl BenchmarkCommand {
@@ -39,6 +50,8 @@ impl BenchmarkCommand {
         match self.command {
             Subcommands::NewPayloadFcu(command) => command.execute(ctx).await,
             Subcommands::NewPayloadOnly(command) => command.execute(ctx).await,
+            Subcommands::ReplayPayloads(command) => command.execute(ctx).await,
+            Subcommands::DumpPayloads(command) => command.execute(ctx).await,
         }
     }
 
@@ -0,0 +1,95 @@
+//! Scrapes the Prometheus endpoint of the benchmarked node, to attribute state root and persistence
+//! time to the replayed blocks.
+
+use reqwest::{Client, Url};
+use std::time::Duration;
+
+/// The gauge with the duration of the latest state root computation, in seconds.
+const STATE_ROOT_DURATION: &str = "reth_sync_block_validation_state_root_duration";
+
+/// The sum of all persistence durations, in seconds.
+const PERSISTENCE_DURATION_SUM: &str = "reth_consensus_engine_beacon_persistence_duration_sum";
+
+/// The timing metrics of the node at a point in time.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct MetricsSnapshot {
+    /// The duration of the latest state root computation, in seconds.
+    pub(crate) state_root_duration: Option<f64>,
+    /// The total time spent persisting blocks, in seconds.
+    pub(crate) persistence_duration_sum: f64,
+}
+
+impl MetricsSnapshot {
+    /// Parses the snapshot from the Prometheus text exposition format.
+    pub(crate) fn parse(text: &str) -> Self {
+        let mut snapshot = Self::default();
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let Some((name, value)) = line.split_once(' ') else { continue };
+            // summaries are rendered with labels, e.g. `name{quantile="0.5"}`
+            let name = name.split('{').next().unwrap_or_default();
+            let Ok(value) = value.trim().parse::<f64>() else { continue };
+            match name {
+                STATE_ROOT_DURATION => snapshot.state_root_duration = Some(value),
+                PERSISTENCE_DURATION_SUM => snapshot.persistence_duration_sum += value,
+                _ => {}
+            }
+        }
+        snapshot
+    }
+
+    /// Returns the persistence time between the `previous` snapshot and this one.
+    pub(crate) fn persistence_since(&self, previous: &Self) -> Duration {
+        Duration::from_secs_f64(
+            (self.persistence_duration_sum - previous.persistence_duration_sum).max(0.0),
+        )
+    }
+
+    /// Returns the duration of the latest state root computation.
+    pub(crate) fn state_root(&self) -> Option<Duration> {
+        self.state_root_duration.map(Duration::from_secs_f64)
+    }
+}
+
+/// Scrapes the metrics endpoint of the node, e.g. `http://localhost:9001`.
+#[derive(Debug)]
+pub(crate) struct NodeMetrics {
+    client: Client,
+    url: Url,
+}
+
+impl NodeMetrics {
+    /// Creates a new scraper for the given metrics endpoint.
+    pub(crate) fn new(url: &str) -> eyre::Result<Self> {
+        Ok(Self { client: Client::new(), url: url.parse()? })
+    }
+
+    /// Fetches the current timing metrics of the node.
+    pub(crate) async fn snapshot(&self) -> eyre::Result<MetricsSnapshot> {
+        let text =
+            self.client.get(self.url.clone()).send().await?.error_for_status()?.text().await?;
+        Ok(MetricsSnapshot::parse(&text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_metrics_snapshot() {
+        let text = r#"
+# TYPE reth_sync_block_validation_state_root_duration gauge
+reth_sync_block_validation_state_root_duration 0.25
+# TYPE reth_consensus_engine_beacon_persistence_duration summary
+reth_consensus_engine_beacon_persistence_duration{quantile="0.5"} 0.1
+reth_consensus_engine_beacon_persistence_duration_sum 1.5
+reth_consensus_engine_beacon_persistence_duration_count 3
+"#;
+        let snapshot = MetricsSnapshot::parse(text);
+        assert_eq!(snapshot.state_root(), Some(Duration::from_millis(250)));
+
+        let previous = MetricsSnapshot { state_root_duration: None, persistence_duration_sum: 1.0 };
+        assert_eq!(snapshot.persistence_since(&previous), Duration::from_millis(500));
+        assert_eq!(MetricsSnapshot::parse(""), MetricsSnapshot::default());
+    }
+}
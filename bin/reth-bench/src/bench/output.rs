@@ -14,6 +14,9 @@ pub(crate) const COMBINED_OUTPUT_SUFFIX: &str = "combined_latency.csv";
 /// This is the suffix for new payload output csv files.
 pub(crate) const NEW_PAYLOAD_OUTPUT_SUFFIX: &str = "new_payload_latency.csv";
 
+/// This is the suffix for replay output csv files.
+pub(crate) const REPLAY_OUTPUT_SUFFIX: &str = "replay.csv";
+
 /// This represents the results of a single `newPayload` call in the benchmark, containing the gas
 /// used and the `newPayload` latency.
 #[derive(Debug)]
@@ -119,6 +122,60 @@ impl Serialize for CombinedResult {
     }
 }
 
+/// This represents the results of replaying a single payload from a corpus, containing the
+/// latencies of the engine API calls and, if the node's metrics are scraped, the time the node
+/// spent computing the state root and persisting blocks.
+#[derive(Debug)]
+pub(crate) struct ReplayResult {
+    /// The combined `newPayload` and `forkchoiceUpdated` result.
+    pub(crate) combined_result: CombinedResult,
+    /// The duration of the state root computation of the block.
+    pub(crate) state_root: Option<Duration>,
+    /// The time spent persisting blocks since the previous block was replayed.
+    pub(crate) persistence: Option<Duration>,
+}
+
+impl std::fmt::Display for ReplayResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.combined_result)?;
+        if let Some(state_root) = self.state_root {
+            write!(f, ", state root: {state_root:?}")?;
+        }
+        if let Some(persistence) = self.persistence {
+            write!(f, ", persistence: {persistence:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// This is a [`Serialize`] implementation for the [`ReplayResult`] struct, serializing the
+/// durations as microseconds because the csv writer would fail otherwise. Missing timings are
+/// left empty.
+impl Serialize for ReplayResult {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        let combined = &self.combined_result;
+        let mut state = serializer.serialize_struct("ReplayResult", 8)?;
+        state.serialize_field("block_number", &combined.block_number)?;
+        state.serialize_field("gas_used", &combined.new_payload_result.gas_used)?;
+        state.serialize_field(
+            "gas_per_second",
+            &(combined.new_payload_result.gas_per_second() as u64),
+        )?;
+        state.serialize_field(
+            "new_payload_latency",
+            &combined.new_payload_result.latency.as_micros(),
+        )?;
+        state.serialize_field("fcu_latency", &combined.fcu_latency.as_micros())?;
+        state.serialize_field("total_latency", &combined.total_latency.as_micros())?;
+        state.serialize_field("state_root", &self.state_root.map(|time| time.as_micros()))?;
+        state.serialize_field("persistence", &self.persistence.map(|time| time.as_micros()))?;
+        state.end()
+    }
+}
+
 /// This represents a row of total gas data in the benchmark.
 #[derive(Debug)]
 pub(crate) struct TotalGasRow {
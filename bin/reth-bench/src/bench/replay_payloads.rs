@@ -0,0 +1,157 @@
+//! Runs the `reth bench replay-payloads` command, replaying a recorded payload corpus without
+//! access to an RPC, calling first newPayload for each block, then calling forkchoiceUpdated.
+
+use crate::{
+    bench::{
+        context::{auth_provider, ensure_output_dir},
+        corpus::read_corpus,
+        node_metrics::NodeMetrics,
+        output::{
+            CombinedResult, NewPayloadResult, ReplayResult, TotalGasOutput, TotalGasRow,
+            GAS_OUTPUT_SUFFIX, REPLAY_OUTPUT_SUFFIX,
+        },
+    },
+    valid_payload::{call_forkchoice_updated, call_new_payload},
+};
+use clap::Parser;
+use csv::Writer;
+use reth_cli_runner::CliContext;
+use reth_node_core::args::BenchmarkArgs;
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tracing::{debug, info};
+
+/// `reth benchmark replay-payloads` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The payload corpus to replay.
+    ///
+    /// This is either a JSONL file written by `reth-bench dump-payloads`, or a directory of
+    /// engine API messages stored by a node running with `--debug.engine-api-store`.
+    #[arg(long, value_name = "PATH", verbatim_doc_comment)]
+    payloads: PathBuf,
+
+    /// The metrics endpoint of the benchmarked node, e.g. `http://localhost:9001`.
+    ///
+    /// If set, the endpoint is scraped after every block to report the state root and
+    /// persistence time of the node.
+    #[arg(long, value_name = "METRICS_URL", verbatim_doc_comment)]
+    metrics_url: Option<String>,
+
+    #[command(flatten)]
+    benchmark: BenchmarkArgs,
+}
+
+impl Command {
+    /// Execute `benchmark replay-payloads` command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        info!("Running benchmark using payloads from {}", self.payloads.display());
+        ensure_output_dir(&self.benchmark)?;
+
+        // read the entire corpus up front, so that reading it is not part of the measurements
+        let payloads = read_corpus(&self.payloads, self.benchmark.from, self.benchmark.to)?;
+        info!(
+            first_block = payloads[0].block_number(),
+            last_block = payloads[payloads.len() - 1].block_number(),
+            "Loaded {} payloads",
+            payloads.len()
+        );
+
+        let auth_provider = auth_provider(&self.benchmark).await?;
+        let node_metrics = self.metrics_url.as_deref().map(NodeMetrics::new).transpose()?;
+        let mut previous_snapshot = match &node_metrics {
+            Some(metrics) => Some(metrics.snapshot().await?),
+            None => None,
+        };
+
+        // put results in a summary vec so they can be printed at the end
+        let mut results = Vec::new();
+        // only the engine API calls are timed, not the scrapes of the metrics endpoint
+        let mut total_benchmark_duration = Duration::ZERO;
+
+        for payload in payloads {
+            let gas_used = payload.gas_used();
+            let block_number = payload.block_number();
+            let forkchoice_state = payload.forkchoice_state();
+
+            debug!(?block_number, "Sending payload",);
+
+            let start = Instant::now();
+            let message_version = call_new_payload(
+                &auth_provider,
+                payload.execution_payload,
+                payload.parent_beacon_block_root,
+                payload.versioned_hashes,
+            )
+            .await?;
+
+            let new_payload_result = NewPayloadResult { gas_used, latency: start.elapsed() };
+
+            call_forkchoice_updated(&auth_provider, message_version, forkchoice_state, None)
+                .await?;
+
+            // calculate the total duration and the fcu latency, record
+            let total_latency = start.elapsed();
+            let fcu_latency = total_latency - new_payload_result.latency;
+            let combined_result =
+                CombinedResult { block_number, new_payload_result, fcu_latency, total_latency };
+
+            total_benchmark_duration += total_latency;
+
+            let mut replay_result =
+                ReplayResult { combined_result, state_root: None, persistence: None };
+            if let (Some(metrics), Some(previous)) = (&node_metrics, &mut previous_snapshot) {
+                let snapshot = metrics.snapshot().await?;
+                replay_result.state_root = snapshot.state_root();
+                replay_result.persistence = Some(snapshot.persistence_since(previous));
+                *previous = snapshot;
+            }
+
+            info!(%replay_result);
+
+            // record the current result
+            let gas_row = TotalGasRow { block_number, gas_used, time: total_benchmark_duration };
+            results.push((gas_row, replay_result));
+        }
+
+        let (gas_output_results, replay_results): (_, Vec<ReplayResult>) =
+            results.into_iter().unzip();
+
+        // write the csv output to files
+        if let Some(path) = self.benchmark.output {
+            // first write the per block results to a file
+            let output_path = path.join(REPLAY_OUTPUT_SUFFIX);
+            info!("Writing replay output to file: {:?}", output_path);
+            let mut writer = Writer::from_path(output_path)?;
+            for result in replay_results {
+                writer.serialize(result)?;
+            }
+            writer.flush()?;
+
+            // now write the gas output to a file
+            let output_path = path.join(GAS_OUTPUT_SUFFIX);
+            info!("Writing total gas output to file: {:?}", output_path);
+            let mut writer = Writer::from_path(output_path)?;
+            for row in &gas_output_results {
+                writer.serialize(row)?;
+            }
+            writer.flush()?;
+
+            info!("Finished writing benchmark output files to {:?}.", path);
+        }
+
+        // accumulate the results and calculate the overall Ggas/s
+        let gas_output = TotalGasOutput::new(gas_output_results);
+        info!(
+            total_duration=?gas_output.total_duration,
+            total_gas_used=?gas_output.total_gas_used,
+            blocks_processed=?gas_output.blocks_processed,
+            "Total Ggas/s: {:.4}",
+            gas_output.total_gigagas_per_second()
+        );
+
+        Ok(())
+    }
+}
@@ -9,7 +9,7 @@ use clap::{value_parser, Parser, Subcommand};
 use reth_chainspec::ChainSpec;
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
-    config_cmd, db, dump_genesis, import, init_cmd, init_state,
+    config_cmd, db, dump_genesis, export_era, import, import_era, init_cmd, init_state,
     node::{self, NoArgs},
     p2p, prune, recover, stage,
 };
@@ -165,6 +165,12 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
             Commands::Import(command) => runner.run_blocking_until_ctrl_c(
                 command.execute::<EthereumNode, _, _>(EthExecutorProvider::ethereum),
             ),
+            Commands::ImportEra(command) => runner.run_blocking_until_ctrl_c(
+                command.execute::<EthereumNode, _, _>(EthExecutorProvider::ethereum),
+            ),
+            Commands::ExportEra(command) => {
+                runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
+            }
             Commands::DumpGenesis(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Db(command) => {
                 runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
@@ -216,6 +222,12 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// This syncs RLP encoded blocks from a file.
     #[command(name = "import")]
     Import(import::ImportCommand<C>),
+    /// This syncs blocks from ERA1 and ERA archives.
+    #[command(name = "import-era")]
+    ImportEra(import_era::ImportEraCommand<C>),
+    /// Exports blocks to ERA1 and ERA archives.
+    #[command(name = "export-era")]
+    ExportEra(export_era::ExportEraCommand<C>),
     /// Dumps genesis block JSON configuration to stdout.
     DumpGenesis(dump_genesis::DumpGenesisCommand<C>),
     /// Database debugging utilities
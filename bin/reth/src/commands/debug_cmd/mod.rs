@@ -12,6 +12,7 @@ mod build_block;
 mod execution;
 mod in_memory_merkle;
 mod merkle;
+mod replay_engine;
 
 /// `reth debug` command
 #[derive(Debug, Parser)]
@@ -31,6 +32,8 @@ pub enum Subcommands<C: ChainSpecParser> {
     InMemoryMerkle(in_memory_merkle::Command<C>),
     /// Debug block building.
     BuildBlock(build_block::Command<C>),
+    /// Debug engine API by replaying stored messages.
+    ReplayEngine(replay_engine::Command<C>),
 }
 
 impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
@@ -46,6 +49,7 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
             Subcommands::Merkle(command) => command.execute::<N>(ctx).await,
             Subcommands::InMemoryMerkle(command) => command.execute::<N>(ctx).await,
             Subcommands::BuildBlock(command) => command.execute::<N>(ctx).await,
+            Subcommands::ReplayEngine(command) => command.execute::<N>(ctx).await,
         }
     }
 }
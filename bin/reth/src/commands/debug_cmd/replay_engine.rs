@@ -0,0 +1,355 @@
+//! Command for replaying stored engine API messages.
+
+use alloy_consensus::Header;
+use alloy_primitives::B256;
+use alloy_rpc_types::engine::PayloadAttributes;
+use clap::Parser;
+use eyre::Context;
+use futures::StreamExt;
+use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use reth_cli_runner::CliContext;
+use reth_consensus::FullConsensus;
+use reth_engine_service::service::{ChainEvent, EngineService};
+use reth_engine_tree::tree::{NoopInvalidBlockHook, TreeConfig};
+use reth_engine_util::engine_store::{
+    EngineMessageStore, StoredEngineApiMessage, StoredResponseStatus,
+};
+use reth_errors::ConsensusError;
+use reth_ethereum_payload_builder::EthereumBuilderConfig;
+use reth_fs_util as fs;
+use reth_network_p2p::{
+    bodies::client::BodiesClient,
+    download::DownloadClient,
+    error::PeerRequestResult,
+    headers::client::{HeadersClient, HeadersRequest},
+    priority::Priority,
+    BlockClient,
+};
+use reth_network_peers::{PeerId, WithPeerId};
+use reth_node_api::{BeaconConsensusEngineHandle, EngineApiMessageVersion};
+use reth_node_ethereum::{
+    consensus::EthBeaconConsensus, node::EthereumEngineValidator, EthEngineTypes, EthEvmConfig,
+    EthExecutorProvider,
+};
+use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
+use reth_primitives::{Block, BlockBody, EthPrimitives};
+use reth_provider::{
+    providers::BlockchainProvider, BlockNumReader, CanonStateSubscriptions, ChainSpecProvider,
+};
+use reth_prune::PrunerBuilder;
+use reth_stages::Pipeline;
+use reth_static_file::StaticFileProducer;
+use reth_transaction_pool::noop::NoopTransactionPool;
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use tokio::sync::{mpsc::unbounded_channel, oneshot};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::*;
+
+/// `reth debug replay-engine` command
+/// This script will read stored engine messages and replay them by the timestamp.
+/// It does not require a running consensus layer client, the messages are sent directly to the
+/// engine of a node that is started on the given datadir.
+///
+/// The node is offline: it doesn't connect to peers and has no pipeline stages, so the replayed
+/// messages must extend the chain stored in the datadir. Every canonical block is persisted before
+/// the command exits.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The path to read engine API messages from, as stored with `--debug.engine-api-store`.
+    #[arg(long = "engine-api-store", value_name = "PATH")]
+    engine_api_store: PathBuf,
+
+    /// The replay speed relative to the time the messages were received at, e.g. `2` replays
+    /// twice as fast.
+    ///
+    /// If neither this nor `--interval` is set, every message is sent as soon as the response to
+    /// the previous one arrived.
+    #[arg(long, value_name = "FACTOR", value_parser = parse_speed, conflicts_with = "interval")]
+    speed: Option<f64>,
+
+    /// The number of milliseconds between Engine API messages, instead of the recorded timing.
+    #[arg(long)]
+    interval: Option<u64>,
+
+    /// Stop the replay before the first payload above this block.
+    #[arg(long, value_name = "BLOCK")]
+    to: Option<u64>,
+
+    /// Fail if a response status differs from the status the node responded with when the
+    /// messages were stored.
+    #[arg(long)]
+    assert_status: bool,
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
+    /// Execute `debug replay-engine` command
+    pub async fn execute<
+        N: CliNodeTypes<Engine = EthEngineTypes, Primitives = EthPrimitives, ChainSpec = C::ChainSpec>,
+    >(
+        self,
+        ctx: CliContext,
+    ) -> eyre::Result<()> {
+        let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
+
+        let consensus: Arc<dyn FullConsensus<EthPrimitives, Error = ConsensusError>> =
+            Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
+        let executor = EthExecutorProvider::ethereum(provider_factory.chain_spec());
+
+        // Configure blockchain provider
+        let blockchain_db = BlockchainProvider::new(provider_factory.clone())?;
+
+        // Set up payload builder
+        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
+            EthEvmConfig::new(provider_factory.chain_spec()),
+            EthereumBuilderConfig::new(Default::default()),
+        );
+
+        let payload_generator = BasicPayloadJobGenerator::with_builder(
+            blockchain_db.clone(),
+            NoopTransactionPool::default(),
+            ctx.task_executor.clone(),
+            BasicPayloadJobGeneratorConfig::default(),
+            payload_builder,
+        );
+
+        let (payload_service, payload_builder): (_, PayloadBuilderHandle<EthEngineTypes>) =
+            PayloadBuilderService::new(payload_generator, blockchain_db.canonical_state_stream());
+
+        ctx.task_executor.spawn_critical("payload builder service", payload_service);
+
+        // The pipeline has no stages, blocks missing from the datadir can't be downloaded
+        let (sync_metrics_tx, _sync_metrics_rx) = unbounded_channel();
+        let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
+        let static_file_producer = StaticFileProducer::new(provider_factory.clone(), prune_modes);
+        let pipeline = Pipeline::builder().build(provider_factory.clone(), static_file_producer);
+
+        let pruner = PrunerBuilder::new(config.prune.clone().unwrap_or_default())
+            .build_with_provider_factory(provider_factory.clone());
+
+        // Configure the consensus engine
+        let chain_spec = provider_factory.chain_spec();
+        let (to_engine, from_engine) = unbounded_channel();
+        let mut engine_service = EngineService::new(
+            consensus,
+            executor,
+            chain_spec.clone(),
+            OfflineClient,
+            Box::pin(UnboundedReceiverStream::new(from_engine)),
+            pipeline,
+            Box::new(ctx.task_executor.clone()),
+            provider_factory.clone(),
+            blockchain_db.clone(),
+            pruner,
+            payload_builder,
+            EthereumEngineValidator::new(chain_spec),
+            // persist every canonical block, so the replayed chain is stored once the command
+            // exits
+            TreeConfig::default().with_persistence_threshold(0).with_memory_block_buffer_target(0),
+            Box::new(NoopInvalidBlockHook::default()),
+            sync_metrics_tx,
+        );
+        let beacon_engine_handle = BeaconConsensusEngineHandle::<EthEngineTypes>::new(to_engine);
+
+        // Spawn the consensus engine
+        let (tx, mut rx) = oneshot::channel::<eyre::Result<()>>();
+        ctx.task_executor.spawn_critical("consensus engine", async move {
+            let mut result = Ok(());
+            while let Some(event) = engine_service.next().await {
+                debug!(target: "reth::cli", "Event: {event}");
+                if matches!(event, ChainEvent::FatalError) {
+                    result = Err(eyre::eyre!("Fatal error in consensus engine"));
+                    break
+                }
+            }
+            let _ = tx.send(result);
+        });
+
+        let engine_api_store = EngineMessageStore::new(self.engine_api_store.clone());
+        let mut previous_timestamp = None;
+        let mut replayed = 0usize;
+        for filepath in engine_api_store.engine_messages_iter()? {
+            let contents =
+                fs::read(&filepath).wrap_err(format!("failed to read: {}", filepath.display()))?;
+            let message: StoredEngineApiMessage<PayloadAttributes> =
+                serde_json::from_slice(&contents)
+                    .wrap_err(format!("failed to parse: {}", filepath.display()))?;
+
+            if let (Some(to), StoredEngineApiMessage::NewPayload { payload, .. }) =
+                (self.to, &message)
+            {
+                if payload.block_number() > to {
+                    info!(target: "reth::cli", block = payload.block_number(), "Reached the end of the replay range");
+                    break
+                }
+            }
+
+            // Pause before the next message, either for the fixed interval or the recorded time
+            // between the messages, scaled to the replay speed
+            let timestamp = message_timestamp(&filepath);
+            if let Some(interval) = self.interval.filter(|_| replayed > 0) {
+                tokio::time::sleep(Duration::from_millis(interval)).await;
+            } else if let (Some(speed), Some(previous), Some(timestamp)) =
+                (self.speed, previous_timestamp, timestamp)
+            {
+                let elapsed = Duration::from_millis(timestamp.saturating_sub(previous));
+                tokio::time::sleep(elapsed.div_f64(speed)).await;
+            }
+            previous_timestamp = timestamp.or(previous_timestamp);
+
+            debug!(target: "reth::cli", filepath = %filepath.display(), "Forwarding Engine API message");
+            let expected = message.status();
+            let status = match message {
+                StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs, .. } => {
+                    let response = beacon_engine_handle
+                        .fork_choice_updated(
+                            state,
+                            payload_attrs,
+                            EngineApiMessageVersion::default(),
+                        )
+                        .await;
+                    debug!(target: "reth::cli", ?response, "Received for forkchoice updated");
+                    match response {
+                        Ok(updated) => StoredResponseStatus::from_payload_status(
+                            &updated.payload_status.status,
+                        ),
+                        Err(_) => StoredResponseStatus::Error,
+                    }
+                }
+                StoredEngineApiMessage::NewPayload {
+                    payload, sidecar, withdrawals_root, ..
+                } => {
+                    let response =
+                        beacon_engine_handle.new_payload(payload, sidecar, withdrawals_root).await;
+                    debug!(target: "reth::cli", ?response, "Received for new payload");
+                    match response {
+                        Ok(status) => StoredResponseStatus::from_payload_status(&status.status),
+                        Err(_) => StoredResponseStatus::Error,
+                    }
+                }
+            };
+            replayed += 1;
+
+            if let Some(expected) = expected.filter(|expected| *expected != status) {
+                if self.assert_status {
+                    eyre::bail!(
+                        "unexpected response status for {}: expected {expected:?}, got {status:?}",
+                        filepath.display()
+                    );
+                }
+                warn!(target: "reth::cli", filepath = %filepath.display(), ?expected, ?status, "Unexpected response status");
+            }
+        }
+
+        info!(target: "reth::cli", replayed, "Finished replaying engine API messages");
+
+        // every response was awaited, so only the persistence of the canonical chain is pending
+        let canonical_head = blockchain_db.best_block_number()?;
+        info!(target: "reth::cli", canonical_head, "Waiting for the canonical chain to be persisted");
+        loop {
+            if provider_factory.last_block_number()? >= canonical_head {
+                break
+            }
+            tokio::select! {
+                result = &mut rx => return result.unwrap_or_else(|_| Err(eyre::eyre!("consensus engine exited"))),
+                _ = tokio::time::sleep(Duration::from_millis(100)) => {}
+            }
+        }
+        info!(target: "reth::cli", canonical_head, "Persisted the canonical chain");
+
+        Ok(())
+    }
+}
+
+/// A block client for a node without peers, no headers or bodies are ever returned.
+#[derive(Debug, Clone, Copy)]
+struct OfflineClient;
+
+impl DownloadClient for OfflineClient {
+    fn report_bad_message(&self, _peer_id: PeerId) {}
+
+    fn num_connected_peers(&self) -> usize {
+        0
+    }
+}
+
+impl HeadersClient for OfflineClient {
+    type Header = Header;
+    type Output = futures::future::Ready<PeerRequestResult<Vec<Header>>>;
+
+    fn get_headers_with_priority(
+        &self,
+        _request: HeadersRequest,
+        _priority: Priority,
+    ) -> Self::Output {
+        futures::future::ready(Ok(WithPeerId::new(PeerId::ZERO, Vec::new())))
+    }
+}
+
+impl BlockClient for OfflineClient {
+    type Block = Block;
+}
+
+impl BodiesClient for OfflineClient {
+    type Body = BlockBody;
+    type Output = futures::future::Ready<PeerRequestResult<Vec<BlockBody>>>;
+
+    fn get_block_bodies_with_priority(
+        &self,
+        _hashes: Vec<B256>,
+        _priority: Priority,
+    ) -> Self::Output {
+        futures::future::ready(Ok(WithPeerId::new(PeerId::ZERO, Vec::new())))
+    }
+}
+
+/// Returns the time in milliseconds the stored message was received at, from its file name.
+fn message_timestamp(path: &std::path::Path) -> Option<u64> {
+    path.file_name()?.to_str()?.split('-').next()?.parse().ok()
+}
+
+/// Parses a positive replay speed.
+fn parse_speed(value: &str) -> eyre::Result<f64> {
+    let speed: f64 = value.parse()?;
+    if !speed.is_finite() || speed <= 0.0 {
+        eyre::bail!("speed must be a positive number")
+    }
+    Ok(speed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_replay_args() {
+        let cmd = Command::<reth_ethereum_cli::chainspec::EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--engine-api-store",
+            "messages",
+            "--speed",
+            "2.5",
+            "--to",
+            "100",
+            "--assert-status",
+        ]);
+        assert_eq!(cmd.speed, Some(2.5));
+        assert_eq!(cmd.to, Some(100));
+        assert!(cmd.assert_status);
+
+        assert!(Command::<reth_ethereum_cli::chainspec::EthereumChainSpecParser>::try_parse_from(
+            ["reth", "--engine-api-store", "messages", "--speed", "0"]
+        )
+        .is_err());
+        assert!(Command::<reth_ethereum_cli::chainspec::EthereumChainSpecParser>::try_parse_from(
+            ["reth", "--engine-api-store", "messages", "--speed", "1", "--interval", "10"]
+        )
+        .is_err());
+        assert_eq!(message_timestamp("1700000000000-fcu-0x01.json".as_ref()), Some(1700000000000));
+    }
+}
@@ -0,0 +1,103 @@
+use alloc::{collections::BTreeMap, string::String};
+use alloy_eips::eip7840::BlobScheduleItem;
+use alloy_genesis::Genesis;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+
+/// A single item of the EIP-7840 `blobSchedule` of a genesis chain config.
+///
+/// Unlike [`BlobScheduleItem`] this retains the `baseFeeUpdateFraction` of the fork.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobScheduleEntry {
+    /// Target blob count for the block.
+    #[serde(rename = "target")]
+    pub target_blob_count: u64,
+    /// Max blob count for the block.
+    #[serde(rename = "max")]
+    pub max_blob_count: u64,
+    /// Update fraction for excess blob gas calculation, the default of the fork if not set.
+    #[serde(rename = "baseFeeUpdateFraction", default, skip_serializing_if = "Option::is_none")]
+    pub update_fraction: Option<u128>,
+}
+
+impl From<BlobScheduleItem> for BlobScheduleEntry {
+    fn from(item: BlobScheduleItem) -> Self {
+        Self {
+            target_blob_count: item.target_blob_count,
+            max_blob_count: item.max_blob_count,
+            update_fraction: None,
+        }
+    }
+}
+
+/// A [`Genesis`] with the complete blob schedule of its chain config.
+///
+/// This is what a chain spec is built from when it's loaded from a genesis json file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainSpecGenesis {
+    /// The genesis.
+    pub genesis: Genesis,
+    /// The `blobSchedule` of the chain config, keyed by the fork name.
+    pub blob_schedule: BTreeMap<String, BlobScheduleEntry>,
+}
+
+impl From<Genesis> for ChainSpecGenesis {
+    fn from(genesis: Genesis) -> Self {
+        let blob_schedule = genesis
+            .config
+            .blob_schedule
+            .iter()
+            .map(|(fork, item)| (fork.clone(), (*item).into()))
+            .collect();
+        Self { genesis, blob_schedule }
+    }
+}
+
+impl<'de> Deserialize<'de> for ChainSpecGenesis {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let blob_schedule = value
+            .get("config")
+            .and_then(|config| config.get("blobSchedule"))
+            .map(BTreeMap::<String, BlobScheduleEntry>::deserialize)
+            .transpose()
+            .map_err(D::Error::custom)?
+            .unwrap_or_default();
+        let genesis = Genesis::deserialize(value).map_err(D::Error::custom)?;
+        Ok(Self { genesis, blob_schedule })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_blob_schedule_update_fractions() {
+        let s = r#"{
+  "config": {
+    "chainId": 1337,
+    "cancunTime": 0,
+    "pragueTime": 100,
+    "blobSchedule": {
+      "cancun": { "target": 3, "max": 6 },
+      "prague": { "target": 12, "max": 18, "baseFeeUpdateFraction": 10000000 }
+    }
+  }
+}"#;
+        let genesis: ChainSpecGenesis = serde_json::from_str(s).unwrap();
+        assert_eq!(genesis.genesis.config.chain_id, 1337);
+        assert_eq!(genesis.genesis.config.blob_schedule.len(), 2);
+        assert_eq!(
+            genesis.blob_schedule["cancun"],
+            BlobScheduleEntry { target_blob_count: 3, max_blob_count: 6, update_fraction: None }
+        );
+        assert_eq!(
+            genesis.blob_schedule["prague"],
+            BlobScheduleEntry {
+                target_blob_count: 12,
+                max_blob_count: 18,
+                update_fraction: Some(10_000_000)
+            }
+        );
+    }
+}
@@ -16,6 +16,8 @@ pub(crate) mod constants;
 pub use constants::MIN_TRANSACTION_GAS;
 
 mod api;
+/// The genesis a chain spec is loaded from.
+mod genesis;
 /// The chain info module.
 mod info;
 /// The chain spec module.
@@ -26,12 +28,13 @@ pub use alloy_chains::{Chain, ChainKind, NamedChain};
 pub use reth_ethereum_forks::*;
 
 pub use api::EthChainSpec;
+pub use genesis::{BlobScheduleEntry, ChainSpecGenesis};
 pub use info::ChainInfo;
 #[cfg(any(test, feature = "test-utils"))]
 pub use spec::test_fork_ids;
 pub use spec::{
     BaseFeeParams, BaseFeeParamsKind, ChainSpec, ChainSpecBuilder, ChainSpecProvider,
-    DepositContract, ForkBaseFeeParams, DEV, HOLESKY, MAINNET, SEPOLIA,
+    DepositContract, ForkBaseFeeParams, HardforkBlobParams, DEV, HOLESKY, MAINNET, SEPOLIA,
 };
 
 use reth_primitives_traits::sync::OnceLock;
@@ -1,6 +1,9 @@
 pub use alloy_eips::eip1559::BaseFeeParams;
 
-use crate::{constants::MAINNET_DEPOSIT_CONTRACT, once_cell_set, EthChainSpec};
+use crate::{
+    constants::MAINNET_DEPOSIT_CONTRACT, once_cell_set, BlobScheduleEntry, ChainSpecGenesis,
+    EthChainSpec,
+};
 use alloc::{boxed::Box, collections::BTreeMap, string::String, sync::Arc, vec::Vec};
 use alloy_chains::{Chain, NamedChain};
 use alloy_consensus::{
@@ -175,13 +178,26 @@ impl HardforkBlobParams {
     /// Constructs params for chainspec from a provided blob schedule.
     /// Falls back to defaults if the schedule is empty.
     pub fn from_schedule(blob_schedule: &BTreeMap<String, BlobScheduleItem>) -> Self {
+        Self::from_entries(
+            &blob_schedule.iter().map(|(fork, item)| (fork.clone(), (*item).into())).collect(),
+        )
+    }
+
+    /// Constructs params for chainspec from the entries of a blob schedule.
+    ///
+    /// Falls back to the defaults of the fork for missing entries and update fractions.
+    pub fn from_entries(blob_schedule: &BTreeMap<String, BlobScheduleEntry>) -> Self {
         let extract = |key: &str, default: fn() -> BlobParams| {
             blob_schedule
                 .get(key)
-                .map(|item| BlobParams {
-                    target_blob_count: item.target_blob_count,
-                    max_blob_count: item.max_blob_count,
-                    ..default()
+                .map(|entry| {
+                    let default = default();
+                    BlobParams {
+                        target_blob_count: entry.target_blob_count,
+                        max_blob_count: entry.max_blob_count,
+                        update_fraction: entry.update_fraction.unwrap_or(default.update_fraction),
+                        ..default
+                    }
                 })
                 .unwrap_or_else(default) // Use default if key is missing
         };
@@ -375,6 +391,19 @@ impl ChainSpec {
         self.hardforks.fork(EthereumHardfork::London).active_at_block(0).then_some(genesis_base_fee)
     }
 
+    /// Returns the [`BlobParams`] of the fork that is active at the given timestamp.
+    ///
+    /// Returns `None` if blobs are not enabled at the timestamp, i.e. before Cancun.
+    pub fn blob_params_at_timestamp(&self, timestamp: u64) -> Option<BlobParams> {
+        if self.is_prague_active_at_timestamp(timestamp) {
+            Some(self.blob_params.prague)
+        } else if self.is_cancun_active_at_timestamp(timestamp) {
+            Some(self.blob_params.cancun)
+        } else {
+            None
+        }
+    }
+
     /// Get the [`BaseFeeParams`] for the chain at the given timestamp.
     pub fn base_fee_params_at_timestamp(&self, timestamp: u64) -> BaseFeeParams {
         match self.base_fee_params {
@@ -642,6 +671,12 @@ impl ChainSpec {
 
 impl From<Genesis> for ChainSpec {
     fn from(genesis: Genesis) -> Self {
+        ChainSpecGenesis::from(genesis).into()
+    }
+}
+
+impl From<ChainSpecGenesis> for ChainSpec {
+    fn from(ChainSpecGenesis { genesis, blob_schedule }: ChainSpecGenesis) -> Self {
         // Block-based hardforks
         let hardfork_opts = [
             (EthereumHardfork::Homestead.boxed(), genesis.config.homestead_block),
@@ -719,7 +754,7 @@ impl From<Genesis> for ChainSpec {
         ordered_hardforks.append(&mut hardforks);
 
         // Extract blob parameters directly from blob_schedule
-        let blob_params = HardforkBlobParams::from_schedule(&genesis.config.blob_schedule);
+        let blob_params = HardforkBlobParams::from_entries(&blob_schedule);
 
         // NOTE: in full node, we prune all receipts except the deposit contract's. We do not
         // have the deployment block in the genesis file, so we use block zero. We use the same
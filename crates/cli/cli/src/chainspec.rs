@@ -1,6 +1,7 @@
 use std::{fs, path::PathBuf, sync::Arc};
 
 use clap::builder::TypedValueParser;
+use reth_chainspec::ChainSpecGenesis;
 
 #[derive(Debug, Clone)]
 struct Parser<C>(std::marker::PhantomData<C>);
@@ -62,8 +63,8 @@ pub trait ChainSpecParser: Clone + Send + Sync + 'static {
     }
 }
 
-/// A helper to parse a [`Genesis`](alloy_genesis::Genesis) as argument or from disk.
-pub fn parse_genesis(s: &str) -> eyre::Result<alloy_genesis::Genesis> {
+/// A helper to parse a [`ChainSpecGenesis`] as argument or from disk.
+pub fn parse_genesis(s: &str) -> eyre::Result<ChainSpecGenesis> {
     // try to read json from path first
     let raw = match fs::read_to_string(PathBuf::from(shellexpand::full(s)?.into_owned())) {
         Ok(raw) => raw,
@@ -14,6 +14,7 @@ mod list;
 mod stats;
 /// DB List TUI
 mod tui;
+mod verify;
 
 /// `reth db` command
 #[derive(Debug, Parser)]
@@ -46,6 +47,8 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Verifies the consistency of the database and static files
+    Verify(verify::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -130,6 +133,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::Verify(command) => {
+                let Environment { provider_factory, .. } =
+                    self.env.init::<N>(command.access_rights())?;
+                command.execute(provider_factory)?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
@@ -0,0 +1,701 @@
+use super::{Check, Inconsistency, Repair};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{keccak256, BlockNumber, B256};
+use reth_db::{static_file::iter_static_files, tables};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::{
+        storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress, ShardedKey,
+        StoredBlockBodyIndices,
+    },
+    transaction::DbTx,
+};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockBodyIndicesProvider, DBProvider, DatabaseProviderRO,
+    HeaderProvider, ProviderError, ProviderFactory, PruneCheckpointReader, StageCheckpointReader,
+    StaticFileProviderFactory, TransactionsProviderExt,
+};
+use reth_prune::PruneSegment;
+use reth_stages::StageId;
+use reth_static_file_types::{SegmentRangeInclusive, StaticFileSegment};
+use reth_trie::{
+    prefix_set::{PrefixSetMut, TriePrefixSets},
+    StateRoot, StorageRoot,
+};
+use reth_trie_db::{DatabaseStateRoot, DatabaseStorageRoot};
+use std::sync::mpsc::Sender;
+use tracing::info;
+
+/// Number of transactions hashed at once by the transaction hash numbers check.
+const TRANSACTIONS_CHUNK_SIZE: u64 = 100_000;
+
+/// Pairs of stages where the first one processes data produced by the second one, so its
+/// checkpoint can't be ahead.
+const STAGE_DEPENDENCIES: [(StageId, StageId); 15] = [
+    (StageId::Bodies, StageId::Headers),
+    (StageId::SenderRecovery, StageId::Bodies),
+    (StageId::Execution, StageId::Bodies),
+    (StageId::AccountHashing, StageId::Execution),
+    (StageId::StorageHashing, StageId::Execution),
+    (StageId::MerkleExecute, StageId::AccountHashing),
+    (StageId::MerkleExecute, StageId::StorageHashing),
+    (StageId::TransactionLookup, StageId::Bodies),
+    (StageId::IndexAccountHistory, StageId::Execution),
+    (StageId::IndexStorageHistory, StageId::Execution),
+    (StageId::Finish, StageId::SenderRecovery),
+    (StageId::Finish, StageId::MerkleExecute),
+    (StageId::Finish, StageId::TransactionLookup),
+    (StageId::Finish, StageId::IndexAccountHistory),
+    (StageId::Finish, StageId::IndexStorageHistory),
+];
+
+/// Configuration of the checks.
+#[derive(Debug)]
+pub(crate) struct Config {
+    /// Number of storage tries sampled by the trie check.
+    pub(crate) trie_samples: u64,
+    /// Maximum number of inconsistencies listed for each check.
+    pub(crate) max_inconsistencies: usize,
+}
+
+/// Inconsistencies found by a check.
+#[derive(Debug)]
+pub(crate) struct Findings {
+    /// Number of entries checked.
+    pub(crate) entries_checked: u64,
+    /// Total number of inconsistencies found.
+    pub(crate) total: u64,
+    /// Number of repairable inconsistencies found.
+    pub(crate) repairable: u64,
+    /// Inconsistencies found, up to the configured maximum.
+    pub(crate) inconsistencies: Vec<Inconsistency>,
+    /// Reason the check was skipped.
+    pub(crate) skipped: Option<String>,
+    max_inconsistencies: usize,
+    /// Receives the repairs as they are found, if repairing.
+    repairs: Option<Sender<Repair>>,
+}
+
+impl Findings {
+    const fn new(config: &Config, repairs: Option<Sender<Repair>>) -> Self {
+        Self {
+            entries_checked: 0,
+            total: 0,
+            repairable: 0,
+            inconsistencies: Vec::new(),
+            skipped: None,
+            max_inconsistencies: config.max_inconsistencies,
+            repairs,
+        }
+    }
+
+    /// Records an inconsistency, with the repair that fixes it if there's one.
+    ///
+    /// The message is only built if the inconsistency is listed in the report.
+    fn inconsistent(&mut self, message: impl FnOnce() -> String, repair: Option<Repair>) {
+        self.total += 1;
+        if self.inconsistencies.len() < self.max_inconsistencies {
+            self.inconsistencies
+                .push(Inconsistency { message: message(), repairable: repair.is_some() });
+        }
+        if let Some(repair) = repair {
+            self.repairable += 1;
+            if let Some(repairs) = &self.repairs {
+                // the receiver only goes away if repairing failed, which is reported separately
+                let _ = repairs.send(repair);
+            }
+        }
+    }
+
+    /// Marks the check as skipped.
+    fn skip(&mut self, reason: String) {
+        self.skipped = Some(reason);
+    }
+}
+
+/// Runs the check on its own read-only transaction.
+///
+/// Repairs of the inconsistencies found are sent to `repairs`, if given.
+pub(crate) fn run<N: ProviderNodeTypes>(
+    check: Check,
+    provider_factory: &ProviderFactory<N>,
+    config: &Config,
+    repairs: Option<Sender<Repair>>,
+) -> eyre::Result<Findings> {
+    let provider = provider_factory.provider()?.disable_long_read_transaction_safety();
+    let mut findings = Findings::new(config, repairs);
+
+    info!(target: "reth::cli", ?check, "Running check");
+    match check {
+        Check::BodyIndices => body_indices(&provider, &mut findings)?,
+        Check::TransactionHashNumbers => transaction_hash_numbers(&provider, &mut findings)?,
+        Check::HistoryIndices => {
+            account_history_indices(&provider, &mut findings)?;
+            storage_history_indices(&provider, &mut findings)?;
+        }
+        Check::HashedState => hashed_state(&provider, &mut findings)?,
+        Check::Trie => trie(&provider, config, &mut findings)?,
+        Check::StageCheckpoints => stage_checkpoints(&provider, &mut findings)?,
+    }
+    info!(target: "reth::cli", ?check, inconsistencies = findings.total, "Finished check");
+
+    // the repairs stop being received once all checks have dropped their senders
+    findings.repairs = None;
+    Ok(findings)
+}
+
+/// Returns the block number of the stage checkpoint, or `0` if there's none.
+fn stage_checkpoint<N: ProviderNodeTypes>(
+    provider: &DatabaseProviderRO<N::DB, N>,
+    id: StageId,
+) -> eyre::Result<BlockNumber> {
+    Ok(provider
+        .get_stage_checkpoint(id)?
+        .map(|checkpoint| checkpoint.block_number)
+        .unwrap_or_default())
+}
+
+/// Checks that `BlockBodyIndices` number transactions contiguously, and that they match the
+/// transaction ranges of the transaction and receipt static files.
+fn body_indices<N: ProviderNodeTypes>(
+    provider: &DatabaseProviderRO<N::DB, N>,
+    findings: &mut Findings,
+) -> eyre::Result<()> {
+    let tx = provider.tx_ref();
+
+    let mut previous: Option<(BlockNumber, StoredBlockBodyIndices)> = None;
+    for entry in tx.cursor_read::<tables::BlockBodyIndices>()?.walk(None)? {
+        let (number, indices) = entry?;
+        findings.entries_checked += 1;
+
+        if let Some((previous_number, previous_indices)) = previous {
+            if number != previous_number + 1 {
+                findings.inconsistent(
+                    || {
+                        format!(
+                            "block body indices are missing for blocks {}..{number}",
+                            previous_number + 1
+                        )
+                    },
+                    None,
+                );
+            } else if indices.first_tx_num() != previous_indices.next_tx_num() {
+                findings.inconsistent(
+                    || {
+                        format!(
+                            "block {number} starts at transaction {}, but block {previous_number} ends before transaction {}",
+                            indices.first_tx_num(),
+                            previous_indices.next_tx_num()
+                        )
+                    },
+                    None,
+                );
+            }
+        }
+        previous = Some((number, indices));
+    }
+
+    let static_files = iter_static_files(provider.static_file_provider().directory())?;
+    for segment in [StaticFileSegment::Transactions, StaticFileSegment::Receipts] {
+        let Some(ranges) = static_files.get(&segment) else { continue };
+
+        let mut previous_end = None;
+        for (block_range, tx_range) in ranges {
+            findings.entries_checked += 1;
+
+            if previous_end.is_some_and(|end: BlockNumber| block_range.start() != end + 1) {
+                findings.inconsistent(
+                    || format!("{segment} static file of blocks {block_range} doesn't follow the previous one"),
+                    None,
+                );
+            }
+            previous_end = Some(block_range.end());
+
+            let (Some(first), Some(last)) = (
+                provider.block_body_indices(block_range.start())?,
+                provider.block_body_indices(block_range.end())?,
+            ) else {
+                findings.inconsistent(
+                    || format!("block body indices are missing for {segment} static file of blocks {block_range}"),
+                    None,
+                );
+                continue
+            };
+
+            let expected = (first.first_tx_num() < last.next_tx_num())
+                .then(|| SegmentRangeInclusive::new(first.first_tx_num(), last.last_tx_num()));
+            if tx_range.as_ref() != expected.as_ref() {
+                findings.inconsistent(
+                    || {
+                        format!(
+                            "{segment} static file of blocks {block_range} has transactions {tx_range:?}, but block body indices have {expected:?}"
+                        )
+                    },
+                    None,
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that every transaction up to the `TransactionLookup` checkpoint has a hash number
+/// pointing to it, and that there are no other hash numbers.
+fn transaction_hash_numbers<N: ProviderNodeTypes>(
+    provider: &DatabaseProviderRO<N::DB, N>,
+    findings: &mut Findings,
+) -> eyre::Result<()> {
+    let tx = provider.tx_ref();
+
+    let checkpoint = stage_checkpoint(provider, StageId::TransactionLookup)?;
+    let Some(end) = provider.block_body_indices(checkpoint)?.map(|indices| indices.next_tx_num())
+    else {
+        findings.skip(format!(
+            "block body indices of the TransactionLookup checkpoint block {checkpoint} are missing"
+        ));
+        return Ok(())
+    };
+
+    // Pruned transactions don't have hash numbers, and expired ones can't be hashed anymore.
+    let pruned = provider
+        .get_prune_checkpoint(PruneSegment::TransactionLookup)?
+        .and_then(|checkpoint| checkpoint.tx_number)
+        .map_or(0, |tx_number| tx_number + 1);
+    let earliest_block = provider.static_file_provider().earliest_history_height();
+    let expired = match earliest_block {
+        0 => 0,
+        block => provider.block_body_indices(block)?.map_or(0, |indices| indices.first_tx_num()),
+    };
+    let start = pruned.max(expired).min(end);
+
+    let mut cursor = tx.cursor_read::<tables::TransactionHashNumbers>()?;
+    for chunk_start in (start..end).step_by(TRANSACTIONS_CHUNK_SIZE as usize) {
+        let chunk = chunk_start..(chunk_start + TRANSACTIONS_CHUNK_SIZE).min(end);
+        info!(target: "reth::cli", ?chunk, %end, "Checking transaction hash numbers");
+
+        for (hash, number) in provider.transaction_hashes_by_range(chunk)? {
+            findings.entries_checked += 1;
+
+            match cursor.seek_exact(hash)? {
+                Some((_, stored)) if stored == number => {}
+                Some((_, stored)) => findings.inconsistent(
+                    || format!("transaction {number} with hash {hash} has hash number {stored}"),
+                    Some(Repair::InsertTransactionHashNumber { hash, number }),
+                ),
+                None => findings.inconsistent(
+                    || format!("transaction {number} with hash {hash} has no hash number"),
+                    Some(Repair::InsertTransactionHashNumber { hash, number }),
+                ),
+            }
+        }
+    }
+
+    let mut indexed = 0;
+    for entry in cursor.walk(None)? {
+        let (hash, number) = entry?;
+        if number >= end {
+            findings.inconsistent(
+                || format!("hash number of {hash} points to transaction {number}, which is beyond the TransactionLookup checkpoint"),
+                Some(Repair::DeleteTransactionHashNumber { hash }),
+            );
+        } else if number >= start {
+            indexed += 1;
+        }
+    }
+
+    if indexed > end - start {
+        findings.inconsistent(
+            || {
+                format!(
+                    "{} hash numbers point to transactions {start}..{end}, but there are only {} transactions",
+                    indexed,
+                    end - start
+                )
+            },
+            None,
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks that `AccountsHistory` and `AccountChangeSets` agree up to the
+/// `IndexAccountHistory` checkpoint.
+fn account_history_indices<N: ProviderNodeTypes>(
+    provider: &DatabaseProviderRO<N::DB, N>,
+    findings: &mut Findings,
+) -> eyre::Result<()> {
+    let tx = provider.tx_ref();
+
+    let checkpoint = stage_checkpoint(provider, StageId::IndexAccountHistory)?;
+    let from = provider
+        .get_prune_checkpoint(PruneSegment::AccountHistory)?
+        .and_then(|checkpoint| checkpoint.block_number)
+        .map_or(0, |block| block + 1);
+
+    let mut history = tx.cursor_read::<tables::AccountsHistory>()?;
+    for entry in tx.cursor_read::<tables::AccountChangeSets>()?.walk_range(from..=checkpoint)? {
+        let (block, AccountBeforeTx { address, .. }) = entry?;
+        findings.entries_checked += 1;
+
+        if !history
+            .seek(ShardedKey::new(address, block))?
+            .is_some_and(|(key, blocks)| key.key == address && blocks.contains(block))
+        {
+            findings.inconsistent(
+                || format!("account {address} changed in block {block}, but its history index doesn't have it"),
+                None,
+            );
+        }
+    }
+
+    let mut changesets = tx.cursor_dup_read::<tables::AccountChangeSets>()?;
+    for entry in history.walk(None)? {
+        let (key, blocks) = entry?;
+        let address = key.key;
+
+        for block in blocks.iter().filter(|&block| block >= from) {
+            findings.entries_checked += 1;
+
+            if block > checkpoint {
+                findings.inconsistent(
+                    || format!("history index of account {address} has block {block}, which is beyond the IndexAccountHistory checkpoint"),
+                    None,
+                );
+            } else if changesets
+                .seek_by_key_subkey(block, address)?
+                .is_none_or(|changeset| changeset.address != address)
+            {
+                findings.inconsistent(
+                    || format!("history index of account {address} has block {block}, but there's no changeset for it"),
+                    None,
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that `StoragesHistory` and `StorageChangeSets` agree up to the
+/// `IndexStorageHistory` checkpoint.
+fn storage_history_indices<N: ProviderNodeTypes>(
+    provider: &DatabaseProviderRO<N::DB, N>,
+    findings: &mut Findings,
+) -> eyre::Result<()> {
+    let tx = provider.tx_ref();
+
+    let checkpoint = stage_checkpoint(provider, StageId::IndexStorageHistory)?;
+    let from = provider
+        .get_prune_checkpoint(PruneSegment::StorageHistory)?
+        .and_then(|checkpoint| checkpoint.block_number)
+        .map_or(0, |block| block + 1);
+
+    let mut history = tx.cursor_read::<tables::StoragesHistory>()?;
+    for entry in tx
+        .cursor_read::<tables::StorageChangeSets>()?
+        .walk_range(BlockNumberAddress::range(from..=checkpoint))?
+    {
+        let (BlockNumberAddress((block, address)), storage) = entry?;
+        findings.entries_checked += 1;
+
+        if !history.seek(StorageShardedKey::new(address, storage.key, block))?.is_some_and(
+            |(key, blocks)| {
+                key.address == address &&
+                    key.sharded_key.key == storage.key &&
+                    blocks.contains(block)
+            },
+        ) {
+            findings.inconsistent(
+                || {
+                    format!(
+                        "storage slot {} of account {address} changed in block {block}, but its history index doesn't have it",
+                        storage.key
+                    )
+                },
+                None,
+            );
+        }
+    }
+
+    let mut changesets = tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+    for entry in history.walk(None)? {
+        let (key, blocks) = entry?;
+        let (address, slot) = (key.address, key.sharded_key.key);
+
+        for block in blocks.iter().filter(|&block| block >= from) {
+            findings.entries_checked += 1;
+
+            if block > checkpoint {
+                findings.inconsistent(
+                    || format!("history index of storage slot {slot} of account {address} has block {block}, which is beyond the IndexStorageHistory checkpoint"),
+                    None,
+                );
+            } else if changesets
+                .seek_by_key_subkey(BlockNumberAddress((block, address)), slot)?
+                .is_none_or(|changeset| changeset.key != slot)
+            {
+                findings.inconsistent(
+                    || format!("history index of storage slot {slot} of account {address} has block {block}, but there's no changeset for it"),
+                    None,
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that `HashedAccounts` and `HashedStorages` match the plain state.
+fn hashed_state<N: ProviderNodeTypes>(
+    provider: &DatabaseProviderRO<N::DB, N>,
+    findings: &mut Findings,
+) -> eyre::Result<()> {
+    let tx = provider.tx_ref();
+
+    let execution = stage_checkpoint(provider, StageId::Execution)?;
+    let account_hashing = stage_checkpoint(provider, StageId::AccountHashing)?;
+    let storage_hashing = stage_checkpoint(provider, StageId::StorageHashing)?;
+    if account_hashing != execution || storage_hashing != execution {
+        findings.skip(format!(
+            "hashing stages are at blocks {account_hashing} and {storage_hashing}, but Execution is at block {execution}"
+        ));
+        return Ok(())
+    }
+
+    let mut hashed_accounts = tx.cursor_read::<tables::HashedAccounts>()?;
+    let (mut plain, mut missing) = (0, 0);
+    for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+        let (address, account) = entry?;
+        findings.entries_checked += 1;
+        plain += 1;
+
+        let hashed_address = keccak256(address);
+        match hashed_accounts.seek_exact(hashed_address)? {
+            Some((_, hashed)) if hashed == account => {}
+            Some(_) => findings.inconsistent(
+                || format!("hashed account {hashed_address} doesn't match account {address}"),
+                Some(Repair::RehashAccount { address }),
+            ),
+            None => {
+                missing += 1;
+                findings.inconsistent(
+                    || format!("account {address} has no hashed account {hashed_address}"),
+                    Some(Repair::RehashAccount { address }),
+                );
+            }
+        }
+    }
+    let dangling = (tx.entries::<tables::HashedAccounts>()? + missing).saturating_sub(plain);
+    if dangling > 0 {
+        findings.inconsistent(|| format!("{dangling} hashed accounts have no plain account"), None);
+    }
+
+    let mut hashed_storages = tx.cursor_dup_read::<tables::HashedStorages>()?;
+    let (mut plain, mut missing) = (0, 0);
+    for entry in tx.cursor_dup_read::<tables::PlainStorageState>()?.walk(None)? {
+        let (address, storage) = entry?;
+        findings.entries_checked += 1;
+        plain += 1;
+
+        let (hashed_address, hashed_key) = (keccak256(address), keccak256(storage.key));
+        match hashed_storages
+            .seek_by_key_subkey(hashed_address, hashed_key)?
+            .filter(|hashed| hashed.key == hashed_key)
+        {
+            Some(hashed) if hashed.value == storage.value => {}
+            Some(_) => findings.inconsistent(
+                || {
+                    format!(
+                        "hashed storage slot {hashed_key} doesn't match storage slot {} of account {address}",
+                        storage.key
+                    )
+                },
+                Some(Repair::RehashStorage { address, key: storage.key }),
+            ),
+            None => {
+                missing += 1;
+                findings.inconsistent(
+                    || format!("storage slot {} of account {address} has no hashed storage slot", storage.key),
+                    Some(Repair::RehashStorage { address, key: storage.key }),
+                );
+            }
+        }
+    }
+    let dangling = (tx.entries::<tables::HashedStorages>()? + missing).saturating_sub(plain);
+    if dangling > 0 {
+        findings.inconsistent(
+            || format!("{dangling} hashed storage slots have no plain storage slot"),
+            None,
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks the state root computed from the stored trie nodes against the header, recomputes
+/// sampled storage tries from their leaves, and looks for storage tries of missing accounts.
+fn trie<N: ProviderNodeTypes>(
+    provider: &DatabaseProviderRO<N::DB, N>,
+    config: &Config,
+    findings: &mut Findings,
+) -> eyre::Result<()> {
+    let tx = provider.tx_ref();
+
+    let execution = stage_checkpoint(provider, StageId::Execution)?;
+    let merkle = stage_checkpoint(provider, StageId::MerkleExecute)?;
+    if merkle != execution {
+        findings.skip(format!(
+            "MerkleExecute is at block {merkle}, but Execution is at block {execution}"
+        ));
+        return Ok(())
+    }
+
+    // The account trie is recomputed from the hashed accounts, using the storage roots of the
+    // stored storage tries, which are sampled below. Without a prefix set, only the stored root
+    // node would be read back.
+    let header =
+        provider.sealed_header(merkle)?.ok_or(ProviderError::HeaderNotFound(merkle.into()))?;
+    let state_root = StateRoot::from_tx(tx)
+        .with_prefix_sets(TriePrefixSets {
+            account_prefix_set: PrefixSetMut::all().freeze(),
+            ..Default::default()
+        })
+        .root()?;
+    findings.entries_checked += 1;
+    if state_root != header.state_root() {
+        findings.inconsistent(
+            || {
+                format!(
+                    "state root {state_root} recomputed from the hashed accounts doesn't match state root {} of block {merkle}",
+                    header.state_root()
+                )
+            },
+            Some(Repair::RebuildTrie),
+        );
+    }
+
+    // Sample storage tries evenly across the hashed address space. A storage root computed from
+    // the stored trie nodes must match the one recomputed from the storage leaves.
+    let samples = config.trie_samples.max(1);
+    let mut hashed_storages = tx.cursor_dup_read::<tables::HashedStorages>()?;
+    let mut last_sampled = None;
+    for sample in 0..samples {
+        let mut seek_key = B256::ZERO;
+        seek_key[..8].copy_from_slice(&(u64::MAX / samples * sample).to_be_bytes());
+
+        let Some((hashed_address, _)) = hashed_storages.seek(seek_key)? else { break };
+        if last_sampled.replace(hashed_address) == Some(hashed_address) {
+            continue
+        }
+        findings.entries_checked += 1;
+
+        let root = StorageRoot::from_tx_hashed(tx, hashed_address).root()?;
+        let recomputed = StorageRoot::from_tx_hashed(tx, hashed_address)
+            .with_prefix_set(PrefixSetMut::all().freeze())
+            .root()?;
+        if root != recomputed {
+            findings.inconsistent(
+                || format!("storage trie of account {hashed_address} has root {root}, but its storage has root {recomputed}"),
+                Some(Repair::RecomputeStorageTrie { hashed_address }),
+            );
+        }
+    }
+
+    let mut hashed_accounts = tx.cursor_read::<tables::HashedAccounts>()?;
+    let mut storage_tries = tx.cursor_dup_read::<tables::StoragesTrie>()?;
+    let mut entry = storage_tries.first()?;
+    while let Some((hashed_address, _)) = entry {
+        findings.entries_checked += 1;
+        if hashed_accounts.seek_exact(hashed_address)?.is_none() {
+            findings.inconsistent(
+                || {
+                    format!(
+                        "storage trie of account {hashed_address} exists, but the account doesn't"
+                    )
+                },
+                Some(Repair::DeleteStorageTrie { hashed_address }),
+            );
+        }
+        entry = storage_tries.next_no_dup()?;
+    }
+
+    Ok(())
+}
+
+/// Checks the stage checkpoints against each other, and against the data they refer to.
+fn stage_checkpoints<N: ProviderNodeTypes>(
+    provider: &DatabaseProviderRO<N::DB, N>,
+    findings: &mut Findings,
+) -> eyre::Result<()> {
+    let tx = provider.tx_ref();
+    let static_file_provider = provider.static_file_provider();
+
+    for (stage, dependency) in STAGE_DEPENDENCIES {
+        findings.entries_checked += 1;
+        let stage_block = stage_checkpoint(provider, stage)?;
+        let dependency_block = stage_checkpoint(provider, dependency)?;
+        if stage_block > dependency_block {
+            findings.inconsistent(
+                || format!("{stage} checkpoint at block {stage_block} is ahead of {dependency} checkpoint at block {dependency_block}"),
+                None,
+            );
+        }
+    }
+
+    let headers = stage_checkpoint(provider, StageId::Headers)?;
+    let highest_header =
+        static_file_provider.get_highest_static_file_block(StaticFileSegment::Headers);
+    findings.entries_checked += 1;
+    if highest_header.is_none_or(|block| block < headers) {
+        findings.inconsistent(
+            || format!("Headers checkpoint is at block {headers}, but the highest header is {highest_header:?}"),
+            None,
+        );
+    }
+
+    let bodies = stage_checkpoint(provider, StageId::Bodies)?;
+    let highest_body_indices =
+        tx.cursor_read::<tables::BlockBodyIndices>()?.last()?.map(|(block, _)| block);
+    findings.entries_checked += 1;
+    if highest_body_indices != Some(bodies) {
+        findings.inconsistent(
+            || format!("Bodies checkpoint is at block {bodies}, but the highest block body indices are for {highest_body_indices:?}"),
+            None,
+        );
+    }
+
+    let highest_transactions =
+        static_file_provider.get_highest_static_file_block(StaticFileSegment::Transactions);
+    findings.entries_checked += 1;
+    if highest_transactions.is_none_or(|block| block != bodies) {
+        findings.inconsistent(
+            || format!("Bodies checkpoint is at block {bodies}, but the highest transactions static file block is {highest_transactions:?}"),
+            None,
+        );
+    }
+
+    let execution = stage_checkpoint(provider, StageId::Execution)?;
+    let highest_changeset =
+        tx.cursor_read::<tables::AccountChangeSets>()?.last()?.map(|(block, _)| block);
+    findings.entries_checked += 1;
+    if highest_changeset.is_some_and(|block| block > execution) {
+        findings.inconsistent(
+            || format!("Execution checkpoint is at block {execution}, but there are account changesets up to block {highest_changeset:?}"),
+            None,
+        );
+    }
+
+    let highest_receipts =
+        static_file_provider.get_highest_static_file_block(StaticFileSegment::Receipts);
+    findings.entries_checked += 1;
+    if highest_receipts.is_some_and(|block| block > execution) {
+        findings.inconsistent(
+            || format!("Execution checkpoint is at block {execution}, but there are receipts static files up to block {highest_receipts:?}"),
+            None,
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,479 @@
+//! `reth db verify` command.
+
+use crate::common::AccessRights;
+use clap::{Parser, ValueEnum};
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use reth_provider::{providers::ProviderNodeTypes, DatabaseProviderFactory, ProviderFactory};
+use serde::Serialize;
+use std::{
+    path::PathBuf,
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+use tracing::{error, info, warn};
+
+mod checks;
+mod repair;
+
+use repair::{Repair, Repairer};
+
+/// The arguments for the `reth db verify` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Checks to run. All checks are run if none are specified.
+    #[arg(long = "check", value_delimiter = ',')]
+    checks: Vec<Check>,
+
+    /// Number of storage tries sampled across the hashed address space by the trie check.
+    #[arg(long = "trie.samples", default_value_t = 1000)]
+    trie_samples: u64,
+
+    /// Maximum number of inconsistencies listed in the report for each check.
+    ///
+    /// All inconsistencies are counted and repaired, regardless of this limit.
+    #[arg(long, default_value_t = 100)]
+    max_inconsistencies: usize,
+
+    /// Writes the report as JSON to the given file.
+    #[arg(long, value_name = "PATH")]
+    report: Option<PathBuf>,
+
+    /// Repairs the inconsistencies that can be fixed in place.
+    ///
+    /// Derived entries, like transaction hash numbers, hashed state and trie nodes, are rewritten
+    /// from the data they are derived from. Other inconsistencies are only reported.
+    ///
+    /// Repairs are applied while the checks are running, and the trie is recomputed over the
+    /// repaired state afterwards. Nothing is committed if the recomputed state root doesn't match
+    /// the header.
+    #[arg(long)]
+    repair: bool,
+}
+
+impl Command {
+    /// Returns the access rights required by the command.
+    pub const fn access_rights(&self) -> AccessRights {
+        if self.repair {
+            AccessRights::RW
+        } else {
+            AccessRights::RO
+        }
+    }
+
+    /// Execute `db verify` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        warn!("This command should be run without the node running!");
+
+        let checks = if self.checks.is_empty() { Check::value_variants() } else { &self.checks };
+        let config = checks::Config {
+            trie_samples: self.trie_samples,
+            max_inconsistencies: self.max_inconsistencies,
+        };
+
+        info!(target: "reth::cli", ?checks, "Verifying database");
+        let start = Instant::now();
+
+        // Repairs are applied on a single read-write transaction while the checks are running on
+        // their own read-only ones, which don't see the repairs.
+        let mut repairer = self
+            .repair
+            .then(|| provider_factory.database_provider_rw().map(Repairer::new))
+            .transpose()?;
+        let (repairs_tx, repairs_rx) = mpsc::channel();
+        let (outcomes, repaired) = std::thread::scope(|scope| {
+            let handles = checks
+                .iter()
+                .map(|&check| {
+                    let provider_factory = &provider_factory;
+                    let config = &config;
+                    let repairs = self.repair.then(|| repairs_tx.clone());
+                    scope.spawn(move || {
+                        let start = Instant::now();
+                        let outcome = checks::run(check, provider_factory, config, repairs);
+                        (check, outcome, start.elapsed())
+                    })
+                })
+                .collect::<Vec<_>>();
+            drop(repairs_tx);
+
+            // stops once all checks have finished and dropped their senders
+            let mut repaired = Ok(0);
+            if let Some(repairer) = repairer.as_mut() {
+                for repair in repairs_rx {
+                    if let Err(err) = repairer.apply(repair) {
+                        repaired = Err(err);
+                        break
+                    }
+                }
+            }
+
+            let outcomes = handles
+                .into_iter()
+                .map(|handle| handle.join().expect("verification thread panicked"))
+                .collect::<Vec<_>>();
+            (outcomes, repaired)
+        });
+        repaired?;
+
+        let mut report = VerifyReport::default();
+        for (check, outcome, elapsed) in outcomes {
+            match outcome {
+                Ok(findings) => report.checks.push(CheckReport::new(check, &findings, elapsed)),
+                Err(err) => {
+                    error!(target: "reth::cli", ?check, %err, "Check failed");
+                    report.checks.push(CheckReport::failed(check, err, elapsed));
+                }
+            }
+        }
+
+        if let Some(repairer) = repairer {
+            report.repaired = repairer.finish()?;
+            if report.repaired > 0 {
+                info!(target: "reth::cli", repaired = report.repaired, "Repaired database");
+            }
+        }
+        report.consistent = report.checks.iter().all(|check| {
+            check.status == CheckStatus::Consistent ||
+                check.status == CheckStatus::Skipped ||
+                (self.repair && check.status == CheckStatus::Inconsistent && check.repairable)
+        });
+
+        report.print();
+        info!(target: "reth::cli", elapsed = ?start.elapsed(), "Verification finished");
+
+        if let Some(path) = &self.report {
+            reth_fs_util::write(path, serde_json::to_vec_pretty(&report)?)?;
+            info!(target: "reth::cli", ?path, "Wrote verification report");
+        }
+
+        eyre::ensure!(report.consistent, "Database is inconsistent");
+        Ok(())
+    }
+}
+
+/// Invariant checked by `reth db verify`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Check {
+    /// `BlockBodyIndices` against the transaction ranges of the transaction and receipt static
+    /// files.
+    BodyIndices,
+    /// `TransactionHashNumbers` against the transactions.
+    TransactionHashNumbers,
+    /// `AccountsHistory` and `StoragesHistory` against the changesets.
+    HistoryIndices,
+    /// `HashedAccounts` and `HashedStorages` against the plain state.
+    HashedState,
+    /// The state root recomputed from the hashed accounts against the header, and storage trie
+    /// nodes against roots recomputed over sampled storage tries.
+    Trie,
+    /// Stage checkpoints against each other and the data they refer to.
+    StageCheckpoints,
+}
+
+/// Outcome of a check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CheckStatus {
+    /// No inconsistencies were found.
+    Consistent,
+    /// Inconsistencies were found.
+    Inconsistent,
+    /// The check couldn't run, e.g. because the stages it depends on haven't caught up yet.
+    Skipped,
+    /// The check failed with an error while reading the data.
+    Failed,
+}
+
+/// Inconsistency found by a check.
+#[derive(Debug, Clone, Serialize)]
+pub struct Inconsistency {
+    /// Description of the inconsistency.
+    pub message: String,
+    /// Whether the inconsistency can be repaired with `--repair`.
+    pub repairable: bool,
+}
+
+/// Report of a single check.
+#[derive(Debug, Serialize)]
+pub struct CheckReport {
+    /// The check.
+    pub check: Check,
+    /// Outcome of the check.
+    pub status: CheckStatus,
+    /// Number of entries checked.
+    pub entries_checked: u64,
+    /// Total number of inconsistencies found.
+    pub inconsistencies_found: u64,
+    /// Whether all inconsistencies found can be repaired with `--repair`.
+    pub repairable: bool,
+    /// Inconsistencies found, up to `--max-inconsistencies`.
+    pub inconsistencies: Vec<Inconsistency>,
+    /// Reason the check was skipped or failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Time it took to run the check, in milliseconds.
+    pub elapsed_ms: u64,
+}
+
+impl CheckReport {
+    fn new(check: Check, findings: &checks::Findings, elapsed: Duration) -> Self {
+        let status = if findings.skipped.is_some() {
+            CheckStatus::Skipped
+        } else if findings.total > 0 {
+            CheckStatus::Inconsistent
+        } else {
+            CheckStatus::Consistent
+        };
+
+        Self {
+            check,
+            status,
+            entries_checked: findings.entries_checked,
+            inconsistencies_found: findings.total,
+            repairable: findings.total == findings.repairable,
+            inconsistencies: findings.inconsistencies.clone(),
+            reason: findings.skipped.clone(),
+            elapsed_ms: elapsed.as_millis() as u64,
+        }
+    }
+
+    fn failed(check: Check, err: eyre::Report, elapsed: Duration) -> Self {
+        Self {
+            check,
+            status: CheckStatus::Failed,
+            entries_checked: 0,
+            inconsistencies_found: 0,
+            repairable: false,
+            inconsistencies: Vec::new(),
+            reason: Some(format!("{err:#}")),
+            elapsed_ms: elapsed.as_millis() as u64,
+        }
+    }
+}
+
+/// Report of `reth db verify`.
+#[derive(Debug, Default, Serialize)]
+pub struct VerifyReport {
+    /// Whether the database is consistent, after repairs if `--repair` was passed.
+    pub consistent: bool,
+    /// Number of repairs applied.
+    pub repaired: usize,
+    /// Reports of the individual checks.
+    pub checks: Vec<CheckReport>,
+}
+
+impl VerifyReport {
+    /// Prints the report as a table, followed by the listed inconsistencies.
+    fn print(&self) {
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["Check", "Status", "Entries Checked", "Inconsistencies", "Elapsed"]);
+        for check in &self.checks {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(format!("{:?}", check.check)))
+                .add_cell(Cell::new(format!("{:?}", check.status)))
+                .add_cell(Cell::new(check.entries_checked))
+                .add_cell(Cell::new(check.inconsistencies_found))
+                .add_cell(Cell::new(format!("{:?}", Duration::from_millis(check.elapsed_ms))));
+            table.add_row(row);
+        }
+        println!("{table}");
+
+        for check in &self.checks {
+            if let Some(reason) = &check.reason {
+                println!("{:?}: {reason}", check.check);
+            }
+            for inconsistency in &check.inconsistencies {
+                println!("{:?}: {}", check.check, inconsistency.message);
+            }
+            let unlisted = check.inconsistencies_found - check.inconsistencies.len() as u64;
+            if unlisted > 0 {
+                println!("{:?}: ... and {unlisted} more", check.check);
+            }
+        }
+
+        if self.repaired > 0 {
+            println!("Repaired {} inconsistencies", self.repaired);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, Address, B256, U256};
+    use reth_db::tables;
+    use reth_db_api::{
+        cursor::{DbCursorRO, DbCursorRW},
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives::{Account, StorageEntry};
+    use reth_provider::{
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        BlockWriter, StorageLocation, TrieWriter,
+    };
+    use reth_testing_utils::generators::{self, random_block, BlockParams};
+    use reth_trie::{StateRoot, StoredNibbles};
+    use reth_trie_db::DatabaseStateRoot;
+    use serde_json::Value;
+
+    /// Address of the account with storage.
+    const STORAGE_ACCOUNT: Address = Address::with_last_byte(1);
+
+    /// Writes a state with enough accounts and storage slots for the trie to have branch nodes,
+    /// and block 0 committing to it.
+    fn fixture(provider_factory: &ProviderFactory<MockNodeTypesWithDB>) -> B256 {
+        let provider = provider_factory.database_provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        for i in 1..=100u8 {
+            let address = Address::with_last_byte(i);
+            let account = Account { nonce: i as u64, balance: U256::from(i), bytecode_hash: None };
+            tx.put::<tables::PlainAccountState>(address, account).unwrap();
+            tx.put::<tables::HashedAccounts>(keccak256(address), account).unwrap();
+        }
+        let mut hashed_storages = tx.cursor_dup_write::<tables::HashedStorages>().unwrap();
+        for i in 1..=50u8 {
+            let (key, value) = (B256::with_last_byte(i), U256::from(i));
+            tx.put::<tables::PlainStorageState>(STORAGE_ACCOUNT, StorageEntry { key, value })
+                .unwrap();
+            hashed_storages
+                .upsert(keccak256(STORAGE_ACCOUNT), &StorageEntry { key: keccak256(key), value })
+                .unwrap();
+        }
+
+        let (state_root, updates) = StateRoot::from_tx(tx).root_with_updates().unwrap();
+        provider.write_trie_updates(&updates).unwrap();
+
+        let block = random_block(
+            &mut generators::rng(),
+            0,
+            BlockParams { tx_count: Some(3), ..Default::default() },
+        );
+        let (mut header, body) = block.split_header_body();
+        header.state_root = state_root;
+        let block =
+            reth_primitives::SealedBlock::<reth_primitives::Block>::seal_parts(header, body);
+        provider.insert_block(block.try_recover().unwrap(), StorageLocation::Database).unwrap();
+        provider.commit().unwrap();
+
+        state_root
+    }
+
+    /// Runs the command with the given arguments and returns the written report.
+    fn verify(
+        provider_factory: &ProviderFactory<MockNodeTypesWithDB>,
+        args: &[&str],
+    ) -> (eyre::Result<()>, Value) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.json");
+        let cmd = Command::try_parse_from(
+            ["reth", "--check", "transaction-hash-numbers,hashed-state,trie", "--report"]
+                .into_iter()
+                .chain([path.to_str().unwrap()])
+                .chain(args.iter().copied()),
+        )
+        .unwrap();
+        let result = cmd.execute(provider_factory.clone());
+        (result, serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap())
+    }
+
+    fn inconsistencies(report: &Value) -> Vec<(String, u64)> {
+        report["checks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|check| {
+                (
+                    check["check"].as_str().unwrap().to_string(),
+                    check["inconsistencies_found"].as_u64().unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_checks() {
+        let cmd =
+            Command::try_parse_from(["reth", "--check", "body-indices,trie", "--repair"]).unwrap();
+        assert_eq!(cmd.checks, vec![Check::BodyIndices, Check::Trie]);
+        assert!(cmd.access_rights().is_read_write());
+
+        let cmd = Command::try_parse_from(["reth"]).unwrap();
+        assert!(cmd.checks.is_empty());
+        assert!(!cmd.access_rights().is_read_write());
+    }
+
+    #[test]
+    fn consistent_database() {
+        let provider_factory = create_test_provider_factory();
+        fixture(&provider_factory);
+
+        let (result, report) = verify(&provider_factory, &[]);
+        result.unwrap();
+        assert_eq!(report["consistent"], true);
+        assert_eq!(
+            inconsistencies(&report),
+            vec![
+                ("transaction-hash-numbers".to_string(), 0),
+                ("hashed-state".to_string(), 0),
+                ("trie".to_string(), 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn repairs_corrupted_database() {
+        let provider_factory = create_test_provider_factory();
+        let state_root = fixture(&provider_factory);
+
+        let provider = provider_factory.database_provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        // a missing and a dangling transaction hash number
+        let (hash, _) =
+            tx.cursor_read::<tables::TransactionHashNumbers>().unwrap().first().unwrap().unwrap();
+        tx.delete::<tables::TransactionHashNumbers>(hash, None).unwrap();
+        tx.put::<tables::TransactionHashNumbers>(B256::repeat_byte(0xaa), 100).unwrap();
+        // a hashed account that doesn't match the plain state, which the trie commits to
+        let hashed_address = keccak256(Address::with_last_byte(2));
+        tx.put::<tables::HashedAccounts>(hashed_address, Account::default()).unwrap();
+        // the storage trie of an account that doesn't exist
+        let (_, node) =
+            tx.cursor_dup_read::<tables::StoragesTrie>().unwrap().first().unwrap().unwrap();
+        tx.put::<tables::StoragesTrie>(B256::repeat_byte(0xbb), node).unwrap();
+        // an account trie node that doesn't match the hashed accounts
+        let (nibbles, mut node) =
+            tx.cursor_read::<tables::AccountsTrie>().unwrap().last().unwrap().unwrap();
+        node.hashes = node.hashes.iter().map(|_| B256::repeat_byte(0xcc)).collect();
+        tx.put::<tables::AccountsTrie>(StoredNibbles(nibbles.0), node).unwrap();
+        provider.commit().unwrap();
+
+        let (result, report) = verify(&provider_factory, &[]);
+        assert!(result.is_err());
+        assert_eq!(report["consistent"], false);
+        assert_eq!(
+            inconsistencies(&report),
+            vec![
+                ("transaction-hash-numbers".to_string(), 2),
+                ("hashed-state".to_string(), 1),
+                ("trie".to_string(), 2)
+            ]
+        );
+
+        let (result, report) = verify(&provider_factory, &["--repair"]);
+        result.unwrap();
+        assert_eq!(report["consistent"], true);
+        assert_eq!(report["repaired"], 5);
+
+        let (result, report) = verify(&provider_factory, &[]);
+        result.unwrap();
+        assert_eq!(report["repaired"], 0);
+        assert_eq!(
+            StateRoot::from_tx(provider_factory.provider().unwrap().tx_ref()).root().unwrap(),
+            state_root
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{keccak256, Address, BlockNumber, TxHash, TxNumber, B256};
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::StorageEntry;
+use reth_provider::{DBProvider, HeaderProvider, ProviderError, StageCheckpointReader, TrieWriter};
+use reth_stages::StageId;
+use reth_trie::{
+    prefix_set::{PrefixSetMut, TriePrefixSetsMut},
+    Nibbles, StateRoot,
+};
+use reth_trie_db::DatabaseStateRoot;
+use tracing::{info, warn};
+
+/// Repair of an inconsistency in an entry that's derived from other data.
+#[derive(Debug, Clone)]
+pub(crate) enum Repair {
+    /// Inserts a missing transaction hash number.
+    InsertTransactionHashNumber { hash: TxHash, number: TxNumber },
+    /// Deletes a transaction hash number that doesn't point to the transaction with the hash.
+    DeleteTransactionHashNumber { hash: TxHash },
+    /// Rewrites the hashed account from the plain account state.
+    RehashAccount { address: Address },
+    /// Rewrites the hashed storage slot from the plain storage state.
+    RehashStorage { address: Address, key: B256 },
+    /// Deletes the storage trie of an account that doesn't exist.
+    DeleteStorageTrie { hashed_address: B256 },
+    /// Recomputes the storage trie of an account from its storage.
+    RecomputeStorageTrie { hashed_address: B256 },
+    /// Recomputes the whole trie from the hashed state.
+    RebuildTrie,
+}
+
+/// Applies repairs as they are found, on a single read-write transaction.
+///
+/// Repairs of the hashed state and storage tries invalidate the trie nodes above them, so the
+/// affected parts of the trie are recomputed by [`Repairer::finish`] before committing.
+#[derive(Debug)]
+pub(crate) struct Repairer<Provider> {
+    provider: Provider,
+    applied: usize,
+    prefix_sets: TriePrefixSetsMut,
+    rebuild_trie: bool,
+}
+
+impl<Provider> Repairer<Provider>
+where
+    Provider: DBProvider<Tx: DbTxMut> + HeaderProvider + StageCheckpointReader + TrieWriter,
+{
+    /// Creates a new [`Repairer`] writing to the provider's transaction.
+    pub(crate) fn new(provider: Provider) -> Self {
+        Self {
+            provider,
+            applied: 0,
+            prefix_sets: TriePrefixSetsMut::default(),
+            rebuild_trie: false,
+        }
+    }
+
+    /// Applies the repair.
+    pub(crate) fn apply(&mut self, repair: Repair) -> eyre::Result<()> {
+        let tx = self.provider.tx_ref();
+        match repair {
+            Repair::InsertTransactionHashNumber { hash, number } => {
+                tx.put::<tables::TransactionHashNumbers>(hash, number)?;
+            }
+            Repair::DeleteTransactionHashNumber { hash } => {
+                tx.delete::<tables::TransactionHashNumbers>(hash, None)?;
+            }
+            Repair::RehashAccount { address } => {
+                let hashed_address = keccak256(address);
+                match tx.get::<tables::PlainAccountState>(address)? {
+                    Some(account) => tx.put::<tables::HashedAccounts>(hashed_address, account)?,
+                    None => {
+                        tx.delete::<tables::HashedAccounts>(hashed_address, None)?;
+                        self.prefix_sets.destroyed_accounts.insert(hashed_address);
+                    }
+                }
+                self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+            }
+            Repair::RehashStorage { address, key } => {
+                let value = tx
+                    .cursor_dup_read::<tables::PlainStorageState>()?
+                    .seek_by_key_subkey(address, key)?
+                    .filter(|entry| entry.key == key)
+                    .map(|entry| entry.value)
+                    .unwrap_or_default();
+
+                let (hashed_address, hashed_key) = (keccak256(address), keccak256(key));
+                let mut cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+                if cursor
+                    .seek_by_key_subkey(hashed_address, hashed_key)?
+                    .is_some_and(|entry| entry.key == hashed_key)
+                {
+                    cursor.delete_current()?;
+                }
+                if !value.is_zero() {
+                    cursor.upsert(hashed_address, &StorageEntry { key: hashed_key, value })?;
+                }
+
+                self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+                self.prefix_sets
+                    .storage_prefix_sets
+                    .entry(hashed_address)
+                    .or_default()
+                    .insert(Nibbles::unpack(hashed_key));
+            }
+            Repair::DeleteStorageTrie { hashed_address } => {
+                let mut cursor = tx.cursor_dup_write::<tables::StoragesTrie>()?;
+                if cursor.seek_exact(hashed_address)?.is_some() {
+                    cursor.delete_current_duplicates()?;
+                }
+            }
+            Repair::RecomputeStorageTrie { hashed_address } => {
+                self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+                self.prefix_sets.storage_prefix_sets.insert(hashed_address, PrefixSetMut::all());
+            }
+            Repair::RebuildTrie => self.rebuild_trie = true,
+        }
+
+        self.applied += 1;
+        Ok(())
+    }
+
+    /// Recomputes the parts of the trie invalidated by the repairs, and commits them if the
+    /// resulting state root matches the header of the `MerkleExecute` checkpoint.
+    ///
+    /// Returns the number of applied repairs.
+    pub(crate) fn finish(self) -> eyre::Result<usize> {
+        let Self { provider, applied, prefix_sets, rebuild_trie } = self;
+        if applied == 0 {
+            return Ok(0)
+        }
+
+        let tx = provider.tx_ref();
+        let trie_invalidated = rebuild_trie ||
+            !prefix_sets.account_prefix_set.is_empty() ||
+            !prefix_sets.destroyed_accounts.is_empty();
+        let execution = checkpoint(&provider, StageId::Execution)?;
+        let merkle = checkpoint(&provider, StageId::MerkleExecute)?;
+        if trie_invalidated && merkle != execution {
+            warn!(target: "reth::cli", merkle, execution, "Not recomputing the trie, MerkleExecute is behind Execution");
+        } else if trie_invalidated {
+            let (state_root, updates) = if rebuild_trie {
+                info!(target: "reth::cli", "Rebuilding the trie");
+                tx.clear::<tables::AccountsTrie>()?;
+                tx.clear::<tables::StoragesTrie>()?;
+                StateRoot::from_tx(tx).root_with_updates()?
+            } else {
+                info!(target: "reth::cli", "Recomputing the repaired parts of the trie");
+                StateRoot::from_tx(tx).with_prefix_sets(prefix_sets.freeze()).root_with_updates()?
+            };
+
+            let header = provider
+                .sealed_header(merkle)?
+                .ok_or(ProviderError::HeaderNotFound(merkle.into()))?;
+            eyre::ensure!(
+                state_root == header.state_root(),
+                "state root {state_root} of the repaired trie doesn't match state root {} of block {merkle}, not committing the repairs",
+                header.state_root()
+            );
+            provider.write_trie_updates(&updates)?;
+        }
+
+        provider.commit()?;
+        Ok(applied)
+    }
+}
+
+/// Returns the block number of the stage checkpoint, or `0` if there's none.
+fn checkpoint<Provider: StageCheckpointReader>(
+    provider: &Provider,
+    id: StageId,
+) -> eyre::Result<BlockNumber> {
+    Ok(provider
+        .get_stage_checkpoint(id)?
+        .map(|checkpoint| checkpoint.block_number)
+        .unwrap_or_default())
+}
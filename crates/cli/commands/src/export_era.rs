@@ -0,0 +1,311 @@
+//! Command that exports chain history to ERA1 and ERA archives.
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::{BlockHeader, ReceiptWithBloom};
+use alloy_primitives::BlockNumber;
+use alloy_rlp::Encodable;
+use clap::Parser;
+use reqwest::{header::ACCEPT, StatusCode};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_era::{
+    era::{self, BeaconBlockPayload, EraWriter, SLOTS_PER_HISTORICAL_ROOT},
+    era1::{self, BlockTuple, Era1Writer, MAX_BLOCKS_PER_ERA1},
+};
+use reth_node_core::version::SHORT_VERSION;
+use reth_primitives::{Block, NodePrimitives, Receipt};
+use reth_provider::{BlockNumReader, BlockReader, HeaderProvider, ReceiptProvider};
+use serde::Deserialize;
+use std::{io::BufWriter, path::PathBuf};
+use tracing::info;
+
+/// Exports chain history to ERA1 and ERA archives.
+///
+/// Pre-merge blocks are exported to ERA1 archives. ERA archives are built from beacon chain data,
+/// so post-merge blocks are only exported if a beacon node API is configured with `--beacon-api`.
+/// The execution payloads of the beacon blocks are checked against the local headers.
+#[derive(Debug, Parser)]
+pub struct ExportEraCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block to export.
+    ///
+    /// ERA1 archives always start at an epoch boundary, so this is rounded down to the first block
+    /// of its epoch.
+    #[arg(long, value_name = "BLOCK", default_value_t = 0, verbatim_doc_comment)]
+    from: BlockNumber,
+
+    /// The last block to export.
+    ///
+    /// Defaults to the latest block.
+    #[arg(long, value_name = "BLOCK", verbatim_doc_comment)]
+    to: Option<BlockNumber>,
+
+    /// The network name used in the archive file names.
+    ///
+    /// Defaults to the name of the chain.
+    #[arg(long, value_name = "NAME", verbatim_doc_comment)]
+    network: Option<String>,
+
+    /// The beacon node API the beacon blocks and states of ERA archives are fetched from.
+    ///
+    /// Without it, the export stops at the merge.
+    #[arg(long, value_name = "URL", verbatim_doc_comment)]
+    beacon_api: Option<String>,
+
+    /// The directory to write the archives to.
+    #[arg(value_name = "OUT_DIR", verbatim_doc_comment)]
+    out_dir: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ExportEraCommand<C> {
+    /// Execute `export-era` command
+    pub async fn execute<N>(self) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<
+            ChainSpec = C::ChainSpec,
+            Primitives: NodePrimitives<Block = Block, Receipt = Receipt>,
+        >,
+    {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+
+        let network = self.network.unwrap_or_else(|| self.env.chain.chain().to_string());
+        let mut last = provider.last_block_number()?;
+        if let Some(to) = self.to {
+            last = last.min(to);
+        }
+
+        reth_fs_util::create_dir_all(&self.out_dir)?;
+
+        let epoch_len = MAX_BLOCKS_PER_ERA1 as u64;
+        let mut number = self.from / epoch_len * epoch_len;
+        let mut exported_files = 0;
+        while number <= last {
+            let epoch = number / epoch_len;
+            let tmp_path = self.out_dir.join(format!("{network}-{epoch:05}.era1.tmp"));
+            let mut writer =
+                Era1Writer::new(BufWriter::new(reth_fs_util::create_file(&tmp_path)?))?;
+
+            let mut reached_merge = false;
+            while number <= last && number / epoch_len == epoch {
+                let block = provider
+                    .block_by_number(number)?
+                    .ok_or_else(|| eyre::eyre!("block {number} not found"))?;
+                // ERA1 archives only hold proof-of-work blocks
+                if number > 0 && block.header.difficulty.is_zero() {
+                    reached_merge = true;
+                    break
+                }
+
+                let receipts = provider
+                    .receipts_by_block(number.into())?
+                    .ok_or_else(|| eyre::eyre!("receipts of block {number} not found"))?
+                    .into_iter()
+                    .map(ReceiptWithBloom::from)
+                    .collect::<Vec<_>>();
+                let total_difficulty = provider
+                    .header_td_by_number(number)?
+                    .ok_or_else(|| eyre::eyre!("total difficulty of block {number} not found"))?;
+
+                let mut tuple = BlockTuple {
+                    header: Vec::new(),
+                    body: Vec::new(),
+                    receipts: Vec::new(),
+                    total_difficulty,
+                };
+                block.header.encode(&mut tuple.header);
+                block.body.encode(&mut tuple.body);
+                receipts.encode(&mut tuple.receipts);
+                writer.push(number, &tuple)?;
+
+                number += 1;
+            }
+
+            if writer.is_empty() {
+                reth_fs_util::remove_file(&tmp_path)?;
+                break
+            }
+
+            let blocks = writer.len();
+            let (_, accumulator) = writer.finish()?;
+            let path = self.out_dir.join(era1::file_name(&network, epoch, accumulator));
+            reth_fs_util::rename(&tmp_path, &path)?;
+            exported_files += 1;
+            info!(target: "reth::cli", path = %path.display(), blocks, "Exported era1 archive");
+
+            if reached_merge {
+                break
+            }
+        }
+
+        // the remaining blocks are post-merge blocks
+        let number = number.max(self.from);
+        if number <= last {
+            let Some(beacon_api) = self.beacon_api else {
+                info!(target: "reth::cli", block = number, "Reached the merge, exporting post-merge blocks requires --beacon-api");
+                return Ok(())
+            };
+            let beacon = BeaconApi::new(beacon_api).await?;
+
+            let slot_of = |number: BlockNumber| -> eyre::Result<u64> {
+                let header = provider
+                    .header_by_number(number)?
+                    .ok_or_else(|| eyre::eyre!("header of block {number} not found"))?;
+                beacon.slot_at(header.timestamp())
+            };
+            let last_slot = slot_of(last)?;
+
+            // only complete eras are exported, i.e. eras whose last block is known locally
+            let mut era = slot_of(number)? / SLOTS_PER_HISTORICAL_ROOT + 1;
+            while era * SLOTS_PER_HISTORICAL_ROOT <= last_slot {
+                let tmp_path = self.out_dir.join(format!("{network}-{era:05}.era.tmp"));
+                let mut writer =
+                    EraWriter::new(BufWriter::new(reth_fs_util::create_file(&tmp_path)?), era)?;
+
+                let mut blocks = 0;
+                for slot in writer.start_slot()..writer.state_slot() {
+                    let Some(signed_block) = beacon.block(slot).await? else { continue };
+                    if let Some(payload) = BeaconBlockPayload::decode(&signed_block)? {
+                        let block = payload.block_number();
+                        let local_hash = provider
+                            .sealed_header(block)?
+                            .ok_or_else(|| eyre::eyre!("header of block {block} not found"))?
+                            .hash();
+                        if payload.payload.block_hash() != local_hash {
+                            eyre::bail!("execution payload of slot {slot} does not match local block {block}")
+                        }
+                        blocks += 1;
+                    }
+                    writer.push_block(slot, &signed_block)?;
+                }
+
+                let state = beacon.state(writer.state_slot()).await?;
+                let (_, historical_root) = writer.finish(&state)?;
+                let path = self.out_dir.join(era::file_name(&network, era, historical_root));
+                reth_fs_util::rename(&tmp_path, &path)?;
+                exported_files += 1;
+                info!(target: "reth::cli", path = %path.display(), blocks, "Exported era archive");
+
+                era += 1;
+            }
+        }
+
+        info!(target: "reth::cli", exported_files, "Export finished");
+
+        Ok(())
+    }
+}
+
+/// A minimal client of the beacon node API.
+#[derive(Debug)]
+struct BeaconApi {
+    client: reqwest::Client,
+    url: String,
+    genesis_time: u64,
+    seconds_per_slot: u64,
+}
+
+/// The `data` of a beacon node API response.
+#[derive(Debug, Deserialize)]
+struct BeaconResponse<T> {
+    data: T,
+}
+
+#[derive(Debug, Deserialize)]
+struct BeaconGenesis {
+    genesis_time: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BeaconSpec {
+    #[serde(rename = "SECONDS_PER_SLOT")]
+    seconds_per_slot: String,
+}
+
+impl BeaconApi {
+    /// Connects to the beacon node API and fetches the genesis time and slot duration.
+    async fn new(url: String) -> eyre::Result<Self> {
+        let client = reqwest::Client::new();
+        let url = url.trim_end_matches('/').to_string();
+        let BeaconResponse { data: BeaconGenesis { genesis_time } } = client
+            .get(format!("{url}/eth/v1/beacon/genesis"))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let BeaconResponse { data: BeaconSpec { seconds_per_slot } } = client
+            .get(format!("{url}/eth/v1/config/spec"))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        // the beacon node API encodes integers as decimal strings
+        let genesis_time = genesis_time.parse()?;
+        let seconds_per_slot = seconds_per_slot.parse()?;
+        if seconds_per_slot == 0 {
+            eyre::bail!("beacon node reports a slot duration of zero");
+        }
+        Ok(Self { client, url, genesis_time, seconds_per_slot })
+    }
+
+    /// Returns the slot of the given timestamp.
+    fn slot_at(&self, timestamp: u64) -> eyre::Result<u64> {
+        let elapsed = timestamp.checked_sub(self.genesis_time).ok_or_else(|| {
+            eyre::eyre!("timestamp {timestamp} is before the beacon chain genesis")
+        })?;
+        Ok(elapsed / self.seconds_per_slot)
+    }
+
+    /// Fetches the SSZ encoded `SignedBeaconBlock` of the slot, `None` if the slot is empty.
+    async fn block(&self, slot: u64) -> eyre::Result<Option<Vec<u8>>> {
+        self.get_ssz(format!("{}/eth/v2/beacon/blocks/{slot}", self.url)).await
+    }
+
+    /// Fetches the SSZ encoded `BeaconState` at the slot.
+    async fn state(&self, slot: u64) -> eyre::Result<Vec<u8>> {
+        self.get_ssz(format!("{}/eth/v2/debug/beacon/states/{slot}", self.url))
+            .await?
+            .ok_or_else(|| eyre::eyre!("beacon state at slot {slot} not found"))
+    }
+
+    async fn get_ssz(&self, url: String) -> eyre::Result<Option<Vec<u8>>> {
+        let response =
+            self.client.get(url).header(ACCEPT, "application/octet-stream").send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None)
+        }
+        Ok(Some(response.error_for_status()?.bytes().await?.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_export_era_command() {
+        let args: ExportEraCommand<EthereumChainSpecParser> = ExportEraCommand::parse_from([
+            "reth",
+            "--chain",
+            "mainnet",
+            "--from",
+            "10000",
+            "--to",
+            "20000",
+            "--beacon-api",
+            "http://localhost:5052",
+            "out",
+        ]);
+        assert_eq!(args.from, 10000);
+        assert_eq!(args.to, Some(20000));
+        assert_eq!(args.network, None);
+        assert_eq!(args.beacon_api.as_deref(), Some("http://localhost:5052"));
+        assert_eq!(args.out_dir, PathBuf::from("out"));
+    }
+}
@@ -0,0 +1,424 @@
+//! Command that imports chain history from ERA1 and ERA archives.
+use crate::{
+    common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs},
+    import::build_import_pipeline,
+};
+use alloy_consensus::Header;
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db::tables;
+use reth_db_api::transaction::DbTx;
+use reth_downloaders::file_client::FileClient;
+use reth_era::{
+    era::{EraReader, ERA_EXTENSION},
+    era1::{Era1File, ERA1_EXTENSION},
+    TrustedAccumulators,
+};
+use reth_ethereum_consensus::EthBeaconConsensus;
+use reth_evm::execute::BlockExecutorProvider;
+use reth_node_core::version::SHORT_VERSION;
+use reth_primitives::{
+    Block, BlockBody, NodePrimitives, Receipt, Receipts, SealedBlock, TransactionSigned,
+};
+use reth_provider::{
+    providers::ProviderNodeTypes, writer::UnifiedStorageWriter, BlockNumReader, ChainSpecProvider,
+    DatabaseProviderFactory, ExecutionOutcome, OriginalValuesKnown, ProviderFactory,
+    StageCheckpointReader, StageCheckpointWriter, StateWriter, StaticFileProviderFactory,
+    StorageLocation,
+};
+use reth_prune::PruneModes;
+use reth_stages::{StageCheckpoint, StageId};
+use reth_static_file::StaticFileProducer;
+use reth_static_file_types::StaticFileSegment;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{debug, error, info, warn};
+
+/// Imports chain history from ERA1 and ERA archives.
+#[derive(Debug, Parser)]
+pub struct ImportEraCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// Disables stages that require state.
+    ///
+    /// The receipts stored in ERA1 archives are imported instead, and the execution checkpoint is
+    /// advanced to the last block with receipts. The state at that block can then be initialized
+    /// with `reth init-state`.
+    #[arg(long, verbatim_doc_comment)]
+    no_state: bool,
+
+    /// Path to the trusted epoch accumulator roots that ERA1 archives are verified against.
+    ///
+    /// The file lists one hex encoded root per line, in epoch order. Required to import ERA1
+    /// archives.
+    #[arg(long, value_name = "FILE", verbatim_doc_comment)]
+    accumulators: Option<PathBuf>,
+
+    /// The path to an era archive or to a directory of era archives.
+    ///
+    /// Files with the `.era1` extension are read as pre-merge ERA1 archives, files with the
+    /// `.era` extension as post-merge ERA archives. ERA1 archives are imported before ERA
+    /// archives, and archives of the same kind in the order of their file names.
+    ///
+    /// The online stages (headers and bodies) are replaced by the archive import, after which the
+    /// remaining stages are executed.
+    #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraCommand<C> {
+    /// Execute `import-era` command
+    pub async fn execute<N, E, F>(self, executor: F) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<
+            ChainSpec = C::ChainSpec,
+            Primitives: NodePrimitives<Block = Block, Receipt = Receipt>,
+        >,
+        E: BlockExecutorProvider<Primitives = N::Primitives>,
+        F: FnOnce(Arc<N::ChainSpec>) -> E,
+    {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        if self.no_state {
+            info!(target: "reth::cli", "Disabled stages requiring state");
+        }
+
+        let archives = collect_archives(&self.path)?;
+        if archives.is_empty() {
+            eyre::bail!("no era archives found at {}", self.path.display());
+        }
+        debug!(target: "reth::cli", archives = archives.len(), "Found era archives");
+
+        let trusted = match &self.accumulators {
+            Some(path) => TrustedAccumulators::parse(&reth_fs_util::read_to_string(path)?)?,
+            None if archives.iter().any(|archive| matches!(archive, EraArchive::Era1(_))) => {
+                eyre::bail!("ERA1 archives are verified against trusted accumulator roots, pass them with --accumulators")
+            }
+            None => TrustedAccumulators::default(),
+        };
+
+        let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
+
+        let executor = executor(provider_factory.chain_spec());
+        let consensus = Arc::new(EthBeaconConsensus::new(self.env.chain.clone()));
+        info!(target: "reth::cli", "Consensus engine initialized");
+
+        let mut local_head = provider_factory.last_block_number()?;
+        let mut total_decoded_blocks = 0;
+        let mut total_decoded_txns = 0;
+        let mut total_imported_receipts = 0;
+
+        for archive in archives {
+            info!(target: "reth::cli", path = %archive.path().display(), "Reading era archive");
+
+            let ArchiveBlocks { blocks, receipts } = archive.read_blocks(
+                local_head,
+                &trusted,
+                provider_factory.chain_spec().as_ref(),
+            )?;
+            if blocks.is_empty() {
+                info!(target: "reth::cli", path = %archive.path().display(), "Era archive already imported");
+                continue
+            }
+
+            let first_block = local_head + 1;
+            let file_client = FileClient::from_blocks(blocks);
+            let tip = file_client.tip().ok_or(eyre::eyre!("file client has no tip"))?;
+            let max_block = file_client.max_block().unwrap_or(local_head);
+
+            total_decoded_blocks += file_client.headers_len();
+            total_decoded_txns += file_client.total_transactions();
+
+            let (mut pipeline, events) = build_import_pipeline(
+                &config,
+                provider_factory.clone(),
+                &consensus,
+                Arc::new(file_client),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+                self.no_state,
+                executor.clone(),
+            )?;
+
+            // override the tip
+            pipeline.set_tip(tip);
+            debug!(target: "reth::cli", ?tip, "Tip manually set");
+
+            let latest_block_number = provider_factory
+                .provider()?
+                .get_stage_checkpoint(StageId::Finish)?
+                .map(|ch| ch.block_number);
+            tokio::spawn(reth_node_events::node::handle_events(None, latest_block_number, events));
+
+            // Run pipeline
+            info!(target: "reth::cli", "Starting sync pipeline");
+            tokio::select! {
+                res = pipeline.run() => res?,
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+            }
+
+            // without state the receipts can not be recomputed, import the ones of the archive
+            if let Some(receipts) = receipts.filter(|_| self.no_state) {
+                total_imported_receipts +=
+                    import_receipts(&provider_factory, first_block, receipts)?;
+            }
+
+            local_head = max_block;
+        }
+
+        let provider = provider_factory.provider()?;
+
+        let imported_head = provider.last_block_number()?;
+        let total_imported_txns = provider.tx_ref().entries::<tables::TransactionHashNumbers>()?;
+
+        if imported_head < local_head {
+            error!(target: "reth::cli",
+                imported_head,
+                expected_head = local_head,
+                "Era archives were partially imported"
+            );
+        }
+
+        info!(target: "reth::cli",
+            total_decoded_blocks,
+            total_decoded_txns,
+            imported_head,
+            total_imported_txns,
+            total_imported_receipts,
+            "Era archives imported"
+        );
+
+        Ok(())
+    }
+}
+
+/// Writes the receipts of the imported blocks starting at `first_block` to storage and advances the
+/// execution checkpoint.
+///
+/// Returns the number of imported receipts.
+fn import_receipts<N>(
+    provider_factory: &ProviderFactory<N>,
+    first_block: BlockNumber,
+    mut receipts: Vec<Vec<Receipt>>,
+) -> eyre::Result<usize>
+where
+    N: ProviderNodeTypes<Primitives: NodePrimitives<Receipt = Receipt>>,
+{
+    let provider = provider_factory.database_provider_rw()?;
+
+    let receipts_head = provider_factory
+        .static_file_provider()
+        .get_highest_static_file_block(StaticFileSegment::Receipts)
+        .unwrap_or_default();
+    if receipts_head + 1 != first_block {
+        warn!(target: "reth::cli",
+            receipts_head,
+            first_block,
+            "Receipts of previously imported blocks are missing, skipping receipts"
+        );
+        return Ok(0)
+    }
+
+    // the pipeline may not have imported all blocks of the archive
+    let imported_head = provider.last_block_number()?;
+    receipts.truncate((imported_head + 1).saturating_sub(first_block) as usize);
+    if receipts.is_empty() {
+        return Ok(0)
+    }
+    let last_block = first_block + receipts.len() as u64 - 1;
+    let total_receipts = receipts.iter().map(Vec::len).sum();
+
+    let receipts = Receipts {
+        receipt_vec: receipts
+            .into_iter()
+            .map(|receipts| receipts.into_iter().map(Some).collect())
+            .collect(),
+    };
+    provider.write_state(
+        &ExecutionOutcome::new(Default::default(), receipts, first_block, Default::default()),
+        OriginalValuesKnown::Yes,
+        StorageLocation::StaticFiles,
+    )?;
+    provider.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(last_block))?;
+    UnifiedStorageWriter::commit(provider)?;
+
+    debug!(target: "reth::cli", first_block, last_block, total_receipts, "Imported receipts");
+    Ok(total_receipts)
+}
+
+/// The blocks read from an era archive.
+#[derive(Debug, Default)]
+struct ArchiveBlocks {
+    /// The blocks above the local head.
+    blocks: Vec<SealedBlock>,
+    /// The receipts of the blocks, if the archive stores them.
+    receipts: Option<Vec<Vec<Receipt>>>,
+}
+
+/// An era archive to import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum EraArchive {
+    /// A pre-merge ERA1 archive.
+    Era1(PathBuf),
+    /// A post-merge ERA archive.
+    Era(PathBuf),
+}
+
+impl EraArchive {
+    /// Returns the archive for the given path, if it has an era extension.
+    fn from_path(path: PathBuf) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            ERA1_EXTENSION => Some(Self::Era1(path)),
+            ERA_EXTENSION => Some(Self::Era(path)),
+            _ => None,
+        }
+    }
+
+    fn path(&self) -> &Path {
+        match self {
+            Self::Era1(path) | Self::Era(path) => path,
+        }
+    }
+
+    /// Reads and verifies the blocks of the archive that are above the local head.
+    fn read_blocks<Spec: EthChainSpec>(
+        &self,
+        local_head: u64,
+        trusted: &TrustedAccumulators,
+        chain_spec: &Spec,
+    ) -> eyre::Result<ArchiveBlocks> {
+        match self {
+            Self::Era1(path) => {
+                let file = Era1File::open(path)?;
+                if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+                    file.verify_file_name(name)?;
+                }
+                file.verify::<Header>(trusted)?;
+
+                if file.start_block == 0 {
+                    let genesis_hash = file.blocks.first().map(|block| block.hash());
+                    if genesis_hash != Some(chain_spec.genesis_hash()) {
+                        eyre::bail!(
+                            "genesis block of {} does not match the chain spec",
+                            path.display()
+                        );
+                    }
+                }
+
+                let mut blocks = Vec::new();
+                let mut receipts = Vec::new();
+                for (_, block) in (file.start_block..)
+                    .zip(&file.blocks)
+                    .filter(|(number, _)| *number > local_head)
+                {
+                    blocks.push(SealedBlock::from_parts_unchecked(
+                        block.decode_header::<Header>()?,
+                        block.decode_body::<BlockBody>()?,
+                        block.hash(),
+                    ));
+                    receipts.push(
+                        block
+                            .decode_receipts()?
+                            .into_iter()
+                            .map(|receipt| receipt.receipt)
+                            .collect(),
+                    );
+                }
+                Ok(ArchiveBlocks { blocks, receipts: Some(receipts) })
+            }
+            Self::Era(path) => {
+                let mut blocks = Vec::new();
+                for payload in EraReader::open(path)? {
+                    let payload = payload?;
+                    if payload.block_number() <= local_head {
+                        continue
+                    }
+                    let block_hash = payload.payload.block_hash();
+                    let block = payload.try_into_block::<TransactionSigned>()?;
+                    blocks.push(SealedBlock::new_unchecked(block, block_hash));
+                }
+                Ok(ArchiveBlocks { blocks, receipts: None })
+            }
+        }
+    }
+}
+
+/// Returns the era archives at the given path, in import order.
+fn collect_archives(path: &Path) -> eyre::Result<Vec<EraArchive>> {
+    if !path.is_dir() {
+        return EraArchive::from_path(path.to_path_buf())
+            .map(|archive| vec![archive])
+            .ok_or_else(|| eyre::eyre!("{} is not an era archive", path.display()))
+    }
+
+    let mut archives = Vec::new();
+    for entry in reth_fs_util::read_dir(path)? {
+        if let Some(archive) = EraArchive::from_path(entry?.path()) {
+            archives.push(archive);
+        }
+    }
+    // ERA1 archives precede ERA archives, era file names contain the zero padded epoch
+    archives.sort_by(|a, b| {
+        matches!(a, EraArchive::Era(_))
+            .cmp(&matches!(b, EraArchive::Era(_)))
+            .then_with(|| a.path().cmp(b.path()))
+    });
+    Ok(archives)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_import_era_command() {
+        let args: ImportEraCommand<EthereumChainSpecParser> = ImportEraCommand::parse_from([
+            "reth",
+            "--chain",
+            "sepolia",
+            "--no-state",
+            "--accumulators",
+            "accumulators.txt",
+            "era",
+        ]);
+        assert!(args.no_state);
+        assert_eq!(args.accumulators, Some(PathBuf::from("accumulators.txt")));
+        assert_eq!(args.path, PathBuf::from("era"));
+    }
+
+    #[test]
+    fn collects_archives_in_import_order() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "mainnet-01234-deadbeef.era",
+            "mainnet-00001-12345678.era1",
+            "notes.txt",
+            "mainnet-00000-5ec1ffb8.era1",
+            "mainnet-01233-deadbeef.era",
+        ] {
+            std::fs::write(dir.path().join(name), []).unwrap();
+        }
+
+        let archives = collect_archives(dir.path()).unwrap();
+        let names = archives
+            .iter()
+            .map(|archive| archive.path().file_name().unwrap().to_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "mainnet-00000-5ec1ffb8.era1",
+                "mainnet-00001-12345678.era1",
+                "mainnet-01233-deadbeef.era",
+                "mainnet-01234-deadbeef.era",
+            ]
+        );
+
+        assert!(collect_archives(&dir.path().join("notes.txt")).is_err());
+    }
+}
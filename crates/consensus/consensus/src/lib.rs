@@ -451,6 +451,15 @@ pub enum ConsensusError {
         /// The block's timestamp.
         timestamp: u64,
     },
+
+    /// Error when the withdrawals list of an L2 block body is not empty.
+    #[display("expected empty withdrawals list, got {_0} withdrawals")]
+    L2WithdrawalsNotEmpty(#[error(ignore)] usize),
+
+    /// Error when the withdrawals root of an L2 block is different from the storage root of the
+    /// `L2ToL1MessagePasser` after executing the block.
+    #[display("mismatched L2 withdrawals root: {_0}")]
+    L2WithdrawalsRootMismatch(GotExpectedBoxed<B256>),
 }
 
 impl ConsensusError {
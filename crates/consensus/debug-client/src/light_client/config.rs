@@ -0,0 +1,143 @@
+use super::ssz::compute_domain;
+use alloy_primitives::{b256, B256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of slots per epoch.
+pub const SLOTS_PER_EPOCH: u64 = 32;
+
+/// Number of epochs per sync committee period.
+pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
+
+/// Seconds per slot.
+pub const SECONDS_PER_SLOT: u64 = 12;
+
+/// `DOMAIN_SYNC_COMMITTEE` signature domain type.
+const DOMAIN_SYNC_COMMITTEE: [u8; 4] = [7, 0, 0, 0];
+
+/// Returns the sync committee period of the given slot.
+pub const fn sync_committee_period_at_slot(slot: u64) -> u64 {
+    slot / SLOTS_PER_EPOCH / EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+/// Beacon chain parameters the light client needs to verify sync committee signatures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LightClientConfig {
+    /// Unix timestamp of the beacon chain genesis.
+    pub genesis_time: u64,
+    /// Genesis validators root, part of every signature domain.
+    pub genesis_validators_root: B256,
+    /// Fork versions and their activation epochs, in activation order.
+    pub forks: Vec<(u64, [u8; 4])>,
+    /// Activation epoch of Deneb, the first fork whose light-client headers are supported.
+    pub deneb_epoch: u64,
+    /// Activation epoch of Electra, which deepens the beacon state merkle branches.
+    pub electra_epoch: u64,
+}
+
+impl LightClientConfig {
+    /// Ethereum mainnet.
+    pub fn mainnet() -> Self {
+        Self {
+            genesis_time: 1606824023,
+            genesis_validators_root: b256!(
+                "4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95"
+            ),
+            forks: vec![
+                (0, [0, 0, 0, 0]),
+                (74240, [1, 0, 0, 0]),
+                (144896, [2, 0, 0, 0]),
+                (194048, [3, 0, 0, 0]),
+                (269568, [4, 0, 0, 0]),
+                (364032, [5, 0, 0, 0]),
+            ],
+            deneb_epoch: 269568,
+            electra_epoch: 364032,
+        }
+    }
+
+    /// Sepolia testnet.
+    pub fn sepolia() -> Self {
+        Self {
+            genesis_time: 1655733600,
+            genesis_validators_root: b256!(
+                "d8ea171f3c94aea21ebc42a1ed61052acf3f9209c00e4efbaaddac09ed9b8078"
+            ),
+            forks: vec![
+                (0, [0x90, 0, 0, 0x69]),
+                (50, [0x90, 0, 0, 0x70]),
+                (100, [0x90, 0, 0, 0x71]),
+                (56832, [0x90, 0, 0, 0x72]),
+                (132608, [0x90, 0, 0, 0x73]),
+                (222464, [0x90, 0, 0, 0x74]),
+            ],
+            deneb_epoch: 132608,
+            electra_epoch: 222464,
+        }
+    }
+
+    /// Holesky testnet.
+    pub fn holesky() -> Self {
+        Self {
+            genesis_time: 1695902400,
+            genesis_validators_root: b256!(
+                "9143aa7c615a7f7115e2b6aac319c03529df8242ae705fba9df39b79c59fa8b1"
+            ),
+            forks: vec![
+                (0, [1, 1, 0x70, 0]),
+                (0, [2, 1, 0x70, 0]),
+                (0, [3, 1, 0x70, 0]),
+                (256, [4, 1, 0x70, 0]),
+                (29696, [5, 1, 0x70, 0]),
+                (115968, [6, 1, 0x70, 0]),
+            ],
+            deneb_epoch: 29696,
+            electra_epoch: 115968,
+        }
+    }
+
+    /// Returns the config for a known chain id.
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        match chain_id {
+            1 => Some(Self::mainnet()),
+            11155111 => Some(Self::sepolia()),
+            17000 => Some(Self::holesky()),
+            _ => None,
+        }
+    }
+
+    /// Returns the fork version active at the given epoch.
+    pub fn fork_version(&self, epoch: u64) -> [u8; 4] {
+        self.forks
+            .iter()
+            .rev()
+            .find(|(activation, _)| epoch >= *activation)
+            .map(|(_, version)| *version)
+            .unwrap_or_default()
+    }
+
+    /// Returns whether light-client headers at the given slot are supported.
+    pub const fn is_supported_slot(&self, slot: u64) -> bool {
+        slot / SLOTS_PER_EPOCH >= self.deneb_epoch
+    }
+
+    /// Returns whether the given slot uses the Electra beacon state layout.
+    pub const fn is_electra_slot(&self, slot: u64) -> bool {
+        slot / SLOTS_PER_EPOCH >= self.electra_epoch
+    }
+
+    /// Returns the domain sync committee members sign with at the given signature slot.
+    pub fn sync_committee_domain(&self, signature_slot: u64) -> B256 {
+        let fork_version_slot = signature_slot.max(1) - 1;
+        compute_domain(
+            DOMAIN_SYNC_COMMITTEE,
+            self.fork_version(fork_version_slot / SLOTS_PER_EPOCH),
+            self.genesis_validators_root,
+        )
+    }
+
+    /// Returns the current wall clock slot.
+    pub fn current_slot(&self) -> u64 {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        now.saturating_sub(self.genesis_time) / SECONDS_PER_SLOT
+    }
+}
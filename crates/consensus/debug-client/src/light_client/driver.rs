@@ -0,0 +1,136 @@
+use super::{LightClientHeader, LightClientProvider, LightClientSync, SECONDS_PER_SLOT};
+use crate::{block_to_execution_payload_v3, BlockProvider};
+use alloy_rpc_types_eth::Block;
+use reth_node_api::EngineTypes;
+use reth_rpc_builder::auth::AuthServerHandle;
+use reth_tracing::tracing::{debug, warn};
+use std::time::Duration;
+
+/// Consensus client that only forwards execution payloads attested by the beacon sync committee.
+///
+/// Sync committee signatures are verified through a [`LightClientSync`], while the full blocks
+/// are fetched from a [`BlockProvider`] and checked against the attested execution payload
+/// headers before they are sent to the execution client.
+#[derive(Debug)]
+pub struct LightClientConsensusClient<L, P> {
+    /// Handle to execution client.
+    auth_server: AuthServerHandle,
+    /// Verified light-client state.
+    sync: LightClientSync<L>,
+    /// Provider to get full blocks from.
+    block_provider: P,
+    /// Interval at which the light client polls for updates.
+    interval: Duration,
+}
+
+impl<L: LightClientProvider, P: BlockProvider> LightClientConsensusClient<L, P> {
+    /// Create a new light-client consensus client with the given handle to execution client,
+    /// light-client sync and block provider.
+    pub const fn new(
+        auth_server: AuthServerHandle,
+        sync: LightClientSync<L>,
+        block_provider: P,
+    ) -> Self {
+        Self { auth_server, sync, block_provider, interval: Duration::from_secs(SECONDS_PER_SLOT) }
+    }
+
+    /// Sets the interval at which the client polls for light-client updates.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Spawn the client to start sending FCUs and new payloads for attested blocks.
+    pub async fn run<T: EngineTypes>(mut self) {
+        let execution_client = self.auth_server.http_client();
+        let mut interval = tokio::time::interval(self.interval);
+        let mut last_head = None;
+
+        loop {
+            interval.tick().await;
+
+            let current_slot = self.sync.config().current_slot();
+            let store = match self.sync.sync(current_slot).await {
+                Ok(store) => store,
+                Err(err) => {
+                    warn!(target: "consensus::debug-client", %err, "failed to sync light client");
+                    continue
+                }
+            };
+            let head = store.optimistic_header().clone();
+            let finalized_block_hash = store.finalized_execution().block_hash;
+            let block_hash = head.execution.block_hash;
+            let block_number = head.execution.block_number;
+            if last_head == Some(block_hash) {
+                continue
+            }
+
+            let block = match self.block_provider.get_block(block_number).await {
+                Ok(block) => block,
+                Err(err) => {
+                    warn!(target: "consensus::debug-client", %err, %block_number, "failed to fetch attested block");
+                    continue
+                }
+            };
+            if let Err(err) = ensure_attested(&head, &block) {
+                warn!(target: "consensus::debug-client", %err, %block_hash, %block_number, "rejecting unattested block");
+                continue
+            }
+            debug!(target: "consensus::debug-client", %block_hash, %block_number, slot = head.beacon.slot, "forwarding attested block");
+
+            let payload = block_to_execution_payload_v3(block);
+            let _ = reth_rpc_api::EngineApiClient::<T>::new_payload_v3(
+                &execution_client,
+                payload.execution_payload_v3,
+                payload.versioned_hashes,
+                payload.parent_beacon_block_root,
+            )
+            .await
+            .inspect_err(|err| {
+                warn!(target: "consensus::debug-client", %err, %block_hash, %block_number, "failed to submit new payload to execution client");
+            });
+
+            // The light client only learns finalized checkpoints, so they double as safe blocks.
+            let state = alloy_rpc_types_engine::ForkchoiceState {
+                head_block_hash: block_hash,
+                safe_block_hash: finalized_block_hash,
+                finalized_block_hash,
+            };
+            let _ = reth_rpc_api::EngineApiClient::<T>::fork_choice_updated_v3(
+                &execution_client,
+                state,
+                None,
+            )
+            .await
+            .inspect_err(|err| {
+                warn!(target: "consensus::debug-client", %err, ?state, "failed to submit fork choice update to execution client");
+            });
+
+            last_head = Some(block_hash);
+        }
+    }
+}
+
+/// Ensures that the block fetched from the block provider is the one committed to by the attested
+/// header.
+///
+/// The execution client recomputes the block hash from the payload, so matching the hash binds
+/// the forwarded payload to the attested header.
+fn ensure_attested(header: &LightClientHeader, block: &Block) -> eyre::Result<()> {
+    if block.header.hash != header.execution.block_hash {
+        eyre::bail!(
+            "block hash {} does not match attested hash {}",
+            block.header.hash,
+            header.execution.block_hash
+        )
+    }
+    let parent_beacon_block_root = block.header.parent_beacon_block_root;
+    if parent_beacon_block_root != Some(header.beacon.parent_root) {
+        eyre::bail!(
+            "parent beacon block root {:?} does not match attested root {}",
+            parent_beacon_block_root,
+            header.beacon.parent_root
+        )
+    }
+    Ok(())
+}
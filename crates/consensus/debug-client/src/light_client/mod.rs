@@ -0,0 +1,127 @@
+//! Beacon light-client verification.
+//!
+//! Follows the chain by verifying sync committee signatures over beacon block headers, starting
+//! from a trusted checkpoint, so that only execution payloads attested by the sync committee are
+//! forwarded to the execution client.
+
+mod config;
+mod driver;
+mod ssz;
+mod store;
+mod types;
+
+pub use config::{
+    sync_committee_period_at_slot, LightClientConfig, EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
+    SECONDS_PER_SLOT, SLOTS_PER_EPOCH,
+};
+pub use driver::LightClientConsensusClient;
+pub use ssz::{branch_root, compute_domain, is_valid_merkle_branch, signing_root};
+pub use store::*;
+pub use types::*;
+
+use alloy_primitives::B256;
+use reth_tracing::tracing::warn;
+use std::future::Future;
+
+/// Maximum number of updates a beacon node serves per `light_client/updates` request.
+const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
+
+/// Supplies the light client with bootstraps and updates, e.g. from a beacon API.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait LightClientProvider: Send + Sync + 'static {
+    /// Get the bootstrap for the block with the given root.
+    fn bootstrap(
+        &self,
+        block_root: B256,
+    ) -> impl Future<Output = eyre::Result<LightClientBootstrap>> + Send;
+
+    /// Get the best updates for `count` sync committee periods starting at `start_period`.
+    fn updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> impl Future<Output = eyre::Result<Vec<LightClientUpdate>>> + Send;
+
+    /// Get the latest finality update.
+    fn finality_update(
+        &self,
+    ) -> impl Future<Output = eyre::Result<LightClientFinalityUpdate>> + Send;
+}
+
+/// Keeps a [`LightClientStore`] in sync with the updates served by a [`LightClientProvider`].
+#[derive(Debug)]
+pub struct LightClientSync<L> {
+    provider: L,
+    config: LightClientConfig,
+    checkpoint: B256,
+    store: Option<LightClientStore>,
+}
+
+impl<L: LightClientProvider> LightClientSync<L> {
+    /// Create a new light-client sync that bootstraps from the trusted beacon block root
+    /// `checkpoint`.
+    pub const fn new(provider: L, config: LightClientConfig, checkpoint: B256) -> Self {
+        Self { provider, config, checkpoint, store: None }
+    }
+
+    /// Returns the chain parameters.
+    pub const fn config(&self) -> &LightClientConfig {
+        &self.config
+    }
+
+    /// Returns the store, if bootstrapped.
+    pub const fn store(&self) -> Option<&LightClientStore> {
+        self.store.as_ref()
+    }
+
+    /// Bootstraps the store if necessary, catches up on sync committee periods and applies the
+    /// latest finality update.
+    ///
+    /// A finality update that can't be fetched or applied is logged and skipped, the store is
+    /// still returned with the progress of the committee updates.
+    pub async fn sync(&mut self, current_slot: u64) -> eyre::Result<&LightClientStore> {
+        let Self { provider, config, checkpoint, store } = self;
+        let store = match store {
+            Some(store) => store,
+            None => {
+                let bootstrap = provider.bootstrap(*checkpoint).await?;
+                store.insert(LightClientStore::bootstrap(config.clone(), *checkpoint, bootstrap)?)
+            }
+        };
+
+        let current_period = sync_committee_period_at_slot(current_slot);
+        let store_period = store.finalized_period();
+        if !store.is_next_sync_committee_known() || current_period > store_period {
+            let count = (current_period.saturating_sub(store_period) + 1)
+                .min(MAX_REQUEST_LIGHT_CLIENT_UPDATES);
+            for update in provider.updates(store_period, count).await? {
+                process_update(store, update, current_slot)?;
+            }
+        }
+
+        match provider.finality_update().await {
+            Ok(update) => {
+                if let Err(err) = process_update(store, update.into(), current_slot) {
+                    warn!(target: "consensus::debug-client", %err, "rejecting light client finality update");
+                }
+            }
+            Err(err) => {
+                warn!(target: "consensus::debug-client", %err, "failed to fetch light client finality update")
+            }
+        }
+
+        Ok(store)
+    }
+}
+
+/// Applies the update, ignoring updates the store has already moved past.
+fn process_update(
+    store: &mut LightClientStore,
+    update: LightClientUpdate,
+    current_slot: u64,
+) -> Result<(), LightClientError> {
+    match store.process_update(update, current_slot) {
+        Err(LightClientError::StaleUpdate(_)) => Ok(()),
+        res => res,
+    }
+}
@@ -0,0 +1,159 @@
+//! SSZ `hash_tree_root` for the containers the light client has to verify.
+//!
+//! Fields are serialized with [`ssz::Encode`], this only merkleizes the serialized fields.
+
+use super::types::{BeaconBlockHeader, ExecutionPayloadHeader, SyncCommittee};
+use alloy_primitives::{Bytes, B256};
+use sha2::{Digest, Sha256};
+use ssz::Encode;
+
+/// Hashes two 32 byte chunks together.
+fn hash_pair(left: &B256, right: &B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+/// Merkleizes the given chunks, padding them with zero chunks to the next power of two.
+fn merkleize(chunks: &[B256]) -> B256 {
+    let mut layer = chunks.to_vec();
+    layer.resize(chunks.len().next_power_of_two().max(1), B256::ZERO);
+    while layer.len() > 1 {
+        layer = layer.chunks_exact(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+    }
+    layer[0]
+}
+
+/// Packs serialized bytes into right-padded chunks.
+fn pack(bytes: &[u8]) -> Vec<B256> {
+    bytes
+        .chunks(32)
+        .map(|bytes| {
+            let mut chunk = B256::ZERO;
+            chunk[..bytes.len()].copy_from_slice(bytes);
+            chunk
+        })
+        .collect()
+}
+
+/// Root of a basic value or a fixed size byte vector.
+fn value_root<T: Encode>(value: &T) -> B256 {
+    merkleize(&pack(&value.as_ssz_bytes()))
+}
+
+/// Root of a `ByteList[32]`.
+///
+/// Oversized values are truncated; the length mix-in still makes the root unprovable.
+fn short_byte_list_root(bytes: &Bytes) -> B256 {
+    let chunk = pack(&bytes[..bytes.len().min(32)]).first().copied().unwrap_or_default();
+    hash_pair(&chunk, &value_root(&(bytes.len() as u64)))
+}
+
+/// Computes the root of a merkle tree from a leaf and its branch, where `index` is the position of
+/// the leaf at depth `branch.len()`.
+pub fn branch_root(leaf: B256, branch: &[B256], index: u64) -> B256 {
+    branch.iter().enumerate().fold(leaf, |node, (depth, sibling)| {
+        if (index >> depth) & 1 == 1 {
+            hash_pair(sibling, &node)
+        } else {
+            hash_pair(&node, sibling)
+        }
+    })
+}
+
+/// Returns whether `branch` proves `leaf` at generalized index `gindex` under `root`.
+pub fn is_valid_merkle_branch(leaf: B256, branch: &[B256], gindex: u64, root: B256) -> bool {
+    let depth = gindex.ilog2() as usize;
+    branch.len() == depth && branch_root(leaf, branch, gindex - (1 << depth)) == root
+}
+
+/// Computes `compute_signing_root` of an object root with the given domain.
+pub fn signing_root(object_root: B256, domain: B256) -> B256 {
+    hash_pair(&object_root, &domain)
+}
+
+/// Computes `compute_domain` for the given domain type, fork version and genesis validators root.
+pub fn compute_domain(
+    domain_type: [u8; 4],
+    fork_version: [u8; 4],
+    genesis_validators_root: B256,
+) -> B256 {
+    let fork_data_root = hash_pair(&value_root(&fork_version), &genesis_validators_root);
+    let mut domain = B256::ZERO;
+    domain[..4].copy_from_slice(&domain_type);
+    domain[4..].copy_from_slice(&fork_data_root[..28]);
+    domain
+}
+
+impl BeaconBlockHeader {
+    /// Computes the SSZ `hash_tree_root` of the header.
+    pub fn tree_hash_root(&self) -> B256 {
+        merkleize(&[
+            value_root(&self.slot),
+            value_root(&self.proposer_index),
+            value_root(&self.parent_root),
+            value_root(&self.state_root),
+            value_root(&self.body_root),
+        ])
+    }
+}
+
+impl ExecutionPayloadHeader {
+    /// Computes the SSZ `hash_tree_root` of the header.
+    pub fn tree_hash_root(&self) -> B256 {
+        merkleize(&[
+            value_root(&self.parent_hash),
+            value_root(&self.fee_recipient),
+            value_root(&self.state_root),
+            value_root(&self.receipts_root),
+            value_root(&self.logs_bloom),
+            value_root(&self.prev_randao),
+            value_root(&self.block_number),
+            value_root(&self.gas_limit),
+            value_root(&self.gas_used),
+            value_root(&self.timestamp),
+            short_byte_list_root(&self.extra_data),
+            value_root(&self.base_fee_per_gas),
+            value_root(&self.block_hash),
+            value_root(&self.transactions_root),
+            value_root(&self.withdrawals_root),
+            value_root(&self.blob_gas_used),
+            value_root(&self.excess_blob_gas),
+        ])
+    }
+}
+
+impl SyncCommittee {
+    /// Computes the SSZ `hash_tree_root` of the committee.
+    pub fn tree_hash_root(&self) -> B256 {
+        let pubkeys = merkleize(&self.pubkeys.iter().map(value_root).collect::<Vec<_>>());
+        hash_pair(&pubkeys, &value_root(&self.aggregate_pubkey))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn zero_header_root() {
+        // Root of eight zero chunks.
+        assert_eq!(
+            BeaconBlockHeader::default().tree_hash_root(),
+            b256!("c78009fdf07fc56a11f122370658a353aaa542ed63e44c4bc15ff4cd105ab33c")
+        );
+    }
+
+    #[test]
+    fn merkle_branch_roundtrip() {
+        let leaf = B256::repeat_byte(1);
+        let branch = vec![B256::repeat_byte(2), B256::repeat_byte(3), B256::repeat_byte(4)];
+        // gindex 13 is index 5 at depth 3.
+        let root = branch_root(leaf, &branch, 5);
+        assert!(is_valid_merkle_branch(leaf, &branch, 13, root));
+        assert!(!is_valid_merkle_branch(leaf, &branch, 12, root));
+        assert!(!is_valid_merkle_branch(leaf, &branch[..2], 13, root));
+    }
+}
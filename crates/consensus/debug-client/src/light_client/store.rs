@@ -0,0 +1,381 @@
+//! The light-client store, see the `LightClientStore` of the sync protocol.
+//!
+//! The store tracks the finalized and optimistic headers and the current and next sync
+//! committees, and only advances on updates signed by a supermajority of the sync committee.
+
+use super::{
+    config::{sync_committee_period_at_slot, LightClientConfig},
+    ssz::{is_valid_merkle_branch, signing_root},
+    types::{
+        ExecutionPayloadHeader, LightClientBootstrap, LightClientHeader, LightClientUpdate,
+        SyncCommittee, SYNC_COMMITTEE_SIZE,
+    },
+};
+use alloy_primitives::B256;
+use blst::{
+    min_pk::{PublicKey, Signature},
+    BLST_ERROR,
+};
+
+/// Generalized index of the execution payload in the beacon block body.
+pub const EXECUTION_PAYLOAD_GINDEX: u64 = 25;
+/// Generalized index of the finalized checkpoint root in the Deneb beacon state.
+pub const FINALIZED_ROOT_GINDEX: u64 = 105;
+/// Generalized index of the current sync committee in the Deneb beacon state.
+pub const CURRENT_SYNC_COMMITTEE_GINDEX: u64 = 54;
+/// Generalized index of the next sync committee in the Deneb beacon state.
+pub const NEXT_SYNC_COMMITTEE_GINDEX: u64 = 55;
+/// Generalized index of the finalized checkpoint root in the Electra beacon state.
+pub const FINALIZED_ROOT_GINDEX_ELECTRA: u64 = 169;
+/// Generalized index of the current sync committee in the Electra beacon state.
+pub const CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA: u64 = 86;
+/// Generalized index of the next sync committee in the Electra beacon state.
+pub const NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA: u64 = 87;
+
+/// Minimum number of participants for an update to be considered at all.
+const MIN_SYNC_COMMITTEE_PARTICIPANTS: usize = 1;
+
+/// BLS signature ciphersuite used by the beacon chain.
+const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Errors returned when verifying light-client data.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum LightClientError {
+    /// The bootstrap header does not match the trusted checkpoint.
+    #[error("bootstrap header root {got} does not match trusted checkpoint {expected}")]
+    CheckpointMismatch {
+        /// The trusted checkpoint root.
+        expected: B256,
+        /// Root of the header served by the provider.
+        got: B256,
+    },
+    /// Header is from a fork before Deneb.
+    #[error("light-client header at slot {0} predates deneb")]
+    UnsupportedFork(u64),
+    /// The execution payload header is not proven by the beacon block body.
+    #[error("invalid execution branch for header at slot {0}")]
+    InvalidExecutionBranch(u64),
+    /// The sync committee is not proven by the beacon state.
+    #[error("invalid sync committee branch")]
+    InvalidSyncCommitteeBranch,
+    /// The finalized header is not proven by the attested beacon state.
+    #[error("invalid finality branch")]
+    InvalidFinalityBranch,
+    /// A sync committee has the wrong number of members or an invalid key.
+    #[error("malformed sync committee")]
+    MalformedSyncCommittee,
+    /// Not enough sync committee participants.
+    #[error("insufficient sync committee participation: {0}")]
+    InsufficientParticipation(usize),
+    /// The update slots are out of order or in the future.
+    #[error("invalid update slots: signature {signature_slot}, attested {attested_slot}")]
+    InvalidSlots {
+        /// Signature slot of the update.
+        signature_slot: u64,
+        /// Attested header slot of the update.
+        attested_slot: u64,
+    },
+    /// The update was signed in a period the store has no committee for.
+    #[error("update signed in period {0} without a known sync committee")]
+    UnknownSyncCommittee(u64),
+    /// The update does not advance the store.
+    #[error("stale update at slot {0}")]
+    StaleUpdate(u64),
+    /// The update carries a next sync committee that contradicts the known one.
+    #[error("next sync committee mismatch")]
+    NextSyncCommitteeMismatch,
+    /// The sync committee signature does not verify.
+    #[error("invalid sync committee signature: {0:?}")]
+    InvalidSignature(BLST_ERROR),
+}
+
+/// Sync committee together with its decoded public keys.
+#[derive(Debug, Clone)]
+struct VerifiedSyncCommittee {
+    committee: SyncCommittee,
+    pubkeys: Vec<PublicKey>,
+}
+
+impl VerifiedSyncCommittee {
+    fn new(committee: SyncCommittee) -> Result<Self, LightClientError> {
+        if committee.pubkeys.len() != SYNC_COMMITTEE_SIZE {
+            return Err(LightClientError::MalformedSyncCommittee)
+        }
+        let pubkeys = committee
+            .pubkeys
+            .iter()
+            .map(|pubkey| PublicKey::key_validate(pubkey.as_slice()))
+            .collect::<Result<_, _>>()
+            .map_err(|_| LightClientError::MalformedSyncCommittee)?;
+        Ok(Self { committee, pubkeys })
+    }
+}
+
+/// Light-client store following the Altair sync protocol.
+///
+/// Only headers from Deneb onwards are supported, so every header commits to an execution payload
+/// header that is proven against its beacon block body.
+#[derive(Debug, Clone)]
+pub struct LightClientStore {
+    config: LightClientConfig,
+    finalized_header: LightClientHeader,
+    optimistic_header: LightClientHeader,
+    current_sync_committee: VerifiedSyncCommittee,
+    next_sync_committee: Option<VerifiedSyncCommittee>,
+}
+
+impl LightClientStore {
+    /// Initializes the store from a bootstrap for the trusted checkpoint `trusted_block_root`.
+    pub fn bootstrap(
+        config: LightClientConfig,
+        trusted_block_root: B256,
+        bootstrap: LightClientBootstrap,
+    ) -> Result<Self, LightClientError> {
+        validate_header(&config, &bootstrap.header)?;
+        let root = bootstrap.header.beacon.tree_hash_root();
+        if root != trusted_block_root {
+            return Err(LightClientError::CheckpointMismatch {
+                expected: trusted_block_root,
+                got: root,
+            })
+        }
+
+        let slot = bootstrap.header.beacon.slot;
+        let gindex = if config.is_electra_slot(slot) {
+            CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA
+        } else {
+            CURRENT_SYNC_COMMITTEE_GINDEX
+        };
+        if !is_valid_merkle_branch(
+            bootstrap.current_sync_committee.tree_hash_root(),
+            &bootstrap.current_sync_committee_branch,
+            gindex,
+            bootstrap.header.beacon.state_root,
+        ) {
+            return Err(LightClientError::InvalidSyncCommitteeBranch)
+        }
+
+        Ok(Self {
+            config,
+            optimistic_header: bootstrap.header.clone(),
+            finalized_header: bootstrap.header,
+            current_sync_committee: VerifiedSyncCommittee::new(bootstrap.current_sync_committee)?,
+            next_sync_committee: None,
+        })
+    }
+
+    /// Returns the chain parameters of the store.
+    pub const fn config(&self) -> &LightClientConfig {
+        &self.config
+    }
+
+    /// Returns the latest finalized header.
+    pub const fn finalized_header(&self) -> &LightClientHeader {
+        &self.finalized_header
+    }
+
+    /// Returns the latest header attested to by a majority of the sync committee.
+    pub const fn optimistic_header(&self) -> &LightClientHeader {
+        &self.optimistic_header
+    }
+
+    /// Returns the execution payload header of the latest finalized block.
+    pub const fn finalized_execution(&self) -> &ExecutionPayloadHeader {
+        &self.finalized_header.execution
+    }
+
+    /// Returns the execution payload header of the latest attested block.
+    pub const fn optimistic_execution(&self) -> &ExecutionPayloadHeader {
+        &self.optimistic_header.execution
+    }
+
+    /// Returns the sync committee period of the finalized header.
+    pub const fn finalized_period(&self) -> u64 {
+        sync_committee_period_at_slot(self.finalized_header.beacon.slot)
+    }
+
+    /// Returns whether the next sync committee is known.
+    pub const fn is_next_sync_committee_known(&self) -> bool {
+        self.next_sync_committee.is_some()
+    }
+
+    /// Verifies the update and applies it to the store.
+    ///
+    /// Finalized headers and sync committees are only advanced with a supermajority of the
+    /// committee, the optimistic header with a simple majority.
+    pub fn process_update(
+        &mut self,
+        update: LightClientUpdate,
+        current_slot: u64,
+    ) -> Result<(), LightClientError> {
+        let participants = update.sync_aggregate.num_participants();
+        if participants < MIN_SYNC_COMMITTEE_PARTICIPANTS {
+            return Err(LightClientError::InsufficientParticipation(participants))
+        }
+        validate_header(&self.config, &update.attested_header)?;
+
+        let attested_slot = update.attested_header.beacon.slot;
+        let finalized_header = update
+            .finalized_header
+            .as_ref()
+            .filter(|_| is_non_empty_branch(&update.finality_branch));
+        let finalized_slot = finalized_header.map(|header| header.beacon.slot).unwrap_or_default();
+        if !(current_slot >= update.signature_slot &&
+            update.signature_slot > attested_slot &&
+            attested_slot >= finalized_slot)
+        {
+            return Err(LightClientError::InvalidSlots {
+                signature_slot: update.signature_slot,
+                attested_slot,
+            })
+        }
+
+        let store_period = self.finalized_period();
+        let signature_period = sync_committee_period_at_slot(update.signature_slot);
+        let sync_committee = if signature_period == store_period {
+            &self.current_sync_committee
+        } else if signature_period == store_period + 1 {
+            self.next_sync_committee
+                .as_ref()
+                .ok_or(LightClientError::UnknownSyncCommittee(signature_period))?
+        } else {
+            return Err(LightClientError::UnknownSyncCommittee(signature_period))
+        };
+
+        let next_sync_committee = update
+            .next_sync_committee
+            .as_ref()
+            .filter(|_| is_non_empty_branch(&update.next_sync_committee_branch));
+        let attested_period = sync_committee_period_at_slot(attested_slot);
+        let has_next_sync_committee = self.next_sync_committee.is_none() &&
+            next_sync_committee.is_some() &&
+            attested_period == store_period;
+        if attested_slot <= self.finalized_header.beacon.slot && !has_next_sync_committee {
+            return Err(LightClientError::StaleUpdate(attested_slot))
+        }
+
+        let electra = self.config.is_electra_slot(attested_slot);
+        let state_root = update.attested_header.beacon.state_root;
+        if let Some(finalized_header) = finalized_header {
+            validate_header(&self.config, finalized_header)?;
+            let gindex =
+                if electra { FINALIZED_ROOT_GINDEX_ELECTRA } else { FINALIZED_ROOT_GINDEX };
+            if !is_valid_merkle_branch(
+                finalized_header.beacon.tree_hash_root(),
+                &update.finality_branch,
+                gindex,
+                state_root,
+            ) {
+                return Err(LightClientError::InvalidFinalityBranch)
+            }
+        }
+        if let Some(next_sync_committee) = next_sync_committee {
+            if let Some(known) =
+                self.next_sync_committee.as_ref().filter(|_| attested_period == store_period)
+            {
+                if known.committee != *next_sync_committee {
+                    return Err(LightClientError::NextSyncCommitteeMismatch)
+                }
+            }
+            let gindex = if electra {
+                NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA
+            } else {
+                NEXT_SYNC_COMMITTEE_GINDEX
+            };
+            if !is_valid_merkle_branch(
+                next_sync_committee.tree_hash_root(),
+                &update.next_sync_committee_branch,
+                gindex,
+                state_root,
+            ) {
+                return Err(LightClientError::InvalidSyncCommitteeBranch)
+            }
+        }
+
+        let participant_pubkeys = sync_committee
+            .pubkeys
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| update.sync_aggregate.is_participant(*index))
+            .map(|(_, pubkey)| pubkey)
+            .collect::<Vec<_>>();
+        let signature =
+            Signature::from_bytes(update.sync_aggregate.sync_committee_signature.as_slice())
+                .map_err(LightClientError::InvalidSignature)?;
+        let message = signing_root(
+            update.attested_header.beacon.tree_hash_root(),
+            self.config.sync_committee_domain(update.signature_slot),
+        );
+        match signature.fast_aggregate_verify(
+            true,
+            message.as_slice(),
+            BLS_DST,
+            &participant_pubkeys,
+        ) {
+            BLST_ERROR::BLST_SUCCESS => {}
+            err => return Err(LightClientError::InvalidSignature(err)),
+        }
+
+        // Apply the update.
+        if participants * 2 > SYNC_COMMITTEE_SIZE &&
+            attested_slot > self.optimistic_header.beacon.slot
+        {
+            self.optimistic_header = update.attested_header.clone();
+        }
+        let Some(finalized_header) = finalized_header else { return Ok(()) };
+        if participants * 3 < SYNC_COMMITTEE_SIZE * 2 {
+            return Ok(())
+        }
+        let finalized_period = sync_committee_period_at_slot(finalized_slot);
+        let has_finalized_next_sync_committee =
+            has_next_sync_committee && finalized_period == attested_period;
+        if finalized_slot <= self.finalized_header.beacon.slot && !has_finalized_next_sync_committee
+        {
+            return Ok(())
+        }
+        let next_sync_committee =
+            next_sync_committee.cloned().map(VerifiedSyncCommittee::new).transpose()?;
+        if self.next_sync_committee.is_none() {
+            if finalized_period != store_period {
+                return Err(LightClientError::UnknownSyncCommittee(finalized_period))
+            }
+            self.next_sync_committee = next_sync_committee;
+        } else if finalized_period == store_period + 1 {
+            self.current_sync_committee =
+                self.next_sync_committee.take().expect("next sync committee is known");
+            self.next_sync_committee = next_sync_committee;
+        }
+        if finalized_slot > self.finalized_header.beacon.slot {
+            self.finalized_header = finalized_header.clone();
+            if finalized_slot > self.optimistic_header.beacon.slot {
+                self.optimistic_header = finalized_header.clone();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks that the header is supported and its execution payload header is proven.
+fn validate_header(
+    config: &LightClientConfig,
+    header: &LightClientHeader,
+) -> Result<(), LightClientError> {
+    let slot = header.beacon.slot;
+    if !config.is_supported_slot(slot) {
+        return Err(LightClientError::UnsupportedFork(slot))
+    }
+    if !is_valid_merkle_branch(
+        header.execution.tree_hash_root(),
+        &header.execution_branch,
+        EXECUTION_PAYLOAD_GINDEX,
+        header.beacon.body_root,
+    ) {
+        return Err(LightClientError::InvalidExecutionBranch(slot))
+    }
+    Ok(())
+}
+
+/// Beacon nodes serve absent branches either empty or zero-filled.
+fn is_non_empty_branch(branch: &[B256]) -> bool {
+    branch.iter().any(|node| !node.is_zero())
+}
@@ -0,0 +1,217 @@
+//! Beacon API light-client containers.
+//!
+//! See <https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md>
+//! and the `/eth/v1/beacon/light_client/*` endpoints of the beacon API.
+
+use alloy_primitives::{Address, Bloom, Bytes, FixedBytes, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// Number of validators in a sync committee.
+pub const SYNC_COMMITTEE_SIZE: usize = 512;
+
+/// Compressed BLS public key.
+pub type BlsPublicKey = FixedBytes<48>;
+
+/// Compressed BLS signature.
+pub type BlsSignature = FixedBytes<96>;
+
+/// Beacon API response envelope carrying the fork name of the payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionedResponse<T> {
+    /// Fork name of the object, e.g. `deneb` or `electra`.
+    pub version: String,
+    /// The response payload.
+    pub data: T,
+}
+
+/// Header of a beacon block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconBlockHeader {
+    /// Slot of the block.
+    #[serde(with = "quoted")]
+    pub slot: u64,
+    /// Index of the block proposer.
+    #[serde(with = "quoted")]
+    pub proposer_index: u64,
+    /// Root of the parent beacon block.
+    pub parent_root: B256,
+    /// Root of the beacon state after this block.
+    pub state_root: B256,
+    /// Root of the beacon block body.
+    pub body_root: B256,
+}
+
+/// Execution payload header as included in a light-client header (Deneb layout).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionPayloadHeader {
+    /// Hash of the parent execution block.
+    pub parent_hash: B256,
+    /// Fee recipient of the execution block.
+    pub fee_recipient: Address,
+    /// State root of the execution block.
+    pub state_root: B256,
+    /// Receipts root of the execution block.
+    pub receipts_root: B256,
+    /// Logs bloom of the execution block.
+    pub logs_bloom: Bloom,
+    /// `prevRandao` of the execution block.
+    pub prev_randao: B256,
+    /// Number of the execution block.
+    #[serde(with = "quoted")]
+    pub block_number: u64,
+    /// Gas limit of the execution block.
+    #[serde(with = "quoted")]
+    pub gas_limit: u64,
+    /// Gas used by the execution block.
+    #[serde(with = "quoted")]
+    pub gas_used: u64,
+    /// Timestamp of the execution block.
+    #[serde(with = "quoted")]
+    pub timestamp: u64,
+    /// Extra data of the execution block.
+    pub extra_data: Bytes,
+    /// Base fee of the execution block.
+    #[serde(with = "quoted")]
+    pub base_fee_per_gas: U256,
+    /// Hash of the execution block.
+    pub block_hash: B256,
+    /// SSZ root of the transactions list.
+    pub transactions_root: B256,
+    /// SSZ root of the withdrawals list.
+    pub withdrawals_root: B256,
+    /// Blob gas used by the execution block.
+    #[serde(with = "quoted")]
+    pub blob_gas_used: u64,
+    /// Excess blob gas of the execution block.
+    #[serde(with = "quoted")]
+    pub excess_blob_gas: u64,
+}
+
+/// Beacon block header together with the execution payload header it commits to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientHeader {
+    /// The beacon block header.
+    pub beacon: BeaconBlockHeader,
+    /// The execution payload header of the block.
+    pub execution: ExecutionPayloadHeader,
+    /// Merkle branch proving `execution` against `beacon.body_root`.
+    pub execution_branch: Vec<B256>,
+}
+
+/// A sync committee.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncCommittee {
+    /// Public keys of the committee members, [`SYNC_COMMITTEE_SIZE`] entries.
+    pub pubkeys: Vec<BlsPublicKey>,
+    /// Aggregate of all member public keys.
+    pub aggregate_pubkey: BlsPublicKey,
+}
+
+/// Sync committee participation bits and aggregate signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncAggregate {
+    /// Participation bitvector, one bit per committee member.
+    pub sync_committee_bits: FixedBytes<{ SYNC_COMMITTEE_SIZE / 8 }>,
+    /// Aggregate signature of the participants.
+    pub sync_committee_signature: BlsSignature,
+}
+
+impl SyncAggregate {
+    /// Returns the number of participating committee members.
+    pub fn num_participants(&self) -> usize {
+        self.sync_committee_bits.iter().map(|byte| byte.count_ones() as usize).sum()
+    }
+
+    /// Returns whether the committee member at `index` participated.
+    pub fn is_participant(&self, index: usize) -> bool {
+        self.sync_committee_bits[index / 8] & (1 << (index % 8)) != 0
+    }
+}
+
+/// Response of `/eth/v1/beacon/light_client/bootstrap/{block_root}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientBootstrap {
+    /// Header of the trusted checkpoint block.
+    pub header: LightClientHeader,
+    /// Sync committee of the checkpoint period.
+    pub current_sync_committee: SyncCommittee,
+    /// Merkle branch proving the committee against `header.beacon.state_root`.
+    pub current_sync_committee_branch: Vec<B256>,
+}
+
+/// Response item of `/eth/v1/beacon/light_client/updates`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientUpdate {
+    /// Header attested to by the sync committee.
+    pub attested_header: LightClientHeader,
+    /// Next sync committee, if this update carries one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_sync_committee: Option<SyncCommittee>,
+    /// Merkle branch proving `next_sync_committee` against the attested state root.
+    #[serde(default)]
+    pub next_sync_committee_branch: Vec<B256>,
+    /// Finalized header, if this update carries one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finalized_header: Option<LightClientHeader>,
+    /// Merkle branch proving `finalized_header` against the attested state root.
+    #[serde(default)]
+    pub finality_branch: Vec<B256>,
+    /// Sync committee signature over `attested_header`.
+    pub sync_aggregate: SyncAggregate,
+    /// Slot at which the signature was produced.
+    #[serde(with = "quoted")]
+    pub signature_slot: u64,
+}
+
+/// Response of `/eth/v1/beacon/light_client/finality_update`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientFinalityUpdate {
+    /// Header attested to by the sync committee.
+    pub attested_header: LightClientHeader,
+    /// Finalized header.
+    pub finalized_header: LightClientHeader,
+    /// Merkle branch proving `finalized_header` against the attested state root.
+    pub finality_branch: Vec<B256>,
+    /// Sync committee signature over `attested_header`.
+    pub sync_aggregate: SyncAggregate,
+    /// Slot at which the signature was produced.
+    #[serde(with = "quoted")]
+    pub signature_slot: u64,
+}
+
+impl From<LightClientFinalityUpdate> for LightClientUpdate {
+    fn from(update: LightClientFinalityUpdate) -> Self {
+        Self {
+            attested_header: update.attested_header,
+            next_sync_committee: None,
+            next_sync_committee_branch: Vec::new(),
+            finalized_header: Some(update.finalized_header),
+            finality_branch: update.finality_branch,
+            sync_aggregate: update.sync_aggregate,
+            signature_slot: update.signature_slot,
+        }
+    }
+}
+
+/// The beacon API encodes integers as decimal strings.
+mod quoted {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::{fmt::Display, str::FromStr};
+
+    pub(super) fn serialize<T: Display, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub(super) fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{
+    LightClientBootstrap, LightClientFinalityUpdate, LightClientProvider, LightClientUpdate,
+    VersionedResponse,
+};
+use alloy_primitives::B256;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+
+/// Light-client provider that fetches data from the light-client endpoints of a beacon API.
+#[derive(Debug, Clone)]
+pub struct BeaconApiLightClientProvider {
+    http_client: Client,
+    base_url: String,
+}
+
+impl BeaconApiLightClientProvider {
+    /// Create a new beacon API light-client provider with the given base URL.
+    pub fn new(base_url: String) -> Self {
+        Self { http_client: Client::new(), base_url: base_url.trim_end_matches('/').to_string() }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str, query: &[(&str, u64)]) -> eyre::Result<T> {
+        Ok(self
+            .http_client
+            .get(format!("{}{path}", self.base_url))
+            .query(query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+}
+
+impl LightClientProvider for BeaconApiLightClientProvider {
+    async fn bootstrap(&self, block_root: B256) -> eyre::Result<LightClientBootstrap> {
+        let response: VersionedResponse<LightClientBootstrap> =
+            self.get(&format!("/eth/v1/beacon/light_client/bootstrap/{block_root}"), &[]).await?;
+        Ok(response.data)
+    }
+
+    async fn updates(&self, start_period: u64, count: u64) -> eyre::Result<Vec<LightClientUpdate>> {
+        let response: Vec<VersionedResponse<LightClientUpdate>> = self
+            .get(
+                "/eth/v1/beacon/light_client/updates",
+                &[("start_period", start_period), ("count", count)],
+            )
+            .await?;
+        Ok(response.into_iter().map(|update| update.data).collect())
+    }
+
+    async fn finality_update(&self) -> eyre::Result<LightClientFinalityUpdate> {
+        let response: VersionedResponse<LightClientFinalityUpdate> =
+            self.get("/eth/v1/beacon/light_client/finality_update", &[]).await?;
+        Ok(response.data)
+    }
+}
@@ -1,5 +1,7 @@
+mod beacon;
 mod etherscan;
 mod rpc;
 
+pub use beacon::BeaconApiLightClientProvider;
 pub use etherscan::EtherscanBlockProvider;
 pub use rpc::RpcBlockProvider;
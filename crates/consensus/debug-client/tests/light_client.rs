@@ -0,0 +1,289 @@
+//! Light-client sync against a local beacon API fixture server.
+
+use alloy_primitives::{FixedBytes, B256, U256};
+use blst::min_pk::{AggregatePublicKey, AggregateSignature, SecretKey};
+use reth_consensus_debug_client::{
+    light_client::{
+        branch_root, signing_root, BeaconBlockHeader, ExecutionPayloadHeader, LightClientError,
+        LightClientHeader, SyncAggregate, SyncCommittee, EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
+        SLOTS_PER_EPOCH, SYNC_COMMITTEE_SIZE,
+    },
+    BeaconApiLightClientProvider, LightClientBootstrap, LightClientConfig,
+    LightClientFinalityUpdate, LightClientSync, LightClientUpdate, VersionedResponse,
+};
+use std::collections::HashMap;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const PERIOD_SLOTS: u64 = SLOTS_PER_EPOCH * EPOCHS_PER_SYNC_COMMITTEE_PERIOD;
+
+fn config() -> LightClientConfig {
+    LightClientConfig {
+        genesis_time: 0,
+        genesis_validators_root: B256::repeat_byte(0x42),
+        forks: vec![(0, [4, 0, 0, 0])],
+        deneb_epoch: 0,
+        electra_epoch: u64::MAX,
+    }
+}
+
+fn hash(left: B256, right: B256) -> B256 {
+    branch_root(left, &[right], 0)
+}
+
+/// Builds a merkle tree over `leaves` and returns its root and the branch of `index`.
+fn prove(leaves: &[B256], index: usize) -> (B256, Vec<B256>) {
+    let mut layer = leaves.to_vec();
+    let mut index = index;
+    let mut branch = Vec::new();
+    while layer.len() > 1 {
+        branch.push(layer[index ^ 1]);
+        layer = layer.chunks_exact(2).map(|pair| hash(pair[0], pair[1])).collect();
+        index /= 2;
+    }
+    (layer[0], branch)
+}
+
+struct Committee {
+    secret_keys: Vec<SecretKey>,
+    committee: SyncCommittee,
+}
+
+impl Committee {
+    fn new(seed: u8) -> Self {
+        let secret_keys = (0..SYNC_COMMITTEE_SIZE)
+            .map(|i| {
+                let mut ikm = [seed; 32];
+                ikm[..8].copy_from_slice(&(i as u64).to_le_bytes());
+                SecretKey::key_gen(&ikm, &[]).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let public_keys = secret_keys.iter().map(|sk| sk.sk_to_pk()).collect::<Vec<_>>();
+        let aggregate_pubkey =
+            AggregatePublicKey::aggregate(&public_keys.iter().collect::<Vec<_>>(), false)
+                .unwrap()
+                .to_public_key();
+        let committee = SyncCommittee {
+            pubkeys: public_keys.iter().map(|pk| FixedBytes::from(pk.compress())).collect(),
+            aggregate_pubkey: FixedBytes::from(aggregate_pubkey.compress()),
+        };
+        Self { secret_keys, committee }
+    }
+
+    fn sign(&self, header: &BeaconBlockHeader, signature_slot: u64) -> SyncAggregate {
+        let message =
+            signing_root(header.tree_hash_root(), config().sync_committee_domain(signature_slot));
+        let signatures = self
+            .secret_keys
+            .iter()
+            .map(|sk| sk.sign(message.as_slice(), BLS_DST, &[]))
+            .collect::<Vec<_>>();
+        let signature =
+            AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>(), false)
+                .unwrap()
+                .to_signature();
+        SyncAggregate {
+            sync_committee_bits: FixedBytes::repeat_byte(0xff),
+            sync_committee_signature: FixedBytes::from(signature.compress()),
+        }
+    }
+}
+
+/// Light-client header at `slot` whose beacon state root is `state_root`.
+fn header(slot: u64, state_root: B256) -> LightClientHeader {
+    let execution = ExecutionPayloadHeader {
+        block_number: slot,
+        block_hash: B256::from(U256::from(slot)),
+        ..Default::default()
+    };
+    let mut leaves = (0..16).map(B256::repeat_byte).collect::<Vec<_>>();
+    leaves[9] = execution.tree_hash_root();
+    let (body_root, execution_branch) = prove(&leaves, 9);
+    LightClientHeader {
+        beacon: BeaconBlockHeader { slot, state_root, body_root, ..Default::default() },
+        execution,
+        execution_branch,
+    }
+}
+
+/// Beacon state with the finalized root at gindex 105 and the sync committees at 54 and 55.
+struct State {
+    leaves: Vec<B256>,
+    finalized_epoch: B256,
+}
+
+impl State {
+    fn new(
+        finalized: Option<&LightClientHeader>,
+        current: &SyncCommittee,
+        next: &SyncCommittee,
+    ) -> Self {
+        let mut leaves = (0..32).map(|i| B256::repeat_byte(0x80 + i)).collect::<Vec<_>>();
+        let finalized_epoch = B256::repeat_byte(0x11);
+        if let Some(finalized) = finalized {
+            leaves[20] = hash(finalized_epoch, finalized.beacon.tree_hash_root());
+        }
+        leaves[22] = current.tree_hash_root();
+        leaves[23] = next.tree_hash_root();
+        Self { leaves, finalized_epoch }
+    }
+
+    fn root(&self) -> B256 {
+        prove(&self.leaves, 0).0
+    }
+
+    fn finality_branch(&self) -> Vec<B256> {
+        let mut branch = vec![self.finalized_epoch];
+        branch.extend(prove(&self.leaves, 20).1);
+        branch
+    }
+}
+
+struct Fixtures {
+    checkpoint: B256,
+    bootstrap: LightClientBootstrap,
+    updates: Vec<LightClientUpdate>,
+    finality_update: LightClientFinalityUpdate,
+}
+
+/// Bootstrap in period 0, an update handing over to the next committee and a finality update in
+/// period 1 signed by `finality_signer`.
+fn fixtures(current: &Committee, next: &Committee, finality_signer: &Committee) -> Fixtures {
+    let bootstrap_state = State::new(None, &current.committee, &next.committee);
+    let bootstrap_header = header(100, bootstrap_state.root());
+    let checkpoint = bootstrap_header.beacon.tree_hash_root();
+    let bootstrap = LightClientBootstrap {
+        header: bootstrap_header,
+        current_sync_committee: current.committee.clone(),
+        current_sync_committee_branch: prove(&bootstrap_state.leaves, 22).1,
+    };
+
+    let finalized = header(150, B256::repeat_byte(1));
+    let state = State::new(Some(&finalized), &current.committee, &next.committee);
+    let attested = header(200, state.root());
+    let update = LightClientUpdate {
+        sync_aggregate: current.sign(&attested.beacon, 201),
+        attested_header: attested,
+        next_sync_committee: Some(next.committee.clone()),
+        next_sync_committee_branch: prove(&state.leaves, 23).1,
+        finalized_header: Some(finalized),
+        finality_branch: state.finality_branch(),
+        signature_slot: 201,
+    };
+
+    let finalized = header(PERIOD_SLOTS + 200, B256::repeat_byte(2));
+    let state = State::new(Some(&finalized), &next.committee, &current.committee);
+    let attested = header(PERIOD_SLOTS + 300, state.root());
+    let finality_update = LightClientFinalityUpdate {
+        sync_aggregate: finality_signer.sign(&attested.beacon, PERIOD_SLOTS + 301),
+        attested_header: attested,
+        finalized_header: finalized,
+        finality_branch: state.finality_branch(),
+        signature_slot: PERIOD_SLOTS + 301,
+    };
+
+    Fixtures { checkpoint, bootstrap, updates: vec![update], finality_update }
+}
+
+fn versioned<T>(data: T) -> VersionedResponse<T> {
+    VersionedResponse { version: "deneb".to_string(), data }
+}
+
+/// Serves the fixtures from the light-client endpoints of a beacon API and returns its URL.
+async fn serve(fixtures: &Fixtures) -> String {
+    let routes = HashMap::from([
+        (
+            format!("/eth/v1/beacon/light_client/bootstrap/{}", fixtures.checkpoint),
+            serde_json::to_string(&versioned(&fixtures.bootstrap)).unwrap(),
+        ),
+        (
+            "/eth/v1/beacon/light_client/updates".to_string(),
+            serde_json::to_string(&fixtures.updates.iter().map(versioned).collect::<Vec<_>>())
+                .unwrap(),
+        ),
+        (
+            "/eth/v1/beacon/light_client/finality_update".to_string(),
+            serde_json::to_string(&versioned(&fixtures.finality_update)).unwrap(),
+        ),
+    ]);
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let request = String::from_utf8_lossy(&request);
+            let target = request.split_whitespace().nth(1).unwrap_or_default();
+            let path = target.split('?').next().unwrap_or_default();
+            let response = match routes.get(path) {
+                Some(body) => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                ),
+                None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string(),
+            };
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    url
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn follows_attested_chain() {
+    let (current, next) = (Committee::new(1), Committee::new(2));
+    let fixtures = fixtures(&current, &next, &next);
+    let url = serve(&fixtures).await;
+
+    let provider = BeaconApiLightClientProvider::new(url);
+    let mut sync = LightClientSync::new(provider, config(), fixtures.checkpoint);
+    let store = sync.sync(PERIOD_SLOTS + 400).await.unwrap();
+
+    assert_eq!(store.finalized_header(), &fixtures.finality_update.finalized_header);
+    assert_eq!(store.optimistic_header(), &fixtures.finality_update.attested_header);
+    assert_eq!(store.finalized_period(), 1);
+    assert!(!store.is_next_sync_committee_known());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn skips_finality_update_signed_by_wrong_committee() {
+    let (current, next) = (Committee::new(1), Committee::new(2));
+    let fixtures = fixtures(&current, &next, &current);
+    let url = serve(&fixtures).await;
+
+    let provider = BeaconApiLightClientProvider::new(url);
+    let mut sync = LightClientSync::new(provider, config(), fixtures.checkpoint);
+    let store = sync.sync(PERIOD_SLOTS + 400).await.unwrap();
+
+    // The store only advanced through the verified update.
+    assert_eq!(store.finalized_header().beacon.slot, 150);
+    assert_eq!(store.optimistic_header().beacon.slot, 200);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rejects_untrusted_bootstrap() {
+    let (current, next) = (Committee::new(1), Committee::new(2));
+    let mut fixtures = fixtures(&current, &next, &next);
+    fixtures.bootstrap.header.beacon.proposer_index = 1;
+    let url = serve(&fixtures).await;
+
+    let provider = BeaconApiLightClientProvider::new(url);
+    let mut sync = LightClientSync::new(provider, config(), fixtures.checkpoint);
+    let err = sync.sync(PERIOD_SLOTS + 400).await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<LightClientError>(),
+        Some(LightClientError::CheckpointMismatch { .. })
+    ));
+    assert!(sync.store().is_none());
+}
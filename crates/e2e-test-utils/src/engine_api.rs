@@ -1,4 +1,5 @@
 use crate::traits::PayloadEnvelopeExt;
+use alloy_consensus::BlockHeader;
 use alloy_primitives::B256;
 use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatusEnum};
 use jsonrpsee::{
@@ -9,7 +10,7 @@ use reth_chainspec::EthereumHardforks;
 use reth_node_api::{EngineTypes, NodePrimitives};
 use reth_node_builder::BuiltPayload;
 use reth_payload_builder::PayloadId;
-use reth_payload_primitives::PayloadBuilderAttributes;
+use reth_payload_primitives::{ExecutionPayloadInputV4, PayloadBuilderAttributes};
 use reth_provider::CanonStateNotificationStream;
 use reth_rpc_api::EngineApiClient;
 use reth_rpc_layer::AuthClientService;
@@ -43,6 +44,14 @@ impl<E: EngineTypes, ChainSpec: EthereumHardforks, N: NodePrimitives>
         Ok(self.engine_api_client.request("engine_getPayloadV3", (payload_id,)).await?)
     }
 
+    /// Retrieves a v4 payload from the engine api
+    pub async fn get_payload_v4(
+        &self,
+        payload_id: PayloadId,
+    ) -> eyre::Result<E::ExecutionPayloadEnvelopeV4> {
+        Ok(EngineApiClient::<E>::get_payload_v4(&self.engine_api_client, payload_id).await?)
+    }
+
     /// Submits a payload to the engine api
     pub async fn submit_payload(
         &self,
@@ -52,7 +61,6 @@ impl<E: EngineTypes, ChainSpec: EthereumHardforks, N: NodePrimitives>
     ) -> eyre::Result<B256>
     where
         E::ExecutionPayloadEnvelopeV3: From<E::BuiltPayload> + PayloadEnvelopeExt,
-        E::ExecutionPayloadEnvelopeV4: From<E::BuiltPayload> + PayloadEnvelopeExt,
     {
         let versioned_hashes =
             payload.block().blob_versioned_hashes_iter().copied().collect::<Vec<_>>();
@@ -61,11 +69,16 @@ impl<E: EngineTypes, ChainSpec: EthereumHardforks, N: NodePrimitives>
             .chain_spec
             .is_prague_active_at_timestamp(payload_builder_attributes.timestamp())
         {
-            let requests = payload.requests().unwrap();
-            let envelope: <E as EngineTypes>::ExecutionPayloadEnvelopeV4 = payload.into();
+            let requests = payload.requests().unwrap_or_default();
+            // the payload is the same as in V3, but may include the withdrawals root of the block
+            let withdrawals_root = payload.block().header().withdrawals_root();
+            let envelope: <E as EngineTypes>::ExecutionPayloadEnvelopeV3 = payload.into();
             EngineApiClient::<E>::new_payload_v4(
                 &self.engine_api_client,
-                envelope.execution_payload(),
+                ExecutionPayloadInputV4 {
+                    execution_payload: envelope.execution_payload(),
+                    withdrawals_root,
+                },
                 versioned_hashes,
                 payload_builder_attributes.parent_beacon_block_root().unwrap(),
                 requests,
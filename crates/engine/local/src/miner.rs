@@ -213,6 +213,7 @@ where
             payload,
             // todo: prague support
             sidecar,
+            withdrawals_root: block.header().withdrawals_root(),
             tx,
         })?;
 
@@ -32,12 +32,16 @@ pub use event::*;
 mod invalid_block_hook;
 pub use invalid_block_hook::InvalidBlockHook;
 
+use alloy_primitives::B256;
+use reth_errors::RethError;
 use reth_payload_primitives::{
     validate_execution_requests, EngineApiMessageVersion, EngineObjectValidationError,
     InvalidPayloadAttributesError, PayloadOrAttributes, PayloadTypes,
 };
-use reth_primitives::{NodePrimitives, SealedBlock};
+use reth_primitives::{NodePrimitives, RecoveredBlock, SealedBlock};
 use reth_primitives_traits::Block;
+use reth_storage_api::StorageRootProvider;
+use reth_trie::HashedPostState;
 use serde::{de::DeserializeOwned, ser::Serialize};
 
 use alloy_eips::eip7685::Requests;
@@ -110,11 +114,32 @@ pub trait PayloadValidator: fmt::Debug + Send + Sync + Unpin + 'static {
     ///
     /// Implementers should ensure that the checks are done in the order that conforms with the
     /// engine-API specification.
+    ///
+    /// The withdrawals root is only set if it was passed along with the payload, see
+    /// [`ExecutionPayloadInputV4`](reth_payload_primitives::ExecutionPayloadInputV4). It is only
+    /// used by chains that don't derive the withdrawals root from the withdrawals of the payload.
     fn ensure_well_formed_payload(
         &self,
         payload: ExecutionPayload,
         sidecar: ExecutionPayloadSidecar,
+        withdrawals_root: Option<B256>,
     ) -> Result<SealedBlock<Self::Block>, PayloadError>;
+
+    /// Verifies payload post-execution w.r.t. hashed state updates.
+    ///
+    /// This is called after the block has been executed, with the hashed state updates of the
+    /// block and the state of its parent. By default this is a no-op, the OP stack uses it to
+    /// verify the withdrawals root post-Isthmus.
+    ///
+    /// Returns [`RethError::Consensus`] if the block is invalid.
+    fn validate_block_post_execution_with_hashed_state(
+        &self,
+        _parent_state: &dyn StorageRootProvider,
+        _state_updates: &HashedPostState,
+        _block: &RecoveredBlock<Self::Block>,
+    ) -> Result<(), RethError> {
+        Ok(())
+    }
 }
 
 /// Type that validates the payloads processed by the engine.
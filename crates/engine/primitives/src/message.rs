@@ -2,6 +2,7 @@ use crate::{
     error::BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, EngineApiMessageVersion,
     EngineTypes, ForkchoiceStatus,
 };
+use alloy_primitives::B256;
 use alloy_rpc_types_engine::{
     ExecutionPayload, ExecutionPayloadSidecar, ForkChoiceUpdateResult, ForkchoiceState,
     ForkchoiceUpdateError, ForkchoiceUpdated, PayloadId, PayloadStatus, PayloadStatusEnum,
@@ -149,6 +150,10 @@ pub enum BeaconEngineMessage<Engine: EngineTypes> {
         /// The execution payload sidecar with additional version-specific fields received by
         /// engine API.
         sidecar: ExecutionPayloadSidecar,
+        /// The withdrawals root of the block, if it was received with the payload.
+        ///
+        /// See also [`ExecutionPayloadInputV4`](reth_payload_primitives::ExecutionPayloadInputV4).
+        withdrawals_root: Option<B256>,
         /// The sender for returning payload status result.
         tx: oneshot::Sender<Result<PayloadStatus, BeaconOnNewPayloadError>>,
     },
@@ -222,9 +227,15 @@ where
         &self,
         payload: ExecutionPayload,
         sidecar: ExecutionPayloadSidecar,
+        withdrawals_root: Option<B256>,
     ) -> Result<PayloadStatus, BeaconOnNewPayloadError> {
         let (tx, rx) = oneshot::channel();
-        let _ = self.to_engine.send(BeaconEngineMessage::NewPayload { payload, sidecar, tx });
+        let _ = self.to_engine.send(BeaconEngineMessage::NewPayload {
+            payload,
+            sidecar,
+            withdrawals_root,
+            tx,
+        });
         rx.await.map_err(|_| BeaconOnNewPayloadError::EngineUnavailable)?
     }
 
@@ -29,7 +29,7 @@ use reth_engine_primitives::{
     BeaconConsensusEngineEvent, BeaconEngineMessage, BeaconOnNewPayloadError, EngineTypes,
     EngineValidator, ForkchoiceStateTracker, OnForkChoiceUpdated,
 };
-use reth_errors::{ConsensusError, ProviderResult};
+use reth_errors::{ConsensusError, ProviderResult, RethError};
 use reth_evm::{
     execute::BlockExecutorProvider,
     system_calls::{NoopHook, OnStateHook},
@@ -775,6 +775,7 @@ where
         &mut self,
         payload: ExecutionPayload,
         sidecar: ExecutionPayloadSidecar,
+        withdrawals_root: Option<B256>,
     ) -> Result<TreeOutcome<PayloadStatus>, InsertBlockFatalError> {
         trace!(target: "engine::tree", "invoked new payload");
         self.metrics.engine.new_payload_messages.increment(1);
@@ -805,7 +806,11 @@ where
         //
         // This validation **MUST** be instantly run in all cases even during active sync process.
         let parent_hash = payload.parent_hash();
-        let block = match self.payload_validator.ensure_well_formed_payload(payload, sidecar) {
+        let block = match self.payload_validator.ensure_well_formed_payload(
+            payload,
+            sidecar,
+            withdrawals_root,
+        ) {
             Ok(block) => block,
             Err(error) => {
                 error!(target: "engine::tree", %error, "Invalid payload");
@@ -1307,8 +1312,14 @@ where
                                     error!(target: "engine::tree", "Failed to send event: {err:?}");
                                 }
                             }
-                            BeaconEngineMessage::NewPayload { payload, sidecar, tx } => {
-                                let output = self.on_new_payload(payload, sidecar);
+                            BeaconEngineMessage::NewPayload {
+                                payload,
+                                sidecar,
+                                withdrawals_root,
+                                tx,
+                            } => {
+                                let output =
+                                    self.on_new_payload(payload, sidecar, withdrawals_root);
                                 if let Err(err) =
                                     tx.send(output.map(|o| o.outcome).map_err(|e| {
                                         BeaconOnNewPayloadError::Internal(Box::new(e))
@@ -2304,6 +2315,22 @@ where
 
         let hashed_state = self.provider.hashed_post_state(&output.state);
 
+        if let Err(err) = self.payload_validator.validate_block_post_execution_with_hashed_state(
+            &state_provider,
+            &hashed_state,
+            &block,
+        ) {
+            return Err(match err {
+                RethError::Consensus(err) => {
+                    // call post-block hook
+                    self.invalid_block_hook.on_invalid_block(&parent_block, &block, &output, None);
+                    err.into()
+                }
+                RethError::Provider(err) => err.into(),
+                err => InsertBlockErrorKind::Other(Box::new(err)),
+            })
+        }
+
         trace!(target: "engine::tree", block=?block_num_hash, "Calculating block state root");
         let root_time = Instant::now();
 
@@ -3083,6 +3110,7 @@ mod tests {
                         parent_beacon_block_root: block.parent_beacon_block_root.unwrap(),
                         versioned_hashes: vec![],
                     }),
+                    None,
                 )
                 .unwrap();
         }
@@ -3348,7 +3376,7 @@ mod tests {
 
         let outcome = test_harness
             .tree
-            .on_new_payload(payload.into(), ExecutionPayloadSidecar::none())
+            .on_new_payload(payload.into(), ExecutionPayloadSidecar::none(), None)
             .unwrap();
         assert!(outcome.outcome.is_syncing());
 
@@ -3394,6 +3422,7 @@ mod tests {
                 BeaconEngineMessage::NewPayload {
                     payload: payload.clone().into(),
                     sidecar: ExecutionPayloadSidecar::none(),
+                    withdrawals_root: None,
                     tx,
                 }
                 .into(),
@@ -1,17 +1,25 @@
 //! Stores engine API messages to disk for later inspection and replay.
 
-use alloy_rpc_types_engine::{ExecutionPayload, ExecutionPayloadSidecar, ForkchoiceState};
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::{
+    ExecutionPayload, ExecutionPayloadSidecar, ForkchoiceState, PayloadStatus, PayloadStatusEnum,
+};
 use futures::{Stream, StreamExt};
-use reth_engine_primitives::{BeaconEngineMessage, EngineTypes};
+use reth_engine_primitives::{
+    BeaconEngineMessage, BeaconOnNewPayloadError, EngineTypes, ForkchoiceStatus,
+    OnForkChoiceUpdated,
+};
+use reth_errors::RethResult;
 use reth_fs_util as fs;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
 };
+use tokio::sync::oneshot;
 use tracing::*;
 
 /// A message from the engine API that has been stored to disk.
@@ -24,6 +32,9 @@ pub enum StoredEngineApiMessage<Attributes> {
         state: ForkchoiceState,
         /// The payload attributes sent in the persisted call, if any.
         payload_attrs: Option<Attributes>,
+        /// The status the node responded with, if the response was recorded.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        status: Option<StoredResponseStatus>,
     },
     /// The on-disk representation of an `engine_newPayload` method call.
     NewPayload {
@@ -32,9 +43,69 @@ pub enum StoredEngineApiMessage<Attributes> {
         /// The execution payload sidecar with additional version-specific fields received by
         /// engine API.
         sidecar: ExecutionPayloadSidecar,
+        /// The withdrawals root sent with the payload, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        withdrawals_root: Option<B256>,
+        /// The status the node responded with, if the response was recorded.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        status: Option<StoredResponseStatus>,
     },
 }
 
+impl<Attributes> StoredEngineApiMessage<Attributes> {
+    /// Returns the recorded response status of the message, if any.
+    pub const fn status(&self) -> Option<StoredResponseStatus> {
+        match self {
+            Self::ForkchoiceUpdated { status, .. } | Self::NewPayload { status, .. } => *status,
+        }
+    }
+
+    const fn set_status(&mut self, new_status: StoredResponseStatus) {
+        match self {
+            Self::ForkchoiceUpdated { status, .. } | Self::NewPayload { status, .. } => {
+                *status = Some(new_status)
+            }
+        }
+    }
+}
+
+/// The status of the response to an engine API message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StoredResponseStatus {
+    /// The payload or forkchoice state is valid.
+    Valid,
+    /// The payload or forkchoice state is invalid.
+    Invalid,
+    /// The node is syncing and could not validate the payload or forkchoice state.
+    Syncing,
+    /// The payload was accepted, but not validated.
+    Accepted,
+    /// The node failed to process the message.
+    Error,
+}
+
+impl StoredResponseStatus {
+    /// Returns the status of a `newPayload` response.
+    pub const fn from_payload_status(status: &PayloadStatusEnum) -> Self {
+        match status {
+            PayloadStatusEnum::Valid => Self::Valid,
+            PayloadStatusEnum::Invalid { .. } => Self::Invalid,
+            PayloadStatusEnum::Syncing => Self::Syncing,
+            PayloadStatusEnum::Accepted => Self::Accepted,
+        }
+    }
+
+    /// Returns the status of a `forkchoiceUpdated` response.
+    pub const fn from_forkchoice_status(status: ForkchoiceStatus) -> Self {
+        match status {
+            ForkchoiceStatus::Valid => Self::Valid,
+            ForkchoiceStatus::Invalid => Self::Invalid,
+            ForkchoiceStatus::Syncing => Self::Syncing,
+        }
+    }
+}
+
 /// This can read and write engine API messages in a specific directory.
 #[derive(Debug)]
 pub struct EngineMessageStore {
@@ -60,7 +131,89 @@ impl EngineMessageStore {
     where
         Engine: EngineTypes,
     {
-        fs::create_dir_all(&self.path)?; // ensure that store path had been created
+        if let Some((path, stored)) = self.stored_message(msg, received_at) {
+            self.write(&path, &stored)?;
+        }
+        Ok(())
+    }
+
+    /// Stores the received [`BeaconEngineMessage`] to disk like [`Self::on_message`], and updates
+    /// the stored message with the status of the response once it is sent.
+    ///
+    /// Returns the message to forward, its response is relayed to the original sender. If the
+    /// message can't be stored, it is returned unchanged.
+    ///
+    /// Note: This must be called from within a tokio runtime, the response is awaited on a
+    /// spawned task.
+    pub fn on_message_with_response<Engine>(
+        &self,
+        msg: BeaconEngineMessage<Engine>,
+        received_at: SystemTime,
+    ) -> BeaconEngineMessage<Engine>
+    where
+        Engine: EngineTypes,
+    {
+        let Some((path, mut stored)) = self.stored_message(&msg, received_at) else { return msg };
+        if let Err(error) = self.write(&path, &stored) {
+            error!(target: "engine::stream::store", %msg, %error, "Error handling Engine API message");
+            return msg
+        }
+
+        let store = Self::new(self.path.clone());
+        match msg {
+            BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, version, tx } => {
+                let (response_tx, response_rx) =
+                    oneshot::channel::<RethResult<OnForkChoiceUpdated>>();
+                tokio::spawn(async move {
+                    let Ok(response) = response_rx.await else { return };
+                    stored.set_status(match &response {
+                        Ok(on_updated) => StoredResponseStatus::from_forkchoice_status(
+                            on_updated.forkchoice_status(),
+                        ),
+                        Err(_) => StoredResponseStatus::Error,
+                    });
+                    store.write_response(&path, &stored);
+                    let _ = tx.send(response);
+                });
+                BeaconEngineMessage::ForkchoiceUpdated {
+                    state,
+                    payload_attrs,
+                    version,
+                    tx: response_tx,
+                }
+            }
+            BeaconEngineMessage::NewPayload { payload, sidecar, withdrawals_root, tx } => {
+                let (response_tx, response_rx) =
+                    oneshot::channel::<Result<PayloadStatus, BeaconOnNewPayloadError>>();
+                tokio::spawn(async move {
+                    let Ok(response) = response_rx.await else { return };
+                    stored.set_status(match &response {
+                        Ok(status) => StoredResponseStatus::from_payload_status(&status.status),
+                        Err(_) => StoredResponseStatus::Error,
+                    });
+                    store.write_response(&path, &stored);
+                    let _ = tx.send(response);
+                });
+                BeaconEngineMessage::NewPayload {
+                    payload,
+                    sidecar,
+                    withdrawals_root,
+                    tx: response_tx,
+                }
+            }
+            msg => msg,
+        }
+    }
+
+    /// Returns the path and on-disk representation of the message, if it is stored.
+    fn stored_message<Engine>(
+        &self,
+        msg: &BeaconEngineMessage<Engine>,
+        received_at: SystemTime,
+    ) -> Option<(PathBuf, StoredEngineApiMessage<Engine::PayloadAttributes>)>
+    where
+        Engine: EngineTypes,
+    {
         let timestamp = received_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
         match msg {
             BeaconEngineMessage::ForkchoiceUpdated {
@@ -70,32 +223,54 @@ impl EngineMessageStore {
                 version: _version,
             } => {
                 let filename = format!("{}-fcu-{}.json", timestamp, state.head_block_hash);
-                fs::write(
+                Some((
                     self.path.join(filename),
-                    serde_json::to_vec(&StoredEngineApiMessage::ForkchoiceUpdated {
+                    StoredEngineApiMessage::ForkchoiceUpdated {
                         state: *state,
                         payload_attrs: payload_attrs.clone(),
-                    })?,
-                )?;
+                        status: None,
+                    },
+                ))
             }
-            BeaconEngineMessage::NewPayload { payload, sidecar, tx: _tx } => {
+            BeaconEngineMessage::NewPayload { payload, sidecar, withdrawals_root, tx: _tx } => {
                 let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
-                fs::write(
+                Some((
                     self.path.join(filename),
-                    serde_json::to_vec(
-                        &StoredEngineApiMessage::<Engine::PayloadAttributes>::NewPayload {
-                            payload: payload.clone(),
-                            sidecar: sidecar.clone(),
-                        },
-                    )?,
-                )?;
+                    StoredEngineApiMessage::NewPayload {
+                        payload: payload.clone(),
+                        sidecar: sidecar.clone(),
+                        withdrawals_root: *withdrawals_root,
+                        status: None,
+                    },
+                ))
             }
             // noop
-            BeaconEngineMessage::TransitionConfigurationExchanged => (),
-        };
+            BeaconEngineMessage::TransitionConfigurationExchanged => None,
+        }
+    }
+
+    /// Writes the stored message to the given path.
+    fn write<Attributes: Serialize>(
+        &self,
+        path: &Path,
+        stored: &StoredEngineApiMessage<Attributes>,
+    ) -> eyre::Result<()> {
+        fs::create_dir_all(&self.path)?; // ensure that store path had been created
+        fs::write(path, serde_json::to_vec(stored)?)?;
         Ok(())
     }
 
+    /// Writes the stored message with its response status, logging any error.
+    fn write_response<Attributes: Serialize>(
+        &self,
+        path: &Path,
+        stored: &StoredEngineApiMessage<Attributes>,
+    ) {
+        if let Err(error) = self.write(path, stored) {
+            error!(target: "engine::stream::store", ?path, %error, "Error storing Engine API response");
+        }
+    }
+
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
@@ -105,12 +280,12 @@ impl EngineMessageStore {
             if let Some(filename) = filename.to_str().filter(|n| n.ends_with(".json")) {
                 if let Some(Ok(timestamp)) = filename.split('-').next().map(|n| n.parse::<u64>()) {
                     filenames_by_ts.entry(timestamp).or_default().push(entry.path());
-                    tracing::debug!(target: "engine::store", timestamp, filename, "Queued engine API message");
+                    tracing::debug!(target: "engine::stream::store", timestamp, filename, "Queued engine API message");
                 } else {
-                    tracing::warn!(target: "engine::store", %filename, "Could not parse timestamp from filename")
+                    tracing::warn!(target: "engine::stream::store", %filename, "Could not parse timestamp from filename")
                 }
             } else {
-                tracing::warn!(target: "engine::store", ?filename, "Skipping non json file");
+                tracing::warn!(target: "engine::stream::store", ?filename, "Skipping non json file");
             }
         }
         Ok(filenames_by_ts.into_iter().flat_map(|(_, paths)| paths))
@@ -146,11 +321,50 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
         let next = ready!(this.stream.poll_next_unpin(cx));
-        if let Some(msg) = &next {
-            if let Err(error) = this.store.on_message(msg, SystemTime::now()) {
-                error!(target: "engine::stream::store", ?msg, %error, "Error handling Engine API message");
-            }
-        }
-        Poll::Ready(next)
+        Poll::Ready(next.map(|msg| this.store.on_message_with_response(msg, SystemTime::now())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_engine::ExecutionPayloadV1;
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_primitives::{Block, TransactionSigned};
+
+    #[tokio::test]
+    async fn records_response_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EngineMessageStore::new(dir.path().to_path_buf());
+
+        let payload = ExecutionPayload::V1(ExecutionPayloadV1::from_block_slow(&Block::<
+            TransactionSigned,
+        >::default(
+        )));
+        let (tx, rx) = oneshot::channel();
+        let msg = BeaconEngineMessage::<EthEngineTypes>::NewPayload {
+            payload,
+            sidecar: ExecutionPayloadSidecar::none(),
+            withdrawals_root: None,
+            tx,
+        };
+
+        // the message is stored without a status until the response is sent
+        let BeaconEngineMessage::NewPayload { tx: forwarded_tx, .. } =
+            store.on_message_with_response(msg, SystemTime::now())
+        else {
+            panic!("expected a new payload message")
+        };
+        let path = store.engine_messages_iter().unwrap().next().unwrap();
+        let stored: StoredEngineApiMessage<()> =
+            serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(stored.status(), None);
+
+        // the response is relayed to the original sender and its status is recorded
+        forwarded_tx.send(Ok(PayloadStatus::from_status(PayloadStatusEnum::Valid))).unwrap();
+        assert_eq!(rx.await.unwrap().unwrap().status, PayloadStatusEnum::Valid);
+        let stored: StoredEngineApiMessage<()> =
+            serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(stored.status(), Some(StoredResponseStatus::Valid));
     }
 }
@@ -40,7 +40,12 @@ where
         loop {
             let next = ready!(this.stream.poll_next_unpin(cx));
             let item = match next {
-                Some(BeaconEngineMessage::NewPayload { payload, sidecar, tx }) => {
+                Some(BeaconEngineMessage::NewPayload {
+                    payload,
+                    sidecar,
+                    withdrawals_root,
+                    tx,
+                }) => {
                     if this.skipped < this.threshold {
                         *this.skipped += 1;
                         tracing::warn!(
@@ -55,7 +60,7 @@ where
                         continue
                     }
                     *this.skipped = 0;
-                    Some(BeaconEngineMessage::NewPayload { payload, sidecar, tx })
+                    Some(BeaconEngineMessage::NewPayload { payload, sidecar, withdrawals_root, tx })
                 }
                 next => next,
             };
@@ -0,0 +1,188 @@
+//! The epoch accumulator that ERA1 files commit to.
+//!
+//! The accumulator is the SSZ `hash_tree_root` of `List[HeaderRecord, 8192]`, see the
+//! [Portal network spec](https://github.com/ethereum/portal-network-specs/blob/master/history/history-network.md#the-historical-hashes-accumulator).
+
+use crate::{era1::MAX_BLOCKS_PER_ERA1, EraError};
+use alloy_primitives::{B256, U256};
+use sha2::{Digest, Sha256};
+
+/// Depth of the merkle tree over the header records, `log2(MAX_BLOCKS_PER_ERA1)`.
+const TREE_DEPTH: usize = MAX_BLOCKS_PER_ERA1.trailing_zeros() as usize;
+
+/// A header record of the epoch accumulator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderRecord {
+    /// Hash of the block.
+    pub block_hash: B256,
+    /// Total difficulty of the chain up to and including the block.
+    pub total_difficulty: U256,
+}
+
+impl HeaderRecord {
+    /// Returns the SSZ `hash_tree_root` of this record.
+    pub fn tree_hash_root(&self) -> B256 {
+        hash_pair(&self.block_hash, &B256::from(self.total_difficulty.to_le_bytes::<32>()))
+    }
+}
+
+/// Computes the epoch accumulator root of the given header records.
+///
+/// # Panics
+///
+/// If more than [`MAX_BLOCKS_PER_ERA1`] records are passed.
+pub fn accumulator_root(records: &[HeaderRecord]) -> B256 {
+    assert!(records.len() <= MAX_BLOCKS_PER_ERA1, "too many header records");
+
+    let root = merkleize(records.iter().map(HeaderRecord::tree_hash_root).collect(), TREE_DEPTH);
+
+    // mix in the length of the list
+    hash_pair(&root, &B256::from(U256::from(records.len()).to_le_bytes::<32>()))
+}
+
+/// The trusted epoch accumulator roots of a network, indexed by epoch.
+///
+/// ERA1 files are verified against these, since the accumulator root stored in a file only
+/// commits to the file's own contents.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedAccumulators {
+    roots: Vec<B256>,
+}
+
+impl TrustedAccumulators {
+    /// Creates a new instance from the accumulator roots of consecutive epochs, starting at epoch
+    /// zero.
+    pub const fn new(roots: Vec<B256>) -> Self {
+        Self { roots }
+    }
+
+    /// Parses the accumulator roots from a list of hex encoded roots, one per line and in epoch
+    /// order.
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn parse(s: &str) -> Result<Self, EraError> {
+        s.lines()
+            .enumerate()
+            .map(|(line, root)| (line + 1, root.trim()))
+            .filter(|(_, root)| !root.is_empty() && !root.starts_with('#'))
+            .map(|(line, root)| root.parse().map_err(|_| EraError::InvalidTrustedRoot(line)))
+            .collect::<Result<_, _>>()
+            .map(Self::new)
+    }
+
+    /// Returns the number of epochs with a trusted root.
+    pub fn len(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Returns `true` if there are no trusted roots.
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Returns the trusted root of the given epoch.
+    pub fn root(&self, epoch: u64) -> Option<B256> {
+        self.roots.get(usize::try_from(epoch).ok()?).copied()
+    }
+
+    /// Returns an error if the given root is not the trusted root of the epoch.
+    pub fn verify(&self, epoch: u64, root: B256) -> Result<(), EraError> {
+        let trusted = self.root(epoch).ok_or(EraError::UnknownEpoch(epoch))?;
+        if trusted != root {
+            return Err(EraError::UntrustedAccumulator { epoch, computed: root, trusted })
+        }
+        Ok(())
+    }
+}
+
+/// Computes the SSZ merkle root of the given leaves, padded with zero hashes to a tree of the given
+/// depth.
+pub(crate) fn merkleize(mut layer: Vec<B256>, depth: usize) -> B256 {
+    let mut zero_hash = B256::ZERO;
+    for _ in 0..depth {
+        if layer.len() % 2 == 1 {
+            layer.push(zero_hash);
+        }
+        layer = layer.chunks_exact(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+        zero_hash = hash_pair(&zero_hash, &zero_hash);
+    }
+    layer.first().copied().unwrap_or(zero_hash)
+}
+
+pub(crate) fn hash_pair(left: &B256, right: &B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn empty_accumulator() {
+        // hash_tree_root of an empty list of limit 8192: mix_in_length(zero_hashes[13], 0)
+        let mut zero = B256::ZERO;
+        for _ in 0..13 {
+            zero = hash_pair(&zero, &zero);
+        }
+        assert_eq!(accumulator_root(&[]), hash_pair(&zero, &B256::ZERO));
+    }
+
+    #[test]
+    fn accumulator_depends_on_every_record() {
+        let records = (0..5u64)
+            .map(|i| HeaderRecord {
+                block_hash: B256::with_last_byte(i as u8),
+                total_difficulty: U256::from(i * 100),
+            })
+            .collect::<Vec<_>>();
+        let root = accumulator_root(&records);
+
+        let mut modified = records.clone();
+        modified[4].total_difficulty += U256::from(1);
+        assert_ne!(accumulator_root(&modified), root);
+        assert_ne!(accumulator_root(&records[..4]), root);
+    }
+
+    #[test]
+    fn single_record_root() {
+        let record = HeaderRecord {
+            block_hash: b256!("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"),
+            total_difficulty: U256::from(17_179_869_184u64),
+        };
+        let mut node = record.tree_hash_root();
+        let mut zero = B256::ZERO;
+        for _ in 0..13 {
+            node = hash_pair(&node, &zero);
+            zero = hash_pair(&zero, &zero);
+        }
+        let mut len = [0u8; 32];
+        len[0] = 1;
+        assert_eq!(accumulator_root(&[record]), hash_pair(&node, &B256::from(len)));
+    }
+
+    #[test]
+    fn trusted_accumulators() {
+        let root = B256::repeat_byte(2);
+        let roots = format!("# testnet\n{}\n\n {root:x} \n", B256::repeat_byte(1));
+        let trusted = TrustedAccumulators::parse(&roots).unwrap();
+        assert_eq!(trusted.len(), 2);
+        assert_eq!(trusted.root(0), Some(B256::repeat_byte(1)));
+        assert_eq!(trusted.root(1), Some(root));
+
+        trusted.verify(1, root).unwrap();
+        assert!(matches!(
+            trusted.verify(0, root),
+            Err(EraError::UntrustedAccumulator { epoch: 0, .. })
+        ));
+        assert!(matches!(trusted.verify(2, root), Err(EraError::UnknownEpoch(2))));
+
+        assert!(matches!(
+            TrustedAccumulators::parse("# roots\nnot a root"),
+            Err(EraError::InvalidTrustedRoot(2))
+        ));
+    }
+}
@@ -0,0 +1,212 @@
+//! The e2store container format shared by ERA1 and ERA files.
+//!
+//! An e2store file is a flat sequence of entries, each prefixed by an 8 byte header:
+//!
+//! ```text
+//! entry  := header | data
+//! header := type (2 bytes, LE) | length (4 bytes, LE) | reserved (2 bytes, zero)
+//! ```
+
+use crate::EraError;
+use std::io::{self, ErrorKind, Read, Write};
+
+/// Size of the header that precedes every entry.
+pub const HEADER_SIZE: u64 = 8;
+
+/// Type of the `Version` entry that starts every e2store file (`"e2"` in ASCII).
+pub const VERSION: u16 = 0x3265;
+
+/// A single e2store entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The type of the entry.
+    pub entry_type: u16,
+    /// The raw payload of the entry.
+    pub data: Vec<u8>,
+}
+
+impl Entry {
+    /// Creates a new entry.
+    pub const fn new(entry_type: u16, data: Vec<u8>) -> Self {
+        Self { entry_type, data }
+    }
+
+    /// Returns the `Version` entry.
+    pub const fn version() -> Self {
+        Self::new(VERSION, Vec::new())
+    }
+
+    /// Returns the number of bytes this entry occupies in the file, including its header.
+    pub fn encoded_len(&self) -> u64 {
+        HEADER_SIZE + self.data.len() as u64
+    }
+
+    /// Returns an error if this entry is not of the expected type.
+    ///
+    /// The `offset` is only used for the error message.
+    pub const fn expect_type(&self, expected: u16, offset: u64) -> Result<(), EraError> {
+        if self.entry_type != expected {
+            return Err(EraError::UnexpectedEntry { expected, got: self.entry_type, offset })
+        }
+        Ok(())
+    }
+}
+
+/// Reads [`Entry`]s from an e2store file.
+#[derive(Debug)]
+pub struct E2StoreReader<R> {
+    reader: R,
+    position: u64,
+}
+
+impl<R: Read> E2StoreReader<R> {
+    /// Creates a new reader positioned at the start of the file.
+    pub const fn new(reader: R) -> Self {
+        Self { reader, position: 0 }
+    }
+
+    /// Returns the offset of the next entry.
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Reads the next entry together with its offset, returning `None` at the end of the file.
+    pub fn next_entry(&mut self) -> Result<Option<(u64, Entry)>, EraError> {
+        let offset = self.position;
+
+        let mut header = [0u8; HEADER_SIZE as usize];
+        let mut read = 0;
+        while read < header.len() {
+            match self.reader.read(&mut header[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(EraError::TruncatedEntry { offset }),
+                Ok(n) => read += n,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let entry_type = u16::from_le_bytes([header[0], header[1]]);
+        let len = u32::from_le_bytes([header[2], header[3], header[4], header[5]]);
+        if header[6..] != [0, 0] {
+            return Err(EraError::InvalidReservedBytes { offset })
+        }
+
+        let mut data = vec![0u8; len as usize];
+        self.reader.read_exact(&mut data).map_err(|err| match err.kind() {
+            ErrorKind::UnexpectedEof => EraError::TruncatedEntry { offset },
+            _ => err.into(),
+        })?;
+
+        let entry = Entry::new(entry_type, data);
+        self.position += entry.encoded_len();
+        Ok(Some((offset, entry)))
+    }
+}
+
+impl<R: Read> Iterator for E2StoreReader<R> {
+    type Item = Result<(u64, Entry), EraError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry().transpose()
+    }
+}
+
+/// Writes [`Entry`]s to an e2store file.
+#[derive(Debug)]
+pub struct E2StoreWriter<W> {
+    writer: W,
+    position: u64,
+}
+
+impl<W: Write> E2StoreWriter<W> {
+    /// Creates a new writer positioned at the start of the file.
+    pub const fn new(writer: W) -> Self {
+        Self { writer, position: 0 }
+    }
+
+    /// Returns the offset at which the next entry will be written.
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Writes an entry and returns the offset it was written at.
+    pub fn write_entry(&mut self, entry: &Entry) -> io::Result<u64> {
+        let len = u32::try_from(entry.data.len())
+            .map_err(|_| io::Error::new(ErrorKind::InvalidInput, "entry too large"))?;
+
+        let offset = self.position;
+        self.writer.write_all(&entry.entry_type.to_le_bytes())?;
+        self.writer.write_all(&len.to_le_bytes())?;
+        self.writer.write_all(&[0, 0])?;
+        self.writer.write_all(&entry.data)?;
+        self.position += entry.encoded_len();
+        Ok(offset)
+    }
+
+    /// Flushes the underlying writer and returns it.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Compresses data with the snappy framing format used by era entries.
+pub fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+    encoder.write_all(data)?;
+    encoder.into_inner().map_err(|err| err.into_error())
+}
+
+/// Decompresses data compressed with the snappy framing format.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, EraError> {
+    let mut out = Vec::new();
+    snap::read::FrameDecoder::new(data).read_to_end(&mut out).map_err(EraError::Snappy)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_roundtrip() {
+        let entries =
+            vec![Entry::version(), Entry::new(0x03, vec![1, 2, 3]), Entry::new(7, vec![])];
+
+        let mut writer = E2StoreWriter::new(Vec::new());
+        for entry in &entries {
+            writer.write_entry(entry).unwrap();
+        }
+        let buf = writer.finish().unwrap();
+        assert_eq!(&buf[..8], &[0x65, 0x32, 0, 0, 0, 0, 0, 0]);
+
+        let read = E2StoreReader::new(&buf[..]).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(read.iter().map(|(offset, _)| *offset).collect::<Vec<_>>(), vec![0, 8, 19]);
+        assert_eq!(read.into_iter().map(|(_, entry)| entry).collect::<Vec<_>>(), entries);
+    }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        let mut buf = vec![0x03, 0, 4, 0, 0, 0, 0, 1];
+        buf.extend([0; 4]);
+        assert!(matches!(
+            E2StoreReader::new(&buf[..]).next_entry(),
+            Err(EraError::InvalidReservedBytes { offset: 0 })
+        ));
+
+        let buf = [0x03, 0, 4, 0, 0, 0, 0, 0, 1, 2];
+        assert!(matches!(
+            E2StoreReader::new(&buf[..]).next_entry(),
+            Err(EraError::TruncatedEntry { offset: 0 })
+        ));
+    }
+
+    #[test]
+    fn snappy_roundtrip() {
+        let data = b"hello era hello era hello era".repeat(100);
+        let compressed = compress(&data).unwrap();
+        assert!(compressed.len() < data.len());
+        assert_eq!(decompress(&compressed).unwrap(), data);
+    }
+}
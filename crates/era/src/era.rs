@@ -0,0 +1,673 @@
+//! ERA archives of post-merge beacon chain history.
+//!
+//! ```text
+//! era                := group+
+//! group              := Version | block* | era-state | other-entries* | slot-index(block)? | slot-index(state)
+//! block              := CompressedSignedBeaconBlock
+//! era-state          := CompressedBeaconState
+//! slot-index(block)  := starting-slot | index | index | index ... | count
+//! ```
+//!
+//! Beacon blocks are stored as snappy compressed SSZ. Since Bellatrix every beacon block embeds the
+//! execution payload of an execution block, which [`EraReader`] extracts so that the execution
+//! history can be imported without the beacon state.
+//!
+//! The file of era `N` holds the blocks of the slots `(N - 1) * 8192..N * 8192` and the state at
+//! slot `N * 8192`, see [`EraWriter`].
+
+use crate::{
+    accumulator::merkleize,
+    e2s::{compress, decompress, E2StoreReader, E2StoreWriter, Entry, VERSION},
+    EraError,
+};
+use alloy_consensus::Block;
+use alloy_eips::{eip2718::Decodable2718, eip7685::Requests};
+use alloy_primitives::{hex, B256};
+use alloy_rpc_types_engine::{
+    ExecutionPayload, ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3,
+};
+use ssz::Decode;
+use std::{
+    fs::File,
+    io::{BufReader, Read, Write},
+    path::Path,
+};
+
+/// Type of the placeholder entry.
+pub const EMPTY: u16 = 0x00;
+/// Type of the snappy compressed SSZ signed beacon block entry.
+pub const COMPRESSED_SIGNED_BEACON_BLOCK: u16 = 0x01;
+/// Type of the snappy compressed SSZ beacon state entry.
+pub const COMPRESSED_BEACON_STATE: u16 = 0x02;
+/// Type of the slot index entry (`"i2"` in ASCII).
+pub const SLOT_INDEX: u16 = 0x3269;
+
+/// Number of slots covered by a single ERA file.
+pub const SLOTS_PER_HISTORICAL_ROOT: u64 = 8192;
+
+/// File extension of ERA files.
+pub const ERA_EXTENSION: &str = "era";
+
+/// Offset of the `body` offset within a `BeaconBlock`: `slot | proposer_index | parent_root |
+/// state_root`.
+const BLOCK_BODY_OFFSET: usize = 8 + 8 + 32 + 32;
+/// Offset of the first variable-size field offset within a `BeaconBlockBody`: `randao_reveal |
+/// eth1_data | graffiti`.
+const BODY_FIRST_OFFSET: usize = 96 + 72 + 32;
+/// Offset of the `execution_payload` offset within a `BeaconBlockBody`, which follows five
+/// operation lists and the fixed-size sync aggregate.
+const BODY_EXECUTION_PAYLOAD_OFFSET: usize = BODY_FIRST_OFFSET + 5 * 4 + 160;
+/// Size of the fixed part of a Bellatrix `BeaconBlockBody`.
+const BELLATRIX_BODY_FIXED_LEN: usize = BODY_EXECUTION_PAYLOAD_OFFSET + 4;
+/// Size of the fixed part of a Capella `BeaconBlockBody`, which adds
+/// `bls_to_execution_changes`.
+const CAPELLA_BODY_FIXED_LEN: usize = BELLATRIX_BODY_FIXED_LEN + 4;
+/// Size of the fixed part of a Deneb `BeaconBlockBody`, which adds `blob_kzg_commitments`.
+const DENEB_BODY_FIXED_LEN: usize = CAPELLA_BODY_FIXED_LEN + 4;
+/// Size of the fixed part of an Electra `BeaconBlockBody`, which adds `execution_requests`.
+const ELECTRA_BODY_FIXED_LEN: usize = DENEB_BODY_FIXED_LEN + 4;
+
+/// SSZ sizes of the deposit, withdrawal and consolidation requests, in request type order.
+const REQUEST_SIZES: [usize; 3] = [192, 76, 116];
+
+/// Offset of the `genesis_validators_root` within a `BeaconState`: `genesis_time`.
+const STATE_GENESIS_VALIDATORS_ROOT_OFFSET: usize = 8;
+/// Offset of the `slot` within a `BeaconState`: `genesis_time | genesis_validators_root`.
+const STATE_SLOT_OFFSET: usize = STATE_GENESIS_VALIDATORS_ROOT_OFFSET + 32;
+/// Offset of the `block_roots` within a `BeaconState`, which follow the `slot`, `fork` and
+/// `latest_block_header`.
+const STATE_BLOCK_ROOTS_OFFSET: usize = STATE_SLOT_OFFSET + 8 + 16 + 112;
+
+/// Returns the canonical name of an ERA file: `<network>-<era>-<short-historical-root>.era`.
+///
+/// The short historical root is the hex encoding of the first 4 bytes of the root returned by
+/// [`EraWriter::finish`].
+pub fn file_name(network: &str, era: u64, historical_root: B256) -> String {
+    format!("{network}-{era:05}-{}.{ERA_EXTENSION}", hex::encode(&historical_root[..4]))
+}
+
+/// The execution payload of a beacon block, together with the beacon block fields that are
+/// committed to by the execution block header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeaconBlockPayload {
+    /// Slot of the beacon block.
+    pub slot: u64,
+    /// Root of the parent beacon block.
+    pub parent_root: B256,
+    /// The execution payload.
+    pub payload: ExecutionPayload,
+    /// The execution requests of the block, since Electra.
+    pub requests: Option<Requests>,
+}
+
+impl BeaconBlockPayload {
+    /// Extracts the execution payload from an SSZ encoded `SignedBeaconBlock`.
+    ///
+    /// Returns `None` for blocks without an execution payload, i.e. blocks before Bellatrix and
+    /// Bellatrix blocks before the merge transition.
+    pub fn decode(signed_block: &[u8]) -> Result<Option<Self>, EraError> {
+        let message = signed_block
+            .get(read_offset(signed_block, 0)?..)
+            .ok_or(EraError::Malformed("beacon block offset out of bounds"))?;
+        let slot = u64::from_le_bytes(
+            message
+                .get(..8)
+                .ok_or(EraError::Malformed("truncated beacon block"))?
+                .try_into()
+                .expect("8 bytes"),
+        );
+        let parent_root = B256::from_slice(
+            message.get(16..48).ok_or(EraError::Malformed("truncated beacon block"))?,
+        );
+        let body = message
+            .get(read_offset(message, BLOCK_BODY_OFFSET)?..)
+            .ok_or(EraError::Malformed("beacon block body offset out of bounds"))?;
+
+        // the first offset of a container points right after its fixed part, which identifies the
+        // fork of the body
+        let fixed_len = read_offset(body, BODY_FIRST_OFFSET)?;
+        if fixed_len < BELLATRIX_BODY_FIXED_LEN {
+            return Ok(None)
+        }
+
+        let payload_start = read_offset(body, BODY_EXECUTION_PAYLOAD_OFFSET)?;
+        let payload_end = if fixed_len > BELLATRIX_BODY_FIXED_LEN {
+            read_offset(body, BELLATRIX_BODY_FIXED_LEN)?
+        } else {
+            body.len()
+        };
+        let payload = body
+            .get(payload_start..payload_end)
+            .ok_or(EraError::Malformed("execution payload offset out of bounds"))?;
+
+        let payload = match fixed_len {
+            BELLATRIX_BODY_FIXED_LEN => {
+                ExecutionPayload::V1(ExecutionPayloadV1::from_ssz_bytes(payload)?)
+            }
+            CAPELLA_BODY_FIXED_LEN => {
+                ExecutionPayload::V2(ExecutionPayloadV2::from_ssz_bytes(payload)?)
+            }
+            _ => ExecutionPayload::V3(ExecutionPayloadV3::from_ssz_bytes(payload)?),
+        };
+
+        // pre-merge bellatrix blocks carry an empty payload
+        if payload.block_hash().is_zero() {
+            return Ok(None)
+        }
+
+        let requests = if fixed_len >= ELECTRA_BODY_FIXED_LEN {
+            let requests = body
+                .get(read_offset(body, DENEB_BODY_FIXED_LEN)?..)
+                .ok_or(EraError::Malformed("execution requests offset out of bounds"))?;
+            Some(decode_requests(requests)?)
+        } else {
+            None
+        };
+
+        Ok(Some(Self { slot, parent_root, payload, requests }))
+    }
+
+    /// Returns the number of the execution block.
+    pub const fn block_number(&self) -> u64 {
+        self.payload.as_v1().block_number
+    }
+
+    /// Converts the payload into a block and verifies that the hash of the assembled header
+    /// matches the block hash of the payload.
+    pub fn try_into_block<T: Decodable2718>(self) -> Result<Block<T>, EraError> {
+        let expected = self.payload.block_hash();
+        let is_cancun = matches!(self.payload, ExecutionPayload::V3(_));
+
+        let mut block = self.payload.try_into_block::<T>()?;
+        if is_cancun {
+            block.header.parent_beacon_block_root = Some(self.parent_root);
+        }
+        if let Some(requests) = self.requests {
+            block.header.requests_hash = Some(requests.requests_hash());
+        }
+
+        let computed = block.header.hash_slow();
+        if computed != expected {
+            return Err(EraError::BlockHashMismatch {
+                block: block.header.number,
+                computed,
+                expected,
+            })
+        }
+        Ok(block)
+    }
+}
+
+/// Reads the execution payloads of the beacon blocks stored in an ERA file.
+#[derive(Debug)]
+pub struct EraReader<R> {
+    entries: E2StoreReader<R>,
+}
+
+impl EraReader<BufReader<File>> {
+    /// Opens an ERA file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, EraError> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: Read> EraReader<R> {
+    /// Creates a new reader.
+    pub const fn new(reader: R) -> Self {
+        Self { entries: E2StoreReader::new(reader) }
+    }
+
+    /// Returns the next execution payload, skipping beacon blocks without one and all other
+    /// entries.
+    pub fn next_payload(&mut self) -> Result<Option<BeaconBlockPayload>, EraError> {
+        while let Some((offset, entry)) = self.entries.next_entry()? {
+            if offset == 0 {
+                entry.expect_type(VERSION, offset)?;
+            }
+            if entry.entry_type != COMPRESSED_SIGNED_BEACON_BLOCK {
+                continue
+            }
+            if let Some(payload) = BeaconBlockPayload::decode(&decompress(&entry.data)?)? {
+                return Ok(Some(payload))
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<R: Read> Iterator for EraReader<R> {
+    type Item = Result<BeaconBlockPayload, EraError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_payload().transpose()
+    }
+}
+
+/// Writes an ERA file.
+///
+/// The beacon blocks of the era are pushed in ascending slot order, the state at the end of the
+/// era and the slot indices are written by [`EraWriter::finish`].
+#[derive(Debug)]
+pub struct EraWriter<W> {
+    writer: E2StoreWriter<W>,
+    era: u64,
+    /// Offsets of the block entries by slot, zero for empty slots.
+    block_offsets: Vec<u64>,
+}
+
+impl<W: Write> EraWriter<W> {
+    /// Creates a new writer for the given era and writes the version entry.
+    pub fn new(writer: W, era: u64) -> Result<Self, EraError> {
+        let mut writer = E2StoreWriter::new(writer);
+        writer.write_entry(&Entry::version())?;
+        Ok(Self { writer, era, block_offsets: Vec::new() })
+    }
+
+    /// Returns the first slot of the blocks of the era.
+    pub const fn start_slot(&self) -> u64 {
+        self.era.saturating_sub(1) * SLOTS_PER_HISTORICAL_ROOT
+    }
+
+    /// Returns the slot of the state at the end of the era.
+    pub const fn state_slot(&self) -> u64 {
+        self.era * SLOTS_PER_HISTORICAL_ROOT
+    }
+
+    /// Appends the SSZ encoded `SignedBeaconBlock` of the given slot.
+    ///
+    /// Slots without a block are skipped.
+    pub fn push_block(&mut self, slot: u64, signed_block: &[u8]) -> Result<(), EraError> {
+        let next_slot = self.start_slot() + self.block_offsets.len() as u64;
+        if self.era == 0 || slot < next_slot || slot >= self.state_slot() {
+            return Err(EraError::UnexpectedSlot { expected: next_slot, got: slot })
+        }
+        self.block_offsets.resize((slot - self.start_slot()) as usize, 0);
+
+        let offset = self
+            .writer
+            .write_entry(&Entry::new(COMPRESSED_SIGNED_BEACON_BLOCK, compress(signed_block)?))?;
+        self.block_offsets.push(offset);
+        Ok(())
+    }
+
+    /// Writes the SSZ encoded `BeaconState` at the end of the era and the slot indices, and returns
+    /// the underlying writer together with the historical root of the era.
+    ///
+    /// The historical root is the root of the `block_roots` of the state, or the
+    /// `genesis_validators_root` for era zero.
+    pub fn finish(mut self, state: &[u8]) -> Result<(W, B256), EraError> {
+        let block_roots = state
+            .get(STATE_BLOCK_ROOTS_OFFSET..)
+            .and_then(|roots| roots.get(..SLOTS_PER_HISTORICAL_ROOT as usize * 32))
+            .ok_or(EraError::Malformed("truncated beacon state"))?;
+        let state_slot = u64::from_le_bytes(
+            state[STATE_SLOT_OFFSET..STATE_SLOT_OFFSET + 8].try_into().expect("8 bytes"),
+        );
+        if state_slot != self.state_slot() {
+            return Err(EraError::UnexpectedSlot { expected: self.state_slot(), got: state_slot })
+        }
+        let historical_root = if self.era == 0 {
+            B256::from_slice(
+                &state[STATE_GENESIS_VALIDATORS_ROOT_OFFSET..
+                    STATE_GENESIS_VALIDATORS_ROOT_OFFSET + 32],
+            )
+        } else {
+            merkleize(
+                block_roots.chunks_exact(32).map(B256::from_slice).collect(),
+                SLOTS_PER_HISTORICAL_ROOT.trailing_zeros() as usize,
+            )
+        };
+
+        let state_offset =
+            self.writer.write_entry(&Entry::new(COMPRESSED_BEACON_STATE, compress(state)?))?;
+
+        // there are no blocks in era zero, so its block index is omitted
+        if self.era > 0 {
+            self.block_offsets.resize(SLOTS_PER_HISTORICAL_ROOT as usize, 0);
+            let offsets = std::mem::take(&mut self.block_offsets);
+            self.write_slot_index(self.start_slot(), &offsets)?;
+        }
+        self.write_slot_index(self.state_slot(), &[state_offset])?;
+
+        Ok((self.writer.finish()?, historical_root))
+    }
+
+    /// Writes a slot index of the given entry offsets, offsets of zero are kept for empty slots.
+    fn write_slot_index(&mut self, start_slot: u64, offsets: &[u64]) -> Result<(), EraError> {
+        let index_offset = self.writer.position() as i64;
+        let mut index = Vec::with_capacity((offsets.len() + 2) * 8);
+        index.extend_from_slice(&(start_slot as i64).to_le_bytes());
+        for offset in offsets {
+            let relative = if *offset == 0 { 0 } else { *offset as i64 - index_offset };
+            index.extend_from_slice(&relative.to_le_bytes());
+        }
+        index.extend_from_slice(&(offsets.len() as i64).to_le_bytes());
+        self.writer.write_entry(&Entry::new(SLOT_INDEX, index))?;
+        Ok(())
+    }
+}
+
+/// Reads an SSZ offset at the given position.
+fn read_offset(buf: &[u8], pos: usize) -> Result<usize, EraError> {
+    let bytes = buf.get(pos..pos + 4).ok_or(EraError::Malformed("truncated ssz offset"))?;
+    Ok(u32::from_le_bytes(bytes.try_into().expect("4 bytes")) as usize)
+}
+
+/// Decodes the SSZ `ExecutionRequests` container into EIP-7685 requests.
+fn decode_requests(buf: &[u8]) -> Result<Requests, EraError> {
+    let offsets = [read_offset(buf, 0)?, read_offset(buf, 4)?, read_offset(buf, 8)?, buf.len()];
+    if offsets[0] != 12 {
+        return Err(EraError::Malformed("invalid execution requests offset"))
+    }
+
+    let mut requests = Requests::default();
+    for (request_type, size) in REQUEST_SIZES.into_iter().enumerate() {
+        let data = buf
+            .get(offsets[request_type]..offsets[request_type + 1])
+            .ok_or(EraError::Malformed("execution requests offset out of bounds"))?;
+        if data.len() % size != 0 {
+            return Err(EraError::Malformed("invalid execution request length"))
+        }
+        requests.push_request_with_type(request_type as u8, data.iter().copied());
+    }
+    Ok(requests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bytes, U256};
+    use reth_primitives::TransactionSigned;
+    use ssz::Encode;
+
+    fn payload_v1(number: u64) -> ExecutionPayloadV1 {
+        ExecutionPayloadV1 {
+            parent_hash: B256::repeat_byte(1),
+            fee_recipient: Default::default(),
+            state_root: B256::repeat_byte(2),
+            receipts_root: alloy_consensus::constants::EMPTY_RECEIPTS,
+            logs_bloom: Default::default(),
+            prev_randao: B256::repeat_byte(3),
+            block_number: number,
+            gas_limit: 30_000_000,
+            gas_used: 0,
+            timestamp: 1_700_000_000 + number,
+            extra_data: Bytes::new(),
+            base_fee_per_gas: U256::from(7),
+            block_hash: B256::ZERO,
+            transactions: vec![],
+        }
+    }
+
+    /// Sets the block hash of the payload to the hash of the block it assembles to.
+    fn seal(mut payload: BeaconBlockPayload) -> BeaconBlockPayload {
+        let expected = payload.payload.block_hash();
+        let block_hash = match payload.clone().try_into_block::<TransactionSigned>() {
+            Err(EraError::BlockHashMismatch { computed, expected: e, .. }) if e == expected => {
+                computed
+            }
+            res => panic!("unexpected result {res:?}"),
+        };
+        payload.payload.as_v1_mut().block_hash = block_hash;
+        payload
+    }
+
+    /// Encodes a `SignedBeaconBlock` whose body has the fixed part size of the given fork.
+    fn signed_block(
+        slot: u64,
+        parent_root: B256,
+        fixed_len: usize,
+        payload: Option<&[u8]>,
+        requests: Option<&[u8]>,
+    ) -> Vec<u8> {
+        let mut body = vec![0u8; fixed_len];
+        let put_offset = |body: &mut Vec<u8>, pos: usize| {
+            let offset = body.len() as u32;
+            body[pos..pos + 4].copy_from_slice(&offset.to_le_bytes());
+        };
+        // empty operation lists
+        for i in 0..5 {
+            put_offset(&mut body, BODY_FIRST_OFFSET + i * 4);
+        }
+        if let Some(payload) = payload {
+            put_offset(&mut body, BODY_EXECUTION_PAYLOAD_OFFSET);
+            body.extend_from_slice(payload);
+            let mut pos = BELLATRIX_BODY_FIXED_LEN;
+            while pos + 4 <= fixed_len {
+                put_offset(&mut body, pos);
+                pos += 4;
+            }
+            if let Some(requests) = requests {
+                put_offset(&mut body, DENEB_BODY_FIXED_LEN);
+                body.extend_from_slice(requests);
+            }
+        }
+
+        let mut message = Vec::new();
+        message.extend_from_slice(&slot.to_le_bytes());
+        message.extend_from_slice(&0u64.to_le_bytes());
+        message.extend_from_slice(parent_root.as_slice());
+        message.extend_from_slice(B256::ZERO.as_slice());
+        message.extend_from_slice(&((BLOCK_BODY_OFFSET + 4) as u32).to_le_bytes());
+        message.extend_from_slice(&body);
+
+        let mut signed = Vec::new();
+        signed.extend_from_slice(&100u32.to_le_bytes());
+        signed.extend_from_slice(&[0u8; 96]);
+        signed.extend_from_slice(&message);
+        signed
+    }
+
+    #[test]
+    fn decode_capella_block() {
+        let payload = seal(BeaconBlockPayload {
+            slot: 10,
+            parent_root: B256::repeat_byte(9),
+            payload: ExecutionPayload::V2(ExecutionPayloadV2 {
+                payload_inner: payload_v1(100),
+                withdrawals: vec![],
+            }),
+            requests: None,
+        });
+        let ExecutionPayload::V2(inner) = &payload.payload else { unreachable!() };
+        let encoded = signed_block(
+            10,
+            payload.parent_root,
+            CAPELLA_BODY_FIXED_LEN,
+            Some(&inner.as_ssz_bytes()),
+            None,
+        );
+
+        let decoded = BeaconBlockPayload::decode(&encoded).unwrap().unwrap();
+        assert_eq!(decoded, payload);
+        assert_eq!(decoded.block_number(), 100);
+        let block = decoded.try_into_block::<TransactionSigned>().unwrap();
+        assert_eq!(block.header.parent_beacon_block_root, None);
+        assert_eq!(block.body.withdrawals.map(|w| w.len()), Some(0));
+    }
+
+    #[test]
+    fn decode_electra_block() {
+        let mut requests = Vec::new();
+        requests.extend_from_slice(&12u32.to_le_bytes());
+        requests.extend_from_slice(&12u32.to_le_bytes());
+        requests.extend_from_slice(&(12u32 + 76).to_le_bytes());
+        requests.extend_from_slice(&[5u8; 76]);
+
+        let mut expected_requests = Requests::default();
+        expected_requests.push_request_with_type(1, [5u8; 76]);
+
+        let payload = seal(BeaconBlockPayload {
+            slot: 11,
+            parent_root: B256::repeat_byte(8),
+            payload: ExecutionPayload::V3(ExecutionPayloadV3 {
+                payload_inner: ExecutionPayloadV2 {
+                    payload_inner: payload_v1(101),
+                    withdrawals: vec![],
+                },
+                blob_gas_used: 0,
+                excess_blob_gas: 0,
+            }),
+            requests: Some(expected_requests.clone()),
+        });
+        let ExecutionPayload::V3(inner) = &payload.payload else { unreachable!() };
+        let encoded = signed_block(
+            11,
+            payload.parent_root,
+            ELECTRA_BODY_FIXED_LEN,
+            Some(&inner.as_ssz_bytes()),
+            Some(&requests),
+        );
+
+        let decoded = BeaconBlockPayload::decode(&encoded).unwrap().unwrap();
+        assert_eq!(decoded, payload);
+        let block = decoded.try_into_block::<TransactionSigned>().unwrap();
+        assert_eq!(block.header.parent_beacon_block_root, Some(B256::repeat_byte(8)));
+        assert_eq!(block.header.requests_hash, Some(expected_requests.requests_hash()));
+    }
+
+    #[test]
+    fn reader_skips_blocks_without_payload() {
+        let altair = signed_block(1, B256::ZERO, BODY_EXECUTION_PAYLOAD_OFFSET, None, None);
+        let empty_payload = ExecutionPayloadV1 { block_hash: B256::ZERO, ..payload_v1(0) };
+        let bellatrix_premerge = signed_block(
+            2,
+            B256::ZERO,
+            BELLATRIX_BODY_FIXED_LEN,
+            Some(&empty_payload.as_ssz_bytes()),
+            None,
+        );
+        let payload = seal(BeaconBlockPayload {
+            slot: 3,
+            parent_root: B256::ZERO,
+            payload: ExecutionPayload::V1(payload_v1(1)),
+            requests: None,
+        });
+        let ExecutionPayload::V1(inner) = &payload.payload else { unreachable!() };
+        let bellatrix = signed_block(
+            3,
+            B256::ZERO,
+            BELLATRIX_BODY_FIXED_LEN,
+            Some(&inner.as_ssz_bytes()),
+            None,
+        );
+
+        let mut writer = E2StoreWriter::new(Vec::new());
+        writer.write_entry(&Entry::version()).unwrap();
+        for block in [altair, bellatrix_premerge, bellatrix] {
+            writer
+                .write_entry(&Entry::new(COMPRESSED_SIGNED_BEACON_BLOCK, compress(&block).unwrap()))
+                .unwrap();
+        }
+        writer
+            .write_entry(&Entry::new(COMPRESSED_BEACON_STATE, compress(&[1, 2]).unwrap()))
+            .unwrap();
+        writer.write_entry(&Entry::new(SLOT_INDEX, vec![0; 24])).unwrap();
+        let buf = writer.finish().unwrap();
+
+        let payloads = EraReader::new(&buf[..]).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(payloads, vec![payload]);
+    }
+
+    /// Encodes the prefix of a `BeaconState` up to and including the `block_roots`.
+    fn state(slot: u64, block_roots: &[B256]) -> Vec<u8> {
+        let mut state = vec![0u8; STATE_BLOCK_ROOTS_OFFSET];
+        state[STATE_GENESIS_VALIDATORS_ROOT_OFFSET..STATE_SLOT_OFFSET].fill(7);
+        state[STATE_SLOT_OFFSET..STATE_SLOT_OFFSET + 8].copy_from_slice(&slot.to_le_bytes());
+        for slot in 0..SLOTS_PER_HISTORICAL_ROOT as usize {
+            state.extend_from_slice(block_roots.get(slot).unwrap_or(&B256::ZERO).as_slice());
+        }
+        state
+    }
+
+    /// Returns the entries of the given slot index.
+    fn slot_index(entry: &Entry) -> Vec<i64> {
+        entry
+            .data
+            .chunks_exact(8)
+            .map(|chunk| i64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn era_roundtrip() {
+        let payload = seal(BeaconBlockPayload {
+            slot: 8194,
+            parent_root: B256::ZERO,
+            payload: ExecutionPayload::V1(payload_v1(1)),
+            requests: None,
+        });
+        let ExecutionPayload::V1(inner) = &payload.payload else { unreachable!() };
+        let block = signed_block(
+            8194,
+            B256::ZERO,
+            BELLATRIX_BODY_FIXED_LEN,
+            Some(&inner.as_ssz_bytes()),
+            None,
+        );
+        let altair = signed_block(8192, B256::ZERO, BODY_EXECUTION_PAYLOAD_OFFSET, None, None);
+        let block_roots = [B256::repeat_byte(1), B256::repeat_byte(1), B256::repeat_byte(2)];
+
+        let mut writer = EraWriter::new(Vec::new(), 2).unwrap();
+        assert_eq!(writer.start_slot(), 8192);
+        assert_eq!(writer.state_slot(), 16384);
+        writer.push_block(8192, &altair).unwrap();
+        writer.push_block(8194, &block).unwrap();
+        assert!(matches!(
+            writer.push_block(8193, &block),
+            Err(EraError::UnexpectedSlot { expected: 8195, got: 8193 })
+        ));
+        assert!(matches!(
+            EraWriter::new(Vec::new(), 2).unwrap().finish(&state(8192, &block_roots)),
+            Err(EraError::UnexpectedSlot { expected: 16384, got: 8192 })
+        ));
+        let (buf, historical_root) = writer.finish(&state(16384, &block_roots)).unwrap();
+
+        let mut leaves = block_roots.to_vec();
+        leaves.resize(SLOTS_PER_HISTORICAL_ROOT as usize, B256::ZERO);
+        assert_eq!(historical_root, merkleize(leaves, 13));
+        assert_eq!(
+            file_name("mainnet", 2, historical_root),
+            format!("mainnet-00002-{}.era", hex::encode(&historical_root[..4]))
+        );
+
+        let payloads = EraReader::new(&buf[..]).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(payloads, vec![payload]);
+
+        let mut entries = E2StoreReader::new(&buf[..]);
+        let entries = std::iter::from_fn(|| entries.next_entry().unwrap()).collect::<Vec<_>>();
+        let [(_, version), (altair_offset, _), (block_offset, _), (state_offset, state_entry), (block_index_offset, block_index), (state_index_offset, state_index)] =
+            entries.as_slice()
+        else {
+            panic!("unexpected entries {entries:?}")
+        };
+        assert_eq!(version.entry_type, VERSION);
+        assert_eq!(state_entry.entry_type, COMPRESSED_BEACON_STATE);
+
+        let block_index = slot_index(block_index);
+        assert_eq!(block_index.len(), SLOTS_PER_HISTORICAL_ROOT as usize + 2);
+        assert_eq!(block_index[0], 8192);
+        assert_eq!(block_index[1], *altair_offset as i64 - *block_index_offset as i64);
+        assert_eq!(block_index[2], 0);
+        assert_eq!(block_index[3], *block_offset as i64 - *block_index_offset as i64);
+        assert_eq!(block_index[block_index.len() - 1], SLOTS_PER_HISTORICAL_ROOT as i64);
+        assert_eq!(
+            slot_index(state_index),
+            vec![16384, *state_offset as i64 - *state_index_offset as i64, 1]
+        );
+    }
+
+    #[test]
+    fn genesis_era() {
+        let mut writer = EraWriter::new(Vec::new(), 0).unwrap();
+        assert!(matches!(writer.push_block(0, &[]), Err(EraError::UnexpectedSlot { .. })));
+        let (buf, historical_root) = writer.finish(&state(0, &[])).unwrap();
+        assert_eq!(historical_root, B256::repeat_byte(7));
+
+        let mut entries = E2StoreReader::new(&buf[..]);
+        let types = std::iter::from_fn(|| entries.next_entry().unwrap())
+            .map(|(_, entry)| entry.entry_type)
+            .collect::<Vec<_>>();
+        assert_eq!(types, vec![VERSION, COMPRESSED_BEACON_STATE, SLOT_INDEX]);
+    }
+}
@@ -0,0 +1,539 @@
+//! ERA1 archives of pre-merge execution history.
+//!
+//! ```text
+//! era1        := Version | block-tuple* | other-entries* | Accumulator | BlockIndex
+//! block-tuple := CompressedHeader | CompressedBody | CompressedReceipts | TotalDifficulty
+//! block-index := starting-number | index | index | index ... | count
+//! ```
+//!
+//! Headers, bodies and receipts are stored as snappy compressed RLP, the total difficulty as a
+//! little endian `uint256`. Each file holds at most [`MAX_BLOCKS_PER_ERA1`] blocks and commits to
+//! them with the epoch accumulator root.
+
+use crate::{
+    accumulator_root,
+    e2s::{compress, decompress, E2StoreReader, E2StoreWriter, Entry},
+    EraError, HeaderRecord, TrustedAccumulators,
+};
+use alloy_consensus::{proofs::calculate_receipt_root, BlockHeader, ReceiptWithBloom};
+use alloy_primitives::{hex, keccak256, BlockNumber, B256, U256};
+use alloy_rlp::Decodable;
+use reth_ethereum_primitives::Receipt;
+use std::{
+    fs::File,
+    io::{BufReader, Read, Write},
+    path::Path,
+};
+
+/// Type of the snappy compressed RLP header entry.
+pub const COMPRESSED_HEADER: u16 = 0x03;
+/// Type of the snappy compressed RLP body entry.
+pub const COMPRESSED_BODY: u16 = 0x04;
+/// Type of the snappy compressed RLP receipts entry.
+pub const COMPRESSED_RECEIPTS: u16 = 0x05;
+/// Type of the total difficulty entry.
+pub const TOTAL_DIFFICULTY: u16 = 0x06;
+/// Type of the epoch accumulator root entry.
+pub const ACCUMULATOR: u16 = 0x07;
+/// Type of the block index entry (`"f2"` in ASCII).
+pub const BLOCK_INDEX: u16 = 0x3266;
+
+/// Maximum number of blocks stored in a single ERA1 file.
+pub const MAX_BLOCKS_PER_ERA1: usize = 8192;
+
+/// File extension of ERA1 files.
+pub const ERA1_EXTENSION: &str = "era1";
+
+/// Returns the canonical name of an ERA1 file: `<network>-<epoch>-<short-root>.era1`.
+///
+/// The short root is the hex encoding of the first 4 bytes of the accumulator root.
+pub fn file_name(network: &str, epoch: u64, accumulator: B256) -> String {
+    format!("{network}-{epoch:05}-{}.{ERA1_EXTENSION}", hex::encode(&accumulator[..4]))
+}
+
+/// A block stored in an ERA1 file.
+///
+/// The header, body and receipts are kept as uncompressed RLP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockTuple {
+    /// The RLP encoded header.
+    pub header: Vec<u8>,
+    /// The RLP encoded body.
+    pub body: Vec<u8>,
+    /// The RLP encoded list of receipts.
+    pub receipts: Vec<u8>,
+    /// The total difficulty of the chain up to and including this block.
+    pub total_difficulty: U256,
+}
+
+impl BlockTuple {
+    /// Returns the hash of the block.
+    pub fn hash(&self) -> B256 {
+        keccak256(&self.header)
+    }
+
+    /// Returns the accumulator record of this block.
+    pub fn header_record(&self) -> HeaderRecord {
+        HeaderRecord { block_hash: self.hash(), total_difficulty: self.total_difficulty }
+    }
+
+    /// Decodes the header.
+    pub fn decode_header<H: Decodable>(&self) -> Result<H, EraError> {
+        Ok(H::decode(&mut self.header.as_slice())?)
+    }
+
+    /// Decodes the body.
+    pub fn decode_body<B: Decodable>(&self) -> Result<B, EraError> {
+        Ok(B::decode(&mut self.body.as_slice())?)
+    }
+
+    /// Decodes the receipts.
+    pub fn decode_receipts(&self) -> Result<Vec<ReceiptWithBloom<Receipt>>, EraError> {
+        Ok(Vec::decode(&mut self.receipts.as_slice())?)
+    }
+
+    /// Returns the receipts root of the stored receipts.
+    pub fn receipts_root(&self) -> Result<B256, EraError> {
+        Ok(calculate_receipt_root(&self.decode_receipts()?))
+    }
+}
+
+/// The contents of an ERA1 file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Era1File {
+    /// Number of the first block in the file.
+    pub start_block: BlockNumber,
+    /// The blocks in the file, in ascending order.
+    pub blocks: Vec<BlockTuple>,
+    /// The accumulator root stored in the file.
+    pub accumulator: B256,
+}
+
+impl Era1File {
+    /// Reads an ERA1 file from disk.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, EraError> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads an ERA1 file and validates its structure and block index.
+    ///
+    /// This does not verify the accumulator, see [`Self::verify`].
+    pub fn read<R: Read>(reader: R) -> Result<Self, EraError> {
+        let mut entries = E2StoreReader::new(reader);
+
+        let (offset, version) = entries.next_entry()?.ok_or(EraError::Malformed("empty file"))?;
+        version.expect_type(crate::e2s::VERSION, offset)?;
+
+        let mut blocks = Vec::new();
+        let mut header_offsets = Vec::new();
+        let (accumulator, index_offset, index) = loop {
+            let (offset, entry) =
+                entries.next_entry()?.ok_or(EraError::Malformed("missing accumulator"))?;
+            match entry.entry_type {
+                COMPRESSED_HEADER => {
+                    if blocks.len() == MAX_BLOCKS_PER_ERA1 {
+                        return Err(EraError::TooManyBlocks(MAX_BLOCKS_PER_ERA1))
+                    }
+                    let mut next = |expected| -> Result<Vec<u8>, EraError> {
+                        let (offset, entry) = entries
+                            .next_entry()?
+                            .ok_or(EraError::Malformed("incomplete block tuple"))?;
+                        entry.expect_type(expected, offset)?;
+                        Ok(entry.data)
+                    };
+                    let body = next(COMPRESSED_BODY)?;
+                    let receipts = next(COMPRESSED_RECEIPTS)?;
+                    let total_difficulty = next(TOTAL_DIFFICULTY)?;
+                    if total_difficulty.len() != 32 {
+                        return Err(EraError::Malformed("invalid total difficulty length"))
+                    }
+
+                    header_offsets.push(offset);
+                    blocks.push(BlockTuple {
+                        header: decompress(&entry.data)?,
+                        body: decompress(&body)?,
+                        receipts: decompress(&receipts)?,
+                        total_difficulty: U256::from_le_slice(&total_difficulty),
+                    });
+                }
+                ACCUMULATOR => {
+                    if entry.data.len() != 32 {
+                        return Err(EraError::Malformed("invalid accumulator length"))
+                    }
+                    let (index_offset, index) =
+                        entries.next_entry()?.ok_or(EraError::Malformed("missing block index"))?;
+                    index.expect_type(BLOCK_INDEX, index_offset)?;
+                    break (B256::from_slice(&entry.data), index_offset, index.data)
+                }
+                _ => {}
+            }
+        };
+
+        if entries.next_entry()?.is_some() {
+            return Err(EraError::Malformed("entries after block index"))
+        }
+
+        let start_block = decode_block_index(&index, index_offset, &header_offsets)?;
+        Ok(Self { start_block, blocks, accumulator })
+    }
+
+    /// Returns the number of the last block in the file.
+    pub fn end_block(&self) -> Option<BlockNumber> {
+        (!self.blocks.is_empty()).then(|| self.start_block + self.blocks.len() as u64 - 1)
+    }
+
+    /// Returns the epoch of the file.
+    pub const fn epoch(&self) -> u64 {
+        self.start_block / MAX_BLOCKS_PER_ERA1 as u64
+    }
+
+    /// Returns the accumulator records of the blocks in the file.
+    pub fn header_records(&self) -> Vec<HeaderRecord> {
+        self.blocks.iter().map(BlockTuple::header_record).collect()
+    }
+
+    /// Returns an error if the stored accumulator root does not commit to the blocks of the file.
+    pub fn verify_accumulator(&self) -> Result<(), EraError> {
+        let computed = accumulator_root(&self.header_records());
+        if computed != self.accumulator {
+            return Err(EraError::AccumulatorMismatch { computed, stored: self.accumulator })
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the given file name does not commit to the stored accumulator root.
+    ///
+    /// Only names following the `<network>-<epoch>-<short-root>.era1` convention are checked.
+    pub fn verify_file_name(&self, name: &str) -> Result<(), EraError> {
+        let Some(short_root) = name
+            .strip_suffix(ERA1_EXTENSION)
+            .and_then(|stem| stem.strip_suffix('.'))
+            .and_then(|stem| stem.rsplit('-').next())
+            .filter(|short_root| short_root.len() == 8)
+        else {
+            return Ok(())
+        };
+
+        if !short_root.eq_ignore_ascii_case(&hex::encode(&self.accumulator[..4])) {
+            return Err(EraError::FileNameMismatch {
+                name: name.to_string(),
+                root: self.accumulator,
+            })
+        }
+        Ok(())
+    }
+
+    /// Verifies the contents of the file against the trusted accumulator roots.
+    ///
+    /// This checks that
+    /// - the accumulator root commits to the block hashes and total difficulties,
+    /// - the accumulator root is the trusted root of the file's epoch,
+    /// - the headers form a chain starting at [`Self::start_block`],
+    /// - the total difficulties are consistent with the header difficulties,
+    /// - the receipts match the receipts root of their header.
+    pub fn verify<H: BlockHeader + Decodable>(
+        &self,
+        trusted: &TrustedAccumulators,
+    ) -> Result<(), EraError> {
+        self.verify_accumulator()?;
+        if self.start_block % MAX_BLOCKS_PER_ERA1 as u64 != 0 {
+            return Err(EraError::Malformed("file does not start at an epoch boundary"))
+        }
+        trusted.verify(self.epoch(), self.accumulator)?;
+
+        let mut parent: Option<(B256, U256)> = None;
+        for (number, block) in (self.start_block..).zip(&self.blocks) {
+            let header = block.decode_header::<H>()?;
+            if header.number() != number {
+                return Err(EraError::UnexpectedBlockNumber {
+                    expected: number,
+                    got: header.number(),
+                })
+            }
+
+            let parent_td = match parent {
+                Some((parent_hash, parent_td)) => {
+                    if header.parent_hash() != parent_hash {
+                        return Err(EraError::Malformed("blocks do not form a chain"))
+                    }
+                    Some(parent_td)
+                }
+                None if number == 0 => Some(U256::ZERO),
+                None => None,
+            };
+            if parent_td.is_some_and(|td| td + header.difficulty() != block.total_difficulty) {
+                return Err(EraError::InvalidTotalDifficulty(number))
+            }
+
+            let receipts_root = block.receipts_root()?;
+            if receipts_root != header.receipts_root() {
+                return Err(EraError::ReceiptsRootMismatch {
+                    block: number,
+                    computed: receipts_root,
+                    expected: header.receipts_root(),
+                })
+            }
+
+            parent = Some((block.hash(), block.total_difficulty));
+        }
+        Ok(())
+    }
+}
+
+/// Decodes the block index and checks that it points at the given header entries.
+///
+/// Returns the starting block number.
+fn decode_block_index(
+    index: &[u8],
+    index_offset: u64,
+    header_offsets: &[u64],
+) -> Result<BlockNumber, EraError> {
+    if index.len() < 16 || index.len() % 8 != 0 {
+        return Err(EraError::InvalidIndex("invalid length"))
+    }
+    let values = index
+        .chunks_exact(8)
+        .map(|chunk| i64::from_le_bytes(chunk.try_into().expect("8 bytes")))
+        .collect::<Vec<_>>();
+
+    let (start, rest) = values.split_first().expect("at least two values");
+    let (count, offsets) = rest.split_last().expect("at least two values");
+
+    if *count as usize != offsets.len() || offsets.len() != header_offsets.len() {
+        return Err(EraError::InvalidIndex("block count"))
+    }
+    for (relative, absolute) in offsets.iter().zip(header_offsets) {
+        if index_offset as i64 + relative != *absolute as i64 {
+            return Err(EraError::InvalidIndex("block offset"))
+        }
+    }
+
+    u64::try_from(*start).map_err(|_| EraError::InvalidIndex("starting number"))
+}
+
+/// Writes an ERA1 file.
+///
+/// Blocks must be pushed in ascending order. The accumulator and block index are written by
+/// [`Era1Writer::finish`].
+#[derive(Debug)]
+pub struct Era1Writer<W> {
+    writer: E2StoreWriter<W>,
+    start_block: Option<BlockNumber>,
+    header_offsets: Vec<u64>,
+    records: Vec<HeaderRecord>,
+}
+
+impl<W: Write> Era1Writer<W> {
+    /// Creates a new writer and writes the version entry.
+    pub fn new(writer: W) -> Result<Self, EraError> {
+        let mut writer = E2StoreWriter::new(writer);
+        writer.write_entry(&Entry::version())?;
+        Ok(Self { writer, start_block: None, header_offsets: Vec::new(), records: Vec::new() })
+    }
+
+    /// Returns the number of blocks written so far.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no blocks have been written.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Appends a block to the file.
+    pub fn push(&mut self, number: BlockNumber, block: &BlockTuple) -> Result<(), EraError> {
+        if self.records.len() == MAX_BLOCKS_PER_ERA1 {
+            return Err(EraError::TooManyBlocks(MAX_BLOCKS_PER_ERA1))
+        }
+        let start_block = *self.start_block.get_or_insert(number);
+        let expected = start_block + self.records.len() as u64;
+        if number != expected {
+            return Err(EraError::UnexpectedBlockNumber { expected, got: number })
+        }
+
+        let offset =
+            self.writer.write_entry(&Entry::new(COMPRESSED_HEADER, compress(&block.header)?))?;
+        self.writer.write_entry(&Entry::new(COMPRESSED_BODY, compress(&block.body)?))?;
+        self.writer.write_entry(&Entry::new(COMPRESSED_RECEIPTS, compress(&block.receipts)?))?;
+        self.writer.write_entry(&Entry::new(
+            TOTAL_DIFFICULTY,
+            block.total_difficulty.to_le_bytes::<32>().to_vec(),
+        ))?;
+
+        self.header_offsets.push(offset);
+        self.records.push(block.header_record());
+        Ok(())
+    }
+
+    /// Writes the accumulator and block index and returns the underlying writer together with the
+    /// accumulator root.
+    pub fn finish(mut self) -> Result<(W, B256), EraError> {
+        let accumulator = accumulator_root(&self.records);
+        self.writer.write_entry(&Entry::new(ACCUMULATOR, accumulator.to_vec()))?;
+
+        let index_offset = self.writer.position() as i64;
+        let mut index = Vec::with_capacity((self.header_offsets.len() + 2) * 8);
+        index.extend_from_slice(&(self.start_block.unwrap_or_default() as i64).to_le_bytes());
+        for offset in &self.header_offsets {
+            index.extend_from_slice(&(*offset as i64 - index_offset).to_le_bytes());
+        }
+        index.extend_from_slice(&(self.header_offsets.len() as i64).to_le_bytes());
+        self.writer.write_entry(&Entry::new(BLOCK_INDEX, index))?;
+
+        Ok((self.writer.finish()?, accumulator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, TxType};
+    use alloy_primitives::Log;
+    use alloy_rlp::Encodable;
+    use reth_primitives::BlockBody;
+
+    fn receipt(cumulative_gas_used: u64) -> ReceiptWithBloom<Receipt> {
+        Receipt {
+            tx_type: TxType::Legacy,
+            success: true,
+            cumulative_gas_used,
+            logs: vec![Log::empty()],
+        }
+        .into()
+    }
+
+    fn chain(start: BlockNumber, len: u64) -> Vec<BlockTuple> {
+        let mut parent_hash = B256::ZERO;
+        let mut total_difficulty = U256::from(start * 10);
+        (start..start + len)
+            .map(|number| {
+                let receipts = vec![receipt(21_000), receipt(42_000)];
+                let header = Header {
+                    number,
+                    parent_hash,
+                    difficulty: U256::from(10),
+                    receipts_root: calculate_receipt_root(&receipts),
+                    ..Default::default()
+                };
+                total_difficulty += header.difficulty;
+
+                let mut encoded_header = Vec::new();
+                header.encode(&mut encoded_header);
+                let mut body = Vec::new();
+                <BlockBody as Encodable>::encode(&BlockBody::default(), &mut body);
+                let mut encoded_receipts = Vec::new();
+                receipts.encode(&mut encoded_receipts);
+
+                let block = BlockTuple {
+                    header: encoded_header,
+                    body,
+                    receipts: encoded_receipts,
+                    total_difficulty,
+                };
+                parent_hash = block.hash();
+                block
+            })
+            .collect()
+    }
+
+    /// Trusts the accumulator root of the given file.
+    fn trusted(file: &Era1File) -> TrustedAccumulators {
+        let mut roots = vec![B256::ZERO; file.epoch() as usize + 1];
+        roots[file.epoch() as usize] = accumulator_root(&file.header_records());
+        TrustedAccumulators::new(roots)
+    }
+
+    fn write(start: BlockNumber, blocks: &[BlockTuple]) -> (Vec<u8>, B256) {
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        for (number, block) in (start..).zip(blocks) {
+            writer.push(number, block).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn era1_roundtrip() {
+        let blocks = chain(8192, 16);
+        let (buf, accumulator) = write(8192, &blocks);
+
+        let file = Era1File::read(&buf[..]).unwrap();
+        assert_eq!(file.start_block, 8192);
+        assert_eq!(file.end_block(), Some(8207));
+        assert_eq!(file.epoch(), 1);
+        assert_eq!(file.accumulator, accumulator);
+        assert_eq!(file.blocks, blocks);
+
+        file.verify::<Header>(&trusted(&file)).unwrap();
+        file.verify_file_name(&file_name("mainnet", file.epoch(), accumulator)).unwrap();
+        assert!(file.verify_file_name("mainnet-00001-00000000.era1").is_err());
+
+        let header = file.blocks[3].decode_header::<Header>().unwrap();
+        assert_eq!(header.number, 8195);
+        assert_eq!(file.blocks[3].decode_body::<BlockBody>().unwrap(), BlockBody::default());
+        assert_eq!(file.blocks[3].decode_receipts().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn detects_tampering() {
+        let mut blocks = chain(0, 4);
+        blocks[2].total_difficulty += U256::from(1);
+        let (buf, _) = write(0, &blocks);
+        let file = Era1File::read(&buf[..]).unwrap();
+        // the accumulator commits to the modified total difficulty, but the chain does not
+        file.verify_accumulator().unwrap();
+        assert!(matches!(
+            file.verify::<Header>(&trusted(&file)),
+            Err(EraError::InvalidTotalDifficulty(2))
+        ));
+
+        let blocks = chain(0, 4);
+        let (buf, _) = write(0, &blocks);
+        let mut file = Era1File::read(&buf[..]).unwrap();
+        file.blocks[1].receipts = file.blocks[0].receipts.clone();
+        file.blocks.swap(2, 3);
+        assert!(matches!(
+            file.verify::<Header>(&trusted(&file)),
+            Err(EraError::AccumulatorMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_untrusted_accumulator() {
+        let blocks = chain(8192, 4);
+        let (buf, accumulator) = write(8192, &blocks);
+        let file = Era1File::read(&buf[..]).unwrap();
+
+        // the file is consistent, but commits to a different chain than the trusted one
+        let trusted = TrustedAccumulators::new(vec![B256::ZERO, B256::repeat_byte(1)]);
+        assert!(matches!(
+            file.verify::<Header>(&trusted),
+            Err(EraError::UntrustedAccumulator { epoch: 1, computed, .. }) if computed == accumulator
+        ));
+        assert!(matches!(
+            file.verify::<Header>(&TrustedAccumulators::default()),
+            Err(EraError::UnknownEpoch(1))
+        ));
+    }
+
+    #[test]
+    fn rejects_out_of_order_blocks() {
+        let blocks = chain(0, 2);
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        writer.push(0, &blocks[0]).unwrap();
+        assert!(matches!(
+            writer.push(2, &blocks[1]),
+            Err(EraError::UnexpectedBlockNumber { expected: 1, got: 2 })
+        ));
+    }
+
+    #[test]
+    fn rejects_corrupted_index() {
+        let blocks = chain(0, 2);
+        let (mut buf, _) = write(0, &blocks);
+        // bump the count stored at the very end of the index
+        let len = buf.len();
+        buf[len - 8] += 1;
+        assert!(matches!(Era1File::read(&buf[..]), Err(EraError::InvalidIndex(_))));
+    }
+}
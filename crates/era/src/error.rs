@@ -0,0 +1,130 @@
+use alloy_primitives::{BlockNumber, B256};
+use alloy_rpc_types_engine::PayloadError;
+
+/// Errors that can occur when reading, writing or verifying era archives.
+#[derive(Debug, thiserror::Error)]
+pub enum EraError {
+    /// An error occurred when reading or writing the underlying file.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// An entry failed to decompress.
+    #[error("snappy decompression failed: {0}")]
+    Snappy(std::io::Error),
+    /// An entry could not be RLP decoded.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// An entry could not be SSZ decoded.
+    #[error("ssz decoding failed: {0}")]
+    Ssz(String),
+    /// An execution payload could not be converted into a block.
+    #[error(transparent)]
+    Payload(#[from] PayloadError),
+    /// The e2store entry header has non-zero reserved bytes.
+    #[error("entry at offset {offset} has non-zero reserved bytes")]
+    InvalidReservedBytes {
+        /// Offset of the entry in the file.
+        offset: u64,
+    },
+    /// The file ended in the middle of an entry.
+    #[error("truncated entry at offset {offset}")]
+    TruncatedEntry {
+        /// Offset of the entry in the file.
+        offset: u64,
+    },
+    /// An entry of an unexpected type was encountered.
+    #[error("unexpected entry type {got:#06x} at offset {offset}, expected {expected:#06x}")]
+    UnexpectedEntry {
+        /// The expected entry type.
+        expected: u16,
+        /// The entry type that was read.
+        got: u16,
+        /// Offset of the entry in the file.
+        offset: u64,
+    },
+    /// The file is structurally invalid.
+    #[error("malformed archive: {0}")]
+    Malformed(&'static str),
+    /// The block index does not match the blocks stored in the file.
+    #[error("block index mismatch: {0}")]
+    InvalidIndex(&'static str),
+    /// More blocks were added to an archive than it can hold.
+    #[error("archive is full, it can hold at most {0} blocks")]
+    TooManyBlocks(usize),
+    /// A block was added out of order.
+    #[error("expected block {expected}, got {got}")]
+    UnexpectedBlockNumber {
+        /// The expected block number.
+        expected: BlockNumber,
+        /// The block number that was added.
+        got: BlockNumber,
+    },
+    /// A beacon block or state of an unexpected slot was added.
+    #[error("expected slot {expected}, got {got}")]
+    UnexpectedSlot {
+        /// The expected slot.
+        expected: u64,
+        /// The slot that was added.
+        got: u64,
+    },
+    /// The accumulator root recomputed from the headers does not match the stored one.
+    #[error("accumulator root mismatch: computed {computed}, stored {stored}")]
+    AccumulatorMismatch {
+        /// Root computed from the header records of the file.
+        computed: B256,
+        /// Root stored in the file.
+        stored: B256,
+    },
+    /// The accumulator root of an ERA1 file is not the trusted root of its epoch.
+    #[error("accumulator root {computed} of epoch {epoch} is not the trusted root {trusted}")]
+    UntrustedAccumulator {
+        /// The epoch of the file.
+        epoch: u64,
+        /// Root computed from the header records of the file.
+        computed: B256,
+        /// The trusted root of the epoch.
+        trusted: B256,
+    },
+    /// There is no trusted accumulator root for the epoch of an ERA1 file.
+    #[error("no trusted accumulator root for epoch {0}")]
+    UnknownEpoch(u64),
+    /// A trusted accumulator root could not be parsed.
+    #[error("invalid trusted accumulator root on line {0}")]
+    InvalidTrustedRoot(usize),
+    /// The file name does not commit to the accumulator root stored in the file.
+    #[error("file name {name} does not match accumulator root {root}")]
+    FileNameMismatch {
+        /// The name of the file.
+        name: String,
+        /// The accumulator root stored in the file.
+        root: B256,
+    },
+    /// The total difficulty of a block is not the sum of its parent's and its own difficulty.
+    #[error("invalid total difficulty for block {0}")]
+    InvalidTotalDifficulty(BlockNumber),
+    /// The receipts of a block do not match the receipts root of its header.
+    #[error("receipts root mismatch for block {block}: computed {computed}, expected {expected}")]
+    ReceiptsRootMismatch {
+        /// The block number.
+        block: BlockNumber,
+        /// Root computed from the stored receipts.
+        computed: B256,
+        /// Receipts root of the block header.
+        expected: B256,
+    },
+    /// The hash of a block assembled from an execution payload does not match the payload.
+    #[error("block hash mismatch for block {block}: computed {computed}, expected {expected}")]
+    BlockHashMismatch {
+        /// The block number.
+        block: BlockNumber,
+        /// Hash of the assembled block header.
+        computed: B256,
+        /// Block hash committed to by the payload.
+        expected: B256,
+    },
+}
+
+impl From<ssz::DecodeError> for EraError {
+    fn from(err: ssz::DecodeError) -> Self {
+        Self::Ssz(format!("{err:?}"))
+    }
+}
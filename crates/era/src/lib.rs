@@ -0,0 +1,34 @@
+//! Readers and writers for era history archives.
+//!
+//! Era archives are [e2store](https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md)
+//! files that store a fixed range of chain history:
+//!
+//! - [ERA1](https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era1.md) files
+//!   contain pre-merge execution blocks together with their receipts, total difficulty and the
+//!   epoch accumulator that commits to them. See [`era1`].
+//! - [ERA](https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era.md) files
+//!   contain post-merge beacon chain blocks and states. The execution payloads embedded in the
+//!   beacon blocks can be extracted with [`era`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod accumulator;
+pub use accumulator::{accumulator_root, HeaderRecord, TrustedAccumulators};
+
+pub mod e2s;
+pub mod era;
+pub mod era1;
+
+mod error;
+pub use error::EraError;
+
+#[cfg(test)]
+use reth_primitives as _;
+#[cfg(test)]
+use tempfile as _;
@@ -37,6 +37,7 @@ impl ChainSpecParser for EthereumChainSpecParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_eips::eip7840::BlobParams;
     use reth_chainspec::EthereumHardforks;
 
     #[test]
@@ -100,4 +101,40 @@ mod tests {
         assert!(spec.is_prague_active_at_timestamp(0));
         assert!(spec.is_osaka_active_at_timestamp(0));
     }
+
+    #[test]
+    fn parse_raw_chainspec_blob_schedule() {
+        let s = r#"{
+  "alloc": {},
+  "config": {
+    "chainId": 1337,
+    "homesteadBlock": 0,
+    "eip150Block": 0,
+    "eip155Block": 0,
+    "eip158Block": 0,
+    "byzantiumBlock": 0,
+    "constantinopleBlock": 0,
+    "petersburgBlock": 0,
+    "istanbulBlock": 0,
+    "berlinBlock": 0,
+    "londonBlock": 0,
+    "terminalTotalDifficulty": 0,
+    "shanghaiTime": 0,
+    "cancunTime": 0,
+    "pragueTime": 100,
+    "blobSchedule": {
+      "cancun": { "target": 3, "max": 6, "baseFeeUpdateFraction": 3338477 },
+      "prague": { "target": 12, "max": 18, "baseFeeUpdateFraction": 10000000 }
+    }
+  }
+}"#;
+
+        let spec = <EthereumChainSpecParser as ChainSpecParser>::parse(s).unwrap();
+        let cancun = spec.blob_params_at_timestamp(0).unwrap();
+        assert_eq!(cancun, BlobParams::cancun());
+        let prague = spec.blob_params_at_timestamp(100).unwrap();
+        assert_eq!(prague.target_blob_count, 12);
+        assert_eq!(prague.max_blob_count, 18);
+        assert_eq!(prague.update_fraction, 10_000_000);
+    }
 }
@@ -13,6 +13,7 @@ extern crate alloc;
 
 mod payload;
 use alloc::sync::Arc;
+use alloy_primitives::B256;
 use alloy_rpc_types_engine::{ExecutionPayload, ExecutionPayloadSidecar, PayloadError};
 pub use alloy_rpc_types_engine::{
     ExecutionPayloadEnvelopeV2, ExecutionPayloadEnvelopeV3, ExecutionPayloadEnvelopeV4,
@@ -102,6 +103,7 @@ impl PayloadValidator for EthereumEngineValidator {
         &self,
         payload: ExecutionPayload,
         sidecar: ExecutionPayloadSidecar,
+        _withdrawals_root: Option<B256>,
     ) -> Result<SealedBlock, PayloadError> {
         self.inner.ensure_well_formed_payload(payload, sidecar)
     }
@@ -128,6 +128,18 @@ impl<EXT, DB: Database> Evm for EthEvm<'_, EXT, DB> {
     }
 }
 
+/// Returns the [`BlobExcessGasAndPrice`] for the given excess blob gas, priced with the given
+/// [`BlobParams`].
+const fn blob_excess_gas_and_price(
+    excess_blob_gas: u64,
+    blob_params: BlobParams,
+) -> BlobExcessGasAndPrice {
+    BlobExcessGasAndPrice {
+        excess_blob_gas,
+        blob_gasprice: blob_params.calc_blob_fee(excess_blob_gas),
+    }
+}
+
 /// Ethereum-related EVM configuration.
 #[derive(Debug, Clone)]
 pub struct EthEvmConfig {
@@ -164,6 +176,16 @@ impl ConfigureEvmEnv for EthEvmConfig {
         cfg_env.handler_cfg.spec_id = spec_id;
     }
 
+    fn blob_params(&self, timestamp: u64, spec_id: SpecId) -> BlobParams {
+        self.chain_spec.blob_params_at_timestamp(timestamp).unwrap_or_else(|| {
+            if spec_id >= SpecId::PRAGUE {
+                BlobParams::prague()
+            } else {
+                BlobParams::cancun()
+            }
+        })
+    }
+
     fn next_cfg_and_block_env(
         &self,
         parent: &Self::Header,
@@ -179,15 +201,14 @@ impl ConfigureEvmEnv for EthEvmConfig {
             parent.number() + 1,
         );
 
-        let blob_params =
-            if spec_id >= SpecId::PRAGUE { BlobParams::prague() } else { BlobParams::cancun() };
-
         // if the parent block did not have excess blob gas (i.e. it was pre-cancun), but it is
         // cancun now, we need to set the excess blob gas to the default value(0)
-        let blob_excess_gas_and_price = parent
-            .next_block_excess_blob_gas(blob_params)
-            .or_else(|| (spec_id == SpecId::CANCUN).then_some(0))
-            .map(|gas| BlobExcessGasAndPrice::new(gas, spec_id >= SpecId::PRAGUE));
+        let blob_excess_gas_and_price =
+            self.chain_spec.blob_params_at_timestamp(attributes.timestamp).map(|blob_params| {
+                let excess_blob_gas =
+                    parent.next_block_excess_blob_gas(blob_params).unwrap_or_default();
+                blob_excess_gas_and_price(excess_blob_gas, blob_params)
+            });
 
         let mut basefee = parent.next_block_base_fee(
             self.chain_spec.base_fee_params_at_timestamp(attributes.timestamp),
@@ -302,6 +323,32 @@ mod tests {
         assert_eq!(cfg_env_with_handler_cfg.chain_id, chain_spec.chain().id());
     }
 
+    #[test]
+    fn test_fill_block_env_blob_params_from_chain_spec() {
+        let mut chain_spec = ChainSpec::builder()
+            .chain(Chain::mainnet())
+            .genesis(Genesis::default())
+            .prague_activated()
+            .build();
+        chain_spec.blob_params.prague.update_fraction = 10_000_000;
+        let blob_params = chain_spec.blob_params.prague;
+
+        let excess_blob_gas = 10_000_000;
+        let header = Header { excess_blob_gas: Some(excess_blob_gas), ..Default::default() };
+        let EvmEnv { block_env, .. } =
+            EthEvmConfig::new(Arc::new(chain_spec)).cfg_and_block_env(&header);
+
+        let blob_excess_gas_and_price = block_env.blob_excess_gas_and_price.unwrap();
+        assert_eq!(
+            blob_excess_gas_and_price.blob_gasprice,
+            blob_params.calc_blob_fee(excess_blob_gas)
+        );
+        assert_ne!(
+            blob_excess_gas_and_price.blob_gasprice,
+            BlobParams::prague().calc_blob_fee(excess_blob_gas)
+        );
+    }
+
     #[test]
     #[allow(clippy::needless_update)]
     fn test_evm_with_env_default_spec() {
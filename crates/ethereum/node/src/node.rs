@@ -21,6 +21,7 @@ use reth_node_builder::{
     rpc::{EngineValidatorBuilder, RpcAddOns},
     BuilderContext, Node, NodeAdapter, NodeComponentsBuilder, PayloadTypes,
 };
+use reth_payload_util::MinerConfig;
 use reth_primitives::{EthPrimitives, PooledTransaction};
 use reth_provider::{CanonStateSubscriptions, EthStorage};
 use reth_rpc::EthApi;
@@ -33,11 +34,21 @@ use reth_trie_db::MerklePatriciaTrie;
 use std::sync::Arc;
 
 /// Type configuration for a regular Ethereum node.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct EthereumNode;
+pub struct EthereumNode {
+    /// Runtime settings of the block producer, shared by the transaction pool, the payload
+    /// builder and the `miner` namespace.
+    pub miner_config: MinerConfig,
+}
 
 impl EthereumNode {
+    /// Sets the [`MinerConfig`] shared by the node's components.
+    pub fn with_miner_config(mut self, miner_config: MinerConfig) -> Self {
+        self.miner_config = miner_config;
+        self
+    }
+
     /// Returns a [`ComponentsBuilder`] configured for a regular Ethereum node.
     pub fn components<Node>() -> ComponentsBuilder<
         Node,
@@ -107,10 +118,12 @@ where
 
     fn components_builder(&self) -> Self::ComponentsBuilder {
         Self::components()
+            .pool(EthereumPoolBuilder::default().with_miner_config(self.miner_config.clone()))
+            .payload(EthereumPayloadBuilder::default().with_miner_config(self.miner_config.clone()))
     }
 
     fn add_ons(&self) -> Self::AddOns {
-        EthereumAddOns::default()
+        EthereumAddOns::default().with_miner_config(self.miner_config.clone())
     }
 }
 
@@ -144,10 +157,19 @@ where
 ///
 /// This contains various settings that can be configured and take precedence over the node's
 /// config.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
 pub struct EthereumPoolBuilder {
-    // TODO add options for txpool args
+    /// Runtime settings of the block producer, e.g. the minimum priority fee.
+    miner_config: MinerConfig,
+}
+
+impl EthereumPoolBuilder {
+    /// Sets the [`MinerConfig`] consulted by the transaction validator.
+    pub fn with_miner_config(mut self, miner_config: MinerConfig) -> Self {
+        self.miner_config = miner_config;
+        self
+    }
 }
 
 impl<Types, Node> PoolBuilder<Node> for EthereumPoolBuilder
@@ -166,6 +188,7 @@ where
             .kzg_settings(ctx.kzg_settings()?)
             .with_local_transactions_config(pool_config.local_transactions_config.clone())
             .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
+            .with_miner_config(self.miner_config)
             .build_with_tasks(
                 ctx.provider().clone(),
                 ctx.task_executor().clone(),
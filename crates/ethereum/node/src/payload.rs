@@ -13,6 +13,7 @@ use reth_node_builder::{
     components::PayloadServiceBuilder, BuilderContext, PayloadBuilderConfig, PayloadTypes,
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
+use reth_payload_util::MinerConfig;
 use reth_primitives::EthPrimitives;
 use reth_provider::CanonStateSubscriptions;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
@@ -20,9 +21,18 @@ use reth_transaction_pool::{PoolTransaction, TransactionPool};
 /// A basic ethereum payload service.
 #[derive(Clone, Default, Debug)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder {
+    /// Runtime settings of the block producer.
+    miner_config: MinerConfig,
+}
 
 impl EthereumPayloadBuilder {
+    /// Sets the [`MinerConfig`] whose settings override the payload builder config.
+    pub fn with_miner_config(mut self, miner_config: MinerConfig) -> Self {
+        self.miner_config = miner_config;
+        self
+    }
+
     /// A helper method initializing [`PayloadBuilderService`] with the given EVM config.
     pub fn spawn<Types, Node, Evm, Pool>(
         self,
@@ -47,7 +57,8 @@ impl EthereumPayloadBuilder {
         let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
             evm_config,
             EthereumBuilderConfig::new(conf.extra_data_bytes()).with_gas_limit(conf.gas_limit()),
-        );
+        )
+        .with_miner_config(self.miner_config);
 
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
             .interval(conf.interval())
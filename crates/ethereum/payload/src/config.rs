@@ -9,12 +9,14 @@ pub struct EthereumBuilderConfig {
     pub extra_data: Bytes,
     /// Desired gas limit.
     pub desired_gas_limit: u64,
+    /// Minimum priority fee per gas of included transactions.
+    pub minimum_priority_fee: Option<u128>,
 }
 
 impl EthereumBuilderConfig {
     /// Create new payload builder config.
     pub const fn new(extra_data: Bytes) -> Self {
-        Self { extra_data, desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT }
+        Self { extra_data, desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT, minimum_priority_fee: None }
     }
 
     /// Set desired gas limit.
@@ -22,6 +24,12 @@ impl EthereumBuilderConfig {
         self.desired_gas_limit = desired_gas_limit;
         self
     }
+
+    /// Set minimum priority fee per gas of included transactions.
+    pub const fn with_minimum_priority_fee(mut self, minimum_priority_fee: Option<u128>) -> Self {
+        self.minimum_priority_fee = minimum_priority_fee;
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
@@ -10,10 +10,7 @@
 #![allow(clippy::useless_let_if_seq)]
 
 use alloy_consensus::{Header, Transaction, Typed2718, EMPTY_OMMER_ROOT_HASH};
-use alloy_eips::{
-    eip4844::MAX_DATA_GAS_PER_BLOCK, eip6110, eip7685::Requests, eip7840::BlobParams,
-    merge::BEACON_NONCE,
-};
+use alloy_eips::{eip4844::DATA_GAS_PER_BLOB, eip6110, eip7685::Requests, merge::BEACON_NONCE};
 use alloy_primitives::U256;
 use reth_basic_payload_builder::{
     commit_withdrawals, is_better_payload, BuildArguments, BuildOutcome, PayloadBuilder,
@@ -30,6 +27,7 @@ use reth_execution_types::ExecutionOutcome;
 use reth_payload_builder::{EthBuiltPayload, EthPayloadBuilderAttributes};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadBuilderAttributes;
+use reth_payload_util::MinerConfig;
 use reth_primitives::{
     Block, BlockBody, EthereumHardforks, InvalidTransactionError, Receipt, RecoveredBlock,
     TransactionSigned,
@@ -60,18 +58,42 @@ type BestTransactionsIter<Pool> = Box<
 >;
 
 /// Ethereum payload builder
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig> {
     /// The type responsible for creating the evm.
     evm_config: EvmConfig,
     /// Payload builder configuration.
     builder_config: EthereumBuilderConfig,
+    /// Runtime overrides of the payload builder configuration.
+    miner_config: MinerConfig,
 }
 
 impl<EvmConfig> EthereumPayloadBuilder<EvmConfig> {
     /// `EthereumPayloadBuilder` constructor.
-    pub const fn new(evm_config: EvmConfig, builder_config: EthereumBuilderConfig) -> Self {
-        Self { evm_config, builder_config }
+    pub fn new(evm_config: EvmConfig, builder_config: EthereumBuilderConfig) -> Self {
+        Self { evm_config, builder_config, miner_config: MinerConfig::default() }
+    }
+
+    /// Sets the [`MinerConfig`] whose settings override the builder configuration for every
+    /// subsequent payload.
+    pub fn with_miner_config(mut self, miner_config: MinerConfig) -> Self {
+        self.miner_config = miner_config;
+        self
+    }
+
+    /// Returns the builder configuration with the current [`MinerConfig`] overrides applied.
+    fn builder_config(&self) -> EthereumBuilderConfig {
+        let mut builder_config = self.builder_config.clone();
+        if let Some(extra_data) = self.miner_config.extra_data() {
+            builder_config.extra_data = extra_data;
+        }
+        if let Some(gas_limit) = self.miner_config.gas_limit() {
+            builder_config.desired_gas_limit = gas_limit;
+        }
+        if let Some(minimum_priority_fee) = self.miner_config.minimum_priority_fee() {
+            builder_config.minimum_priority_fee = Some(minimum_priority_fee);
+        }
+        builder_config
     }
 }
 
@@ -83,6 +105,7 @@ where
     /// (that has the `parent` as its parent).
     fn cfg_and_block_env(
         &self,
+        builder_config: &EthereumBuilderConfig,
         config: &PayloadConfig<EthPayloadBuilderAttributes>,
         parent: &Header,
     ) -> Result<EvmEnv, EvmConfig::Error> {
@@ -90,7 +113,7 @@ where
             timestamp: config.attributes.timestamp(),
             suggested_fee_recipient: config.attributes.suggested_fee_recipient(),
             prev_randao: config.attributes.prev_randao(),
-            gas_limit: self.builder_config.gas_limit(parent.gas_limit),
+            gas_limit: builder_config.gas_limit(parent.gas_limit),
         };
         self.evm_config.next_cfg_and_block_env(parent, next_attributes)
     }
@@ -110,14 +133,15 @@ where
         &self,
         args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
     ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
+        let builder_config = self.builder_config();
         let evm_env = self
-            .cfg_and_block_env(&args.config, &args.config.parent_header)
+            .cfg_and_block_env(&builder_config, &args.config, &args.config.parent_header)
             .map_err(PayloadBuilderError::other)?;
 
         let pool = args.pool.clone();
         default_ethereum_payload(
             self.evm_config.clone(),
-            self.builder_config.clone(),
+            builder_config,
             args,
             evm_env,
             |attributes| pool.best_transactions_with_attributes(attributes),
@@ -139,15 +163,16 @@ where
             None,
         );
 
+        let builder_config = self.builder_config();
         let evm_env = self
-            .cfg_and_block_env(&args.config, &args.config.parent_header)
+            .cfg_and_block_env(&builder_config, &args.config, &args.config.parent_header)
             .map_err(PayloadBuilderError::other)?;
 
         let pool = args.pool.clone();
 
         default_ethereum_payload(
             self.evm_config.clone(),
-            self.builder_config.clone(),
+            builder_config,
             args,
             evm_env,
             |attributes| pool.best_transactions_with_attributes(attributes),
@@ -190,6 +215,9 @@ where
     let mut sum_blob_gas_used = 0;
     let block_gas_limit: u64 = evm_env.block_env.gas_limit.to::<u64>();
     let base_fee = evm_env.block_env.basefee.to::<u64>();
+    let blob_params = chain_spec.blob_params_at_timestamp(attributes.timestamp);
+    let max_blob_gas_per_block =
+        blob_params.map_or(0, |params| params.max_blob_count * DATA_GAS_PER_BLOB);
 
     let mut executed_txs = Vec::new();
     let mut executed_senders = Vec::new();
@@ -244,6 +272,14 @@ where
             continue
         }
 
+        // skip transactions that don't pay the configured minimum tip
+        if builder_config.minimum_priority_fee.is_some_and(|minimum_priority_fee| {
+            pool_tx.effective_tip_per_gas(base_fee).is_none_or(|tip| tip < minimum_priority_fee)
+        }) {
+            best_txs.mark_invalid(&pool_tx, InvalidPoolTransactionError::Underpriced);
+            continue
+        }
+
         // check if the job was cancelled, if so we can exit early
         if cancel.is_cancelled() {
             return Ok(BuildOutcome::Cancelled)
@@ -256,7 +292,7 @@ where
         // the EIP-4844 can still fit in the block
         if let Some(blob_tx) = tx.as_eip4844() {
             let tx_blob_gas = blob_tx.blob_gas();
-            if sum_blob_gas_used + tx_blob_gas > MAX_DATA_GAS_PER_BLOCK {
+            if sum_blob_gas_used + tx_blob_gas > max_blob_gas_per_block {
                 // we can't fit this _blob_ transaction into the block, so we mark it as
                 // invalid, which removes its dependent transactions from
                 // the iterator. This is similar to the gas limit condition
@@ -266,7 +302,7 @@ where
                     &pool_tx,
                     InvalidPoolTransactionError::ExceedsGasLimit(
                         tx_blob_gas,
-                        MAX_DATA_GAS_PER_BLOCK,
+                        max_blob_gas_per_block,
                     ),
                 );
                 continue
@@ -315,7 +351,7 @@ where
             sum_blob_gas_used += tx_blob_gas;
 
             // if we've reached the max data gas per block, we can skip blob txs entirely
-            if sum_blob_gas_used == MAX_DATA_GAS_PER_BLOCK {
+            if sum_blob_gas_used == max_blob_gas_per_block {
                 best_txs.skip_blobs();
             }
         }
@@ -418,7 +454,7 @@ where
     let mut blob_gas_used = None;
 
     // only determine cancun fields when active
-    if chain_spec.is_cancun_active_at_timestamp(attributes.timestamp) {
+    if let Some(blob_params) = blob_params {
         // grab the blob sidecars from the executed txs
         blob_sidecars = pool
             .get_all_blobs_exact(
@@ -427,12 +463,6 @@ where
             .map_err(PayloadBuilderError::other)?;
 
         excess_blob_gas = if chain_spec.is_cancun_active_at_timestamp(parent_header.timestamp) {
-            let blob_params = if chain_spec.is_prague_active_at_timestamp(attributes.timestamp) {
-                BlobParams::prague()
-            } else {
-                // cancun
-                BlobParams::cancun()
-            };
             parent_header.next_block_excess_blob_gas(blob_params)
         } else {
             // for the first post-fork block, both parent.blob_gas_used and
@@ -18,10 +18,13 @@
 extern crate alloc;
 
 use alloy_consensus::BlockHeader as _;
+use alloy_eips::eip7840::BlobParams;
 use alloy_primitives::{Address, Bytes, B256, U256};
 use reth_primitives_traits::{BlockHeader, SignedTransaction};
 use revm::{Database, DatabaseCommit, GetInspector};
-use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg, EVMError, ResultAndState, SpecId, TxEnv};
+use revm_primitives::{
+    BlobExcessGasAndPrice, BlockEnv, CfgEnvWithHandlerCfg, EVMError, ResultAndState, SpecId, TxEnv,
+};
 
 pub mod either;
 /// EVM environment configuration.
@@ -208,7 +211,22 @@ pub trait ConfigureEvmEnv: Send + Sync + Unpin + Clone + 'static {
 
         // EIP-4844 excess blob gas of this block, introduced in Cancun
         if let Some(excess_blob_gas) = header.excess_blob_gas() {
-            block_env.set_blob_excess_gas_and_price(excess_blob_gas, spec_id >= SpecId::PRAGUE);
+            let blob_params = self.blob_params(header.timestamp(), spec_id);
+            block_env.blob_excess_gas_and_price = Some(BlobExcessGasAndPrice {
+                excess_blob_gas,
+                blob_gasprice: blob_params.calc_blob_fee(excess_blob_gas),
+            });
+        }
+    }
+
+    /// Returns the [`BlobParams`] that price the blob gas of a block with the given timestamp.
+    ///
+    /// Defaults to the parameters of Prague if it's active, Cancun otherwise.
+    fn blob_params(&self, _timestamp: u64, spec_id: SpecId) -> BlobParams {
+        if spec_id >= SpecId::PRAGUE {
+            BlobParams::prague()
+        } else {
+            BlobParams::cancun()
         }
     }
 
@@ -245,11 +245,24 @@ impl Discv4 {
     /// ```
     pub async fn bind(
         local_address: SocketAddr,
-        mut local_node_record: NodeRecord,
+        local_node_record: NodeRecord,
         secret_key: SecretKey,
         config: Discv4Config,
     ) -> io::Result<(Self, Discv4Service)> {
         let socket = UdpSocket::bind(local_address).await?;
+        Self::with_socket(socket, local_node_record, secret_key, config)
+    }
+
+    /// Same as [`Self::bind`], but uses the given, already bound UDP socket.
+    ///
+    /// This is useful if the socket needs to be configured before binding, for example an IPv6-only
+    /// socket next to an IPv4 socket on the same port for dual-stack operation.
+    pub fn with_socket(
+        socket: UdpSocket,
+        mut local_node_record: NodeRecord,
+        secret_key: SecretKey,
+        config: Discv4Config,
+    ) -> io::Result<(Self, Discv4Service)> {
         let local_addr = socket.local_addr()?;
         local_node_record.udp_port = local_addr.port();
         trace!(target: "discv4", ?local_addr,"opened UDP socket");
@@ -72,6 +72,8 @@ pub struct ConfigBuilder {
     /// NOTE: IP address of `RLPx` socket overwrites IP address of same IP version in
     /// [`discv5::ListenConfig`].
     tcp_socket: SocketAddr,
+    /// Additional IPv6 `RLPx` TCP socket to advertise, if `RLPx` is dual-stack.
+    tcp_socket_ipv6: Option<SocketAddr>,
     /// List of `(key, rlp-encoded-value)` tuples that should be advertised in local node record
     /// (in addition to tcp port, udp port and fork).
     other_enr_kv_pairs: Vec<(&'static [u8], Bytes)>,
@@ -95,6 +97,7 @@ impl ConfigBuilder {
             bootstrap_nodes,
             fork,
             tcp_socket,
+            tcp_socket_ipv6,
             other_enr_kv_pairs,
             lookup_interval,
             bootstrap_lookup_interval,
@@ -107,6 +110,7 @@ impl ConfigBuilder {
             bootstrap_nodes,
             fork: fork.map(|(key, fork_id)| (key, fork_id.fork_id)),
             tcp_socket,
+            tcp_socket_ipv6,
             other_enr_kv_pairs,
             lookup_interval: Some(lookup_interval),
             bootstrap_lookup_interval: Some(bootstrap_lookup_interval),
@@ -177,6 +181,14 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets an additional IPv6 tcp socket to advertise in the local [`Enr`](discv5::enr::Enr),
+    /// when `RLPx` listens on both IPv4 and IPv6. The IP address of this socket will overwrite the
+    /// IPv6 discovery address, if one is configured.
+    pub const fn tcp_socket_ipv6(mut self, socket: SocketAddr) -> Self {
+        self.tcp_socket_ipv6 = Some(socket);
+        self
+    }
+
     /// Adds an additional kv-pair to include in the local [`Enr`](discv5::enr::Enr). Takes the key
     /// to use for the kv-pair and the rlp encoded value.
     pub fn add_enr_kv_pair(mut self, key: &'static [u8], value: Bytes) -> Self {
@@ -221,6 +233,7 @@ impl ConfigBuilder {
             bootstrap_nodes,
             fork,
             tcp_socket,
+            tcp_socket_ipv6,
             other_enr_kv_pairs,
             lookup_interval,
             bootstrap_lookup_interval,
@@ -234,6 +247,10 @@ impl ConfigBuilder {
 
         discv5_config.listen_config =
             amend_listen_config_wrt_rlpx(&discv5_config.listen_config, tcp_socket.ip());
+        if let Some(tcp_socket_ipv6) = tcp_socket_ipv6 {
+            discv5_config.listen_config =
+                amend_listen_config_wrt_rlpx(&discv5_config.listen_config, tcp_socket_ipv6.ip());
+        }
 
         let fork = fork.map(|(key, fork_id)| (key, fork_id.into()));
 
@@ -251,6 +268,7 @@ impl ConfigBuilder {
             bootstrap_nodes,
             fork,
             tcp_socket,
+            tcp_socket_ipv6,
             other_enr_kv_pairs,
             lookup_interval,
             bootstrap_lookup_interval,
@@ -276,6 +294,8 @@ pub struct Config {
     /// NOTE: IP address of `RLPx` socket overwrites IP address of same IP version in
     /// [`discv5::ListenConfig`].
     pub(super) tcp_socket: SocketAddr,
+    /// Additional IPv6 `RLPx` TCP socket to advertise, if `RLPx` is dual-stack.
+    pub(super) tcp_socket_ipv6: Option<SocketAddr>,
     /// Additional kv-pairs (besides tcp port, udp port and fork) that should be advertised to
     /// peers by including in local node record.
     pub(super) other_enr_kv_pairs: Vec<(&'static [u8], Bytes)>,
@@ -300,6 +320,7 @@ impl Config {
             bootstrap_nodes: HashSet::default(),
             fork: None,
             tcp_socket: rlpx_tcp_socket,
+            tcp_socket_ipv6: None,
             other_enr_kv_pairs: Vec::new(),
             lookup_interval: None,
             bootstrap_lookup_interval: None,
@@ -345,6 +366,12 @@ impl Config {
     pub const fn rlpx_socket(&self) -> &SocketAddr {
         &self.tcp_socket
     }
+
+    /// Returns the additional IPv6 `RLPx` (TCP) socket, if `RLPx` is dual-stack. This socket will
+    /// be advertised to peers in the local [`Enr`](discv5::enr::Enr) too.
+    pub const fn rlpx_socket_ipv6(&self) -> Option<&SocketAddr> {
+        self.tcp_socket_ipv6.as_ref()
+    }
 }
 
 /// Returns the IPv4 discovery socket if one is configured.
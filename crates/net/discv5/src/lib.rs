@@ -330,7 +330,9 @@ impl Discv5 {
             "discovered peer"
         );
 
-        Some(DiscoveredPeer { node_record, fork_id })
+        let alt_tcp = self.alt_rlpx_socket(enr, node_record.address);
+
+        Some(DiscoveredPeer { node_record, fork_id, alt_tcp })
     }
 
     /// Tries to convert an [`Enr`](discv5::Enr) into the backwards compatible type [`NodeRecord`],
@@ -345,15 +347,39 @@ impl Discv5 {
         if enr.tcp4().is_none() && enr.tcp6().is_none() {
             return Err(Error::UnreachableRlpx)
         }
-        let Some(tcp_port) = (match self.rlpx_ip_mode {
-            IpMode::Ip4 => enr.tcp4(),
-            IpMode::Ip6 => enr.tcp6(),
-            _ => unimplemented!("dual-stack support not implemented for rlpx"),
-        }) else {
+        let (address, tcp_port) = match self.rlpx_ip_mode {
+            IpMode::Ip4 => (socket.ip(), enr.tcp4()),
+            IpMode::Ip6 => (socket.ip(), enr.tcp6()),
+            IpMode::DualStack => {
+                // prefer the IP version the peer was discovered over, otherwise fall back to the
+                // other IP version advertised in the ENR
+                let tcp_port = if socket.is_ipv4() { enr.tcp4() } else { enr.tcp6() };
+                match tcp_port {
+                    Some(tcp_port) => (socket.ip(), Some(tcp_port)),
+                    None => match self.alt_rlpx_socket(enr, socket.ip()) {
+                        Some(alt) => (alt.ip(), Some(alt.port())),
+                        None => (socket.ip(), None),
+                    },
+                }
+            }
+        };
+        let Some(tcp_port) = tcp_port else {
             return Err(Error::IpVersionMismatchRlpx(self.rlpx_ip_mode))
         };
 
-        Ok(NodeRecord { address: socket.ip(), tcp_port, udp_port: socket.port(), id })
+        Ok(NodeRecord { address, tcp_port, udp_port: socket.port(), id })
+    }
+
+    /// Returns the `RLPx` socket advertised in the [`Enr`](discv5::Enr) for the other IP version
+    /// than the given address, if the local `RLPx` [`IpMode`] is dual-stack.
+    pub fn alt_rlpx_socket(&self, enr: &discv5::Enr, address: IpAddr) -> Option<SocketAddr> {
+        if !matches!(self.rlpx_ip_mode, IpMode::DualStack) {
+            return None
+        }
+        match address {
+            IpAddr::V4(_) => Some(SocketAddr::new(enr.ip6()?.into(), enr.tcp6()?)),
+            IpAddr::V6(_) => Some(SocketAddr::new(enr.ip4()?.into(), enr.tcp4()?)),
+        }
     }
 
     /// Applies filtering rules on an ENR. Returns [`Ok`](FilterOutcome::Ok) if peer should be
@@ -417,6 +443,9 @@ pub struct DiscoveredPeer {
     pub node_record: NodeRecord,
     /// [`ForkId`] extracted from ENR w.r.t. configured
     pub fork_id: Option<ForkId>,
+    /// `RLPx` socket of the other IP version than the node record, if both the peer and the local
+    /// node are dual-stack.
+    pub alt_tcp: Option<SocketAddr>,
 }
 
 /// Builds the local ENR with the supplied key.
@@ -426,7 +455,8 @@ pub fn build_local_enr(
 ) -> (Enr<SecretKey>, NodeRecord, Option<&'static [u8]>, IpMode) {
     let mut builder = discv5::enr::Enr::builder();
 
-    let Config { discv5_config, fork, tcp_socket, other_enr_kv_pairs, .. } = config;
+    let Config { discv5_config, fork, tcp_socket, tcp_socket_ipv6, other_enr_kv_pairs, .. } =
+        config;
 
     let socket = match discv5_config.listen_config {
         ListenConfig::Ipv4 { ip, port } => {
@@ -463,7 +493,17 @@ pub fn build_local_enr(
         }
     };
 
-    let rlpx_ip_mode = if tcp_socket.is_ipv4() { IpMode::Ip4 } else { IpMode::Ip6 };
+    // advertise the IPv6 `RLPx` socket whenever the node listens on one, independent of the IP
+    // versions discv5 listens on
+    if let Some(tcp_socket_ipv6) = tcp_socket_ipv6 {
+        builder.tcp6(tcp_socket_ipv6.port());
+    }
+
+    let rlpx_ip_mode = match tcp_socket_ipv6 {
+        Some(_) if tcp_socket.is_ipv4() => IpMode::DualStack,
+        _ if tcp_socket.is_ipv4() => IpMode::Ip4,
+        _ => IpMode::Ip6,
+    };
 
     // identifies which network node is on
     let network_stack_id = fork.as_ref().map(|(network_stack_id, fork_value)| {
@@ -774,6 +814,40 @@ mod test {
         )
     }
 
+    #[test]
+    fn discovered_dual_stack_enr() {
+        reth_tracing::init_test_tracing();
+
+        // rig test
+        const REMOTE_RLPX_PORT: u16 = 30303;
+        const REMOTE_RLPX_PORT_IPV6: u16 = 30304;
+        let remote_socket = "104.28.44.25:9000".parse().unwrap();
+        let remote_ipv6 = "2001:db8::1".parse().unwrap();
+        let remote_key = CombinedKey::generate_secp256k1();
+        let remote_enr = Enr::builder()
+            .tcp4(REMOTE_RLPX_PORT)
+            .ip6(remote_ipv6)
+            .tcp6(REMOTE_RLPX_PORT_IPV6)
+            .build(&remote_key)
+            .unwrap();
+
+        let discv5 = Discv5 { rlpx_ip_mode: IpMode::DualStack, ..discv5_noop() };
+
+        // test
+        let peer = discv5.on_discovered_peer(&remote_enr, remote_socket).unwrap();
+
+        assert_eq!(
+            NodeRecord {
+                address: remote_socket.ip(),
+                udp_port: remote_socket.port(),
+                tcp_port: REMOTE_RLPX_PORT,
+                id: enr_to_discv4_id(&remote_enr).unwrap(),
+            },
+            peer.node_record
+        );
+        assert_eq!(Some((remote_ipv6, REMOTE_RLPX_PORT_IPV6).into()), peer.alt_tcp);
+    }
+
     // Copied from sigp/discv5 with slight modification (U256 type)
     // <https://github.com/sigp/discv5/blob/master/src/kbucket/key.rs#L89-L101>
     #[allow(unreachable_pub)]
@@ -894,4 +968,34 @@ mod test {
         assert_eq!(fork_id, decoded_fork_id);
         assert_eq!(TCP_PORT, enr.tcp4().unwrap()); // listen config is defaulting to ip mode ipv4
     }
+
+    #[test]
+    fn build_dual_stack_enr_from_config() {
+        const TCP_PORT: u16 = 30303;
+        const TCP_PORT_IPV6: u16 = 30304;
+
+        let config = Config::builder((Ipv4Addr::UNSPECIFIED, TCP_PORT).into())
+            .tcp_socket_ipv6((Ipv6Addr::UNSPECIFIED, TCP_PORT_IPV6).into())
+            .build();
+
+        let sk = SecretKey::new(&mut thread_rng());
+        let (enr, _, _, ip_mode) = build_local_enr(&sk, &config);
+
+        assert_eq!(TCP_PORT, enr.tcp4().unwrap());
+        assert_eq!(TCP_PORT_IPV6, enr.tcp6().unwrap());
+        assert!(matches!(ip_mode, IpMode::DualStack));
+
+        // discv5 only listening on one IP version doesn't hide the IPv6 `RLPx` socket
+        for listen_config in [
+            ListenConfig::Ipv4 { ip: Ipv4Addr::UNSPECIFIED, port: DEFAULT_DISCOVERY_V5_PORT },
+            ListenConfig::Ipv6 { ip: Ipv6Addr::UNSPECIFIED, port: DEFAULT_DISCOVERY_V5_PORT },
+        ] {
+            let mut config = config.clone();
+            config.discv5_config.listen_config = listen_config;
+            let (enr, _, _, ip_mode) = build_local_enr(&sk, &config);
+
+            assert_eq!(TCP_PORT_IPV6, enr.tcp6().unwrap());
+            assert!(matches!(ip_mode, IpMode::DualStack));
+        }
+    }
 }
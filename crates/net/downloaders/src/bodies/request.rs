@@ -112,7 +112,14 @@ where
         tracing::trace!(target: "downloaders::bodies", request_len = req.len(), "Requesting bodies");
         let client = Arc::clone(&self.client);
         self.last_request_len = Some(req.len());
-        self.fut = Some(client.get_block_bodies_with_priority(req, priority));
+        // the pending headers are sorted, this lets the client pick a peer that has the blocks
+        let range_hint = self
+            .pending_headers
+            .front()
+            .zip(self.pending_headers.back())
+            .map(|(first, last)| first.number()..=last.number());
+        self.fut =
+            Some(client.get_block_bodies_with_priority_and_range_hint(req, priority, range_hint));
     }
 
     /// Process block response.
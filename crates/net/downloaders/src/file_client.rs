@@ -14,7 +14,7 @@ use reth_network_p2p::{
     BlockClient,
 };
 use reth_network_peers::PeerId;
-use reth_primitives::SealedHeader;
+use reth_primitives::{SealedBlock, SealedHeader};
 use reth_primitives_traits::{Block, BlockBody, FullBlock};
 use thiserror::Error;
 use tokio::{fs::File, io::AsyncReadExt};
@@ -94,6 +94,24 @@ impl<B: FullBlock> FileClient<B> {
         Ok(Self::from_reader(&reader[..], file_len).await?.file_client)
     }
 
+    /// Create a new file client from blocks that have already been decoded, e.g. from an era
+    /// archive.
+    pub fn from_blocks(blocks: impl IntoIterator<Item = SealedBlock<B>>) -> Self {
+        let mut headers = HashMap::default();
+        let mut hash_to_number = HashMap::default();
+        let mut bodies = HashMap::default();
+
+        for block in blocks {
+            let block_hash = block.hash();
+            let (header, body) = block.split_header_body();
+            hash_to_number.insert(block_hash, header.number());
+            headers.insert(header.number(), header);
+            bodies.insert(block_hash, body);
+        }
+
+        Self { headers, hash_to_number, bodies }
+    }
+
     /// Get the tip hash of the chain.
     pub fn tip(&self) -> Option<B256> {
         self.headers.get(&self.max_block()?).map(|h| h.hash_slow())
@@ -0,0 +1,29 @@
+//! Implements the eth/69 `BlockRangeUpdate` message type.
+
+use alloy_primitives::B256;
+use alloy_rlp::{RlpDecodable, RlpEncodable};
+use reth_codecs_derive::add_arbitrary_tests;
+
+/// Announces the range of blocks a peer is able to serve, introduced in `eth/69`.
+///
+/// This is sent whenever the range changes, for example when the peer imports a new block or
+/// expires history, so that peers can route requests to nodes that have the requested blocks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct BlockRangeUpdate {
+    /// The earliest block that can be served.
+    pub earliest: u64,
+    /// The latest block that can be served.
+    pub latest: u64,
+    /// The hash of the latest block.
+    pub latest_hash: B256,
+}
+
+impl BlockRangeUpdate {
+    /// Returns `true` if the range is well formed, i.e. `earliest` does not exceed `latest`.
+    pub const fn is_valid(&self) -> bool {
+        self.earliest <= self.latest
+    }
+}
@@ -12,7 +12,9 @@
 extern crate alloc;
 
 mod status;
-pub use status::{Status, StatusBuilder};
+pub use status::{
+    Status, StatusBuilder, StatusEth69, StatusMessage, UnifiedStatus, UnifiedStatusBuilder,
+};
 
 pub mod version;
 pub use version::{EthVersion, ProtocolVersion};
@@ -23,6 +25,9 @@ pub use message::{EthMessage, EthMessageID, ProtocolMessage};
 pub mod header;
 pub use header::*;
 
+pub mod block_range;
+pub use block_range::*;
+
 pub mod blocks;
 pub use blocks::*;
 
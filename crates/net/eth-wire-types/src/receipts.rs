@@ -1,9 +1,11 @@
 //! Implements the `GetReceipts` and `Receipts` message types.
 
 use alloc::vec::Vec;
-use alloy_consensus::{ReceiptWithBloom, RlpDecodableReceipt, RlpEncodableReceipt};
-use alloy_primitives::B256;
-use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
+use alloy_consensus::{ReceiptWithBloom, RlpDecodableReceipt, RlpEncodableReceipt, TxReceipt};
+use alloy_primitives::{logs_bloom, Bloom, Log, B256};
+use alloy_rlp::{
+    Buf, BufMut, Decodable, Encodable, Header, RlpDecodableWrapper, RlpEncodableWrapper,
+};
 use reth_codecs_derive::add_arbitrary_tests;
 use reth_ethereum_primitives::Receipt;
 
@@ -46,13 +48,202 @@ impl<T: RlpDecodableReceipt> alloy_rlp::Decodable for Receipts<T> {
     }
 }
 
+/// The eth/69 response to [`GetReceipts`].
+///
+/// Since [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642) receipts are sent without their
+/// bloom, which the receiver recomputes from the logs. Each receipt is encoded as
+/// `[tx-type, post-state-or-status, cumulative-gas, logs, ...]`, where any trailing fields of the
+/// receipt encoding are kept as is.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub struct Receipts69<T = Receipt>(
+    /// Each receipt list should correspond to a block hash in the request.
+    pub Vec<Vec<T>>,
+);
+
+impl<T: TxReceipt> Receipts69<T> {
+    /// Converts the receipts into the legacy [`Receipts`] by computing the bloom of every receipt.
+    pub fn into_receipts(self) -> Receipts<T> {
+        Receipts(
+            self.0
+                .into_iter()
+                .map(|receipts| {
+                    receipts
+                        .into_iter()
+                        .map(|receipt| ReceiptWithBloom { logs_bloom: receipt.bloom(), receipt })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<T> From<Receipts<T>> for Receipts69<T> {
+    fn from(receipts: Receipts<T>) -> Self {
+        Self(
+            receipts
+                .0
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(|r| r.receipt).collect())
+                .collect(),
+        )
+    }
+}
+
+impl<T: RlpEncodableReceipt> Encodable for Receipts69<T> {
+    fn encode(&self, out: &mut dyn BufMut) {
+        let blocks = self.encoded_blocks();
+        Header { list: true, payload_length: blocks.iter().map(Vec::len).sum() }.encode(out);
+        for block in blocks {
+            out.put_slice(&block);
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.encoded_blocks().iter().map(Vec::len).sum::<usize>();
+        Header { list: true, payload_length }.length_with_payload()
+    }
+}
+
+impl<T: RlpEncodableReceipt> Receipts69<T> {
+    /// Returns the eth/69 encoding of every block's receipt list.
+    fn encoded_blocks(&self) -> Vec<Vec<u8>> {
+        self.0
+            .iter()
+            .map(|receipts| {
+                let encoded = receipts.iter().map(encode_receipt_69).collect::<Vec<_>>();
+                let payload_length = encoded.iter().map(Vec::len).sum();
+                let mut out = Vec::with_capacity(payload_length + 9);
+                Header { list: true, payload_length }.encode(&mut out);
+                for receipt in encoded {
+                    out.extend_from_slice(&receipt);
+                }
+                out
+            })
+            .collect()
+    }
+}
+
+impl<T: RlpDecodableReceipt> Decodable for Receipts69<T> {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let mut blocks = Vec::new();
+        let mut payload = list_payload(buf)?;
+        while !payload.is_empty() {
+            let mut receipts = Vec::new();
+            let mut block = list_payload(&mut payload)?;
+            while !block.is_empty() {
+                receipts.push(decode_receipt_69(&mut block)?);
+            }
+            blocks.push(receipts);
+        }
+        Ok(Self(blocks))
+    }
+}
+
+/// Decodes a list header and returns its payload, advancing the buffer past the list.
+fn list_payload<'a>(buf: &mut &'a [u8]) -> alloy_rlp::Result<&'a [u8]> {
+    let header = Header::decode(buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString)
+    }
+    take(buf, header.payload_length)
+}
+
+/// Splits off the next `len` bytes of the buffer.
+fn take<'a>(buf: &mut &'a [u8], len: usize) -> alloy_rlp::Result<&'a [u8]> {
+    if buf.len() < len {
+        return Err(alloy_rlp::Error::InputTooShort)
+    }
+    let (head, tail) = buf.split_at(len);
+    *buf = tail;
+    Ok(head)
+}
+
+/// Splits off the next RLP item of the buffer, including its header.
+fn raw_item<'a>(buf: &mut &'a [u8]) -> alloy_rlp::Result<&'a [u8]> {
+    let start = *buf;
+    let header = Header::decode(buf)?;
+    // a single byte below 0x80 is its own encoding and is not consumed by the header
+    take(buf, header.payload_length)?;
+    Ok(&start[..start.len() - buf.len()])
+}
+
+/// Encodes the receipt without its bloom, by stripping the bloom from its regular network
+/// encoding.
+fn encode_receipt_69<T: RlpEncodableReceipt>(receipt: &T) -> Vec<u8> {
+    let mut with_bloom = Vec::with_capacity(receipt.rlp_encoded_length_with_bloom(&Bloom::ZERO));
+    receipt.rlp_encode_with_bloom(&Bloom::ZERO, &mut with_bloom);
+    strip_bloom(&with_bloom).expect("receipt encoding is valid RLP")
+}
+
+/// Converts the network encoding of a receipt into its eth/69 encoding.
+fn strip_bloom(mut buf: &[u8]) -> alloy_rlp::Result<Vec<u8>> {
+    let header = Header::decode(&mut buf)?;
+    let tx_type = if header.list {
+        0u8
+    } else {
+        let tx_type = *buf.first().ok_or(alloy_rlp::Error::InputTooShort)?;
+        buf.advance(1);
+        tx_type
+    };
+    let mut fields = list_payload(&mut buf)?;
+    let status = raw_item(&mut fields)?;
+    let cumulative_gas_used = raw_item(&mut fields)?;
+    let _bloom = raw_item(&mut fields)?;
+    // logs and any additional fields of the receipt
+    let rest = fields;
+
+    let payload_length = tx_type.length() + status.len() + cumulative_gas_used.len() + rest.len();
+    let mut out = Vec::with_capacity(payload_length + 9);
+    Header { list: true, payload_length }.encode(&mut out);
+    tx_type.encode(&mut out);
+    out.extend_from_slice(status);
+    out.extend_from_slice(cumulative_gas_used);
+    out.extend_from_slice(rest);
+    Ok(out)
+}
+
+/// Decodes an eth/69 receipt, by restoring its regular network encoding with the bloom computed
+/// from its logs.
+fn decode_receipt_69<T: RlpDecodableReceipt>(buf: &mut &[u8]) -> alloy_rlp::Result<T> {
+    let mut fields = list_payload(buf)?;
+    let tx_type = u8::decode(&mut fields)?;
+    let status = raw_item(&mut fields)?;
+    let cumulative_gas_used = raw_item(&mut fields)?;
+    // logs and any additional fields of the receipt
+    let rest = fields;
+    let logs = Vec::<Log>::decode(&mut fields)?;
+    let bloom = logs_bloom(&logs);
+
+    let payload_length = status.len() + cumulative_gas_used.len() + bloom.length() + rest.len();
+    let list_header = Header { list: true, payload_length };
+    let mut with_bloom = Vec::with_capacity(payload_length + 16);
+    if tx_type != 0 {
+        Header { list: false, payload_length: 1 + list_header.length_with_payload() }
+            .encode(&mut with_bloom);
+        with_bloom.put_u8(tx_type);
+    }
+    list_header.encode(&mut with_bloom);
+    with_bloom.extend_from_slice(status);
+    with_bloom.extend_from_slice(cumulative_gas_used);
+    bloom.encode(&mut with_bloom);
+    with_bloom.extend_from_slice(rest);
+
+    let mut with_bloom = with_bloom.as_slice();
+    let receipt = T::rlp_decode_with_bloom(&mut with_bloom)?;
+    if !with_bloom.is_empty() {
+        return Err(alloy_rlp::Error::UnexpectedLength)
+    }
+    Ok(receipt.receipt)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{message::RequestPair, GetReceipts, Receipts};
     use alloy_consensus::TxType;
-    use alloy_primitives::{hex, Log};
-    use alloy_rlp::{Decodable, Encodable};
+    use alloy_primitives::hex;
 
     #[test]
     fn roundtrip_eip1559() {
@@ -70,6 +261,56 @@ mod tests {
         assert_eq!(receipts, decoded);
     }
 
+    #[test]
+    fn roundtrip_receipts_69() {
+        let log = Log::new_unchecked(
+            hex!("0000000000000000000000000000000000000011").into(),
+            vec![hex!("000000000000000000000000000000000000000000000000000000000000dead").into()],
+            hex!("0100ff")[..].into(),
+        );
+        let receipts = Receipts69(vec![
+            vec![
+                Receipt {
+                    tx_type: TxType::Legacy,
+                    success: true,
+                    cumulative_gas_used: 21_000,
+                    logs: vec![log.clone()],
+                },
+                Receipt {
+                    tx_type: TxType::Eip1559,
+                    success: false,
+                    cumulative_gas_used: 42_000,
+                    logs: vec![],
+                },
+            ],
+            vec![],
+        ]);
+
+        let mut out = vec![];
+        receipts.encode(&mut out);
+        assert_eq!(out.len(), receipts.length());
+
+        let decoded = Receipts69::<Receipt>::decode(&mut out.as_slice()).unwrap();
+        assert_eq!(receipts, decoded);
+
+        // the bloom is dropped on the wire and recomputed from the logs
+        let with_bloom = decoded.into_receipts();
+        assert_eq!(with_bloom.0[0][0].logs_bloom, alloy_primitives::logs_bloom(&[log]));
+        assert_eq!(Receipts69::from(with_bloom), receipts);
+    }
+
+    #[test]
+    fn encode_receipt_69_fields() {
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 1,
+            logs: vec![],
+        };
+        // [tx-type, status, cumulative-gas, logs]
+        assert_eq!(encode_receipt_69(&receipt), hex!("c4020101c0"));
+    }
+
     #[test]
     // Test vector from: https://eips.ethereum.org/EIPS/eip-2481
     fn encode_get_receipts() {
@@ -1,7 +1,7 @@
 use crate::EthVersion;
 use alloy_chains::{Chain, NamedChain};
 use alloy_primitives::{hex, B256, U256};
-use alloy_rlp::{RlpDecodable, RlpEncodable};
+use alloy_rlp::{BufMut, Encodable, RlpDecodable, RlpEncodable};
 use core::fmt::{Debug, Display};
 use reth_chainspec::{EthChainSpec, Hardforks, MAINNET};
 use reth_codecs_derive::add_arbitrary_tests;
@@ -213,9 +213,384 @@ impl StatusBuilder {
     }
 }
 
+/// The eth/69 status message, as defined in [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642).
+///
+/// Compared to the legacy [`Status`] this drops the total difficulty and advertises the range of
+/// blocks the peer is able to serve instead.
+#[derive(Copy, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct StatusEth69 {
+    /// The current protocol version, 69 or higher.
+    pub version: EthVersion,
+
+    /// The chain id, as introduced in
+    /// [EIP155](https://eips.ethereum.org/EIPS/eip-155#list-of-chain-ids).
+    pub chain: Chain,
+
+    /// The genesis hash of the peer's chain.
+    pub genesis: B256,
+
+    /// The fork identifier, as defined by
+    /// [EIP-2124](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2124.md).
+    pub forkid: ForkId,
+
+    /// The earliest block the peer can serve.
+    pub earliest: u64,
+
+    /// The latest block the peer can serve.
+    pub latest: u64,
+
+    /// The hash of the latest block the peer can serve.
+    pub blockhash: B256,
+}
+
+impl Display for StatusEth69 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let hexed_blockhash = hex::encode(self.blockhash);
+        let hexed_genesis = hex::encode(self.genesis);
+        write!(
+            f,
+            "StatusEth69 {{ version: {}, chain: {}, genesis: {}, forkid: {:X?}, earliest: {}, latest: {}, blockhash: {} }}",
+            self.version,
+            self.chain,
+            hexed_genesis,
+            self.forkid,
+            self.earliest,
+            self.latest,
+            hexed_blockhash,
+        )
+    }
+}
+
+impl Debug for StatusEth69 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// The status message as sent on the wire, which depends on the negotiated [`EthVersion`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub enum StatusMessage {
+    /// The status message used up to eth/68.
+    Legacy(Status),
+    /// The eth/69 status message.
+    Eth69(StatusEth69),
+}
+
+impl StatusMessage {
+    /// Returns the protocol version of the message.
+    pub const fn version(&self) -> EthVersion {
+        match self {
+            Self::Legacy(status) => status.version,
+            Self::Eth69(status) => status.version,
+        }
+    }
+
+    /// Returns the chain of the message.
+    pub const fn chain(&self) -> &Chain {
+        match self {
+            Self::Legacy(status) => &status.chain,
+            Self::Eth69(status) => &status.chain,
+        }
+    }
+
+    /// Returns the genesis hash of the message.
+    pub const fn genesis(&self) -> B256 {
+        match self {
+            Self::Legacy(status) => status.genesis,
+            Self::Eth69(status) => status.genesis,
+        }
+    }
+
+    /// Returns the fork id of the message.
+    pub const fn forkid(&self) -> ForkId {
+        match self {
+            Self::Legacy(status) => status.forkid,
+            Self::Eth69(status) => status.forkid,
+        }
+    }
+
+    /// Returns the hash of the best block of the message.
+    pub const fn blockhash(&self) -> B256 {
+        match self {
+            Self::Legacy(status) => status.blockhash,
+            Self::Eth69(status) => status.blockhash,
+        }
+    }
+
+    /// Returns the total difficulty, which is only part of the legacy message.
+    pub const fn total_difficulty(&self) -> Option<U256> {
+        match self {
+            Self::Legacy(status) => Some(status.total_difficulty),
+            Self::Eth69(_) => None,
+        }
+    }
+}
+
+impl Encodable for StatusMessage {
+    fn encode(&self, out: &mut dyn BufMut) {
+        match self {
+            Self::Legacy(status) => status.encode(out),
+            Self::Eth69(status) => status.encode(out),
+        }
+    }
+
+    fn length(&self) -> usize {
+        match self {
+            Self::Legacy(status) => status.length(),
+            Self::Eth69(status) => status.length(),
+        }
+    }
+}
+
+impl Display for StatusMessage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Legacy(status) => Display::fmt(status, f),
+            Self::Eth69(status) => Display::fmt(status, f),
+        }
+    }
+}
+
+impl From<Status> for StatusMessage {
+    fn from(status: Status) -> Self {
+        Self::Legacy(status)
+    }
+}
+
+impl From<StatusEth69> for StatusMessage {
+    fn from(status: StatusEth69) -> Self {
+        Self::Eth69(status)
+    }
+}
+
+/// The status of the local node or of a peer, independent of the negotiated [`EthVersion`].
+///
+/// This holds the union of the fields of [`Status`] and [`StatusEth69`] and is converted into the
+/// matching [`StatusMessage`] for the negotiated version with [`UnifiedStatus::into_message`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnifiedStatus {
+    /// The protocol version.
+    pub version: EthVersion,
+    /// The chain id.
+    pub chain: Chain,
+    /// The genesis hash of the chain.
+    pub genesis: B256,
+    /// The fork identifier.
+    pub forkid: ForkId,
+    /// The hash of the best block.
+    pub blockhash: B256,
+    /// Total difficulty of the best chain, only sent before eth/69.
+    pub total_difficulty: Option<U256>,
+    /// The earliest block that can be served, only sent since eth/69.
+    pub earliest_block: Option<u64>,
+    /// The latest block that can be served, only sent since eth/69.
+    pub latest_block: Option<u64>,
+}
+
+impl UnifiedStatus {
+    /// Helper for returning a builder for the status.
+    pub fn builder() -> UnifiedStatusBuilder {
+        Default::default()
+    }
+
+    /// Sets the [`EthVersion`] for the status.
+    pub fn set_eth_version(&mut self, version: EthVersion) {
+        self.version = version;
+    }
+
+    /// Create a [`UnifiedStatusBuilder`] from the given [`EthChainSpec`] and head block.
+    ///
+    /// Sets the `chain`, `genesis`, `blockhash`, `total_difficulty`, `latest_block` and `forkid`
+    /// fields based on the [`EthChainSpec`] and head.
+    pub fn spec_builder<Spec>(spec: Spec, head: &Head) -> UnifiedStatusBuilder
+    where
+        Spec: EthChainSpec + Hardforks,
+    {
+        Self::builder()
+            .chain(spec.chain())
+            .genesis(spec.genesis_hash())
+            .blockhash(head.hash)
+            .total_difficulty(Some(head.total_difficulty))
+            .latest_block(Some(head.number))
+            .forkid(spec.fork_id(head))
+    }
+
+    /// Returns the advertised `(earliest, latest)` block range, if known.
+    pub const fn block_range(&self) -> Option<(u64, u64)> {
+        match (self.earliest_block, self.latest_block) {
+            (Some(earliest), Some(latest)) => Some((earliest, latest)),
+            _ => None,
+        }
+    }
+
+    /// Converts the status into the [`StatusMessage`] matching its version.
+    pub fn into_message(self) -> StatusMessage {
+        if self.version >= EthVersion::Eth69 {
+            StatusMessage::Eth69(StatusEth69 {
+                version: self.version,
+                chain: self.chain,
+                genesis: self.genesis,
+                forkid: self.forkid,
+                earliest: self.earliest_block.unwrap_or_default(),
+                latest: self.latest_block.unwrap_or_default(),
+                blockhash: self.blockhash,
+            })
+        } else {
+            StatusMessage::Legacy(Status {
+                version: self.version,
+                chain: self.chain,
+                total_difficulty: self.total_difficulty.unwrap_or_default(),
+                blockhash: self.blockhash,
+                genesis: self.genesis,
+                forkid: self.forkid,
+            })
+        }
+    }
+
+    /// Creates the status from a [`StatusMessage`] received from a peer.
+    pub const fn from_message(message: StatusMessage) -> Self {
+        match message {
+            StatusMessage::Legacy(status) => Self {
+                version: status.version,
+                chain: status.chain,
+                genesis: status.genesis,
+                forkid: status.forkid,
+                blockhash: status.blockhash,
+                total_difficulty: Some(status.total_difficulty),
+                earliest_block: None,
+                latest_block: None,
+            },
+            StatusMessage::Eth69(status) => Self {
+                version: status.version,
+                chain: status.chain,
+                genesis: status.genesis,
+                forkid: status.forkid,
+                blockhash: status.blockhash,
+                total_difficulty: None,
+                earliest_block: Some(status.earliest),
+                latest_block: Some(status.latest),
+            },
+        }
+    }
+}
+
+impl Display for UnifiedStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let hexed_blockhash = hex::encode(self.blockhash);
+        let hexed_genesis = hex::encode(self.genesis);
+        write!(
+            f,
+            "UnifiedStatus {{ version: {}, chain: {}, genesis: {}, forkid: {:X?}, blockhash: {}, total_difficulty: {:?}, earliest_block: {:?}, latest_block: {:?} }}",
+            self.version,
+            self.chain,
+            hexed_genesis,
+            self.forkid,
+            hexed_blockhash,
+            self.total_difficulty,
+            self.earliest_block,
+            self.latest_block,
+        )
+    }
+}
+
+impl Default for UnifiedStatus {
+    fn default() -> Self {
+        let status = Status::default();
+        Self {
+            version: EthVersion::LATEST,
+            chain: status.chain,
+            genesis: status.genesis,
+            forkid: status.forkid,
+            blockhash: status.blockhash,
+            total_difficulty: Some(status.total_difficulty),
+            earliest_block: Some(0),
+            latest_block: Some(0),
+        }
+    }
+}
+
+impl From<Status> for UnifiedStatus {
+    fn from(status: Status) -> Self {
+        Self::from_message(StatusMessage::Legacy(status))
+    }
+}
+
+impl From<StatusEth69> for UnifiedStatus {
+    fn from(status: StatusEth69) -> Self {
+        Self::from_message(StatusMessage::Eth69(status))
+    }
+}
+
+/// Builder for [`UnifiedStatus`].
+#[derive(Debug, Default)]
+pub struct UnifiedStatusBuilder {
+    status: UnifiedStatus,
+}
+
+impl UnifiedStatusBuilder {
+    /// Consumes the type and creates the [`UnifiedStatus`].
+    pub const fn build(self) -> UnifiedStatus {
+        self.status
+    }
+
+    /// Sets the protocol version.
+    pub const fn version(mut self, version: EthVersion) -> Self {
+        self.status.version = version;
+        self
+    }
+
+    /// Sets the chain id.
+    pub const fn chain(mut self, chain: Chain) -> Self {
+        self.status.chain = chain;
+        self
+    }
+
+    /// Sets the genesis hash.
+    pub const fn genesis(mut self, genesis: B256) -> Self {
+        self.status.genesis = genesis;
+        self
+    }
+
+    /// Sets the fork id.
+    pub const fn forkid(mut self, forkid: ForkId) -> Self {
+        self.status.forkid = forkid;
+        self
+    }
+
+    /// Sets the block hash.
+    pub const fn blockhash(mut self, blockhash: B256) -> Self {
+        self.status.blockhash = blockhash;
+        self
+    }
+
+    /// Sets the total difficulty.
+    pub const fn total_difficulty(mut self, total_difficulty: Option<U256>) -> Self {
+        self.status.total_difficulty = total_difficulty;
+        self
+    }
+
+    /// Sets the earliest block that can be served.
+    pub const fn earliest_block(mut self, earliest_block: Option<u64>) -> Self {
+        self.status.earliest_block = earliest_block;
+        self
+    }
+
+    /// Sets the latest block that can be served.
+    pub const fn latest_block(mut self, latest_block: Option<u64>) -> Self {
+        self.status.latest_block = latest_block;
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{EthVersion, Status};
+    use crate::{EthVersion, Status, StatusEth69, StatusMessage, UnifiedStatus};
     use alloy_consensus::constants::MAINNET_GENESIS_HASH;
     use alloy_genesis::Genesis;
     use alloy_primitives::{hex, B256, U256};
@@ -387,4 +762,50 @@ mod tests {
         assert_eq!(status.blockhash, head_hash);
         assert_eq!(status.genesis, genesis_hash);
     }
+
+    #[test]
+    fn roundtrip_eth69_status_message() {
+        let status = StatusEth69 {
+            version: EthVersion::Eth69,
+            chain: Chain::from_named(NamedChain::Mainnet),
+            genesis: MAINNET_GENESIS_HASH,
+            forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
+            earliest: 15_537_394,
+            latest: 21_000_000,
+            blockhash: B256::from_str(
+                "feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d",
+            )
+            .unwrap(),
+        };
+
+        let mut rlp_status = vec![];
+        status.encode(&mut rlp_status);
+        assert_eq!(StatusEth69::decode(&mut &rlp_status[..]).unwrap(), status);
+    }
+
+    #[test]
+    fn unified_status_message_by_version() {
+        let mut status = UnifiedStatus::builder()
+            .total_difficulty(Some(U256::from(100)))
+            .earliest_block(Some(10))
+            .latest_block(Some(20))
+            .build();
+
+        status.set_eth_version(EthVersion::Eth68);
+        let StatusMessage::Legacy(legacy) = status.into_message() else {
+            panic!("expected legacy status")
+        };
+        assert_eq!(legacy.total_difficulty, U256::from(100));
+        let from_legacy = UnifiedStatus::from(legacy);
+        assert_eq!(from_legacy.block_range(), None);
+
+        status.set_eth_version(EthVersion::Eth69);
+        let StatusMessage::Eth69(eth69) = status.into_message() else {
+            panic!("expected eth69 status")
+        };
+        assert_eq!((eth69.earliest, eth69.latest), (10, 20));
+        let from_eth69 = UnifiedStatus::from(eth69);
+        assert_eq!(from_eth69.total_difficulty, None);
+        assert_eq!(from_eth69.block_range(), Some((10, 20)));
+    }
 }
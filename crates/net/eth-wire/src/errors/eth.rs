@@ -38,6 +38,14 @@ pub enum EthStreamError {
         /// The number of transaction sizes.
         sizes_len: usize,
     },
+    #[error("invalid block range update: earliest={earliest} latest={latest}")]
+    /// Received an eth/69 `BlockRangeUpdate` whose earliest block exceeds the latest block.
+    InvalidBlockRangeUpdate {
+        /// The announced earliest block.
+        earliest: u64,
+        /// The announced latest block.
+        latest: u64,
+    },
     /// Error when data is not received from peer for a prolonged period.
     #[error("never received data from remote peer")]
     StreamTimeout,
@@ -102,4 +110,12 @@ pub enum EthHandshakeError {
         /// The maximum allowed bit length for the total difficulty.
         maximum: usize,
     },
+    #[error("earliest block {earliest} is greater than latest block {latest}")]
+    /// Invalid block range advertised in an eth/69 status message.
+    EarliestBlockGreaterThanLatestBlock {
+        /// The advertised earliest block.
+        earliest: u64,
+        /// The advertised latest block.
+        latest: u64,
+    },
 }
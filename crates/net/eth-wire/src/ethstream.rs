@@ -4,7 +4,7 @@ use crate::{
     message::{EthBroadcastMessage, ProtocolBroadcastMessage},
     p2pstream::HANDSHAKE_TIMEOUT,
     CanDisconnect, DisconnectReason, EthMessage, EthNetworkPrimitives, EthVersion, ProtocolMessage,
-    Status,
+    StatusMessage, UnifiedStatus,
 };
 use alloy_primitives::bytes::{Bytes, BytesMut};
 use alloy_rlp::Encodable;
@@ -58,21 +58,24 @@ where
     /// Consumes the [`UnauthedEthStream`] and returns an [`EthStream`] after the `Status`
     /// handshake is completed successfully. This also returns the `Status` message sent by the
     /// remote peer.
+    ///
+    /// The status message sent to the peer is the one of the version of the given
+    /// [`UnifiedStatus`], see [`UnifiedStatus::into_message`].
     pub async fn handshake<N: NetworkPrimitives>(
         self,
-        status: Status,
+        status: UnifiedStatus,
         fork_filter: ForkFilter,
-    ) -> Result<(EthStream<S, N>, Status), EthStreamError> {
+    ) -> Result<(EthStream<S, N>, UnifiedStatus), EthStreamError> {
         self.handshake_with_timeout(status, fork_filter, HANDSHAKE_TIMEOUT).await
     }
 
     /// Wrapper around handshake which enforces a timeout.
     pub async fn handshake_with_timeout<N: NetworkPrimitives>(
         self,
-        status: Status,
+        status: UnifiedStatus,
         fork_filter: ForkFilter,
         timeout_limit: Duration,
-    ) -> Result<(EthStream<S, N>, Status), EthStreamError> {
+    ) -> Result<(EthStream<S, N>, UnifiedStatus), EthStreamError> {
         timeout(timeout_limit, Self::handshake_without_timeout(self, status, fork_filter))
             .await
             .map_err(|_| EthStreamError::StreamTimeout)?
@@ -81,9 +84,9 @@ where
     /// Handshake with no timeout
     pub async fn handshake_without_timeout<N: NetworkPrimitives>(
         mut self,
-        status: Status,
+        status: UnifiedStatus,
         fork_filter: ForkFilter,
-    ) -> Result<(EthStream<S, N>, Status), EthStreamError> {
+    ) -> Result<(EthStream<S, N>, UnifiedStatus), EthStreamError> {
         trace!(
             %status,
             "sending eth status to peer"
@@ -93,8 +96,10 @@ where
         // The max length for a status with TTD is: <msg id = 1 byte> + <rlp(status) = 88 byte>
         self.inner
             .send(
-                alloy_rlp::encode(ProtocolMessage::<N>::from(EthMessage::<N>::Status(status)))
-                    .into(),
+                alloy_rlp::encode(ProtocolMessage::<N>::from(EthMessage::<N>::Status(
+                    status.into_message(),
+                )))
+                .into(),
             )
             .await?;
 
@@ -131,45 +136,59 @@ where
                     status=%resp,
                     "validating incoming eth status from peer"
                 );
-                if status.genesis != resp.genesis {
+                if status.genesis != resp.genesis() {
                     self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
                     return Err(EthHandshakeError::MismatchedGenesis(
-                        GotExpected { expected: status.genesis, got: resp.genesis }.into(),
+                        GotExpected { expected: status.genesis, got: resp.genesis() }.into(),
                     )
                     .into())
                 }
 
-                if status.version != resp.version {
+                if status.version != resp.version() {
                     self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
                     return Err(EthHandshakeError::MismatchedProtocolVersion(GotExpected {
-                        got: resp.version,
+                        got: resp.version(),
                         expected: status.version,
                     })
                     .into())
                 }
 
-                if status.chain != resp.chain {
+                if status.chain != *resp.chain() {
                     self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
                     return Err(EthHandshakeError::MismatchedChain(GotExpected {
-                        got: resp.chain,
+                        got: *resp.chain(),
                         expected: status.chain,
                     })
                     .into())
                 }
 
-                // TD at mainnet block #7753254 is 76 bits. If it becomes 100 million times
-                // larger, it will still fit within 160 bits
-                if status.total_difficulty.bit_len() > 160 {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
-                        got: status.total_difficulty.bit_len(),
-                        maximum: 160,
+                match resp {
+                    StatusMessage::Legacy(legacy) => {
+                        // TD at mainnet block #7753254 is 76 bits. If it becomes 100 million
+                        // times larger, it will still fit within 160 bits
+                        if legacy.total_difficulty.bit_len() > 160 {
+                            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+                            return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
+                                got: legacy.total_difficulty.bit_len(),
+                                maximum: 160,
+                            }
+                            .into())
+                        }
+                    }
+                    StatusMessage::Eth69(eth69) => {
+                        if eth69.earliest > eth69.latest {
+                            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+                            return Err(EthHandshakeError::EarliestBlockGreaterThanLatestBlock {
+                                earliest: eth69.earliest,
+                                latest: eth69.latest,
+                            }
+                            .into())
+                        }
                     }
-                    .into())
                 }
 
                 if let Err(err) =
-                    fork_filter.validate(resp.forkid).map_err(EthHandshakeError::InvalidFork)
+                    fork_filter.validate(resp.forkid()).map_err(EthHandshakeError::InvalidFork)
                 {
                     self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
                     return Err(err.into())
@@ -179,7 +198,7 @@ where
                 // the handshake
                 let stream = EthStream::new(version, self.inner);
 
-                Ok((stream, resp))
+                Ok((stream, UnifiedStatus::from_message(resp)))
             }
             _ => {
                 self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
@@ -377,7 +396,7 @@ mod tests {
         hello::DEFAULT_TCP_PORT,
         p2pstream::UnauthedP2PStream,
         EthMessage, EthStream, EthVersion, HelloMessageWithProtocols, PassthroughCodec,
-        ProtocolVersion, Status,
+        ProtocolVersion, Status, UnifiedStatus,
     };
     use alloy_chains::NamedChain;
     use alloy_primitives::{bytes::Bytes, B256, U256};
@@ -397,7 +416,7 @@ mod tests {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status: UnifiedStatus = Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::ZERO,
@@ -405,7 +424,8 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        }
+        .into();
 
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let local_addr = listener.local_addr().unwrap();
@@ -441,12 +461,110 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn can_handshake_eth69() {
+        let genesis = B256::random();
+        let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
+
+        let status = UnifiedStatus::builder()
+            .version(EthVersion::Eth69)
+            .chain(NamedChain::Mainnet.into())
+            .genesis(genesis)
+            .forkid(fork_filter.current())
+            .blockhash(B256::random())
+            .earliest_block(Some(10))
+            .latest_block(Some(100))
+            .build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let fork_filter_clone = fork_filter.clone();
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = PassthroughCodec::default().framed(incoming);
+            let (_, their_status) = UnauthedEthStream::new(stream)
+                .handshake::<EthNetworkPrimitives>(status, fork_filter_clone)
+                .await
+                .unwrap();
+
+            // the total difficulty is not part of the eth/69 status
+            assert_eq!(their_status.total_difficulty, None);
+            assert_eq!(their_status.block_range(), Some((10, 100)));
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+
+        let (stream, their_status) = UnauthedEthStream::new(sink)
+            .handshake::<EthNetworkPrimitives>(status, fork_filter)
+            .await
+            .unwrap();
+
+        assert_eq!(stream.version(), EthVersion::Eth69);
+        assert_eq!(their_status, UnifiedStatus { total_difficulty: None, ..status });
+
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn fail_handshake_on_invalid_block_range() {
+        let genesis = B256::random();
+        let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
+
+        let status = UnifiedStatus::builder()
+            .version(EthVersion::Eth69)
+            .chain(NamedChain::Mainnet.into())
+            .genesis(genesis)
+            .forkid(fork_filter.current())
+            .earliest_block(Some(100))
+            .latest_block(Some(10))
+            .build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let fork_filter_clone = fork_filter.clone();
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = PassthroughCodec::default().framed(incoming);
+            let handshake_res = UnauthedEthStream::new(stream)
+                .handshake::<EthNetworkPrimitives>(status, fork_filter_clone)
+                .await;
+
+            assert!(matches!(
+                handshake_res,
+                Err(EthStreamError::EthHandshakeError(
+                    EthHandshakeError::EarliestBlockGreaterThanLatestBlock {
+                        earliest: 100,
+                        latest: 10
+                    }
+                ))
+            ));
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+
+        let handshake_res = UnauthedEthStream::new(sink)
+            .handshake::<EthNetworkPrimitives>(status, fork_filter)
+            .await;
+        assert!(matches!(
+            handshake_res,
+            Err(EthStreamError::EthHandshakeError(
+                EthHandshakeError::EarliestBlockGreaterThanLatestBlock { .. }
+            ))
+        ));
+
+        handle.await.unwrap();
+    }
+
     #[tokio::test]
     async fn pass_handshake_on_low_td_bitlen() {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status: UnifiedStatus = Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::from(2).pow(U256::from(100)) - U256::from(1),
@@ -454,7 +572,8 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        }
+        .into();
 
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let local_addr = listener.local_addr().unwrap();
@@ -495,7 +614,7 @@ mod tests {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status: UnifiedStatus = Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::from(2).pow(U256::from(164)),
@@ -503,7 +622,8 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        }
+        .into();
 
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let local_addr = listener.local_addr().unwrap();
@@ -639,7 +759,7 @@ mod tests {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status: UnifiedStatus = Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::ZERO,
@@ -647,7 +767,8 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        }
+        .into();
 
         let status_copy = status;
         let fork_filter_clone = fork_filter.clone();
@@ -710,7 +831,7 @@ mod tests {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status: UnifiedStatus = Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::ZERO,
@@ -718,7 +839,8 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        }
+        .into();
 
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let local_addr = listener.local_addr().unwrap();
@@ -52,10 +52,10 @@ pub mod fuzz_rlp {
     use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
     use reth_codecs::add_arbitrary_tests;
     use reth_eth_wire::{
-        BlockBodies, BlockHeaders, DisconnectReason, GetBlockBodies, GetBlockHeaders, GetNodeData,
-        GetPooledTransactions, GetReceipts, HelloMessage, NewBlock, NewBlockHashes,
-        NewPooledTransactionHashes66, NewPooledTransactionHashes68, NodeData, P2PMessage,
-        PooledTransactions, Receipts, Status, Transactions,
+        BlockBodies, BlockHeaders, BlockRangeUpdate, DisconnectReason, GetBlockBodies,
+        GetBlockHeaders, GetNodeData, GetPooledTransactions, GetReceipts, HelloMessage, NewBlock,
+        NewBlockHashes, NewPooledTransactionHashes66, NewPooledTransactionHashes68, NodeData,
+        P2PMessage, PooledTransactions, Receipts, Status, Transactions,
     };
     use reth_primitives::TransactionSigned;
     use serde::{Deserialize, Serialize};
@@ -112,6 +112,7 @@ pub mod fuzz_rlp {
     fuzz_type_and_name!(Status, fuzz_Status);
     fuzz_type_and_name!(NewBlockHashes, fuzz_NewBlockHashes);
     fuzz_type_and_name!(Transactions, fuzz_Transactions);
+    fuzz_type_and_name!(BlockRangeUpdate, fuzz_BlockRangeUpdate);
 
     // GetBlockHeaders implements all the traits required for roundtrip_encoding, so why is this
     // wrapper type needed?
@@ -35,7 +35,7 @@ pub use events::{
 
 use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
 
-use reth_eth_wire_types::{capability::Capabilities, DisconnectReason, EthVersion, Status};
+use reth_eth_wire_types::{capability::Capabilities, DisconnectReason, EthVersion, UnifiedStatus};
 use reth_network_peers::NodeRecord;
 
 /// The `PeerId` type.
@@ -231,7 +231,7 @@ pub struct PeerInfo {
     /// The negotiated eth version.
     pub eth_version: EthVersion,
     /// The Status message the peer sent for the `eth` handshake
-    pub status: Arc<Status>,
+    pub status: Arc<UnifiedStatus>,
     /// The timestamp when the session to that peer has been established.
     pub session_established: Instant,
     /// The peer's connection kind
@@ -9,10 +9,13 @@ use std::net::{IpAddr, SocketAddr};
 /// - `tcp`: A `SocketAddr` representing the peer's data transfer address.
 /// - `udp`: An optional `SocketAddr` representing the peer's discover address. `None` if the peer
 ///   is directly connecting to us or the port is the same to `tcp`'s
+/// - `alt_tcp`: An optional `SocketAddr` of the other IP version than `tcp`, if the peer is
+///   reachable over both IPv4 and IPv6.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct PeerAddr {
     tcp: SocketAddr,
     udp: Option<SocketAddr>,
+    alt_tcp: Option<SocketAddr>,
 }
 
 impl PeerAddr {
@@ -26,14 +29,58 @@ impl PeerAddr {
         self.udp
     }
 
+    /// Returns the peer's TCP address of the other IP version, if the peer is dual-stack.
+    pub const fn alt_tcp(&self) -> Option<SocketAddr> {
+        self.alt_tcp
+    }
+
     /// Returns a new `PeerAddr` with the given `tcp` and `udp` addresses.
     pub const fn new(tcp: SocketAddr, udp: Option<SocketAddr>) -> Self {
-        Self { tcp, udp }
+        Self { tcp, udp, alt_tcp: None }
     }
 
     /// Returns a new `PeerAddr` with a `tcp` address only.
     pub const fn from_tcp(tcp: SocketAddr) -> Self {
-        Self { tcp, udp: None }
+        Self { tcp, udp: None, alt_tcp: None }
+    }
+
+    /// Sets the TCP address of the other IP version. Ignored if it has the same IP version as
+    /// `tcp`.
+    pub fn with_alt_tcp(mut self, alt_tcp: Option<SocketAddr>) -> Self {
+        self.alt_tcp = alt_tcp.filter(|alt| alt.is_ipv6() != self.tcp.is_ipv6());
+        self
+    }
+
+    /// Keeps the TCP address of the other IP version from `other`, if this address doesn't have
+    /// one yet.
+    pub fn or_alt_tcp_from(self, other: &Self) -> Self {
+        if self.alt_tcp.is_some() {
+            return self
+        }
+        let alt_tcp = [Some(other.tcp), other.alt_tcp]
+            .into_iter()
+            .flatten()
+            .find(|addr| addr.is_ipv6() != self.tcp.is_ipv6());
+        self.with_alt_tcp(alt_tcp)
+    }
+
+    /// Swaps `tcp` and `alt_tcp`, so that the other IP version is dialed next. Does nothing if
+    /// the peer isn't dual-stack.
+    pub const fn swap_tcp(mut self) -> Self {
+        if let Some(alt_tcp) = self.alt_tcp {
+            self.alt_tcp = Some(self.tcp);
+            self.tcp = alt_tcp;
+        }
+        self
+    }
+
+    /// Returns the address with `tcp` set to the preferred IP version, if the peer is dual-stack.
+    pub const fn with_ip_preference(self, prefer_ipv6: bool) -> Self {
+        if self.tcp.is_ipv6() == prefer_ipv6 {
+            self
+        } else {
+            self.swap_tcp()
+        }
     }
 
     /// Returns a new `PeerAddr` with the given `tcp` and `udp` ports.
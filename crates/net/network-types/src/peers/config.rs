@@ -163,6 +163,8 @@ pub struct PeersConfig {
     /// This acts as an IP based rate limit.
     #[cfg_attr(feature = "serde", serde(default, with = "humantime_serde"))]
     pub incoming_ip_throttle_duration: Duration,
+    /// Whether to dial peers that are reachable over both IPv4 and IPv6 on IPv6 first.
+    pub prefer_ipv6: bool,
 }
 
 impl Default for PeersConfig {
@@ -180,6 +182,7 @@ impl Default for PeersConfig {
             basic_nodes: Default::default(),
             max_backoff_count: 5,
             incoming_ip_throttle_duration: INBOUND_IP_THROTTLE_DURATION,
+            prefer_ipv6: false,
         }
     }
 }
@@ -270,6 +273,12 @@ impl PeersConfig {
         self
     }
 
+    /// Configures whether to dial dual-stack peers on IPv6 first.
+    pub const fn with_prefer_ipv6(mut self, prefer_ipv6: bool) -> Self {
+        self.prefer_ipv6 = prefer_ipv6;
+        self
+    }
+
     /// Returns the maximum number of peers, inbound and outbound.
     pub const fn max_peers(&self) -> usize {
         self.connection_info.max_outbound + self.connection_info.max_inbound
@@ -11,7 +11,7 @@ use reth_discv4::{Discv4Config, Discv4ConfigBuilder, NatResolver, DEFAULT_DISCOV
 use reth_discv5::NetworkStackId;
 use reth_dns_discovery::DnsDiscoveryConfig;
 use reth_eth_wire::{
-    EthNetworkPrimitives, HelloMessage, HelloMessageWithProtocols, NetworkPrimitives, Status,
+    EthNetworkPrimitives, HelloMessage, HelloMessageWithProtocols, NetworkPrimitives, UnifiedStatus,
 };
 use reth_ethereum_forks::{ForkFilter, Head};
 use reth_network_peers::{mainnet_nodes, pk2id, sepolia_nodes, PeerId, TrustedPeer};
@@ -46,12 +46,16 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     pub dns_discovery_config: Option<DnsDiscoveryConfig>,
     /// Address to use for discovery v4.
     pub discovery_v4_addr: SocketAddr,
+    /// Additional IPv6 address to use for discovery v4, for dual-stack operation.
+    pub discovery_v4_addr_ipv6: Option<SocketAddr>,
     /// How to set up discovery.
     pub discovery_v4_config: Option<Discv4Config>,
     /// How to set up discovery version 5.
     pub discovery_v5_config: Option<reth_discv5::Config>,
     /// Address to listen for incoming connections
     pub listener_addr: SocketAddr,
+    /// Additional IPv6 address to listen for incoming connections, for dual-stack operation.
+    pub listener_addr_ipv6: Option<SocketAddr>,
     /// How to instantiate peer manager.
     pub peers_config: PeersConfig,
     /// How to configure the [`SessionManager`](crate::session::SessionManager).
@@ -72,7 +76,7 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     /// The executor to use for spawning tasks.
     pub executor: Box<dyn TaskSpawner>,
     /// The `Status` message to send to peers at the beginning.
-    pub status: Status,
+    pub status: UnifiedStatus,
     /// Sets the hello message for the p2p handshake in `RLPx`
     pub hello_message: HelloMessageWithProtocols,
     /// Additional protocols to announce and handle in `RLPx`
@@ -103,7 +107,7 @@ impl<C, N: NetworkPrimitives> NetworkConfig<C, N> {
     /// Create a new instance with all mandatory fields set, rest is field with defaults.
     pub fn new(client: C, secret_key: SecretKey) -> Self
     where
-        C: ChainSpecProvider<ChainSpec: Hardforks>,
+        C: ChainSpecProvider<ChainSpec: Hardforks> + BlockNumReader,
     {
         NetworkConfig::builder(secret_key).build(client)
     }
@@ -183,8 +187,12 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     boot_nodes: HashSet<TrustedPeer>,
     /// Address to use for discovery
     discovery_addr: Option<SocketAddr>,
+    /// Additional IPv6 address to use for discovery
+    discovery_addr_ipv6: Option<SocketAddr>,
     /// Listener for incoming connections
     listener_addr: Option<SocketAddr>,
+    /// Additional IPv6 listener for incoming connections
+    listener_addr_ipv6: Option<SocketAddr>,
     /// How to instantiate peer manager.
     peers_config: Option<PeersConfig>,
     /// How to configure the sessions manager
@@ -227,7 +235,9 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             discovery_v5_builder: None,
             boot_nodes: Default::default(),
             discovery_addr: None,
+            discovery_addr_ipv6: None,
             listener_addr: None,
+            listener_addr_ipv6: None,
             peers_config: None,
             sessions_config: None,
             network_mode: Default::default(),
@@ -279,7 +289,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
 
     /// Sets the highest synced block.
     ///
-    /// This is used to construct the appropriate [`ForkFilter`] and [`Status`] message.
+    /// This is used to construct the appropriate [`ForkFilter`] and [`UnifiedStatus`] message.
     ///
     /// If not set, this defaults to the genesis specified by the current chain specification.
     pub const fn set_head(mut self, head: Head) -> Self {
@@ -339,6 +349,14 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self.listener_addr(addr).discovery_addr(addr)
     }
 
+    /// Sets the additional IPv6 discovery and listener address, for dual-stack operation.
+    ///
+    /// This is a convenience function for both [`NetworkConfigBuilder::listener_addr_ipv6`] and
+    /// [`NetworkConfigBuilder::discovery_addr_ipv6`].
+    pub const fn set_addrs_ipv6(self, addr: SocketAddr) -> Self {
+        self.listener_addr_ipv6(addr).discovery_addr_ipv6(addr)
+    }
+
     /// Sets the socket address the network will listen on.
     ///
     /// By default, this is [`DEFAULT_DISCOVERY_ADDRESS`]
@@ -355,6 +373,22 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Sets an additional IPv6 socket address the network will listen on, next to the
+    /// [`NetworkConfigBuilder::listener_addr`].
+    ///
+    /// By default, the network only listens on a single address.
+    pub const fn listener_addr_ipv6(mut self, listener_addr: SocketAddr) -> Self {
+        self.listener_addr_ipv6 = Some(listener_addr);
+        self
+    }
+
+    /// Sets an additional IPv6 socket address the discovery v4 network will listen on, next to
+    /// the [`NetworkConfigBuilder::discovery_addr`].
+    pub const fn discovery_addr_ipv6(mut self, discovery_addr: SocketAddr) -> Self {
+        self.discovery_addr_ipv6 = Some(discovery_addr);
+        self
+    }
+
     /// Sets the socket address the discovery network will listen on
     pub const fn discovery_addr(mut self, discovery_addr: SocketAddr) -> Self {
         self.discovery_addr = Some(discovery_addr);
@@ -528,7 +562,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
     /// establishing a connection.
     pub fn build<C>(self, client: C) -> NetworkConfig<C, N>
     where
-        C: ChainSpecProvider<ChainSpec: Hardforks>,
+        C: ChainSpecProvider<ChainSpec: Hardforks> + BlockNumReader,
     {
         let peer_id = self.get_peer_id();
         let chain_spec = client.chain_spec();
@@ -539,7 +573,9 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             mut discovery_v5_builder,
             boot_nodes,
             discovery_addr,
+            discovery_addr_ipv6,
             listener_addr,
+            listener_addr_ipv6,
             peers_config,
             sessions_config,
             network_mode,
@@ -559,6 +595,11 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
                 builder = builder.fork(network_stack_id, fork_id)
             }
 
+            // advertise the IPv6 `RLPx` socket too, if dual-stack
+            if let Some(listener_addr_ipv6) = listener_addr_ipv6 {
+                builder = builder.tcp_socket_ipv6(listener_addr_ipv6)
+            }
+
             builder
         });
 
@@ -576,8 +617,10 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             total_difficulty: chain_spec.genesis().difficulty,
         });
 
-        // set the status
-        let status = Status::spec_builder(&chain_spec, &head).build();
+        // set the status, the earliest block is advanced by history expiry
+        let status = UnifiedStatus::spec_builder(&chain_spec, &head)
+            .earliest_block(Some(client.earliest_block_number().unwrap_or_default()))
+            .build();
 
         // set a fork filter based on the chain spec and head
         let fork_filter = chain_spec.fork_filter(head);
@@ -604,7 +647,9 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             discovery_v4_config: discovery_v4_builder.map(|builder| builder.build()),
             discovery_v5_config: discovery_v5_builder.map(|builder| builder.build()),
             discovery_v4_addr: discovery_addr.unwrap_or(DEFAULT_DISCOVERY_ADDRESS),
+            discovery_v4_addr_ipv6: discovery_addr_ipv6,
             listener_addr,
+            listener_addr_ipv6,
             peers_config: peers_config.unwrap_or_default(),
             sessions_config: sessions_config.unwrap_or_default(),
             chain_id,
@@ -653,6 +698,7 @@ mod tests {
     use reth_chainspec::{Chain, MAINNET};
     use reth_dns_discovery::tree::LinkEntry;
     use reth_primitives::ForkHash;
+    use reth_provider::test_utils::MockEthProvider;
     use reth_storage_api::noop::NoopProvider;
     use std::sync::Arc;
 
@@ -673,6 +719,15 @@ mod tests {
         assert_eq!(bootstrap_nodes.len(), 1);
     }
 
+    #[test]
+    fn test_network_status_earliest_block() {
+        let provider = MockEthProvider::default();
+        provider.set_earliest_block_number(100);
+        let config = builder().build(provider);
+
+        assert_eq!(config.status.earliest_block, Some(100));
+    }
+
     #[test]
     fn test_network_fork_filter_default() {
         let mut chain_spec = Arc::clone(&MAINNET);
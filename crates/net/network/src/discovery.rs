@@ -16,14 +16,16 @@ use reth_network_api::{DiscoveredEvent, DiscoveryEvent};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::PeerAddr;
 use secp256k1::SecretKey;
+use socket2::{Domain, Protocol, Socket, Type};
 use std::{
     collections::VecDeque,
+    io,
     net::{IpAddr, SocketAddr},
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
 };
-use tokio::{sync::mpsc, task::JoinHandle};
+use tokio::{net::UdpSocket, sync::mpsc, task::JoinHandle};
 use tokio_stream::{wrappers::ReceiverStream, Stream};
 use tracing::trace;
 
@@ -50,6 +52,12 @@ pub struct Discovery {
     discv4_updates: Option<ReceiverStream<DiscoveryUpdate>>,
     /// The handle to the spawned discv4 service
     _discv4_service: Option<JoinHandle<()>>,
+    /// Handler to interact with the additional IPv6 Discovery v4 service, if dual-stack
+    discv4_ipv6: Option<Discv4>,
+    /// All KAD table updates from the IPv6 discv4 service.
+    discv4_ipv6_updates: Option<ReceiverStream<DiscoveryUpdate>>,
+    /// The handle to the spawned IPv6 discv4 service
+    _discv4_ipv6_service: Option<JoinHandle<()>>,
     /// Handler to interact with the Discovery v5 service
     discv5: Option<Discv5>,
     /// All KAD table updates from the discv5 service.
@@ -127,6 +135,9 @@ impl Discovery {
             discv4,
             discv4_updates,
             _discv4_service,
+            discv4_ipv6: None,
+            discv4_ipv6_updates: None,
+            _discv4_ipv6_service: None,
             discv5,
             discv5_updates,
             discovered_nodes: LruMap::new(DEFAULT_MAX_CAPACITY_DISCOVERED_PEERS_CACHE),
@@ -137,6 +148,32 @@ impl Discovery {
         })
     }
 
+    /// Spawns an additional [`reth_discv4::Discv4Service`] on the IPv6 `discovery_v4_addr`, for
+    /// dual-stack operation.
+    ///
+    /// The socket doesn't accept IPv4 traffic, so it can share the port with the IPv4 discv4
+    /// service.
+    pub fn spawn_discv4_ipv6(
+        &mut self,
+        tcp_addr: SocketAddr,
+        discovery_v4_addr: SocketAddr,
+        sk: SecretKey,
+        discv4_config: Discv4Config,
+    ) -> Result<(), NetworkError> {
+        let local_enr =
+            NodeRecord::from_secret_key(discovery_v4_addr, &sk).with_tcp_port(tcp_addr.port());
+        let (discv4, mut discv4_service) = bind_udp_ipv6_only(discovery_v4_addr)
+            .and_then(|socket| Discv4::with_socket(socket, local_enr, sk, discv4_config))
+            .map_err(|err| {
+                NetworkError::from_io_error(err, ServiceKind::Discovery(discovery_v4_addr))
+            })?;
+        self.discv4_ipv6_updates = Some(discv4_service.update_stream());
+        self._discv4_ipv6_service = Some(discv4_service.spawn());
+        self.discv4_ipv6 = Some(discv4);
+
+        Ok(())
+    }
+
     /// Registers a listener for receiving [`DiscoveryEvent`] updates.
     pub(crate) fn add_listener(&mut self, tx: mpsc::UnboundedSender<DiscoveryEvent>) {
         self.discovery_listeners.push(tx);
@@ -150,7 +187,7 @@ impl Discovery {
 
     /// Updates the `eth:ForkId` field in discv4.
     pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
-        if let Some(discv4) = &self.discv4 {
+        for discv4 in self.discv4.iter().chain(&self.discv4_ipv6) {
             // use forward-compatible forkid entry
             discv4.set_eip868_rlp(b"eth".to_vec(), EnrForkIdEntry::from(fork_id))
         }
@@ -159,7 +196,7 @@ impl Discovery {
 
     /// Bans the [`IpAddr`] in the discovery service.
     pub(crate) fn ban_ip(&self, ip: IpAddr) {
-        if let Some(discv4) = &self.discv4 {
+        for discv4 in self.discv4.iter().chain(&self.discv4_ipv6) {
             discv4.ban_ip(ip)
         }
         if let Some(discv5) = &self.discv5 {
@@ -169,7 +206,7 @@ impl Discovery {
 
     /// Bans the [`PeerId`] and [`IpAddr`] in the discovery service.
     pub(crate) fn ban(&self, peer_id: PeerId, ip: IpAddr) {
-        if let Some(discv4) = &self.discv4 {
+        for discv4 in self.discv4.iter().chain(&self.discv4_ipv6) {
             discv4.ban(peer_id, ip)
         }
         if let Some(discv5) = &self.discv5 {
@@ -187,9 +224,19 @@ impl Discovery {
         self.local_enr.id // local discv4 and discv5 have same id, since signed with same secret key
     }
 
-    /// Add a node to the discv4 table.
+    /// Returns a shared reference to the additional IPv6 discv4, if dual-stack.
+    pub fn discv4_ipv6(&self) -> Option<Discv4> {
+        self.discv4_ipv6.clone()
+    }
+
+    /// Add a node to the discv4 table, of the discv4 service of the node's IP version if
+    /// dual-stack.
     pub(crate) fn add_discv4_node(&self, node: NodeRecord) {
-        if let Some(discv4) = &self.discv4 {
+        let discv4 = match &self.discv4_ipv6 {
+            Some(discv4_ipv6) if node.address.is_ipv6() => Some(discv4_ipv6),
+            _ => self.discv4.as_ref(),
+        };
+        if let Some(discv4) = discv4 {
             discv4.add_node(node);
         }
     }
@@ -208,8 +255,14 @@ impl Discovery {
         Ok(())
     }
 
-    /// Processes an incoming [`NodeRecord`] update from a discovery service
-    fn on_node_record_update(&mut self, record: NodeRecord, fork_id: Option<ForkId>) {
+    /// Processes an incoming [`NodeRecord`] update from a discovery service, with the `RLPx`
+    /// socket of the other IP version if the peer is dual-stack.
+    fn on_node_record_update(
+        &mut self,
+        record: NodeRecord,
+        fork_id: Option<ForkId>,
+        alt_tcp: Option<SocketAddr>,
+    ) {
         let peer_id = record.id;
         let tcp_addr = record.tcp_addr();
         if tcp_addr.port() == 0 {
@@ -217,7 +270,20 @@ impl Discovery {
             return
         }
         let udp_addr = record.udp_addr();
-        let addr = PeerAddr::new(tcp_addr, Some(udp_addr));
+        let addr = PeerAddr::new(tcp_addr, Some(udp_addr)).with_alt_tcp(alt_tcp);
+
+        if let Some(known) = self.discovered_nodes.get(&peer_id) {
+            // the peer can be discovered separately over IPv4 and IPv6
+            let merged = known.or_alt_tcp_from(&addr);
+            if merged != *known {
+                *known = merged;
+                self.queued_events.push_back(DiscoveryEvent::NewNode(
+                    DiscoveredEvent::EventQueued { peer_id, addr: merged, fork_id },
+                ));
+            }
+            return
+        }
+
         _ =
             self.discovered_nodes.get_or_insert(peer_id, || {
                 self.queued_events.push_back(DiscoveryEvent::NewNode(
@@ -231,7 +297,7 @@ impl Discovery {
     fn on_discv4_update(&mut self, update: DiscoveryUpdate) {
         match update {
             DiscoveryUpdate::Added(record) | DiscoveryUpdate::DiscoveredAtCapacity(record) => {
-                self.on_node_record_update(record, None);
+                self.on_node_record_update(record, None, None);
             }
             DiscoveryUpdate::EnrForkId(node, fork_id) => {
                 self.queued_events.push_back(DiscoveryEvent::EnrForkId(node.id, fork_id))
@@ -262,15 +328,22 @@ impl Discovery {
                 self.on_discv4_update(update)
             }
 
+            // drain the IPv6 discv4 update stream
+            while let Some(Poll::Ready(Some(update))) =
+                self.discv4_ipv6_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
+            {
+                self.on_discv4_update(update)
+            }
+
             // drain the discv5 update stream
             while let Some(Poll::Ready(Some(update))) =
                 self.discv5_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
             {
                 if let Some(discv5) = self.discv5.as_mut() {
-                    if let Some(DiscoveredPeer { node_record, fork_id }) =
+                    if let Some(DiscoveredPeer { node_record, fork_id, alt_tcp }) =
                         discv5.on_discv5_update(update)
                     {
-                        self.on_node_record_update(node_record, fork_id);
+                        self.on_node_record_update(node_record, fork_id, alt_tcp);
                     }
                 }
             }
@@ -286,7 +359,7 @@ impl Discovery {
                         "failed adding node discovered by dns to discv5"
                     );
                 }
-                self.on_node_record_update(update.node_record, update.fork_id);
+                self.on_node_record_update(update.node_record, update.fork_id, None);
             }
 
             if self.queued_events.is_empty() {
@@ -296,6 +369,18 @@ impl Discovery {
     }
 }
 
+/// Binds a [`UdpSocket`] to the IPv6 `addr`, that doesn't accept IPv4-mapped traffic.
+fn bind_udp_ipv6_only(addr: SocketAddr) -> io::Result<UdpSocket> {
+    if !addr.is_ipv6() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "expected IPv6 discovery address"))
+    }
+    let socket = Socket::new(Domain::IPV6, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_only_v6(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    UdpSocket::from_std(socket.into())
+}
+
 impl Stream for Discovery {
     type Item = DiscoveryEvent;
 
@@ -327,6 +412,9 @@ impl Discovery {
             discv5_updates: None,
             queued_events: Default::default(),
             _discv4_service: Default::default(),
+            discv4_ipv6: None,
+            discv4_ipv6_updates: None,
+            _discv4_ipv6_service: None,
             _dns_discovery: None,
             dns_discovery_updates: None,
             _dns_disc_service: None,
@@ -6,6 +6,7 @@ use crate::{
 };
 use alloy_consensus::{BlockHeader, ReceiptWithBloom};
 use alloy_eips::BlockHashOrNumber;
+use alloy_primitives::B256;
 use alloy_rlp::Encodable;
 use futures::StreamExt;
 use reth_eth_wire::{
@@ -154,6 +155,19 @@ where
         let _ = response.send(Ok(BlockHeaders(headers)));
     }
 
+    /// Returns `true` if the block is below the earliest block whose body and receipts are kept.
+    ///
+    /// Expired history is not served to peers, even if it can be read from era files, so that
+    /// peers move on to nodes that still store it instead of stalling the node.
+    fn is_history_expired(&self, hash: B256) -> bool {
+        let earliest_block = self.client.earliest_block_number().unwrap_or_default();
+        earliest_block > 0 &&
+            self.client
+                .block_number(hash)
+                .unwrap_or_default()
+                .is_none_or(|number| number < earliest_block)
+    }
+
     fn on_bodies_request(
         &self,
         _peer_id: PeerId,
@@ -166,6 +180,10 @@ where
         let mut total_bytes = 0;
 
         for hash in request.0 {
+            if self.is_history_expired(hash) {
+                break
+            }
+
             if let Some(block) = self.client.block_by_hash(hash).unwrap_or_default() {
                 let (_, body) = block.split();
                 total_bytes += body.length();
@@ -195,6 +213,10 @@ where
         let mut total_bytes = 0;
 
         for hash in request.0 {
+            if self.is_history_expired(hash) {
+                break
+            }
+
             if let Some(receipts_by_block) =
                 self.client.receipts_by_block(BlockHashOrNumber::Hash(hash)).unwrap_or_default()
             {
@@ -316,3 +338,48 @@ pub enum IncomingEthRequest<N: NetworkPrimitives = EthNetworkPrimitives> {
         response: oneshot::Sender<RequestResult<Receipts<N::Receipt>>>,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use reth_primitives::Block;
+    use reth_provider::test_utils::MockEthProvider;
+    use tokio::sync::mpsc;
+
+    fn bodies_response(handler: &EthRequestHandler<MockEthProvider>, hashes: Vec<B256>) -> usize {
+        let (tx, mut rx) = oneshot::channel();
+        handler.on_bodies_request(PeerId::random(), GetBlockBodies(hashes), tx);
+        rx.try_recv().unwrap().unwrap().0.len()
+    }
+
+    #[test]
+    fn does_not_serve_expired_history() {
+        let provider = MockEthProvider::default();
+        let hashes = (0..4u64)
+            .map(|number| {
+                let hash = B256::with_last_byte(number as u8 + 1);
+                let header = Header { number, ..Default::default() };
+                provider.add_block(hash, Block { header, body: Default::default() });
+                hash
+            })
+            .collect::<Vec<_>>();
+        let (peers_tx, _peers_rx) = mpsc::unbounded_channel();
+        let (_requests_tx, requests_rx) = mpsc::channel(1);
+        let handler =
+            EthRequestHandler::new(provider.clone(), PeersHandle::new(peers_tx), requests_rx);
+
+        assert!(!handler.is_history_expired(hashes[0]));
+        assert_eq!(bodies_response(&handler, hashes.clone()), 4);
+
+        provider.set_earliest_block_number(2);
+        assert!(handler.is_history_expired(hashes[1]));
+        assert!(!handler.is_history_expired(hashes[2]));
+        // unknown blocks are treated as expired
+        assert!(handler.is_history_expired(B256::repeat_byte(0xff)));
+
+        // the response stops at the first expired block
+        assert_eq!(bodies_response(&handler, hashes[2..].to_vec()), 2);
+        assert_eq!(bodies_response(&handler, hashes[1..].to_vec()), 0);
+    }
+}
@@ -5,19 +5,23 @@ mod client;
 pub use client::FetchClient;
 
 use crate::message::BlockRequest;
+use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::B256;
 use futures::StreamExt;
-use reth_eth_wire::{EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, NetworkPrimitives};
+use reth_eth_wire::{
+    BlockRangeUpdate, EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, NetworkPrimitives,
+};
 use reth_network_api::test_utils::PeersHandle;
 use reth_network_p2p::{
     error::{EthResponseValidator, PeerRequestResult, RequestError, RequestResult},
-    headers::client::HeadersRequest,
+    headers::client::{HeadersDirection, HeadersRequest},
     priority::Priority,
 };
 use reth_network_peers::PeerId;
 use reth_network_types::ReputationChangeKind;
 use std::{
     collections::{HashMap, VecDeque},
+    ops::RangeInclusive,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc,
@@ -74,11 +78,14 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     }
 
     /// Invoked when connected to a new peer.
+    ///
+    /// The `range` is the block range the peer advertised in its eth/69 status, if any.
     pub(crate) fn new_active_peer(
         &mut self,
         peer_id: PeerId,
         best_hash: B256,
         best_number: u64,
+        range: Option<RangeInclusive<u64>>,
         timeout: Arc<AtomicU64>,
     ) {
         self.peers.insert(
@@ -87,6 +94,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 state: PeerState::Idle,
                 best_hash,
                 best_number,
+                range,
                 timeout,
                 last_response_likely_bad: false,
             },
@@ -123,6 +131,17 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         false
     }
 
+    /// Updates the block range the peer can serve, announced via an eth/69 `BlockRangeUpdate`.
+    pub(crate) fn update_peer_block_range(&mut self, peer_id: &PeerId, update: BlockRangeUpdate) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.range = Some(update.earliest..=update.latest);
+            if update.latest > peer.best_number {
+                peer.best_hash = update.latest_hash;
+                peer.best_number = update.latest;
+            }
+        }
+    }
+
     /// Invoked when an active session is about to be disconnected.
     pub(crate) fn on_pending_disconnect(&mut self, peer_id: &PeerId) {
         if let Some(peer) = self.peers.get_mut(peer_id) {
@@ -130,11 +149,16 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         }
     }
 
-    /// Returns the _next_ idle peer that's ready to accept a request,
+    /// Returns the _next_ idle peer that's ready to accept a request for the given block range,
     /// prioritizing those with the lowest timeout/latency and those that recently responded with
     /// adequate data.
-    fn next_best_peer(&self) -> Option<PeerId> {
-        let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
+    ///
+    /// Only peers that can serve the requested range are considered, so requests for expired
+    /// history are not sent to peers that pruned it.
+    fn next_best_peer(&self, range: Option<&RangeInclusive<u64>>) -> Option<PeerId> {
+        let mut idle = self.peers.iter().filter(|(_, peer)| {
+            peer.state.is_idle() && range.is_none_or(|range| peer.can_serve(range))
+        });
 
         let mut best_peer = idle.next()?;
 
@@ -163,9 +187,18 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             return PollAction::NoRequests
         }
 
-        let Some(peer_id) = self.next_best_peer() else { return PollAction::NoPeersAvailable };
+        // the first request that an idle peer can serve is sent, a request for a range that no
+        // idle peer can serve doesn't block the requests queued after it
+        let Some((idx, peer_id)) =
+            self.queued_requests.iter().enumerate().find_map(|(idx, request)| {
+                let range = request.block_range();
+                Some((idx, self.next_best_peer(range.as_ref())?))
+            })
+        else {
+            return PollAction::NoPeersAvailable
+        };
 
-        let request = self.queued_requests.pop_front().expect("not empty");
+        let request = self.queued_requests.remove(idx).expect("exists");
         let request = self.prepare_block_request(peer_id, request);
 
         PollAction::Ready(FetchAction::BlockRequest { peer_id, request })
@@ -338,6 +371,10 @@ struct Peer {
     best_hash: B256,
     /// Tracks the best number of the peer.
     best_number: u64,
+    /// The range of blocks the peer advertised it can serve.
+    ///
+    /// This is only known for eth/69 peers, older peers are assumed to serve all blocks.
+    range: Option<RangeInclusive<u64>>,
     /// Tracks the current timeout value we use for the peer.
     timeout: Arc<AtomicU64>,
     /// Tracks whether the peer has recently responded with a likely bad response.
@@ -353,6 +390,13 @@ impl Peer {
     fn timeout(&self) -> u64 {
         self.timeout.load(Ordering::Relaxed)
     }
+
+    /// Returns `true` if the peer can serve all blocks of the given range.
+    fn can_serve(&self, range: &RangeInclusive<u64>) -> bool {
+        self.range
+            .as_ref()
+            .is_none_or(|served| served.start() <= range.start() && range.end() <= served.end())
+    }
 }
 
 /// Tracks the state of an individual peer
@@ -415,6 +459,7 @@ pub(crate) enum DownloadRequest<N: NetworkPrimitives> {
         request: Vec<B256>,
         response: oneshot::Sender<PeerRequestResult<Vec<N::BlockBody>>>,
         priority: Priority,
+        range_hint: Option<RangeInclusive<u64>>,
     },
 }
 
@@ -442,6 +487,21 @@ impl<N: NetworkPrimitives> DownloadRequest<N> {
     const fn is_normal_priority(&self) -> bool {
         self.get_priority().is_normal()
     }
+
+    /// Returns the range of block numbers this request targets, if known.
+    fn block_range(&self) -> Option<RangeInclusive<u64>> {
+        match self {
+            Self::GetBlockHeaders { request, .. } => {
+                let BlockHashOrNumber::Number(start) = request.start else { return None };
+                let last = request.limit.saturating_sub(1);
+                match request.direction {
+                    HeadersDirection::Rising => Some(start..=start.saturating_add(last)),
+                    HeadersDirection::Falling => Some(start.saturating_sub(last)..=start),
+                }
+            }
+            Self::GetBlockBodies { range_hint, .. } => range_hint.clone(),
+        }
+    }
 }
 
 /// An action the syncer can emit.
@@ -487,6 +547,7 @@ mod tests {
                 request: vec![],
                 response: tx,
                 priority: Priority::default(),
+                range_hint: None,
             });
             assert!(fetcher.poll(cx).is_pending());
 
@@ -503,20 +564,20 @@ mod tests {
         // Add a few random peers
         let peer1 = B512::random();
         let peer2 = B512::random();
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)));
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(1)));
+        fetcher.new_active_peer(peer1, B256::random(), 1, None, Arc::new(AtomicU64::new(1)));
+        fetcher.new_active_peer(peer2, B256::random(), 2, None, Arc::new(AtomicU64::new(1)));
 
-        let first_peer = fetcher.next_best_peer().unwrap();
+        let first_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         // Pending disconnect for first_peer
         fetcher.on_pending_disconnect(&first_peer);
         // first_peer now isn't idle, so we should get other peer
-        let second_peer = fetcher.next_best_peer().unwrap();
+        let second_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         assert_ne!(first_peer, second_peer);
         // without idle peers, returns None
         fetcher.on_pending_disconnect(&second_peer);
-        assert_eq!(fetcher.next_best_peer(), None);
+        assert_eq!(fetcher.next_best_peer(None), None);
     }
 
     #[tokio::test]
@@ -531,18 +592,18 @@ mod tests {
 
         let peer2_timeout = Arc::new(AtomicU64::new(300));
 
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(30)));
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::clone(&peer2_timeout));
-        fetcher.new_active_peer(peer3, B256::random(), 3, Arc::new(AtomicU64::new(50)));
+        fetcher.new_active_peer(peer1, B256::random(), 1, None, Arc::new(AtomicU64::new(30)));
+        fetcher.new_active_peer(peer2, B256::random(), 2, None, Arc::clone(&peer2_timeout));
+        fetcher.new_active_peer(peer3, B256::random(), 3, None, Arc::new(AtomicU64::new(50)));
 
         // Must always get peer1 (lowest timeout)
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
         // peer2's timeout changes below peer1's
         peer2_timeout.store(10, Ordering::Relaxed);
         // Then we get peer 2 always (now lowest)
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
     }
 
     #[tokio::test]
@@ -576,6 +637,116 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_peer_selection_by_block_range() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        // a fast peer that expired its history and a slower archive peer
+        let expired = B512::random();
+        let archive = B512::random();
+        fetcher.new_active_peer(
+            expired,
+            B256::random(),
+            1000,
+            Some(900..=1000),
+            Arc::new(AtomicU64::new(1)),
+        );
+        fetcher.new_active_peer(
+            archive,
+            B256::random(),
+            1000,
+            Some(0..=1000),
+            Arc::new(AtomicU64::new(10)),
+        );
+
+        assert_eq!(fetcher.next_best_peer(None), Some(expired));
+        assert_eq!(fetcher.next_best_peer(Some(&(950..=960))), Some(expired));
+        assert_eq!(fetcher.next_best_peer(Some(&(10..=20))), Some(archive));
+
+        // the range requested by a headers request
+        let (tx, _rx) = oneshot::channel();
+        let request = DownloadRequest::<EthNetworkPrimitives>::GetBlockHeaders {
+            request: HeadersRequest {
+                start: 100u64.into(),
+                limit: 10,
+                direction: HeadersDirection::Falling,
+            },
+            response: tx,
+            priority: Priority::Normal,
+        };
+        assert_eq!(request.block_range(), Some(91..=100));
+        assert_eq!(fetcher.next_best_peer(request.block_range().as_ref()), Some(archive));
+
+        // the expired peer announces a new range that includes the requested blocks
+        fetcher.update_peer_block_range(
+            &expired,
+            BlockRangeUpdate { earliest: 0, latest: 1001, latest_hash: B256::random() },
+        );
+        assert_eq!(fetcher.peers[&expired].best_number, 1001);
+        assert_eq!(fetcher.next_best_peer(Some(&(10..=20))), Some(expired));
+
+        // no peer can serve the range
+        assert_eq!(fetcher.next_best_peer(Some(&(2000..=2010))), None);
+
+        // the capable peer is busy, don't fall back to a peer that can't serve the range
+        fetcher.new_active_peer(
+            B512::random(),
+            B256::random(),
+            1000,
+            Some(500..=1000),
+            Arc::new(AtomicU64::new(1)),
+        );
+        fetcher.on_pending_disconnect(&archive);
+        fetcher.on_pending_disconnect(&expired);
+        assert_eq!(fetcher.next_best_peer(Some(&(10..=20))), None);
+    }
+
+    #[tokio::test]
+    async fn test_request_without_capable_peer_does_not_block_queue() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let peer = B512::random();
+        fetcher.new_active_peer(
+            peer,
+            B256::random(),
+            1000,
+            Some(900..=1000),
+            Arc::new(AtomicU64::new(1)),
+        );
+
+        let headers_request = |start: u64| {
+            let (tx, _rx) = oneshot::channel();
+            DownloadRequest::<EthNetworkPrimitives>::GetBlockHeaders {
+                request: HeadersRequest {
+                    start: start.into(),
+                    limit: 10,
+                    direction: HeadersDirection::Rising,
+                },
+                response: tx,
+                priority: Priority::Normal,
+            }
+        };
+        // the front request is for history that no peer can serve
+        fetcher.queued_requests.push_back(headers_request(10));
+        fetcher.queued_requests.push_back(headers_request(950));
+
+        let PollAction::Ready(FetchAction::BlockRequest {
+            peer_id,
+            request: BlockRequest::GetBlockHeaders(request),
+        }) = fetcher.poll_action()
+        else {
+            panic!("expected a headers request")
+        };
+        assert_eq!(peer_id, peer);
+        assert_eq!(request.start_block, BlockHashOrNumber::Number(950));
+
+        // the request for the expired history remains queued
+        assert_eq!(fetcher.queued_requests.len(), 1);
+        assert!(matches!(fetcher.poll_action(), PollAction::NoPeersAvailable));
+    }
+
     #[tokio::test]
     async fn test_header_response_outcome() {
         let manager = PeersManager::new(PeersConfig::default());
@@ -601,6 +772,7 @@ mod tests {
             peer_id,
             Default::default(),
             Default::default(),
+            None,
             Default::default(),
         );
 
@@ -1,6 +1,7 @@
 //! Contains connection-oriented interfaces.
 
 use futures::{ready, Stream};
+use socket2::{Domain, Protocol, Socket, Type};
 use std::{
     io,
     net::SocketAddr,
@@ -11,7 +12,8 @@ use tokio::net::{TcpListener, TcpStream};
 
 /// A tcp connection listener.
 ///
-/// Listens for incoming connections.
+/// Listens for incoming connections, optionally on an additional IPv6 socket for dual-stack
+/// operation.
 #[must_use = "Transport does nothing unless polled."]
 #[pin_project::pin_project]
 #[derive(Debug)]
@@ -21,6 +23,10 @@ pub struct ConnectionListener {
     /// The active tcp listener for incoming connections.
     #[pin]
     incoming: TcpListenerStream,
+    /// Local address of the IPv6 listener stream, if dual-stack.
+    local_address_ipv6: Option<SocketAddr>,
+    /// The IPv6 tcp listener for incoming connections, if dual-stack.
+    incoming_ipv6: Option<TcpListenerStream>,
 }
 
 impl ConnectionListener {
@@ -31,25 +37,39 @@ impl ConnectionListener {
         Ok(Self::new(listener, local_addr))
     }
 
+    /// Creates a [`TcpListener`] for `addr` and an additional IPv6-only [`TcpListener`] for
+    /// `addr_ipv6`, that listen for incoming connections.
+    ///
+    /// Since the IPv6 socket doesn't accept IPv4 connections, both can use the same port.
+    pub async fn bind_dual_stack(addr: SocketAddr, addr_ipv6: SocketAddr) -> io::Result<Self> {
+        let mut listener = Self::bind(addr).await?;
+        let listener_ipv6 = bind_ipv6_only(addr_ipv6)?;
+        listener.local_address_ipv6 = Some(listener_ipv6.local_addr()?);
+        listener.incoming_ipv6 = Some(TcpListenerStream { inner: listener_ipv6 });
+        Ok(listener)
+    }
+
     /// Creates a new connection listener stream.
     pub(crate) const fn new(listener: TcpListener, local_address: SocketAddr) -> Self {
-        Self { local_address, incoming: TcpListenerStream { inner: listener } }
+        Self {
+            local_address,
+            incoming: TcpListenerStream { inner: listener },
+            local_address_ipv6: None,
+            incoming_ipv6: None,
+        }
     }
 
     /// Polls the type to make progress.
     pub fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<ListenerEvent> {
         let this = self.project();
-        match ready!(this.incoming.poll_next(cx)) {
-            Some(Ok((stream, remote_addr))) => {
-                if let Err(err) = stream.set_nodelay(true) {
-                    tracing::warn!(target: "net", "set nodelay failed: {:?}", err);
-                }
-                Poll::Ready(ListenerEvent::Incoming { stream, remote_addr })
-            }
-            Some(Err(err)) => Poll::Ready(ListenerEvent::Error(err)),
-            None => {
-                Poll::Ready(ListenerEvent::ListenerClosed { local_address: *this.local_address })
+        if let Poll::Ready(event) = poll_incoming(this.incoming, *this.local_address, cx) {
+            return Poll::Ready(event)
+        }
+        match (this.incoming_ipv6.as_mut(), *this.local_address_ipv6) {
+            (Some(incoming), Some(local_address)) => {
+                poll_incoming(Pin::new(incoming), local_address, cx)
             }
+            _ => Poll::Pending,
         }
     }
 
@@ -57,6 +77,43 @@ impl ConnectionListener {
     pub const fn local_address(&self) -> SocketAddr {
         self.local_address
     }
+
+    /// Returns the IPv6 socket address this listener listens on, if dual-stack.
+    pub const fn local_address_ipv6(&self) -> Option<SocketAddr> {
+        self.local_address_ipv6
+    }
+}
+
+/// Polls the [`TcpListenerStream`] for the next incoming connection.
+fn poll_incoming(
+    incoming: Pin<&mut TcpListenerStream>,
+    local_address: SocketAddr,
+    cx: &mut Context<'_>,
+) -> Poll<ListenerEvent> {
+    match ready!(incoming.poll_next(cx)) {
+        Some(Ok((stream, remote_addr))) => {
+            if let Err(err) = stream.set_nodelay(true) {
+                tracing::warn!(target: "net", "set nodelay failed: {:?}", err);
+            }
+            Poll::Ready(ListenerEvent::Incoming { stream, remote_addr })
+        }
+        Some(Err(err)) => Poll::Ready(ListenerEvent::Error(err)),
+        None => Poll::Ready(ListenerEvent::ListenerClosed { local_address }),
+    }
+}
+
+/// Binds a [`TcpListener`] to the IPv6 `addr`, that doesn't accept IPv4-mapped connections.
+fn bind_ipv6_only(addr: SocketAddr) -> io::Result<TcpListener> {
+    if !addr.is_ipv6() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "expected IPv6 listener address"))
+    }
+    let socket = Socket::new(Domain::IPV6, Type::STREAM, Some(Protocol::TCP))?;
+    socket.set_only_v6(true)?;
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(1024)?;
+    TcpListener::from_std(socket.into())
 }
 
 /// Event type produced by the [`TcpListenerStream`].
@@ -104,7 +161,7 @@ impl Stream for TcpListenerStream {
 mod tests {
     use super::*;
     use std::{
-        net::{Ipv4Addr, SocketAddrV4},
+        net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6},
         pin::pin,
     };
     use tokio::macros::support::poll_fn;
@@ -129,4 +186,28 @@ mod tests {
 
         let _ = TcpStream::connect(local_addr).await.unwrap();
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_incoming_listener_dual_stack() {
+        let listener = ConnectionListener::bind_dual_stack(
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 0)),
+            SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 0, 0, 0)),
+        )
+        .await
+        .unwrap();
+        let local_addr_ipv6 = listener.local_address_ipv6().unwrap();
+
+        let handle = tokio::task::spawn(async move {
+            let mut listener = pin!(listener);
+            match poll_fn(|cx| listener.as_mut().poll(cx)).await {
+                ListenerEvent::Incoming { remote_addr, .. } => assert!(remote_addr.is_ipv6()),
+                _ => {
+                    panic!("unexpected event")
+                }
+            }
+        });
+
+        let _ = TcpStream::connect(local_addr_ipv6).await.unwrap();
+        handle.await.unwrap();
+    }
 }
@@ -213,9 +213,11 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             client,
             secret_key,
             discovery_v4_addr,
+            discovery_v4_addr_ipv6,
             mut discovery_v4_config,
             mut discovery_v5_config,
             listener_addr,
+            listener_addr_ipv6,
             peers_config,
             sessions_config,
             chain_id,
@@ -236,12 +238,17 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         let peers_manager = PeersManager::new(peers_config);
         let peers_handle = peers_manager.handle();
 
-        let incoming = ConnectionListener::bind(listener_addr).await.map_err(|err| {
-            NetworkError::from_io_error(err, ServiceKind::Listener(listener_addr))
-        })?;
+        let incoming = match listener_addr_ipv6 {
+            Some(listener_addr_ipv6) => {
+                ConnectionListener::bind_dual_stack(listener_addr, listener_addr_ipv6).await
+            }
+            None => ConnectionListener::bind(listener_addr).await,
+        }
+        .map_err(|err| NetworkError::from_io_error(err, ServiceKind::Listener(listener_addr)))?;
 
         // retrieve the tcp address of the socket
         let listener_addr = incoming.local_address();
+        let listener_addr_ipv6 = incoming.local_address_ipv6().unwrap_or(listener_addr);
 
         // resolve boot nodes
         let resolved_boot_nodes =
@@ -258,7 +265,16 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             discv5.extend_unsigned_boot_nodes(resolved_boot_nodes)
         }
 
-        let discovery = Discovery::new(
+        // the IPv6 discv4 service only bootstraps from IPv6 boot nodes, and doesn't resolve the
+        // external IP since resolvers return the IPv4 address
+        let discovery_v4_config_ipv6 = discovery_v4_addr_ipv6.and_then(|_| {
+            let mut disc_config = discovery_v4_config.clone()?;
+            disc_config.bootstrap_nodes.retain(|node| node.address.is_ipv6());
+            disc_config.external_ip_resolver = None;
+            Some(disc_config)
+        });
+
+        let mut discovery = Discovery::new(
             listener_addr,
             discovery_v4_addr,
             secret_key,
@@ -267,6 +283,16 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             dns_discovery_config,
         )
         .await?;
+        if let (Some(discovery_v4_addr_ipv6), Some(disc_config)) =
+            (discovery_v4_addr_ipv6, discovery_v4_config_ipv6)
+        {
+            discovery.spawn_discv4_ipv6(
+                listener_addr_ipv6,
+                discovery_v4_addr_ipv6,
+                secret_key,
+                disc_config,
+            )?;
+        }
         // need to retrieve the addr here since provided port could be `0`
         let local_peer_id = discovery.local_id();
         let discv4 = discovery.discv4();
@@ -567,6 +593,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             PeerMessage::EthRequest(req) => {
                 self.on_eth_request(peer_id, req);
             }
+            PeerMessage::BlockRangeUpdate(update) => {
+                self.swarm.state_mut().on_block_range_update(peer_id, update);
+            }
             PeerMessage::ReceivedTransaction(msg) => {
                 self.notify_tx_manager(NetworkTransactionEvent::IncomingTransactions {
                     peer_id,
@@ -650,7 +679,10 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 let _ = tx.send(self.status());
             }
             NetworkHandleMessage::StatusUpdate { head } => {
-                if let Some(transition) = self.swarm.sessions_mut().on_status_update(head) {
+                let earliest_block = self.swarm.state().client().earliest_block_number().ok();
+                if let Some(transition) =
+                    self.swarm.sessions_mut().on_status_update(head, earliest_block)
+                {
                     self.swarm.state_mut().update_fork_id(transition.current);
                 }
             }
@@ -84,6 +84,8 @@ pub struct PeersManager {
     net_connection_state: NetworkConnectionState,
     /// How long to temporarily ban ip on an incoming connection attempt.
     incoming_ip_throttle_duration: Duration,
+    /// Whether to dial dual-stack peers on IPv6 first.
+    prefer_ipv6: bool,
 }
 
 impl PeersManager {
@@ -101,6 +103,7 @@ impl PeersManager {
             basic_nodes,
             max_backoff_count,
             incoming_ip_throttle_duration,
+            prefer_ipv6,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -150,6 +153,7 @@ impl PeersManager {
             max_backoff_count,
             net_connection_state: NetworkConnectionState::default(),
             incoming_ip_throttle_duration,
+            prefer_ipv6,
         }
     }
 
@@ -575,6 +579,13 @@ impl PeersManager {
             }
         }
 
+        // the peer may only be reachable over the other IP version, if it's dual-stack
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            if peer.addr.tcp() == *remote_addr {
+                peer.addr = peer.addr.swap_tcp();
+            }
+        }
+
         self.on_connection_failure(remote_addr, peer_id, err, ReputationChangeKind::FailedToConnect)
     }
 
@@ -720,13 +731,20 @@ impl PeersManager {
         if self.ban_list.is_banned(&peer_id, &addr.tcp().ip()) {
             return
         }
+        let addr = addr.with_ip_preference(self.prefer_ipv6);
 
         match self.peers.entry(peer_id) {
             Entry::Occupied(mut entry) => {
                 let peer = entry.get_mut();
                 peer.kind = kind;
                 peer.fork_id = fork_id;
-                peer.addr = addr;
+                // a peer can be discovered separately over IPv4 and IPv6, but the address of an
+                // incoming connection only has the outgoing port of the peer
+                peer.addr = if peer.remove_after_disconnect {
+                    addr
+                } else {
+                    addr.or_alt_tcp_from(&peer.addr).with_ip_preference(self.prefer_ipv6)
+                };
 
                 if peer.state.is_incoming() {
                     // now that we have an actual discovered address, for that peer and not just the
@@ -799,6 +817,7 @@ impl PeersManager {
         if self.ban_list.is_banned(&peer_id, &addr.tcp().ip()) {
             return
         }
+        let addr = addr.with_ip_preference(self.prefer_ipv6);
 
         match self.peers.entry(peer_id) {
             Entry::Vacant(entry) => {
@@ -807,6 +826,7 @@ impl PeersManager {
                 peer.state = PeerConnectionState::PendingOut;
                 peer.fork_id = fork_id;
                 entry.insert(peer);
+                self.connection_info.inc_pending_out();
                 self.queued_actions
                     .push_back(PeerAction::Connect { peer_id, remote_addr: addr.tcp() });
             }
@@ -1155,7 +1175,7 @@ mod tests {
     use std::{
         future::{poll_fn, Future},
         io,
-        net::{IpAddr, Ipv4Addr, SocketAddr},
+        net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
         pin::Pin,
         task::{Context, Poll},
         time::Duration,
@@ -2593,6 +2613,71 @@ mod tests {
         assert_eq!(peers.connection_info.num_outbound, 0);
     }
 
+    #[tokio::test]
+    async fn test_dual_stack_peer_prefers_ipv6() {
+        let peer = PeerId::random();
+        let ipv4 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let ipv6 = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 8008);
+        let config = PeersConfig::test().with_prefer_ipv6(true);
+        let mut peers = PeersManager::new(config);
+        peers.add_peer(peer, PeerAddr::from_tcp(ipv4).with_alt_tcp(Some(ipv6)), None);
+
+        match event!(peers) {
+            PeerAction::PeerAdded(peer_id) => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        match event!(peers) {
+            PeerAction::Connect { peer_id, remote_addr } => {
+                assert_eq!(peer_id, peer);
+                assert_eq!(remote_addr, ipv6);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dual_stack_peer_discovered_separately() {
+        let peer = PeerId::random();
+        let ipv4 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let ipv6 = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 8008);
+        let mut peers = PeersManager::new(PeersConfig::test());
+        peers.add_peer(peer, PeerAddr::from_tcp(ipv6), None);
+        peers.add_peer(peer, PeerAddr::from_tcp(ipv4), None);
+
+        let addr = peers.peers.get(&peer).unwrap().addr;
+        assert_eq!(addr.tcp(), ipv4);
+        assert_eq!(addr.alt_tcp(), Some(ipv6));
+    }
+
+    #[tokio::test]
+    async fn test_dual_stack_peer_dials_other_ip_version_on_failure() {
+        let peer = PeerId::random();
+        let ipv4 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let ipv6 = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 8008);
+        let mut peers = PeersManager::new(PeersConfig::test());
+        peers.add_and_connect(peer, PeerAddr::from_tcp(ipv4).with_alt_tcp(Some(ipv6)), None);
+
+        match event!(peers) {
+            PeerAction::Connect { remote_addr, .. } => {
+                assert_eq!(remote_addr, ipv4);
+            }
+            _ => unreachable!(),
+        }
+
+        peers.on_outgoing_connection_failure(
+            &ipv4,
+            &peer,
+            &io::Error::new(io::ErrorKind::NetworkUnreachable, ""),
+        );
+
+        let addr = peers.peers.get(&peer).unwrap().addr;
+        assert_eq!(addr.tcp(), ipv6);
+        assert_eq!(addr.alt_tcp(), Some(ipv4));
+        assert_eq!(peers.connection_info.num_pending_out, 0);
+    }
+
     #[tokio::test]
     async fn test_max_concurrent_dials() {
         let config = PeersConfig::default();
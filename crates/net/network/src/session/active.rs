@@ -237,6 +237,24 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
             EthMessage::Receipts(resp) => {
                 on_response!(resp, GetReceipts)
             }
+            EthMessage::Receipts69(resp) => {
+                // restore the blooms that eth/69 peers don't send
+                let RequestPair { request_id, message } = resp;
+                let resp = RequestPair { request_id, message: message.into_receipts() };
+                on_response!(resp, GetReceipts)
+            }
+            EthMessage::BlockRangeUpdate(msg) => {
+                if !msg.is_valid() {
+                    return OnIncomingMessageOutcome::BadMessage {
+                        error: EthStreamError::InvalidBlockRangeUpdate {
+                            earliest: msg.earliest,
+                            latest: msg.latest,
+                        },
+                        message: EthMessage::BlockRangeUpdate(msg),
+                    }
+                }
+                self.try_emit_broadcast(PeerMessage::BlockRangeUpdate(msg)).into()
+            }
         }
     }
 
@@ -257,10 +275,20 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
     fn on_internal_peer_message(&mut self, msg: PeerMessage<N>) {
         match msg {
             PeerMessage::NewBlockHashes(msg) => {
-                self.queued_outgoing.push_back(EthMessage::NewBlockHashes(msg).into());
+                // block announcements are not part of eth/69
+                if !self.conn.version().is_eth69() {
+                    self.queued_outgoing.push_back(EthMessage::NewBlockHashes(msg).into());
+                }
             }
             PeerMessage::NewBlock(msg) => {
-                self.queued_outgoing.push_back(EthBroadcastMessage::NewBlock(msg.block).into());
+                if !self.conn.version().is_eth69() {
+                    self.queued_outgoing.push_back(EthBroadcastMessage::NewBlock(msg.block).into());
+                }
+            }
+            PeerMessage::BlockRangeUpdate(msg) => {
+                if self.conn.version().is_eth69() {
+                    self.queued_outgoing.push_back(EthMessage::BlockRangeUpdate(msg).into());
+                }
             }
             PeerMessage::PooledTransactions(msg) => {
                 if msg.is_valid_for_version(self.conn.version()) {
@@ -295,6 +323,14 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
     /// This will queue the response to be sent to the peer
     fn handle_outgoing_response(&mut self, id: u64, resp: PeerResponseResult<N>) {
         match resp.try_into_message(id) {
+            Ok(EthMessage::Receipts(RequestPair { request_id, message }))
+                if self.conn.version().is_eth69() =>
+            {
+                // eth/69 peers expect receipts without blooms
+                let msg =
+                    EthMessage::Receipts69(RequestPair { request_id, message: message.into() });
+                self.queued_outgoing.push_back(msg.into());
+            }
             Ok(msg) => {
                 self.queued_outgoing.push_back(msg.into());
             }
@@ -796,7 +832,7 @@ mod tests {
     use reth_ecies::stream::ECIESStream;
     use reth_eth_wire::{
         EthNetworkPrimitives, EthStream, GetBlockBodies, HelloMessageWithProtocols, P2PStream,
-        Status, StatusBuilder, UnauthedEthStream, UnauthedP2PStream,
+        UnauthedEthStream, UnauthedP2PStream, UnifiedStatus,
     };
     use reth_network_peers::pk2id;
     use reth_network_types::session::config::PROTOCOL_BREACH_REQUEST_TIMEOUT;
@@ -820,7 +856,7 @@ mod tests {
         secret_key: SecretKey,
         local_peer_id: PeerId,
         hello: HelloMessageWithProtocols,
-        status: Status,
+        status: UnifiedStatus,
         fork_filter: ForkFilter,
         next_id: usize,
     }
@@ -948,7 +984,7 @@ mod tests {
                 hello: eth_hello(&secret_key),
                 secret_key,
                 local_peer_id,
-                status: StatusBuilder::default().build(),
+                status: UnifiedStatus::default(),
                 fork_filter: MAINNET
                     .hardfork_fork_filter(EthereumHardfork::Frontier)
                     .expect("The Frontier fork filter should exist on mainnet"),
@@ -33,9 +33,9 @@ use counter::SessionCounter;
 use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
-    errors::EthStreamError, multiplex::RlpxProtocolMultiplexer, Capabilities, DisconnectReason,
-    EthVersion, HelloMessageWithProtocols, NetworkPrimitives, Status, UnauthedEthStream,
-    UnauthedP2PStream,
+    errors::EthStreamError, multiplex::RlpxProtocolMultiplexer, BlockRangeUpdate, Capabilities,
+    DisconnectReason, EthVersion, HelloMessageWithProtocols, NetworkPrimitives, UnauthedEthStream,
+    UnauthedP2PStream, UnifiedStatus,
 };
 use reth_ethereum_forks::{ForkFilter, ForkId, ForkTransition, Head};
 use reth_metrics::common::mpsc::MeteredPollSender;
@@ -54,6 +54,10 @@ use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::PollSender;
 use tracing::{debug, instrument, trace};
 
+/// The number of blocks the local head has to advance before a new `BlockRangeUpdate` is sent to
+/// eth/69 peers.
+const BLOCK_RANGE_UPDATE_INTERVAL: u64 = 32;
+
 /// Internal identifier for active sessions.
 #[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Eq, Hash)]
 pub struct SessionId(usize);
@@ -77,7 +81,11 @@ pub struct SessionManager<N: NetworkPrimitives> {
     /// The secret key used for authenticating sessions.
     secret_key: SecretKey,
     /// The `Status` message to send to peers.
-    status: Status,
+    status: UnifiedStatus,
+    /// The latest block last announced to eth/69 peers via `BlockRangeUpdate`.
+    announced_latest_block: u64,
+    /// The earliest block last announced to eth/69 peers via `BlockRangeUpdate`.
+    announced_earliest_block: u64,
     /// The `HelloMessage` message to send to peers.
     hello_message: HelloMessageWithProtocols,
     /// The [`ForkFilter`] used to validate the peer's `Status` message.
@@ -124,7 +132,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         secret_key: SecretKey,
         config: SessionsConfig,
         executor: Box<dyn TaskSpawner>,
-        status: Status,
+        status: UnifiedStatus,
         hello_message: HelloMessageWithProtocols,
         fork_filter: ForkFilter,
         extra_protocols: RlpxSubProtocols,
@@ -140,6 +148,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             protocol_breach_request_timeout: config.protocol_breach_request_timeout,
             pending_session_timeout: config.pending_session_timeout,
             secret_key,
+            announced_latest_block: status.latest_block.unwrap_or_default(),
+            announced_earliest_block: status.earliest_block.unwrap_or_default(),
             status,
             hello_message,
             fork_filter,
@@ -171,7 +181,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
     }
 
     /// Returns the current status of the session.
-    pub const fn status(&self) -> Status {
+    pub const fn status(&self) -> UnifiedStatus {
         self.status
     }
 
@@ -214,14 +224,52 @@ impl<N: NetworkPrimitives> SessionManager<N> {
     ///
     /// If the updated activated another fork, this will return a [`ForkTransition`] and updates the
     /// active [`ForkId`]. See also [`ForkFilter::set_head`].
-    pub(crate) fn on_status_update(&mut self, head: Head) -> Option<ForkTransition> {
+    ///
+    /// Once the head advanced by [`BLOCK_RANGE_UPDATE_INTERVAL`] blocks, or the earliest block
+    /// changed because history expired, the new block range is announced to all eth/69 peers.
+    pub(crate) fn on_status_update(
+        &mut self,
+        head: Head,
+        earliest_block: Option<u64>,
+    ) -> Option<ForkTransition> {
         self.status.blockhash = head.hash;
-        self.status.total_difficulty = head.total_difficulty;
+        self.status.total_difficulty = Some(head.total_difficulty);
+        self.status.latest_block = Some(head.number);
+        if let Some(earliest_block) = earliest_block {
+            self.status.earliest_block = Some(earliest_block);
+        }
         let transition = self.fork_filter.set_head(head);
         self.status.forkid = self.fork_filter.current();
+
+        let earliest = self.status.earliest_block.unwrap_or_default();
+        if head.number.abs_diff(self.announced_latest_block) >= BLOCK_RANGE_UPDATE_INTERVAL ||
+            earliest != self.announced_earliest_block
+        {
+            self.announced_latest_block = head.number;
+            self.announced_earliest_block = earliest;
+            self.announce_block_range(BlockRangeUpdate {
+                earliest,
+                latest: head.number,
+                latest_hash: head.hash,
+            });
+        }
+
         transition
     }
 
+    /// Sends the [`BlockRangeUpdate`] to all eth/69 sessions.
+    fn announce_block_range(&mut self, update: BlockRangeUpdate) {
+        let peers = self
+            .active_sessions
+            .iter()
+            .filter(|(_, session)| session.version.is_eth69())
+            .map(|(peer_id, _)| *peer_id)
+            .collect::<Vec<_>>();
+        for peer_id in peers {
+            self.send_message(&peer_id, PeerMessage::BlockRangeUpdate(update));
+        }
+    }
+
     /// An incoming TCP connection was received. This starts the authentication process to turn this
     /// stream into an active peer session.
     ///
@@ -675,7 +723,7 @@ pub enum SessionEvent<N: NetworkPrimitives> {
         /// negotiated eth version
         version: EthVersion,
         /// The Status message the peer sent during the `eth` handshake
-        status: Arc<Status>,
+        status: Arc<UnifiedStatus>,
         /// The channel for sending messages to the peer with the session
         messages: PeerRequestSender<PeerRequest<N>>,
         /// The direction of the session, either `Inbound` or `Outgoing`
@@ -818,7 +866,7 @@ pub(crate) async fn start_pending_incoming_session<N: NetworkPrimitives>(
     remote_addr: SocketAddr,
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
-    status: Status,
+    status: UnifiedStatus,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -849,7 +897,7 @@ async fn start_pending_outbound_session<N: NetworkPrimitives>(
     remote_peer_id: PeerId,
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
-    status: Status,
+    status: UnifiedStatus,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -899,7 +947,7 @@ async fn authenticate<N: NetworkPrimitives>(
     secret_key: SecretKey,
     direction: Direction,
     hello: HelloMessageWithProtocols,
-    status: Status,
+    status: UnifiedStatus,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -980,7 +1028,7 @@ async fn authenticate_stream<N: NetworkPrimitives>(
     local_addr: Option<SocketAddr>,
     direction: Direction,
     mut hello: HelloMessageWithProtocols,
-    mut status: Status,
+    mut status: UnifiedStatus,
     fork_filter: ForkFilter,
     mut extra_handlers: RlpxSubProtocolHandlers,
 ) -> PendingSessionEvent<N> {
@@ -12,8 +12,8 @@ use alloy_consensus::BlockHeader;
 use alloy_primitives::B256;
 use rand::seq::SliceRandom;
 use reth_eth_wire::{
-    BlockHashNumber, Capabilities, DisconnectReason, EthNetworkPrimitives, NetworkPrimitives,
-    NewBlockHashes, Status,
+    BlockHashNumber, BlockRangeUpdate, Capabilities, DisconnectReason, EthNetworkPrimitives,
+    NetworkPrimitives, NewBlockHashes, UnifiedStatus,
 };
 use reth_ethereum_forks::ForkId;
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent, PeerRequest, PeerRequestSender};
@@ -123,6 +123,11 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         &mut self.discovery
     }
 
+    /// Returns the client that can interact with the chain.
+    pub(crate) const fn client(&self) -> &BlockNumReader {
+        &self.client
+    }
+
     /// Returns access to the [`PeersManager`]
     pub(crate) const fn peers(&self) -> &PeersManager {
         &self.peers_manager
@@ -146,16 +151,18 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         &mut self,
         peer: PeerId,
         capabilities: Arc<Capabilities>,
-        status: Arc<Status>,
+        status: Arc<UnifiedStatus>,
         request_tx: PeerRequestSender<PeerRequest<N>>,
         timeout: Arc<AtomicU64>,
     ) {
         debug_assert!(!self.active_peers.contains_key(&peer), "Already connected; not possible");
 
-        // find the corresponding block number
-        let block_number =
-            self.client.block_number(status.blockhash).ok().flatten().unwrap_or_default();
-        self.state_fetcher.new_active_peer(peer, status.blockhash, block_number, timeout);
+        // find the corresponding block number, eth/69 peers advertise it in their status
+        let block_number = status.latest_block.unwrap_or_else(|| {
+            self.client.block_number(status.blockhash).ok().flatten().unwrap_or_default()
+        });
+        let range = status.block_range().map(|(earliest, latest)| earliest..=latest);
+        self.state_fetcher.new_active_peer(peer, status.blockhash, block_number, range, timeout);
 
         self.active_peers.insert(
             peer,
@@ -255,6 +262,14 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         self.state_fetcher.update_peer_block(peer_id, hash, number);
     }
 
+    /// Invoked for an eth/69 `BlockRangeUpdate` message.
+    pub(crate) fn on_block_range_update(&mut self, peer_id: PeerId, update: BlockRangeUpdate) {
+        if let Some(peer) = self.active_peers.get_mut(&peer_id) {
+            peer.best_hash = update.latest_hash;
+        }
+        self.state_fetcher.update_peer_block_range(&peer_id, update);
+    }
+
     /// Invoked when a new [`ForkId`] is activated.
     pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
         self.discovery.update_fork_id(fork_id)
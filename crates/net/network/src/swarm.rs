@@ -9,7 +9,7 @@ use crate::{
 use futures::Stream;
 use reth_eth_wire::{
     errors::EthStreamError, Capabilities, DisconnectReason, EthNetworkPrimitives, EthVersion,
-    NetworkPrimitives, Status,
+    NetworkPrimitives, UnifiedStatus,
 };
 use reth_network_api::{PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
@@ -382,7 +382,7 @@ pub(crate) enum SwarmEvent<N: NetworkPrimitives = EthNetworkPrimitives> {
         /// negotiated eth version
         version: EthVersion,
         messages: PeerRequestSender<PeerRequest<N>>,
-        status: Arc<Status>,
+        status: Arc<UnifiedStatus>,
         direction: Direction,
     },
     SessionClosed {
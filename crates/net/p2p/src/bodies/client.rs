@@ -1,4 +1,5 @@
 use std::{
+    ops::RangeInclusive,
     pin::Pin,
     task::{ready, Context, Poll},
 };
@@ -29,6 +30,21 @@ pub trait BodiesClient: DownloadClient {
     fn get_block_bodies_with_priority(&self, hashes: Vec<B256>, priority: Priority)
         -> Self::Output;
 
+    /// Fetches the block bodies for the requested blocks with priority, with a hint of the range
+    /// of block numbers the hashes belong to.
+    ///
+    /// Clients that know which blocks their peers can serve, e.g. from the block range advertised
+    /// since eth/69, can use the hint to pick a peer. The default implementation ignores it.
+    fn get_block_bodies_with_priority_and_range_hint(
+        &self,
+        hashes: Vec<B256>,
+        priority: Priority,
+        range_hint: Option<RangeInclusive<u64>>,
+    ) -> Self::Output {
+        let _ = range_hint;
+        self.get_block_bodies_with_priority(hashes, priority)
+    }
+
     /// Fetches a single block body for the requested hash.
     fn get_block_body(&self, hash: B256) -> SingleBodyRequest<Self::Output> {
         self.get_block_body_with_priority(hash, Priority::Normal)
@@ -89,7 +89,7 @@ impl LaunchContext {
     /// `config`.
     ///
     /// Attaches both the `NodeConfig` and the loaded `reth.toml` config to the launch context.
-    pub fn with_loaded_toml_config<ChainSpec: EthChainSpec>(
+    pub fn with_loaded_toml_config<ChainSpec: EthChainSpec + EthereumHardforks>(
         self,
         config: NodeConfig<ChainSpec>,
     ) -> eyre::Result<LaunchContextWith<WithConfigs<ChainSpec>>> {
@@ -101,7 +101,7 @@ impl LaunchContext {
     /// `config`.
     ///
     /// This is async because the trusted peers may have to be resolved.
-    pub fn load_toml_config<ChainSpec: EthChainSpec>(
+    pub fn load_toml_config<ChainSpec: EthChainSpec + EthereumHardforks>(
         &self,
         config: &NodeConfig<ChainSpec>,
     ) -> eyre::Result<reth_config::Config> {
@@ -121,7 +121,7 @@ impl LaunchContext {
     }
 
     /// Save prune config to the toml file if node is a full node.
-    fn save_pruning_config_if_full_node<ChainSpec: EthChainSpec>(
+    fn save_pruning_config_if_full_node<ChainSpec: EthChainSpec + EthereumHardforks>(
         reth_config: &mut reth_config::Config,
         config: &NodeConfig<ChainSpec>,
         config_path: impl AsRef<std::path::Path>,
@@ -261,7 +261,9 @@ impl<L, R> LaunchContextWith<Attached<L, R>> {
         &mut self.attachment.right
     }
 }
-impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpec>, R>> {
+impl<R, ChainSpec: EthChainSpec + EthereumHardforks>
+    LaunchContextWith<Attached<WithConfigs<ChainSpec>, R>>
+{
     /// Adjust certain settings in the config to make sure they are set correctly
     ///
     /// This includes:
@@ -386,13 +388,20 @@ where
     where
         N: ProviderNodeTypes<DB = DB, ChainSpec = ChainSpec>,
     {
-        let factory = ProviderFactory::new(
-            self.right().clone(),
-            self.chain_spec(),
-            StaticFileProvider::read_write(self.data_dir().static_files())?,
-        )
-        .with_prune_modes(self.prune_modes())
-        .with_static_files_metrics();
+        let mut static_file_provider =
+            StaticFileProvider::read_write(self.data_dir().static_files())?;
+        if let Some(era_dir) = &self.node_config().pruning.bodies_era_dir {
+            static_file_provider = static_file_provider.with_era_history(era_dir)?;
+        }
+
+        let rpc = &self.node_config().rpc;
+        let factory =
+            ProviderFactory::new(self.right().clone(), self.chain_spec(), static_file_provider)
+                .with_prune_modes(self.prune_modes())
+                .with_trie_changesets(
+                    rpc.rpc_eth_proof_history.then_some(rpc.rpc_eth_proof_history_distance),
+                )
+                .with_static_files_metrics();
 
         let has_receipt_pruning =
             self.toml_config().prune.as_ref().is_some_and(|a| a.has_receipts_pruning());
@@ -1071,6 +1080,10 @@ mod tests {
                     storage_history_full: false,
                     storage_history_distance: None,
                     storage_history_before: None,
+                    bodies_pre_merge: false,
+                    bodies_distance: None,
+                    bodies_before: None,
+                    bodies_era_dir: None,
                     receipts_log_filter: vec![],
                 },
                 ..NodeConfig::test()
@@ -2,8 +2,11 @@
 
 use alloy_consensus::BlockHeader;
 use futures::{future::Either, stream, stream_select, StreamExt};
-use reth_chainspec::EthChainSpec;
-use reth_consensus_debug_client::{DebugConsensusClient, EtherscanBlockProvider};
+use reth_chainspec::{Chain, EthChainSpec};
+use reth_consensus_debug_client::{
+    BeaconApiLightClientProvider, DebugConsensusClient, EtherscanBlockProvider, LightClientConfig,
+    LightClientConsensusClient, LightClientSync, RpcBlockProvider,
+};
 use reth_db_api::{
     database_metrics::{DatabaseMetadata, DatabaseMetrics},
     Database,
@@ -284,26 +287,47 @@ where
             add_ons.launch_add_ons(add_ons_ctx).await?;
 
         // TODO: migrate to devmode with https://github.com/paradigmxyz/reth/issues/10104
-        if let Some(maybe_custom_etherscan_url) = ctx.node_config().debug.etherscan.clone() {
-            info!(target: "reth::cli", "Using etherscan as consensus client");
+        let debug = &ctx.node_config().debug;
+        if let Some(beacon_api_url) = debug.beacon_light_client.clone() {
+            info!(target: "reth::cli", "Using beacon light client as consensus client");
 
             let chain = ctx.node_config().chain.chain();
-            let etherscan_url = maybe_custom_etherscan_url.map(Ok).unwrap_or_else(|| {
-                // If URL isn't provided, use default Etherscan URL for the chain if it is known
-                chain
-                    .etherscan_urls()
-                    .map(|urls| urls.0.to_string())
-                    .ok_or_else(|| eyre::eyre!("failed to get etherscan url for chain: {chain}"))
+            let config = LightClientConfig::from_chain_id(chain.id()).ok_or_else(|| {
+                eyre::eyre!("beacon light client is not supported for chain: {chain}")
             })?;
+            let checkpoint = debug
+                .beacon_checkpoint
+                .ok_or_else(|| eyre::eyre!("beacon light client requires a checkpoint"))?;
+            let sync = LightClientSync::new(
+                BeaconApiLightClientProvider::new(beacon_api_url),
+                config,
+                checkpoint,
+            );
 
-            let block_provider = EtherscanBlockProvider::new(
-                etherscan_url,
-                chain.etherscan_api_key().ok_or_else(|| {
-                    eyre::eyre!(
-                        "etherscan api key not found for rpc consensus client for chain: {chain}"
+            let auth = rpc_server_handles.auth.clone();
+            let light_client_consensus_client = match debug.rpc_consensus_ws.clone() {
+                Some(ws_rpc_url) => Either::Left(
+                    LightClientConsensusClient::new(auth, sync, RpcBlockProvider::new(ws_rpc_url))
+                        .run::<<Types as NodeTypesWithEngine>::Engine>(),
+                ),
+                None => Either::Right(
+                    LightClientConsensusClient::new(
+                        auth,
+                        sync,
+                        etherscan_block_provider(chain, debug.etherscan.clone().flatten())?,
                     )
-                })?,
-            );
+                    .run::<<Types as NodeTypesWithEngine>::Engine>(),
+                ),
+            };
+            ctx.task_executor()
+                .spawn_critical("light client consensus client", light_client_consensus_client);
+        } else if let Some(maybe_custom_etherscan_url) = debug.etherscan.clone() {
+            info!(target: "reth::cli", "Using etherscan as consensus client");
+
+            let block_provider = etherscan_block_provider(
+                ctx.node_config().chain.chain(),
+                maybe_custom_etherscan_url,
+            )?;
             let rpc_consensus_client = DebugConsensusClient::new(
                 rpc_server_handles.auth.clone(),
                 Arc::new(block_provider),
@@ -419,3 +443,25 @@ where
         Ok(handle)
     }
 }
+
+/// Creates the Etherscan block provider for the chain, using the default Etherscan URL of the
+/// chain if no custom URL is provided.
+fn etherscan_block_provider(
+    chain: Chain,
+    custom_etherscan_url: Option<String>,
+) -> eyre::Result<EtherscanBlockProvider> {
+    let etherscan_url = custom_etherscan_url.map(Ok).unwrap_or_else(|| {
+        // If URL isn't provided, use default Etherscan URL for the chain if it is known
+        chain
+            .etherscan_urls()
+            .map(|urls| urls.0.to_string())
+            .ok_or_else(|| eyre::eyre!("failed to get etherscan url for chain: {chain}"))
+    })?;
+
+    Ok(EtherscanBlockProvider::new(
+        etherscan_url,
+        chain.etherscan_api_key().ok_or_else(|| {
+            eyre::eyre!("etherscan api key not found for rpc consensus client for chain: {chain}")
+        })?,
+    ))
+}
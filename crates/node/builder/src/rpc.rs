@@ -9,6 +9,7 @@ use std::{
 
 use alloy_rpc_types::engine::ClientVersionV1;
 use futures::TryFutureExt;
+use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
 use reth_node_api::{
     AddOnsContext, BlockTy, EngineValidator, FullNodeComponents, NodeAddOns, NodeTypes,
     NodeTypesWithEngine,
@@ -18,16 +19,18 @@ use reth_node_core::{
     version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
 };
 use reth_payload_builder::PayloadStore;
+use reth_payload_util::MinerConfig;
 use reth_primitives::EthPrimitives;
 use reth_rpc::{
     eth::{EthApiTypes, FullEthApiServer},
-    EthApi,
+    EthApi, MinerApi,
 };
-use reth_rpc_api::eth::helpers::AddDevSigners;
+use reth_rpc_api::{eth::helpers::AddDevSigners, MinerApiServer};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
+    response_cache::maintain_response_cache_task,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_tasks::TaskExecutor;
@@ -339,6 +342,8 @@ pub struct RpcAddOns<Node: FullNodeComponents, EthApi: EthApiTypes, EV> {
     eth_api_builder: Box<dyn FnOnce(&EthApiBuilderCtx<Node>) -> EthApi + Send + Sync>,
     /// Engine validator
     engine_validator_builder: EV,
+    /// Runtime settings of the block producer updated by the `miner` namespace.
+    miner_config: Option<MinerConfig>,
     _pd: PhantomData<(Node, EthApi)>,
 }
 
@@ -350,6 +355,7 @@ impl<Node: FullNodeComponents, EthApi: EthApiTypes, EV: Debug> Debug
             .field("hooks", &self.hooks)
             .field("eth_api_builder", &"...")
             .field("engine_validator_builder", &self.engine_validator_builder)
+            .field("miner_config", &self.miner_config)
             .finish()
     }
 }
@@ -364,10 +370,20 @@ impl<Node: FullNodeComponents, EthApi: EthApiTypes, EV> RpcAddOns<Node, EthApi,
             hooks: RpcHooks::default(),
             eth_api_builder: Box::new(eth_api_builder),
             engine_validator_builder,
+            miner_config: None,
             _pd: PhantomData,
         }
     }
 
+    /// Sets the [`MinerConfig`] updated by the `miner` namespace.
+    ///
+    /// This should be the same instance that is shared with the payload builder and the
+    /// transaction pool.
+    pub fn with_miner_config(mut self, miner_config: MinerConfig) -> Self {
+        self.miner_config = Some(miner_config);
+        self
+    }
+
     /// Sets the hook that is run once the rpc server is started.
     pub fn on_rpc_started<F>(mut self, hook: F) -> Self
     where
@@ -420,7 +436,30 @@ where
     where
         F: FnOnce(&mut TransportRpcModules, &mut AuthRpcModule) -> eyre::Result<()>,
     {
-        let Self { eth_api_builder, engine_validator_builder, hooks, _pd: _ } = self;
+        self.launch_add_ons_with_registry(ctx, |modules, auth_modules, _| {
+            ext(modules, auth_modules)
+        })
+        .await
+    }
+
+    /// Launches the RPC servers with the given context and an additional hook for extending
+    /// modules.
+    ///
+    /// Unlike [`Self::launch_add_ons_with`], the hook has access to the [`RpcRegistry`] to build
+    /// handlers on top of the configured ones.
+    pub async fn launch_add_ons_with_registry<F>(
+        self,
+        ctx: AddOnsContext<'_, N>,
+        ext: F,
+    ) -> eyre::Result<RpcHandle<N, EthApi>>
+    where
+        F: FnOnce(
+            &mut TransportRpcModules,
+            &mut AuthRpcModule,
+            &mut RpcRegistry<N, EthApi>,
+        ) -> eyre::Result<()>,
+    {
+        let Self { eth_api_builder, engine_validator_builder, hooks, miner_config, _pd: _ } = self;
 
         let engine_validator = engine_validator_builder.build(&ctx).await?;
         let AddOnsContext { node, config, beacon_engine_handle, jwt_secret } = ctx;
@@ -481,10 +520,29 @@ where
 
         let RpcHooks { on_rpc_started, extend_rpc_modules } = hooks;
 
-        ext(ctx.modules, ctx.auth_module)?;
+        if let Some(miner_config) = miner_config {
+            // install the miner api that updates the block producer settings
+            ctx.modules.replace_if_module_configured(
+                RethRpcModule::Miner,
+                MinerApi::new(miner_config).into_rpc(),
+            )?;
+        }
+
+        ext(ctx.modules, ctx.auth_module, ctx.registry)?;
         extend_rpc_modules.extend_rpc_modules(ctx)?;
 
-        let server_config = config.rpc.rpc_server_config();
+        let mut server_config = config.rpc.rpc_server_config();
+        if let Some(response_cache) = config.rpc.response_cache() {
+            node.task_executor().spawn_critical(
+                "rpc response cache task",
+                Box::pin(maintain_response_cache_task(
+                    response_cache.clone(),
+                    node.provider().canonical_state_stream(),
+                    node.provider().finalized_block_stream(),
+                )),
+            );
+            server_config = server_config.with_response_cache(Some(response_cache));
+        }
         let cloned_modules = modules.clone();
         let launch_rpc = server_config.start(&cloned_modules).map_ok(|handle| {
             if let Some(path) = handle.ipc_endpoint() {
@@ -46,6 +46,28 @@ pub struct DebugArgs {
     )]
     pub rpc_consensus_ws: Option<String>,
 
+    /// Runs a consensus client that verifies beacon light-client updates from the given beacon API
+    /// and only follows blocks attested by the sync committee.
+    ///
+    /// Blocks are fetched from `--debug.rpc-consensus-ws` if set, otherwise from Etherscan.
+    #[arg(
+        long = "debug.beacon-light-client",
+        help_heading = "Debug",
+        conflicts_with = "tip",
+        requires = "beacon_checkpoint",
+        value_name = "BEACON_API_URL"
+    )]
+    pub beacon_light_client: Option<String>,
+
+    /// Trusted beacon block root the light client bootstraps from.
+    #[arg(
+        long = "debug.beacon-checkpoint",
+        help_heading = "Debug",
+        requires = "beacon_light_client",
+        value_name = "BLOCK_ROOT"
+    )]
+    pub beacon_checkpoint: Option<B256>,
+
     /// If provided, the engine will skip `n` consecutive FCUs.
     #[arg(long = "debug.skip-fcu", help_heading = "Debug")]
     pub skip_fcu: Option<usize>,
@@ -97,6 +119,8 @@ impl Default for DebugArgs {
             max_block: None,
             etherscan: None,
             rpc_consensus_ws: None,
+            beacon_light_client: None,
+            beacon_checkpoint: None,
             skip_fcu: None,
             skip_new_payload: None,
             reorg_frequency: None,
@@ -334,6 +358,32 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn test_parse_beacon_light_client_args() {
+        let checkpoint = B256::repeat_byte(1);
+        let expected_args = DebugArgs {
+            beacon_light_client: Some("http://localhost:5052".to_string()),
+            beacon_checkpoint: Some(checkpoint),
+            ..Default::default()
+        };
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.beacon-light-client",
+            "http://localhost:5052",
+            "--debug.beacon-checkpoint",
+            &checkpoint.to_string(),
+        ])
+        .args;
+        assert_eq!(args, expected_args);
+
+        assert!(CommandParser::<DebugArgs>::try_parse_from([
+            "reth",
+            "--debug.beacon-light-client",
+            "http://localhost:5052",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_parse_invalid_block_args() {
         let expected_args = DebugArgs {
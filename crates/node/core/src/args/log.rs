@@ -4,7 +4,7 @@ use crate::dirs::{LogsDir, PlatformPath};
 use clap::{ArgAction, Args, ValueEnum};
 use reth_tracing::{
     tracing_subscriber::filter::Directive, FileInfo, FileWorkerGuard, LayerInfo, LogFormat,
-    RethTracer, Tracer,
+    OtlpConfig, RethTracer, Tracer,
 };
 use std::{fmt, fmt::Display};
 use tracing::{level_filters::LevelFilter, Level};
@@ -69,6 +69,9 @@ pub struct LogArgs {
     /// The verbosity settings for the tracer.
     #[command(flatten)]
     pub verbosity: Verbosity,
+    /// The settings for exporting spans to an OTLP collector.
+    #[command(flatten)]
+    pub otlp: OtlpArgs,
 }
 
 impl LogArgs {
@@ -110,11 +113,77 @@ impl LogArgs {
             tracer = tracer.with_file(file, info);
         }
 
+        if let Some(config) = self.otlp.config() {
+            tracer = tracer.with_otlp(config);
+        }
+
         let guard = tracer.init()?;
         Ok(guard)
     }
 }
 
+/// The OTLP settings for the cli.
+#[derive(Debug, Clone, Args)]
+#[command(next_help_heading = "Tracing")]
+pub struct OtlpArgs {
+    /// Export spans to the OTLP/HTTP collector at the given URL, e.g. `http://localhost:4318`.
+    ///
+    /// Spans are posted as protobuf to the `/v1/traces` path of the collector.
+    #[arg(long = "tracing.otlp.endpoint", value_name = "URL", global = true)]
+    pub endpoint: Option<String>,
+
+    /// The ratio of traces to export, between 0 and 1.
+    #[arg(
+        long = "tracing.otlp.sample-ratio",
+        value_name = "RATIO",
+        global = true,
+        default_value_t = 1.0
+    )]
+    pub sample_ratio: f64,
+
+    /// Comma separated `key=value` attributes added to all exported spans, e.g.
+    /// `service.instance.id=node-1`.
+    #[arg(
+        long = "tracing.otlp.resource-attributes",
+        value_name = "KEY=VALUE",
+        global = true,
+        value_delimiter = ',',
+        value_parser = parse_key_value
+    )]
+    pub resource_attributes: Vec<(String, String)>,
+
+    /// The filter to use for exported spans.
+    #[arg(
+        long = "tracing.otlp.filter",
+        value_name = "FILTER",
+        global = true,
+        default_value = "debug"
+    )]
+    pub filter: String,
+}
+
+impl OtlpArgs {
+    /// Returns the [`OtlpConfig`] if an endpoint is configured.
+    pub fn config(&self) -> Option<OtlpConfig> {
+        let endpoint = self.endpoint.clone()?;
+        let config = OtlpConfig::new(endpoint)
+            .with_sample_ratio(self.sample_ratio)
+            .with_filter(self.filter.clone());
+        Some(
+            self.resource_attributes
+                .iter()
+                .fold(config, |config, (key, value)| config.with_resource_attribute(key, value)),
+        )
+    }
+}
+
+/// Parses a `key=value` pair.
+fn parse_key_value(s: &str) -> eyre::Result<(String, String)> {
+    let (key, value) =
+        s.split_once('=').ok_or_else(|| eyre::eyre!("expected `key=value`, got `{s}`"))?;
+    Ok((key.trim().to_string(), value.trim().to_string()))
+}
+
 /// The color mode for the cli.
 #[derive(Debug, Copy, Clone, ValueEnum, Eq, PartialEq)]
 pub enum ColorMode {
@@ -174,3 +243,53 @@ impl Verbosity {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn otlp_disabled_by_default() {
+        let args = CommandParser::<OtlpArgs>::parse_from(["reth"]).args;
+        assert!(args.config().is_none());
+    }
+
+    #[test]
+    fn parse_otlp_args() {
+        let args = CommandParser::<OtlpArgs>::parse_from([
+            "reth",
+            "--tracing.otlp.endpoint",
+            "http://localhost:4318",
+            "--tracing.otlp.sample-ratio",
+            "0.1",
+            "--tracing.otlp.resource-attributes",
+            "service.instance.id=node-1,deployment.environment=prod",
+        ])
+        .args;
+        assert_eq!(args.endpoint.as_deref(), Some("http://localhost:4318"));
+        assert_eq!(args.sample_ratio, 0.1);
+        assert_eq!(
+            args.resource_attributes,
+            vec![
+                ("service.instance.id".to_string(), "node-1".to_string()),
+                ("deployment.environment".to_string(), "prod".to_string()),
+            ]
+        );
+        assert!(args.config().is_some());
+
+        assert!(CommandParser::<OtlpArgs>::try_parse_from([
+            "reth",
+            "--tracing.otlp.resource-attributes",
+            "service.instance.id"
+        ])
+        .is_err());
+    }
+}
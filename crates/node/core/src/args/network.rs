@@ -99,6 +99,15 @@ pub struct NetworkArgs {
     #[arg(long = "port", value_name = "PORT", default_value_t = DEFAULT_DISCOVERY_PORT)]
     pub port: u16,
 
+    /// Additional IPv6 network listening address, to listen on both IPv4 and IPv6. Uses the same
+    /// port as `--addr`.
+    #[arg(long = "addr.ipv6", value_name = "ADDR_IPV6")]
+    pub addr_ipv6: Option<Ipv6Addr>,
+
+    /// Dial peers that are reachable over both IPv4 and IPv6 on IPv6 first.
+    #[arg(long)]
+    pub prefer_ipv6: bool,
+
     /// Maximum number of outbound requests. default: 100
     #[arg(long)]
     pub max_outbound_peers: Option<usize>,
@@ -214,7 +223,8 @@ impl NetworkArgs {
             .peers
             .clone()
             .with_max_inbound_opt(self.max_inbound_peers)
-            .with_max_outbound_opt(self.max_outbound_peers);
+            .with_max_outbound_opt(self.max_outbound_peers)
+            .with_prefer_ipv6(self.prefer_ipv6 || config.peers.prefer_ipv6);
 
         // Configure transactions manager
         let transactions_manager_config = TransactionsManagerConfig {
@@ -264,6 +274,18 @@ impl NetworkArgs {
                 // set discovery port based on instance number
                 self.discovery.port,
             ))
+            // listen on IPv6 too, if dual-stack
+            .apply(|builder| match self.addr_ipv6 {
+                Some(addr_ipv6) => {
+                    builder.listener_addr_ipv6(SocketAddr::new(addr_ipv6.into(), self.port))
+                }
+                None => builder,
+            })
+            .apply(|builder| match self.discovery.addr_ipv6 {
+                Some(addr_ipv6) => builder
+                    .discovery_addr_ipv6(SocketAddr::new(addr_ipv6.into(), self.discovery.port)),
+                None => builder,
+            })
     }
 
     /// If `no_persist_peers` is false then this returns the path to the persistent peers file path.
@@ -321,6 +343,8 @@ impl Default for NetworkArgs {
             nat: NatResolver::Any,
             addr: DEFAULT_DISCOVERY_ADDR,
             port: DEFAULT_DISCOVERY_PORT,
+            addr_ipv6: None,
+            prefer_ipv6: false,
             max_outbound_peers: None,
             max_inbound_peers: None,
             max_concurrent_tx_requests: DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
@@ -367,6 +391,11 @@ pub struct DiscoveryArgs {
     #[arg(id = "discovery.port", long = "discovery.port", value_name = "DISCOVERY_PORT", default_value_t = DEFAULT_DISCOVERY_PORT)]
     pub port: u16,
 
+    /// Additional UDP IPv6 address to use for devp2p peer discovery version 4, to discover peers
+    /// over both IPv4 and IPv6. Uses the same port as `--discovery.addr`.
+    #[arg(id = "discovery.addr.ipv6", long = "discovery.addr.ipv6", value_name = "DISCOVERY_ADDR_IPV6", default_value = None)]
+    pub addr_ipv6: Option<Ipv6Addr>,
+
     /// The UDP IPv4 address to use for devp2p peer discovery version 5. Overwritten by `RLPx`
     /// address, if it's also IPv4.
     #[arg(id = "discovery.v5.addr", long = "discovery.v5.addr", value_name = "DISCOVERY_V5_ADDR", default_value = None)]
@@ -508,6 +537,7 @@ impl Default for DiscoveryArgs {
             disable_nat: false,
             addr: DEFAULT_DISCOVERY_ADDR,
             port: DEFAULT_DISCOVERY_PORT,
+            addr_ipv6: None,
             discv5_addr: None,
             discv5_addr_ipv6: None,
             discv5_port: DEFAULT_DISCOVERY_V5_PORT,
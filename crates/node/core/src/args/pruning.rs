@@ -3,10 +3,10 @@
 use crate::args::error::ReceiptsLogError;
 use alloy_primitives::{Address, BlockNumber};
 use clap::{builder::RangedU64ValueParser, Args};
-use reth_chainspec::EthChainSpec;
+use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition};
 use reth_config::config::PruneConfig;
 use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig, MINIMUM_PRUNING_DISTANCE};
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, path::PathBuf};
 
 /// Parameters for pruning and full node
 #[derive(Debug, Clone, Args, PartialEq, Eq, Default)]
@@ -82,6 +82,22 @@ pub struct PruningArgs {
     #[arg(long = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance"])]
     pub storage_history_before: Option<BlockNumber>,
 
+    // Bodies History
+    /// Expires block bodies and receipts of all pre-merge blocks.
+    #[arg(long = "prune.bodies.pre-merge", conflicts_with_all = &["bodies_distance", "bodies_before"])]
+    pub bodies_pre_merge: bool,
+    /// Expire block bodies and receipts before the `head-N` block number. In other words, keep
+    /// last N + 1 blocks.
+    #[arg(long = "prune.bodies.distance", value_name = "BLOCKS", conflicts_with_all = &["bodies_pre_merge", "bodies_before"])]
+    pub bodies_distance: Option<u64>,
+    /// Expire block bodies and receipts before the specified block number. The specified block
+    /// number is not expired.
+    #[arg(long = "prune.bodies.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["bodies_pre_merge", "bodies_distance"])]
+    pub bodies_before: Option<BlockNumber>,
+    /// Directory of ERA1 files to serve expired block bodies and receipts from.
+    #[arg(long = "prune.bodies.era-dir", value_name = "PATH")]
+    pub bodies_era_dir: Option<PathBuf>,
+
     // Receipts Log Filter
     /// Configure receipts log filter. Format:
     /// <`address`>:<`prune_mode`>[,<`address`>:<`prune_mode`>...] Where <`prune_mode`> can be
@@ -92,7 +108,10 @@ pub struct PruningArgs {
 
 impl PruningArgs {
     /// Returns pruning configuration.
-    pub fn prune_config(&self, chain_spec: &impl EthChainSpec) -> Option<PruneConfig> {
+    pub fn prune_config(
+        &self,
+        chain_spec: &(impl EthChainSpec + EthereumHardforks),
+    ) -> Option<PruneConfig> {
         // Initialise with a default prune configuration.
         let mut config = PruneConfig::default();
 
@@ -111,6 +130,7 @@ impl PruningArgs {
                         .or(Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE))),
                     account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    bodies_history: None,
                     receipts_log_filter: ReceiptsLogPruneConfig(
                         chain_spec
                             .deposit_contract()
@@ -141,6 +161,9 @@ impl PruningArgs {
         if let Some(mode) = self.storage_history_prune_mode() {
             config.segments.storage_history = Some(mode);
         }
+        if let Some(mode) = self.bodies_prune_mode(chain_spec) {
+            config.segments.bodies_history = Some(mode);
+        }
 
         Some(config)
     }
@@ -203,6 +226,22 @@ impl PruningArgs {
             None
         }
     }
+
+    fn bodies_prune_mode(&self, chain_spec: &impl EthereumHardforks) -> Option<PruneMode> {
+        if self.bodies_pre_merge {
+            match chain_spec.ethereum_fork_activation(EthereumHardfork::Paris) {
+                ForkCondition::Block(block) |
+                ForkCondition::TTD { activation_block_number: block, .. } => {
+                    Some(PruneMode::Before(block))
+                }
+                _ => None,
+            }
+        } else if let Some(distance) = self.bodies_distance {
+            Some(PruneMode::Distance(distance))
+        } else {
+            self.bodies_before.map(PruneMode::Before)
+        }
+    }
 }
 
 pub(crate) fn parse_receipts_log_filter(
@@ -267,6 +306,14 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn bodies_pre_merge_prunes_before_paris() {
+        let args =
+            CommandParser::<PruningArgs>::parse_from(["reth", "--prune.bodies.pre-merge"]).args;
+        let config = args.prune_config(&*reth_chainspec::MAINNET).unwrap();
+        assert_eq!(config.segments.bodies_history, Some(PruneMode::Before(15537394)));
+    }
+
     #[test]
     fn test_parse_receipts_log_filter() {
         let filter1 = "0x0000000000000000000000000000000000000001:full";
@@ -14,7 +14,9 @@ use clap::{
     Arg, Args, Command,
 };
 use rand::Rng;
-use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+use reth_rpc_server_types::{
+    constants, RethRpcModule, RpcClientKey, RpcClientQuota, RpcMethodWeight, RpcModuleSelection,
+};
 
 use crate::args::{
     types::{MaxU32, ZeroAsNoneU64},
@@ -181,10 +183,77 @@ pub struct RpcServerArgs {
     )]
     pub rpc_eth_proof_window: u64,
 
+    /// Record trie changesets for every persisted block, so that historical proofs can be
+    /// generated beyond the proof window for the blocks they are retained for.
+    #[arg(long = "rpc.eth-proof-history", default_value_t = false)]
+    pub rpc_eth_proof_history: bool,
+
+    /// The number of most recent blocks trie changesets are retained for if
+    /// `--rpc.eth-proof-history` is set.
+    #[arg(
+        long = "rpc.eth-proof-history-distance",
+        value_name = "BLOCKS",
+        default_value_t = constants::DEFAULT_ETH_PROOF_HISTORY_DISTANCE,
+        value_parser = RangedU64ValueParser::<u64>::new().range(1..)
+    )]
+    pub rpc_eth_proof_history_distance: u64,
+
     /// Maximum number of concurrent getproof requests.
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
 
+    /// Per-client rate limit for the http and ws servers, in quota units per second.
+    ///
+    /// Every call consumes the weight of its method. Rate limiting is disabled if not set.
+    #[arg(long = "rpc.rate-limit", value_name = "UNITS_PER_SECOND")]
+    pub rpc_rate_limit: Option<u64>,
+
+    /// Maximum number of quota units a client can accumulate. Defaults to the rate limit.
+    #[arg(long = "rpc.rate-limit.burst", value_name = "UNITS", requires = "rpc_rate_limit")]
+    pub rpc_rate_limit_burst: Option<u64>,
+
+    /// How clients are identified for rate limiting: `ip`, `header:<name>` or `jwt:<claim>`.
+    ///
+    /// The IP is the address of the connection, see `--rpc.rate-limit.trusted-proxies`. JWT
+    /// claims require `--rpc.jwtsecret`. Requests without a key share a single quota.
+    #[arg(long = "rpc.rate-limit.key", value_name = "KEY", default_value_t = RpcClientKey::Ip)]
+    pub rpc_rate_limit_key: RpcClientKey,
+
+    /// Addresses of proxies whose `X-Forwarded-For` and `X-Real-IP` headers identify the client
+    /// IP for rate limiting, e.g. `127.0.0.1,10.0.0.2`.
+    #[arg(
+        long = "rpc.rate-limit.trusted-proxies",
+        value_name = "IP",
+        value_delimiter = ',',
+        requires = "rpc_rate_limit"
+    )]
+    pub rpc_rate_limit_trusted_proxies: Vec<IpAddr>,
+
+    /// Weight of a method or namespace, e.g. `trace_=20,eth_getLogs=10`.
+    ///
+    /// Methods cost 1 unit by default, `debug_`, `trace_` and `eth_getLogs` cost 10.
+    #[arg(
+        long = "rpc.rate-limit.method-weight",
+        value_name = "METHOD=WEIGHT",
+        value_delimiter = ','
+    )]
+    pub rpc_rate_limit_method_weights: Vec<RpcMethodWeight>,
+
+    /// Quota of individual clients in units per second, e.g. `<api-key>=1000`.
+    #[arg(
+        long = "rpc.rate-limit.client-quota",
+        value_name = "CLIENT=UNITS_PER_SECOND",
+        value_delimiter = ','
+    )]
+    pub rpc_rate_limit_client_quotas: Vec<RpcClientQuota>,
+
+    /// Maximum size of the cache of responses against finalized blocks in megabytes.
+    ///
+    /// Caches the serialized responses of block, transaction, receipt and trace lookups of
+    /// finalized blocks for the http and ws servers. The cache is disabled if not set.
+    #[arg(long = "rpc.response-cache-size", value_name = "MB")]
+    pub rpc_response_cache_size: Option<u64>,
+
     /// Path to file containing disallowed addresses, json-encoded list of strings. Block
     /// validation API will reject blocks containing transactions from these addresses.
     #[arg(long = "builder.disallow", value_name = "PATH", value_parser = reth_cli_util::parsers::read_json_from_file::<HashSet<Address>>)]
@@ -328,9 +397,18 @@ impl Default for RpcServerArgs {
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
             rpc_max_simulate_blocks: constants::DEFAULT_MAX_SIMULATE_BLOCKS,
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
+            rpc_eth_proof_history: false,
+            rpc_eth_proof_history_distance: constants::DEFAULT_ETH_PROOF_HISTORY_DISTANCE,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
+            rpc_rate_limit: None,
+            rpc_rate_limit_burst: None,
+            rpc_rate_limit_key: RpcClientKey::Ip,
+            rpc_rate_limit_trusted_proxies: Vec::new(),
+            rpc_rate_limit_method_weights: Vec::new(),
+            rpc_rate_limit_client_quotas: Vec::new(),
+            rpc_response_cache_size: None,
             builder_disallow: Default::default(),
         }
     }
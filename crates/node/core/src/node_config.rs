@@ -12,7 +12,7 @@ use alloy_consensus::BlockHeader;
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::{BlockNumber, B256};
 use eyre::eyre;
-use reth_chainspec::{ChainSpec, EthChainSpec, MAINNET};
+use reth_chainspec::{ChainSpec, EthChainSpec, EthereumHardforks, MAINNET};
 use reth_config::config::PruneConfig;
 use reth_ethereum_forks::Head;
 use reth_network_p2p::headers::client::HeadersClient;
@@ -274,7 +274,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig>
     where
-        ChainSpec: EthChainSpec,
+        ChainSpec: EthChainSpec + EthereumHardforks,
     {
         self.pruning.prune_config(&self.chain)
     }
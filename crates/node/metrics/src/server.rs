@@ -5,12 +5,27 @@ use crate::{
     version::VersionInfo,
 };
 use eyre::WrapErr;
-use http::{header::CONTENT_TYPE, HeaderValue, Response};
+use http::{
+    header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    HeaderValue, Request, Response, StatusCode,
+};
+use jsonrpsee_server::HttpBody;
 use metrics::describe_gauge;
 use metrics_process::Collector;
 use reth_metrics::metrics::Unit;
+use reth_profiling::ProfilingError;
 use reth_tasks::TaskExecutor;
-use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+use std::{convert::Infallible, net::SocketAddr, sync::Arc, time::Duration};
+
+/// The path serving CPU profiles in the pprof format, the duration is set by the `seconds` query
+/// parameter.
+const CPU_PROFILE_PATH: &str = "/debug/pprof/profile";
+
+/// The path serving jemalloc heap profiles.
+const HEAP_PROFILE_PATH: &str = "/debug/pprof/heap";
+
+/// The duration of CPU profiles if no `seconds` query parameter is given.
+const DEFAULT_CPU_PROFILE_SECONDS: u64 = 30;
 
 /// Configuration for the [`MetricServer`]
 #[derive(Debug)]
@@ -36,6 +51,10 @@ impl MetricServerConfig {
 }
 
 /// [`MetricServer`] responsible for serving the metrics endpoint
+///
+/// Besides the Prometheus metrics, the server collects profiles on request:
+/// - `/debug/pprof/profile?seconds=30`: a CPU profile in the pprof format
+/// - `/debug/pprof/heap`: a jemalloc heap profile
 #[derive(Debug)]
 pub struct MetricServer {
     config: MetricServerConfig,
@@ -101,14 +120,26 @@ impl MetricServer {
 
                 let handle = install_prometheus_recorder();
                 let hook = hook.clone();
-                let service = tower::service_fn(move |_| {
-                    (hook)();
-                    let metrics = handle.handle().render();
-                    let mut response = Response::new(metrics);
-                    response
-                        .headers_mut()
-                        .insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
-                    async move { Ok::<_, Infallible>(response) }
+                let service = tower::service_fn(move |req: Request<_>| {
+                    let hook = hook.clone();
+                    async move {
+                        let response = match req.uri().path() {
+                            CPU_PROFILE_PATH => cpu_profile(req.uri().query()).await,
+                            HEAP_PROFILE_PATH => {
+                                profile_response(reth_profiling::heap_profile(), "heap")
+                            }
+                            _ => {
+                                (hook)();
+                                let metrics = handle.handle().render();
+                                let mut response = Response::new(HttpBody::from(metrics));
+                                response
+                                    .headers_mut()
+                                    .insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+                                response
+                            }
+                        };
+                        Ok::<_, Infallible>(response)
+                    }
                 });
 
                 let mut shutdown = signal.clone().ignore_guard();
@@ -127,6 +158,60 @@ impl MetricServer {
     }
 }
 
+/// Collects a CPU profile for the duration given by the `seconds` query parameter.
+async fn cpu_profile(query: Option<&str>) -> Response<HttpBody> {
+    let seconds = query
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .find_map(|param| param.strip_prefix("seconds="))
+        .map(str::parse::<u64>)
+        .transpose();
+    let Ok(seconds) = seconds else {
+        return error_response(StatusCode::BAD_REQUEST, "invalid seconds parameter".to_string())
+    };
+
+    let profile = reth_profiling::profile_cpu(
+        Duration::from_secs(seconds.unwrap_or(DEFAULT_CPU_PROFILE_SECONDS)),
+        reth_profiling::DEFAULT_CPU_PROFILE_FREQUENCY,
+    )
+    .await;
+    profile_response(profile, "profile")
+}
+
+/// Returns the profile as a file download, or the error that occurred while collecting it.
+fn profile_response(
+    profile: Result<Vec<u8>, ProfilingError>,
+    file_name: &'static str,
+) -> Response<HttpBody> {
+    let err = match profile {
+        Ok(profile) => {
+            let mut response = Response::new(HttpBody::from(profile));
+            let headers = response.headers_mut();
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/octet-stream"));
+            if let Ok(value) = HeaderValue::from_str(&format!("attachment; filename={file_name}")) {
+                headers.insert(CONTENT_DISPOSITION, value);
+            }
+            return response
+        }
+        Err(err) => err,
+    };
+
+    let status = match err {
+        ProfilingError::AlreadyRunning => StatusCode::CONFLICT,
+        ProfilingError::DurationTooLong(..) => StatusCode::BAD_REQUEST,
+        ProfilingError::Unsupported(_) => StatusCode::NOT_IMPLEMENTED,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    error_response(status, err.to_string())
+}
+
+fn error_response(status: StatusCode, message: String) -> Response<HttpBody> {
+    let mut response = Response::new(HttpBody::from(message));
+    *response.status_mut() = status;
+    response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+    response
+}
+
 fn describe_db_metrics() {
     describe_gauge!("db.table_size", Unit::Bytes, "The size of a database table (in bytes)");
     describe_gauge!("db.table_pages", "The number of database pages for a table");
@@ -252,5 +337,20 @@ mod tests {
         let body = response.text().await.unwrap();
         assert!(body.contains("reth_process_cpu_seconds_total"));
         assert!(body.contains("reth_process_start_time_seconds"));
+
+        // Collect a CPU profile
+        let response =
+            Client::new().get(format!("{url}/debug/pprof/profile?seconds=1")).send().await.unwrap();
+        assert!(response.status().is_success());
+        let profile = response.bytes().await.unwrap();
+        // gzip magic bytes
+        assert_eq!(&profile[..2], &[0x1f, 0x8b]);
+
+        let response = Client::new()
+            .get(format!("{url}/debug/pprof/profile?seconds=invalid"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 }
@@ -16,10 +16,13 @@ pub mod constants;
 mod dev;
 mod op;
 mod op_sepolia;
+#[cfg(feature = "superchain-registry")]
+pub mod superchain;
 
 use alloc::{boxed::Box, vec, vec::Vec};
 use alloy_chains::Chain;
 use alloy_consensus::{BlockHeader, Header};
+use alloy_eips::eip7840::BlobParams;
 use alloy_genesis::Genesis;
 use alloy_primitives::{B256, U256};
 pub use base::BASE_MAINNET;
@@ -30,8 +33,9 @@ pub use op::OP_MAINNET;
 use op_alloy_consensus::{decode_holocene_extra_data, EIP1559ParamError};
 pub use op_sepolia::OP_SEPOLIA;
 use reth_chainspec::{
-    BaseFeeParams, BaseFeeParamsKind, ChainSpec, ChainSpecBuilder, DepositContract, EthChainSpec,
-    EthereumHardforks, ForkFilter, ForkId, Hardforks, Head,
+    BaseFeeParams, BaseFeeParamsKind, ChainSpec, ChainSpecBuilder, ChainSpecGenesis,
+    DepositContract, EthChainSpec, EthereumHardforks, ForkFilter, ForkId, HardforkBlobParams,
+    Hardforks, Head,
 };
 use reth_ethereum_forks::{ChainHardforks, EthereumHardfork, ForkCondition, Hardfork};
 use reth_network_peers::NodeRecord;
@@ -167,6 +171,8 @@ impl OpChainSpecBuilder {
     /// Enable Isthmus at genesis
     pub fn isthmus_activated(mut self) -> Self {
         self = self.holocene_activated();
+        // Isthmus also activates changes from L1's Prague hardfork
+        self.inner = self.inner.with_fork(EthereumHardfork::Prague, ForkCondition::Timestamp(0));
         self.inner = self.inner.with_fork(OpHardfork::Isthmus, ForkCondition::Timestamp(0));
         self
     }
@@ -260,6 +266,10 @@ impl EthChainSpec for OpChainSpec {
         self.inner.base_fee_params_at_timestamp(timestamp)
     }
 
+    fn blob_params_at_timestamp(&self, timestamp: u64) -> Option<BlobParams> {
+        self.inner.blob_params_at_timestamp(timestamp)
+    }
+
     fn deposit_contract(&self) -> Option<&DepositContract> {
         self.inner.deposit_contract()
     }
@@ -339,6 +349,12 @@ impl OpHardforks for OpChainSpec {
 
 impl From<Genesis> for OpChainSpec {
     fn from(genesis: Genesis) -> Self {
+        ChainSpecGenesis::from(genesis).into()
+    }
+}
+
+impl From<ChainSpecGenesis> for OpChainSpec {
+    fn from(ChainSpecGenesis { genesis, blob_schedule }: ChainSpecGenesis) -> Self {
         use reth_optimism_forks::OpHardfork;
         let optimism_genesis_info = OpGenesisInfo::extract_from(&genesis);
         let genesis_info =
@@ -379,7 +395,11 @@ impl From<Genesis> for OpChainSpec {
         let time_hardfork_opts = [
             (EthereumHardfork::Shanghai.boxed(), genesis.config.shanghai_time),
             (EthereumHardfork::Cancun.boxed(), genesis.config.cancun_time),
-            (EthereumHardfork::Prague.boxed(), genesis.config.prague_time),
+            // Isthmus activates the Prague EIPs, so Prague defaults to the Isthmus timestamp
+            (
+                EthereumHardfork::Prague.boxed(),
+                genesis.config.prague_time.or(genesis_info.isthmus_time),
+            ),
             (OpHardfork::Regolith.boxed(), genesis_info.regolith_time),
             (OpHardfork::Canyon.boxed(), genesis_info.canyon_time),
             (OpHardfork::Ecotone.boxed(), genesis_info.ecotone_time),
@@ -398,35 +418,40 @@ impl From<Genesis> for OpChainSpec {
 
         block_hardforks.append(&mut time_hardforks);
 
-        // Ordered Hardforks
-        let mainnet_hardforks = OpHardfork::op_mainnet();
-        let mainnet_order = mainnet_hardforks.forks_iter();
-
-        let mut ordered_hardforks = Vec::with_capacity(block_hardforks.len());
-        for (hardfork, _) in mainnet_order {
-            if let Some(pos) = block_hardforks.iter().position(|(e, _)| **e == *hardfork) {
-                ordered_hardforks.push(block_hardforks.remove(pos));
-            }
-        }
-
-        // append the remaining unknown hardforks to ensure we don't filter any out
-        ordered_hardforks.append(&mut block_hardforks);
-
         Self {
             inner: ChainSpec {
                 chain: genesis.config.chain_id.into(),
                 genesis,
-                hardforks: ChainHardforks::new(ordered_hardforks),
+                hardforks: ordered_hardforks(block_hardforks),
                 // We assume no OP network merges, and set the paris block and total difficulty to
                 // zero
                 paris_block_and_final_difficulty: Some((0, U256::ZERO)),
                 base_fee_params: optimism_genesis_info.base_fee_params,
+                blob_params: HardforkBlobParams::from_entries(&blob_schedule),
                 ..Default::default()
             },
         }
     }
 }
 
+/// Orders the hardforks of a chain like the hardforks of OP mainnet.
+fn ordered_hardforks(mut hardforks: Vec<(Box<dyn Hardfork>, ForkCondition)>) -> ChainHardforks {
+    let mainnet_hardforks = OpHardfork::op_mainnet();
+    let mainnet_order = mainnet_hardforks.forks_iter();
+
+    let mut ordered_hardforks = Vec::with_capacity(hardforks.len());
+    for (hardfork, _) in mainnet_order {
+        if let Some(pos) = hardforks.iter().position(|(e, _)| **e == *hardfork) {
+            ordered_hardforks.push(hardforks.remove(pos));
+        }
+    }
+
+    // append the remaining unknown hardforks to ensure we don't filter any out
+    ordered_hardforks.append(&mut hardforks);
+
+    ChainHardforks::new(ordered_hardforks)
+}
+
 #[derive(Default, Debug)]
 struct OpGenesisInfo {
     optimism_chain_info: op_alloy_rpc_types::OpChainInfo,
@@ -975,6 +1000,7 @@ mod tests {
                     (String::from("fjordTime"), 0.into()),
                     (String::from("graniteTime"), 0.into()),
                     (String::from("holoceneTime"), 0.into()),
+                    (String::from("isthmusTime"), 0.into()),
                 ]
                 .into_iter()
                 .collect(),
@@ -1009,7 +1035,8 @@ mod tests {
             OpHardfork::Fjord.boxed(),
             OpHardfork::Granite.boxed(),
             OpHardfork::Holocene.boxed(),
-            // OpHardfork::Isthmus.boxed(),
+            EthereumHardfork::Prague.boxed(),
+            OpHardfork::Isthmus.boxed(),
         ];
 
         for (expected, actual) in expected_hardforks.iter().zip(hardforks.iter()) {
@@ -0,0 +1,759 @@
+//! Chain specs loaded from a local checkout of the [superchain registry].
+//!
+//! The registry lists every chain in `chainList.json`. Each chain has a TOML config at
+//! `superchain/configs/<superchain>/<chain>.toml`, which holds its hardfork times, EIP-1559
+//! parameters and genesis system config. Hardforks the chain doesn't configure are inherited from
+//! `superchain/configs/<superchain>/superchain.toml` once the chain has joined the superchain. The
+//! genesis is stored zstd-compressed at `superchain/extra/genesis/<superchain>/<chain>.json.zst`.
+//! It references contract code by hash in `superchain/extra/bytecodes`. Both are compressed with
+//! the dictionary at `superchain/extra/dictionary`.
+//!
+//! [superchain registry]: https://github.com/ethereum-optimism/superchain-registry
+
+use crate::{ordered_hardforks, OpChainSpec};
+use alloy_chains::Chain;
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_genesis::{ChainConfig, Genesis, GenesisAccount};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use reth_chainspec::{BaseFeeParams, BaseFeeParamsKind, ChainSpec, EthChainSpec};
+use reth_ethereum_forks::{ChainHardforks, EthereumHardfork, ForkCondition, Hardfork};
+use reth_optimism_forks::OpHardfork;
+use reth_primitives_traits::GotExpected;
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Errors that can occur when loading a chain from the superchain registry.
+#[derive(Debug, thiserror::Error)]
+pub enum SuperchainRegistryError {
+    /// Failed to read a registry file.
+    #[error("failed to read {path}: {err}")]
+    Io {
+        /// The file that couldn't be read.
+        path: PathBuf,
+        /// The underlying error.
+        err: io::Error,
+    },
+    /// Failed to parse a TOML config.
+    #[error("failed to parse {path}: {err}")]
+    Toml {
+        /// The config that couldn't be parsed.
+        path: PathBuf,
+        /// The underlying error.
+        err: toml::de::Error,
+    },
+    /// Failed to parse a JSON file.
+    #[error("failed to parse {path}: {err}")]
+    Json {
+        /// The file that couldn't be parsed.
+        path: PathBuf,
+        /// The underlying error.
+        err: serde_json::Error,
+    },
+    /// The chain isn't listed in the registry.
+    #[error("chain {0} is not in the superchain registry")]
+    UnknownChain(String),
+    /// The chain name matches chains in several superchains.
+    #[error("chain {name} is ambiguous, use one of: {}", candidates.join(", "))]
+    AmbiguousChain {
+        /// The requested name.
+        name: String,
+        /// Identifiers of all chains with that name.
+        candidates: Vec<String>,
+    },
+    /// The registry only stores the state root of the genesis, not the allocations.
+    #[error("genesis allocations of chain {0} are not included in the superchain registry")]
+    MissingGenesisState(String),
+    /// The path isn't a chain config of a registry checkout.
+    #[error("{} is not a chain config of the superchain registry", .0.display())]
+    InvalidConfigPath(PathBuf),
+    /// The gas limit of the genesis block doesn't match the gas limit of the genesis system
+    /// config.
+    #[error("genesis gas limit of chain {chain} doesn't match its system config: {mismatch}")]
+    GasLimitMismatch {
+        /// Identifier of the chain.
+        chain: String,
+        /// The gas limit of the genesis block and of the system config.
+        mismatch: GotExpected<u64>,
+    },
+    /// The hash of the genesis block built from the registry doesn't match the configured L2
+    /// genesis hash.
+    #[error("genesis hash of chain {chain} doesn't match its config: {mismatch}")]
+    GenesisHashMismatch {
+        /// Identifier of the chain.
+        chain: String,
+        /// The hash of the built genesis block and the configured hash.
+        mismatch: GotExpected<B256>,
+    },
+}
+
+/// A chain entry of the registry's `chainList.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainListEntry {
+    /// Display name of the chain.
+    pub name: String,
+    /// Identifier of the chain, `<superchain>/<chain>`.
+    pub identifier: String,
+    /// Chain id.
+    pub chain_id: u64,
+}
+
+impl ChainListEntry {
+    /// Returns the superchain and the chain name from the identifier.
+    pub fn split_identifier(&self) -> Option<(&str, &str)> {
+        self.identifier.split_once('/')
+    }
+}
+
+/// Hardfork activation times, as configured in the registry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct HardforkConfig {
+    /// Regolith activation time, only configured for chains launched before Regolith.
+    pub regolith_time: Option<u64>,
+    /// Canyon activation time.
+    pub canyon_time: Option<u64>,
+    /// Delta activation time.
+    pub delta_time: Option<u64>,
+    /// Ecotone activation time.
+    pub ecotone_time: Option<u64>,
+    /// Fjord activation time.
+    pub fjord_time: Option<u64>,
+    /// Granite activation time.
+    pub granite_time: Option<u64>,
+    /// Holocene activation time.
+    pub holocene_time: Option<u64>,
+    /// Isthmus activation time.
+    pub isthmus_time: Option<u64>,
+}
+
+impl HardforkConfig {
+    /// Fills in the hardforks that aren't configured for the chain with the superchain defaults
+    /// that activate at or after the chain joined the superchain.
+    fn inherit(&mut self, superchain: &Self, superchain_time: Option<u64>) {
+        let Some(superchain_time) = superchain_time else { return };
+        let inherit = |chain: &mut Option<u64>, default: Option<u64>| {
+            if chain.is_none() {
+                *chain = default.filter(|time| *time >= superchain_time);
+            }
+        };
+        inherit(&mut self.canyon_time, superchain.canyon_time);
+        inherit(&mut self.delta_time, superchain.delta_time);
+        inherit(&mut self.ecotone_time, superchain.ecotone_time);
+        inherit(&mut self.fjord_time, superchain.fjord_time);
+        inherit(&mut self.granite_time, superchain.granite_time);
+        inherit(&mut self.holocene_time, superchain.holocene_time);
+        inherit(&mut self.isthmus_time, superchain.isthmus_time);
+    }
+}
+
+/// EIP-1559 parameters of the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct OptimismConfig {
+    /// EIP-1559 elasticity multiplier.
+    pub eip1559_elasticity: u64,
+    /// EIP-1559 base fee max change denominator.
+    pub eip1559_denominator: u64,
+    /// EIP-1559 base fee max change denominator after Canyon.
+    pub eip1559_denominator_canyon: Option<u64>,
+}
+
+/// A block reference in the genesis config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct BlockRef {
+    /// Block hash.
+    pub hash: B256,
+    /// Block number.
+    pub number: u64,
+}
+
+/// The system config of the chain at genesis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemConfig {
+    /// Address of the batch submitter.
+    pub batcher_address: Address,
+    /// L1 fee overhead, unused since Ecotone.
+    pub overhead: B256,
+    /// L1 fee scalar.
+    pub scalar: B256,
+    /// L2 block gas limit.
+    pub gas_limit: u64,
+    /// Base fee scalar, set for chains launched after Ecotone.
+    pub base_fee_scalar: Option<u64>,
+    /// Blob base fee scalar, set for chains launched after Ecotone.
+    pub blob_base_fee_scalar: Option<u64>,
+}
+
+/// Rollup genesis of the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct RollupGenesis {
+    /// Timestamp of the L2 genesis block.
+    pub l2_time: u64,
+    /// L1 block the chain derives from at genesis.
+    pub l1: BlockRef,
+    /// L2 genesis block.
+    pub l2: BlockRef,
+    /// System config at genesis.
+    pub system_config: SystemConfig,
+}
+
+/// Per-chain config, `superchain/configs/<superchain>/<chain>.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SuperchainChainConfig {
+    /// Display name of the chain.
+    pub name: String,
+    /// Chain id.
+    pub chain_id: u64,
+    /// Timestamp at which the chain joined the superchain and started following its upgrades.
+    pub superchain_time: Option<u64>,
+    /// L2 block time in seconds.
+    pub block_time: u64,
+    /// Hardfork activation times, including the ones inherited from the superchain.
+    #[serde(default)]
+    pub hardforks: HardforkConfig,
+    /// EIP-1559 parameters.
+    pub optimism: Option<OptimismConfig>,
+    /// Rollup genesis.
+    pub genesis: RollupGenesis,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SuperchainConfig {
+    #[serde(default)]
+    hardforks: HardforkConfig,
+}
+
+/// Genesis as stored in the registry, with contract code referenced by hash.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RegistryGenesis {
+    nonce: u64,
+    timestamp: u64,
+    extra_data: Bytes,
+    gas_limit: u64,
+    difficulty: U256,
+    mix_hash: B256,
+    coinbase: Address,
+    number: u64,
+    base_fee_per_gas: Option<U256>,
+    excess_blob_gas: Option<u64>,
+    blob_gas_used: Option<u64>,
+    #[serde(default)]
+    alloc: BTreeMap<Address, RegistryGenesisAccount>,
+    state_hash: Option<B256>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RegistryGenesisAccount {
+    code_hash: Option<B256>,
+    #[serde(default)]
+    storage: BTreeMap<B256, B256>,
+    #[serde(default)]
+    balance: U256,
+    #[serde(default)]
+    nonce: u64,
+}
+
+/// A local checkout of the superchain registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuperchainRegistry {
+    root: PathBuf,
+}
+
+impl SuperchainRegistry {
+    /// Creates a registry from the root directory of the checkout.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Creates the registry of the checkout that contains the given chain config,
+    /// `<root>/superchain/configs/<superchain>/<chain>.toml`, and returns it with the chain's
+    /// entry.
+    pub fn from_config_path(
+        path: &Path,
+    ) -> Result<(Self, ChainListEntry), SuperchainRegistryError> {
+        let invalid = || SuperchainRegistryError::InvalidConfigPath(path.to_path_buf());
+        let chain = path
+            .extension()
+            .filter(|ext| *ext == "toml")
+            .and_then(|_| path.file_stem())
+            .and_then(|chain| chain.to_str())
+            .ok_or_else(invalid)?;
+        let superchain_dir = path.parent().ok_or_else(invalid)?;
+        let superchain =
+            superchain_dir.file_name().and_then(|name| name.to_str()).ok_or_else(invalid)?;
+        let configs = superchain_dir.parent().filter(|dir| dir.ends_with("superchain/configs"));
+        let root = configs
+            .and_then(|configs| configs.parent())
+            .and_then(|dir| dir.parent())
+            .ok_or_else(invalid)?;
+
+        let registry = Self::new(root);
+        let entry = registry.find_chain(&format!("{superchain}/{chain}"))?;
+        Ok((registry, entry))
+    }
+
+    /// Returns the root directory of the checkout.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns all chains listed in `chainList.json`.
+    pub fn chains(&self) -> Result<Vec<ChainListEntry>, SuperchainRegistryError> {
+        let path = self.root.join("chainList.json");
+        let list = read(&path)?;
+        serde_json::from_slice(&list).map_err(|err| SuperchainRegistryError::Json { path, err })
+    }
+
+    /// Looks up a chain by its identifier, e.g. `mainnet/base`, or by its name if that is unique
+    /// across all superchains, e.g. `base`.
+    pub fn find_chain(&self, name: &str) -> Result<ChainListEntry, SuperchainRegistryError> {
+        let mut candidates = self
+            .chains()?
+            .into_iter()
+            .filter(|entry| {
+                entry.identifier == name ||
+                    entry.split_identifier().is_some_and(|(_, chain)| chain == name)
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(pos) = candidates.iter().position(|entry| entry.identifier == name) {
+            return Ok(candidates.swap_remove(pos))
+        }
+
+        match candidates.len() {
+            0 => Err(SuperchainRegistryError::UnknownChain(name.to_string())),
+            1 => Ok(candidates.remove(0)),
+            _ => Err(SuperchainRegistryError::AmbiguousChain {
+                name: name.to_string(),
+                candidates: candidates.into_iter().map(|entry| entry.identifier).collect(),
+            }),
+        }
+    }
+
+    /// Loads the config of the chain, with the hardforks inherited from its superchain.
+    pub fn chain_config(
+        &self,
+        entry: &ChainListEntry,
+    ) -> Result<SuperchainChainConfig, SuperchainRegistryError> {
+        let (superchain, chain) = self.split(entry)?;
+        let configs = self.root.join("superchain").join("configs").join(superchain);
+
+        let mut config: SuperchainChainConfig = read_toml(&configs.join(format!("{chain}.toml")))?;
+        let superchain_path = configs.join("superchain.toml");
+        if superchain_path.exists() {
+            let superchain: SuperchainConfig = read_toml(&superchain_path)?;
+            config.hardforks.inherit(&superchain.hardforks, config.superchain_time);
+        }
+
+        Ok(config)
+    }
+
+    /// Loads the genesis of the chain, with the chain config built from its registry config.
+    pub fn genesis(&self, entry: &ChainListEntry) -> Result<Genesis, SuperchainRegistryError> {
+        let config = self.chain_config(entry)?;
+        self.load_genesis(entry, &config)
+    }
+
+    /// Builds the [`OpChainSpec`] of the chain with the given identifier or name.
+    pub fn chain_spec(&self, name: &str) -> Result<OpChainSpec, SuperchainRegistryError> {
+        let entry = self.find_chain(name)?;
+        self.chain_spec_of(&entry)
+    }
+
+    /// Builds the [`OpChainSpec`] of the given chain.
+    ///
+    /// The hardforks and base fee params are taken from the registry config. The genesis block
+    /// must match the genesis system config and the configured L2 genesis hash.
+    pub fn chain_spec_of(
+        &self,
+        entry: &ChainListEntry,
+    ) -> Result<OpChainSpec, SuperchainRegistryError> {
+        let config = self.chain_config(entry)?;
+        let genesis = self.load_genesis(entry, &config)?;
+
+        let system_config = &config.genesis.system_config;
+        if genesis.gas_limit != system_config.gas_limit {
+            return Err(SuperchainRegistryError::GasLimitMismatch {
+                chain: entry.identifier.clone(),
+                mismatch: GotExpected { got: genesis.gas_limit, expected: system_config.gas_limit },
+            })
+        }
+
+        let spec = OpChainSpec {
+            inner: ChainSpec {
+                chain: Chain::from_id(config.chain_id),
+                genesis,
+                // We assume no OP network merges, and set the paris block and total difficulty to
+                // zero
+                paris_block_and_final_difficulty: Some((0, U256::ZERO)),
+                hardforks: hardforks(&config),
+                base_fee_params: base_fee_params(&config),
+                ..Default::default()
+            },
+        };
+
+        let genesis_hash = spec.genesis_hash();
+        if genesis_hash != config.genesis.l2.hash {
+            return Err(SuperchainRegistryError::GenesisHashMismatch {
+                chain: entry.identifier.clone(),
+                mismatch: GotExpected { got: genesis_hash, expected: config.genesis.l2.hash },
+            })
+        }
+
+        Ok(spec)
+    }
+
+    fn load_genesis(
+        &self,
+        entry: &ChainListEntry,
+        config: &SuperchainChainConfig,
+    ) -> Result<Genesis, SuperchainRegistryError> {
+        let (superchain, chain) = self.split(entry)?;
+
+        let extra = self.root.join("superchain").join("extra");
+        let dictionary_path = extra.join("dictionary");
+        let dictionary =
+            if dictionary_path.exists() { read(&dictionary_path)? } else { Vec::new() };
+
+        let path = extra.join("genesis").join(superchain).join(format!("{chain}.json.zst"));
+        let json = read_compressed(&path, &dictionary)?;
+        let genesis: RegistryGenesis = serde_json::from_slice(&json)
+            .map_err(|err| SuperchainRegistryError::Json { path, err })?;
+        if genesis.state_hash.is_some() {
+            return Err(SuperchainRegistryError::MissingGenesisState(entry.identifier.clone()))
+        }
+
+        let mut alloc = BTreeMap::new();
+        for (address, account) in genesis.alloc {
+            let code = match account.code_hash {
+                Some(code_hash) if code_hash != KECCAK_EMPTY => {
+                    let path = extra.join("bytecodes").join(format!("{code_hash}.bin.zst"));
+                    Some(read_compressed(&path, &dictionary)?.into())
+                }
+                _ => None,
+            };
+            alloc.insert(
+                address,
+                GenesisAccount {
+                    nonce: Some(account.nonce),
+                    balance: account.balance,
+                    code,
+                    storage: (!account.storage.is_empty()).then_some(account.storage),
+                    private_key: None,
+                },
+            );
+        }
+
+        Ok(Genesis {
+            config: chain_config(config),
+            nonce: genesis.nonce,
+            timestamp: genesis.timestamp,
+            extra_data: genesis.extra_data,
+            gas_limit: genesis.gas_limit,
+            difficulty: genesis.difficulty,
+            mix_hash: genesis.mix_hash,
+            coinbase: genesis.coinbase,
+            alloc,
+            base_fee_per_gas: genesis.base_fee_per_gas.map(|fee| fee.to()),
+            excess_blob_gas: genesis.excess_blob_gas,
+            blob_gas_used: genesis.blob_gas_used,
+            number: Some(genesis.number),
+        })
+    }
+
+    fn split<'a>(
+        &self,
+        entry: &'a ChainListEntry,
+    ) -> Result<(&'a str, &'a str), SuperchainRegistryError> {
+        entry
+            .split_identifier()
+            .ok_or_else(|| SuperchainRegistryError::UnknownChain(entry.identifier.clone()))
+    }
+}
+
+/// Converts the registry config into the chain config of the genesis.
+fn chain_config(config: &SuperchainChainConfig) -> ChainConfig {
+    let bedrock_block = config.genesis.l2.number;
+    let hardforks = &config.hardforks;
+
+    ChainConfig {
+        chain_id: config.chain_id,
+        homestead_block: Some(0),
+        eip150_block: Some(0),
+        eip155_block: Some(0),
+        eip158_block: Some(0),
+        byzantium_block: Some(0),
+        constantinople_block: Some(0),
+        petersburg_block: Some(0),
+        istanbul_block: Some(0),
+        muir_glacier_block: Some(0),
+        berlin_block: Some(0),
+        london_block: Some(bedrock_block),
+        arrow_glacier_block: Some(bedrock_block),
+        gray_glacier_block: Some(bedrock_block),
+        merge_netsplit_block: Some(bedrock_block),
+        // Canyon activates Shanghai, Ecotone activates Cancun and Isthmus activates Prague
+        shanghai_time: hardforks.canyon_time,
+        cancun_time: hardforks.ecotone_time,
+        prague_time: hardforks.isthmus_time,
+        terminal_total_difficulty: Some(U256::ZERO),
+        terminal_total_difficulty_passed: true,
+        ..Default::default()
+    }
+}
+
+/// Returns the hardforks of the chain.
+///
+/// The chain starts at Bedrock. Chains launched after Regolith don't configure it and activate it
+/// at genesis.
+fn hardforks(config: &SuperchainChainConfig) -> ChainHardforks {
+    let bedrock_block = config.genesis.l2.number;
+    let hardforks = &config.hardforks;
+
+    let mut forks = [
+        EthereumHardfork::Homestead,
+        EthereumHardfork::Tangerine,
+        EthereumHardfork::SpuriousDragon,
+        EthereumHardfork::Byzantium,
+        EthereumHardfork::Constantinople,
+        EthereumHardfork::Petersburg,
+        EthereumHardfork::Istanbul,
+        EthereumHardfork::MuirGlacier,
+        EthereumHardfork::Berlin,
+    ]
+    .into_iter()
+    .map(|fork| (Box::new(fork) as Box<dyn Hardfork>, ForkCondition::Block(0)))
+    .collect::<Vec<_>>();
+    forks.extend(
+        [
+            EthereumHardfork::London.boxed(),
+            EthereumHardfork::ArrowGlacier.boxed(),
+            EthereumHardfork::GrayGlacier.boxed(),
+            OpHardfork::Bedrock.boxed(),
+        ]
+        .into_iter()
+        .map(|fork| (fork, ForkCondition::Block(bedrock_block))),
+    );
+    forks.push((
+        EthereumHardfork::Paris.boxed(),
+        ForkCondition::TTD {
+            activation_block_number: 0,
+            total_difficulty: U256::ZERO,
+            fork_block: Some(bedrock_block),
+        },
+    ));
+
+    let regolith_time = hardforks.regolith_time.unwrap_or(config.genesis.l2_time);
+    forks.extend(
+        [
+            (OpHardfork::Regolith.boxed(), Some(regolith_time)),
+            // Canyon activates Shanghai, Ecotone activates Cancun and Isthmus activates Prague
+            (EthereumHardfork::Shanghai.boxed(), hardforks.canyon_time),
+            (OpHardfork::Canyon.boxed(), hardforks.canyon_time),
+            (EthereumHardfork::Cancun.boxed(), hardforks.ecotone_time),
+            (OpHardfork::Ecotone.boxed(), hardforks.ecotone_time),
+            (OpHardfork::Fjord.boxed(), hardforks.fjord_time),
+            (OpHardfork::Granite.boxed(), hardforks.granite_time),
+            (OpHardfork::Holocene.boxed(), hardforks.holocene_time),
+            (EthereumHardfork::Prague.boxed(), hardforks.isthmus_time),
+            (OpHardfork::Isthmus.boxed(), hardforks.isthmus_time),
+        ]
+        .into_iter()
+        .filter_map(|(fork, time)| time.map(|time| (fork, ForkCondition::Timestamp(time)))),
+    );
+
+    ordered_hardforks(forks)
+}
+
+/// Returns the base fee params of the chain, the EIP-1559 denominator changes with Canyon.
+fn base_fee_params(config: &SuperchainChainConfig) -> BaseFeeParamsKind {
+    let Some(optimism) = config.optimism else { return BaseFeeParamsKind::default() };
+    let params = |denominator: u64| {
+        BaseFeeParams::new(denominator as u128, optimism.eip1559_elasticity as u128)
+    };
+    match optimism.eip1559_denominator_canyon {
+        Some(denominator_canyon) => BaseFeeParamsKind::Variable(
+            vec![
+                (EthereumHardfork::London.boxed(), params(optimism.eip1559_denominator)),
+                (OpHardfork::Canyon.boxed(), params(denominator_canyon)),
+            ]
+            .into(),
+        ),
+        None => params(optimism.eip1559_denominator).into(),
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, SuperchainRegistryError> {
+    fs::read(path).map_err(|err| SuperchainRegistryError::Io { path: path.to_path_buf(), err })
+}
+
+fn read_toml<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, SuperchainRegistryError> {
+    let contents = read(path)?;
+    let contents = String::from_utf8_lossy(&contents);
+    toml::from_str(&contents)
+        .map_err(|err| SuperchainRegistryError::Toml { path: path.to_path_buf(), err })
+}
+
+fn read_compressed(path: &Path, dictionary: &[u8]) -> Result<Vec<u8>, SuperchainRegistryError> {
+    let compressed = read(path)?;
+    let io_err = |err| SuperchainRegistryError::Io { path: path.to_path_buf(), err };
+    let mut decoder =
+        zstd::stream::read::Decoder::with_dictionary(compressed.as_slice(), dictionary)
+            .map_err(io_err)?;
+    let mut decompressed = Vec::new();
+    decoder.read_to_end(&mut decompressed).map_err(io_err)?;
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, keccak256};
+    use reth_chainspec::{
+        BaseFeeParams, EthChainSpec, EthereumHardfork, EthereumHardforks, Hardforks,
+    };
+    use reth_optimism_forks::{OpHardfork, OpHardforks};
+
+    fn write(path: PathBuf, contents: &[u8]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn load_chain_spec_from_registry() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let code = test_code();
+        let code_hash = keccak256(&code);
+
+        write(
+            root.join("chainList.json"),
+            br#"[
+                {"name":"Test","identifier":"sepolia/test","chainId":4242,"rpc":[]},
+                {"name":"Test","identifier":"mainnet/test","chainId":4243,"rpc":[]}
+            ]"#,
+        );
+        write(
+            root.join("superchain/configs/sepolia/superchain.toml"),
+            b"name = \"Sepolia\"\n[hardforks]\ncanyon_time = 0\ngranite_time = 10\necotone_time = 100\nfjord_time = 200\n",
+        );
+        let config_path = root.join("superchain/configs/sepolia/test.toml");
+        let config_toml = r#"
+name = "Test"
+chain_id = 4242
+superchain_time = 50
+block_time = 2
+
+[hardforks]
+  canyon_time = 0
+  delta_time = 0
+  holocene_time = 300
+
+[optimism]
+  eip1559_elasticity = 6
+  eip1559_denominator = 50
+  eip1559_denominator_canyon = 250
+
+[genesis]
+  l2_time = 1700000000
+  [genesis.l1]
+    hash = "0x0000000000000000000000000000000000000000000000000000000000000001"
+    number = 10
+  [genesis.l2]
+    hash = "0x0000000000000000000000000000000000000000000000000000000000000002"
+    number = 0
+  [genesis.system_config]
+    batcherAddress = "0x0000000000000000000000000000000000000042"
+    overhead = "0x0000000000000000000000000000000000000000000000000000000000000000"
+    scalar = "0x010000000000000000000000000000000000000000000000000c5fc500000558"
+    gasLimit = 30000000
+"#;
+        write(config_path.clone(), config_toml.as_bytes());
+        let genesis = format!(
+            r#"{{"nonce":0,"timestamp":1700000000,"extraData":"0x","gasLimit":30000000,
+            "difficulty":"0x0","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000",
+            "coinbase":"0x4200000000000000000000000000000000000011","number":0,"gasUsed":0,
+            "parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000",
+            "baseFeePerGas":"0x3b9aca00","excessBlobGas":null,"blobGasUsed":null,
+            "alloc":{{"0x4200000000000000000000000000000000000016":{{"codeHash":"{code_hash}",
+            "storage":{{"0x0000000000000000000000000000000000000000000000000000000000000001":
+            "0x0000000000000000000000000000000000000000000000000000000000000002"}},"nonce":1}}}}}}"#
+        );
+        write(
+            root.join("superchain/extra/genesis/sepolia/test.json.zst"),
+            &zstd::encode_all(genesis.as_bytes(), 0).unwrap(),
+        );
+        write(
+            root.join(format!("superchain/extra/bytecodes/{code_hash}.bin.zst")),
+            &zstd::encode_all(code.as_ref(), 0).unwrap(),
+        );
+
+        let registry = SuperchainRegistry::new(root);
+        assert!(matches!(
+            registry.chain_spec("test"),
+            Err(SuperchainRegistryError::AmbiguousChain { .. })
+        ));
+        assert!(matches!(
+            registry.chain_spec("unknown"),
+            Err(SuperchainRegistryError::UnknownChain(_))
+        ));
+
+        let entry = registry.find_chain("sepolia/test").unwrap();
+        let config = registry.chain_config(&entry).unwrap();
+        assert_eq!(
+            config.genesis.system_config.batcher_address,
+            address!("0000000000000000000000000000000000000042")
+        );
+        // only superchain hardforks after the chain joined the superchain are inherited
+        assert_eq!(config.hardforks.granite_time, None);
+        assert_eq!(config.hardforks.ecotone_time, Some(100));
+        assert_eq!(config.hardforks.fjord_time, Some(200));
+
+        // the genesis hash must match the configured L2 genesis hash
+        let Err(SuperchainRegistryError::GenesisHashMismatch { mismatch, .. }) =
+            registry.chain_spec("sepolia/test")
+        else {
+            panic!("expected genesis hash mismatch")
+        };
+        let config_toml = config_toml.replace(
+            "0x0000000000000000000000000000000000000000000000000000000000000002",
+            &mismatch.got.to_string(),
+        );
+        write(config_path.clone(), config_toml.as_bytes());
+
+        let (registry, entry) = SuperchainRegistry::from_config_path(&config_path).unwrap();
+        assert_eq!(entry.identifier, "sepolia/test");
+        let spec = registry.chain_spec_of(&entry).unwrap();
+        assert_eq!(spec.genesis_hash(), mismatch.got);
+        assert_eq!(spec.chain.id(), 4242);
+        // launched after Regolith
+        assert!(spec.is_fork_active_at_timestamp(OpHardfork::Regolith, 1700000000));
+        assert!(spec.is_fork_active_at_timestamp(OpHardfork::Canyon, 0));
+        assert!(spec.is_shanghai_active_at_timestamp(0));
+        assert!(spec.is_fork_active_at_timestamp(OpHardfork::Ecotone, 100));
+        assert!(spec.is_cancun_active_at_timestamp(100));
+        assert!(!spec.is_fork_active_at_timestamp(OpHardfork::Holocene, 299));
+        assert!(spec.is_holocene_active_at_timestamp(300));
+        assert!(!spec.is_fork_active_at_timestamp(EthereumHardfork::Prague, u64::MAX));
+        assert_eq!(spec.base_fee_params_at_timestamp(0), BaseFeeParams::new(250, 6));
+
+        assert!(matches!(
+            SuperchainRegistry::from_config_path(&root.join("test.toml")),
+            Err(SuperchainRegistryError::InvalidConfigPath(_))
+        ));
+
+        let account = spec.genesis.alloc.get(&address!("4200000000000000000000000000000000000016"));
+        assert_eq!(account.and_then(|account| account.code.clone()), Some(code));
+    }
+
+    fn test_code() -> Bytes {
+        Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3])
+    }
+}
@@ -1,8 +1,9 @@
 use reth_cli::chainspec::{parse_genesis, ChainSpecParser};
 use reth_optimism_chainspec::{
-    OpChainSpec, BASE_MAINNET, BASE_SEPOLIA, OP_DEV, OP_MAINNET, OP_SEPOLIA,
+    superchain::SuperchainRegistry, OpChainSpec, BASE_MAINNET, BASE_SEPOLIA, OP_DEV, OP_MAINNET,
+    OP_SEPOLIA,
 };
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 /// Optimism chain specification parser.
 #[derive(Debug, Clone, Default)]
@@ -25,12 +26,18 @@ impl ChainSpecParser for OpChainSpecParser {
     fn parse(s: &str) -> eyre::Result<Arc<Self::ChainSpec>> {
         chain_value_parser(s)
     }
+
+    fn help_message() -> String {
+        format!("The chain this node is running.\nPossible values are either a built-in chain, the path to a chain specification file or the path to the config of a chain in a superchain registry checkout, `<registry>/superchain/configs/<superchain>/<chain>.toml`.\n\nBuilt-in chains:\n    {}", Self::SUPPORTED_CHAINS.join(", "))
+    }
 }
 
 /// Clap value parser for [`OpChainSpec`]s.
 ///
-/// The value parser matches either a known chain, the path
-/// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct.
+/// The value parser matches either a known chain, the path to the TOML config of a chain in a
+/// superchain registry checkout (e.g. `superchain-registry/superchain/configs/sepolia/zora.toml`),
+/// the path to a json file, or a json formatted string in-memory. The json needs to be a Genesis
+/// struct.
 pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<OpChainSpec>, eyre::Error> {
     Ok(match s {
         "dev" => OP_DEV.clone(),
@@ -38,6 +45,10 @@ pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<OpChainSpec>, eyre::Error
         "optimism_sepolia" | "optimism-sepolia" => OP_SEPOLIA.clone(),
         "base" => BASE_MAINNET.clone(),
         "base_sepolia" | "base-sepolia" => BASE_SEPOLIA.clone(),
+        _ if Path::new(s).extension().is_some_and(|ext| ext == "toml") => {
+            let (registry, entry) = SuperchainRegistry::from_config_path(Path::new(s))?;
+            Arc::new(registry.chain_spec_of(&entry)?)
+        }
         _ => Arc::new(parse_genesis(s)?.into()),
     })
 }
@@ -0,0 +1,79 @@
+//! Block verification w.r.t. consensus rules new in Isthmus hardfork.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{address, Address, B256};
+use reth_consensus::ConsensusError;
+use reth_primitives::GotExpected;
+use reth_primitives_traits::BlockBody;
+
+/// The L2 contract `L2ToL1MessagePasser`, stores commitments to withdrawal transactions.
+pub const ADDRESS_L2_TO_L1_MESSAGE_PASSER: Address =
+    address!("4200000000000000000000000000000000000016");
+
+/// Verifies that the block body contains an empty list of withdrawals.
+///
+/// Withdrawals from L2 are initiated with the `L2ToL1MessagePasser`, the withdrawals list of the
+/// block body remains empty.
+pub fn ensure_empty_withdrawals<B: BlockBody>(body: &B) -> Result<(), ConsensusError> {
+    let withdrawals = body.withdrawals().ok_or(ConsensusError::BodyWithdrawalsMissing)?;
+    if !withdrawals.is_empty() {
+        return Err(ConsensusError::L2WithdrawalsNotEmpty(withdrawals.len()))
+    }
+    Ok(())
+}
+
+/// Verifies that the header contains a withdrawals root.
+///
+/// Post-Isthmus the withdrawals root is the storage root of the [`L2ToL1MessagePasser`] after
+/// executing the block, which is verified post-execution with [`verify_withdrawals_root`].
+///
+/// [`L2ToL1MessagePasser`]: ADDRESS_L2_TO_L1_MESSAGE_PASSER
+pub fn ensure_withdrawals_root_is_some<H: BlockHeader>(header: &H) -> Result<(), ConsensusError> {
+    header.withdrawals_root().ok_or(ConsensusError::WithdrawalsRootMissing)?;
+    Ok(())
+}
+
+/// Verifies that the withdrawals root in the header matches the storage root of the
+/// [`L2ToL1MessagePasser`] after executing the block.
+///
+/// [`L2ToL1MessagePasser`]: ADDRESS_L2_TO_L1_MESSAGE_PASSER
+pub fn verify_withdrawals_root<H: BlockHeader>(
+    header: &H,
+    storage_root: B256,
+) -> Result<(), ConsensusError> {
+    let header_withdrawals_root =
+        header.withdrawals_root().ok_or(ConsensusError::WithdrawalsRootMissing)?;
+    if header_withdrawals_root != storage_root {
+        return Err(ConsensusError::L2WithdrawalsRootMismatch(
+            GotExpected { got: storage_root, expected: header_withdrawals_root }.into(),
+        ))
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn withdrawals_root() {
+        let storage_root =
+            b256!("2d28b3d3d7e5c9f5f5e8e3c3f3b5a6f4e9c1a7b2d3e4f5a6b7c8d9e0f1a2b3c4");
+        let header = Header { withdrawals_root: Some(storage_root), ..Default::default() };
+
+        assert!(ensure_withdrawals_root_is_some(&header).is_ok());
+        assert!(verify_withdrawals_root(&header, storage_root).is_ok());
+        assert!(matches!(
+            verify_withdrawals_root(&header, B256::ZERO),
+            Err(ConsensusError::L2WithdrawalsRootMismatch(_))
+        ));
+
+        let header = Header::default();
+        assert!(matches!(
+            ensure_withdrawals_root_is_some(&header),
+            Err(ConsensusError::WithdrawalsRootMissing)
+        ));
+    }
+}
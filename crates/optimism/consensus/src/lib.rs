@@ -14,8 +14,7 @@ extern crate alloc;
 
 use alloc::sync::Arc;
 use alloy_consensus::{BlockHeader as _, EMPTY_OMMER_ROOT_HASH};
-use alloy_eips::eip7840::BlobParams;
-use alloy_primitives::{B64, U256};
+use alloy_primitives::{B256, B64, U256};
 use reth_chainspec::EthereumHardforks;
 use reth_consensus::{
     Consensus, ConsensusError, FullConsensus, HeaderValidator, PostExecutionInput,
@@ -31,6 +30,8 @@ use reth_optimism_forks::OpHardforks;
 use reth_optimism_primitives::{OpBlock, OpPrimitives, OpReceipt};
 use reth_primitives::{GotExpected, RecoveredBlock, SealedHeader};
 
+pub mod isthmus;
+
 mod proof;
 pub use proof::calculate_receipt_root_no_memo_optimism;
 use reth_primitives_traits::{Block, BlockBody, BlockHeader, SealedBlock};
@@ -52,6 +53,27 @@ impl OpBeaconConsensus {
     pub const fn new(chain_spec: Arc<OpChainSpec>) -> Self {
         Self { chain_spec }
     }
+
+    /// Validates the withdrawals root of the block post-execution.
+    ///
+    /// Post-Isthmus the withdrawals root is the storage root of the
+    /// [`L2ToL1MessagePasser`](isthmus::ADDRESS_L2_TO_L1_MESSAGE_PASSER) after executing the
+    /// block. The storage root is only computed if Isthmus is active at the block's timestamp.
+    pub fn validate_withdrawals_root_post_execution<H, F, E>(
+        &self,
+        header: &H,
+        storage_root: F,
+    ) -> Result<(), E>
+    where
+        H: BlockHeader,
+        F: FnOnce() -> Result<B256, E>,
+        E: From<ConsensusError>,
+    {
+        if self.chain_spec.is_isthmus_active_at_timestamp(header.timestamp()) {
+            isthmus::verify_withdrawals_root(header, storage_root()?)?;
+        }
+        Ok(())
+    }
 }
 
 impl FullConsensus<OpPrimitives> for OpBeaconConsensus {
@@ -94,7 +116,12 @@ impl<B: Block> Consensus<B> for OpBeaconConsensus {
         }
 
         // EIP-4895: Beacon chain push withdrawals as operations
-        if self.chain_spec.is_shanghai_active_at_timestamp(block.timestamp()) {
+        if self.chain_spec.is_isthmus_active_at_timestamp(block.timestamp()) {
+            // Post-Isthmus the withdrawals root is the storage root of the L2 to L1 message
+            // passer, which is verified post-execution
+            isthmus::ensure_empty_withdrawals(block.body())?;
+            isthmus::ensure_withdrawals_root_is_some(block.header())?;
+        } else if self.chain_spec.is_shanghai_active_at_timestamp(block.timestamp()) {
             validate_shanghai_withdrawals(block)?;
         }
 
@@ -149,8 +176,8 @@ impl<H: BlockHeader> HeaderValidator<H> for OpBeaconConsensus {
         }
 
         // ensure that the blob gas fields for this block
-        if self.chain_spec.is_cancun_active_at_timestamp(header.timestamp()) {
-            validate_against_parent_4844(header.header(), parent.header(), BlobParams::cancun())?;
+        if let Some(blob_params) = self.chain_spec.blob_params_at_timestamp(header.timestamp()) {
+            validate_against_parent_4844(header.header(), parent.header(), blob_params)?;
         }
 
         Ok(())
@@ -173,6 +173,13 @@ where
             block.header().parent_beacon_block_root,
             &mut evm,
         )?;
+        // Isthmus activates the EIP-2935 blockhashes contract from L1's Prague hardfork
+        self.system_caller.apply_blockhashes_contract_call(
+            block.header().timestamp,
+            block.header().number,
+            block.header().parent_hash,
+            &mut evm,
+        )?;
 
         // Ensure that the create2deployer is force-deployed at the canyon transition. Optimism
         // blocks will always have at least a single transaction in them (the L1 info transaction),
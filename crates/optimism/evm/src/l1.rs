@@ -28,6 +28,9 @@ const CREATE_2_DEPLOYER_BYTECODE: [u8; 1584] = hex!("608060405260043610610043576
 /// The function selector of the "setL1BlockValuesEcotone" function in the `L1Block` contract.
 const L1_BLOCK_ECOTONE_SELECTOR: [u8; 4] = hex!("440a5e20");
 
+/// The function selector of the "setL1BlockValuesIsthmus" function in the `L1Block` contract.
+const L1_BLOCK_ISTHMUS_SELECTOR: [u8; 4] = hex!("098999be");
+
 /// Extracts the [`L1BlockInfo`] from the L2 block. The L1 info transaction is always the first
 /// transaction in the L2 block.
 ///
@@ -67,10 +70,12 @@ pub fn extract_l1_info_from_tx<T: Transaction>(
 /// # Panics
 /// If the input is shorter than 4 bytes.
 pub fn parse_l1_info(input: &[u8]) -> Result<L1BlockInfo, OpBlockExecutionError> {
-    // If the first 4 bytes of the calldata are the L1BlockInfoEcotone selector, then we parse the
-    // calldata as an Ecotone hardfork L1BlockInfo transaction. Otherwise, we parse it as a
-    // Bedrock hardfork L1BlockInfo transaction.
-    if input[0..4] == L1_BLOCK_ECOTONE_SELECTOR {
+    // If the first 4 bytes of the calldata are the L1BlockInfoEcotone or L1BlockInfoIsthmus
+    // selector, then we parse the calldata as an Ecotone or Isthmus hardfork L1BlockInfo
+    // transaction. Otherwise, we parse it as a Bedrock hardfork L1BlockInfo transaction.
+    if input[0..4] == L1_BLOCK_ISTHMUS_SELECTOR {
+        parse_l1_info_tx_isthmus(input[4..].as_ref())
+    } else if input[0..4] == L1_BLOCK_ECOTONE_SELECTOR {
         parse_l1_info_tx_ecotone(input[4..].as_ref())
     } else {
         parse_l1_info_tx_bedrock(input[4..].as_ref())
@@ -185,6 +190,58 @@ pub fn parse_l1_info_tx_ecotone(data: &[u8]) -> Result<L1BlockInfo, OpBlockExecu
     Ok(l1block)
 }
 
+/// Updates the L1 block values for an Isthmus upgraded chain.
+/// Params are packed and passed in as raw msg.data instead of ABI to reduce calldata size.
+/// Params are expected to be in the following order:
+///   1. _baseFeeScalar       L1 base fee scalar
+///   2. _blobBaseFeeScalar   L1 blob base fee scalar
+///   3. _sequenceNumber      Number of L2 blocks since epoch start.
+///   4. _timestamp           L1 timestamp.
+///   5. _number              L1 blocknumber.
+///   6. _basefee             L1 base fee.
+///   7. _blobBaseFee         L1 blob base fee.
+///   8. _hash                L1 blockhash.
+///   9. _batcherHash         Versioned hash to authenticate batcher by.
+///  10. _operatorFeeScalar   Operator fee scalar.
+///  11. _operatorFeeConstant Operator fee constant.
+///
+/// <https://github.com/ethereum-optimism/specs/blob/main/specs/protocol/isthmus/l1-attributes.md>
+pub fn parse_l1_info_tx_isthmus(data: &[u8]) -> Result<L1BlockInfo, OpBlockExecutionError> {
+    if data.len() != 172 {
+        return Err(OpBlockExecutionError::L1BlockInfoError {
+            message: "unexpected l1 block info tx calldata length found".to_string(),
+        })
+    }
+
+    // https://github.com/ethereum-optimism/op-geth/blob/optimism/core/types/rollup_cost.go
+    //
+    // data layout assumed for Isthmus, which extends the Ecotone layout:
+    // offset type varname
+    // 0     <selector>
+    // 4     uint32 _basefeeScalar (start offset in this scope)
+    // ...   Ecotone fields
+    // 164   uint32 _operatorFeeScalar
+    // 168   uint64 _operatorFeeConstant
+
+    let mut l1block = parse_l1_info_tx_ecotone(&data[..160])?;
+
+    let operator_fee_scalar = U256::try_from_be_slice(&data[160..164]).ok_or_else(|| {
+        OpBlockExecutionError::L1BlockInfoError {
+            message: "could not convert operator fee scalar".to_string(),
+        }
+    })?;
+    let operator_fee_constant = U256::try_from_be_slice(&data[164..172]).ok_or_else(|| {
+        OpBlockExecutionError::L1BlockInfoError {
+            message: "could not convert operator fee constant".to_string(),
+        }
+    })?;
+
+    l1block.operator_fee_scalar = Some(operator_fee_scalar);
+    l1block.operator_fee_constant = Some(operator_fee_constant);
+
+    Ok(l1block)
+}
+
 /// An extension trait for [`L1BlockInfo`] that allows us to calculate the L1 cost of a transaction
 /// based off of the [`ChainSpec`]'s activated hardfork.
 pub trait RethL1BlockInfo {
@@ -229,7 +286,9 @@ impl RethL1BlockInfo for L1BlockInfo {
             return Ok(U256::ZERO)
         }
 
-        let spec_id = if chain_spec.is_fork_active_at_timestamp(OpHardfork::Fjord, timestamp) {
+        let spec_id = if chain_spec.is_fork_active_at_timestamp(OpHardfork::Isthmus, timestamp) {
+            SpecId::ISTHMUS
+        } else if chain_spec.is_fork_active_at_timestamp(OpHardfork::Fjord, timestamp) {
             SpecId::FJORD
         } else if chain_spec.is_fork_active_at_timestamp(OpHardfork::Ecotone, timestamp) {
             SpecId::ECOTONE
@@ -252,7 +311,9 @@ impl RethL1BlockInfo for L1BlockInfo {
         timestamp: u64,
         input: &[u8],
     ) -> Result<U256, BlockExecutionError> {
-        let spec_id = if chain_spec.is_fork_active_at_timestamp(OpHardfork::Fjord, timestamp) {
+        let spec_id = if chain_spec.is_fork_active_at_timestamp(OpHardfork::Isthmus, timestamp) {
+            SpecId::ISTHMUS
+        } else if chain_spec.is_fork_active_at_timestamp(OpHardfork::Fjord, timestamp) {
             SpecId::FJORD
         } else if chain_spec.is_fork_active_at_timestamp(OpHardfork::Regolith, timestamp) {
             SpecId::REGOLITH
@@ -404,4 +465,27 @@ mod tests {
         assert_eq!(l1_block_info.l1_blob_base_fee, l1_blob_base_fee);
         assert_eq!(l1_block_info.l1_blob_base_fee_scalar, l1_blob_base_fee_scalar);
     }
+
+    #[test]
+    fn parse_l1_info_isthmus() {
+        // rig
+
+        // Fjord L1 block info from `parse_l1_info_fjord`, with the Isthmus selector and the
+        // operator fee params appended
+        const DATA: &[u8] = &hex!("098999be0000146b000f79c500000000000000040000000066d052e700000000013ad8a3000000000000000000000000000000000000000000000000000000003ef1278700000000000000000000000000000000000000000000000000000000000000012fdf87b89884a61e74b322bbcf60386f543bfae7827725efaaf0ab1de2294a590000000000000000000000006887246668a3b87f54deb3b94ba47a6f63f32985000004d2000000000000162e");
+
+        // test
+
+        let l1_block_info = parse_l1_info(DATA).unwrap();
+
+        assert_eq!(l1_block_info.l1_base_fee, U256::from(1055991687));
+        assert_eq!(l1_block_info.l1_base_fee_scalar, U256::from(5227));
+        assert_eq!(l1_block_info.l1_blob_base_fee, Some(U256::from(1)));
+        assert_eq!(l1_block_info.l1_blob_base_fee_scalar, Some(U256::from(1014213)));
+        assert_eq!(l1_block_info.operator_fee_scalar, Some(U256::from(1234)));
+        assert_eq!(l1_block_info.operator_fee_constant, Some(U256::from(5678)));
+
+        // the calldata must include the operator fee params
+        assert!(parse_l1_info(&DATA[..DATA.len() - 12]).is_err());
+    }
 }
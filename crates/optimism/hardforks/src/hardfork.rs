@@ -161,7 +161,8 @@ impl OpHardfork {
                 Self::Fjord => Some(1716998400),
                 Self::Granite => Some(1723478400),
                 Self::Holocene => Some(1732633200),
-                Self::Isthmus => todo!(),
+                // Isthmus isn't scheduled yet
+                Self::Isthmus => None,
             },
         )
     }
@@ -197,7 +198,8 @@ impl OpHardfork {
                 Self::Fjord => Some(1720627201),
                 Self::Granite => Some(1726070401),
                 Self::Holocene => Some(1736445601),
-                Self::Isthmus => todo!(),
+                // Isthmus isn't scheduled yet
+                Self::Isthmus => None,
             },
         )
     }
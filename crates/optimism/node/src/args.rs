@@ -10,6 +10,50 @@ pub struct RollupArgs {
     #[arg(long = "rollup.sequencer-http", value_name = "HTTP_URL")]
     pub sequencer_http: Option<String>,
 
+    /// Additional HTTP endpoints for the sequencer mempool, tried in order when the endpoints
+    /// before them fail
+    #[arg(
+        long = "rollup.sequencer-http-fallback",
+        value_name = "HTTP_URL",
+        value_delimiter = ',',
+        requires = "sequencer_http"
+    )]
+    pub sequencer_http_fallback: Vec<String>,
+
+    /// Number of times forwarding a transaction to the sequencer is retried after all endpoints
+    /// failed
+    #[arg(long = "rollup.sequencer-max-retries", value_name = "RETRIES", default_value_t = 2)]
+    pub sequencer_max_retries: usize,
+
+    /// Keep transactions that couldn't be forwarded to the sequencer and forward them again once
+    /// a sequencer endpoint is reachable
+    #[arg(long = "rollup.sequencer-rebroadcast")]
+    pub sequencer_rebroadcast: bool,
+
+    /// HTTP endpoint of the interop supervisor, enables validation of executing messages in the
+    /// transaction pool and payload builder
+    #[arg(long = "rollup.supervisor-http", value_name = "HTTP_URL")]
+    pub supervisor_http: Option<String>,
+
+    /// Publish the payloads built by the sequencer as flashblocks, incremental pre-confirmations
+    /// of the next block, and serve `pending` rpc queries from the latest flashblock
+    #[arg(long = "rollup.flashblocks")]
+    pub flashblocks: bool,
+
+    /// Interval in milliseconds in which flashblocks are published
+    #[arg(
+        long = "rollup.flashblocks-interval",
+        value_name = "MILLIS",
+        default_value_t = 200,
+        requires = "flashblocks"
+    )]
+    pub flashblocks_interval: u64,
+
+    /// Websocket endpoint of a sequencer publishing flashblocks, used to serve `pending` rpc
+    /// queries from the latest flashblock on nodes that don't build the payloads themselves
+    #[arg(long = "rollup.flashblocks-url", value_name = "WS_URL", conflicts_with = "flashblocks")]
+    pub flashblocks_url: Option<String>,
+
     /// Disable transaction pool gossip
     #[arg(long = "rollup.disable-tx-pool-gossip")]
     pub disable_txpool_gossip: bool,
@@ -40,6 +84,13 @@ impl Default for RollupArgs {
     fn default() -> Self {
         Self {
             sequencer_http: None,
+            sequencer_http_fallback: Vec::new(),
+            sequencer_max_retries: 2,
+            sequencer_rebroadcast: false,
+            supervisor_http: None,
+            flashblocks: false,
+            flashblocks_interval: 200,
+            flashblocks_url: None,
             disable_txpool_gossip: false,
             enable_genesis_walkback: false,
             compute_pending_block: false,
@@ -105,6 +156,76 @@ mod tests {
         assert_eq!(args, expected_args);
     }
 
+    #[test]
+    fn test_parse_optimism_sequencer_fallback_args() {
+        let expected_args = RollupArgs {
+            sequencer_http: Some("http://host:port".into()),
+            sequencer_http_fallback: vec!["http://a:port".into(), "http://b:port".into()],
+            sequencer_max_retries: 5,
+            sequencer_rebroadcast: true,
+            ..Default::default()
+        };
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.sequencer-http",
+            "http://host:port",
+            "--rollup.sequencer-http-fallback",
+            "http://a:port,http://b:port",
+            "--rollup.sequencer-max-retries",
+            "5",
+            "--rollup.sequencer-rebroadcast",
+        ])
+        .args;
+        assert_eq!(args, expected_args);
+    }
+
+    #[test]
+    fn test_parse_optimism_supervisor_http_args() {
+        let expected_args =
+            RollupArgs { supervisor_http: Some("http://host:port".into()), ..Default::default() };
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.supervisor-http",
+            "http://host:port",
+        ])
+        .args;
+        assert_eq!(args, expected_args);
+    }
+
+    #[test]
+    fn test_parse_optimism_flashblocks_args() {
+        let expected_args =
+            RollupArgs { flashblocks: true, flashblocks_interval: 250, ..Default::default() };
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.flashblocks",
+            "--rollup.flashblocks-interval",
+            "250",
+        ])
+        .args;
+        assert_eq!(args, expected_args);
+
+        let expected_args = RollupArgs {
+            flashblocks_url: Some("ws://localhost:8546".to_string()),
+            ..Default::default()
+        };
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.flashblocks-url",
+            "ws://localhost:8546",
+        ])
+        .args;
+        assert_eq!(args, expected_args);
+
+        let result = CommandParser::<RollupArgs>::try_parse_from([
+            "reth",
+            "--rollup.flashblocks",
+            "--rollup.flashblocks-url",
+            "ws://localhost:8546",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_parse_optimism_disable_txpool_args() {
         let expected_args = RollupArgs { disable_txpool_gossip: true, ..Default::default() };
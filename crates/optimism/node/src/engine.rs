@@ -1,11 +1,12 @@
+use alloy_eips::eip7685::Requests;
+use alloy_primitives::{keccak256, B256};
 use alloy_rpc_types_engine::{
     ExecutionPayload, ExecutionPayloadEnvelopeV2, ExecutionPayloadSidecar, ExecutionPayloadV1,
     PayloadError,
 };
-use op_alloy_rpc_types_engine::{
-    OpExecutionPayloadEnvelopeV3, OpExecutionPayloadEnvelopeV4, OpPayloadAttributes,
-};
+use op_alloy_rpc_types_engine::{OpExecutionPayloadEnvelopeV3, OpPayloadAttributes};
 use reth_chainspec::ChainSpec;
+use reth_errors::RethError;
 use reth_node_api::{
     payload::{
         validate_parent_beacon_block_root_presence, EngineApiMessageVersion,
@@ -16,12 +17,17 @@ use reth_node_api::{
     PayloadValidator,
 };
 use reth_optimism_chainspec::OpChainSpec;
+use reth_optimism_consensus::{isthmus::ADDRESS_L2_TO_L1_MESSAGE_PASSER, OpBeaconConsensus};
 use reth_optimism_forks::{OpHardfork, OpHardforks};
-use reth_optimism_payload_builder::{OpBuiltPayload, OpPayloadBuilderAttributes};
+use reth_optimism_payload_builder::{
+    payload::OpExecutionPayloadEnvelopeV4, OpBuiltPayload, OpPayloadBuilderAttributes,
+};
 use reth_optimism_primitives::OpBlock;
 use reth_payload_validator::ExecutionPayloadValidator;
-use reth_primitives::SealedBlock;
+use reth_primitives::{RecoveredBlock, SealedBlock};
+use reth_provider::StorageRootProvider;
 use reth_rpc_types_compat::engine::payload::block_to_payload;
+use reth_trie::HashedPostState;
 use std::sync::Arc;
 
 /// The types used in the optimism beacon consensus engine.
@@ -74,12 +80,16 @@ impl PayloadTypes for OpPayloadTypes {
 #[derive(Debug, Clone)]
 pub struct OpEngineValidator {
     inner: ExecutionPayloadValidator<OpChainSpec>,
+    consensus: OpBeaconConsensus,
 }
 
 impl OpEngineValidator {
     /// Instantiates a new validator.
-    pub const fn new(chain_spec: Arc<OpChainSpec>) -> Self {
-        Self { inner: ExecutionPayloadValidator::new(chain_spec) }
+    pub fn new(chain_spec: Arc<OpChainSpec>) -> Self {
+        Self {
+            consensus: OpBeaconConsensus::new(chain_spec.clone()),
+            inner: ExecutionPayloadValidator::new(chain_spec),
+        }
     }
 
     /// Returns the chain spec used by the validator.
@@ -96,8 +106,33 @@ impl PayloadValidator for OpEngineValidator {
         &self,
         payload: ExecutionPayload,
         sidecar: ExecutionPayloadSidecar,
+        withdrawals_root: Option<B256>,
     ) -> Result<SealedBlock<Self::Block>, PayloadError> {
-        self.inner.ensure_well_formed_payload(payload, sidecar)
+        // Post-Isthmus the withdrawals root can't be derived from the empty withdrawals list of
+        // the payload, it is passed along with the payload instead
+        let withdrawals_root = withdrawals_root
+            .filter(|_| self.chain_spec().is_isthmus_active_at_timestamp(payload.timestamp()));
+        self.inner.ensure_well_formed_payload_with_withdrawals_root(
+            payload,
+            sidecar,
+            withdrawals_root,
+        )
+    }
+
+    fn validate_block_post_execution_with_hashed_state(
+        &self,
+        parent_state: &dyn StorageRootProvider,
+        state_updates: &HashedPostState,
+        block: &RecoveredBlock<Self::Block>,
+    ) -> Result<(), RethError> {
+        self.consensus.validate_withdrawals_root_post_execution(block.header(), || {
+            let storage_updates = state_updates
+                .storages
+                .get(&keccak256(ADDRESS_L2_TO_L1_MESSAGE_PASSER))
+                .cloned()
+                .unwrap_or_default();
+            Ok(parent_state.storage_root(ADDRESS_L2_TO_L1_MESSAGE_PASSER, storage_updates)?)
+        })
     }
 }
 
@@ -105,6 +140,19 @@ impl<Types> EngineValidator<Types> for OpEngineValidator
 where
     Types: EngineTypes<PayloadAttributes = OpPayloadAttributes>,
 {
+    fn validate_execution_requests(
+        &self,
+        requests: &Requests,
+    ) -> Result<(), EngineObjectValidationError> {
+        // Isthmus activates the Prague engine API version, but L2 blocks never contain requests
+        if requests.iter().len() != 0 {
+            return Err(EngineObjectValidationError::InvalidParams(
+                "NonEmptyExecutionRequests".to_string().into(),
+            ))
+        }
+        Ok(())
+    }
+
     fn validate_version_specific_fields(
         &self,
         version: EngineApiMessageVersion,
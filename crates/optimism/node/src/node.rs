@@ -26,13 +26,20 @@ use reth_optimism_consensus::OpBeaconConsensus;
 use reth_optimism_evm::{OpEvmConfig, OpExecutionStrategyFactory};
 use reth_optimism_payload_builder::{
     builder::OpPayloadTransactions,
+    conditional::MaybeConditionalTransaction,
     config::{OpBuilderConfig, OpDAConfig},
+    interop::InteropTxValidator,
+    FlashblocksPublisher,
 };
 use reth_optimism_primitives::{OpPrimitives, OpReceipt, OpTransactionSigned};
 use reth_optimism_rpc::{
+    eth::ext::L2EthApiExtServer,
+    flashblocks::{FlashblocksApiServer, FlashblocksConsumer, OpFlashblocksApi},
     miner::{MinerApiExtServer, OpMinerExtApi},
+    output::{OpOutputRootApi, OutputRootApiServer},
+    trace::{DebugTraceApiExtServer, OpDebugTraceExtApi, OpTraceExtApi, TraceApiExtServer},
     witness::{DebugExecutionWitnessApiServer, OpDebugWitnessApi},
-    OpEthApi, SequencerClient,
+    OpEthApi, OpEthExtApi, SequencerClient, SequencerClientConfig, SupervisorClient,
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
 use reth_provider::{CanonStateSubscriptions, EthStorage};
@@ -43,7 +50,7 @@ use reth_transaction_pool::{
     TransactionValidationTaskExecutor,
 };
 use reth_trie_db::MerklePatriciaTrie;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 /// Storage implementation for Optimism.
 pub type OpStorage = EthStorage<OpTransactionSigned>;
@@ -61,12 +68,20 @@ pub struct OpNode {
     ///
     /// By default no throttling is applied.
     pub da_config: OpDAConfig,
+    /// Publisher of the flashblocks built by the payload builder, shared with the RPC to serve
+    /// `pending` queries.
+    ///
+    /// Flashblocks are disabled by default.
+    pub flashblocks: Option<FlashblocksPublisher>,
 }
 
 impl OpNode {
     /// Creates a new instance of the Optimism node type.
     pub fn new(args: RollupArgs) -> Self {
-        Self { args, da_config: OpDAConfig::default() }
+        let flashblocks = args
+            .flashblocks
+            .then(|| FlashblocksPublisher::new(Duration::from_millis(args.flashblocks_interval)));
+        Self { args, da_config: OpDAConfig::default(), flashblocks }
     }
 
     /// Configure the data availability configuration for the OP builder.
@@ -97,11 +112,17 @@ impl OpNode {
     {
         let RollupArgs { disable_txpool_gossip, compute_pending_block, discovery_v4, .. } =
             self.args;
+        let interop_validator = self.args.supervisor_http.clone().map(|endpoint| {
+            Arc::new(SupervisorClient::new(endpoint)) as Arc<dyn InteropTxValidator>
+        });
         ComponentsBuilder::default()
             .node_types::<Node>()
-            .pool(OpPoolBuilder::default())
+            .pool(OpPoolBuilder::default().with_interop_validator(interop_validator.clone()))
             .payload(
-                OpPayloadBuilder::new(compute_pending_block).with_da_config(self.da_config.clone()),
+                OpPayloadBuilder::new(compute_pending_block)
+                    .with_da_config(self.da_config.clone())
+                    .with_interop_validator(interop_validator.clone())
+                    .with_flashblocks(self.flashblocks.clone()),
             )
             .network(OpNetworkBuilder {
                 disable_txpool_gossip,
@@ -140,9 +161,24 @@ where
     }
 
     fn add_ons(&self) -> Self::AddOns {
+        let sequencer_client = self.args.sequencer_http.clone().map(|endpoint| {
+            let endpoints = std::iter::once(endpoint)
+                .chain(self.args.sequencer_http_fallback.iter().cloned())
+                .collect();
+            SequencerClient::with_endpoints(
+                endpoints,
+                SequencerClientConfig {
+                    max_retries: self.args.sequencer_max_retries,
+                    rebroadcast: self.args.sequencer_rebroadcast,
+                    ..Default::default()
+                },
+            )
+        });
         Self::AddOns::builder()
-            .with_sequencer(self.args.sequencer_http.clone())
+            .with_sequencer_client(sequencer_client)
             .with_da_config(self.da_config.clone())
+            .with_flashblocks(self.flashblocks.clone())
+            .with_flashblocks_url(self.args.flashblocks_url.clone())
             .build()
     }
 }
@@ -166,6 +202,14 @@ pub struct OpAddOns<N: FullNodeComponents> {
     pub rpc_add_ons: RpcAddOns<N, OpEthApi<N>, OpEngineValidatorBuilder>,
     /// Data availability configuration for the OP builder.
     pub da_config: OpDAConfig,
+    /// Sequencer client, configured to forward submitted transactions to sequencer of given OP
+    /// network.
+    pub sequencer_client: Option<SequencerClient>,
+    /// Publisher of the flashblocks built by the payload builder, if flashblocks are enabled.
+    pub flashblocks: Option<FlashblocksPublisher>,
+    /// Consumer of the flashblocks of a sequencer, if the node follows the flashblocks of a
+    /// sequencer.
+    pub flashblocks_consumer: Option<FlashblocksConsumer>,
 }
 
 impl<N: FullNodeComponents<Types: NodeTypes<Primitives = OpPrimitives>>> Default for OpAddOns<N> {
@@ -190,6 +234,7 @@ where
             Storage = OpStorage,
             Engine = OpEngineTypes,
         >,
+        Pool: TransactionPool<Transaction: MaybeConditionalTransaction>,
     >,
 {
     type Handle = RpcHandle<N, OpEthApi<N>>;
@@ -198,7 +243,8 @@ where
         self,
         ctx: reth_node_api::AddOnsContext<'_, N>,
     ) -> eyre::Result<Self::Handle> {
-        let Self { rpc_add_ons, da_config } = self;
+        let Self { rpc_add_ons, da_config, sequencer_client, flashblocks, flashblocks_consumer } =
+            self;
         // install additional OP specific rpc methods
         let debug_ext = OpDebugWitnessApi::new(
             ctx.node.provider().clone(),
@@ -206,12 +252,57 @@ where
             Box::new(ctx.node.task_executor().clone()),
         );
         let miner_ext = OpMinerExtApi::new(da_config);
+        let output_ext = OpOutputRootApi::new(
+            ctx.node.provider().clone(),
+            Box::new(ctx.node.task_executor().clone()),
+            ctx.config.rpc.rpc_eth_proof_window,
+        );
+        if let Some(sequencer_client) = sequencer_client.clone() {
+            ctx.node.task_executor().spawn(sequencer_client.run_health_checks());
+        }
+        if let Some(flashblocks_consumer) = flashblocks_consumer {
+            ctx.node.task_executor().spawn(Box::pin(
+                flashblocks_consumer
+                    .run(ctx.node.provider().clone(), ctx.node.block_executor().clone()),
+            ));
+        }
+        let flashblocks_ext = flashblocks.map(|flashblocks| {
+            OpFlashblocksApi::new(flashblocks, Box::new(ctx.node.task_executor().clone()))
+        });
+        let eth_ext = OpEthExtApi::new(
+            sequencer_client,
+            ctx.node.pool().clone(),
+            ctx.node.provider().clone(),
+        );
 
         rpc_add_ons
-            .launch_add_ons_with(ctx, move |modules, auth_modules| {
+            .launch_add_ons_with_registry(ctx, move |modules, auth_modules, registry| {
                 debug!(target: "reth::cli", "Installing debug payload witness rpc endpoint");
                 modules.merge_if_module_configured(RethRpcModule::Debug, debug_ext.into_rpc())?;
 
+                debug!(target: "reth::cli", "Installing deposit and fee aware trace rpc endpoints");
+                let trace_ext = OpTraceExtApi::new(registry.trace_api());
+                modules.replace_if_module_configured(RethRpcModule::Trace, trace_ext.into_rpc())?;
+                let debug_trace_ext = OpDebugTraceExtApi::new(registry.debug_api());
+                modules.replace_if_module_configured(
+                    RethRpcModule::Debug,
+                    debug_trace_ext.into_rpc(),
+                )?;
+
+                debug!(target: "reth::cli", "Installing eth_sendRawTransactionConditional rpc endpoint");
+                modules.merge_if_module_configured(RethRpcModule::Eth, eth_ext.into_rpc())?;
+
+                debug!(target: "reth::cli", "Installing optimism output root rpc endpoint");
+                modules.merge_if_module_configured(RethRpcModule::Optimism, output_ext.into_rpc())?;
+
+                if let Some(flashblocks_ext) = flashblocks_ext {
+                    debug!(target: "reth::cli", "Installing optimism flashblocks rpc subscription");
+                    modules.merge_if_module_configured(
+                        RethRpcModule::Optimism,
+                        flashblocks_ext.into_rpc(),
+                    )?;
+                }
+
                 // extend the miner namespace if configured in the regular http server
                 modules.merge_if_module_configured(
                     RethRpcModule::Miner,
@@ -239,6 +330,7 @@ where
             Storage = OpStorage,
             Engine = OpEngineTypes,
         >,
+        Pool: TransactionPool<Transaction: MaybeConditionalTransaction>,
     >,
 {
     type EthApi = OpEthApi<N>;
@@ -274,6 +366,10 @@ pub struct OpAddOnsBuilder {
     sequencer_client: Option<SequencerClient>,
     /// Data availability configuration for the OP builder.
     da_config: Option<OpDAConfig>,
+    /// Publisher of the flashblocks built by the payload builder.
+    flashblocks: Option<FlashblocksPublisher>,
+    /// Websocket URL of a sequencer publishing flashblocks.
+    flashblocks_url: Option<String>,
 }
 
 impl OpAddOnsBuilder {
@@ -283,11 +379,32 @@ impl OpAddOnsBuilder {
         self
     }
 
+    /// With a configured [`SequencerClient`], e.g. one that fails over between multiple
+    /// endpoints.
+    pub fn with_sequencer_client(mut self, sequencer_client: Option<SequencerClient>) -> Self {
+        self.sequencer_client = sequencer_client;
+        self
+    }
+
     /// Configure the data availability configuration for the OP builder.
     pub fn with_da_config(mut self, da_config: OpDAConfig) -> Self {
         self.da_config = Some(da_config);
         self
     }
+
+    /// With a [`FlashblocksPublisher`], served on the flashblocks subscription and used for
+    /// `pending` queries.
+    pub fn with_flashblocks(mut self, flashblocks: Option<FlashblocksPublisher>) -> Self {
+        self.flashblocks = flashblocks;
+        self
+    }
+
+    /// With the websocket URL of a sequencer publishing flashblocks, which are consumed to serve
+    /// `pending` queries.
+    pub fn with_flashblocks_url(mut self, flashblocks_url: Option<String>) -> Self {
+        self.flashblocks_url = flashblocks_url;
+        self
+    }
 }
 
 impl OpAddOnsBuilder {
@@ -296,14 +413,30 @@ impl OpAddOnsBuilder {
     where
         N: FullNodeComponents<Types: NodeTypes<Primitives = OpPrimitives>>,
     {
-        let Self { sequencer_client, da_config } = self;
+        let Self { sequencer_client, da_config, flashblocks, flashblocks_url } = self;
+        let flashblocks_consumer = flashblocks_url.map(FlashblocksConsumer::new);
+        let preconfirmed =
+            flashblocks.as_ref().map(FlashblocksPublisher::subscribe_preconfirmed).or_else(|| {
+                flashblocks_consumer.as_ref().map(FlashblocksConsumer::subscribe_preconfirmed)
+            });
 
         OpAddOns {
             rpc_add_ons: RpcAddOns::new(
-                move |ctx| OpEthApi::<N>::builder().with_sequencer(sequencer_client).build(ctx),
+                {
+                    let sequencer_client = sequencer_client.clone();
+                    move |ctx| {
+                        OpEthApi::<N>::builder()
+                            .with_sequencer(sequencer_client)
+                            .with_preconfirmed_blocks(preconfirmed)
+                            .build(ctx)
+                    }
+                },
                 Default::default(),
             ),
             da_config: da_config.unwrap_or_default(),
+            sequencer_client,
+            flashblocks,
+            flashblocks_consumer,
         }
     }
 }
@@ -340,6 +473,19 @@ where
 pub struct OpPoolBuilder {
     /// Enforced overrides that are applied to the pool config.
     pub pool_config_overrides: PoolBuilderConfigOverrides,
+    /// Validates the executing messages of interop transactions, if interop is enabled.
+    pub interop_validator: Option<Arc<dyn InteropTxValidator>>,
+}
+
+impl OpPoolBuilder {
+    /// Configures the validator for the executing messages of interop transactions.
+    pub fn with_interop_validator(
+        mut self,
+        interop_validator: Option<Arc<dyn InteropTxValidator>>,
+    ) -> Self {
+        self.interop_validator = interop_validator;
+        self
+    }
 }
 
 impl<Node> PoolBuilder<Node> for OpPoolBuilder
@@ -349,7 +495,7 @@ where
     type Pool = OpTransactionPool<Node::Provider, DiskFileBlobStore>;
 
     async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
-        let Self { pool_config_overrides } = self;
+        let Self { pool_config_overrides, interop_validator } = self;
         let data_dir = ctx.config().datadir();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
 
@@ -370,6 +516,7 @@ where
                 // In --dev mode we can't require gas fees because we're unable to decode
                 // the L1 block info
                 .require_l1_data_gas_fee(!ctx.config().dev.dev)
+                .with_interop_validator(interop_validator.clone())
         });
 
         let transaction_pool = reth_transaction_pool::Pool::new(
@@ -436,13 +583,23 @@ pub struct OpPayloadBuilder<Txs = ()> {
     /// This data availability configuration specifies constraints for the payload builder
     /// when assembling payloads
     pub da_config: OpDAConfig,
+    /// Validates the executing messages of interop transactions, if interop is enabled.
+    pub interop_validator: Option<Arc<dyn InteropTxValidator>>,
+    /// Publishes the built payloads as flashblocks, if flashblocks are enabled.
+    pub flashblocks: Option<FlashblocksPublisher>,
 }
 
 impl OpPayloadBuilder {
     /// Create a new instance with the given `compute_pending_block` flag and data availability
     /// config.
     pub fn new(compute_pending_block: bool) -> Self {
-        Self { compute_pending_block, best_transactions: (), da_config: OpDAConfig::default() }
+        Self {
+            compute_pending_block,
+            best_transactions: (),
+            da_config: OpDAConfig::default(),
+            interop_validator: None,
+            flashblocks: None,
+        }
     }
 
     /// Configure the data availability configuration for the OP payload builder.
@@ -450,6 +607,21 @@ impl OpPayloadBuilder {
         self.da_config = da_config;
         self
     }
+
+    /// Configures the validator for the executing messages of interop transactions.
+    pub fn with_interop_validator(
+        mut self,
+        interop_validator: Option<Arc<dyn InteropTxValidator>>,
+    ) -> Self {
+        self.interop_validator = interop_validator;
+        self
+    }
+
+    /// Configures the publisher of flashblocks.
+    pub fn with_flashblocks(mut self, flashblocks: Option<FlashblocksPublisher>) -> Self {
+        self.flashblocks = flashblocks;
+        self
+    }
 }
 
 impl<Txs> OpPayloadBuilder<Txs>
@@ -462,8 +634,14 @@ where
         self,
         best_transactions: T,
     ) -> OpPayloadBuilder<T> {
-        let Self { compute_pending_block, da_config, .. } = self;
-        OpPayloadBuilder { compute_pending_block, best_transactions, da_config }
+        let Self { compute_pending_block, da_config, interop_validator, flashblocks, .. } = self;
+        OpPayloadBuilder {
+            compute_pending_block,
+            best_transactions,
+            da_config,
+            interop_validator,
+            flashblocks,
+        }
     }
 
     /// A helper method to initialize [`PayloadBuilderService`] with the given EVM config.
@@ -481,21 +659,29 @@ where
                 Primitives = OpPrimitives,
             >,
         >,
-        Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
-            + Unpin
+        Pool: TransactionPool<
+                Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>
+                                 + MaybeConditionalTransaction,
+            > + Unpin
             + 'static,
         Evm: ConfigureEvmFor<PrimitivesTy<Node::Types>>,
     {
+        // with flashblocks enabled the payload is rebuilt once per flashblock
+        let interval = self.flashblocks.as_ref().map(FlashblocksPublisher::interval);
         let payload_builder = reth_optimism_payload_builder::OpPayloadBuilder::with_builder_config(
             evm_config,
-            OpBuilderConfig { da_config: self.da_config },
+            OpBuilderConfig {
+                da_config: self.da_config,
+                interop_validator: self.interop_validator,
+                flashblocks: self.flashblocks,
+            },
         )
         .with_transactions(self.best_transactions)
         .set_compute_pending_block(self.compute_pending_block);
         let conf = ctx.payload_builder_config();
 
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
-            .interval(conf.interval())
+            .interval(interval.unwrap_or_else(|| conf.interval()))
             .deadline(conf.deadline())
             .max_payload_tasks(conf.max_payload_tasks());
 
@@ -524,8 +710,10 @@ where
             Primitives = OpPrimitives,
         >,
     >,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
-        + Unpin
+    Pool: TransactionPool<
+            Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>
+                             + MaybeConditionalTransaction,
+        > + Unpin
         + 'static,
     Txs: OpPayloadTransactions,
 {
@@ -4,11 +4,16 @@ use alloy_consensus::{
 };
 use alloy_eips::eip2718::Encodable2718;
 use alloy_primitives::{Address, TxHash, TxKind, U256};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
 use op_alloy_consensus::OpTypedTransaction;
 use parking_lot::RwLock;
 use reth_chainspec::ChainSpec;
 use reth_node_api::{Block, BlockBody};
 use reth_optimism_evm::RethL1BlockInfo;
+use reth_optimism_payload_builder::{
+    conditional::MaybeConditionalTransaction,
+    interop::{executing_message_entries, ExecutingDescriptor, InteropTxValidator, SafetyLevel},
+};
 use reth_optimism_primitives::{OpBlock, OpTransactionSigned};
 use reth_primitives::{
     transaction::TransactionConversionError, GotExpected, InvalidTransactionError, Recovered,
@@ -18,14 +23,18 @@ use reth_primitives_traits::SignedTransaction;
 use reth_provider::{BlockReaderIdExt, StateProviderFactory};
 use reth_revm::L1BlockInfo;
 use reth_transaction_pool::{
-    CoinbaseTipOrdering, EthBlobTransactionSidecar, EthPoolTransaction, EthPooledTransaction,
-    EthTransactionValidator, Pool, PoolTransaction, TransactionOrigin,
-    TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
+    error::InvalidPoolTransactionError, CoinbaseTipOrdering, EthBlobTransactionSidecar,
+    EthPoolTransaction, EthPooledTransaction, EthTransactionValidator, Pool, PoolTransaction,
+    TransactionOrigin, TransactionValidationOutcome, TransactionValidationTaskExecutor,
+    TransactionValidator,
 };
 use revm::primitives::{AccessList, KzgSettings};
-use std::sync::{
-    atomic::{AtomicU64, Ordering},
-    Arc, OnceLock,
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, OnceLock,
+    },
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 /// Type alias for default optimism transaction pool
@@ -46,6 +55,9 @@ pub struct OpPooledTransaction {
     inner: EthPooledTransaction<OpTransactionSigned>,
     /// The estimated size of this transaction, lazily computed.
     estimated_tx_compressed_size: OnceLock<u64>,
+    /// The conditional the transaction was submitted with via
+    /// `eth_sendRawTransactionConditional`, if any.
+    conditional: Option<Box<TransactionConditional>>,
 }
 
 impl OpPooledTransaction {
@@ -54,6 +66,7 @@ impl OpPooledTransaction {
         Self {
             inner: EthPooledTransaction::new(transaction, encoded_length),
             estimated_tx_compressed_size: Default::default(),
+            conditional: None,
         }
     }
 
@@ -67,6 +80,16 @@ impl OpPooledTransaction {
     }
 }
 
+impl MaybeConditionalTransaction for OpPooledTransaction {
+    fn set_conditional(&mut self, conditional: TransactionConditional) {
+        self.conditional = Some(Box::new(conditional));
+    }
+
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        self.conditional.as_deref()
+    }
+}
+
 impl From<Recovered<op_alloy_consensus::OpPooledTransaction>> for OpPooledTransaction {
     fn from(tx: Recovered<op_alloy_consensus::OpPooledTransaction>) -> Self {
         let encoded_len = tx.encode_2718_len();
@@ -74,6 +97,7 @@ impl From<Recovered<op_alloy_consensus::OpPooledTransaction>> for OpPooledTransa
         Self {
             inner: EthPooledTransaction::new(tx, encoded_len),
             estimated_tx_compressed_size: Default::default(),
+            conditional: None,
         }
     }
 }
@@ -238,6 +262,8 @@ pub struct OpTransactionValidator<Client, Tx> {
     /// derived from the tracked L1 block info that is extracted from the first transaction in the
     /// L2 block.
     require_l1_data_gas_fee: bool,
+    /// Validates the executing messages of interop transactions, if interop is enabled.
+    interop_validator: Option<Arc<dyn InteropTxValidator>>,
 }
 
 impl<Client, Tx> OpTransactionValidator<Client, Tx> {
@@ -267,6 +293,16 @@ impl<Client, Tx> OpTransactionValidator<Client, Tx> {
     pub const fn requires_l1_data_gas_fee(&self) -> bool {
         self.require_l1_data_gas_fee
     }
+
+    /// Configures the validator for the executing messages of interop transactions.
+    ///
+    /// Transactions with invalid executing messages are rejected.
+    pub fn with_interop_validator(
+        self,
+        interop_validator: Option<Arc<dyn InteropTxValidator>>,
+    ) -> Self {
+        Self { interop_validator, ..self }
+    }
 }
 
 impl<Client, Tx> OpTransactionValidator<Client, Tx>
@@ -297,7 +333,12 @@ where
         inner: EthTransactionValidator<Client, Tx>,
         block_info: OpL1BlockInfo,
     ) -> Self {
-        Self { inner, block_info: Arc::new(block_info), require_l1_data_gas_fee: true }
+        Self {
+            inner,
+            block_info: Arc::new(block_info),
+            require_l1_data_gas_fee: true,
+            interop_validator: None,
+        }
     }
 
     /// Update the L1 block info for the given header and system transaction, if any.
@@ -389,6 +430,66 @@ where
         outcome
     }
 
+    /// Checks the executing messages of the valid transactions against the interop validator, if
+    /// configured.
+    ///
+    /// The executing messages of all transactions are checked at once.
+    pub async fn validate_executing_messages(
+        &self,
+        outcomes: Vec<TransactionValidationOutcome<Tx>>,
+    ) -> Vec<TransactionValidationOutcome<Tx>> {
+        let Some(validator) = &self.interop_validator else { return outcomes };
+        let (indices, access_lists): (Vec<_>, Vec<_>) = outcomes
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, outcome)| {
+                let TransactionValidationOutcome::Valid { transaction, .. } = outcome else {
+                    return None
+                };
+                let entries = transaction
+                    .transaction()
+                    .access_list()
+                    .map(executing_message_entries)
+                    .unwrap_or_default();
+                (!entries.is_empty()).then_some((idx, entries))
+            })
+            .unzip();
+        if access_lists.is_empty() {
+            return outcomes
+        }
+
+        // the transactions are executed in a future block, checked against the current time
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or_else(|_| self.block_timestamp(), |now| now.as_secs());
+        let descriptor = ExecutingDescriptor { timestamp, timeout: None };
+        let results =
+            validator.check_access_lists(access_lists, SafetyLevel::Unsafe, descriptor).await;
+
+        let mut checks = outcomes.iter().map(|_| None).collect::<Vec<_>>();
+        for (idx, res) in indices.into_iter().zip(results) {
+            checks[idx] = Some(res);
+        }
+
+        outcomes
+            .into_iter()
+            .zip(checks)
+            .map(|(outcome, check)| match (outcome, check) {
+                (TransactionValidationOutcome::Valid { transaction, .. }, Some(Err(err))) => {
+                    if err.is_invalid() {
+                        TransactionValidationOutcome::Invalid(
+                            transaction.into_transaction(),
+                            InvalidPoolTransactionError::Other(Box::new(err)),
+                        )
+                    } else {
+                        TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err))
+                    }
+                }
+                (outcome, _) => outcome,
+            })
+            .collect()
+    }
+
     /// Validates all given transactions.
     ///
     /// Returns all outcomes for the given transactions in the same order.
@@ -414,14 +515,16 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> TransactionValidationOutcome<Self::Transaction> {
-        self.validate_one(origin, transaction)
+        let outcome = self.validate_one(origin, transaction);
+        let mut outcomes = self.validate_executing_messages(vec![outcome]).await;
+        outcomes.pop().expect("one outcome")
     }
 
     async fn validate_transactions(
         &self,
         transactions: Vec<(TransactionOrigin, Self::Transaction)>,
     ) -> Vec<TransactionValidationOutcome<Self::Transaction>> {
-        self.validate_all(transactions)
+        self.validate_executing_messages(self.validate_all(transactions)).await
     }
 
     fn on_new_head_block<B>(&self, new_tip_block: &SealedBlock<B>)
@@ -448,17 +551,24 @@ pub struct OpL1BlockInfo {
 #[cfg(test)]
 mod tests {
     use crate::txpool::{OpPooledTransaction, OpTransactionValidator};
-    use alloy_eips::eip2718::Encodable2718;
-    use alloy_primitives::{PrimitiveSignature as Signature, TxKind, U256};
+    use alloy_consensus::TxEip1559;
+    use alloy_eips::{
+        eip2718::Encodable2718,
+        eip2930::{AccessList, AccessListItem},
+    };
+    use alloy_primitives::{Address, PrimitiveSignature as Signature, TxKind, B256, U256};
     use op_alloy_consensus::{OpTypedTransaction, TxDeposit};
     use reth_chainspec::MAINNET;
+    use reth_optimism_chainspec::OP_MAINNET;
+    use reth_optimism_payload_builder::interop::{LocalInteropTxValidator, CROSS_L2_INBOX_ADDRESS};
     use reth_optimism_primitives::OpTransactionSigned;
     use reth_primitives::Recovered;
-    use reth_provider::test_utils::MockEthProvider;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use reth_transaction_pool::{
         blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder, TransactionOrigin,
         TransactionValidationOutcome,
     };
+    use std::sync::Arc;
     #[test]
     fn validate_optimism_transaction() {
         let client = MockEthProvider::default();
@@ -493,4 +603,53 @@ mod tests {
         };
         assert_eq!(err.to_string(), "transaction type not supported");
     }
+
+    #[test]
+    fn validate_executing_messages() {
+        let signer = Address::with_last_byte(1);
+        let client = MockEthProvider::default();
+        client.add_account(signer, ExtendedAccount::new(0, U256::MAX));
+        // the L1 data fee requires the optimism hardforks
+        let chain_spec = Arc::new(OP_MAINNET.inner.clone());
+        let validator = EthTransactionValidatorBuilder::new(chain_spec.clone())
+            .no_shanghai()
+            .no_cancun()
+            .build(client, InMemoryBlobStore::default());
+        let interop_validator = Arc::new(LocalInteropTxValidator::default());
+        let validator = OpTransactionValidator::new(validator)
+            .with_interop_validator(Some(interop_validator.clone()));
+
+        let (valid, invalid) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        interop_validator.insert(valid);
+        let pooled_tx = |nonce, entries| {
+            let tx = OpTypedTransaction::Eip1559(TxEip1559 {
+                chain_id: chain_spec.chain.id(),
+                nonce,
+                gas_limit: 100_000,
+                max_fee_per_gas: 1_000_000_000,
+                to: TxKind::Call(Address::ZERO),
+                access_list: AccessList(vec![AccessListItem {
+                    address: CROSS_L2_INBOX_ADDRESS,
+                    storage_keys: entries,
+                }]),
+                ..Default::default()
+            });
+            let signed_tx = OpTransactionSigned::new_unhashed(tx, Signature::test_signature());
+            let signed_recovered = Recovered::new_unchecked(signed_tx, signer);
+            let len = signed_recovered.encode_2718_len();
+            (TransactionOrigin::External, OpPooledTransaction::new(signed_recovered, len))
+        };
+
+        let outcomes = validator.validate_all(vec![
+            pooled_tx(0, vec![valid]),
+            pooled_tx(1, vec![valid, invalid]),
+            pooled_tx(2, vec![]),
+        ]);
+        let outcomes = futures::executor::block_on(validator.validate_executing_messages(outcomes));
+        assert!(outcomes[0].is_valid());
+        assert!(
+            matches!(&outcomes[1], TransactionValidationOutcome::Invalid(_, err) if err.to_string().contains("invalid executing message"))
+        );
+        assert!(outcomes[2].is_valid());
+    }
 }
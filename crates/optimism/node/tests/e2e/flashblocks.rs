@@ -0,0 +1,167 @@
+use crate::trace::deposit_payload_attributes;
+use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
+use alloy_genesis::Genesis;
+use alloy_network::ReceiptResponse;
+use alloy_primitives::{hex, Address, Bytes, TxKind, B256, U256};
+use alloy_rpc_types_eth::{TransactionInput, TransactionRequest};
+use reth_chainspec::EthChainSpec;
+use reth_e2e_test_utils::{
+    node::NodeTestContext, transaction::TransactionTestContext, wallet::Wallet, NodeHelperType,
+};
+use reth_node_builder::{EngineNodeLauncher, Node, NodeBuilder, NodeConfig, NodeHandle};
+use reth_node_core::args::{DiscoveryArgs, NetworkArgs, RpcServerArgs};
+use reth_optimism_chainspec::{OpChainSpec, OpChainSpecBuilder};
+use reth_optimism_node::{args::RollupArgs, OpNode};
+use reth_provider::providers::BlockchainProvider;
+use reth_rpc_api::EthApiServer;
+use reth_rpc_server_types::RpcModuleSelection;
+use reth_tasks::{TaskExecutor, TaskManager};
+use std::{sync::Arc, time::Duration};
+
+/// Init code of a contract whose runtime code returns `42` as a word.
+const INIT_CODE: [u8; 22] = hex!("600a600c600039600a6000f3602a60005260206000f3");
+
+/// Launches an [`OpNode`] with the rpc enabled on http and ws.
+async fn launch(
+    exec: TaskExecutor,
+    chain_spec: Arc<OpChainSpec>,
+    args: RollupArgs,
+) -> eyre::Result<NodeHelperType<OpNode>> {
+    let config = NodeConfig::new(chain_spec)
+        .with_network(NetworkArgs {
+            discovery: DiscoveryArgs { disable_discovery: true, ..DiscoveryArgs::default() },
+            ..NetworkArgs::default()
+        })
+        .with_unused_ports()
+        .with_rpc(RpcServerArgs {
+            ws_api: Some(RpcModuleSelection::All),
+            ..RpcServerArgs::default()
+                .with_unused_ports()
+                .with_http()
+                .with_http_api(RpcModuleSelection::All)
+                .with_ws()
+        });
+
+    let node = OpNode::new(args);
+    let NodeHandle { node, node_exit_future: _ } = NodeBuilder::new(config)
+        .testing_node(exec)
+        .with_types_and_provider::<OpNode, BlockchainProvider<_>>()
+        .with_components(node.components_builder())
+        .with_add_ons(node.add_ons())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+
+    let node = NodeTestContext::new(node, deposit_payload_attributes).await?;
+    let genesis = node.block_hash(0);
+    node.engine_api.update_forkchoice(genesis, genesis).await?;
+    Ok(node)
+}
+
+#[tokio::test]
+async fn pending_state_from_flashblocks() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let genesis: Genesis = serde_json::from_str(include_str!("../assets/genesis.json")).unwrap();
+    let chain_spec =
+        Arc::new(OpChainSpecBuilder::base_mainnet().genesis(genesis).ecotone_activated().build());
+    let tasks = TaskManager::current();
+
+    let mut sequencer = launch(
+        tasks.executor(),
+        chain_spec.clone(),
+        RollupArgs { flashblocks: true, flashblocks_interval: 10, ..Default::default() },
+    )
+    .await?;
+    let flashblocks_url = sequencer.inner.rpc_server_handle().ws_url();
+    let follower = launch(
+        tasks.executor(),
+        chain_spec.clone(),
+        RollupArgs { flashblocks_url, ..Default::default() },
+    )
+    .await?;
+
+    let wallet = Wallet::default().with_chain_id(chain_spec.chain().into());
+    let contract = wallet.inner.address().create(0);
+    let create = TransactionRequest {
+        nonce: Some(0),
+        to: Some(TxKind::Create),
+        gas: Some(100_000),
+        max_fee_per_gas: Some(20e9 as u128),
+        max_priority_fee_per_gas: Some(20e9 as u128),
+        chain_id: Some(wallet.chain_id),
+        input: TransactionInput::new(Bytes::from_static(&INIT_CODE)),
+        ..Default::default()
+    };
+    let raw_tx = TransactionTestContext::sign_tx(wallet.inner, create).await.encoded_2718();
+    let tx_hash = sequencer.rpc.inject_tx(raw_tx.into()).await?;
+
+    // the follower only sees the flashblocks published after it subscribed, so keep starting
+    // payloads which include the transaction until it pre-confirms it
+    let mut attempts = 0;
+    while EthApiServer::transaction_receipt(follower.rpc.inner.eth_api(), tx_hash).await?.is_none()
+    {
+        attempts += 1;
+        assert!(attempts < 50, "flashblocks not consumed by the follower");
+        sequencer.payload.new_payload().await?;
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    // a transaction with a higher tip which arrives after the first flashblock is appended to the
+    // pre-confirmed transactions
+    let signer = Wallet::new(2).with_chain_id(wallet.chain_id).gen().pop().unwrap();
+    let transfer = TransactionRequest {
+        nonce: Some(0),
+        to: Some(TxKind::Call(Address::with_last_byte(1))),
+        value: Some(U256::from(100)),
+        gas: Some(21_000),
+        max_fee_per_gas: Some(40e9 as u128),
+        max_priority_fee_per_gas: Some(40e9 as u128),
+        chain_id: Some(wallet.chain_id),
+        ..Default::default()
+    };
+    let raw_tx = TransactionTestContext::sign_tx(signer, transfer).await.encoded_2718();
+    let transfer_hash = sequencer.rpc.inject_tx(raw_tx.into()).await?;
+    let mut attempts = 0;
+    while EthApiServer::transaction_receipt(follower.rpc.inner.eth_api(), transfer_hash)
+        .await?
+        .is_none()
+    {
+        attempts += 1;
+        assert!(attempts < 50, "transaction not appended to the flashblocks");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    for node in [&sequencer, &follower] {
+        let eth_api = node.rpc.inner.eth_api();
+
+        let block = EthApiServer::block_by_number(eth_api, BlockNumberOrTag::Pending, false)
+            .await?
+            .unwrap();
+        assert_eq!(block.header.number, 1);
+        let transactions = block.transactions.hashes().skip(1).collect::<Vec<_>>();
+        assert_eq!(transactions, vec![tx_hash, transfer_hash]);
+
+        let receipt = EthApiServer::transaction_receipt(eth_api, tx_hash).await?.unwrap();
+        assert_eq!(receipt.inner.block_number, Some(1));
+        assert_eq!(receipt.inner.block_hash, Some(block.header.hash));
+        assert_eq!(receipt.inner.contract_address, Some(contract));
+        assert!(receipt.inner.status());
+
+        // the contract only exists in the pending block
+        let call = TransactionRequest::default().to(contract);
+        let output =
+            EthApiServer::call(eth_api, call.clone(), Some(BlockId::pending()), None, None).await?;
+        assert_eq!(output, Bytes::from(B256::with_last_byte(42)));
+        let output = EthApiServer::call(eth_api, call, Some(BlockId::latest()), None, None).await?;
+        assert!(output.is_empty());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use alloy_consensus::constants::EMPTY_ROOT_HASH;
+use alloy_genesis::{Genesis, GenesisAccount};
+use alloy_primitives::{Address, Bytes, B256, B64};
+use alloy_rpc_types_engine::{PayloadAttributes, PayloadStatusEnum};
+use reth_e2e_test_utils::{setup_engine, transaction::TransactionTestContext};
+use reth_node_api::{payload::ExecutionPayloadInputV4, PayloadBuilderAttributes};
+use reth_optimism_chainspec::OpChainSpecBuilder;
+use reth_optimism_consensus::isthmus::ADDRESS_L2_TO_L1_MESSAGE_PASSER;
+use reth_optimism_node::{OpEngineTypes, OpNode, OpPayloadBuilderAttributes};
+use reth_payload_builder::EthPayloadBuilderAttributes;
+use reth_rpc_api::EngineApiClient;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Post-Holocene payload attributes must contain the EIP-1559 parameters.
+fn isthmus_payload_attributes(timestamp: u64) -> OpPayloadBuilderAttributes {
+    let attributes = PayloadAttributes {
+        timestamp,
+        prev_randao: B256::ZERO,
+        suggested_fee_recipient: Address::ZERO,
+        withdrawals: Some(vec![]),
+        parent_beacon_block_root: Some(B256::ZERO),
+    };
+
+    OpPayloadBuilderAttributes {
+        payload_attributes: EthPayloadBuilderAttributes::new(B256::ZERO, attributes),
+        transactions: vec![],
+        no_tx_pool: false,
+        gas_limit: Some(30_000_000),
+        eip_1559_params: Some(B64::ZERO),
+    }
+}
+
+#[tokio::test]
+async fn withdrawals_root_round_trip() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let mut genesis: Genesis =
+        serde_json::from_str(include_str!("../assets/genesis.json")).unwrap();
+    // Holocene is active at genesis, the base fee of the next block is derived from its extra data
+    genesis.extra_data = Bytes::from([0u8; 9]);
+    // a withdrawal was initiated, so the withdrawals root differs from the root of an empty
+    // withdrawals list
+    genesis.alloc.insert(
+        ADDRESS_L2_TO_L1_MESSAGE_PASSER,
+        GenesisAccount::default()
+            .with_storage(Some(BTreeMap::from([(B256::ZERO, B256::with_last_byte(1))]))),
+    );
+    let chain_spec =
+        Arc::new(OpChainSpecBuilder::base_mainnet().genesis(genesis).isthmus_activated().build());
+    let (mut nodes, _tasks, wallet) =
+        setup_engine::<OpNode>(1, chain_spec, false, isthmus_payload_attributes).await?;
+    let mut node = nodes.pop().unwrap();
+
+    let raw_tx =
+        TransactionTestContext::optimism_l1_block_info_tx(wallet.chain_id, wallet.inner, 0).await;
+    node.rpc.inject_tx(raw_tx).await?;
+    let attributes = node.payload.new_payload().await?;
+    node.payload.expect_attr_event(attributes.clone()).await?;
+    node.payload.wait_for_built_payload(attributes.payload_id()).await;
+
+    // the withdrawals root of the block is returned along with the payload
+    let envelope = node.engine_api.get_payload_v4(attributes.payload_id()).await?;
+    let payload = node.payload.expect_built_payload().await?;
+    let block = payload.block();
+    let withdrawals_root = block.header().withdrawals_root;
+    assert!(withdrawals_root.is_some_and(|root| root != EMPTY_ROOT_HASH));
+    assert_eq!(envelope.execution_payload.withdrawals_root, withdrawals_root);
+    let parent_beacon_block_root = attributes.parent_beacon_block_root().unwrap();
+
+    // the withdrawals root can't be derived from the payload, so the block hash doesn't match
+    // without it
+    let status = EngineApiClient::<OpEngineTypes>::new_payload_v4(
+        &node.engine_api.engine_api_client,
+        ExecutionPayloadInputV4::from(envelope.execution_payload.execution_payload.clone()),
+        vec![],
+        parent_beacon_block_root,
+        Default::default(),
+    )
+    .await?;
+    assert!(status.is_invalid());
+
+    let status = EngineApiClient::<OpEngineTypes>::new_payload_v4(
+        &node.engine_api.engine_api_client,
+        envelope.execution_payload,
+        vec![],
+        parent_beacon_block_root,
+        Default::default(),
+    )
+    .await?;
+    assert_eq!(status.status, PayloadStatusEnum::Valid);
+
+    node.engine_api.update_forkchoice(block.hash(), block.hash()).await?;
+    node.wait_block(block.header().number, block.hash(), false).await?;
+
+    Ok(())
+}
@@ -1,6 +1,15 @@
 #![allow(missing_docs)]
 
+#[cfg(feature = "optimism")]
+mod flashblocks;
+
+#[cfg(feature = "optimism")]
+mod isthmus;
+
 #[cfg(feature = "optimism")]
 mod p2p;
 
+#[cfg(feature = "optimism")]
+mod trace;
+
 const fn main() {}
@@ -0,0 +1,145 @@
+use alloy_eips::{eip2718::Encodable2718, BlockId};
+use alloy_genesis::Genesis;
+use alloy_primitives::{address, hex, Address, Bytes, TxKind, B256, U256};
+use alloy_rpc_types_engine::PayloadAttributes;
+use alloy_rpc_types_trace::{
+    filter::TraceFilter,
+    geth::{GethDebugBuiltInTracerType, GethDebugTracingOptions, GethTrace},
+    parity::{Action, LocalizedTransactionTrace},
+};
+use op_alloy_consensus::{DepositTransaction, OpTypedTransaction, TxDeposit};
+use reth_e2e_test_utils::{setup_engine, transaction::TransactionTestContext};
+use reth_optimism_chainspec::OpChainSpecBuilder;
+use reth_optimism_node::{OpNode, OpPayloadBuilderAttributes};
+use reth_optimism_primitives::OpTransactionSigned;
+use reth_optimism_rpc::trace::{
+    DebugTraceApiExtServer, OpDebugTraceExtApi, OpTraceExtApi, TraceApiExtServer, BASE_FEE_VAULT,
+    DEPOSIT_MINT_CALLER,
+};
+use reth_payload_builder::EthPayloadBuilderAttributes;
+use reth_primitives_traits::{SignedTransaction, WithEncoded};
+use std::sync::Arc;
+
+/// The depositor of [`deposit`].
+const DEPOSITOR: Address = address!("00000000000000000000000000000000000d3b05");
+
+/// The value minted by [`deposit`].
+const MINT: u128 = 1_000_000_000_000_000_000;
+
+/// A user deposit, which mints [`MINT`] to [`DEPOSITOR`].
+///
+/// It's the first transaction of the block, so it carries the L1 block info.
+fn deposit() -> OpTransactionSigned {
+    let l1_block_info = Bytes::from_static(&hex!("015d8eb900000000000000000000000000000000000000000000000000000000008057650000000000000000000000000000000000000000000000000000000063d96d10000000000000000000000000000000000000000000000000000000000009f35273d89754a1e0387b89520d989d3be9c37c1f32495a88faf1ea05c61121ab0d1900000000000000000000000000000000000000000000000000000000000000010000000000000000000000002d679b567db6187c0c8323fa982cfb88b74dbcc7000000000000000000000000000000000000000000000000000000000000083400000000000000000000000000000000000000000000000000000000000f4240"));
+    let deposit = TxDeposit {
+        source_hash: B256::with_last_byte(1),
+        from: DEPOSITOR,
+        to: TxKind::Call(address!("4200000000000000000000000000000000000015")),
+        mint: Some(MINT),
+        value: U256::ZERO,
+        gas_limit: 1_000_000,
+        is_system_transaction: false,
+        input: l1_block_info,
+    };
+    OpTransactionSigned::new_unhashed(OpTypedTransaction::Deposit(deposit), TxDeposit::signature())
+}
+
+/// Payload attributes which force include [`deposit`].
+pub(crate) fn deposit_payload_attributes(timestamp: u64) -> OpPayloadBuilderAttributes {
+    let attributes = PayloadAttributes {
+        timestamp,
+        prev_randao: B256::ZERO,
+        suggested_fee_recipient: Address::ZERO,
+        withdrawals: Some(vec![]),
+        parent_beacon_block_root: Some(B256::ZERO),
+    };
+    let deposit = deposit();
+
+    OpPayloadBuilderAttributes {
+        payload_attributes: EthPayloadBuilderAttributes::new(B256::ZERO, attributes),
+        transactions: vec![WithEncoded::new(deposit.encoded_2718().into(), deposit)],
+        no_tx_pool: false,
+        gas_limit: Some(30_000_000),
+        eip_1559_params: None,
+    }
+}
+
+/// Returns the value minted by the trace, if it's a mint of a deposit.
+fn minted(trace: &LocalizedTransactionTrace) -> Option<(Address, U256)> {
+    match &trace.trace.action {
+        Action::Call(call) if call.from == DEPOSIT_MINT_CALLER => Some((call.to, call.value)),
+        _ => None,
+    }
+}
+
+#[tokio::test]
+async fn traces_include_deposit_mint() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let genesis: Genesis = serde_json::from_str(include_str!("../assets/genesis.json")).unwrap();
+    let chain_spec =
+        Arc::new(OpChainSpecBuilder::base_mainnet().genesis(genesis).ecotone_activated().build());
+    let (mut nodes, _tasks, wallet) =
+        setup_engine::<OpNode>(1, chain_spec, false, deposit_payload_attributes).await?;
+    let mut node = nodes.pop().unwrap();
+
+    let raw_tx = TransactionTestContext::transfer_tx_bytes(wallet.chain_id, wallet.inner).await;
+    let transfer_hash = node.rpc.inject_tx(raw_tx).await?;
+    let (payload, _) = node.advance_block().await?;
+    let block = payload.block();
+    node.wait_block(block.header().number, block.hash(), false).await?;
+
+    let transactions = &block.body().transactions;
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(transactions[0].mint(), Some(MINT));
+    let deposit_hash = *transactions[0].tx_hash();
+    let minted_by_deposit = Some((DEPOSITOR, U256::from(MINT)));
+
+    let trace_api = OpTraceExtApi::new(node.rpc.inner.trace_api());
+
+    // the mint precedes the traces of the deposit, the fee credits follow the transfer
+    let traces = trace_api.trace_block(BlockId::number(block.header().number)).await?.unwrap();
+    let deposit_traces = traces
+        .iter()
+        .filter(|trace| trace.transaction_hash == Some(deposit_hash))
+        .collect::<Vec<_>>();
+    assert_eq!(deposit_traces.len(), 2);
+    assert_eq!(minted(deposit_traces[0]), minted_by_deposit);
+    assert!(traces.iter().any(|trace| trace.transaction_hash == Some(transfer_hash) &&
+        matches!(&trace.trace.action, Action::Call(call) if call.to == BASE_FEE_VAULT)));
+
+    let traces = trace_api.trace_transaction(deposit_hash).await?.unwrap();
+    assert_eq!(traces.len(), 2);
+    assert_eq!(minted(&traces[0]), minted_by_deposit);
+
+    let trace = trace_api.trace_get(deposit_hash, vec![0usize.into()]).await?.unwrap();
+    assert_eq!(minted(&trace), minted_by_deposit);
+
+    let filter = TraceFilter {
+        from_block: Some(block.header().number),
+        to_block: Some(block.header().number),
+        from_address: vec![DEPOSIT_MINT_CALLER],
+        ..Default::default()
+    };
+    let traces = trace_api.trace_filter(filter).await?;
+    assert_eq!(traces.len(), 1);
+    assert_eq!(traces[0].transaction_hash, Some(deposit_hash));
+    assert_eq!(minted(&traces[0]), minted_by_deposit);
+
+    // the mint is the first call of the top level frame of the call tracer
+    let debug_api = OpDebugTraceExtApi::new(node.rpc.inner.debug_api());
+    let opts = GethDebugTracingOptions::default()
+        .with_tracer(GethDebugBuiltInTracerType::CallTracer.into());
+    let GethTrace::CallTracer(frame) =
+        debug_api.debug_trace_transaction(deposit_hash, Some(opts)).await?
+    else {
+        panic!("expected a call tracer trace")
+    };
+    assert_eq!(frame.from, DEPOSITOR);
+    let mint = &frame.calls[0];
+    assert_eq!(mint.from, DEPOSIT_MINT_CALLER);
+    assert_eq!(mint.to, Some(DEPOSITOR));
+    assert_eq!(mint.value, Some(U256::from(MINT)));
+
+    Ok(())
+}
@@ -0,0 +1,189 @@
+//! Tests for transactions submitted with a conditional via `eth_sendRawTransactionConditional`.
+
+use alloy_consensus::{Header, TxEip1559};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_network::TxSignerSync;
+use alloy_primitives::{map::HashMap, Address, TxKind, B256, U256};
+use alloy_rpc_types_eth::erc4337::{AccountStorage, TransactionConditional};
+use alloy_signer_local::PrivateKeySigner;
+use op_alloy_consensus::OpTypedTransaction;
+use reth_optimism_node::txpool::OpPooledTransaction;
+use reth_optimism_payload_builder::conditional::{
+    ConditionalBlock, ConditionalPayloadTransactions, MaybeConditionalTransaction,
+};
+use reth_optimism_primitives::OpTransactionSigned;
+use reth_optimism_rpc::{eth::ext::L2EthApiExtServer, OpEthExtApi, TxConditionalErr};
+use reth_payload_util::PayloadTransactions;
+use reth_primitives::{Block, Recovered};
+use reth_primitives_traits::SignedTransaction;
+use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+use reth_transaction_pool::{
+    blobstore::InMemoryBlobStore, noop::MockTransactionValidator, pool::BestPayloadTransactions,
+    CoinbaseTipOrdering, Pool, TransactionOrigin, TransactionPool,
+};
+
+type TestPool = Pool<
+    MockTransactionValidator<OpPooledTransaction>,
+    CoinbaseTipOrdering<OpPooledTransaction>,
+    InMemoryBlobStore,
+>;
+
+/// Account with a known storage slot.
+const CONTRACT: Address = Address::with_last_byte(0xcc);
+const SLOT: U256 = U256::from_limbs([1, 0, 0, 0]);
+const VALUE: B256 = B256::with_last_byte(42);
+
+fn pool() -> TestPool {
+    Pool::new(
+        MockTransactionValidator::default(),
+        CoinbaseTipOrdering::default(),
+        InMemoryBlobStore::default(),
+        Default::default(),
+    )
+}
+
+/// Returns a provider with the latest block at number 10, and the contract with the known slot.
+fn provider() -> MockEthProvider {
+    let provider = MockEthProvider::default();
+    let header = Header { number: 10, timestamp: 1000, ..Default::default() };
+    provider.add_block(B256::random(), Block { header, ..Default::default() });
+    provider.add_account(
+        CONTRACT,
+        ExtendedAccount::new(0, U256::ZERO).extend_storage([(SLOT.into(), VALUE.into())]),
+    );
+    provider
+}
+
+/// Returns a transaction signed by a new random signer.
+fn transaction() -> Recovered<OpTransactionSigned> {
+    let signer = PrivateKeySigner::random();
+    let mut tx = TxEip1559 {
+        chain_id: 1,
+        gas_limit: 21_000,
+        max_fee_per_gas: 1_000_000_000,
+        to: TxKind::Call(Address::random()),
+        ..Default::default()
+    };
+    let signature = signer.sign_transaction_sync(&mut tx).unwrap();
+    Recovered::new_unchecked(
+        OpTransactionSigned::new_unhashed(OpTypedTransaction::Eip1559(tx), signature),
+        signer.address(),
+    )
+}
+
+/// Returns a conditional that expects the known slot of the contract to have the given value.
+fn known_slot(value: B256) -> TransactionConditional {
+    TransactionConditional {
+        known_accounts: HashMap::from_iter([(
+            CONTRACT,
+            AccountStorage::Slots(HashMap::from_iter([(SLOT, value)])),
+        )]),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn send_raw_transaction_conditional() {
+    let pool = pool();
+    let api = OpEthExtApi::new(None, pool.clone(), provider());
+
+    let tx = transaction();
+    let hash = api
+        .send_raw_transaction_conditional(tx.encoded_2718().into(), known_slot(VALUE))
+        .await
+        .unwrap();
+    assert_eq!(hash, *tx.tx_hash());
+    let pooled = pool.get(&hash).unwrap();
+    assert_eq!(pooled.origin, TransactionOrigin::Private);
+    assert!(pooled
+        .transaction
+        .conditional()
+        .is_some_and(|conditional| conditional.known_accounts.contains_key(&CONTRACT)));
+
+    // known slot doesn't match the latest state
+    let err = api
+        .send_raw_transaction_conditional(
+            transaction().encoded_2718().into(),
+            known_slot(B256::ZERO),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), TxConditionalErr::INVALID_CONDITION_CODE);
+    assert_eq!(err.message(), "invalid conditional: storage slot mismatch");
+
+    // latest block is beyond the maximum block number
+    let conditional = TransactionConditional { block_number_max: Some(9), ..Default::default() };
+    let err = api
+        .send_raw_transaction_conditional(transaction().encoded_2718().into(), conditional)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), TxConditionalErr::INVALID_CONDITION_CODE);
+
+    // too many known slots to check
+    let conditional = TransactionConditional {
+        known_accounts: HashMap::from_iter([(
+            CONTRACT,
+            AccountStorage::Slots((0..=1000u64).map(|i| (U256::from(i), B256::ZERO)).collect()),
+        )]),
+        ..Default::default()
+    };
+    let err = api
+        .send_raw_transaction_conditional(transaction().encoded_2718().into(), conditional)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), TxConditionalErr::COST_EXCEEDED_CODE);
+
+    assert_eq!(pool.len(), 1);
+}
+
+#[tokio::test]
+async fn payload_transactions_check_conditionals() {
+    let pool = pool();
+    let provider = provider();
+    let block = ConditionalBlock { number: 11, timestamp: 1002 };
+
+    let add = |tx: Recovered<OpTransactionSigned>, conditional: Option<TransactionConditional>| {
+        let pool = pool.clone();
+        async move {
+            let len = tx.encode_2718_len();
+            let mut tx = OpPooledTransaction::new(tx, len);
+            if let Some(conditional) = conditional {
+                tx.set_conditional(conditional);
+            }
+            pool.add_transaction(TransactionOrigin::Private, tx).await.unwrap()
+        }
+    };
+    let plain = add(transaction(), None).await;
+    let matching = add(transaction(), Some(known_slot(VALUE))).await;
+    let mismatching = add(transaction(), Some(known_slot(B256::ZERO))).await;
+    let not_yet = add(
+        transaction(),
+        Some(TransactionConditional { block_number_min: Some(12), ..Default::default() }),
+    )
+    .await;
+    let expired = add(
+        transaction(),
+        Some(TransactionConditional { timestamp_max: Some(1001), ..Default::default() }),
+    )
+    .await;
+
+    let mut txs = ConditionalPayloadTransactions::new(
+        pool.clone(),
+        block,
+        provider,
+        BestPayloadTransactions::new(pool.best_transactions()),
+    );
+    let mut included = Vec::new();
+    while let Some(tx) = txs.next(()) {
+        included.push(*tx.tx_hash());
+    }
+    included.sort();
+    let mut expected = vec![plain, matching];
+    expected.sort();
+    assert_eq!(included, expected);
+
+    // transactions whose conditional can't be met anymore are removed from the pool
+    assert!(pool.contains(&not_yet));
+    assert!(!pool.contains(&mismatching));
+    assert!(!pool.contains(&expired));
+}
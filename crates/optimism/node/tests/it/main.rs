@@ -6,4 +6,7 @@ mod builder;
 #[cfg(feature = "optimism")]
 mod priority;
 
+#[cfg(feature = "optimism")]
+mod conditional;
+
 const fn main() {}
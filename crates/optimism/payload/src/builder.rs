@@ -1,13 +1,19 @@
 //! Optimism payload builder implementation.
 
 use crate::{
+    conditional::{ConditionalBlock, ConditionalPayloadTransactions, MaybeConditionalTransaction},
     config::{OpBuilderConfig, OpDAConfig},
     error::OpPayloadBuilderError,
+    flashblocks::{FlashblocksCheckpoint, FlashblocksPublisher},
+    interop::{
+        check_pool_executing_messages, executing_message_entries, executing_message_hashes,
+        InteropPayloadTransactions, InteropTxValidator, EXECUTING_MESSAGES_CHECK_DEADLINE,
+    },
     payload::{OpBuiltPayload, OpPayloadBuilderAttributes},
 };
 use alloy_consensus::{Eip658Value, Header, Transaction, Typed2718, EMPTY_OMMER_ROOT_HASH};
-use alloy_eips::{eip4895::Withdrawals, merge::BEACON_NONCE};
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_eips::{eip4895::Withdrawals, eip7685::EMPTY_REQUESTS_HASH, merge::BEACON_NONCE};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
 use alloy_rlp::Encodable;
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_engine::PayloadId;
@@ -21,7 +27,9 @@ use reth_evm::{
 };
 use reth_execution_types::ExecutionOutcome;
 use reth_optimism_chainspec::OpChainSpec;
-use reth_optimism_consensus::calculate_receipt_root_no_memo_optimism;
+use reth_optimism_consensus::{
+    calculate_receipt_root_no_memo_optimism, isthmus::ADDRESS_L2_TO_L1_MESSAGE_PASSER,
+};
 use reth_optimism_forks::OpHardforks;
 use reth_optimism_primitives::{OpPrimitives, OpReceipt, OpTransactionSigned};
 use reth_payload_builder_primitives::PayloadBuilderError;
@@ -32,8 +40,8 @@ use reth_primitives::{
 };
 use reth_primitives_traits::{block::Block as _, proofs, RecoveredBlock};
 use reth_provider::{
-    HashedPostStateProvider, ProviderError, StateProofProvider, StateProviderFactory,
-    StateRootProvider,
+    HashedPostStateProvider, ProviderError, StateProofProvider, StateProvider,
+    StateProviderFactory, StateRootProvider, StorageRootProvider,
 };
 use reth_revm::{database::StateProviderDatabase, witness::ExecutionWitnessRecord};
 use reth_transaction_pool::{
@@ -118,7 +126,7 @@ where
     fn build_payload<'a, Client, Pool, Txs>(
         &self,
         args: BuildArguments<Pool, Client, OpPayloadBuilderAttributes, OpBuiltPayload>,
-        best: impl FnOnce(BestTransactionsAttributes) -> Txs + Send + Sync + 'a,
+        best: impl FnOnce(BestTransactionsAttributes, Arc<dyn StateProvider>) -> Txs + Send + Sync + 'a,
     ) -> Result<BuildOutcome<OpBuiltPayload>, PayloadBuilderError>
     where
         Client: StateProviderFactory + ChainSpecProvider<ChainSpec = OpChainSpec>,
@@ -134,6 +142,7 @@ where
         let ctx = OpPayloadBuilderCtx {
             evm_config: self.evm_config.clone(),
             da_config: self.config.da_config.clone(),
+            interop_validator: self.config.interop_validator.clone(),
             chain_spec: client.chain_spec(),
             config,
             evm_env,
@@ -141,9 +150,14 @@ where
             best_payload,
         };
 
-        let builder = OpBuilder::new(best);
-
-        let state_provider = client.state_by_block_hash(ctx.parent().hash())?;
+        // the best transactions may check the parent state too, e.g. for conditional transactions
+        let state_provider: Arc<dyn StateProvider> =
+            client.state_by_block_hash(ctx.parent().hash())?.into();
+        let builder = OpBuilder::new({
+            let state_provider = state_provider.clone();
+            move |attrs| best(attrs, state_provider)
+        })
+        .with_flashblocks(self.config.flashblocks.as_ref());
         let state = StateProviderDatabase::new(state_provider);
 
         if ctx.attributes().no_tx_pool {
@@ -196,6 +210,7 @@ where
         let ctx = OpPayloadBuilderCtx {
             evm_config: self.evm_config.clone(),
             da_config: self.config.da_config.clone(),
+            interop_validator: self.config.interop_validator.clone(),
             chain_spec: client.chain_spec(),
             config,
             evm_env,
@@ -216,7 +231,10 @@ where
 impl<Pool, Client, EvmConfig, Txs> PayloadBuilder<Pool, Client> for OpPayloadBuilder<EvmConfig, Txs>
 where
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec = OpChainSpec>,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = EvmConfig::Transaction>>,
+    Pool: TransactionPool<
+        Transaction: PoolTransaction<Consensus = EvmConfig::Transaction>
+                         + MaybeConditionalTransaction,
+    >,
     EvmConfig: ConfigureEvm<Header = Header, Transaction = OpTransactionSigned>,
     Txs: OpPayloadTransactions,
 {
@@ -228,7 +246,46 @@ where
         args: BuildArguments<Pool, Client, OpPayloadBuilderAttributes, OpBuiltPayload>,
     ) -> Result<BuildOutcome<OpBuiltPayload>, PayloadBuilderError> {
         let pool = args.pool.clone();
-        self.build_payload(args, |attrs| self.best_transactions.best_transactions(pool, attrs))
+        // Transactions submitted with a conditional are only included if it's met by this block.
+        let block = ConditionalBlock {
+            number: args.config.parent_header.number + 1,
+            timestamp: args.config.attributes.timestamp(),
+        };
+        // Executing messages are checked again, they may have become invalid since the
+        // transactions were admitted to the pool. All of them are checked at once, so that the
+        // validator can't hold up the build for longer than the deadline.
+        let valid_executing_messages = self
+            .config
+            .interop_validator
+            .as_ref()
+            .filter(|_| !args.config.attributes.no_tx_pool)
+            .map(|validator| {
+                check_pool_executing_messages(
+                    validator.as_ref(),
+                    &pool,
+                    block.timestamp,
+                    EXECUTING_MESSAGES_CHECK_DEADLINE,
+                )
+            });
+        let outcome = self.build_payload(args, |attrs, state| {
+            InteropPayloadTransactions::new(
+                valid_executing_messages,
+                ConditionalPayloadTransactions::new(
+                    pool.clone(),
+                    block,
+                    state,
+                    self.best_transactions.best_transactions(pool, attrs),
+                ),
+            )
+        })?;
+
+        if let Some(flashblocks) = &self.config.flashblocks {
+            if let BuildOutcome::Better { payload, .. } | BuildOutcome::Freeze(payload) = &outcome {
+                flashblocks.on_payload(payload);
+            }
+        }
+
+        Ok(outcome)
     }
 
     fn on_missing_payload(
@@ -256,7 +313,7 @@ where
             cancel: Default::default(),
             best_payload: None,
         };
-        self.build_payload(args, |_| NoopPayloadTransactions::default())?
+        self.build_payload(args, |_, _| NoopPayloadTransactions::default())?
             .into_payload()
             .ok_or_else(|| PayloadBuilderError::MissingPayload)
     }
@@ -277,16 +334,27 @@ where
 ///
 /// And finally
 /// 5. build the block: compute all roots (txs, state)
+///
+/// With flashblocks enabled, a rebuild of the payload resumes after step 4 of the previous build
+/// and only includes additional transactions.
 #[derive(derive_more::Debug)]
 pub struct OpBuilder<'a, Txs> {
     /// Yields the best transaction to include if transactions from the mempool are allowed.
     #[debug(skip)]
     best: Box<dyn FnOnce(BestTransactionsAttributes) -> Txs + 'a>,
+    /// Keeps the execution state of the previous build, if flashblocks are enabled.
+    flashblocks: Option<&'a FlashblocksPublisher>,
 }
 
 impl<'a, Txs> OpBuilder<'a, Txs> {
     fn new(best: impl FnOnce(BestTransactionsAttributes) -> Txs + Send + Sync + 'a) -> Self {
-        Self { best: Box::new(best) }
+        Self { best: Box::new(best), flashblocks: None }
+    }
+
+    /// Builds the payload incrementally, resuming from the previous build of the payload.
+    const fn with_flashblocks(mut self, flashblocks: Option<&'a FlashblocksPublisher>) -> Self {
+        self.flashblocks = flashblocks;
+        self
     }
 }
 
@@ -304,30 +372,61 @@ where
         EvmConfig: ConfigureEvm<Header = Header, Transaction = OpTransactionSigned>,
         DB: Database<Error = ProviderError>,
     {
-        let Self { best } = self;
+        let Self { best, flashblocks } = self;
         debug!(target: "payload_builder", id=%ctx.payload_id(), parent_header = ?ctx.parent().hash(), parent_number = ctx.parent().number, "building new payload");
 
-        // 1. apply eip-4788 pre block contract call
-        ctx.apply_pre_beacon_root_contract_call(state)?;
+        let no_tx_pool = ctx.attributes().no_tx_pool;
+        let checkpoint = flashblocks
+            .filter(|_| !no_tx_pool)
+            .and_then(|flashblocks| flashblocks.checkpoint(ctx.payload_id()));
+        let (mut info, resumed) = if let Some(checkpoint) = checkpoint {
+            // the pre-block changes and sequencer transactions are already part of the state
+            let FlashblocksCheckpoint { info, cache, transitions, .. } = checkpoint;
+            trace!(target: "payload_builder", id=%ctx.payload_id(), transactions=info.executed_transactions.len(), "resuming payload from checkpoint");
+            state.cache = cache;
+            state.transition_state = Some(transitions);
+            let resumed = info.executed_transactions.len();
+            (info, Some(resumed))
+        } else {
+            // 1. apply eip-4788 pre block contract call
+            ctx.apply_pre_beacon_root_contract_call(state)?;
 
-        // 2. ensure create2deployer is force deployed
-        ctx.ensure_create2_deployer(state)?;
+            // 2. apply eip-2935 blockhashes update
+            ctx.apply_blockhashes_contract_call(state)?;
 
-        // 3. execute sequencer transactions
-        let mut info = ctx.execute_sequencer_transactions(state)?;
+            // 3. ensure create2deployer is force deployed
+            ctx.ensure_create2_deployer(state)?;
 
-        // 4. if mem pool transactions are requested we execute them
-        if !ctx.attributes().no_tx_pool {
+            // 4. execute sequencer transactions
+            (ctx.execute_sequencer_transactions(state)?, None)
+        };
+
+        // 5. if mem pool transactions are requested we execute them
+        if !no_tx_pool {
             let best_txs = best(ctx.best_transaction_attributes());
             if ctx.execute_best_transactions(&mut info, state, best_txs)?.is_some() {
                 return Ok(BuildOutcomeKind::Cancelled)
             }
 
-            // check if the new payload is even more valuable
-            if !ctx.is_better_payload(info.total_fees) {
+            // check if the new payload is even more valuable, a resumed payload is if it includes
+            // additional transactions
+            let is_better = match resumed {
+                Some(resumed) => info.executed_transactions.len() > resumed,
+                None => ctx.is_better_payload(info.total_fees),
+            };
+            if !is_better {
                 // can skip building the block
                 return Ok(BuildOutcomeKind::Aborted { fees: info.total_fees })
             }
+
+            if let Some(flashblocks) = flashblocks {
+                flashblocks.set_checkpoint(FlashblocksCheckpoint {
+                    payload_id: ctx.payload_id(),
+                    info: info.clone(),
+                    cache: state.cache.clone(),
+                    transitions: state.transition_state.clone().unwrap_or_default(),
+                });
+            }
         }
 
         let withdrawals_root = ctx.commit_withdrawals(state)?;
@@ -348,7 +447,7 @@ where
     where
         EvmConfig: ConfigureEvm<Header = Header, Transaction = OpTransactionSigned>,
         DB: Database<Error = ProviderError> + AsRef<P>,
-        P: StateRootProvider + HashedPostStateProvider,
+        P: StateRootProvider + HashedPostStateProvider + StorageRootProvider,
     {
         let ExecutedPayload { info, withdrawals_root } = match self.execute(&mut state, &ctx)? {
             BuildOutcomeKind::Better { payload } | BuildOutcomeKind::Freeze(payload) => payload,
@@ -388,6 +487,21 @@ where
             })?
         };
 
+        let (withdrawals_root, requests_hash) = if ctx.is_isthmus_active() {
+            // Post-Isthmus the withdrawals root is the storage root of the L2 to L1 message passer
+            // and L2 blocks never contain requests
+            let storage_updates = hashed_state
+                .storages
+                .get(&keccak256(ADDRESS_L2_TO_L1_MESSAGE_PASSER))
+                .cloned()
+                .unwrap_or_default();
+            let storage_root =
+                state_provider.storage_root(ADDRESS_L2_TO_L1_MESSAGE_PASSER, storage_updates)?;
+            (Some(storage_root), Some(EMPTY_REQUESTS_HASH))
+        } else {
+            (withdrawals_root, None)
+        };
+
         // create the block header
         let transactions_root = proofs::calculate_transaction_root(&info.executed_transactions);
 
@@ -418,7 +532,7 @@ where
             parent_beacon_block_root: ctx.attributes().payload_attributes.parent_beacon_block_root,
             blob_gas_used,
             excess_blob_gas,
-            requests_hash: None,
+            requests_hash,
         };
 
         // seal the block
@@ -520,7 +634,7 @@ pub struct ExecutedPayload {
 }
 
 /// This acts as the container for executed transactions and its byproducts (receipts, gas used)
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct ExecutionInfo {
     /// All executed transactions (unrecovered).
     pub executed_transactions: Vec<OpTransactionSigned>,
@@ -583,6 +697,8 @@ pub struct OpPayloadBuilderCtx<EvmConfig> {
     pub evm_config: EvmConfig,
     /// The DA config for the payload builder
     pub da_config: OpDAConfig,
+    /// Validates the executing messages of interop transactions, if interop is enabled
+    pub interop_validator: Option<Arc<dyn InteropTxValidator>>,
     /// The chainspec
     pub chain_spec: Arc<OpChainSpec>,
     /// How to build the payload.
@@ -696,6 +812,11 @@ impl<EvmConfig> OpPayloadBuilderCtx<EvmConfig> {
         self.chain_spec.is_holocene_active_at_timestamp(self.attributes().timestamp())
     }
 
+    /// Returns true if isthmus is active for the payload.
+    pub fn is_isthmus_active(&self) -> bool {
+        self.chain_spec.is_isthmus_active_at_timestamp(self.attributes().timestamp())
+    }
+
     /// Returns true if the fees are higher than the previous payload.
     pub fn is_better_payload(&self, total_fees: U256) -> bool {
         is_better_payload(self.best_payload.as_ref(), total_fees)
@@ -766,6 +887,29 @@ where
         Ok(())
     }
 
+    /// apply eip-2935 pre block contract call
+    pub fn apply_blockhashes_contract_call<DB>(
+        &self,
+        db: &mut DB,
+    ) -> Result<(), PayloadBuilderError>
+    where
+        DB: Database + DatabaseCommit,
+        DB::Error: Display,
+    {
+        SystemCaller::new(self.evm_config.clone(), self.chain_spec.clone())
+            .pre_block_blockhashes_contract_call(db, &self.evm_env, self.parent().hash())
+            .map_err(|err| {
+                warn!(target: "payload_builder",
+                    parent_header=%self.parent().hash(),
+                    %err,
+                    "failed to apply blockhashes contract call for payload"
+                );
+                PayloadBuilderError::Internal(err.into())
+            })?;
+
+        Ok(())
+    }
+
     /// Executes all sequencer transactions that are included in the payload attributes.
     pub fn execute_sequencer_transactions<DB>(
         &self,
@@ -889,6 +1033,12 @@ where
         let mut evm = self.evm_config.evm_with_env(&mut *db, self.evm_env.clone());
 
         while let Some(tx) = best_txs.next(()) {
+            // transactions that are already included, e.g. if the payload was resumed, are skipped
+            let nonce = evm.db_mut().basic(tx.signer())?.map(|account| account.nonce);
+            if nonce.is_some_and(|nonce| nonce > tx.nonce()) {
+                continue
+            }
+
             if info.is_tx_over_limits(tx.tx(), block_gas_limit, tx_da_limit, block_da_limit) {
                 // we can't fit this transaction into the block, so we need to mark it as
                 // invalid which also removes all dependent transaction from
@@ -936,6 +1086,16 @@ where
                 }
             };
 
+            // executing messages that weren't declared in the access list haven't been validated
+            if self.interop_validator.is_some() &&
+                tx.access_list().map(executing_message_entries).unwrap_or_default().is_empty() &&
+                !executing_message_hashes(result.logs()).is_empty()
+            {
+                trace!(target: "payload_builder", ?tx, "skipping transaction with undeclared executing messages and its descendants");
+                best_txs.mark_invalid(tx.signer(), tx.nonce());
+                continue
+            }
+
             // commit changes
             evm.db_mut().commit(state);
 
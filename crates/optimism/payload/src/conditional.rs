@@ -0,0 +1,187 @@
+//! Support for transactions submitted with a [`TransactionConditional`] via
+//! `eth_sendRawTransactionConditional`.
+
+use alloy_consensus::Transaction;
+use alloy_primitives::{Address, StorageKey, B256};
+use alloy_rpc_types_eth::erc4337::{AccountStorage, TransactionConditional};
+use reth_payload_util::PayloadTransactions;
+use reth_primitives::Recovered;
+use reth_primitives_traits::SignedTransaction;
+use reth_provider::{ProviderError, StateProvider};
+use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use tracing::{trace, warn};
+
+/// A pool transaction that may carry a [`TransactionConditional`].
+///
+/// The conditional isn't part of the transaction's encoding, so it's only known to the node the
+/// transaction was submitted to.
+pub trait MaybeConditionalTransaction {
+    /// Attaches the conditional to the transaction.
+    fn set_conditional(&mut self, conditional: TransactionConditional);
+
+    /// Returns the conditional of the transaction, if any.
+    fn conditional(&self) -> Option<&TransactionConditional>;
+
+    /// Returns the transaction with the conditional attached.
+    fn with_conditional(mut self, conditional: TransactionConditional) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_conditional(conditional);
+        self
+    }
+}
+
+/// Block number and timestamp that a [`TransactionConditional`] is checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConditionalBlock {
+    /// Number of the block.
+    pub number: u64,
+    /// Timestamp of the block.
+    pub timestamp: u64,
+}
+
+impl ConditionalBlock {
+    /// Returns `true` if the block is within the block number and timestamp bounds of the
+    /// conditional.
+    pub fn matches(&self, conditional: &TransactionConditional) -> bool {
+        conditional.block_number_min.is_none_or(|min| self.number >= min) &&
+            conditional.block_number_max.is_none_or(|max| self.number <= max) &&
+            conditional.timestamp_min.is_none_or(|min| self.timestamp >= min) &&
+            conditional.timestamp_max.is_none_or(|max| self.timestamp <= max)
+    }
+
+    /// Returns `true` if neither this block nor any later one can be within the bounds of the
+    /// conditional.
+    pub fn has_expired(&self, conditional: &TransactionConditional) -> bool {
+        conditional.block_number_max.is_some_and(|max| self.number > max) ||
+            conditional.timestamp_max.is_some_and(|max| self.timestamp > max)
+    }
+}
+
+/// Error returned when the known accounts of a [`TransactionConditional`] don't match the state.
+#[derive(Debug, thiserror::Error)]
+pub enum KnownAccountsError {
+    /// A storage slot doesn't have the expected value.
+    #[error("storage slot mismatch")]
+    StorageSlotMismatch,
+    /// The storage root of an account doesn't have the expected value.
+    #[error("storage root mismatch")]
+    StorageRootMismatch,
+    /// Failed to read the state.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+/// Checks the storage slots and storage roots of the accounts known to the conditional against
+/// the state.
+pub fn check_known_accounts<S: StateProvider + ?Sized>(
+    state: &S,
+    conditional: &TransactionConditional,
+) -> Result<(), KnownAccountsError> {
+    for (address, storage) in &conditional.known_accounts {
+        match storage {
+            AccountStorage::Slots(slots) => {
+                for (slot, value) in slots {
+                    let current =
+                        state.storage(*address, StorageKey::from(*slot))?.unwrap_or_default();
+                    if B256::from(current) != *value {
+                        return Err(KnownAccountsError::StorageSlotMismatch)
+                    }
+                }
+            }
+            AccountStorage::RootHash(expected) => {
+                if state.storage_root(*address, Default::default())? != *expected {
+                    return Err(KnownAccountsError::StorageRootMismatch)
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// [`PayloadTransactions`] that skips pool transactions whose conditional isn't met by the block
+/// that's being built, and removes the expired ones from the pool.
+///
+/// The known accounts of a conditional are checked against the state the block is built on.
+/// Transactions whose known accounts don't match are removed from the pool, like op-geth does.
+#[derive(Debug)]
+pub struct ConditionalPayloadTransactions<Pool, State, Txs> {
+    pool: Pool,
+    block: ConditionalBlock,
+    state: State,
+    inner: Txs,
+}
+
+impl<Pool, State, Txs> ConditionalPayloadTransactions<Pool, State, Txs> {
+    /// Creates a new [`ConditionalPayloadTransactions`] for the given block, built on top of the
+    /// given state.
+    pub const fn new(pool: Pool, block: ConditionalBlock, state: State, inner: Txs) -> Self {
+        Self { pool, block, state, inner }
+    }
+}
+
+impl<Pool, State, Txs> PayloadTransactions for ConditionalPayloadTransactions<Pool, State, Txs>
+where
+    Pool: TransactionPool<Transaction: MaybeConditionalTransaction>,
+    State: StateProvider,
+    Txs: PayloadTransactions<Transaction = <Pool::Transaction as PoolTransaction>::Consensus>,
+    Txs::Transaction: SignedTransaction,
+{
+    type Transaction = Txs::Transaction;
+
+    fn next(&mut self, ctx: ()) -> Option<Recovered<Self::Transaction>> {
+        loop {
+            let tx = self.inner.next(ctx)?;
+            let Some(pooled) = self.pool.get(tx.tx_hash()) else { return Some(tx) };
+            let Some(conditional) = pooled.transaction.conditional() else { return Some(tx) };
+
+            if self.block.matches(conditional) {
+                match check_known_accounts(&self.state, conditional) {
+                    Ok(()) => return Some(tx),
+                    Err(KnownAccountsError::Provider(err)) => {
+                        warn!(target: "payload_builder", %err, hash = %tx.tx_hash(), "failed to check known accounts of conditional");
+                    }
+                    Err(err) => {
+                        trace!(target: "payload_builder", %err, hash = %tx.tx_hash(), "skipping transaction with unmet known accounts");
+                        self.pool.remove_transactions(vec![*tx.tx_hash()]);
+                    }
+                }
+            } else {
+                trace!(target: "payload_builder", hash = %tx.tx_hash(), block = ?self.block, "skipping transaction with unmet conditional");
+                if self.block.has_expired(conditional) {
+                    self.pool.remove_transactions(vec![*tx.tx_hash()]);
+                }
+            }
+            self.inner.mark_invalid(tx.signer(), tx.nonce());
+        }
+    }
+
+    fn mark_invalid(&mut self, sender: Address, nonce: u64) {
+        self.inner.mark_invalid(sender, nonce)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conditional_block_bounds() {
+        let conditional = TransactionConditional {
+            block_number_min: Some(10),
+            block_number_max: Some(20),
+            timestamp_min: None,
+            timestamp_max: Some(1000),
+            ..Default::default()
+        };
+
+        let block = |number, timestamp| ConditionalBlock { number, timestamp };
+        assert!(block(10, 1000).matches(&conditional));
+        assert!(!block(9, 900).matches(&conditional));
+        assert!(!block(9, 900).has_expired(&conditional));
+        assert!(!block(21, 900).matches(&conditional));
+        assert!(block(21, 900).has_expired(&conditional));
+        assert!(block(15, 1001).has_expired(&conditional));
+    }
+}
@@ -1,5 +1,6 @@
 //! Additional configuration for the OP builder
 
+use crate::{flashblocks::FlashblocksPublisher, interop::InteropTxValidator};
 use std::sync::{atomic::AtomicU64, Arc};
 
 /// Settings for the OP builder.
@@ -7,12 +8,31 @@ use std::sync::{atomic::AtomicU64, Arc};
 pub struct OpBuilderConfig {
     /// Data availability configuration for the OP builder.
     pub da_config: OpDAConfig,
+    /// Validates the executing messages of interop transactions, if interop is enabled.
+    pub interop_validator: Option<Arc<dyn InteropTxValidator>>,
+    /// Publishes the built payloads as flashblocks, if flashblocks are enabled.
+    pub flashblocks: Option<FlashblocksPublisher>,
 }
 
 impl OpBuilderConfig {
     /// Creates a new OP builder configuration with the given data availability configuration.
     pub const fn new(da_config: OpDAConfig) -> Self {
-        Self { da_config }
+        Self { da_config, interop_validator: None, flashblocks: None }
+    }
+
+    /// Configures the validator for the executing messages of interop transactions.
+    pub fn with_interop_validator(
+        mut self,
+        interop_validator: Option<Arc<dyn InteropTxValidator>>,
+    ) -> Self {
+        self.interop_validator = interop_validator;
+        self
+    }
+
+    /// Configures the publisher of flashblocks.
+    pub fn with_flashblocks(mut self, flashblocks: Option<FlashblocksPublisher>) -> Self {
+        self.flashblocks = flashblocks;
+        self
     }
 
     /// Returns the Data Availability configuration for the OP builder, if it has configured
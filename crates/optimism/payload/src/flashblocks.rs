@@ -0,0 +1,421 @@
+//! Flashblocks, incremental sub-block pre-confirmations of the payload being built.
+//!
+//! While a payload job is running, the builder rebuilds the payload in a fixed interval. With
+//! flashblocks enabled, every rebuild that includes new transactions is published as a
+//! [`FlashblocksPayloadV1`]: the first flashblock of a payload carries the block environment
+//! ([`ExecutionPayloadBaseV1`]), subsequent flashblocks only the newly included transactions and
+//! the updated roots ([`ExecutionPayloadFlashblockDeltaV1`]).
+//!
+//! Published transactions are pre-confirmed, so the payload is built incrementally: every rebuild
+//! resumes from the [`FlashblocksCheckpoint`] of the previous build and only appends transactions.
+
+use crate::{builder::ExecutionInfo, payload::OpBuiltPayload};
+use alloy_eips::{eip2718::Encodable2718, eip4895::Withdrawal};
+use alloy_primitives::{map::HashMap, Address, Bloom, Bytes, TxHash, B256, U256};
+use alloy_rpc_types_engine::PayloadId;
+use parking_lot::Mutex;
+use reth_chain_state::ExecutedBlock;
+use reth_optimism_primitives::{OpPrimitives, OpReceipt};
+use reth_payload_primitives::BuiltPayload;
+use reth_primitives_traits::SignedTransaction;
+use revm::db::{states::TransitionState, CacheState};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{broadcast, watch};
+use tracing::debug;
+
+/// Default interval in which flashblocks are published.
+pub const DEFAULT_FLASHBLOCK_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Maximum number of flashblocks buffered per subscriber.
+const FLASHBLOCKS_CHANNEL_CAPACITY: usize = 128;
+
+/// Receives the block of the latest flashblock.
+///
+/// Once a block is sealed its flashblock is outdated, receivers should check that the block
+/// extends the canonical head.
+pub type PreconfirmedBlockReceiver = watch::Receiver<Option<ExecutedBlock<OpPrimitives>>>;
+
+/// The block environment of a payload, sent with the first flashblock of the payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionPayloadBaseV1 {
+    /// Hash of the parent block.
+    pub parent_hash: B256,
+    /// The fee recipient of the block.
+    pub fee_recipient: Address,
+    /// The `prev_randao` of the block.
+    pub prev_randao: B256,
+    /// Number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// Gas limit of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_limit: u64,
+    /// Timestamp of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+    /// Extra data of the block.
+    pub extra_data: Bytes,
+    /// Base fee of the block.
+    pub base_fee_per_gas: U256,
+    /// Parent beacon block root of the block.
+    pub parent_beacon_block_root: B256,
+}
+
+/// The transactions included since the previous flashblock, and the state of the payload
+/// including them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionPayloadFlashblockDeltaV1 {
+    /// State root of the payload.
+    pub state_root: B256,
+    /// Receipts root of the payload.
+    pub receipts_root: B256,
+    /// Logs bloom of the payload.
+    pub logs_bloom: Bloom,
+    /// Gas used by the payload.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// Hash of the payload.
+    pub block_hash: B256,
+    /// EIP-2718 encoded transactions included since the previous flashblock.
+    pub transactions: Vec<Bytes>,
+    /// Withdrawals of the payload.
+    pub withdrawals: Vec<Withdrawal>,
+    /// Withdrawals root of the payload.
+    pub withdrawals_root: B256,
+}
+
+/// Additional data of a flashblock.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlashblockMetadata {
+    /// Number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// Receipts of the transactions included since the previous flashblock, by transaction hash.
+    pub receipts: HashMap<TxHash, OpReceipt>,
+}
+
+/// A flashblock, an incremental update of the payload being built.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlashblocksPayloadV1 {
+    /// Identifier of the payload.
+    pub payload_id: PayloadId,
+    /// Index of the flashblock within the payload.
+    #[serde(with = "alloy_serde::quantity")]
+    pub index: u64,
+    /// The block environment, only set for the first flashblock of the payload.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<ExecutionPayloadBaseV1>,
+    /// The transactions and state of this flashblock.
+    pub diff: ExecutionPayloadFlashblockDeltaV1,
+    /// Additional data of the flashblock.
+    pub metadata: FlashblockMetadata,
+}
+
+/// The execution state of a payload after its latest build.
+///
+/// The next build of the payload resumes from it, so that the transactions of the payload, and
+/// with them all published flashblocks, remain part of the payload.
+#[derive(Debug, Clone)]
+pub struct FlashblocksCheckpoint {
+    /// Identifier of the payload.
+    pub payload_id: PayloadId,
+    /// The executed transactions, their receipts and the gas used by them.
+    pub info: ExecutionInfo,
+    /// The cached state, including all changes made by the executed transactions.
+    pub cache: CacheState,
+    /// The state transitions of the executed transactions, which aren't merged yet.
+    pub transitions: TransitionState,
+}
+
+/// Publishes the payloads built by the payload builder as flashblocks.
+///
+/// Cheap to clone, all clones share the same stream of flashblocks.
+#[derive(Debug, Clone)]
+pub struct FlashblocksPublisher {
+    inner: Arc<FlashblocksPublisherInner>,
+}
+
+#[derive(Debug)]
+struct FlashblocksPublisherInner {
+    /// Minimum interval between two flashblocks of the same payload.
+    interval: Duration,
+    /// Sender of the published flashblocks.
+    flashblocks: broadcast::Sender<Arc<FlashblocksPayloadV1>>,
+    /// The block of the latest published flashblock.
+    preconfirmed: watch::Sender<Option<ExecutedBlock<OpPrimitives>>>,
+    /// The latest published flashblock.
+    published: Mutex<Option<PublishedFlashblock>>,
+    /// The execution state of the latest build.
+    checkpoint: Mutex<Option<FlashblocksCheckpoint>>,
+}
+
+/// The latest published flashblock of a payload.
+#[derive(Debug)]
+struct PublishedFlashblock {
+    /// Identifier of the payload.
+    payload_id: PayloadId,
+    /// Index of the flashblock.
+    index: u64,
+    /// Number of transactions published for the payload.
+    transactions: usize,
+    /// When the flashblock was published.
+    published_at: Instant,
+}
+
+impl FlashblocksPublisher {
+    /// Creates a new publisher, publishing flashblocks at most once per `interval`.
+    pub fn new(interval: Duration) -> Self {
+        let (flashblocks, _) = broadcast::channel(FLASHBLOCKS_CHANNEL_CAPACITY);
+        let (preconfirmed, _) = watch::channel(None);
+        Self {
+            inner: Arc::new(FlashblocksPublisherInner {
+                interval,
+                flashblocks,
+                preconfirmed,
+                published: Mutex::new(None),
+                checkpoint: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Returns the interval in which flashblocks are published.
+    pub fn interval(&self) -> Duration {
+        self.inner.interval
+    }
+
+    /// Subscribes to the published flashblocks.
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<FlashblocksPayloadV1>> {
+        self.inner.flashblocks.subscribe()
+    }
+
+    /// Subscribes to the blocks of the published flashblocks.
+    pub fn subscribe_preconfirmed(&self) -> PreconfirmedBlockReceiver {
+        self.inner.preconfirmed.subscribe()
+    }
+
+    /// Returns the block of the latest published flashblock.
+    ///
+    /// Once the block is sealed this is outdated, callers should check that it extends the
+    /// canonical head.
+    pub fn preconfirmed_block(&self) -> Option<ExecutedBlock<OpPrimitives>> {
+        self.inner.preconfirmed.borrow().clone()
+    }
+
+    /// Returns the execution state of the latest build of the payload, if it was built before.
+    pub fn checkpoint(&self, payload_id: PayloadId) -> Option<FlashblocksCheckpoint> {
+        self.inner
+            .checkpoint
+            .lock()
+            .as_ref()
+            .filter(|checkpoint| checkpoint.payload_id == payload_id)
+            .cloned()
+    }
+
+    /// Stores the execution state of the latest build of a payload, replacing the state of any
+    /// other payload.
+    pub fn set_checkpoint(&self, checkpoint: FlashblocksCheckpoint) {
+        *self.inner.checkpoint.lock() = Some(checkpoint);
+    }
+
+    /// Publishes the transactions of the built payload that haven't been published yet.
+    ///
+    /// The first payload of a payload job is always published, later payloads of the job are
+    /// published if the interval since the previous flashblock elapsed and they include new
+    /// transactions.
+    ///
+    /// Payloads are built incrementally, later payloads of a payload job extend the transactions
+    /// that were already published for it.
+    pub fn on_payload(&self, payload: &OpBuiltPayload) {
+        let Some(executed) = payload.executed_block() else { return };
+        let transactions = &payload.block().body().transactions;
+
+        let mut published = self.inner.published.lock();
+        let (index, from) = match published.as_ref() {
+            Some(prev) if prev.payload_id == payload.id() => {
+                if transactions.len() <= prev.transactions ||
+                    prev.published_at.elapsed() < self.inner.interval
+                {
+                    return
+                }
+                (prev.index + 1, prev.transactions)
+            }
+            _ => (0, 0),
+        };
+
+        let block = payload.block();
+        let header = block.header();
+        let base = (index == 0).then(|| ExecutionPayloadBaseV1 {
+            parent_hash: header.parent_hash,
+            fee_recipient: header.beneficiary,
+            prev_randao: header.mix_hash,
+            block_number: header.number,
+            gas_limit: header.gas_limit,
+            timestamp: header.timestamp,
+            extra_data: header.extra_data.clone(),
+            base_fee_per_gas: U256::from(header.base_fee_per_gas.unwrap_or_default()),
+            parent_beacon_block_root: header.parent_beacon_block_root.unwrap_or_default(),
+        });
+        let receipts = executed
+            .execution_outcome()
+            .receipts()
+            .receipt_vec
+            .first()
+            .map(|receipts| {
+                transactions[from..]
+                    .iter()
+                    .zip(receipts[from..].iter())
+                    .filter_map(|(tx, receipt)| Some((*tx.tx_hash(), receipt.clone()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let flashblock = FlashblocksPayloadV1 {
+            payload_id: payload.id(),
+            index,
+            base,
+            diff: ExecutionPayloadFlashblockDeltaV1 {
+                state_root: header.state_root,
+                receipts_root: header.receipts_root,
+                logs_bloom: header.logs_bloom,
+                gas_used: header.gas_used,
+                block_hash: block.hash(),
+                transactions: transactions[from..]
+                    .iter()
+                    .map(|tx| tx.encoded_2718().into())
+                    .collect(),
+                withdrawals: block
+                    .body()
+                    .withdrawals
+                    .as_ref()
+                    .map(|withdrawals| withdrawals.to_vec())
+                    .unwrap_or_default(),
+                withdrawals_root: header.withdrawals_root.unwrap_or_default(),
+            },
+            metadata: FlashblockMetadata { block_number: header.number, receipts },
+        };
+
+        debug!(target: "payload_builder", id=%payload.id(), index, transactions=transactions.len() - from, "publishing flashblock");
+        *published = Some(PublishedFlashblock {
+            payload_id: payload.id(),
+            index,
+            transactions: transactions.len(),
+            published_at: Instant::now(),
+        });
+        self.inner.preconfirmed.send_replace(Some(executed));
+        // there may be no subscribers
+        let _ = self.inner.flashblocks.send(Arc::new(flashblock));
+    }
+}
+
+impl Default for FlashblocksPublisher {
+    fn default() -> Self {
+        Self::new(DEFAULT_FLASHBLOCK_INTERVAL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{Bytes, PrimitiveSignature as Signature};
+    use op_alloy_consensus::{OpDepositReceipt, OpTypedTransaction, TxDeposit};
+    use reth_execution_types::ExecutionOutcome;
+    use reth_optimism_chainspec::OP_MAINNET;
+    use reth_optimism_primitives::OpTransactionSigned;
+    use reth_primitives::{Block, BlockBody};
+    use reth_primitives_traits::{Block as _, RecoveredBlock};
+
+    fn deposit(nonce: u64) -> OpTransactionSigned {
+        OpTransactionSigned::new_unhashed(
+            OpTypedTransaction::Deposit(TxDeposit { gas_limit: nonce + 1, ..Default::default() }),
+            Signature::test_signature(),
+        )
+    }
+
+    fn payload(id: u64, transactions: Vec<OpTransactionSigned>) -> OpBuiltPayload {
+        let receipts = transactions
+            .iter()
+            .map(|_| Some(OpReceipt::Deposit(OpDepositReceipt::default())))
+            .collect::<Vec<_>>();
+        let senders = vec![Address::ZERO; transactions.len()];
+        let block = Block {
+            header: Header { number: 1, ..Default::default() },
+            body: BlockBody { transactions, ..Default::default() },
+        }
+        .seal_slow();
+        let executed = ExecutedBlock {
+            recovered_block: Arc::new(RecoveredBlock::new_sealed(block.clone(), senders)),
+            execution_output: Arc::new(ExecutionOutcome::new(
+                Default::default(),
+                vec![receipts].into(),
+                1,
+                Vec::new(),
+            )),
+            hashed_state: Default::default(),
+            trie: Default::default(),
+        };
+        OpBuiltPayload::new(
+            PayloadId::new(id.to_be_bytes()),
+            Arc::new(block),
+            U256::ZERO,
+            OP_MAINNET.clone(),
+            Default::default(),
+            Some(executed),
+        )
+    }
+
+    #[test]
+    fn publishes_transaction_deltas() {
+        let publisher = FlashblocksPublisher::new(Duration::ZERO);
+        let mut flashblocks = publisher.subscribe();
+
+        publisher.on_payload(&payload(1, vec![deposit(0)]));
+        let first = flashblocks.try_recv().unwrap();
+        assert_eq!(first.index, 0);
+        assert!(first.base.is_some());
+        assert_eq!(first.diff.transactions.len(), 1);
+        assert_eq!(first.metadata.receipts.len(), 1);
+
+        // nothing new to publish
+        publisher.on_payload(&payload(1, vec![deposit(0)]));
+        assert!(flashblocks.try_recv().is_err());
+
+        publisher.on_payload(&payload(1, vec![deposit(0), deposit(1)]));
+        let second = flashblocks.try_recv().unwrap();
+        assert_eq!(second.index, 1);
+        assert!(second.base.is_none());
+        assert_eq!(second.diff.transactions, vec![Bytes::from(deposit(1).encoded_2718())]);
+        assert_eq!(
+            publisher.preconfirmed_block().unwrap().recovered_block().body().transactions.len(),
+            2
+        );
+
+        // a new payload starts over
+        publisher.on_payload(&payload(2, vec![deposit(1)]));
+        assert_eq!(flashblocks.try_recv().unwrap().index, 0);
+    }
+
+    #[test]
+    fn checkpoint_of_latest_payload() {
+        let publisher = FlashblocksPublisher::new(Duration::ZERO);
+        let checkpoint = |id: u64, transactions: Vec<OpTransactionSigned>| FlashblocksCheckpoint {
+            payload_id: PayloadId::new(id.to_be_bytes()),
+            info: ExecutionInfo { executed_transactions: transactions, ..Default::default() },
+            cache: CacheState::default(),
+            transitions: TransitionState::default(),
+        };
+        assert!(publisher.checkpoint(PayloadId::new(1u64.to_be_bytes())).is_none());
+
+        publisher.set_checkpoint(checkpoint(1, vec![deposit(0)]));
+        let resumed = publisher.checkpoint(PayloadId::new(1u64.to_be_bytes())).unwrap();
+        assert_eq!(resumed.info.executed_transactions, vec![deposit(0)]);
+
+        // only the latest payload is resumed
+        publisher.set_checkpoint(checkpoint(2, vec![deposit(1)]));
+        assert!(publisher.checkpoint(PayloadId::new(1u64.to_be_bytes())).is_none());
+        assert!(publisher.checkpoint(PayloadId::new(2u64.to_be_bytes())).is_some());
+    }
+}
@@ -0,0 +1,350 @@
+//! Superchain interop support, validation of executing messages.
+//!
+//! Transactions execute messages initiated on other chains of the dependency set by calling the
+//! `CrossL2Inbox` predeploy. Every executing message is declared in the transaction's access list,
+//! as storage keys of the `CrossL2Inbox`, and emits an `ExecutingMessage` log when executed. The
+//! declared entries are checked against a supervisor, see [`InteropTxValidator`].
+
+use alloy_consensus::Transaction;
+use alloy_eips::eip2930::AccessList;
+use alloy_primitives::{address, b256, Address, Log, B256};
+use parking_lot::RwLock;
+use reth_payload_util::PayloadTransactions;
+use reth_primitives::Recovered;
+use reth_transaction_pool::{error::PoolTransactionError, PoolTransaction, TransactionPool};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt, future::Future, pin::Pin, time::Duration};
+use tracing::{trace, warn};
+
+/// The `CrossL2Inbox` predeploy, validates and emits executing messages.
+pub const CROSS_L2_INBOX_ADDRESS: Address = address!("4200000000000000000000000000000000000022");
+
+/// How long the payload builder waits for the executing messages of the pool transactions to be
+/// validated, see [`check_pool_executing_messages`].
+pub const EXECUTING_MESSAGES_CHECK_DEADLINE: Duration = Duration::from_millis(500);
+
+/// Topic of the `ExecutingMessage(bytes32,(address,uint256,uint256,uint256,uint256))` event
+/// emitted by the `CrossL2Inbox`.
+pub const EXECUTING_MESSAGE_TOPIC: B256 =
+    b256!("5c37832d2e8d10e346e55ad62071a6a2f9fa5130614ef2ec6617555c6f467ba7");
+
+/// Returns the `CrossL2Inbox` entries of the access list, which declare the executing messages
+/// of the transaction.
+pub fn executing_message_entries(access_list: &AccessList) -> Vec<B256> {
+    access_list
+        .iter()
+        .filter(|item| item.address == CROSS_L2_INBOX_ADDRESS)
+        .flat_map(|item| item.storage_keys.iter().copied())
+        .collect()
+}
+
+/// Returns the hashes of the messages executed by the logs, emitted by the `CrossL2Inbox`.
+pub fn executing_message_hashes<'a>(logs: impl IntoIterator<Item = &'a Log>) -> Vec<B256> {
+    logs.into_iter()
+        .filter(|log| {
+            log.address == CROSS_L2_INBOX_ADDRESS &&
+                log.topics().first() == Some(&EXECUTING_MESSAGE_TOPIC)
+        })
+        .filter_map(|log| log.topics().get(1).copied())
+        .collect()
+}
+
+/// Safety level of an initiating message, as tracked by the supervisor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SafetyLevel {
+    /// The initiating block is finalized.
+    Finalized,
+    /// The initiating block and all its dependencies are safe.
+    Safe,
+    /// The initiating block is safe, its dependencies may not be.
+    LocalSafe,
+    /// The initiating block and all its dependencies are unsafe.
+    CrossUnsafe,
+    /// The initiating block is unsafe, its dependencies may not be valid.
+    Unsafe,
+}
+
+/// Context of the executing messages, the block they are included in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutingDescriptor {
+    /// Timestamp of the executing block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+    /// How long the messages need to stay valid after `timestamp`, in seconds.
+    #[serde(with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+/// Errors of validating executing messages.
+#[derive(Debug, thiserror::Error)]
+pub enum InteropTxValidatorError {
+    /// At least one executing message is invalid.
+    #[error("invalid executing message: {0}")]
+    InvalidMessage(String),
+    /// The messages couldn't be validated, e.g. the supervisor is unreachable.
+    #[error("failed to validate executing messages: {0}")]
+    Supervisor(String),
+}
+
+impl InteropTxValidatorError {
+    /// Returns `true` if the messages were checked and found invalid, rather than failing to
+    /// check them.
+    pub const fn is_invalid(&self) -> bool {
+        matches!(self, Self::InvalidMessage(_))
+    }
+}
+
+impl PoolTransactionError for InteropTxValidatorError {
+    fn is_bad_transaction(&self) -> bool {
+        // messages may only be invalid until the initiating block is known to the supervisor
+        false
+    }
+}
+
+/// The results of checking multiple access lists, see
+/// [`InteropTxValidator::check_access_lists`].
+pub type CheckAccessListsFuture<'a> =
+    Pin<Box<dyn Future<Output = Vec<Result<(), InteropTxValidatorError>>> + Send + 'a>>;
+
+/// Validates the executing messages of transactions, usually against a supervisor via
+/// `supervisor_checkAccessList`.
+pub trait InteropTxValidator: fmt::Debug + Send + Sync + 'static {
+    /// Checks that all `CrossL2Inbox` access list entries refer to initiating messages of at
+    /// least the given safety level, that are valid in the context of the executing block.
+    fn check_access_list(
+        &self,
+        entries: Vec<B256>,
+        min_safety: SafetyLevel,
+        descriptor: ExecutingDescriptor,
+    ) -> Pin<Box<dyn Future<Output = Result<(), InteropTxValidatorError>> + Send + '_>>;
+
+    /// Checks the access lists of multiple transactions, see [`Self::check_access_list`].
+    ///
+    /// Returns the results in the same order. By default, all access lists are checked
+    /// concurrently.
+    fn check_access_lists(
+        &self,
+        access_lists: Vec<Vec<B256>>,
+        min_safety: SafetyLevel,
+        descriptor: ExecutingDescriptor,
+    ) -> CheckAccessListsFuture<'_> {
+        Box::pin(futures::future::join_all(
+            access_lists
+                .into_iter()
+                .map(|entries| self.check_access_list(entries, min_safety, descriptor)),
+        ))
+    }
+}
+
+/// Checks the executing messages of all pending pool transactions at once, for a block with the
+/// given timestamp.
+///
+/// Returns the access list entries that were found valid. Waits at most `deadline` for the
+/// validator, if it doesn't respond in time no executing messages are considered valid.
+///
+/// This blocks the current thread and must not be called from an async context, but requires the
+/// tokio runtime to be entered.
+pub fn check_pool_executing_messages<Pool: TransactionPool>(
+    validator: &dyn InteropTxValidator,
+    pool: &Pool,
+    timestamp: u64,
+    deadline: Duration,
+) -> HashSet<Vec<B256>> {
+    let access_lists = pool
+        .pending_transactions()
+        .iter()
+        .filter_map(|tx| tx.transaction.access_list().map(executing_message_entries))
+        .filter(|entries| !entries.is_empty())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    if access_lists.is_empty() {
+        return HashSet::new()
+    }
+
+    let descriptor = ExecutingDescriptor { timestamp, timeout: None };
+    let check =
+        validator.check_access_lists(access_lists.clone(), SafetyLevel::CrossUnsafe, descriptor);
+    let Ok(results) = futures::executor::block_on(tokio::time::timeout(deadline, check)) else {
+        warn!(target: "payload_builder", ?deadline, "timed out checking executing messages");
+        return HashSet::new()
+    };
+
+    access_lists
+        .into_iter()
+        .zip(results)
+        .filter_map(|(entries, res)| match res {
+            Ok(()) => Some(entries),
+            Err(err) => {
+                trace!(target: "payload_builder", %err, ?entries, "invalid executing messages");
+                None
+            }
+        })
+        .collect()
+}
+
+/// [`PayloadTransactions`] that skips transactions with executing messages that weren't found
+/// valid for the block that's being built, see [`check_pool_executing_messages`].
+///
+/// Executing messages may have become invalid since the transaction was admitted to the pool.
+#[derive(Debug)]
+pub struct InteropPayloadTransactions<Txs> {
+    valid: Option<HashSet<Vec<B256>>>,
+    inner: Txs,
+}
+
+impl<Txs> InteropPayloadTransactions<Txs> {
+    /// Creates a new [`InteropPayloadTransactions`] that only yields transactions whose executing
+    /// messages are in `valid`.
+    ///
+    /// If `valid` is `None`, interop is disabled and all transactions are yielded.
+    pub const fn new(valid: Option<HashSet<Vec<B256>>>, inner: Txs) -> Self {
+        Self { valid, inner }
+    }
+}
+
+impl<Txs> PayloadTransactions for InteropPayloadTransactions<Txs>
+where
+    Txs: PayloadTransactions<Transaction: Transaction>,
+{
+    type Transaction = Txs::Transaction;
+
+    fn next(&mut self, ctx: ()) -> Option<Recovered<Self::Transaction>> {
+        loop {
+            let tx = self.inner.next(ctx)?;
+            let Some(valid) = &self.valid else { return Some(tx) };
+            let entries = tx.access_list().map(executing_message_entries).unwrap_or_default();
+            if entries.is_empty() || valid.contains(&entries) {
+                return Some(tx)
+            }
+            trace!(target: "payload_builder", ?entries, "skipping transaction with invalid executing messages and its descendants");
+            self.inner.mark_invalid(tx.signer(), tx.nonce());
+        }
+    }
+
+    fn mark_invalid(&mut self, sender: Address, nonce: u64) {
+        self.inner.mark_invalid(sender, nonce)
+    }
+}
+
+/// A local stand-in for the supervisor, that only accepts known access list entries.
+#[derive(Debug, Default)]
+pub struct LocalInteropTxValidator {
+    valid_entries: RwLock<HashSet<B256>>,
+}
+
+impl LocalInteropTxValidator {
+    /// Marks the access list entry as referring to a valid initiating message.
+    pub fn insert(&self, entry: B256) {
+        self.valid_entries.write().insert(entry);
+    }
+
+    /// Marks the access list entry as invalid.
+    pub fn remove(&self, entry: &B256) {
+        self.valid_entries.write().remove(entry);
+    }
+}
+
+impl InteropTxValidator for LocalInteropTxValidator {
+    fn check_access_list(
+        &self,
+        entries: Vec<B256>,
+        _min_safety: SafetyLevel,
+        _descriptor: ExecutingDescriptor,
+    ) -> Pin<Box<dyn Future<Output = Result<(), InteropTxValidatorError>> + Send + '_>> {
+        let valid_entries = self.valid_entries.read();
+        let res = match entries.iter().find(|entry| !valid_entries.contains(*entry)) {
+            Some(entry) => {
+                Err(InteropTxValidatorError::InvalidMessage(format!("unknown entry {entry}")))
+            }
+            None => Ok(()),
+        };
+        Box::pin(async move { res })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip2930::AccessListItem;
+    use alloy_primitives::{Bytes, LogData};
+    use reth_transaction_pool::test_utils::{testing_pool, MockTransaction};
+
+    #[test]
+    fn extracts_executing_messages() {
+        let entry = B256::with_last_byte(1);
+        let access_list = AccessList(vec![
+            AccessListItem {
+                address: CROSS_L2_INBOX_ADDRESS,
+                storage_keys: vec![entry, B256::with_last_byte(2)],
+            },
+            AccessListItem { address: Address::ZERO, storage_keys: vec![B256::with_last_byte(3)] },
+        ]);
+        assert_eq!(executing_message_entries(&access_list), [entry, B256::with_last_byte(2)]);
+
+        let msg_hash = B256::with_last_byte(4);
+        let logs = [
+            Log {
+                address: CROSS_L2_INBOX_ADDRESS,
+                data: LogData::new_unchecked(vec![EXECUTING_MESSAGE_TOPIC, msg_hash], Bytes::new()),
+            },
+            Log {
+                address: Address::ZERO,
+                data: LogData::new_unchecked(vec![EXECUTING_MESSAGE_TOPIC, entry], Bytes::new()),
+            },
+        ];
+        assert_eq!(executing_message_hashes(&logs), [msg_hash]);
+    }
+
+    #[test]
+    fn local_validator() {
+        let validator = LocalInteropTxValidator::default();
+        let entry = B256::with_last_byte(1);
+        let descriptor = ExecutingDescriptor { timestamp: 0, timeout: None };
+        let check = |entries| {
+            futures::executor::block_on(validator.check_access_list(
+                entries,
+                SafetyLevel::CrossUnsafe,
+                descriptor,
+            ))
+        };
+
+        assert!(check(vec![entry]).is_err_and(|err| err.is_invalid()));
+        validator.insert(entry);
+        assert!(check(vec![entry]).is_ok());
+        assert!(check(vec![entry, B256::with_last_byte(2)]).is_err());
+
+        let results = futures::executor::block_on(validator.check_access_lists(
+            vec![vec![B256::with_last_byte(2)], vec![entry]],
+            SafetyLevel::CrossUnsafe,
+            descriptor,
+        ));
+        assert!(results[0].as_ref().is_err_and(|err| err.is_invalid()));
+        assert!(results[1].is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn checks_pool_executing_messages() {
+        let pool = testing_pool();
+        let validator = LocalInteropTxValidator::default();
+        let (valid, invalid) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        validator.insert(valid);
+
+        for entries in [vec![valid], vec![valid, invalid], vec![]] {
+            let mut tx = MockTransaction::eip1559();
+            tx.set_accesslist(AccessList(vec![AccessListItem {
+                address: CROSS_L2_INBOX_ADDRESS,
+                storage_keys: entries,
+            }]));
+            pool.add_external_transaction(tx).await.unwrap();
+        }
+
+        let checked = tokio::task::spawn_blocking(move || {
+            check_pool_executing_messages(&validator, &pool, 0, EXECUTING_MESSAGES_CHECK_DEADLINE)
+        })
+        .await
+        .unwrap();
+        assert_eq!(checked, HashSet::from([vec![valid]]));
+    }
+}
@@ -13,7 +13,11 @@
 
 pub mod builder;
 pub use builder::OpPayloadBuilder;
+pub mod conditional;
 pub mod error;
+pub mod flashblocks;
+pub use flashblocks::FlashblocksPublisher;
+pub mod interop;
 pub mod payload;
 pub use payload::{OpBuiltPayload, OpPayloadAttributes, OpPayloadBuilderAttributes};
 
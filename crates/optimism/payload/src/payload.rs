@@ -6,21 +6,25 @@ use alloy_eips::{
 };
 use alloy_primitives::{keccak256, Address, Bytes, B256, B64, U256};
 use alloy_rlp::Encodable;
-use alloy_rpc_types_engine::{ExecutionPayloadEnvelopeV2, ExecutionPayloadV1, PayloadId};
+use alloy_rpc_types_engine::{
+    BlobsBundleV1, ExecutionPayloadEnvelopeV2, ExecutionPayloadV1, PayloadId,
+};
 use op_alloy_consensus::{encode_holocene_extra_data, EIP1559ParamError};
+use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelopeV3;
 /// Re-export for use in downstream arguments.
 pub use op_alloy_rpc_types_engine::OpPayloadAttributes;
-use op_alloy_rpc_types_engine::{OpExecutionPayloadEnvelopeV3, OpExecutionPayloadEnvelopeV4};
 use reth_chain_state::ExecutedBlock;
 use reth_chainspec::EthereumHardforks;
 use reth_optimism_chainspec::OpChainSpec;
+use reth_optimism_forks::OpHardforks;
 use reth_optimism_primitives::{OpBlock, OpPrimitives, OpTransactionSigned};
 use reth_payload_builder::EthPayloadBuilderAttributes;
-use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
+use reth_payload_primitives::{BuiltPayload, ExecutionPayloadInputV4, PayloadBuilderAttributes};
 use reth_primitives::{transaction::WithEncoded, SealedBlock};
 use reth_rpc_types_compat::engine::payload::{
     block_to_payload_v1, block_to_payload_v3, convert_block_to_payload_field_v2,
 };
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 /// Optimism Payload Builder Attributes
@@ -225,6 +229,31 @@ impl BuiltPayload for &OpBuiltPayload {
     }
 }
 
+/// The response of `engine_getPayloadV4`.
+///
+/// Same as [`op_alloy_rpc_types_engine::OpExecutionPayloadEnvelopeV4`], but the execution payload
+/// includes the withdrawals root of the block post-Isthmus, which is the storage root of the
+/// `L2ToL1MessagePasser` instead of the root of the withdrawals of the payload.
+///
+/// See also <https://specs.optimism.io/protocol/exec-engine.html#engine_getpayloadv4>
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpExecutionPayloadEnvelopeV4 {
+    /// The execution payload, with the withdrawals root post-Isthmus.
+    pub execution_payload: ExecutionPayloadInputV4,
+    /// The expected value to be received by the fee recipient in wei.
+    pub block_value: U256,
+    /// The blobs, commitments, and proofs associated with the executed payload.
+    pub blobs_bundle: BlobsBundleV1,
+    /// Suggestion from the execution layer whether the payload should be used instead of an
+    /// externally provided one.
+    pub should_override_builder: bool,
+    /// Ecotone parent beacon block root.
+    pub parent_beacon_block_root: B256,
+    /// A list of opaque [EIP-7685](https://eips.ethereum.org/EIPS/eip-7685) requests.
+    pub execution_requests: Vec<Bytes>,
+}
+
 // V1 engine_getPayloadV1 response
 impl From<OpBuiltPayload> for ExecutionPayloadV1 {
     fn from(value: OpBuiltPayload) -> Self {
@@ -281,8 +310,16 @@ impl From<OpBuiltPayload> for OpExecutionPayloadEnvelopeV4 {
             } else {
                 B256::ZERO
             };
+        // post-Isthmus the withdrawals root isn't derived from the withdrawals of the payload
+        let withdrawals_root = chain_spec
+            .is_isthmus_active_at_timestamp(block.header().timestamp)
+            .then_some(block.header().withdrawals_root)
+            .flatten();
         Self {
-            execution_payload: block_to_payload_v3(Arc::unwrap_or_clone(block)),
+            execution_payload: ExecutionPayloadInputV4 {
+                execution_payload: block_to_payload_v3(Arc::unwrap_or_clone(block)),
+                withdrawals_root,
+            },
             block_value: fees,
             // From the engine API spec:
             //
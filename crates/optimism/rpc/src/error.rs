@@ -102,15 +102,81 @@ pub enum SequencerClientError {
     /// Thrown when serializing transaction to forward to sequencer
     #[error("invalid sequencer transaction")]
     InvalidSequencerTransaction,
+    /// The sequencer responded with a non-success HTTP status.
+    #[error("sequencer responded with status {0}")]
+    HttpStatus(reqwest::StatusCode),
+    /// The sequencer rejected the request with a JSON-RPC error.
+    #[error("sequencer rejected request: {0}")]
+    Rejected(jsonrpsee_types::ErrorObjectOwned),
+}
+
+impl SequencerClientError {
+    /// Returns `true` if the request may succeed when retried, or sent to another sequencer
+    /// endpoint.
+    ///
+    /// Requests the sequencer rejected, or that can't be serialized, are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::HttpError(err) => !err.is_builder(),
+            Self::HttpStatus(status) => {
+                status.is_server_error() || *status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            Self::InvalidSequencerTransaction | Self::Rejected(_) => false,
+        }
+    }
 }
 
 impl From<SequencerClientError> for jsonrpsee_types::error::ErrorObject<'static> {
     fn from(err: SequencerClientError) -> Self {
-        jsonrpsee_types::error::ErrorObject::owned(
-            INTERNAL_ERROR_CODE,
-            err.to_string(),
-            None::<String>,
-        )
+        match err {
+            // pass the sequencer's error through, e.g. a nonce that's too low
+            SequencerClientError::Rejected(err) => err,
+            err => jsonrpsee_types::error::ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                err.to_string(),
+                None::<String>,
+            ),
+        }
+    }
+}
+
+/// Error type when validating a transaction submitted via `eth_sendRawTransactionConditional`.
+#[derive(Debug, thiserror::Error)]
+pub enum TxConditionalErr {
+    /// The cost of checking the conditional exceeds the maximum allowed.
+    #[error("conditional cost {cost} exceeds maximum of {max}")]
+    ConditionalCostExceeded {
+        /// Cost of the conditional.
+        cost: u64,
+        /// Maximum allowed cost.
+        max: u64,
+    },
+    /// The conditional isn't met by the latest state.
+    #[error("invalid conditional: {0}")]
+    InvalidCondition(&'static str),
+    /// Failed to read the latest state.
+    #[error("internal error: {0}")]
+    Internal(String),
+}
+
+impl TxConditionalErr {
+    /// Error code for a rejected conditional, matching op-geth.
+    pub const INVALID_CONDITION_CODE: i32 = -32003;
+    /// Error code for a conditional that is too costly to check, matching op-geth.
+    pub const COST_EXCEEDED_CODE: i32 = -32005;
+}
+
+impl From<TxConditionalErr> for jsonrpsee_types::error::ErrorObject<'static> {
+    fn from(err: TxConditionalErr) -> Self {
+        match err {
+            TxConditionalErr::ConditionalCostExceeded { .. } => {
+                rpc_err(TxConditionalErr::COST_EXCEEDED_CODE, err.to_string(), None)
+            }
+            TxConditionalErr::InvalidCondition(_) => {
+                rpc_err(TxConditionalErr::INVALID_CONDITION_CODE, err.to_string(), None)
+            }
+            TxConditionalErr::Internal(_) => internal_rpc_err(err.to_string()),
+        }
     }
 }
 
@@ -119,3 +185,34 @@ impl From<BlockError> for OpEthApiError {
         Self::Eth(error.into())
     }
 }
+
+/// Error type of the [`FlashblocksConsumer`](crate::flashblocks::FlashblocksConsumer).
+#[derive(Debug, thiserror::Error)]
+pub enum FlashblocksConsumerError {
+    /// The subscription to the flashblocks failed.
+    #[error(transparent)]
+    Subscription(#[from] jsonrpsee::core::ClientError),
+    /// A flashblock couldn't be deserialized.
+    #[error(transparent)]
+    Decode(#[from] serde_json::Error),
+    /// A transaction of a flashblock couldn't be decoded, or its signer couldn't be recovered.
+    #[error("invalid flashblock transaction")]
+    InvalidTransaction,
+    /// The state of the parent block couldn't be read.
+    #[error(transparent)]
+    Provider(#[from] reth_provider::ProviderError),
+    /// The transactions of the flashblocks couldn't be executed.
+    #[error(transparent)]
+    Execution(#[from] reth_evm::execute::BlockExecutionError),
+    /// The execution of the flashblocks was aborted.
+    #[error("flashblocks execution aborted")]
+    ExecutionAborted,
+    /// The gas used by the executed transactions doesn't match the flashblock.
+    #[error("flashblock gas used mismatch: got {got}, expected {expected}")]
+    GasUsedMismatch {
+        /// Gas used by the executed transactions.
+        got: u64,
+        /// Gas used according to the flashblock.
+        expected: u64,
+    },
+}
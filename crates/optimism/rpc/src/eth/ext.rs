@@ -0,0 +1,154 @@
+//! Eth API extension for OP-Reth.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_eth::erc4337::{AccountStorage, TransactionConditional};
+use jsonrpsee_core::{async_trait, RpcResult};
+use reth_optimism_payload_builder::conditional::{
+    check_known_accounts, ConditionalBlock, KnownAccountsError, MaybeConditionalTransaction,
+};
+use reth_provider::{BlockReaderIdExt, StateProviderFactory};
+pub use reth_rpc_eth_api::L2EthApiExtServer;
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+
+use crate::{error::TxConditionalErr, SequencerClient};
+
+/// Maximum cost of checking a [`TransactionConditional`], matching op-geth.
+pub const MAX_CONDITIONAL_EXECUTION_COST: u64 = 1000;
+
+/// OP-Reth `Eth` API extensions implementation.
+///
+/// Separate from [`super::OpEthApi`] to allow to enable it conditionally.
+#[derive(Clone, Debug)]
+pub struct OpEthExtApi<Pool, Provider> {
+    /// Sequencer client, configured to forward submitted transactions to the sequencer.
+    sequencer_client: Option<SequencerClient>,
+    pool: Pool,
+    provider: Provider,
+}
+
+impl<Pool, Provider> OpEthExtApi<Pool, Provider> {
+    /// Creates a new [`OpEthExtApi`].
+    pub const fn new(
+        sequencer_client: Option<SequencerClient>,
+        pool: Pool,
+        provider: Provider,
+    ) -> Self {
+        Self { sequencer_client, pool, provider }
+    }
+}
+
+impl<Pool, Provider> OpEthExtApi<Pool, Provider>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory,
+{
+    /// Checks the conditional against the latest block and state.
+    fn validate_conditional(
+        &self,
+        condition: &TransactionConditional,
+    ) -> Result<(), TxConditionalErr> {
+        let header = self
+            .provider
+            .latest_header()
+            .map_err(|err| TxConditionalErr::Internal(err.to_string()))?
+            .ok_or_else(|| TxConditionalErr::Internal("missing latest header".to_string()))?;
+        let block = ConditionalBlock { number: header.number(), timestamp: header.timestamp() };
+        if !block.matches(condition) {
+            return Err(TxConditionalErr::InvalidCondition("block number or timestamp out of range"))
+        }
+
+        let state =
+            self.provider.latest().map_err(|err| TxConditionalErr::Internal(err.to_string()))?;
+        check_known_accounts(&state, condition).map_err(|err| match err {
+            KnownAccountsError::StorageSlotMismatch => {
+                TxConditionalErr::InvalidCondition("storage slot mismatch")
+            }
+            KnownAccountsError::StorageRootMismatch => {
+                TxConditionalErr::InvalidCondition("storage root mismatch")
+            }
+            KnownAccountsError::Provider(err) => TxConditionalErr::Internal(err.to_string()),
+        })?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Pool, Provider> L2EthApiExtServer for OpEthExtApi<Pool, Provider>
+where
+    Pool: TransactionPool<Transaction: MaybeConditionalTransaction> + 'static,
+    Provider: BlockReaderIdExt + StateProviderFactory + Clone + 'static,
+{
+    async fn send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        condition: TransactionConditional,
+    ) -> RpcResult<B256> {
+        let cost = conditional_cost(&condition);
+        if cost > MAX_CONDITIONAL_EXECUTION_COST {
+            return Err(TxConditionalErr::ConditionalCostExceeded {
+                cost,
+                max: MAX_CONDITIONAL_EXECUTION_COST,
+            }
+            .into())
+        }
+
+        let recovered = recover_raw_transaction(&bytes)?;
+        let mut tx = <Pool as TransactionPool>::Transaction::from_pooled(recovered);
+
+        self.validate_conditional(&condition)?;
+
+        // On optimism, transactions are forwarded directly to the sequencer to be included in
+        // blocks that it builds.
+        let mut rebroadcast = None;
+        if let Some(client) = self.sequencer_client.as_ref() {
+            tracing::debug!(target: "rpc::eth", hash = %tx.hash(), "forwarding raw conditional transaction to sequencer");
+            match client.forward_raw_transaction_conditional(&bytes, &condition).await {
+                Ok(()) => return Ok(*tx.hash()),
+                // keep the transaction in the local pool and send it again if the pool accepts it
+                Err(err) if client.rebroadcast_enabled() && err.is_retryable() => {
+                    tracing::debug!(target: "rpc::eth", %err, hash = %tx.hash(), "failed to forward raw conditional transaction, keeping it for rebroadcast");
+                    rebroadcast = Some((client, condition.clone()));
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        // The conditional isn't part of the transaction's encoding, so the transaction is
+        // submitted with a `Private` origin to keep it from being propagated without it.
+        tx.set_conditional(condition);
+        let hash = self
+            .pool
+            .add_transaction(TransactionOrigin::Private, tx)
+            .await
+            .map_err(EthApiError::from)?;
+
+        if let Some((client, condition)) = rebroadcast {
+            client.rebroadcast_raw_transaction_conditional(&bytes, &condition);
+        }
+
+        Ok(hash)
+    }
+}
+
+/// Returns the cost of checking the conditional, computed the same way as op-geth.
+///
+/// Each block number and timestamp bound costs one, each storage root costs one and each storage
+/// slot costs one.
+pub fn conditional_cost(condition: &TransactionConditional) -> u64 {
+    let bounds = [
+        condition.block_number_min,
+        condition.block_number_max,
+        condition.timestamp_min,
+        condition.timestamp_max,
+    ];
+    let mut cost = bounds.iter().filter(|bound| bound.is_some()).count() as u64;
+    for storage in condition.known_accounts.values() {
+        cost += match storage {
+            AccountStorage::RootHash(_) => 1,
+            AccountStorage::Slots(slots) => slots.len() as u64,
+        };
+    }
+    cost
+}
@@ -1,5 +1,6 @@
 //! OP-Reth `eth_` endpoint implementation.
 
+pub mod ext;
 pub mod receipt;
 pub mod transaction;
 
@@ -13,16 +14,19 @@ use reth_optimism_primitives::OpPrimitives;
 
 use std::{fmt, sync::Arc};
 
+use alloy_eips::BlockId;
 use alloy_primitives::U256;
 use op_alloy_network::Optimism;
+use reth_chain_state::{ExecutedBlock, MemoryOverlayStateProvider};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_evm::ConfigureEvm;
 use reth_network_api::NetworkInfo;
 use reth_node_builder::EthApiBuilderCtx;
+use reth_optimism_payload_builder::flashblocks::PreconfirmedBlockReceiver;
 use reth_provider::{
     BlockNumReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
     NodePrimitivesProvider, ProviderBlock, ProviderHeader, ProviderReceipt, ProviderTx,
-    StageCheckpointReader, StateProviderFactory,
+    StageCheckpointReader, StateProviderBox, StateProviderFactory,
 };
 use reth_rpc::eth::{core::EthApiInner, DevSigner};
 use reth_rpc_eth_api::{
@@ -30,7 +34,7 @@ use reth_rpc_eth_api::{
         AddDevSigners, EthApiSpec, EthFees, EthSigner, EthState, LoadBlock, LoadFee, LoadState,
         SpawnBlocking, Trace,
     },
-    EthApiTypes, RpcNodeCore, RpcNodeCoreExt,
+    EthApiTypes, FromEthApiError, RpcNodeCore, RpcNodeCoreExt,
 };
 use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle};
 use reth_tasks::{
@@ -95,6 +99,19 @@ where
     }
 }
 
+impl<N> OpEthApi<N>
+where
+    N: OpNodeCore,
+{
+    /// Returns the block of the latest flashblock, if flashblocks are enabled and the block
+    /// extends the canonical head.
+    pub fn preconfirmed_block(&self) -> Option<ExecutedBlock<OpPrimitives>> {
+        let block = self.inner.preconfirmed.as_ref()?.borrow().clone()?;
+        let best_hash = self.provider().chain_info().ok()?.best_hash;
+        (block.recovered_block().header().parent_hash == best_hash).then_some(block)
+    }
+}
+
 impl<N> EthApiTypes for OpEthApi<N>
 where
     Self: Send + Sync,
@@ -218,12 +235,28 @@ where
     }
 }
 
-impl<N> LoadState for OpEthApi<N> where
+impl<N> LoadState for OpEthApi<N>
+where
     N: OpNodeCore<
         Provider: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks>,
         Pool: TransactionPool,
-    >
+    >,
 {
+    /// Returns the state at the given [`BlockId`] enum.
+    ///
+    /// The pending state is the state after the latest flashblock, if there is one.
+    fn state_at_block_id(&self, at: BlockId) -> Result<StateProviderBox, Self::Error> {
+        if at.is_pending() {
+            if let Some(block) = self.preconfirmed_block() {
+                let parent = self
+                    .provider()
+                    .state_by_block_hash(block.recovered_block().header().parent_hash)
+                    .map_err(Self::Error::from_eth_err)?;
+                return Ok(MemoryOverlayStateProvider::new(parent, vec![block]).boxed())
+            }
+        }
+        self.provider().state_by_block_id(at).map_err(Self::Error::from_eth_err)
+    }
 }
 
 impl<N> EthState for OpEthApi<N>
@@ -280,6 +313,8 @@ struct OpEthApiInner<N: OpNodeCore> {
     /// Sequencer client, configured to forward submitted transactions to sequencer of given OP
     /// network.
     sequencer_client: Option<SequencerClient>,
+    /// The block of the latest flashblock, if flashblocks are enabled.
+    preconfirmed: Option<PreconfirmedBlockReceiver>,
 }
 
 impl<N: OpNodeCore> OpEthApiInner<N> {
@@ -300,12 +335,14 @@ pub struct OpEthApiBuilder {
     /// Sequencer client, configured to forward submitted transactions to sequencer of given OP
     /// network.
     sequencer_client: Option<SequencerClient>,
+    /// The block of the latest flashblock, if flashblocks are enabled.
+    preconfirmed: Option<PreconfirmedBlockReceiver>,
 }
 
 impl OpEthApiBuilder {
     /// Creates a [`OpEthApiBuilder`] instance from [`EthApiBuilderCtx`].
     pub const fn new() -> Self {
-        Self { sequencer_client: None }
+        Self { sequencer_client: None, preconfirmed: None }
     }
 
     /// With a [`SequencerClient`].
@@ -313,6 +350,16 @@ impl OpEthApiBuilder {
         self.sequencer_client = sequencer_client;
         self
     }
+
+    /// With the blocks of the flashblocks, either published by this node's payload builder or
+    /// consumed from the sequencer, `pending` queries are served from the latest flashblock.
+    pub fn with_preconfirmed_blocks(
+        mut self,
+        preconfirmed: Option<PreconfirmedBlockReceiver>,
+    ) -> Self {
+        self.preconfirmed = preconfirmed;
+        self
+    }
 }
 
 impl OpEthApiBuilder {
@@ -349,7 +396,11 @@ impl OpEthApiBuilder {
         );
 
         OpEthApi {
-            inner: Arc::new(OpEthApiInner { eth_api, sequencer_client: self.sequencer_client }),
+            inner: Arc::new(OpEthApiInner {
+                eth_api,
+                sequencer_client: self.sequencer_client,
+                preconfirmed: self.preconfirmed,
+            }),
         }
     }
 }
@@ -1,6 +1,6 @@
 //! Loads OP pending block for a RPC response.
 
-use crate::OpEthApi;
+use crate::{eth::receipt::preconfirmed_receipts, OpEthApi};
 use alloy_consensus::{
     constants::EMPTY_WITHDRAWALS, proofs::calculate_transaction_root, Eip658Value, Header,
     Transaction as _, TxReceipt, EMPTY_OMMER_ROOT_HASH,
@@ -59,6 +59,22 @@ where
         self.inner.eth_api.pending_block()
     }
 
+    /// Returns the block of the latest flashblock and its receipts, if flashblocks are enabled.
+    fn actual_pending_block(
+        &self,
+    ) -> Result<
+        Option<(
+            RecoveredBlock<ProviderBlock<Self::Provider>>,
+            Vec<ProviderReceipt<Self::Provider>>,
+        )>,
+        Self::Error,
+    > {
+        Ok(self.preconfirmed_block().and_then(|block| {
+            let receipts = preconfirmed_receipts(&block)?;
+            Some((block.recovered_block().clone(), receipts))
+        }))
+    }
+
     /// Returns the locally built pending block
     async fn local_pending_block(
         &self,
@@ -69,6 +85,10 @@ where
         )>,
         Self::Error,
     > {
+        if let Some(pending) = self.actual_pending_block()? {
+            return Ok(Some(pending))
+        }
+
         // See: <https://github.com/ethereum-optimism/op-geth/blob/f2e69450c6eec9c35d56af91389a1c47737206ca/miner/worker.go#L367-L375>
         let latest = self
             .provider()
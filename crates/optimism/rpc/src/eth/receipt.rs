@@ -1,20 +1,23 @@
 //! Loads and formats OP receipt RPC response.
 
-use alloy_consensus::transaction::TransactionMeta;
+use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::TxHash;
 use alloy_rpc_types_eth::{Log, TransactionReceipt};
 use op_alloy_consensus::{OpDepositReceipt, OpDepositReceiptWithBloom, OpReceiptEnvelope};
 use op_alloy_rpc_types::{L1BlockInfo, OpTransactionReceipt, OpTransactionReceiptFields};
+use reth_chain_state::ExecutedBlock;
 use reth_node_api::{FullNodeComponents, NodeTypes};
 use reth_optimism_chainspec::OpChainSpec;
 use reth_optimism_evm::RethL1BlockInfo;
 use reth_optimism_forks::OpHardforks;
-use reth_optimism_primitives::{OpReceipt, OpTransactionSigned};
+use reth_optimism_primitives::{OpPrimitives, OpReceipt, OpTransactionSigned};
+use reth_primitives_traits::SignedTransaction;
 use reth_provider::{ChainSpecProvider, ReceiptProvider, TransactionsProvider};
-use reth_rpc_eth_api::{helpers::LoadReceipt, FromEthApiError, RpcReceipt};
+use reth_rpc_eth_api::{helpers::LoadReceipt, FromEthApiError, RpcNodeCore, RpcReceipt};
 use reth_rpc_eth_types::{receipt::build_receipt, EthApiError};
 
-use crate::{OpEthApi, OpEthApiError};
+use crate::{eth::OpNodeCore, OpEthApi, OpEthApiError};
 
 impl<N> LoadReceipt for OpEthApi<N>
 where
@@ -55,6 +58,62 @@ where
     }
 }
 
+impl<N> OpEthApi<N>
+where
+    N: OpNodeCore<Provider: ChainSpecProvider<ChainSpec = OpChainSpec>>,
+{
+    /// Returns the receipt of a transaction that is pre-confirmed by the latest flashblock.
+    pub fn preconfirmed_receipt(
+        &self,
+        hash: TxHash,
+    ) -> Result<Option<OpTransactionReceipt>, OpEthApiError> {
+        let Some(block) = self.preconfirmed_block() else { return Ok(None) };
+        let recovered = block.recovered_block();
+        let Some((index, tx)) =
+            recovered.body().transactions.iter().enumerate().find(|(_, tx)| *tx.tx_hash() == hash)
+        else {
+            return Ok(None)
+        };
+        let Some(receipts) = preconfirmed_receipts(&block) else { return Ok(None) };
+        let Some(receipt) = receipts.get(index) else { return Ok(None) };
+
+        let meta = TransactionMeta {
+            tx_hash: hash,
+            index: index as u64,
+            block_hash: recovered.hash(),
+            block_number: recovered.number(),
+            base_fee: recovered.base_fee_per_gas(),
+            excess_blob_gas: recovered.excess_blob_gas(),
+            timestamp: recovered.timestamp(),
+        };
+        let mut l1_block_info = reth_optimism_evm::extract_l1_info(recovered.body())?;
+
+        Ok(Some(
+            OpReceiptBuilder::new(
+                &self.provider().chain_spec(),
+                tx,
+                meta,
+                receipt,
+                &receipts,
+                &mut l1_block_info,
+            )?
+            .build(),
+        ))
+    }
+}
+
+/// Returns the receipts of all transactions of the pre-confirmed block, in the order of the
+/// transactions.
+///
+/// Returns `None` if a receipt is missing, the receipts wouldn't line up with the transactions.
+pub(crate) fn preconfirmed_receipts(block: &ExecutedBlock<OpPrimitives>) -> Option<Vec<OpReceipt>> {
+    let receipts = block.execution_outcome().receipts().receipt_vec.first()?;
+    if receipts.len() != block.recovered_block().body().transactions.len() {
+        return None
+    }
+    receipts.iter().cloned().collect()
+}
+
 /// L1 fee and data gas for a non-deposit transaction, or deposit nonce and receipt version for a
 /// deposit transaction.
 #[derive(Debug, Clone)]
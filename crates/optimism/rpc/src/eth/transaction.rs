@@ -4,17 +4,21 @@ use alloy_consensus::{Signed, Transaction as _};
 use alloy_primitives::{Bytes, PrimitiveSignature as Signature, Sealable, Sealed, B256};
 use alloy_rpc_types_eth::TransactionInfo;
 use op_alloy_consensus::{OpTxEnvelope, OpTypedTransaction};
+use op_alloy_network::Optimism;
 use op_alloy_rpc_types::{OpTransactionRequest, Transaction};
 use reth_node_api::FullNodeComponents;
+use reth_optimism_chainspec::OpChainSpec;
 use reth_optimism_primitives::{OpReceipt, OpTransactionSigned};
 use reth_primitives::Recovered;
 use reth_primitives_traits::transaction::signed::SignedTransaction;
 use reth_provider::{
-    BlockReader, BlockReaderIdExt, ProviderTx, ReceiptProvider, TransactionsProvider,
+    BlockReader, BlockReaderIdExt, ChainSpecProvider, ProviderTx, ReceiptProvider,
+    TransactionsProvider,
 };
 use reth_rpc_eth_api::{
-    helpers::{EthSigner, EthTransactions, LoadTransaction, SpawnBlocking},
-    FromEthApiError, FullEthApiTypes, RpcNodeCore, RpcNodeCoreExt, TransactionCompat,
+    helpers::{EthSigner, EthTransactions, LoadReceipt, LoadTransaction, SpawnBlocking},
+    EthApiTypes, FromEthApiError, FullEthApiTypes, RpcNodeCore, RpcNodeCoreExt, RpcReceipt,
+    TransactionCompat,
 };
 use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
 use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
@@ -23,8 +27,12 @@ use crate::{eth::OpNodeCore, OpEthApi, OpEthApiError, SequencerClient};
 
 impl<N> EthTransactions for OpEthApi<N>
 where
-    Self: LoadTransaction<Provider: BlockReaderIdExt>,
-    N: OpNodeCore<Provider: BlockReader<Transaction = ProviderTx<Self::Provider>>>,
+    Self: LoadTransaction<Provider: BlockReaderIdExt>
+        + EthApiTypes<NetworkTypes = Optimism, Error = OpEthApiError>,
+    N: OpNodeCore<
+        Provider: BlockReader<Transaction = ProviderTx<Self::Provider>>
+                      + ChainSpecProvider<ChainSpec = OpChainSpec>,
+    >,
 {
     fn signers(&self) -> &parking_lot::RwLock<Vec<Box<dyn EthSigner<ProviderTx<Self::Provider>>>>> {
         self.inner.eth_api.signers()
@@ -39,11 +47,15 @@ where
 
         // On optimism, transactions are forwarded directly to the sequencer to be included in
         // blocks that it builds.
-        if let Some(client) = self.raw_tx_forwarder().as_ref() {
+        let mut rebroadcast = None;
+        if let Some(client) = self.raw_tx_forwarder() {
             tracing::debug!(target: "rpc::eth", hash = %pool_transaction.hash(), "forwarding raw transaction to sequencer");
-            let _ = client.forward_raw_transaction(&tx).await.inspect_err(|err| {
-                    tracing::debug!(target: "rpc::eth", %err, hash=% *pool_transaction.hash(), "failed to forward raw transaction");
-                });
+            if let Err(err) = client.forward_raw_transaction(&tx).await {
+                tracing::debug!(target: "rpc::eth", %err, hash=% *pool_transaction.hash(), "failed to forward raw transaction");
+                if err.is_retryable() {
+                    rebroadcast = Some(client);
+                }
+            }
         }
 
         // submit the transaction to the pool with a `Local` origin
@@ -53,8 +65,28 @@ where
             .await
             .map_err(Self::Error::from_eth_err)?;
 
+        // only transactions accepted by the pool are sent again
+        if let Some(client) = rebroadcast {
+            client.rebroadcast_raw_transaction(&tx);
+        }
+
         Ok(hash)
     }
+
+    /// Returns the receipt of the transaction, falling back to the latest flashblock if the
+    /// transaction isn't included in a block yet.
+    async fn transaction_receipt(
+        &self,
+        hash: B256,
+    ) -> Result<Option<RpcReceipt<Self::NetworkTypes>>, Self::Error>
+    where
+        Self: LoadReceipt + 'static,
+    {
+        if let Some((tx, meta, receipt)) = self.load_transaction_and_receipt(hash).await? {
+            return self.build_transaction_receipt(tx, meta, receipt).await.map(Some)
+        }
+        self.preconfirmed_receipt(hash)
+    }
 }
 
 impl<N> LoadTransaction for OpEthApi<N>
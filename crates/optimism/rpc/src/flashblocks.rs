@@ -0,0 +1,334 @@
+//! `optimism_` namespace subscription to the flashblocks published by the payload builder, and
+//! the [`FlashblocksConsumer`] of the subscription for nodes that don't build payloads.
+
+use crate::error::FlashblocksConsumerError;
+use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH};
+use alloy_eips::{
+    eip2718::Decodable2718, eip7685::EMPTY_REQUESTS_HASH, merge::BEACON_NONCE, BlockNumHash,
+};
+use alloy_primitives::{Address, U256};
+use alloy_rpc_types_engine::PayloadId;
+use futures::FutureExt;
+use jsonrpsee::{
+    core::{async_trait, SubscriptionResult},
+    proc_macros::rpc,
+    server::SubscriptionMessage,
+    ws_client::WsClientBuilder,
+    PendingSubscriptionSink, SubscriptionSink,
+};
+use reth_chain_state::ExecutedBlock;
+use reth_chainspec::EthereumHardforks;
+use reth_evm::execute::{BlockExecutorProvider, Executor};
+use reth_optimism_chainspec::OpChainSpec;
+use reth_optimism_forks::OpHardforks;
+use reth_optimism_payload_builder::{
+    flashblocks::{ExecutionPayloadBaseV1, FlashblocksPayloadV1, PreconfirmedBlockReceiver},
+    FlashblocksPublisher,
+};
+use reth_optimism_primitives::{OpBlock, OpPrimitives, OpTransactionSigned};
+use reth_primitives::BlockBody;
+use reth_primitives_traits::{proofs, RecoveredBlock, SealedBlock, SignedTransaction};
+use reth_provider::{ChainSpecProvider, ExecutionOutcome, StateProviderFactory};
+use reth_revm::database::StateProviderDatabase;
+use reth_tasks::TaskSpawner;
+use std::{sync::Arc, time::Duration};
+use tokio::sync::{
+    broadcast::{error::RecvError, Receiver},
+    watch,
+};
+use tracing::{debug, trace, warn};
+
+/// Delay before the [`FlashblocksConsumer`] subscribes again after the subscription closed.
+const FLASHBLOCKS_RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// Flashblocks subscription in the `optimism_` namespace.
+#[rpc(server, client, namespace = "optimism")]
+pub trait FlashblocksApi {
+    /// Subscribes to the flashblocks of the payloads built by this node.
+    #[subscription(
+        name = "subscribeFlashblocks" => "flashblocks",
+        unsubscribe = "unsubscribeFlashblocks",
+        item = FlashblocksPayloadV1
+    )]
+    async fn subscribe_flashblocks(&self) -> SubscriptionResult;
+}
+
+/// Streams the flashblocks of a [`FlashblocksPublisher`] to subscribers.
+#[derive(Debug)]
+pub struct OpFlashblocksApi {
+    publisher: FlashblocksPublisher,
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+impl OpFlashblocksApi {
+    /// Creates a new instance of the [`OpFlashblocksApi`].
+    pub fn new(publisher: FlashblocksPublisher, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        Self { publisher, task_spawner }
+    }
+}
+
+#[async_trait]
+impl FlashblocksApiServer for OpFlashblocksApi {
+    async fn subscribe_flashblocks(&self, pending: PendingSubscriptionSink) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        let flashblocks = self.publisher.subscribe();
+        self.task_spawner.spawn(Box::pin(pipe_flashblocks(sink, flashblocks)));
+        Ok(())
+    }
+}
+
+/// Forwards the flashblocks to the subscription until either side closes.
+///
+/// Flashblocks are deltas, a subscriber that lags behind can't apply later flashblocks, so the
+/// subscription is closed instead of skipping flashblocks.
+async fn pipe_flashblocks(
+    sink: SubscriptionSink,
+    mut flashblocks: Receiver<Arc<FlashblocksPayloadV1>>,
+) {
+    loop {
+        tokio::select! {
+            _ = sink.closed() => {
+                // connection dropped
+                break
+            },
+            flashblock = flashblocks.recv() => {
+                let flashblock = match flashblock {
+                    Ok(flashblock) => flashblock,
+                    Err(RecvError::Lagged(skipped)) => {
+                        debug!(target: "rpc::optimism", skipped, "flashblocks subscriber lagged behind");
+                        break
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                let Ok(msg) = SubscriptionMessage::from_json(&*flashblock) else { break };
+                if sink.send(msg).await.is_err() {
+                    break
+                }
+            }
+        }
+    }
+}
+
+/// Pre-confirms the pending block of a node that doesn't build payloads, e.g. an RPC node, from
+/// the flashblocks published by the sequencer.
+///
+/// Subscribes to `optimism_subscribeFlashblocks` of the sequencer and executes the transactions of
+/// the flashblocks of the payload on top of its parent. Flashblocks that arrive while a block is
+/// executed are applied at once.
+#[derive(Debug)]
+pub struct FlashblocksConsumer {
+    /// Websocket URL of the sequencer.
+    url: String,
+    /// The block of the latest flashblock.
+    preconfirmed: watch::Sender<Option<ExecutedBlock<OpPrimitives>>>,
+}
+
+impl FlashblocksConsumer {
+    /// Creates a new consumer of the flashblocks published on the websocket endpoint at `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        let (preconfirmed, _) = watch::channel(None);
+        Self { url: url.into(), preconfirmed }
+    }
+
+    /// Subscribes to the blocks of the consumed flashblocks.
+    pub fn subscribe_preconfirmed(&self) -> PreconfirmedBlockReceiver {
+        self.preconfirmed.subscribe()
+    }
+
+    /// Consumes the flashblocks of the sequencer, subscribing again whenever the subscription
+    /// closes.
+    pub async fn run<Provider, Executor>(self, provider: Provider, executor: Executor)
+    where
+        Provider:
+            StateProviderFactory + ChainSpecProvider<ChainSpec = OpChainSpec> + Clone + 'static,
+        Executor: BlockExecutorProvider<Primitives = OpPrimitives>,
+    {
+        loop {
+            match self.consume(&provider, &executor).await {
+                Ok(()) => debug!(target: "rpc::optimism", "flashblocks subscription closed"),
+                Err(err) => warn!(target: "rpc::optimism", %err, "flashblocks subscription failed"),
+            }
+            self.preconfirmed.send_replace(None);
+            tokio::time::sleep(FLASHBLOCKS_RESUBSCRIBE_DELAY).await;
+        }
+    }
+
+    /// Consumes the flashblocks until the subscription closes.
+    async fn consume<Provider, Executor>(
+        &self,
+        provider: &Provider,
+        executor: &Executor,
+    ) -> Result<(), FlashblocksConsumerError>
+    where
+        Provider:
+            StateProviderFactory + ChainSpecProvider<ChainSpec = OpChainSpec> + Clone + 'static,
+        Executor: BlockExecutorProvider<Primitives = OpPrimitives>,
+    {
+        let client = WsClientBuilder::default().build(&self.url).await?;
+        let mut flashblocks = client.subscribe_flashblocks().await?;
+        debug!(target: "rpc::optimism", "subscribed to flashblocks");
+
+        let mut pending = PendingFlashblocks::default();
+        while let Some(flashblock) = flashblocks.next().await {
+            pending.apply(flashblock?)?;
+            while let Some(Some(flashblock)) = flashblocks.next().now_or_never() {
+                pending.apply(flashblock?)?;
+            }
+
+            let Some(block) = pending.block(&provider.chain_spec()) else { continue };
+            let provider = provider.clone();
+            let executor = executor.clone();
+            let executed = tokio::task::spawn_blocking(move || {
+                execute_flashblocks(&provider, &executor, block)
+            })
+            .await
+            .map_err(|_| FlashblocksConsumerError::ExecutionAborted)?;
+            match executed {
+                Ok(executed) => {
+                    self.preconfirmed.send_replace(Some(executed));
+                }
+                Err(err) => {
+                    // e.g. the parent block isn't imported yet
+                    debug!(target: "rpc::optimism", %err, "failed to execute flashblocks");
+                    self.preconfirmed.send_replace(None);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The flashblocks of the payload that is currently built by the sequencer.
+#[derive(Debug, Default)]
+struct PendingFlashblocks {
+    /// The payload and its block environment, if the first flashblock of the payload was
+    /// received.
+    payload: Option<(PayloadId, ExecutionPayloadBaseV1)>,
+    /// Index of the next flashblock of the payload.
+    next_index: u64,
+    /// The transactions of all flashblocks of the payload, with their signers.
+    transactions: Vec<(OpTransactionSigned, Address)>,
+    /// The latest flashblock of the payload.
+    latest: Option<FlashblocksPayloadV1>,
+    /// Whether flashblocks were applied since the block was returned last.
+    changed: bool,
+}
+
+impl PendingFlashblocks {
+    /// Applies the flashblock.
+    ///
+    /// Flashblocks of a payload must be applied in order, flashblocks are skipped until the first
+    /// flashblock of the next payload if one is missed.
+    fn apply(&mut self, flashblock: FlashblocksPayloadV1) -> Result<(), FlashblocksConsumerError> {
+        if let Some(base) = flashblock.base.clone() {
+            self.payload = Some((flashblock.payload_id, base));
+            self.transactions.clear();
+            self.next_index = 0;
+        }
+        if self.payload.as_ref().is_none_or(|(id, _)| *id != flashblock.payload_id) ||
+            flashblock.index != self.next_index
+        {
+            trace!(target: "rpc::optimism", id=%flashblock.payload_id, index=flashblock.index, "skipping out of order flashblock");
+            self.payload = None;
+            self.latest = None;
+            return Ok(())
+        }
+
+        for tx in &flashblock.diff.transactions {
+            let tx = OpTransactionSigned::decode_2718(&mut tx.as_ref())
+                .map_err(|_| FlashblocksConsumerError::InvalidTransaction)?;
+            let signer =
+                tx.recover_signer().map_err(|_| FlashblocksConsumerError::InvalidTransaction)?;
+            self.transactions.push((tx, signer));
+        }
+        self.next_index += 1;
+        self.latest = Some(flashblock);
+        self.changed = true;
+
+        Ok(())
+    }
+
+    /// Returns the block of the applied flashblocks, if it changed since it was returned last.
+    fn block(&mut self, chain_spec: &OpChainSpec) -> Option<RecoveredBlock<OpBlock>> {
+        let (_, base) = self.payload.as_ref()?;
+        let latest = self.latest.as_ref()?;
+        if !std::mem::take(&mut self.changed) {
+            return None
+        }
+
+        let timestamp = base.timestamp;
+        let (transactions, senders): (Vec<_>, Vec<_>) = self.transactions.iter().cloned().unzip();
+        let is_shanghai = chain_spec.is_shanghai_active_at_timestamp(timestamp);
+        let (blob_gas_used, excess_blob_gas) =
+            if chain_spec.is_ecotone_active_at_timestamp(timestamp) {
+                (Some(0), Some(0))
+            } else {
+                (None, None)
+            };
+        let header = Header {
+            parent_hash: base.parent_hash,
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            beneficiary: base.fee_recipient,
+            state_root: latest.diff.state_root,
+            transactions_root: proofs::calculate_transaction_root(&transactions),
+            receipts_root: latest.diff.receipts_root,
+            withdrawals_root: is_shanghai.then_some(latest.diff.withdrawals_root),
+            logs_bloom: latest.diff.logs_bloom,
+            difficulty: U256::ZERO,
+            number: base.block_number,
+            gas_limit: base.gas_limit,
+            gas_used: latest.diff.gas_used,
+            timestamp,
+            extra_data: base.extra_data.clone(),
+            mix_hash: base.prev_randao,
+            nonce: BEACON_NONCE.into(),
+            base_fee_per_gas: Some(base.base_fee_per_gas.saturating_to()),
+            blob_gas_used,
+            excess_blob_gas,
+            parent_beacon_block_root: Some(base.parent_beacon_block_root),
+            requests_hash: chain_spec
+                .is_isthmus_active_at_timestamp(timestamp)
+                .then_some(EMPTY_REQUESTS_HASH),
+        };
+        let body = BlockBody {
+            transactions,
+            ommers: Vec::new(),
+            withdrawals: is_shanghai.then(|| latest.diff.withdrawals.clone().into()),
+        };
+        let block = SealedBlock::from_parts_unchecked(header, body, latest.diff.block_hash);
+
+        Some(RecoveredBlock::new_sealed(block, senders))
+    }
+}
+
+/// Executes the block of the flashblocks on top of its parent.
+fn execute_flashblocks<Provider, Executor>(
+    provider: &Provider,
+    executor: &Executor,
+    block: RecoveredBlock<OpBlock>,
+) -> Result<ExecutedBlock<OpPrimitives>, FlashblocksConsumerError>
+where
+    Provider: StateProviderFactory,
+    Executor: BlockExecutorProvider<Primitives = OpPrimitives>,
+{
+    let state = provider.state_by_block_hash(block.header().parent_hash)?;
+    let output = executor.executor(StateProviderDatabase::new(&state)).execute(&block)?;
+    if output.gas_used != block.header().gas_used {
+        return Err(FlashblocksConsumerError::GasUsedMismatch {
+            got: output.gas_used,
+            expected: block.header().gas_used,
+        })
+    }
+    trace!(target: "rpc::optimism", block=?BlockNumHash::new(block.header().number, block.hash()), transactions=block.body().transactions.len(), "executed flashblocks");
+
+    let hashed_state = state.hashed_post_state(&output.state);
+    let number = block.header().number;
+    Ok(ExecutedBlock {
+        recovered_block: Arc::new(block),
+        execution_output: Arc::new(ExecutionOutcome::from((output, number))),
+        hashed_state: Arc::new(hashed_state),
+        // the state root of the block isn't computed
+        trie: Default::default(),
+    })
+}
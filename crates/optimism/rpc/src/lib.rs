@@ -12,10 +12,21 @@
 
 pub mod error;
 pub mod eth;
+pub mod flashblocks;
 pub mod miner;
+pub mod output;
 pub mod sequencer;
+pub mod supervisor;
+pub mod trace;
 pub mod witness;
 
-pub use error::{OpEthApiError, OpInvalidTransactionError, SequencerClientError};
-pub use eth::{OpEthApi, OpReceiptBuilder};
-pub use sequencer::SequencerClient;
+pub use error::{
+    FlashblocksConsumerError, OpEthApiError, OpInvalidTransactionError, SequencerClientError,
+    TxConditionalErr,
+};
+pub use eth::{ext::OpEthExtApi, OpEthApi, OpReceiptBuilder};
+pub use flashblocks::{FlashblocksConsumer, OpFlashblocksApi};
+pub use output::OpOutputRootApi;
+pub use sequencer::{SequencerClient, SequencerClientConfig};
+pub use supervisor::SupervisorClient;
+pub use trace::OpTraceExtApi;
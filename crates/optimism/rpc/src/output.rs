@@ -0,0 +1,252 @@
+//! `optimism_` namespace RPC to prove withdrawals against an output root.
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockId;
+use alloy_primitives::{keccak256, B256};
+use alloy_rpc_types_eth::EIP1186AccountProofResponse;
+use alloy_serde::JsonStorageKey;
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+};
+use reth_optimism_consensus::isthmus::ADDRESS_L2_TO_L1_MESSAGE_PASSER;
+use reth_provider::{BlockReaderIdExt, StateProviderFactory};
+use reth_rpc_eth_types::EthApiError;
+use reth_rpc_server_types::result::internal_rpc_err;
+use reth_tasks::TaskSpawner;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Debug, sync::Arc};
+use tokio::sync::oneshot;
+
+/// Version 0 of the output root.
+pub const OUTPUT_VERSION_V0: B256 = B256::ZERO;
+
+/// Storage slot of the `sentMessages` mapping of the `L2ToL1MessagePasser`.
+const SENT_MESSAGES_SLOT: B256 = B256::ZERO;
+
+/// Computes the version 0 output root of a block, the hash of
+/// `version ++ state_root ++ message_passer_storage_root ++ block_hash`.
+pub fn output_root_v0(
+    state_root: B256,
+    message_passer_storage_root: B256,
+    block_hash: B256,
+) -> B256 {
+    let mut preimage = [0u8; 128];
+    preimage[..32].copy_from_slice(OUTPUT_VERSION_V0.as_slice());
+    preimage[32..64].copy_from_slice(state_root.as_slice());
+    preimage[64..96].copy_from_slice(message_passer_storage_root.as_slice());
+    preimage[96..].copy_from_slice(block_hash.as_slice());
+    keccak256(preimage)
+}
+
+/// Returns the storage slot of the withdrawal hash in the `sentMessages` mapping of the
+/// `L2ToL1MessagePasser`.
+pub fn sent_message_slot(withdrawal_hash: B256) -> B256 {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(withdrawal_hash.as_slice());
+    preimage[32..].copy_from_slice(SENT_MESSAGES_SLOT.as_slice());
+    keccak256(preimage)
+}
+
+/// The output root of a block with its preimage, and the proof of the `L2ToL1MessagePasser`
+/// account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputRootWithProof {
+    /// Version of the output root.
+    pub version: B256,
+    /// The output root.
+    pub output_root: B256,
+    /// State root of the block.
+    pub state_root: B256,
+    /// Storage root of the `L2ToL1MessagePasser` after the block.
+    pub message_passer_storage_root: B256,
+    /// Hash of the block.
+    pub block_hash: B256,
+    /// Number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// Account proof of the `L2ToL1MessagePasser`, with the storage proofs of the requested
+    /// withdrawals.
+    pub message_passer_proof: EIP1186AccountProofResponse,
+}
+
+/// OP specific RPC methods to prove L2 to L1 withdrawals.
+///
+/// Unlike op-node's `optimism_outputAtBlock`, these don't return the sync status of the rollup,
+/// but the proof of the `L2ToL1MessagePasser` that the output root commits to.
+#[rpc(server, namespace = "optimism")]
+pub trait OutputRootApi {
+    /// Returns the version 0 output root of the block and its preimage, together with the account
+    /// proof of the `L2ToL1MessagePasser`.
+    #[method(name = "outputRootWithProof")]
+    async fn output_root_with_proof(&self, block: BlockId) -> RpcResult<OutputRootWithProof>;
+
+    /// Returns the version 0 output root of the block and its preimage, together with the proof
+    /// of the withdrawal hash in the `sentMessages` mapping of the `L2ToL1MessagePasser`.
+    ///
+    /// Fails if the withdrawal wasn't initiated at or before the block.
+    ///
+    /// Like `eth_getProof`, blocks further than the proof window behind the tip are only proven
+    /// from recorded trie changesets.
+    #[method(name = "getWithdrawalProof")]
+    async fn withdrawal_proof(
+        &self,
+        withdrawal_hash: B256,
+        block: BlockId,
+    ) -> RpcResult<OutputRootWithProof>;
+}
+
+/// Implementation of the `optimism_` output root RPC methods.
+pub struct OpOutputRootApi<Provider> {
+    inner: Arc<OpOutputRootApiInner<Provider>>,
+}
+
+impl<Provider> OpOutputRootApi<Provider> {
+    /// Creates a new instance of the `OpOutputRootApi`.
+    ///
+    /// `proof_window` is the maximum distance to the tip of blocks that can be proven, see
+    /// `--rpc.eth-proof-window`.
+    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>, proof_window: u64) -> Self {
+        Self { inner: Arc::new(OpOutputRootApiInner { provider, task_spawner, proof_window }) }
+    }
+}
+
+impl<Provider> OpOutputRootApi<Provider>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory + Clone + 'static,
+{
+    /// Computes the output root of the block and proves the given slots of the
+    /// `L2ToL1MessagePasser` on a blocking task.
+    async fn prove_output_root(
+        &self,
+        block_id: BlockId,
+        slots: Vec<B256>,
+    ) -> RpcResult<OutputRootWithProof> {
+        let (tx, rx) = oneshot::channel();
+        let provider = self.inner.provider.clone();
+        let proof_window = self.inner.proof_window;
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(prove_output_root(&provider, block_id, slots, proof_window));
+        }));
+
+        rx.await.map_err(|err| internal_rpc_err(err.to_string()))?.map_err(Into::into)
+    }
+}
+
+fn prove_output_root<Provider>(
+    provider: &Provider,
+    block_id: BlockId,
+    slots: Vec<B256>,
+    proof_window: u64,
+) -> Result<OutputRootWithProof, EthApiError>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory,
+{
+    let header =
+        provider.sealed_header_by_id(block_id)?.ok_or(EthApiError::HeaderNotFound(block_id))?;
+    let best_number = provider.best_block_number()?;
+    let state = provider.history_by_block_hash(header.hash())?;
+    // Blocks beyond the window are only served from recorded trie changesets.
+    if best_number.saturating_sub(header.number()) > proof_window && !state.has_trie_changesets()? {
+        return Err(EthApiError::ExceedsMaxProofWindow)
+    }
+    let proof = state.proof(Default::default(), ADDRESS_L2_TO_L1_MESSAGE_PASSER, &slots)?;
+
+    let message_passer_storage_root = proof.storage_root;
+    let keys = slots.into_iter().map(JsonStorageKey::from).collect();
+    Ok(OutputRootWithProof {
+        version: OUTPUT_VERSION_V0,
+        output_root: output_root_v0(
+            header.state_root(),
+            message_passer_storage_root,
+            header.hash(),
+        ),
+        state_root: header.state_root(),
+        message_passer_storage_root,
+        block_hash: header.hash(),
+        block_number: header.number(),
+        message_passer_proof: proof.into_eip1186_response(keys),
+    })
+}
+
+#[async_trait]
+impl<Provider> OutputRootApiServer for OpOutputRootApi<Provider>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory + Clone + 'static,
+{
+    async fn output_root_with_proof(&self, block: BlockId) -> RpcResult<OutputRootWithProof> {
+        self.prove_output_root(block, Vec::new()).await
+    }
+
+    async fn withdrawal_proof(
+        &self,
+        withdrawal_hash: B256,
+        block: BlockId,
+    ) -> RpcResult<OutputRootWithProof> {
+        let output =
+            self.prove_output_root(block, vec![sent_message_slot(withdrawal_hash)]).await?;
+        if output.message_passer_proof.storage_proof.iter().all(|proof| proof.value.is_zero()) {
+            return Err(EthApiError::InvalidParams(format!(
+                "withdrawal {withdrawal_hash} not found at block {}",
+                output.block_number
+            ))
+            .into())
+        }
+        Ok(output)
+    }
+}
+
+impl<Provider> Clone for OpOutputRootApi<Provider> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<Provider> Debug for OpOutputRootApi<Provider> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpOutputRootApi").finish_non_exhaustive()
+    }
+}
+
+struct OpOutputRootApiInner<Provider> {
+    provider: Provider,
+    task_spawner: Box<dyn TaskSpawner>,
+    /// Maximum distance to the tip of blocks that are proven without trie changesets
+    proof_window: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn output_root_v0_vectors() {
+        assert_eq!(
+            output_root_v0(B256::ZERO, B256::ZERO, B256::ZERO),
+            b256!("012893657d8eb2efad4de0a91bcd0e39ad9837745dec3ea923737ea803fc8e3d")
+        );
+        assert_eq!(
+            output_root_v0(
+                B256::repeat_byte(0x11),
+                B256::repeat_byte(0x22),
+                B256::repeat_byte(0x33)
+            ),
+            b256!("d50bf2ff34ced71be0d2f0be7c2433c6b39d9c3b16c95daf1ed6f24b7578a3b2")
+        );
+    }
+
+    #[test]
+    fn sent_message_slot_vectors() {
+        // keccak256(0 . 0), the slot of the zero key of a mapping at slot 0
+        assert_eq!(
+            sent_message_slot(B256::ZERO),
+            b256!("ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5")
+        );
+        assert_eq!(
+            sent_message_slot(B256::repeat_byte(0x42)),
+            b256!("507b00885a3c7df6934c248a4d982cdeacfba80b048721e165ff61d8b002a627")
+        );
+    }
+}
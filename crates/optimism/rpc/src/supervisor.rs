@@ -0,0 +1,165 @@
+//! Client for the interop supervisor, validates executing messages.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{self, AtomicUsize},
+        Arc,
+    },
+    time::Duration,
+};
+
+use alloy_primitives::B256;
+use reqwest::Client;
+use reth_optimism_payload_builder::interop::{
+    CheckAccessListsFuture, ExecutingDescriptor, InteropTxValidator, InteropTxValidatorError,
+    SafetyLevel,
+};
+use serde_json::json;
+use tracing::debug;
+
+/// Default timeout of requests to the supervisor.
+pub const DEFAULT_SUPERVISOR_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A client to validate executing messages against the supervisor, via
+/// `supervisor_checkAccessList`.
+#[derive(Debug, Clone)]
+pub struct SupervisorClient {
+    inner: Arc<SupervisorClientInner>,
+}
+
+impl SupervisorClient {
+    /// Creates a new [`SupervisorClient`].
+    pub fn new(supervisor_endpoint: impl Into<String>) -> Self {
+        let client =
+            Client::builder().use_rustls_tls().timeout(DEFAULT_SUPERVISOR_TIMEOUT).build().unwrap();
+        Self::with_client(supervisor_endpoint, client)
+    }
+
+    /// Creates a new [`SupervisorClient`].
+    pub fn with_client(supervisor_endpoint: impl Into<String>, http_client: Client) -> Self {
+        let inner = SupervisorClientInner {
+            supervisor_endpoint: supervisor_endpoint.into(),
+            http_client,
+            id: AtomicUsize::new(0),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Returns the endpoint of the supervisor
+    pub fn endpoint(&self) -> &str {
+        &self.inner.supervisor_endpoint
+    }
+
+    /// Calls `supervisor_checkAccessList` for each of the access lists in a single batch
+    /// request, each call fails if any of its entries is invalid.
+    async fn check(
+        &self,
+        access_lists: Vec<Vec<B256>>,
+        min_safety: SafetyLevel,
+        descriptor: ExecutingDescriptor,
+    ) -> Vec<Result<(), InteropTxValidatorError>> {
+        let first_id = self.inner.id.fetch_add(access_lists.len(), atomic::Ordering::SeqCst);
+        let body = access_lists
+            .iter()
+            .enumerate()
+            .map(|(idx, entries)| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "supervisor_checkAccessList",
+                    "params": [entries, min_safety, descriptor],
+                    "id": first_id + idx
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let responses = match self.send_batch(serde_json::Value::Array(body)).await {
+            Ok(responses) => responses,
+            Err(err) => {
+                return access_lists
+                    .iter()
+                    .map(|_| Err(InteropTxValidatorError::Supervisor(err.clone())))
+                    .collect()
+            }
+        };
+
+        let mut results = access_lists
+            .iter()
+            .map(|_| Err(InteropTxValidatorError::Supervisor("missing response".to_string())))
+            .collect::<Vec<_>>();
+        for response in responses {
+            let Some(idx) = response
+                .get("id")
+                .and_then(|id| id.as_u64())
+                .and_then(|id| (id as usize).checked_sub(first_id))
+                .filter(|idx| *idx < results.len())
+            else {
+                continue
+            };
+            results[idx] = match response.get("error") {
+                Some(error) => {
+                    debug!(target: "rpc::supervisor", %error, "supervisor rejected executing messages");
+                    let message = error
+                        .get("message")
+                        .and_then(|message| message.as_str())
+                        .map_or_else(|| error.to_string(), ToString::to_string);
+                    Err(InteropTxValidatorError::InvalidMessage(message))
+                }
+                None => Ok(()),
+            };
+        }
+        results
+    }
+
+    /// Sends the batch request and returns the responses, in any order.
+    async fn send_batch(&self, body: serde_json::Value) -> Result<Vec<serde_json::Value>, String> {
+        let response = self
+            .inner
+            .http_client
+            .post(self.endpoint())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| err.to_string())?
+            .bytes()
+            .await
+            .map_err(|err| err.to_string())?;
+
+        serde_json::from_slice(&response).map_err(|err| err.to_string())
+    }
+}
+
+impl InteropTxValidator for SupervisorClient {
+    fn check_access_list(
+        &self,
+        entries: Vec<B256>,
+        min_safety: SafetyLevel,
+        descriptor: ExecutingDescriptor,
+    ) -> Pin<Box<dyn Future<Output = Result<(), InteropTxValidatorError>> + Send + '_>> {
+        Box::pin(async move {
+            self.check(vec![entries], min_safety, descriptor).await.pop().expect("one result")
+        })
+    }
+
+    fn check_access_lists(
+        &self,
+        access_lists: Vec<Vec<B256>>,
+        min_safety: SafetyLevel,
+        descriptor: ExecutingDescriptor,
+    ) -> CheckAccessListsFuture<'_> {
+        Box::pin(self.check(access_lists, min_safety, descriptor))
+    }
+}
+
+#[derive(Debug)]
+struct SupervisorClientInner {
+    /// The endpoint of the supervisor
+    supervisor_endpoint: String,
+    /// The HTTP client
+    http_client: Client,
+    /// Keeps track of unique request ids
+    id: AtomicUsize,
+}
@@ -0,0 +1,725 @@
+//! OP specific `trace_` and `debug_trace*` RPC, surfaces the balance changes of deposits and fees.
+//!
+//! Deposits mint value to their sender, and parts of the fees of regular transactions are
+//! credited to the fee vault predeploys. Neither is a call, so neither shows up in the traces of
+//! the EVM. [`OpTraceExtApi`] adds them as synthetic top level calls: the mint precedes the traces
+//! of a deposit, the fee credits follow the traces of a transaction. [`OpDebugTraceExtApi`] adds
+//! them as calls of the top level frame of the call tracer.
+
+use alloy_consensus::{BlockHeader, TxReceipt};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{address, map::HashSet, Address, Bytes, B256, U256};
+use alloy_rpc_types_eth::Index;
+use alloy_rpc_types_trace::{
+    filter::{TraceFilter, TraceFilterMatcher},
+    geth::{CallFrame, GethDebugTracingOptions, GethTrace, TraceResult},
+    parity::{
+        Action, CallAction, CallOutput, CallType, LocalizedTransactionTrace, TraceOutput,
+        TraceResults, TraceResultsWithTransactionHash, TraceType, TransactionTrace,
+    },
+};
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+};
+use op_alloy_consensus::DepositTransaction;
+use op_alloy_rpc_types::OpTransactionReceiptFields;
+use reth_chainspec::ChainSpecProvider;
+use reth_evm::execute::BlockExecutorProvider;
+use reth_optimism_chainspec::OpChainSpec;
+use reth_optimism_forks::OpHardforks;
+use reth_optimism_primitives::{OpBlock, OpReceipt};
+use reth_primitives::{NodePrimitives, RecoveredBlock};
+use reth_primitives_traits::SignedTransaction;
+use reth_provider::{BlockIdReader, BlockNumReader, BlockReader, TransactionsProvider};
+use reth_rpc::{DebugApi, TraceApi};
+use reth_rpc_api::DebugApiServer;
+use reth_rpc_eth_api::{
+    helpers::{EthTransactions, LoadReceipt, TraceExt},
+    FromEthApiError, RpcNodeCoreExt,
+};
+use reth_rpc_eth_types::EthApiError;
+use revm::L1BlockInfo;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::{eth::OpReceiptFieldsBuilder, OpEthApiError};
+
+/// The `BaseFeeVault` predeploy, receives the base fee of all transactions.
+pub const BASE_FEE_VAULT: Address = address!("4200000000000000000000000000000000000019");
+
+/// The `L1FeeVault` predeploy, receives the L1 data fee of all transactions.
+pub const L1_FEE_VAULT: Address = address!("420000000000000000000000000000000000001a");
+
+/// The `OperatorFeeVault` predeploy, receives the operator fee of all transactions since Isthmus.
+pub const OPERATOR_FEE_VAULT: Address = address!("420000000000000000000000000000000000001b");
+
+/// Caller of the synthetic call that mints the value of a deposit to its sender.
+pub const DEPOSIT_MINT_CALLER: Address = Address::ZERO;
+
+/// The OP specific balance changes of a transaction, and its OP receipt fields.
+#[derive(Debug, Clone)]
+pub struct OpTransactionTraceInfo {
+    /// Value minted to the sender of a deposit.
+    pub mint: Option<TransactionTrace>,
+    /// Fees credited to the fee vaults, by a regular transaction.
+    pub fee_credits: Vec<TransactionTrace>,
+    /// Additional OP receipt fields.
+    pub op_receipt_fields: OpTransactionReceiptFields,
+}
+
+impl OpTransactionTraceInfo {
+    /// Adds the synthetic traces to the traces of the transaction.
+    pub fn apply(&self, traces: &mut Vec<TransactionTrace>) {
+        if let Some(mint) = &self.mint {
+            traces.insert(0, mint.clone());
+        }
+        traces.extend(self.fee_credits.iter().cloned());
+    }
+
+    /// Adds the synthetic traces to the trace of the transaction, if it's a call tracer trace.
+    ///
+    /// They're added as calls of the top level frame, other tracers are left as is.
+    pub fn apply_geth(&self, trace: &mut GethTrace) {
+        let GethTrace::CallTracer(frame) = trace else { return };
+        if let Some(mint) = &self.mint {
+            frame.calls.insert(0, call_frame(mint));
+        }
+        frame.calls.extend(self.fee_credits.iter().map(call_frame));
+    }
+
+    /// Drops the synthetic traces that don't match the filter.
+    fn retain_matching(&mut self, matcher: &TraceFilterMatcher) {
+        self.mint = self.mint.take().filter(|mint| matcher.matches(mint));
+        self.fee_credits.retain(|fee| matcher.matches(fee));
+    }
+}
+
+/// Returns the [`OpTransactionTraceInfo`] of all transactions of the block.
+pub fn op_transaction_trace_infos(
+    chain_spec: &OpChainSpec,
+    block: &RecoveredBlock<OpBlock>,
+    receipts: &[OpReceipt],
+) -> Result<Vec<OpTransactionTraceInfo>, OpEthApiError> {
+    if block.body().transactions.is_empty() {
+        return Ok(Vec::new())
+    }
+
+    let mut l1_block_info = reth_optimism_evm::extract_l1_info(block.body())?;
+    let timestamp = block.header().timestamp();
+    let base_fee = block.header().base_fee_per_gas().unwrap_or_default();
+
+    let mut infos = Vec::with_capacity(receipts.len());
+    let mut cumulative_gas_used = 0;
+    for ((sender, tx), receipt) in block.transactions_with_sender().zip(receipts) {
+        let gas_used = receipt.cumulative_gas_used() - cumulative_gas_used;
+        cumulative_gas_used = receipt.cumulative_gas_used();
+
+        let mut fields = OpReceiptFieldsBuilder::new(timestamp).l1_block_info(
+            chain_spec,
+            tx,
+            &mut l1_block_info,
+        )?;
+        if let OpReceipt::Deposit(receipt) = receipt {
+            fields = fields
+                .deposit_nonce(receipt.deposit_nonce)
+                .deposit_version(receipt.deposit_receipt_version);
+        }
+
+        let (mint, fee_credits) = if tx.is_deposit() {
+            let mint = tx
+                .mint()
+                .filter(|mint| *mint > 0)
+                .map(|mint| transfer_trace(DEPOSIT_MINT_CALLER, *sender, U256::from(mint)));
+            (mint, Vec::new())
+        } else {
+            let operator_fee = if chain_spec.is_isthmus_active_at_timestamp(timestamp) {
+                operator_fee(&l1_block_info, gas_used)
+            } else {
+                U256::ZERO
+            };
+            let fee_credits = [
+                (BASE_FEE_VAULT, U256::from(gas_used) * U256::from(base_fee)),
+                (L1_FEE_VAULT, U256::from(fields.l1_fee.unwrap_or_default())),
+                (OPERATOR_FEE_VAULT, operator_fee),
+            ]
+            .into_iter()
+            .filter(|(_, fee)| !fee.is_zero())
+            .map(|(vault, fee)| transfer_trace(*sender, vault, fee))
+            .collect();
+            (None, fee_credits)
+        };
+
+        infos.push(OpTransactionTraceInfo { mint, fee_credits, op_receipt_fields: fields.build() });
+    }
+
+    Ok(infos)
+}
+
+/// Computes the operator fee of a transaction,
+/// `gas_used * operator_fee_scalar / 1e6 + operator_fee_constant`.
+fn operator_fee(l1_block_info: &L1BlockInfo, gas_used: u64) -> U256 {
+    let scalar = l1_block_info.operator_fee_scalar.unwrap_or_default();
+    let constant = l1_block_info.operator_fee_constant.unwrap_or_default();
+    (U256::from(gas_used).saturating_mul(scalar) / U256::from(1_000_000)).saturating_add(constant)
+}
+
+/// Returns a top level call that transfers `value` from `from` to `to`.
+const fn transfer_trace(from: Address, to: Address, value: U256) -> TransactionTrace {
+    TransactionTrace {
+        action: Action::Call(CallAction {
+            from,
+            call_type: CallType::Call,
+            gas: 0,
+            input: Bytes::new(),
+            to,
+            value,
+        }),
+        error: None,
+        result: Some(TraceOutput::Call(CallOutput { gas_used: 0, output: Bytes::new() })),
+        subtraces: 0,
+        trace_address: Vec::new(),
+    }
+}
+
+/// Returns the call tracer frame of a synthetic transfer.
+fn call_frame(trace: &TransactionTrace) -> CallFrame {
+    let (from, to, value) = match &trace.action {
+        Action::Call(call) => (call.from, call.to, call.value),
+        _ => unreachable!("synthetic traces are calls"),
+    };
+    CallFrame {
+        from,
+        to: Some(to),
+        value: Some(value),
+        typ: "CALL".to_string(),
+        output: Some(Bytes::new()),
+        ..Default::default()
+    }
+}
+
+/// Localizes a synthetic trace of the transaction at `index` in the block.
+fn localized_trace(
+    block: &RecoveredBlock<OpBlock>,
+    index: usize,
+    trace: TransactionTrace,
+) -> LocalizedTransactionTrace {
+    LocalizedTransactionTrace {
+        trace,
+        block_hash: Some(block.hash()),
+        block_number: Some(block.number()),
+        transaction_hash: block.body().transactions.get(index).map(|tx| *tx.tx_hash()),
+        transaction_position: Some(index as u64),
+    }
+}
+
+/// Interleaves the localized traces of the transactions of the block with their synthetic
+/// traces.
+fn with_op_traces(
+    block: &RecoveredBlock<OpBlock>,
+    infos: &[OpTransactionTraceInfo],
+    traces: Vec<LocalizedTransactionTrace>,
+) -> Vec<LocalizedTransactionTrace> {
+    let mut traces = traces.into_iter().peekable();
+    let mut all_traces = Vec::new();
+    for (index, info) in infos.iter().enumerate() {
+        all_traces.extend(info.mint.clone().map(|mint| localized_trace(block, index, mint)));
+        while let Some(trace) =
+            traces.next_if(|trace| trace.transaction_position == Some(index as u64))
+        {
+            all_traces.push(trace);
+        }
+        all_traces
+            .extend(info.fee_credits.iter().cloned().map(|fee| localized_trace(block, index, fee)));
+    }
+    all_traces.extend(traces);
+    all_traces
+}
+
+/// Trace results of a transaction, with the OP receipt fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpTraceResults {
+    /// The trace results.
+    #[serde(flatten)]
+    pub inner: TraceResults,
+    /// Additional OP receipt fields.
+    #[serde(flatten)]
+    pub op_receipt_fields: OpTransactionReceiptFields,
+}
+
+/// Trace results of a transaction of a replayed block, with the OP receipt fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpTraceResultsWithTransactionHash {
+    /// The trace results and the transaction hash.
+    #[serde(flatten)]
+    pub inner: TraceResultsWithTransactionHash,
+    /// Additional OP receipt fields.
+    #[serde(flatten)]
+    pub op_receipt_fields: OpTransactionReceiptFields,
+}
+
+/// OP specific overrides of the `trace_` namespace, which include deposit mints and fee vault
+/// credits.
+#[rpc(server, namespace = "trace")]
+pub trait TraceApiExt {
+    /// Returns traces created at given block, including the OP specific balance changes.
+    #[method(name = "block")]
+    async fn trace_block(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>>;
+
+    /// Returns traces matching given filter, including the OP specific balance changes.
+    #[method(name = "filter")]
+    async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTransactionTrace>>;
+
+    /// Returns transaction trace at given index, the OP specific balance changes included.
+    ///
+    /// Like `trace_get`, only a single index is supported.
+    #[method(name = "get")]
+    async fn trace_get(
+        &self,
+        hash: B256,
+        indices: Vec<Index>,
+    ) -> RpcResult<Option<LocalizedTransactionTrace>>;
+
+    /// Returns all traces of the given transaction, including the OP specific balance changes.
+    #[method(name = "transaction")]
+    async fn trace_transaction(
+        &self,
+        hash: B256,
+    ) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>>;
+
+    /// Replays all transactions in a block, with the OP receipt fields of each transaction.
+    #[method(name = "replayBlockTransactions")]
+    async fn replay_block_transactions(
+        &self,
+        block_id: BlockId,
+        trace_types: HashSet<TraceType>,
+    ) -> RpcResult<Option<Vec<OpTraceResultsWithTransactionHash>>>;
+
+    /// Replays a transaction, with its OP receipt fields.
+    #[method(name = "replayTransaction")]
+    async fn replay_transaction(
+        &self,
+        transaction: B256,
+        trace_types: HashSet<TraceType>,
+    ) -> RpcResult<OpTraceResults>;
+}
+
+/// OP specific overrides of the `debug_trace*` methods, which include deposit mints and fee vault
+/// credits in the traces of the call tracer.
+///
+/// `debug_traceBlock` isn't overridden, the receipts of a raw block aren't known.
+#[rpc(server, namespace = "debug")]
+pub trait DebugTraceApiExt {
+    /// Returns the traces of all transactions of the block, including the OP specific balance
+    /// changes.
+    #[method(name = "traceBlockByHash")]
+    async fn debug_trace_block_by_hash(
+        &self,
+        block: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>>;
+
+    /// Returns the traces of all transactions of the block, including the OP specific balance
+    /// changes.
+    #[method(name = "traceBlockByNumber")]
+    async fn debug_trace_block_by_number(
+        &self,
+        block: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>>;
+
+    /// Returns the trace of the transaction, including the OP specific balance changes.
+    #[method(name = "traceTransaction")]
+    async fn debug_trace_transaction(
+        &self,
+        tx_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<GethTrace>;
+}
+
+/// Returns the block with its senders and receipts.
+async fn block_and_receipts<Eth>(
+    eth_api: &Eth,
+    block_id: BlockId,
+) -> Result<Option<(Arc<RecoveredBlock<OpBlock>>, Arc<Vec<OpReceipt>>)>, Eth::Error>
+where
+    Eth: TraceExt
+        + RpcNodeCoreExt<
+            Provider: BlockReader<Block = OpBlock, Receipt = OpReceipt>
+                          + ChainSpecProvider<ChainSpec = OpChainSpec>,
+        >,
+{
+    if block_id.is_pending() {
+        let Some((block, receipts)) = eth_api.local_pending_block().await? else { return Ok(None) };
+        return Ok(Some((Arc::new(block), Arc::new(receipts))))
+    }
+
+    let Some(block_hash) =
+        eth_api.provider().block_hash_for_id(block_id).map_err(Eth::Error::from_eth_err)?
+    else {
+        return Ok(None)
+    };
+    eth_api.cache().get_block_and_receipts(block_hash).await.map_err(Eth::Error::from_eth_err)
+}
+
+/// Returns the block with the [`OpTransactionTraceInfo`] of all its transactions.
+async fn block_trace_infos<Eth>(
+    eth_api: &Eth,
+    block_id: BlockId,
+) -> RpcResult<Option<(Arc<RecoveredBlock<OpBlock>>, Vec<OpTransactionTraceInfo>)>>
+where
+    Eth: TraceExt
+        + RpcNodeCoreExt<
+            Provider: BlockReader<Block = OpBlock, Receipt = OpReceipt>
+                          + ChainSpecProvider<ChainSpec = OpChainSpec>,
+        >,
+{
+    let Some((block, receipts)) =
+        block_and_receipts(eth_api, block_id).await.map_err(Into::into)?
+    else {
+        return Ok(None)
+    };
+    let chain_spec = eth_api.provider().chain_spec();
+    let infos = op_transaction_trace_infos(&chain_spec, &block, &receipts)?;
+    Ok(Some((block, infos)))
+}
+
+/// Returns the block of the transaction with the [`OpTransactionTraceInfo`] of the transaction
+/// and its index.
+async fn transaction_trace_info<Eth>(
+    eth_api: &Eth,
+    hash: B256,
+) -> RpcResult<Option<(Arc<RecoveredBlock<OpBlock>>, usize, OpTransactionTraceInfo)>>
+where
+    Eth: TraceExt
+        + RpcNodeCoreExt<
+            Provider: BlockReader<Block = OpBlock, Receipt = OpReceipt>
+                          + ChainSpecProvider<ChainSpec = OpChainSpec>,
+        >,
+{
+    let Some((_, meta)) =
+        eth_api.provider().transaction_by_hash_with_meta(hash).map_err(EthApiError::from)?
+    else {
+        return Ok(None)
+    };
+    let Some((block, mut infos)) = block_trace_infos(eth_api, meta.block_hash.into()).await? else {
+        return Ok(None)
+    };
+    let index = meta.index as usize;
+    if index >= infos.len() {
+        return Ok(None)
+    }
+    let info = infos.swap_remove(index);
+    Ok(Some((block, index, info)))
+}
+
+/// OP specific `trace_` namespace, extends the traces of [`TraceApi`].
+pub struct OpTraceExtApi<Eth> {
+    inner: TraceApi<Eth>,
+}
+
+impl<Eth> OpTraceExtApi<Eth> {
+    /// Creates a new instance of the `OpTraceExtApi`.
+    pub const fn new(inner: TraceApi<Eth>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl<Eth> TraceApiExtServer for OpTraceExtApi<Eth>
+where
+    Eth: TraceExt
+        + LoadReceipt
+        + RpcNodeCoreExt<
+            Provider: BlockReader<Block = OpBlock, Receipt = OpReceipt>
+                          + ChainSpecProvider<ChainSpec = OpChainSpec>,
+        > + 'static,
+{
+    async fn trace_block(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>> {
+        let Some(traces) = self.inner.trace_block(block_id).await.map_err(Into::into)? else {
+            return Ok(None)
+        };
+        let Some((block, infos)) = block_trace_infos(self.inner.eth_api(), block_id).await? else {
+            return Ok(Some(traces))
+        };
+        Ok(Some(with_op_traces(&block, &infos, traces)))
+    }
+
+    async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTransactionTrace>> {
+        let matcher = filter.matcher();
+        let TraceFilter { from_block, to_block, after, count, .. } = filter.clone();
+        let start = from_block.unwrap_or(0);
+        let end = match to_block {
+            Some(to_block) => to_block,
+            None => {
+                self.inner.eth_api().provider().best_block_number().map_err(EthApiError::from)?
+            }
+        };
+
+        // the synthetic traces are paginated together with the traces of the EVM
+        let traces = self
+            .inner
+            .trace_filter(TraceFilter { after: None, count: None, ..filter })
+            .await
+            .map_err(Into::into)?;
+        let mut traces = traces.into_iter().peekable();
+
+        let mut all_traces = Vec::with_capacity(traces.len());
+        for number in start..=end {
+            let mut block_traces = Vec::new();
+            while let Some(trace) = traces.next_if(|trace| trace.block_number == Some(number)) {
+                block_traces.push(trace);
+            }
+            let Some((block, mut infos)) =
+                block_trace_infos(self.inner.eth_api(), number.into()).await?
+            else {
+                all_traces.extend(block_traces);
+                continue
+            };
+            for info in &mut infos {
+                info.retain_matching(&matcher);
+            }
+            all_traces.extend(with_op_traces(&block, &infos, block_traces));
+        }
+        all_traces.extend(traces);
+
+        let after = after.map_or(0, |after| after as usize);
+        let count = count.map_or(usize::MAX, |count| count as usize);
+        Ok(all_traces.into_iter().skip(after).take(count).collect())
+    }
+
+    async fn trace_get(
+        &self,
+        hash: B256,
+        indices: Vec<Index>,
+    ) -> RpcResult<Option<LocalizedTransactionTrace>> {
+        if indices.len() != 1 {
+            // The OG impl failed if it gets more than a single index
+            return Ok(None)
+        }
+        let index = indices[0].into();
+        Ok(self.trace_transaction(hash).await?.and_then(|traces| traces.into_iter().nth(index)))
+    }
+
+    async fn trace_transaction(
+        &self,
+        hash: B256,
+    ) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>> {
+        let Some(traces) = self.inner.trace_transaction(hash).await.map_err(Into::into)? else {
+            return Ok(None)
+        };
+        let Some((block, index, info)) = transaction_trace_info(self.inner.eth_api(), hash).await?
+        else {
+            return Ok(Some(traces))
+        };
+
+        let mut all_traces = Vec::with_capacity(traces.len() + 1 + info.fee_credits.len());
+        all_traces.extend(info.mint.map(|mint| localized_trace(&block, index, mint)));
+        all_traces.extend(traces);
+        all_traces
+            .extend(info.fee_credits.into_iter().map(|fee| localized_trace(&block, index, fee)));
+        Ok(Some(all_traces))
+    }
+
+    async fn replay_block_transactions(
+        &self,
+        block_id: BlockId,
+        trace_types: HashSet<TraceType>,
+    ) -> RpcResult<Option<Vec<OpTraceResultsWithTransactionHash>>> {
+        let Some(results) = self
+            .inner
+            .replay_block_transactions(block_id, trace_types)
+            .await
+            .map_err(Into::into)?
+        else {
+            return Ok(None)
+        };
+        let Some((_, infos)) = block_trace_infos(self.inner.eth_api(), block_id).await? else {
+            return Ok(None)
+        };
+
+        Ok(Some(
+            results
+                .into_iter()
+                .zip(infos)
+                .map(|(mut inner, info)| {
+                    info.apply(&mut inner.full_trace.trace);
+                    OpTraceResultsWithTransactionHash {
+                        inner,
+                        op_receipt_fields: info.op_receipt_fields,
+                    }
+                })
+                .collect(),
+        ))
+    }
+
+    async fn replay_transaction(
+        &self,
+        transaction: B256,
+        trace_types: HashSet<TraceType>,
+    ) -> RpcResult<OpTraceResults> {
+        let mut inner =
+            self.inner.replay_transaction(transaction, trace_types).await.map_err(Into::into)?;
+        let (_, _, info) = transaction_trace_info(self.inner.eth_api(), transaction)
+            .await?
+            .ok_or(EthApiError::TransactionNotFound)?;
+        info.apply(&mut inner.trace);
+        Ok(OpTraceResults { inner, op_receipt_fields: info.op_receipt_fields })
+    }
+}
+
+impl<Eth> Clone for OpTraceExtApi<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<Eth> std::fmt::Debug for OpTraceExtApi<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpTraceExtApi").finish_non_exhaustive()
+    }
+}
+
+/// OP specific `debug_trace*` methods, extend the traces of [`DebugApi`].
+pub struct OpDebugTraceExtApi<Eth, BlockExecutor> {
+    inner: DebugApi<Eth, BlockExecutor>,
+}
+
+impl<Eth, BlockExecutor> OpDebugTraceExtApi<Eth, BlockExecutor> {
+    /// Creates a new instance of the `OpDebugTraceExtApi`.
+    pub const fn new(inner: DebugApi<Eth, BlockExecutor>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<Eth, BlockExecutor> OpDebugTraceExtApi<Eth, BlockExecutor>
+where
+    Eth: EthTransactions
+        + TraceExt
+        + RpcNodeCoreExt<
+            Provider: BlockReader<Block = OpBlock, Receipt = OpReceipt>
+                          + ChainSpecProvider<ChainSpec = OpChainSpec>,
+        > + 'static,
+{
+    /// Adds the synthetic traces to the traces of the transactions of the block.
+    async fn with_op_traces(
+        &self,
+        block_id: BlockId,
+        mut results: Vec<TraceResult>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let Some((_, infos)) = block_trace_infos(self.inner.eth_api(), block_id).await? else {
+            return Ok(results)
+        };
+        for (result, info) in results.iter_mut().zip(infos) {
+            if let TraceResult::Success { result, .. } = result {
+                info.apply_geth(result);
+            }
+        }
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl<Eth, BlockExecutor> DebugTraceApiExtServer for OpDebugTraceExtApi<Eth, BlockExecutor>
+where
+    Eth: EthTransactions
+        + TraceExt
+        + RpcNodeCoreExt<
+            Provider: BlockReader<Block = OpBlock, Receipt = OpReceipt>
+                          + ChainSpecProvider<ChainSpec = OpChainSpec>,
+        > + 'static,
+    BlockExecutor: BlockExecutorProvider<Primitives: NodePrimitives<Block = OpBlock>>,
+{
+    async fn debug_trace_block_by_hash(
+        &self,
+        block: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let results = DebugApiServer::debug_trace_block_by_hash(&self.inner, block, opts).await?;
+        self.with_op_traces(block.into(), results).await
+    }
+
+    async fn debug_trace_block_by_number(
+        &self,
+        block: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let results = DebugApiServer::debug_trace_block_by_number(&self.inner, block, opts).await?;
+        self.with_op_traces(block.into(), results).await
+    }
+
+    async fn debug_trace_transaction(
+        &self,
+        tx_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<GethTrace> {
+        let mut trace = DebugApiServer::debug_trace_transaction(&self.inner, tx_hash, opts).await?;
+        if let Some((_, _, info)) = transaction_trace_info(self.inner.eth_api(), tx_hash).await? {
+            info.apply_geth(&mut trace);
+        }
+        Ok(trace)
+    }
+}
+
+impl<Eth, BlockExecutor> Clone for OpDebugTraceExtApi<Eth, BlockExecutor> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<Eth, BlockExecutor> std::fmt::Debug for OpDebugTraceExtApi<Eth, BlockExecutor> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpDebugTraceExtApi").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operator_fee_of_transaction() {
+        let mut l1_block_info = L1BlockInfo::default();
+        l1_block_info.operator_fee_scalar = Some(U256::from(2_000_000));
+        l1_block_info.operator_fee_constant = Some(U256::from(7));
+        assert_eq!(operator_fee(&l1_block_info, 21_000), U256::from(42_007));
+        assert_eq!(operator_fee(&L1BlockInfo::default(), 21_000), U256::ZERO);
+    }
+
+    #[test]
+    fn interleaves_synthetic_traces() {
+        let block = RecoveredBlock::new_unhashed(OpBlock::default(), Vec::new());
+        let sender = Address::with_last_byte(1);
+        let mint = transfer_trace(DEPOSIT_MINT_CALLER, sender, U256::from(1));
+        let fee = transfer_trace(sender, BASE_FEE_VAULT, U256::from(2));
+        let infos = [
+            OpTransactionTraceInfo {
+                mint: Some(mint.clone()),
+                fee_credits: Vec::new(),
+                op_receipt_fields: Default::default(),
+            },
+            OpTransactionTraceInfo {
+                mint: None,
+                fee_credits: vec![fee.clone()],
+                op_receipt_fields: Default::default(),
+            },
+        ];
+        let call = |index: u64| LocalizedTransactionTrace {
+            trace: transfer_trace(sender, Address::ZERO, U256::from(index)),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_position: Some(index),
+        };
+
+        let traces = with_op_traces(&block, &infos, vec![call(0), call(1)]);
+        let traces = traces.into_iter().map(|trace| trace.trace).collect::<Vec<_>>();
+        assert_eq!(traces, [mint, call(0).trace, call(1).trace, fee]);
+    }
+}
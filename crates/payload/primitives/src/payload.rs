@@ -2,7 +2,7 @@ use crate::{MessageValidationKind, PayloadAttributes};
 use alloc::vec::Vec;
 use alloy_eips::eip4895::Withdrawal;
 use alloy_primitives::B256;
-use alloy_rpc_types_engine::ExecutionPayload;
+use alloy_rpc_types_engine::{ExecutionPayload, ExecutionPayloadV3};
 
 /// Either an [`ExecutionPayload`] or a types that implements the [`PayloadAttributes`] trait.
 ///
@@ -81,3 +81,51 @@ where
         Self::PayloadAttributes(attributes)
     }
 }
+
+/// The execution payload of `engine_newPayloadV4`, with the withdrawals root of the block.
+///
+/// The withdrawals root is only included by L2s that don't derive it from the withdrawals of the
+/// payload, e.g. the OP stack post-Isthmus, where it is the storage root of the
+/// `L2ToL1MessagePasser`.
+///
+/// See also <https://specs.optimism.io/protocol/exec-engine.html#engine_newpayloadv4>
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionPayloadInputV4 {
+    /// The inner execution payload.
+    #[serde(flatten)]
+    pub execution_payload: ExecutionPayloadV3,
+    /// The withdrawals root of the block, if it isn't derived from the withdrawals.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals_root: Option<B256>,
+}
+
+impl From<ExecutionPayloadV3> for ExecutionPayloadInputV4 {
+    fn from(execution_payload: ExecutionPayloadV3) -> Self {
+        Self { execution_payload, withdrawals_root: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_withdrawals_root() {
+        let payload = r#"{"parentHash":"0x67ead97eb79b47a1638659942384143f36ed44275d4182799875ab5a87324055","feeRecipient":"0x0000000000000000000000000000000000000000","stateRoot":"0x0000000000000000000000000000000000000000000000000000000000000000","receiptsRoot":"0x4e3c608a9f2e129fccb91a1dae7472e78013b8e654bccc8d224ce3d63ae17006","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","prevRandao":"0x0000000000000000000000000000000000000000000000000000000000000000","blockNumber":"0x1","gasLimit":"0x1c9c380","gasUsed":"0x5208","timestamp":"0x675c7b3d","extraData":"0x","baseFeePerGas":"0x3b9aca00","blockHash":"0x7dd3ee7e4fd1fe1ec4d8bcbd5ea50b8ed8b1f45e98364b71a7a1b6e67e7af3f6","transactions":[],"withdrawals":[],"blobGasUsed":"0x0","excessBlobGas":"0x0"}"#;
+
+        // without the withdrawals root, e.g. on L1
+        let input: ExecutionPayloadInputV4 = serde_json::from_str(payload).unwrap();
+        assert_eq!(input.withdrawals_root, None);
+        assert_eq!(serde_json::to_string(&input).unwrap(), payload);
+
+        let with_root = format!(
+            r#"{},"withdrawalsRoot":"0x{}"}}"#,
+            &payload[..payload.len() - 1],
+            "11".repeat(32)
+        );
+        let input: ExecutionPayloadInputV4 = serde_json::from_str(&with_root).unwrap();
+        assert_eq!(input.withdrawals_root, Some(B256::repeat_byte(0x11)));
+        assert_eq!(serde_json::to_string(&input).unwrap(), with_root);
+    }
+}
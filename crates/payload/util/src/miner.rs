@@ -0,0 +1,80 @@
+//! Runtime settings of the block producer.
+
+use alloy_primitives::Bytes;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// Settings of the block producer that can be updated at runtime, e.g. via the `miner_` RPC
+/// namespace.
+///
+/// This type is shareable between the payload builder and the transaction pool. Settings that
+/// were never updated are `None`, in which case the startup configuration of the respective
+/// component applies. Updates take effect for the next payload job.
+#[derive(Debug, Clone, Default)]
+pub struct MinerConfig {
+    inner: Arc<RwLock<MinerConfigInner>>,
+}
+
+impl MinerConfig {
+    /// Returns the extra data to include in built blocks, if set.
+    pub fn extra_data(&self) -> Option<Bytes> {
+        self.inner.read().extra_data.clone()
+    }
+
+    /// Sets the extra data to include in built blocks.
+    pub fn set_extra_data(&self, extra_data: Bytes) {
+        self.inner.write().extra_data = Some(extra_data);
+    }
+
+    /// Returns the gas limit built blocks target, if set.
+    pub fn gas_limit(&self) -> Option<u64> {
+        self.inner.read().gas_limit
+    }
+
+    /// Sets the gas limit built blocks target.
+    pub fn set_gas_limit(&self, gas_limit: u64) {
+        self.inner.write().gas_limit = Some(gas_limit);
+    }
+
+    /// Returns the minimum priority fee of transactions accepted into the pool and built blocks,
+    /// if set.
+    pub fn minimum_priority_fee(&self) -> Option<u128> {
+        self.inner.read().minimum_priority_fee
+    }
+
+    /// Sets the minimum priority fee of transactions accepted into the pool and built blocks.
+    pub fn set_minimum_priority_fee(&self, minimum_priority_fee: u128) {
+        self.inner.write().minimum_priority_fee = Some(minimum_priority_fee);
+    }
+}
+
+#[derive(Debug, Default)]
+struct MinerConfigInner {
+    /// Block extra data
+    extra_data: Option<Bytes>,
+    /// Desired block gas limit
+    gas_limit: Option<u64>,
+    /// Minimum priority fee per gas
+    minimum_priority_fee: Option<u128>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_miner_config() {
+        let config = MinerConfig::default();
+        assert_eq!(config.extra_data(), None);
+        assert_eq!(config.gas_limit(), None);
+        assert_eq!(config.minimum_priority_fee(), None);
+
+        let shared = config.clone();
+        shared.set_extra_data(Bytes::from_static(b"reth"));
+        shared.set_gas_limit(36_000_000);
+        shared.set_minimum_priority_fee(1_000_000_000);
+        assert_eq!(config.extra_data(), Some(Bytes::from_static(b"reth")));
+        assert_eq!(config.gas_limit(), Some(36_000_000));
+        assert_eq!(config.minimum_priority_fee(), Some(1_000_000_000));
+    }
+}
@@ -8,6 +8,7 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+use alloy_primitives::B256;
 use alloy_rpc_types::engine::{
     ExecutionPayload, ExecutionPayloadSidecar, MaybeCancunPayloadFields, PayloadError,
 };
@@ -116,11 +117,29 @@ impl<ChainSpec: EthereumHardforks> ExecutionPayloadValidator<ChainSpec> {
         &self,
         payload: ExecutionPayload,
         sidecar: ExecutionPayloadSidecar,
+    ) -> Result<SealedBlock<reth_primitives::Block<T>>, PayloadError> {
+        self.ensure_well_formed_payload_with_withdrawals_root(payload, sidecar, None)
+    }
+
+    /// Same as [`Self::ensure_well_formed_payload`], but uses the given withdrawals root for the
+    /// block instead of deriving it from the withdrawals of the payload.
+    ///
+    /// This is used by L2s that commit to a different withdrawals root, e.g. the OP stack
+    /// post-Isthmus.
+    pub fn ensure_well_formed_payload_with_withdrawals_root<T: SignedTransaction>(
+        &self,
+        payload: ExecutionPayload,
+        sidecar: ExecutionPayloadSidecar,
+        withdrawals_root: Option<B256>,
     ) -> Result<SealedBlock<reth_primitives::Block<T>>, PayloadError> {
         let expected_hash = payload.block_hash();
 
         // First parse the block
-        let sealed_block = payload.try_into_block_with_sidecar(&sidecar)?.seal_slow();
+        let mut block = payload.try_into_block_with_sidecar(&sidecar)?;
+        if withdrawals_root.is_some() {
+            block.header.withdrawals_root = withdrawals_root;
+        }
+        let sealed_block = block.seal_slow();
 
         // Ensure the hash included in the payload matches the block hash
         if expected_hash != sealed_block.hash() {
@@ -0,0 +1,145 @@
+//! Sampling CPU profiler.
+
+use crate::ProfilingError;
+use std::time::Duration;
+
+/// The default sampling frequency of CPU profiles, in Hz.
+///
+/// Slightly off 100 Hz to not sample in lockstep with periodic work.
+pub const DEFAULT_CPU_PROFILE_FREQUENCY: i32 = 99;
+
+/// The maximum duration of a CPU profile collected with [`profile_cpu`].
+pub const MAX_CPU_PROFILE_DURATION: Duration = Duration::from_secs(600);
+
+#[cfg(unix)]
+mod imp {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use pprof::{protos::Message, ProfilerGuard, ProfilerGuardBuilder};
+    use std::{io::Write, sync::Mutex};
+
+    /// The running profiler, shared by all callers.
+    static PROFILER: Mutex<Option<ProfilerGuard<'static>>> = Mutex::new(None);
+
+    /// Libraries that are not sampled, unwinding from inside of them can deadlock.
+    const BLOCKLIST: [&str; 4] = ["libc", "libgcc", "pthread", "vdso"];
+
+    pub(super) fn start(frequency: i32) -> Result<(), ProfilingError> {
+        let mut profiler = PROFILER.lock().unwrap_or_else(|err| err.into_inner());
+        if profiler.is_some() {
+            return Err(ProfilingError::AlreadyRunning)
+        }
+        *profiler = Some(
+            ProfilerGuardBuilder::default().frequency(frequency).blocklist(&BLOCKLIST).build()?,
+        );
+        Ok(())
+    }
+
+    pub(super) fn stop() -> Result<Vec<u8>, ProfilingError> {
+        let guard = PROFILER
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .take()
+            .ok_or(ProfilingError::NotRunning)?;
+        let report = guard.report().build()?;
+        drop(guard);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&report.pprof()?.encode_to_vec())?;
+        Ok(encoder.finish()?)
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    use super::*;
+
+    pub(super) fn start(_frequency: i32) -> Result<(), ProfilingError> {
+        Err(ProfilingError::Unsupported("CPU profiles are only supported on unix"))
+    }
+
+    pub(super) fn stop() -> Result<Vec<u8>, ProfilingError> {
+        Err(ProfilingError::NotRunning)
+    }
+}
+
+/// Starts collecting a CPU profile, sampling all threads at the given frequency in Hz.
+///
+/// Returns an error if a profile is already being collected.
+pub fn start_cpu_profile(frequency: i32) -> Result<(), ProfilingError> {
+    imp::start(frequency)
+}
+
+/// Stops collecting the CPU profile and returns it in the gzipped pprof format.
+pub fn stop_cpu_profile() -> Result<Vec<u8>, ProfilingError> {
+    imp::stop()
+}
+
+/// Collects a CPU profile for the given duration and returns it in the gzipped pprof format.
+pub async fn profile_cpu(duration: Duration, frequency: i32) -> Result<Vec<u8>, ProfilingError> {
+    if duration > MAX_CPU_PROFILE_DURATION {
+        return Err(ProfilingError::DurationTooLong(
+            duration.as_secs(),
+            MAX_CPU_PROFILE_DURATION.as_secs(),
+        ))
+    }
+
+    start_cpu_profile(frequency)?;
+    let guard = CpuProfileGuard;
+    tokio::time::sleep(duration).await;
+    guard.stop()
+}
+
+/// Stops the CPU profile once dropped, so it doesn't keep running if [`profile_cpu`] is cancelled.
+struct CpuProfileGuard;
+
+impl CpuProfileGuard {
+    /// Stops the CPU profile and returns it.
+    fn stop(self) -> Result<Vec<u8>, ProfilingError> {
+        std::mem::forget(self);
+        stop_cpu_profile()
+    }
+}
+
+impl Drop for CpuProfileGuard {
+    fn drop(&mut self) {
+        let _ = stop_cpu_profile();
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    #[tokio::test]
+    async fn collects_cpu_profile() {
+        assert!(matches!(stop_cpu_profile(), Err(ProfilingError::NotRunning)));
+
+        start_cpu_profile(DEFAULT_CPU_PROFILE_FREQUENCY).unwrap();
+        assert!(matches!(
+            start_cpu_profile(DEFAULT_CPU_PROFILE_FREQUENCY),
+            Err(ProfilingError::AlreadyRunning)
+        ));
+        let profile = stop_cpu_profile().unwrap();
+
+        let mut decoded = Vec::new();
+        GzDecoder::new(&profile[..]).read_to_end(&mut decoded).unwrap();
+        assert!(!decoded.is_empty());
+
+        assert!(matches!(
+            profile_cpu(MAX_CPU_PROFILE_DURATION * 2, DEFAULT_CPU_PROFILE_FREQUENCY).await,
+            Err(ProfilingError::DurationTooLong(..))
+        ));
+
+        // a cancelled profile is stopped
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(10),
+            profile_cpu(Duration::from_secs(60), DEFAULT_CPU_PROFILE_FREQUENCY),
+        )
+        .await;
+        assert!(cancelled.is_err());
+        assert!(matches!(stop_cpu_profile(), Err(ProfilingError::NotRunning)));
+    }
+}
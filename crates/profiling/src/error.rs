@@ -0,0 +1,26 @@
+/// Errors of the profilers.
+#[derive(Debug, thiserror::Error)]
+pub enum ProfilingError {
+    /// A CPU profile is already being collected.
+    #[error("a CPU profile is already running")]
+    AlreadyRunning,
+    /// No CPU profile is being collected.
+    #[error("no CPU profile is running")]
+    NotRunning,
+    /// The profile duration exceeds the maximum.
+    #[error("profile duration of {0}s exceeds the maximum of {1}s")]
+    DurationTooLong(u64, u64),
+    /// The profile is not supported by this build or platform.
+    #[error("{0}")]
+    Unsupported(&'static str),
+    /// The CPU profiler failed.
+    #[cfg(unix)]
+    #[error(transparent)]
+    Pprof(#[from] pprof::Error),
+    /// jemalloc failed to dump the heap profile.
+    #[error("failed to dump heap profile: {0}")]
+    Jemalloc(String),
+    /// Reading or writing a profile failed.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
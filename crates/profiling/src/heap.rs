@@ -0,0 +1,64 @@
+//! Heap profiles dumped by jemalloc.
+
+use crate::ProfilingError;
+use std::path::Path;
+
+#[cfg(all(unix, feature = "jemalloc"))]
+mod imp {
+    use super::*;
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    pub(super) fn dump(path: &Path) -> Result<(), ProfilingError> {
+        // SAFETY: `opt.prof` is a read-only `bool` option.
+        let enabled =
+            unsafe { tikv_jemalloc_ctl::raw::read::<bool>(b"opt.prof\0") }.map_err(|_| {
+                ProfilingError::Unsupported(
+                    "heap profiles require a build with the `jemalloc-prof` feature",
+                )
+            })?;
+        if !enabled {
+            return Err(ProfilingError::Unsupported(
+                "heap profiling is disabled, enable it with `_RJEM_MALLOC_CONF=prof:true`",
+            ))
+        }
+
+        let path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+        // SAFETY: `prof.dump` takes a pointer to a nul terminated path, that outlives the call.
+        unsafe { tikv_jemalloc_ctl::raw::write(b"prof.dump\0", path.as_ptr()) }
+            .map_err(|err| ProfilingError::Jemalloc(err.to_string()))
+    }
+}
+
+#[cfg(not(all(unix, feature = "jemalloc")))]
+mod imp {
+    use super::*;
+
+    pub(super) const fn dump(_path: &Path) -> Result<(), ProfilingError> {
+        Err(ProfilingError::Unsupported("heap profiles require jemalloc"))
+    }
+}
+
+/// Writes a jemalloc heap profile to the given file.
+///
+/// The profile can be read with `jeprof`.
+pub fn write_heap_profile(path: &Path) -> Result<(), ProfilingError> {
+    imp::dump(path)
+}
+
+/// Returns a jemalloc heap profile.
+///
+/// The profile is dumped to a temporary file, which is removed afterwards.
+pub fn heap_profile() -> Result<Vec<u8>, ProfilingError> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static DUMPS: AtomicUsize = AtomicUsize::new(0);
+
+    let path = std::env::temp_dir().join(format!(
+        "reth-heap-{}-{}.prof",
+        std::process::id(),
+        DUMPS.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = write_heap_profile(&path).and_then(|()| Ok(std::fs::read(&path)?));
+    let _ = std::fs::remove_file(&path);
+    result
+}
@@ -0,0 +1,33 @@
+//! On-demand profiling of a running node.
+//!
+//! CPU profiles are sampled with [`pprof`](https://docs.rs/pprof) and encoded in the gzipped
+//! protobuf format read by `go tool pprof`. Heap profiles are dumped by jemalloc and can be read
+//! with `jeprof`.
+//!
+//! Only one CPU profile can be collected at a time, it is shared by all callers, e.g. the metrics
+//! server and the `debug` RPC namespace.
+//!
+//! ## Feature Flags
+//!
+//! - `jemalloc`: Enables heap profiles. The node must be built with the `jemalloc-prof` feature and
+//!   profiling enabled at runtime, e.g. with `_RJEM_MALLOC_CONF=prof:true`.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod cpu;
+pub use cpu::{
+    profile_cpu, start_cpu_profile, stop_cpu_profile, DEFAULT_CPU_PROFILE_FREQUENCY,
+    MAX_CPU_PROFILE_DURATION,
+};
+
+mod error;
+pub use error::ProfilingError;
+
+mod heap;
+pub use heap::{heap_profile, write_heap_profile};
@@ -1,6 +1,6 @@
 use crate::segments::{
-    AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    AccountHistory, Bodies, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory,
+    TransactionLookup, UserReceipts,
 };
 use alloy_eips::eip2718::Encodable2718;
 use reth_db::{table::Value, transaction::DbTxMut};
@@ -64,6 +64,7 @@ where
             receipts,
             account_history,
             storage_history,
+            bodies_history,
             receipts_log_filter,
         } = prune_modes;
 
@@ -89,6 +90,8 @@ where
             .segment_opt(transaction_lookup.map(TransactionLookup::new))
             // Sender recovery
             .segment_opt(sender_recovery.map(SenderRecovery::new))
+            // Bodies history
+            .segment_opt(bodies_history.map(Bodies::new))
     }
 }
 
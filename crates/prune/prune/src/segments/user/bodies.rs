@@ -0,0 +1,184 @@
+use crate::{
+    db_ext::DbTxPruneExt,
+    segments::{PruneInput, Segment},
+    PruneLimiter, PrunerError,
+};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::BlockNumber;
+use rayon::prelude::*;
+use reth_db::{table::Value, tables, transaction::DbTxMut};
+use reth_primitives_traits::NodePrimitives;
+use reth_provider::{
+    providers::StaticFileProvider, BlockReader, DBProvider, StaticFileProviderFactory,
+};
+use reth_prune_types::{
+    PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use reth_static_file_types::StaticFileSegment;
+use std::ops::RangeInclusive;
+use tracing::{instrument, trace};
+
+/// Number of transactions whose hashes are calculated at once when pruning the transaction
+/// lookup entries of expired blocks.
+const TRANSACTION_HASHES_CHUNK_SIZE: u64 = 100_000;
+
+/// Expires block bodies and receipts by deleting their static files.
+///
+/// Static files can only be deleted as a whole, so only the files whose entire block range is
+/// below the prune target are deleted. The static file holding the highest block of a segment is
+/// always kept.
+///
+/// The database entries of the expired blocks are deleted along with the files: block body
+/// indices, transaction lookup entries, senders and receipts that haven't been moved to static
+/// files. [`tables::TransactionBlocks`] is kept, so that expired transactions can still be found
+/// in era files by their number.
+#[derive(Debug)]
+pub struct Bodies {
+    mode: PruneMode,
+}
+
+impl Bodies {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for Bodies
+where
+    Provider: StaticFileProviderFactory<Primitives: NodePrimitives<Receipt: Value>>
+        + DBProvider<Tx: DbTxMut>
+        + BlockReader<Transaction: Encodable2718>,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::Bodies
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let static_file_provider = provider.static_file_provider();
+
+        // The database entries are deleted first, since the transaction hashes are read from the
+        // static files that are about to be deleted.
+        let mut pruned = 0;
+        if let Some(last_block) = static_file_provider
+            .highest_expirable_block(StaticFileSegment::Transactions, input.to_block + 1)
+        {
+            pruned += prune_database(
+                provider,
+                static_file_provider.earliest_history_height()..=last_block,
+            )?;
+        }
+
+        for segment in [StaticFileSegment::Transactions, StaticFileSegment::Receipts] {
+            pruned += expire_segment(&static_file_provider, segment, input.to_block)?;
+        }
+
+        // Files are deleted regardless of the delete limit, since it doesn't depend on the number
+        // of entries in them.
+        let earliest_block = static_file_provider.earliest_history_height();
+        Ok(SegmentOutput {
+            progress: PruneProgress::Finished,
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: earliest_block.checked_sub(1),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+/// Deletes the database entries of the blocks in the range, and returns the number of deleted
+/// entries.
+fn prune_database<Provider>(
+    provider: &Provider,
+    block_range: RangeInclusive<BlockNumber>,
+) -> Result<usize, PrunerError>
+where
+    Provider: StaticFileProviderFactory<Primitives: NodePrimitives<Receipt: Value>>
+        + DBProvider<Tx: DbTxMut>
+        + BlockReader<Transaction: Encodable2718>,
+{
+    let (Some(first_body), Some(last_body)) = (
+        provider.block_body_indices(*block_range.start())?,
+        provider.block_body_indices(*block_range.end())?,
+    ) else {
+        trace!(target: "pruner", ?block_range, "No block body indices of expired blocks to prune");
+        return Ok(0)
+    };
+    let tx_range = first_body.first_tx_num()..last_body.next_tx_num();
+
+    // All entries of the expired blocks are deleted, the limiter is only used for the interface.
+    let mut limiter = PruneLimiter::default();
+    let mut pruned = 0;
+
+    let mut chunk_start = tx_range.start;
+    while chunk_start < tx_range.end {
+        let chunk = chunk_start..tx_range.end.min(chunk_start + TRANSACTION_HASHES_CHUNK_SIZE);
+        let hashes = provider
+            .transactions_by_tx_range(chunk.clone())?
+            .into_par_iter()
+            .map(|transaction| transaction.trie_hash())
+            .collect::<Vec<_>>();
+        pruned += provider
+            .tx_ref()
+            .prune_table_with_iterator::<tables::TransactionHashNumbers>(
+                hashes,
+                &mut limiter,
+                |_| {},
+            )?
+            .0;
+        chunk_start = chunk.end;
+    }
+
+    pruned += provider
+        .tx_ref()
+        .prune_table_with_range::<tables::TransactionSenders>(
+            tx_range.clone(),
+            &mut limiter,
+            |_| false,
+            |_| {},
+        )?
+        .0;
+    pruned += provider
+        .tx_ref()
+        .prune_table_with_range::<tables::Receipts<<Provider::Primitives as NodePrimitives>::Receipt>>(
+            tx_range,
+            &mut limiter,
+            |_| false,
+            |_| {},
+        )?
+        .0;
+    pruned += provider
+        .tx_ref()
+        .prune_table_with_range::<tables::BlockBodyIndices>(
+            block_range.clone(),
+            &mut limiter,
+            |_| false,
+            |_| {},
+        )?
+        .0;
+
+    trace!(target: "pruner", ?block_range, %pruned, "Pruned database entries of expired blocks");
+    Ok(pruned)
+}
+
+/// Deletes the static files of the segment that only contain blocks up to `to_block`, and returns
+/// the number of deleted entries.
+fn expire_segment<N: NodePrimitives>(
+    static_file_provider: &StaticFileProvider<N>,
+    segment: StaticFileSegment,
+    to_block: u64,
+) -> Result<usize, PrunerError> {
+    let deleted = static_file_provider.delete_segment_below_block(segment, to_block + 1)?;
+    let pruned = deleted.iter().filter_map(|header| header.tx_len()).sum::<u64>() as usize;
+    trace!(target: "pruner", ?segment, files = %deleted.len(), %pruned, "Expired static files");
+    Ok(pruned)
+}
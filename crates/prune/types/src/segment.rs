@@ -39,6 +39,8 @@ pub enum PruneSegment {
     Headers,
     /// Prune segment responsible for the `Transactions` table.
     Transactions,
+    /// Prune segment responsible for expiring block bodies and receipts from static files.
+    Bodies,
 }
 
 impl PruneSegment {
@@ -49,7 +51,7 @@ impl PruneSegment {
                 0
             }
             Self::Receipts if purpose.is_static_file() => 0,
-            Self::ContractLogs | Self::AccountHistory | Self::StorageHistory => {
+            Self::ContractLogs | Self::AccountHistory | Self::StorageHistory | Self::Bodies => {
                 MINIMUM_PRUNING_DISTANCE
             }
             Self::Receipts => MINIMUM_PRUNING_DISTANCE,
@@ -13,4 +13,12 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Replaces the log filter overrides of the node with the given directives, e.g.
+    /// `info,engine::tree=debug`.
+    ///
+    /// The directives are applied on top of the filters configured at startup, an empty filter
+    /// restores them.
+    #[method(name = "setLogFilter")]
+    async fn reth_set_log_filter(&self, filter: String) -> RpcResult<()>;
 }
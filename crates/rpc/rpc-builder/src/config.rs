@@ -10,7 +10,8 @@ use tower::layer::util::Identity;
 use tracing::{debug, warn};
 
 use crate::{
-    auth::AuthServerConfig, error::RpcError, IpcServerBuilder, RpcModuleConfig, RpcServerConfig,
+    auth::AuthServerConfig, error::RpcError, rate_limiter::RpcRateLimitConfig,
+    response_cache::RpcResponseCache, IpcServerBuilder, RpcModuleConfig, RpcServerConfig,
     TransportRpcModuleConfig,
 };
 
@@ -55,6 +56,15 @@ pub trait RethRpcServerConfig {
     /// Returns the default ipc server builder
     fn ipc_server_builder(&self) -> IpcServerBuilder<Identity, Identity>;
 
+    /// Returns the per-client rate limit configuration for http and ws, if enabled.
+    fn rate_limit_config(&self) -> Option<RpcRateLimitConfig>;
+
+    /// Creates the cache of responses against finalized blocks for http and ws, if enabled.
+    ///
+    /// Note: the cache is not part of the [`RpcServerConfig`] returned by
+    /// [`Self::rpc_server_config`], because it must be kept in sync with the chain.
+    fn response_cache(&self) -> Option<RpcResponseCache>;
+
     /// Creates the [`RpcServerConfig`] from cli args.
     fn rpc_server_config(&self) -> RpcServerConfig;
 
@@ -173,8 +183,27 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_connections(self.rpc_max_connections.get())
     }
 
+    fn rate_limit_config(&self) -> Option<RpcRateLimitConfig> {
+        let units_per_second = self.rpc_rate_limit?;
+        Some(
+            RpcRateLimitConfig::new(units_per_second)
+                .with_burst(self.rpc_rate_limit_burst.unwrap_or(units_per_second))
+                .with_client_key(self.rpc_rate_limit_key.clone())
+                .with_trusted_proxies(self.rpc_rate_limit_trusted_proxies.iter().copied())
+                .with_method_weights(self.rpc_rate_limit_method_weights.iter().cloned())
+                .with_client_quotas(self.rpc_rate_limit_client_quotas.iter().cloned()),
+        )
+    }
+
+    fn response_cache(&self) -> Option<RpcResponseCache> {
+        let megabytes = self.rpc_response_cache_size?;
+        Some(RpcResponseCache::new(megabytes.saturating_mul(1024 * 1024) as usize))
+    }
+
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_rate_limit(self.rate_limit_config());
 
         if self.http_api.is_some() && !self.http {
             warn!(
@@ -237,7 +266,7 @@ mod tests {
     use clap::{Args, Parser};
     use reth_node_core::args::RpcServerArgs;
     use reth_rpc_eth_types::RPC_DEFAULT_GAS_CAP;
-    use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+    use reth_rpc_server_types::{constants, RethRpcModule, RpcClientKey, RpcModuleSelection};
     use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 
     use crate::config::RethRpcServerConfig;
@@ -381,4 +410,47 @@ mod tests {
         assert_eq!(config.max_blocks_per_filter, Some(100));
         assert_eq!(config.max_logs_per_response, Some(200));
     }
+
+    #[test]
+    fn test_rate_limit_config() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.rate_limit_config().is_none());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.rate-limit",
+            "100",
+            "--rpc.rate-limit.key",
+            "header:x-api-key",
+            "--rpc.rate-limit.method-weight",
+            "trace_=20,eth_call=5",
+            "--rpc.rate-limit.client-quota",
+            "partner=1000",
+            "--rpc.rate-limit.trusted-proxies",
+            "127.0.0.1",
+        ])
+        .args;
+        let config = args.rate_limit_config().unwrap();
+        assert_eq!(config.units_per_second, 100);
+        assert_eq!(config.burst, 100);
+        assert_eq!(config.client_key, RpcClientKey::Header("x-api-key".to_string()));
+        assert_eq!(config.method_weight("trace_block"), 20);
+        assert_eq!(config.method_weight("eth_call"), 5);
+        assert_eq!(config.method_weight("debug_traceCall"), 10);
+        assert_eq!(config.client_quotas.get("partner"), Some(&1000));
+        assert!(config.trusted_proxies.contains(&Ipv4Addr::LOCALHOST.into()));
+    }
+
+    #[test]
+    fn test_response_cache() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.response_cache().is_none());
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.response-cache-size", "64"])
+                .args;
+        let cache = args.response_cache().unwrap();
+        assert_eq!(cache.max_bytes(), 64 * 1024 * 1024);
+        assert!(cache.is_empty());
+    }
 }
@@ -0,0 +1,100 @@
+//! Serves the http and ws servers on a TCP listener.
+
+use http::{Request as HttpRequest, Response as HttpResponse};
+use hyper::body::{Bytes, Incoming};
+use jsonrpsee::{
+    core::BoxError,
+    server::{
+        serve_with_graceful_shutdown, stop_channel, HttpBody, Methods, ServerBuilder, ServerHandle,
+        TowerService, TowerServiceBuilder,
+    },
+};
+use std::{io, net::SocketAddr};
+use tokio::net::{TcpListener, ToSocketAddrs};
+use tower::Service;
+use tracing::debug;
+
+/// The remote address of the connection a request was received on.
+///
+/// Inserted into the extensions of every request served by a [`RpcServer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RemoteAddr(pub(crate) SocketAddr);
+
+/// A bound http or ws server.
+///
+/// Unlike [`jsonrpsee::server::Server`], it makes the [`RemoteAddr`] of every connection
+/// available to the http middleware.
+#[derive(Debug)]
+pub(crate) struct RpcServer<RpcMiddleware, HttpMiddleware> {
+    listener: TcpListener,
+    builder: TowerServiceBuilder<RpcMiddleware, HttpMiddleware>,
+}
+
+impl<RpcMiddleware, HttpMiddleware> RpcServer<RpcMiddleware, HttpMiddleware> {
+    /// Binds the server configured by the builder to the given address.
+    pub(crate) async fn bind(
+        builder: ServerBuilder<HttpMiddleware, RpcMiddleware>,
+        addr: impl ToSocketAddrs,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        Ok(Self { listener, builder: builder.to_service_builder() })
+    }
+
+    /// Returns the address the server is bound to.
+    pub(crate) fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Starts accepting connections and serving the given methods on them.
+    pub(crate) fn start<B>(self, methods: impl Into<Methods>) -> ServerHandle
+    where
+        RpcMiddleware: Send + 'static,
+        HttpMiddleware: Send + 'static,
+        TowerServiceBuilder<RpcMiddleware, HttpMiddleware>: Clone,
+        TowerService<RpcMiddleware, HttpMiddleware>: Service<HttpRequest<HttpBody>, Response = HttpResponse<B>, Error = BoxError>
+            + Clone
+            + Send
+            + 'static,
+        <TowerService<RpcMiddleware, HttpMiddleware> as Service<HttpRequest<HttpBody>>>::Future:
+            Send,
+        B: http_body::Body<Data = Bytes> + Send + 'static,
+        B::Error: Into<BoxError>,
+    {
+        let Self { listener, builder } = self;
+        let methods = methods.into();
+        let (stop_handle, server_handle) = stop_channel();
+
+        tokio::spawn(async move {
+            loop {
+                let (socket, remote_addr) = tokio::select! {
+                    res = listener.accept() => match res {
+                        Ok(conn) => conn,
+                        Err(err) => {
+                            debug!(target: "rpc", %err, "Failed to accept connection");
+                            continue
+                        }
+                    },
+                    _ = stop_handle.clone().shutdown() => break,
+                };
+                if let Err(err) = socket.set_nodelay(true) {
+                    debug!(target: "rpc", %err, "Failed to set NODELAY on socket");
+                }
+
+                let mut service = builder.clone().build(methods.clone(), stop_handle.clone());
+                let service = tower::service_fn(move |req: HttpRequest<Incoming>| {
+                    let mut req = req.map(HttpBody::new);
+                    req.extensions_mut().insert(RemoteAddr(remote_addr));
+                    service.call(req)
+                });
+                let stopped = stop_handle.clone().shutdown();
+                tokio::spawn(async move {
+                    if let Err(err) = serve_with_graceful_shutdown(socket, service, stopped).await {
+                        debug!(target: "rpc", %err, %remote_addr, "Failed to serve connection");
+                    }
+                });
+            }
+        });
+
+        server_handle
+    }
+}
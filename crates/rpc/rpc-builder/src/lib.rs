@@ -215,7 +215,14 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use crate::{auth::AuthRpcModule, error::WsHttpSamePortError, metrics::RpcRequestMetrics};
+use crate::{
+    auth::AuthRpcModule,
+    connection::RpcServer,
+    error::WsHttpSamePortError,
+    metrics::RpcRequestMetrics,
+    rate_limiter::{RpcClientIdLayer, RpcRateLimitConfig, RpcRateLimitLayer},
+    response_cache::{RpcResponseCache, RpcResponseCacheLayer},
+};
 use error::{ConflictingModules, RpcError, ServerKind};
 use eth::DynEthApiBuilder;
 use http::{header::AUTHORIZATION, HeaderMap};
@@ -248,6 +255,7 @@ use reth_rpc_eth_api::{
 };
 use reth_rpc_eth_types::{EthConfig, EthStateCache, EthSubscriptionIdProvider};
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
+use reth_rpc_server_types::RpcClientKey;
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
 use serde::{Deserialize, Serialize};
@@ -270,6 +278,9 @@ pub mod auth;
 /// RPC server utilities.
 pub mod config;
 
+/// Http and ws server connections.
+mod connection;
+
 /// Cors utilities.
 mod cors;
 
@@ -287,6 +298,9 @@ pub use metrics::{MeteredRequestFuture, RpcRequestMetricsService};
 // Rpc rate limiter
 pub mod rate_limiter;
 
+// Rpc response cache
+pub mod response_cache;
+
 /// Convenience function for starting a server in one step.
 #[allow(clippy::too_many_arguments)]
 pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi, BlockExecutor>(
@@ -1535,6 +1549,8 @@ where
                         .into_rpc()
                         .into(),
                         RethRpcModule::Miner => MinerApi::default().into_rpc().into(),
+                        // only served by OP nodes, which install their own methods
+                        RethRpcModule::Optimism => RpcModule::new(()).into(),
                     })
                     .clone()
             })
@@ -1573,6 +1589,10 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Per-client rate limiting for http and ws
+    rate_limit: Option<RpcRateLimitConfig>,
+    /// Cache of responses against finalized blocks for http and ws
+    response_cache: Option<RpcResponseCache>,
     /// Configurable RPC middleware
     rpc_middleware: RpcServiceBuilder<RpcMiddleware>,
 }
@@ -1592,6 +1612,8 @@ impl Default for RpcServerConfig<Identity> {
             ipc_server_config: None,
             ipc_endpoint: None,
             jwt_secret: None,
+            rate_limit: None,
+            response_cache: None,
             rpc_middleware: RpcServiceBuilder::new(),
         }
     }
@@ -1655,6 +1677,8 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ipc_server_config: self.ipc_server_config,
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
+            rate_limit: self.rate_limit,
+            response_cache: self.response_cache,
             rpc_middleware,
         }
     }
@@ -1728,6 +1752,24 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures per-client rate limiting for the http and ws servers.
+    ///
+    /// Calls that exceed the quota of their client are rejected with
+    /// [`RATE_LIMITED_CODE`](rate_limiter::RATE_LIMITED_CODE).
+    pub fn with_rate_limit(mut self, rate_limit: Option<RpcRateLimitConfig>) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Configures the cache of responses against finalized blocks for the http and ws servers.
+    ///
+    /// The cache must be kept in sync with the chain by the
+    /// [`maintain_response_cache_task`](response_cache::maintain_response_cache_task).
+    pub fn with_response_cache(mut self, response_cache: Option<RpcResponseCache>) -> Self {
+        self.response_cache = response_cache;
+        self
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be launched on [`RpcServerConfig::start`].
@@ -1762,6 +1804,20 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
+    /// Creates the [`RpcClientIdLayer`] if rate limiting is configured
+    fn maybe_client_id_layer(
+        rate_limit: Option<&RpcRateLimitConfig>,
+        jwt_secret: Option<JwtSecret>,
+    ) -> Result<Option<RpcClientIdLayer>, RpcError> {
+        let Some(config) = rate_limit else { return Ok(None) };
+        if matches!(config.client_key, RpcClientKey::JwtClaim(_)) && jwt_secret.is_none() {
+            return Err(RpcError::Custom(
+                "rate limiting by JWT claim requires a JWT secret".to_string(),
+            ))
+        }
+        Ok(Some(RpcClientIdLayer::new(config, jwt_secret)))
+    }
+
     /// Returns a [`CompressionLayer`] that adds compression support (gzip, deflate, brotli, zstd)
     /// based on the client's `Accept-Encoding` header
     fn maybe_compression_layer() -> Option<CompressionLayer> {
@@ -1793,6 +1849,12 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             constants::DEFAULT_WS_RPC_PORT,
         )));
 
+        // Shared by http and ws, so that a client has the same quota on both.
+        let rate_limit = RpcRateLimitLayer::new(self.rate_limit.clone());
+        let client_id_layer =
+            Self::maybe_client_id_layer(self.rate_limit.as_ref(), self.jwt_secret)?;
+        let response_cache = RpcResponseCacheLayer::new(self.response_cache.clone());
+
         let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
         let ipc_path =
             self.ipc_endpoint.clone().unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
@@ -1828,28 +1890,31 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             modules.config.ensure_ws_http_identical()?;
 
             if let Some(builder) = self.http_server_config {
-                let server = builder
-                    .set_http_middleware(
-                        tower::ServiceBuilder::new()
-                            .option_layer(Self::maybe_cors_layer(cors)?)
-                            .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
-                            .option_layer(Self::maybe_compression_layer()),
-                    )
-                    .set_rpc_middleware(
-                        self.rpc_middleware.clone().layer(
-                            modules
-                                .http
-                                .as_ref()
-                                .or(modules.ws.as_ref())
-                                .map(RpcRequestMetrics::same_port)
-                                .unwrap_or_default(),
+                let server = RpcServer::bind(
+                    builder
+                        .set_http_middleware(
+                            tower::ServiceBuilder::new()
+                                .option_layer(Self::maybe_cors_layer(cors)?)
+                                .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                                .option_layer(client_id_layer.clone())
+                                .option_layer(Self::maybe_compression_layer()),
+                        )
+                        .set_rpc_middleware(
+                            self.rpc_middleware.clone().layer(
+                                modules
+                                    .http
+                                    .as_ref()
+                                    .or(modules.ws.as_ref())
+                                    .map(RpcRequestMetrics::same_port)
+                                    .unwrap_or_default()
+                                    .with_rate_limit(rate_limit.clone())
+                                    .with_response_cache(response_cache.clone()),
+                            ),
                         ),
-                    )
-                    .build(http_socket_addr)
-                    .await
-                    .map_err(|err| {
-                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                    })?;
+                    http_socket_addr,
+                )
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr)))?;
                 let addr = server.local_addr().map_err(|err| {
                     RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
                 })?;
@@ -1876,21 +1941,30 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         let mut http_server = None;
 
         if let Some(builder) = self.ws_server_config {
-            let server = builder
-                .ws_only()
-                .set_http_middleware(
-                    tower::ServiceBuilder::new()
-                        .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
-                )
-                .set_rpc_middleware(
-                    self.rpc_middleware
-                        .clone()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default()),
-                )
-                .build(ws_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+            let server = RpcServer::bind(
+                builder
+                    .ws_only()
+                    .set_http_middleware(
+                        tower::ServiceBuilder::new()
+                            .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
+                            .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                            .option_layer(client_id_layer.clone()),
+                    )
+                    .set_rpc_middleware(
+                        self.rpc_middleware.clone().layer(
+                            modules
+                                .ws
+                                .as_ref()
+                                .map(RpcRequestMetrics::ws)
+                                .unwrap_or_default()
+                                .with_rate_limit(rate_limit.clone())
+                                .with_response_cache(response_cache.clone()),
+                        ),
+                    ),
+                ws_socket_addr,
+            )
+            .await
+            .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
 
             let addr = server
                 .local_addr()
@@ -1901,22 +1975,31 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         }
 
         if let Some(builder) = self.http_server_config {
-            let server = builder
-                .http_only()
-                .set_http_middleware(
-                    tower::ServiceBuilder::new()
-                        .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
-                        .option_layer(Self::maybe_compression_layer()),
-                )
-                .set_rpc_middleware(
-                    self.rpc_middleware.clone().layer(
-                        modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
+            let server = RpcServer::bind(
+                builder
+                    .http_only()
+                    .set_http_middleware(
+                        tower::ServiceBuilder::new()
+                            .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
+                            .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                            .option_layer(client_id_layer.clone())
+                            .option_layer(Self::maybe_compression_layer()),
+                    )
+                    .set_rpc_middleware(
+                        self.rpc_middleware.clone().layer(
+                            modules
+                                .http
+                                .as_ref()
+                                .map(RpcRequestMetrics::http)
+                                .unwrap_or_default()
+                                .with_rate_limit(rate_limit)
+                                .with_response_cache(response_cache),
+                        ),
                     ),
-                )
-                .build(http_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
+                http_socket_addr,
+            )
+            .await
+            .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
             let local_addr = server
                 .local_addr()
                 .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
@@ -2166,6 +2249,27 @@ impl TransportRpcModules {
         Ok(())
     }
 
+    /// Replace the given [`Methods`] in all configured transport modules if the given
+    /// [`RethRpcModule`] is configured for the transport.
+    pub fn replace_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        if self.module_config().contains_http(&module) {
+            self.replace_http(other.clone())?;
+        }
+        if self.module_config().contains_ws(&module) {
+            self.replace_ws(other.clone())?;
+        }
+        if self.module_config().contains_ipc(&module) {
+            self.replace_ipc(other)?;
+        }
+
+        Ok(())
+    }
+
     /// Merge the given [Methods] in the configured http methods.
     ///
     /// Fails if any of the methods in other is present already.
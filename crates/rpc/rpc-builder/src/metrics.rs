@@ -1,3 +1,7 @@
+use crate::{
+    rate_limiter::{RateLimitedRequestFuture, RpcRateLimitLayer, RpcRateLimitService},
+    response_cache::{ResponseCacheFuture, RpcResponseCacheLayer, RpcResponseCacheService},
+};
 use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse, RpcModule};
 use reth_metrics::{
     metrics::{Counter, Histogram},
@@ -20,9 +24,14 @@ use tower::Layer;
 ///   for WS and IPC)
 /// - Request metrics: metrics for each RPC method (e.g. number of calls started, time taken to
 ///   process a call)
+///
+/// Calls are rate limited by the configured [`RpcRateLimitLayer`], so that rejected calls can be
+/// told apart from failed ones, and then served by the [`RpcResponseCacheLayer`].
 #[derive(Default, Debug, Clone)]
 pub(crate) struct RpcRequestMetrics {
     inner: Arc<RpcServerMetricsInner>,
+    rate_limit: RpcRateLimitLayer,
+    response_cache: RpcResponseCacheLayer,
 }
 
 impl RpcRequestMetrics {
@@ -37,9 +46,23 @@ impl RpcRequestMetrics {
                     })
                     .collect(),
             }),
+            rate_limit: RpcRateLimitLayer::default(),
+            response_cache: RpcResponseCacheLayer::default(),
         }
     }
 
+    /// Rate limits the calls with the given layer.
+    pub(crate) fn with_rate_limit(mut self, rate_limit: RpcRateLimitLayer) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Serves the calls from the given response cache.
+    pub(crate) fn with_response_cache(mut self, response_cache: RpcResponseCacheLayer) -> Self {
+        self.response_cache = response_cache;
+        self
+    }
+
     /// Creates a new instance of the metrics layer for HTTP.
     pub(crate) fn http(module: &RpcModule<()>) -> Self {
         Self::new(module, RpcTransport::Http)
@@ -68,7 +91,10 @@ impl<S> Layer<S> for RpcRequestMetrics {
     type Service = RpcRequestMetricsService<S>;
 
     fn layer(&self, inner: S) -> Self::Service {
-        RpcRequestMetricsService::new(inner, self.clone())
+        RpcRequestMetricsService::new(
+            self.rate_limit.layer(self.response_cache.layer(inner)),
+            self.clone(),
+        )
     }
 }
 
@@ -88,12 +114,15 @@ struct RpcServerMetricsInner {
 pub struct RpcRequestMetricsService<S> {
     /// The metrics collector for RPC requests
     metrics: RpcRequestMetrics,
-    /// The inner service being wrapped
-    inner: S,
+    /// The rate limited and cached inner service being wrapped
+    inner: RpcRateLimitService<RpcResponseCacheService<S>>,
 }
 
 impl<S> RpcRequestMetricsService<S> {
-    pub(crate) fn new(service: S, metrics: RpcRequestMetrics) -> Self {
+    pub(crate) fn new(
+        service: RpcRateLimitService<RpcResponseCacheService<S>>,
+        metrics: RpcRequestMetrics,
+    ) -> Self {
         // this instance is kept alive for the duration of the connection
         metrics.inner.connection_metrics.connections_opened_total.increment(1);
         Self { inner: service, metrics }
@@ -104,7 +133,7 @@ impl<'a, S> RpcServiceT<'a> for RpcRequestMetricsService<S>
 where
     S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
 {
-    type Future = MeteredRequestFuture<S::Future>;
+    type Future = MeteredRequestFuture<RateLimitedRequestFuture<ResponseCacheFuture<S::Future>>>;
 
     fn call(&self, req: Request<'a>) -> Self::Future {
         self.metrics.inner.connection_metrics.requests_started_total.increment(1);
@@ -112,8 +141,10 @@ where
         if let Some((_, call_metrics)) = &call_metrics {
             call_metrics.started_total.increment(1);
         }
+        let fut = self.inner.call(req);
         MeteredRequestFuture {
-            fut: self.inner.call(req),
+            rate_limited: fut.is_rejected(),
+            fut,
             started_at: Instant::now(),
             metrics: self.metrics.clone(),
             method: call_metrics.map(|(method, _)| *method),
@@ -139,6 +170,8 @@ pub struct MeteredRequestFuture<F> {
     metrics: RpcRequestMetrics,
     /// the method name if known
     method: Option<&'static str>,
+    /// whether the call was rejected by the rate limiter
+    rate_limited: bool,
 }
 
 impl<F> std::fmt::Debug for MeteredRequestFuture<F> {
@@ -161,6 +194,11 @@ impl<F: Future<Output = MethodResponse>> Future for MeteredRequestFuture<F> {
             this.metrics.inner.connection_metrics.requests_finished_total.increment(1);
             this.metrics.inner.connection_metrics.request_time_seconds.record(elapsed);
 
+            let rate_limited = *this.rate_limited;
+            if rate_limited {
+                this.metrics.inner.connection_metrics.requests_rate_limited_total.increment(1);
+            }
+
             // update call metrics
             if let Some(call_metrics) =
                 this.method.and_then(|method| this.metrics.inner.call_metrics.get(method))
@@ -168,6 +206,8 @@ impl<F: Future<Output = MethodResponse>> Future for MeteredRequestFuture<F> {
                 call_metrics.time_seconds.record(elapsed);
                 if resp.is_success() {
                     call_metrics.successful_total.increment(1);
+                } else if rate_limited {
+                    call_metrics.rate_limited_total.increment(1);
                 } else {
                     call_metrics.failed_total.increment(1);
                 }
@@ -214,6 +254,8 @@ struct RpcServerConnectionMetrics {
     requests_started_total: Counter,
     /// The number of requests finished
     requests_finished_total: Counter,
+    /// The number of requests rejected by the rate limiter
+    requests_rate_limited_total: Counter,
     /// Response for a single request/response pair
     request_time_seconds: Histogram,
 }
@@ -228,6 +270,8 @@ struct RpcServerCallMetrics {
     successful_total: Counter,
     /// The number of failed calls
     failed_total: Counter,
+    /// The number of calls rejected by the rate limiter
+    rate_limited_total: Counter,
     /// Response for a single call
     time_seconds: Histogram,
 }
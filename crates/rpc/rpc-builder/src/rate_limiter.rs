@@ -1,15 +1,37 @@
-//! [`jsonrpsee`] helper layer for rate limiting certain methods.
+//! [`jsonrpsee`] helper layers for rate limiting certain methods and clients.
 
-use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+use crate::connection::RemoteAddr;
+use http::{HeaderMap, Request as HttpRequest};
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, Request},
+    MethodResponse,
+};
+use parking_lot::Mutex;
+use reth_rpc_layer::JwtSecret;
+use reth_rpc_server_types::{RpcClientKey, RpcClientQuota, RpcMethodWeight};
 use std::{
+    collections::{HashMap, HashSet},
     future::Future,
+    net::{IpAddr, SocketAddr},
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio_util::sync::PollSemaphore;
-use tower::Layer;
+use tower::{Layer, Service};
+
+/// Error code of a call rejected by the [`RpcRateLimitLayer`].
+pub const RATE_LIMITED_CODE: i32 = -32005;
+
+/// Default cost weights of expensive methods and namespaces.
+pub const DEFAULT_METHOD_WEIGHTS: [(&str, u64); 3] =
+    [("debug_", 10), ("trace_", 10), ("eth_getLogs", 10)];
+
+/// Maximum number of client buckets kept before idle buckets are evicted.
+const MAX_TRACKED_CLIENTS: usize = 100_000;
 
 /// Rate limiter for the RPC server.
 ///
@@ -114,3 +136,524 @@ impl<F: Future<Output = MethodResponse>> Future for RateLimitingRequestFuture<F>
         res
     }
 }
+
+/// Configuration of the per-client [`RpcRateLimitLayer`].
+///
+/// Every client owns a token bucket that is refilled at [`Self::units_per_second`] up to
+/// [`Self::burst`] units. Every call consumes the weight of its method, which is `1` unless
+/// configured otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcRateLimitConfig {
+    /// Units refilled per second to the bucket of every client.
+    pub units_per_second: u64,
+    /// Maximum number of units a bucket can hold.
+    pub burst: u64,
+    /// How clients are identified.
+    pub client_key: RpcClientKey,
+    /// Proxies whose `X-Forwarded-For` and `X-Real-IP` headers are trusted to identify clients
+    /// by [`RpcClientKey::Ip`].
+    pub trusted_proxies: HashSet<IpAddr>,
+    /// Cost weights by method name or namespace prefix (e.g. `trace_`).
+    pub method_weights: HashMap<String, u64>,
+    /// Units refilled per second for individual clients, overriding
+    /// [`Self::units_per_second`].
+    pub client_quotas: HashMap<String, u64>,
+}
+
+impl RpcRateLimitConfig {
+    /// Creates a new config with the given refill rate, a burst of the same size and the
+    /// [`DEFAULT_METHOD_WEIGHTS`].
+    pub fn new(units_per_second: u64) -> Self {
+        Self {
+            units_per_second,
+            burst: units_per_second,
+            client_key: RpcClientKey::default(),
+            trusted_proxies: HashSet::default(),
+            method_weights: DEFAULT_METHOD_WEIGHTS
+                .into_iter()
+                .map(|(method, weight)| (method.to_string(), weight))
+                .collect(),
+            client_quotas: HashMap::default(),
+        }
+    }
+
+    /// Sets the maximum number of units a bucket can hold.
+    pub const fn with_burst(mut self, burst: u64) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    /// Sets how clients are identified.
+    pub fn with_client_key(mut self, client_key: RpcClientKey) -> Self {
+        self.client_key = client_key;
+        self
+    }
+
+    /// Sets the proxies whose forwarding headers are trusted.
+    pub fn with_trusted_proxies(mut self, proxies: impl IntoIterator<Item = IpAddr>) -> Self {
+        self.trusted_proxies.extend(proxies);
+        self
+    }
+
+    /// Sets the weights of the given methods or namespaces.
+    pub fn with_method_weights(
+        mut self,
+        weights: impl IntoIterator<Item = RpcMethodWeight>,
+    ) -> Self {
+        self.method_weights.extend(weights.into_iter().map(|w| (w.method, w.weight)));
+        self
+    }
+
+    /// Sets the quotas of the given clients.
+    pub fn with_client_quotas(mut self, quotas: impl IntoIterator<Item = RpcClientQuota>) -> Self {
+        self.client_quotas.extend(quotas.into_iter().map(|q| (q.client, q.units_per_second)));
+        self
+    }
+
+    /// Returns the weight of the given method.
+    ///
+    /// An exact match takes precedence over the namespace of the method.
+    pub fn method_weight(&self, method: &str) -> u64 {
+        if let Some(weight) = self.method_weights.get(method) {
+            return *weight
+        }
+        method
+            .find('_')
+            .and_then(|idx| self.method_weights.get(&method[..=idx]))
+            .copied()
+            .unwrap_or(1)
+    }
+
+    /// Returns the refill rate of the given client.
+    fn units_per_second(&self, client: &str) -> u64 {
+        self.client_quotas.get(client).copied().unwrap_or(self.units_per_second)
+    }
+}
+
+/// Identifier of the client that sent a request, as determined by the [`RpcClientIdLayer`].
+///
+/// Inserted into the request extensions, which are propagated to every call of the request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RpcClientId(pub String);
+
+/// HTTP layer that identifies the client of a request for the [`RpcRateLimitLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcClientIdLayer {
+    inner: Arc<RpcClientIdLayerInner>,
+}
+
+#[derive(Debug)]
+struct RpcClientIdLayerInner {
+    /// How clients are identified
+    key: RpcClientKey,
+    /// Proxies whose forwarding headers are trusted
+    trusted_proxies: HashSet<IpAddr>,
+    /// Secret that JWT bearer tokens must be signed with
+    jwt_secret: Option<JwtSecret>,
+}
+
+impl RpcClientIdLayer {
+    /// Creates a new layer that identifies clients as configured.
+    ///
+    /// Clients can only be identified by a JWT claim if a JWT secret is given.
+    pub fn new(config: &RpcRateLimitConfig, jwt_secret: Option<JwtSecret>) -> Self {
+        Self {
+            inner: Arc::new(RpcClientIdLayerInner {
+                key: config.client_key.clone(),
+                trusted_proxies: config.trusted_proxies.clone(),
+                jwt_secret,
+            }),
+        }
+    }
+
+    /// Identifies the client of a request received from the given remote address.
+    pub fn client_id(
+        &self,
+        remote: Option<SocketAddr>,
+        headers: &HeaderMap,
+    ) -> Option<RpcClientId> {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let id = match &self.inner.key {
+            RpcClientKey::Ip => self.client_ip(remote?.ip(), headers).map(|ip| ip.to_string()),
+            RpcClientKey::Header(name) => header(name).map(ToString::to_string),
+            RpcClientKey::JwtClaim(claim) => header(http::header::AUTHORIZATION.as_str())
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .and_then(|token| jwt_claim(token.trim(), claim, self.inner.jwt_secret.as_ref()?)),
+        };
+        id.filter(|id| !id.is_empty()).map(RpcClientId)
+    }
+
+    /// Returns the IP address of the client that connected from `remote`.
+    ///
+    /// Requests from trusted proxies are attributed to the last address in `X-Forwarded-For` that
+    /// is not a trusted proxy itself, or to the `X-Real-IP` header.
+    fn client_ip(&self, remote: IpAddr, headers: &HeaderMap) -> Option<IpAddr> {
+        let trusted_proxies = &self.inner.trusted_proxies;
+        if !trusted_proxies.contains(&remote) {
+            return Some(remote)
+        }
+
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let forwarded_for = header("x-forwarded-for")
+            .into_iter()
+            .flat_map(|value| value.rsplit(','))
+            .map(|ip| ip.trim().parse::<IpAddr>().ok());
+        for ip in forwarded_for {
+            let Some(ip) = ip else { return Some(remote) };
+            if !trusted_proxies.contains(&ip) {
+                return Some(ip)
+            }
+        }
+        header("x-real-ip").and_then(|ip| ip.trim().parse().ok()).or(Some(remote))
+    }
+}
+
+/// Reads the given claim of a JWT signed with the given secret.
+///
+/// Note: this doesn't validate the `iat` claim, this is done by the
+/// [`AuthLayer`](reth_rpc_layer::AuthLayer).
+fn jwt_claim(token: &str, claim: &str, secret: &JwtSecret) -> Option<String> {
+    let mut validation = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS256);
+    validation.validate_exp = false;
+    validation.required_spec_claims.clear();
+    let data = jsonwebtoken::decode::<serde_json::Map<String, serde_json::Value>>(
+        token,
+        &jsonwebtoken::DecodingKey::from_secret(secret.as_bytes()),
+        &validation,
+    )
+    .ok()?;
+    match data.claims.get(claim)? {
+        serde_json::Value::String(value) => Some(value.clone()),
+        value => Some(value.to_string()),
+    }
+}
+
+impl<S> Layer<S> for RpcClientIdLayer {
+    type Service = RpcClientIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcClientIdService { layer: self.clone(), inner }
+    }
+}
+
+/// HTTP service that inserts the [`RpcClientId`] into the request extensions.
+#[derive(Debug, Clone)]
+pub struct RpcClientIdService<S> {
+    layer: RpcClientIdLayer,
+    inner: S,
+}
+
+impl<S, B> Service<HttpRequest<B>> for RpcClientIdService<S>
+where
+    S: Service<HttpRequest<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest<B>) -> Self::Future {
+        let remote = req.extensions().get::<RemoteAddr>().map(|addr| addr.0);
+        if let Some(id) = self.layer.client_id(remote, req.headers()) {
+            req.extensions_mut().insert(id);
+        }
+        self.inner.call(req)
+    }
+}
+
+/// Per-client token bucket rate limiter for the RPC server.
+///
+/// Calls of clients that exhausted their quota are rejected with [`RATE_LIMITED_CODE`] and a
+/// `retryAfterMs` hint. Requests without an [`RpcClientId`] share a single bucket.
+///
+/// This layer is a no-op if it was created without a config.
+#[derive(Debug, Clone, Default)]
+pub struct RpcRateLimitLayer {
+    inner: Option<Arc<RpcRateLimitLayerInner>>,
+}
+
+impl RpcRateLimitLayer {
+    /// Creates a new rate limit layer, if a config is given.
+    pub fn new(config: Option<RpcRateLimitConfig>) -> Self {
+        Self {
+            inner: config.map(|config| {
+                Arc::new(RpcRateLimitLayerInner { config, buckets: Default::default() })
+            }),
+        }
+    }
+}
+
+impl<S> Layer<S> for RpcRateLimitLayer {
+    type Service = RpcRateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcRateLimitService { rate_limiter: self.clone(), inner }
+    }
+}
+
+#[derive(Debug)]
+struct RpcRateLimitLayerInner {
+    /// The rate limit configuration
+    config: RpcRateLimitConfig,
+    /// Token buckets by client
+    buckets: Mutex<HashMap<Option<RpcClientId>, TokenBucket>>,
+}
+
+impl RpcRateLimitLayerInner {
+    /// Takes the weight of the method from the bucket of the client.
+    ///
+    /// Returns the time until the call can be retried if the bucket has insufficient units.
+    fn try_acquire(&self, client: Option<&RpcClientId>, method: &str) -> Result<(), Duration> {
+        let now = Instant::now();
+        let rate = self.config.units_per_second(client.map(|id| id.0.as_str()).unwrap_or_default());
+        // A call can never cost more than a full bucket.
+        let cost = self.config.method_weight(method).min(self.config.burst);
+
+        let mut buckets = self.buckets.lock();
+        if buckets.len() >= MAX_TRACKED_CLIENTS {
+            // Buckets that were refilled completely are equivalent to new ones.
+            let burst = self.config.burst;
+            buckets.retain(|_, bucket| !bucket.is_full(now, rate, burst));
+        }
+        buckets
+            .entry(client.cloned())
+            .or_insert_with(|| TokenBucket::new(self.config.burst, now))
+            .try_take(cost, now, rate, self.config.burst)
+    }
+}
+
+/// Token bucket of a single client.
+#[derive(Debug)]
+struct TokenBucket {
+    /// Available units
+    units: f64,
+    /// Last time the bucket was refilled
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    const fn new(burst: u64, now: Instant) -> Self {
+        Self { units: burst as f64, refilled_at: now }
+    }
+
+    fn refilled(&self, now: Instant, rate: u64, burst: u64) -> f64 {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        (self.units + elapsed * rate as f64).min(burst as f64)
+    }
+
+    fn is_full(&self, now: Instant, rate: u64, burst: u64) -> bool {
+        self.refilled(now, rate, burst) >= burst as f64
+    }
+
+    fn try_take(&mut self, cost: u64, now: Instant, rate: u64, burst: u64) -> Result<(), Duration> {
+        self.units = self.refilled(now, rate, burst);
+        self.refilled_at = now;
+
+        let cost = cost as f64;
+        if self.units >= cost {
+            self.units -= cost;
+            return Ok(())
+        }
+        if rate == 0 {
+            return Err(Duration::MAX)
+        }
+        Err(Duration::from_secs_f64((cost - self.units) / rate as f64))
+    }
+}
+
+/// A [`RpcServiceT`] middleware that enforces per-client quotas on RPC calls.
+#[derive(Debug, Clone)]
+pub struct RpcRateLimitService<S> {
+    /// The rate limiter for RPC calls
+    rate_limiter: RpcRateLimitLayer,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcRateLimitService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + 'static,
+{
+    type Future = RateLimitedRequestFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let Some(rate_limiter) = &self.rate_limiter.inner else {
+            return RateLimitedRequestFuture::Call { fut: self.inner.call(req) }
+        };
+
+        let client = req.extensions().get::<RpcClientId>();
+        match rate_limiter.try_acquire(client, req.method_name()) {
+            Ok(()) => RateLimitedRequestFuture::Call { fut: self.inner.call(req) },
+            Err(retry_after) => {
+                let data = serde_json::json!({
+                    "method": req.method_name(),
+                    "cost": rate_limiter.config.method_weight(req.method_name()),
+                    "retryAfterMs": u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX),
+                });
+                let error =
+                    ErrorObject::owned(RATE_LIMITED_CODE, "rate limit exceeded", Some(data));
+                RateLimitedRequestFuture::Rejected {
+                    response: Some(MethodResponse::error(req.id, error)),
+                }
+            }
+        }
+    }
+}
+
+/// Response future of the [`RpcRateLimitService`].
+#[pin_project::pin_project(project = RateLimitedRequestFutureProj)]
+pub enum RateLimitedRequestFuture<F> {
+    /// The call was admitted.
+    Call {
+        /// The inner call future
+        #[pin]
+        fut: F,
+    },
+    /// The call was rejected.
+    Rejected {
+        /// The rejection response
+        response: Option<MethodResponse>,
+    },
+}
+
+impl<F> RateLimitedRequestFuture<F> {
+    /// Returns `true` if the call was rejected by the rate limiter.
+    pub const fn is_rejected(&self) -> bool {
+        matches!(self, Self::Rejected { .. })
+    }
+}
+
+impl<F> std::fmt::Debug for RateLimitedRequestFuture<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RateLimitedRequestFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for RateLimitedRequestFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            RateLimitedRequestFutureProj::Call { fut } => fut.poll(cx),
+            RateLimitedRequestFutureProj::Rejected { response } => {
+                Poll::Ready(response.take().expect("polled after completion"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn method_weights() {
+        let config = RpcRateLimitConfig::new(100).with_method_weights([
+            RpcMethodWeight { method: "eth_".to_string(), weight: 2 },
+            RpcMethodWeight { method: "eth_call".to_string(), weight: 5 },
+        ]);
+        assert_eq!(config.method_weight("trace_block"), 10);
+        assert_eq!(config.method_weight("eth_getLogs"), 10);
+        assert_eq!(config.method_weight("eth_call"), 5);
+        assert_eq!(config.method_weight("eth_blockNumber"), 2);
+        assert_eq!(config.method_weight("net_version"), 1);
+    }
+
+    #[test]
+    fn token_bucket_refills() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(10, now);
+        assert!(bucket.try_take(10, now, 5, 10).is_ok());
+        assert_eq!(bucket.try_take(5, now, 5, 10), Err(Duration::from_secs(1)));
+
+        let later = now + Duration::from_secs(1);
+        assert!(bucket.try_take(5, later, 5, 10).is_ok());
+        assert!(!bucket.is_full(later, 5, 10));
+        assert!(bucket.is_full(later + Duration::from_secs(2), 5, 10));
+    }
+
+    #[test]
+    fn clients_have_separate_quotas() {
+        let limiter =
+            RpcRateLimitLayer::new(Some(RpcRateLimitConfig::new(10).with_client_quotas([
+                RpcClientQuota { client: "vip".to_string(), units_per_second: 1000 },
+            ])));
+        let inner = limiter.inner.unwrap();
+        assert_eq!(inner.config.units_per_second("vip"), 1000);
+        assert_eq!(inner.config.units_per_second("alice"), 10);
+
+        let alice = RpcClientId("alice".to_string());
+        let bob = RpcClientId("bob".to_string());
+
+        assert!(inner.try_acquire(Some(&alice), "trace_block").is_ok());
+        assert!(inner.try_acquire(Some(&alice), "eth_chainId").is_err());
+        assert!(inner.try_acquire(Some(&bob), "eth_chainId").is_ok());
+        assert!(inner.try_acquire(None, "debug_traceCall").is_ok());
+        assert!(inner.try_acquire(None, "debug_traceCall").is_err());
+    }
+
+    #[test]
+    fn client_id_from_remote_address() {
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+        let layer =
+            RpcClientIdLayer::new(&RpcRateLimitConfig::new(10).with_trusted_proxies([proxy]), None);
+        let id = |remote: IpAddr, headers: &HeaderMap| {
+            layer.client_id(Some(SocketAddr::new(remote, 1234)), headers).map(|id| id.0)
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "1.1.1.1, 2.2.2.2, 10.0.0.1".parse().unwrap());
+        headers.insert("x-real-ip", "3.3.3.3".parse().unwrap());
+
+        // Forwarding headers of untrusted peers are ignored.
+        assert_eq!(id("4.4.4.4".parse().unwrap(), &headers), Some("4.4.4.4".to_string()));
+        // Entries added by trusted proxies are skipped, earlier ones could be spoofed.
+        assert_eq!(id(proxy, &headers), Some("2.2.2.2".to_string()));
+
+        headers.remove("x-forwarded-for");
+        assert_eq!(id(proxy, &headers), Some("3.3.3.3".to_string()));
+        assert_eq!(layer.client_id(None, &headers), None);
+    }
+
+    #[test]
+    fn client_id_from_jwt_claim() {
+        let secret = JwtSecret::random();
+        let config =
+            RpcRateLimitConfig::new(10).with_client_key(RpcClientKey::JwtClaim("id".into()));
+        let layer = RpcClientIdLayer::new(&config, Some(secret));
+        let headers = |secret: &JwtSecret| {
+            let token = jsonwebtoken::encode(
+                &jsonwebtoken::Header::default(),
+                &serde_json::json!({ "iat": 0, "id": "alice" }),
+                &jsonwebtoken::EncodingKey::from_secret(secret.as_bytes()),
+            )
+            .unwrap();
+            let mut headers = HeaderMap::new();
+            headers.insert(http::header::AUTHORIZATION, format!("Bearer {token}").parse().unwrap());
+            headers
+        };
+
+        assert_eq!(
+            layer.client_id(None, &headers(&secret)),
+            Some(RpcClientId("alice".to_string()))
+        );
+        // Tokens that aren't signed with the secret are ignored.
+        assert_eq!(layer.client_id(None, &headers(&JwtSecret::random())), None);
+        assert_eq!(RpcClientIdLayer::new(&config, None).client_id(None, &headers(&secret)), None);
+    }
+
+    #[test]
+    fn client_id_from_header() {
+        let config = RpcRateLimitConfig::new(10)
+            .with_client_key(RpcClientKey::Header("x-api-key".to_string()));
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", "secret".parse().unwrap());
+        assert_eq!(
+            RpcClientIdLayer::new(&config, None).client_id(None, &headers),
+            Some(RpcClientId("secret".to_string()))
+        );
+    }
+}
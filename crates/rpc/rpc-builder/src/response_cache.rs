@@ -0,0 +1,569 @@
+//! [`jsonrpsee`] helper layer for caching responses of calls against finalized blocks.
+
+use alloy_consensus::BlockHeader;
+use futures::{Stream, StreamExt};
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT, types::Request, MethodResponse, ResponsePayload,
+};
+use parking_lot::Mutex;
+use reth_chain_state::CanonStateNotification;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_primitives::{NodePrimitives, SealedHeader};
+use schnellru::{LruMap, Unlimited};
+use serde::Deserialize;
+use serde_json::{value::RawValue, Value};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::Layer;
+
+/// Methods whose responses are cached, with the index of their block parameter.
+///
+/// Methods without a block parameter are pinned to the block of their response.
+const CACHED_METHODS: [(&str, Option<usize>); 7] = [
+    ("eth_getBlockByNumber", Some(0)),
+    ("eth_getBlockByHash", None),
+    ("eth_getBlockReceipts", Some(0)),
+    ("eth_getTransactionByHash", None),
+    ("eth_getTransactionReceipt", None),
+    ("trace_block", Some(0)),
+    ("trace_transaction", None),
+];
+
+/// Cache of serialized responses of calls that are pinned to finalized blocks.
+///
+/// Only successful, non-null responses of blocks at or below the finalized block are cached,
+/// nothing is cached until the finalized block is known. Entries are evicted in LRU order once
+/// the cache exceeds its size limit, and entries of reorged blocks are invalidated by the
+/// [`maintain_response_cache_task`].
+#[derive(Debug, Clone)]
+pub struct RpcResponseCache {
+    inner: Arc<RpcResponseCacheInner>,
+}
+
+impl RpcResponseCache {
+    /// Creates a new cache that holds up to `max_bytes` of serialized responses.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            inner: Arc::new(RpcResponseCacheInner {
+                max_bytes,
+                state: Mutex::new(ResponseCacheState {
+                    entries: LruMap::new(Unlimited),
+                    size: 0,
+                    finalized: None,
+                }),
+                metrics: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the maximum size of the cached responses in bytes.
+    pub fn max_bytes(&self) -> usize {
+        self.inner.max_bytes
+    }
+
+    /// Returns the number of cached responses.
+    pub fn len(&self) -> usize {
+        self.inner.state.lock().entries.len()
+    }
+
+    /// Returns true if no responses are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the size of the cached responses in bytes.
+    pub fn size(&self) -> usize {
+        self.inner.state.lock().size
+    }
+
+    /// Returns the finalized block number responses are cached up to.
+    pub fn finalized(&self) -> Option<u64> {
+        self.inner.state.lock().finalized
+    }
+
+    /// Sets the finalized block number.
+    ///
+    /// If the finalized block moved backwards, all responses above it are invalidated.
+    pub fn set_finalized(&self, number: u64) {
+        let mut state = self.inner.state.lock();
+        if state.finalized.is_some_and(|finalized| finalized > number) {
+            self.inner.remove_from(&mut state, number + 1);
+        }
+        state.finalized = Some(number);
+    }
+
+    /// Invalidates all responses of the given block and its descendants.
+    pub fn invalidate_from(&self, number: u64) {
+        let mut state = self.inner.state.lock();
+        self.inner.remove_from(&mut state, number);
+        if state.finalized.is_some_and(|finalized| finalized >= number) {
+            state.finalized = number.checked_sub(1);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RpcResponseCacheInner {
+    /// Maximum size of the cached responses in bytes
+    max_bytes: usize,
+    /// The cached responses
+    state: Mutex<ResponseCacheState>,
+    /// Cache metrics
+    metrics: RpcResponseCacheMetrics,
+}
+
+impl RpcResponseCacheInner {
+    /// Returns true if responses of the given block can be cached.
+    fn is_final(&self, number: u64) -> bool {
+        self.state.lock().finalized.is_some_and(|finalized| number <= finalized)
+    }
+
+    /// Returns the cached response of the call.
+    fn get(&self, key: &ResponseCacheKey) -> Option<Box<RawValue>> {
+        let payload = self.state.lock().entries.get(key).map(|entry| entry.payload.clone());
+        if payload.is_some() {
+            self.metrics.hits_total.increment(1);
+        } else {
+            self.metrics.misses_total.increment(1);
+        }
+        payload
+    }
+
+    /// Caches the response of the call if its block is final.
+    fn insert(&self, key: ResponseCacheKey, block: u64, payload: Box<RawValue>) {
+        let entry_size = key.size() + payload.get().len();
+        if entry_size > self.max_bytes {
+            return
+        }
+
+        let mut state = self.state.lock();
+        if state.finalized.is_none_or(|finalized| block > finalized) {
+            return
+        }
+        if let Some(replaced) = state.entries.remove(&key) {
+            state.size -= key.size() + replaced.payload.get().len();
+        }
+        while state.size + entry_size > self.max_bytes {
+            let Some((key, entry)) = state.entries.pop_oldest() else { break };
+            state.size -= key.size() + entry.payload.get().len();
+            self.metrics.evictions_total.increment(1);
+        }
+        state.entries.insert(key, ResponseCacheEntry { block, payload });
+        state.size += entry_size;
+        self.update_size_metrics(&state);
+    }
+
+    /// Removes all responses of the given block and its descendants.
+    fn remove_from(&self, state: &mut ResponseCacheState, number: u64) {
+        let stale = state
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.block >= number)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in stale {
+            if let Some(entry) = state.entries.remove(&key) {
+                state.size -= key.size() + entry.payload.get().len();
+                self.metrics.invalidations_total.increment(1);
+            }
+        }
+        self.update_size_metrics(state);
+    }
+
+    fn update_size_metrics(&self, state: &ResponseCacheState) {
+        self.metrics.size_bytes.set(state.size as f64);
+        self.metrics.entries.set(state.entries.len() as f64);
+    }
+}
+
+#[derive(Debug)]
+struct ResponseCacheState {
+    /// Cached responses by call
+    entries: LruMap<ResponseCacheKey, ResponseCacheEntry, Unlimited>,
+    /// Total size of the cached responses in bytes
+    size: usize,
+    /// Highest block number responses are cached for
+    finalized: Option<u64>,
+}
+
+/// Identifies a cached call by its method and raw params.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ResponseCacheKey {
+    method: &'static str,
+    params: String,
+}
+
+impl ResponseCacheKey {
+    const fn size(&self) -> usize {
+        self.method.len() + self.params.len()
+    }
+}
+
+#[derive(Debug)]
+struct ResponseCacheEntry {
+    /// The block the response belongs to
+    block: u64,
+    /// The serialized result of the call
+    payload: Box<RawValue>,
+}
+
+/// Metrics for the [`RpcResponseCache`].
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.response_cache")]
+struct RpcResponseCacheMetrics {
+    /// The number of calls answered from the cache
+    hits_total: Counter,
+    /// The number of cacheable calls that were not cached
+    misses_total: Counter,
+    /// The number of responses evicted due to the size limit
+    evictions_total: Counter,
+    /// The number of responses invalidated by reorgs
+    invalidations_total: Counter,
+    /// The size of the cached responses in bytes
+    size_bytes: Gauge,
+    /// The number of cached responses
+    entries: Gauge,
+}
+
+/// The block a call is pinned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PinnedBlock {
+    /// The call targets the given block number.
+    Number(u64),
+    /// The call targets a block or transaction hash, the block is read from the response.
+    FromResponse,
+}
+
+impl PinnedBlock {
+    /// Returns the block the call with the given params is pinned to, if any.
+    ///
+    /// Calls against moving tags like `latest` or `finalized` are not pinned.
+    fn from_params(block_param: Option<usize>, params: &str) -> Option<Self> {
+        let Some(index) = block_param else { return Some(Self::FromResponse) };
+        let params: Vec<Value> = serde_json::from_str(params).ok()?;
+        let param = params.get(index)?;
+        if let Some(number) = quantity(param) {
+            return Some(Self::Number(number))
+        }
+        match param {
+            Value::String(tag) if tag == "earliest" => Some(Self::Number(0)),
+            Value::String(hash) if hash.len() == 66 => Some(Self::FromResponse),
+            // EIP-1898 block id
+            Value::Object(id) => match id.get("blockNumber") {
+                Some(number) => quantity(number).map(Self::Number),
+                None => id.contains_key("blockHash").then_some(Self::FromResponse),
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Parses a hex quantity or a number.
+fn quantity(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(number) => number.as_u64(),
+        Value::String(s) => u64::from_str_radix(s.strip_prefix("0x")?, 16).ok(),
+        _ => None,
+    }
+}
+
+/// Returns the block number of a block, transaction, receipt or list of them.
+fn response_block(payload: &RawValue) -> Option<u64> {
+    let value: Value = serde_json::from_str(payload.get()).ok()?;
+    let item = match &value {
+        Value::Array(items) => items.first()?,
+        value => value,
+    };
+    item.get("blockNumber").or_else(|| item.get("number")).and_then(quantity)
+}
+
+/// Extracts the result of a successful response.
+fn response_payload(response: &MethodResponse) -> Option<Box<RawValue>> {
+    #[derive(Deserialize)]
+    struct Success {
+        result: Box<RawValue>,
+    }
+
+    if !response.is_success() {
+        return None
+    }
+    let Success { result } = serde_json::from_str(response.as_result()).ok()?;
+    (result.get() != "null").then_some(result)
+}
+
+/// Layer that answers calls from the [`RpcResponseCache`].
+///
+/// This layer is a no-op if it was created without a cache.
+#[derive(Debug, Clone, Default)]
+pub struct RpcResponseCacheLayer {
+    cache: Option<RpcResponseCache>,
+}
+
+impl RpcResponseCacheLayer {
+    /// Creates a new layer that uses the given cache, if any.
+    pub const fn new(cache: Option<RpcResponseCache>) -> Self {
+        Self { cache }
+    }
+}
+
+impl<S> Layer<S> for RpcResponseCacheLayer {
+    type Service = RpcResponseCacheService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcResponseCacheService { cache: self.cache.clone(), inner }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that caches responses of calls against finalized blocks.
+#[derive(Debug, Clone)]
+pub struct RpcResponseCacheService<S> {
+    /// The response cache
+    cache: Option<RpcResponseCache>,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcResponseCacheService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = ResponseCacheFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let Some(cache) = &self.cache else {
+            return ResponseCacheFuture::Call { fut: self.inner.call(req), miss: None }
+        };
+        let Some((method, block_param)) =
+            CACHED_METHODS.iter().find(|(method, _)| *method == req.method_name())
+        else {
+            return ResponseCacheFuture::Call { fut: self.inner.call(req), miss: None }
+        };
+        let pinned = req
+            .params()
+            .as_str()
+            .and_then(|params| PinnedBlock::from_params(*block_param, params))
+            .filter(|pinned| match pinned {
+                PinnedBlock::Number(number) => cache.inner.is_final(*number),
+                PinnedBlock::FromResponse => true,
+            });
+        let Some(pinned) = pinned else {
+            return ResponseCacheFuture::Call { fut: self.inner.call(req), miss: None }
+        };
+
+        let key = ResponseCacheKey {
+            method,
+            params: req.params().as_str().unwrap_or_default().to_string(),
+        };
+        if let Some(payload) = cache.inner.get(&key) {
+            let response =
+                MethodResponse::response(req.id, ResponsePayload::success(payload), usize::MAX);
+            return ResponseCacheFuture::Hit { response: Some(response) }
+        }
+
+        ResponseCacheFuture::Call {
+            fut: self.inner.call(req),
+            miss: Some(CacheMiss { cache: cache.clone(), key, pinned }),
+        }
+    }
+}
+
+/// A call that is cached once it completes.
+#[derive(Debug)]
+pub struct CacheMiss {
+    cache: RpcResponseCache,
+    key: ResponseCacheKey,
+    pinned: PinnedBlock,
+}
+
+impl CacheMiss {
+    fn insert(self, response: &MethodResponse) {
+        let Some(payload) = response_payload(response) else { return };
+        let block = match self.pinned {
+            PinnedBlock::Number(number) => Some(number),
+            PinnedBlock::FromResponse => response_block(&payload),
+        };
+        if let Some(block) = block {
+            self.cache.inner.insert(self.key, block, payload);
+        }
+    }
+}
+
+/// Response future of the [`RpcResponseCacheService`].
+#[pin_project::pin_project(project = ResponseCacheFutureProj)]
+pub enum ResponseCacheFuture<F> {
+    /// The call is forwarded to the inner service.
+    Call {
+        /// The inner call future
+        #[pin]
+        fut: F,
+        /// Set if the response should be cached
+        miss: Option<CacheMiss>,
+    },
+    /// The call was answered from the cache.
+    Hit {
+        /// The cached response
+        response: Option<MethodResponse>,
+    },
+}
+
+impl<F> std::fmt::Debug for ResponseCacheFuture<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ResponseCacheFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for ResponseCacheFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            ResponseCacheFutureProj::Call { fut, miss } => {
+                let res = fut.poll(cx);
+                if let Poll::Ready(response) = &res {
+                    if let Some(miss) = miss.take() {
+                        miss.insert(response);
+                    }
+                }
+                res
+            }
+            ResponseCacheFutureProj::Hit { response } => {
+                Poll::Ready(response.take().expect("polled after completion"))
+            }
+        }
+    }
+}
+
+/// Keeps the [`RpcResponseCache`] in sync with the chain.
+///
+/// Tracks the finalized block and invalidates the responses of reorged blocks.
+pub async fn maintain_response_cache_task<N, H, St, Fin>(
+    cache: RpcResponseCache,
+    mut events: St,
+    mut finalized: Fin,
+) where
+    N: NodePrimitives,
+    H: BlockHeader,
+    St: Stream<Item = CanonStateNotification<N>> + Unpin + 'static,
+    Fin: Stream<Item = SealedHeader<H>> + Unpin + 'static,
+{
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else { break };
+                if let Some(reverted) = event.reverted() {
+                    cache.invalidate_from(*reverted.range().start());
+                }
+            }
+            Some(header) = finalized.next() => {
+                cache.set_finalized(header.header().number());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(params: &str) -> ResponseCacheKey {
+        ResponseCacheKey { method: "eth_getBlockByNumber", params: params.to_string() }
+    }
+
+    fn payload(json: &str) -> Box<RawValue> {
+        RawValue::from_string(json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn pinned_block_from_params() {
+        assert_eq!(
+            PinnedBlock::from_params(Some(0), r#"["0x10", true]"#),
+            Some(PinnedBlock::Number(16))
+        );
+        assert_eq!(
+            PinnedBlock::from_params(Some(0), r#"["earliest"]"#),
+            Some(PinnedBlock::Number(0))
+        );
+        assert_eq!(PinnedBlock::from_params(Some(0), r#"["latest", true]"#), None);
+        assert_eq!(PinnedBlock::from_params(Some(0), r#"["finalized"]"#), None);
+        assert_eq!(
+            PinnedBlock::from_params(Some(0), r#"[{"blockNumber": "0x2"}]"#),
+            Some(PinnedBlock::Number(2))
+        );
+        let hash = format!(r#"["0x{}"]"#, "ab".repeat(32));
+        assert_eq!(PinnedBlock::from_params(Some(0), &hash), Some(PinnedBlock::FromResponse));
+        assert_eq!(PinnedBlock::from_params(None, &hash), Some(PinnedBlock::FromResponse));
+    }
+
+    #[test]
+    fn block_from_response() {
+        assert_eq!(response_block(&payload(r#"{"number":"0x5","hash":"0x00"}"#)), Some(5));
+        assert_eq!(response_block(&payload(r#"{"blockNumber":"0x7"}"#)), Some(7));
+        assert_eq!(response_block(&payload(r#"[{"blockNumber":9},{"blockNumber":9}]"#)), Some(9));
+        assert_eq!(response_block(&payload(r#"{"blockNumber":null}"#)), None);
+        assert_eq!(response_block(&payload("[]")), None);
+    }
+
+    #[test]
+    fn caches_final_blocks_only() {
+        let cache = RpcResponseCache::new(1024);
+        cache.inner.insert(key("[1]"), 1, payload("{}"));
+        assert!(cache.is_empty());
+
+        cache.set_finalized(10);
+        cache.inner.insert(key("[1]"), 1, payload("{}"));
+        cache.inner.insert(key("[11]"), 11, payload("{}"));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.inner.get(&key("[1]")).is_some());
+        assert!(cache.inner.get(&key("[11]")).is_none());
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = RpcResponseCache::new(3 * (key("[1]").size() + 2));
+        cache.set_finalized(10);
+        for block in 1..=3 {
+            cache.inner.insert(key(&format!("[{block}]")), block, payload("{}"));
+        }
+        assert_eq!(cache.size(), cache.max_bytes());
+
+        // touch the oldest entry so the second one is evicted instead
+        assert!(cache.inner.get(&key("[1]")).is_some());
+        cache.inner.insert(key("[4]"), 4, payload("{}"));
+        assert_eq!(cache.len(), 3);
+        assert!(cache.inner.get(&key("[1]")).is_some());
+        assert!(cache.inner.get(&key("[2]")).is_none());
+
+        // entries larger than the cache are never inserted
+        cache.inner.insert(key("[5]"), 5, payload(&format!("\"{}\"", "a".repeat(64))));
+        assert!(cache.inner.get(&key("[5]")).is_none());
+        assert_eq!(cache.size(), cache.max_bytes());
+    }
+
+    #[test]
+    fn invalidates_reorged_blocks() {
+        let cache = RpcResponseCache::new(1024);
+        cache.set_finalized(10);
+        for block in 1..=10 {
+            cache.inner.insert(key(&format!("[{block}]")), block, payload("{}"));
+        }
+
+        cache.invalidate_from(8);
+        assert_eq!(cache.len(), 7);
+        assert_eq!(cache.finalized(), Some(7));
+        assert!(cache.inner.get(&key("[8]")).is_none());
+
+        cache.set_finalized(5);
+        assert_eq!(cache.len(), 5);
+
+        cache.invalidate_from(0);
+        assert!(cache.is_empty());
+        assert_eq!(cache.finalized(), None);
+    }
+}
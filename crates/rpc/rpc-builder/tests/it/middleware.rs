@@ -1,6 +1,7 @@
 use crate::utils::{test_address, test_rpc_builder};
 use alloy_rpc_types_eth::{Block, Header, Receipt, Transaction};
 use jsonrpsee::{
+    http_client::HttpClientBuilder,
     server::{middleware::rpc::RpcServiceT, RpcServiceBuilder},
     types::Request,
     MethodResponse,
@@ -8,7 +9,10 @@ use jsonrpsee::{
 use reth_chainspec::MAINNET;
 use reth_ethereum_engine_primitives::EthereumEngineValidator;
 use reth_rpc::EthApi;
-use reth_rpc_builder::{RpcServerConfig, TransportRpcModuleConfig};
+use reth_rpc_builder::{
+    rate_limiter::{RpcRateLimitConfig, RATE_LIMITED_CODE},
+    RpcServerConfig, TransportRpcModuleConfig,
+};
 use reth_rpc_eth_api::EthApiClient;
 use reth_rpc_server_types::RpcModuleSelection;
 use std::{
@@ -82,3 +86,62 @@ async fn test_rpc_middleware() {
     let count = mylayer.count.load(Ordering::Relaxed);
     assert_eq!(count, 1);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_rate_limit() {
+    let builder = test_rpc_builder();
+    let modules = builder.build(
+        TransportRpcModuleConfig::set_http(RpcModuleSelection::All),
+        Box::new(EthApi::with_spawner),
+        Arc::new(EthereumEngineValidator::new(MAINNET.clone())),
+    );
+
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_rate_limit(Some(RpcRateLimitConfig::new(1)))
+        .start(&modules)
+        .await
+        .unwrap();
+
+    let client = handle.http_client().unwrap();
+    EthApiClient::<Transaction, Block, Receipt, Header>::protocol_version(&client).await.unwrap();
+    let err = EthApiClient::<Transaction, Block, Receipt, Header>::protocol_version(&client)
+        .await
+        .unwrap_err();
+    let jsonrpsee::core::client::Error::Call(err) = err else { panic!("unexpected error {err:?}") };
+    assert_eq!(err.code(), RATE_LIMITED_CODE);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_rate_limit_ignores_untrusted_forwarded_for() {
+    let builder = test_rpc_builder();
+    let modules = builder.build(
+        TransportRpcModuleConfig::set_http(RpcModuleSelection::All),
+        Box::new(EthApi::with_spawner),
+        Arc::new(EthereumEngineValidator::new(MAINNET.clone())),
+    );
+
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_rate_limit(Some(RpcRateLimitConfig::new(1)))
+        .start(&modules)
+        .await
+        .unwrap();
+
+    // the connection is not from a trusted proxy, so both clients share the same bucket
+    let url = handle.http_url().unwrap();
+    let client = |forwarded_for: &str| {
+        let mut headers = http::HeaderMap::new();
+        headers.insert("x-forwarded-for", forwarded_for.parse().unwrap());
+        HttpClientBuilder::default().set_headers(headers).build(&url).unwrap()
+    };
+    EthApiClient::<Transaction, Block, Receipt, Header>::protocol_version(&client("1.1.1.1"))
+        .await
+        .unwrap();
+    let err =
+        EthApiClient::<Transaction, Block, Receipt, Header>::protocol_version(&client("2.2.2.2"))
+            .await
+            .unwrap_err();
+    let jsonrpsee::core::client::Error::Call(err) = err else { panic!("unexpected error {err:?}") };
+    assert_eq!(err.code(), RATE_LIMITED_CODE);
+}
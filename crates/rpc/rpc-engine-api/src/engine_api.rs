@@ -20,8 +20,8 @@ use reth_chainspec::{EthereumHardfork, EthereumHardforks};
 use reth_engine_primitives::{BeaconConsensusEngineHandle, EngineTypes, EngineValidator};
 use reth_payload_builder::PayloadStore;
 use reth_payload_primitives::{
-    validate_payload_timestamp, EngineApiMessageVersion, PayloadBuilderAttributes,
-    PayloadOrAttributes,
+    validate_payload_timestamp, EngineApiMessageVersion, ExecutionPayloadInputV4,
+    PayloadBuilderAttributes, PayloadOrAttributes,
 };
 use reth_rpc_api::EngineApiServer;
 use reth_rpc_types_compat::engine::payload::convert_to_payload_body_v1;
@@ -148,7 +148,7 @@ where
         Ok(self
             .inner
             .beacon_consensus
-            .new_payload(payload, ExecutionPayloadSidecar::none())
+            .new_payload(payload, ExecutionPayloadSidecar::none(), None)
             .await
             .inspect(|_| self.inner.on_new_payload_response())?)
     }
@@ -183,7 +183,7 @@ where
         Ok(self
             .inner
             .beacon_consensus
-            .new_payload(payload, ExecutionPayloadSidecar::none())
+            .new_payload(payload, ExecutionPayloadSidecar::none(), None)
             .await
             .inspect(|_| self.inner.on_new_payload_response())?)
     }
@@ -228,6 +228,7 @@ where
                     versioned_hashes,
                     parent_beacon_block_root,
                 }),
+                None,
             )
             .await
             .inspect(|_| self.inner.on_new_payload_response())?)
@@ -251,14 +252,18 @@ where
     }
 
     /// See also <https://github.com/ethereum/execution-apis/blob/7907424db935b93c2fe6a3c0faab943adebe8557/src/engine/prague.md#engine_newpayloadv4>
+    ///
+    /// L2s may include the withdrawals root of the block in the payload, see
+    /// [`ExecutionPayloadInputV4`].
     pub async fn new_payload_v4(
         &self,
-        payload: ExecutionPayloadV3,
+        payload: ExecutionPayloadInputV4,
         versioned_hashes: Vec<B256>,
         parent_beacon_block_root: B256,
         execution_requests: Requests,
     ) -> EngineApiResult<PayloadStatus> {
-        let payload = ExecutionPayload::from(payload);
+        let ExecutionPayloadInputV4 { execution_payload, withdrawals_root } = payload;
+        let payload = ExecutionPayload::from(execution_payload);
         let payload_or_attrs =
             PayloadOrAttributes::<'_, EngineT::PayloadAttributes>::from_execution_payload(
                 &payload,
@@ -278,6 +283,7 @@ where
                     CancunPayloadFields { versioned_hashes, parent_beacon_block_root },
                     PraguePayloadFields { requests: RequestsOrHash::Requests(execution_requests) },
                 ),
+                withdrawals_root,
             )
             .await
             .inspect(|_| self.inner.on_new_payload_response())?)
@@ -286,13 +292,13 @@ where
     /// Metrics version of `new_payload_v4`
     async fn new_payload_v4_metered(
         &self,
-        payload: ExecutionPayloadV3,
+        payload: ExecutionPayloadInputV4,
         versioned_hashes: Vec<B256>,
         parent_beacon_block_root: B256,
         execution_requests: Requests,
     ) -> RpcResult<PayloadStatus> {
         let start = Instant::now();
-        let gas_used = payload.payload_inner.payload_inner.gas_used;
+        let gas_used = payload.execution_payload.payload_inner.payload_inner.gas_used;
         let res = Self::new_payload_v4(
             self,
             payload,
@@ -773,7 +779,7 @@ where
     /// See also <https://github.com/ethereum/execution-apis/blob/03911ffc053b8b806123f1fc237184b0092a485a/src/engine/prague.md#engine_newpayloadv4>
     async fn new_payload_v4(
         &self,
-        payload: ExecutionPayloadV3,
+        payload: ExecutionPayloadInputV4,
         versioned_hashes: Vec<B256>,
         parent_beacon_block_root: B256,
         execution_requests: Requests,
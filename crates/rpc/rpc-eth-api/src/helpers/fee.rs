@@ -3,7 +3,7 @@
 use super::LoadBlock;
 use crate::FromEthApiError;
 use alloy_consensus::BlockHeader;
-use alloy_eips::eip7840::BlobParams;
+use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
 use alloy_primitives::U256;
 use alloy_rpc_types_eth::{BlockNumberOrTag, FeeHistory};
 use futures::Future;
@@ -163,13 +163,23 @@ pub trait EthFees: LoadFee {
 
 
                 for header in &headers {
+                    let blob_params = self.provider()
+                        .chain_spec()
+                        .blob_params_at_timestamp(header.timestamp());
+
                     base_fee_per_gas.push(header.base_fee_per_gas().unwrap_or_default() as u128);
                     gas_used_ratio.push(header.gas_used() as f64 / header.gas_limit() as f64);
-                    base_fee_per_blob_gas.push(header.blob_fee(BlobParams::cancun()).unwrap_or_default());
-                    blob_gas_used_ratio.push(
-                        header.blob_gas_used().unwrap_or_default() as f64
-                            / alloy_eips::eip4844::MAX_DATA_GAS_PER_BLOCK as f64,
+                    base_fee_per_blob_gas.push(
+                        blob_params.and_then(|params| header.blob_fee(params)).unwrap_or_default()
                     );
+                    let max_blob_gas = blob_params
+                        .map(|params| params.max_blob_count * DATA_GAS_PER_BLOB)
+                        .unwrap_or_default();
+                    blob_gas_used_ratio.push(if max_blob_gas == 0 {
+                        0.
+                    } else {
+                        header.blob_gas_used().unwrap_or_default() as f64 / max_blob_gas as f64
+                    });
 
                     // Percentiles were specified, so we need to collect reward percentile ino
                     if let Some(percentiles) = &reward_percentiles {
@@ -206,7 +216,13 @@ pub trait EthFees: LoadFee {
 
                 // Same goes for the `base_fee_per_blob_gas`:
                 // > "[..] includes the next block after the newest of the returned range, because this value can be derived from the newest block.
-                base_fee_per_blob_gas.push(last_header.next_block_blob_fee(BlobParams::cancun()).unwrap_or_default());
+                base_fee_per_blob_gas.push(
+                    self.provider()
+                        .chain_spec()
+                        .blob_params_at_timestamp(last_header.timestamp())
+                        .and_then(|params| last_header.next_block_blob_fee(params))
+                        .unwrap_or_default()
+                );
             };
 
             Ok(FeeHistory {
@@ -331,7 +347,12 @@ pub trait LoadFee: LoadBlock {
         async move {
             self.block_with_senders(BlockNumberOrTag::Latest.into())
                 .await?
-                .and_then(|h| h.next_block_blob_fee(BlobParams::cancun()))
+                .and_then(|h| {
+                    self.provider()
+                        .chain_spec()
+                        .blob_params_at_timestamp(h.timestamp())
+                        .and_then(|params| h.next_block_blob_fee(params))
+                })
                 .ok_or(EthApiError::ExcessBlobGasNotSet.into())
                 .map(U256::from)
         }
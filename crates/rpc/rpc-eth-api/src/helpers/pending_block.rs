@@ -4,7 +4,7 @@
 use super::SpawnBlocking;
 use crate::{EthApiTypes, FromEthApiError, FromEvmError, RpcNodeCore};
 use alloy_consensus::{BlockHeader, Transaction};
-use alloy_eips::eip4844::MAX_DATA_GAS_PER_BLOCK;
+use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
 use alloy_network::Network;
 use alloy_primitives::B256;
 use alloy_rpc_types_eth::BlockNumberOrTag;
@@ -62,6 +62,32 @@ pub trait LoadPendingBlock:
         &self,
     ) -> &Mutex<Option<PendingBlock<ProviderBlock<Self::Provider>, ProviderReceipt<Self::Provider>>>>;
 
+    /// Returns the actual pending block and its receipts, if one is known.
+    ///
+    /// By default this is the pending block of the provider, received from the CL.
+    #[expect(clippy::type_complexity)]
+    fn actual_pending_block(
+        &self,
+    ) -> Result<
+        Option<(
+            RecoveredBlock<ProviderBlock<Self::Provider>>,
+            Vec<ProviderReceipt<Self::Provider>>,
+        )>,
+        Self::Error,
+    > {
+        let Some(block) =
+            self.provider().pending_block_with_senders().map_err(Self::Error::from_eth_err)?
+        else {
+            return Ok(None)
+        };
+        let receipts = self
+            .provider()
+            .receipts_by_block(block.hash().into())
+            .map_err(Self::Error::from_eth_err)?;
+
+        Ok(receipts.map(|receipts| (block, receipts)))
+    }
+
     /// Configures the [`EvmEnv`] for the pending block
     ///
     /// If no pending block is available, this will derive it from the `latest` block
@@ -72,24 +98,16 @@ pub trait LoadPendingBlock:
         PendingBlockEnv<ProviderBlock<Self::Provider>, ProviderReceipt<Self::Provider>>,
         Self::Error,
     > {
-        if let Some(block) =
-            self.provider().pending_block_with_senders().map_err(Self::Error::from_eth_err)?
-        {
-            if let Some(receipts) = self
-                .provider()
-                .receipts_by_block(block.hash().into())
-                .map_err(Self::Error::from_eth_err)?
-            {
-                // Note: for the PENDING block we assume it is past the known merge block and
-                // thus this will not fail when looking up the total
-                // difficulty value for the blockenv.
-                let evm_env = self.evm_config().cfg_and_block_env(block.header());
-
-                return Ok(PendingBlockEnv::new(
-                    evm_env,
-                    PendingBlockEnvOrigin::ActualPending(block, receipts),
-                ));
-            }
+        if let Some((block, receipts)) = self.actual_pending_block()? {
+            // Note: for the PENDING block we assume it is past the known merge block and
+            // thus this will not fail when looking up the total
+            // difficulty value for the blockenv.
+            let evm_env = self.evm_config().cfg_and_block_env(block.header());
+
+            return Ok(PendingBlockEnv::new(
+                evm_env,
+                PendingBlockEnvOrigin::ActualPending(block, receipts),
+            ));
         }
 
         // no pending block from the CL yet, so we use the latest block and modify the env
@@ -264,6 +282,9 @@ pub trait LoadPendingBlock:
             ));
 
         let chain_spec = self.provider().chain_spec();
+        let max_blob_gas_per_block = chain_spec
+            .blob_params_at_timestamp(evm_env.block_env.timestamp.to::<u64>())
+            .map_or(0, |params| params.max_blob_count * DATA_GAS_PER_BLOB);
 
         let mut system_caller = SystemCaller::new(self.evm_config().clone(), chain_spec.clone());
 
@@ -308,7 +329,7 @@ pub trait LoadPendingBlock:
             // There's only limited amount of blob space available per block, so we need to check if
             // the EIP-4844 can still fit in the block
             if let Some(tx_blob_gas) = tx.blob_gas_used() {
-                if sum_blob_gas_used + tx_blob_gas > MAX_DATA_GAS_PER_BLOCK {
+                if sum_blob_gas_used + tx_blob_gas > max_blob_gas_per_block {
                     // we can't fit this _blob_ transaction into the block, so we mark it as
                     // invalid, which removes its dependent transactions from
                     // the iterator. This is similar to the gas limit condition
@@ -317,7 +338,7 @@ pub trait LoadPendingBlock:
                         &pool_tx,
                         InvalidPoolTransactionError::ExceedsGasLimit(
                             tx_blob_gas,
-                            MAX_DATA_GAS_PER_BLOCK,
+                            max_blob_gas_per_block,
                         ),
                     );
                     continue
@@ -363,7 +384,7 @@ pub trait LoadPendingBlock:
                 sum_blob_gas_used += tx_blob_gas;
 
                 // if we've reached the max data gas per block, we can skip blob txs entirely
-                if sum_blob_gas_used == MAX_DATA_GAS_PER_BLOCK {
+                if sum_blob_gas_used == max_blob_gas_per_block {
                     best_txs.skip_blobs();
                 }
             }
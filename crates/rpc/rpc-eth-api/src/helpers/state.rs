@@ -107,12 +107,16 @@ pub trait EthState: LoadState + SpawnBlocking {
                 .map_err(Self::Error::from_eth_err)?
                 .ok_or(EthApiError::HeaderNotFound(block_id))?;
             let max_window = self.max_proof_window();
-            if chain_info.best_number.saturating_sub(block_number) > max_window {
-                return Err(EthApiError::ExceedsMaxProofWindow.into())
-            }
+            let exceeds_window = chain_info.best_number.saturating_sub(block_number) > max_window;
 
             self.spawn_blocking_io(move |this| {
                 let state = this.state_at_block_id(block_id)?;
+                // Blocks beyond the window are only served from recorded trie changesets.
+                if exceeds_window &&
+                    !state.has_trie_changesets().map_err(Self::Error::from_eth_err)?
+                {
+                    return Err(EthApiError::ExceedsMaxProofWindow.into())
+                }
                 let storage_keys = keys.iter().map(|key| key.as_b256()).collect::<Vec<_>>();
                 let proof = state
                     .proof(Default::default(), address, &storage_keys)
@@ -142,7 +146,9 @@ pub trait EthState: LoadState + SpawnBlocking {
                 .map_err(Self::Error::from_eth_err)?
                 .ok_or(EthApiError::HeaderNotFound(block_id))?;
             let max_window = this.max_proof_window();
-            if chain_info.best_number.saturating_sub(block_number) > max_window {
+            if chain_info.best_number.saturating_sub(block_number) > max_window &&
+                !state.has_trie_changesets().map_err(Self::Error::from_eth_err)?
+            {
                 return Err(EthApiError::ExceedsMaxProofWindow.into())
             }
 
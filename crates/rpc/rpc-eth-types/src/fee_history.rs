@@ -7,7 +7,7 @@ use std::{
 };
 
 use alloy_consensus::{BlockHeader, Transaction, TxReceipt};
-use alloy_eips::eip1559::calc_next_block_base_fee;
+use alloy_eips::{eip1559::calc_next_block_base_fee, eip7840::BlobParams};
 use alloy_primitives::B256;
 use alloy_rpc_types_eth::TxGasAndReward;
 use futures::{
@@ -72,18 +72,22 @@ impl FeeHistoryCache {
     }
 
     /// Insert block data into the cache.
-    async fn insert_blocks<'a, I, B, R>(&self, blocks: I)
+    async fn insert_blocks<'a, I, B, R, C>(&self, blocks: I, chain_spec: &C)
     where
         B: Block + 'a,
         R: TxReceipt,
         I: IntoIterator<Item = (&'a SealedBlock<B>, Arc<Vec<R>>)>,
+        C: EthChainSpec,
     {
         let mut entries = self.inner.entries.write().await;
 
         let percentiles = self.predefined_percentiles();
         // Insert all new blocks and calculate approximated rewards
         for (block, receipts) in blocks {
-            let mut fee_history_entry = FeeHistoryEntry::new(block);
+            let mut fee_history_entry = FeeHistoryEntry::new(
+                block,
+                chain_spec.blob_params_at_timestamp(block.header().timestamp()),
+            );
             fee_history_entry.rewards = calculate_reward_percentiles_for_block(
                 &percentiles,
                 fee_history_entry.gas_used,
@@ -220,6 +224,7 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider, N>(
     // If the node transitions to stage sync, we need to fetch the missing blocks
     let mut missing_blocks = VecDeque::new();
     let mut fetch_missing_block = Fuse::terminated();
+    let chain_spec = provider.chain_spec();
 
     loop {
         if fetch_missing_block.is_terminated() {
@@ -237,7 +242,7 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider, N>(
                 if let Ok(res) = res {
                     let res = res.as_ref()
                         .map(|(b, r)| (b.sealed_block(), r.clone()));
-                    fee_history_cache.insert_blocks(res).await;
+                    fee_history_cache.insert_blocks(res, &*chain_spec).await;
                 }
             }
             event = events.next() =>  {
@@ -253,7 +258,7 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider, N>(
                         (block.clone_sealed_block(), Arc::new(receipts.iter().flatten().cloned().collect::<Vec<_>>()))
                     })
                     .unzip();
-                fee_history_cache.insert_blocks(blocks.iter().zip(receipts)).await;
+                fee_history_cache.insert_blocks(blocks.iter().zip(receipts), &*chain_spec).await;
 
                 // keep track of missing blocks
                 missing_blocks = fee_history_cache.missing_consecutive_blocks().await;
@@ -356,22 +361,29 @@ pub struct FeeHistoryEntry {
     pub rewards: Vec<u128>,
     /// The timestamp of the block.
     pub timestamp: u64,
+    /// Blob parameters for this block.
+    pub blob_params: Option<BlobParams>,
 }
 
 impl FeeHistoryEntry {
     /// Creates a new entry from a sealed block.
     ///
     /// Note: This does not calculate the rewards for the block.
-    pub fn new<B: Block>(block: &SealedBlock<B>) -> Self {
+    pub fn new<B: Block>(block: &SealedBlock<B>, blob_params: Option<BlobParams>) -> Self {
         Self {
             base_fee_per_gas: block.header().base_fee_per_gas().unwrap_or_default(),
             gas_used_ratio: block.header().gas_used() as f64 / block.header().gas_limit() as f64,
             base_fee_per_blob_gas: block
                 .header()
                 .excess_blob_gas()
-                .map(alloy_eips::eip4844::calc_blob_gasprice),
-            blob_gas_used_ratio: block.body().blob_gas_used() as f64 /
-                alloy_eips::eip4844::MAX_DATA_GAS_PER_BLOCK as f64,
+                .zip(blob_params)
+                .map(|(excess_blob_gas, params)| params.calc_blob_fee(excess_blob_gas)),
+            blob_gas_used_ratio: blob_params
+                .map(|params| params.max_blob_count * alloy_eips::eip4844::DATA_GAS_PER_BLOB)
+                .filter(|max_blob_gas| *max_blob_gas > 0)
+                .map_or(0., |max_blob_gas| {
+                    block.body().blob_gas_used() as f64 / max_blob_gas as f64
+                }),
             excess_blob_gas: block.header().excess_blob_gas(),
             blob_gas_used: block.header().blob_gas_used(),
             gas_used: block.header().gas_used(),
@@ -379,6 +391,7 @@ impl FeeHistoryEntry {
             gas_limit: block.header().gas_limit(),
             rewards: Vec::new(),
             timestamp: block.header().timestamp(),
+            blob_params,
         }
     }
 
@@ -394,17 +407,21 @@ impl FeeHistoryEntry {
 
     /// Returns the blob fee for the next block according to the EIP-4844 spec.
     ///
-    /// Returns `None` if `excess_blob_gas` is None.
+    /// Returns `None` if `excess_blob_gas` is None or no blob parameters are known.
     ///
     /// See also [`Self::next_block_excess_blob_gas`]
     pub fn next_block_blob_fee(&self) -> Option<u128> {
-        self.next_block_excess_blob_gas().map(alloy_eips::eip4844::calc_blob_gasprice)
+        let blob_params = self.blob_params?;
+        self.next_block_excess_blob_gas().map(|excess| blob_params.calc_blob_fee(excess))
     }
 
     /// Calculate excess blob gas for the next block according to the EIP-4844 spec.
     ///
     /// Returns a `None` if no excess blob gas is set, no EIP-4844 support
     pub fn next_block_excess_blob_gas(&self) -> Option<u64> {
-        Some(alloy_eips::eip4844::calc_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
+        Some(
+            self.blob_params?
+                .next_block_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?),
+        )
     }
 }
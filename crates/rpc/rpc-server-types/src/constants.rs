@@ -55,6 +55,10 @@ pub const DEFAULT_ETH_PROOF_WINDOW: u64 = 0;
 /// second block time, and a month on a 2 second block time.
 pub const MAX_ETH_PROOF_WINDOW: u64 = 28 * 24 * 60 * 60 / 2;
 
+/// The default number of most recent blocks trie changesets are retained for when historical
+/// proofs are enabled. Equivalent to roughly two weeks of data on a 12 second block time.
+pub const DEFAULT_ETH_PROOF_HISTORY_DISTANCE: u64 = 100_800;
+
 /// GPO specific constants
 pub mod gas_oracle {
     use alloy_primitives::U256;
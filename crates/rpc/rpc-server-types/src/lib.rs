@@ -15,4 +15,7 @@ pub mod result;
 mod module;
 pub use module::{RethRpcModule, RpcModuleSelection};
 
+mod rate_limit;
+pub use rate_limit::{RateLimitParseError, RpcClientKey, RpcClientQuota, RpcMethodWeight};
+
 pub use result::ToRpcResult;
@@ -0,0 +1,148 @@
+//! Types for configuring rate limiting of RPC calls.
+
+use std::{fmt, str::FromStr};
+
+/// Identifies the client of an RPC call for the purpose of rate limiting.
+///
+/// Parsed from `ip`, `header:<name>` or `jwt:<claim>`.
+///
+/// # Example
+///
+/// ```
+/// use reth_rpc_server_types::RpcClientKey;
+/// let key: RpcClientKey = "header:x-api-key".parse().unwrap();
+/// assert_eq!(key, RpcClientKey::Header("x-api-key".to_string()));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub enum RpcClientKey {
+    /// The IP address the client connected from.
+    ///
+    /// The `X-Forwarded-For` and `X-Real-IP` headers are only used for connections from trusted
+    /// proxies.
+    #[default]
+    Ip,
+    /// The value of the given request header, e.g. an API key.
+    Header(String),
+    /// The value of the given claim of the JWT bearer token, which must be signed with the JWT
+    /// secret of the server.
+    JwtClaim(String),
+}
+
+impl FromStr for RpcClientKey {
+    type Err = RateLimitParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s.eq_ignore_ascii_case("ip") => Ok(Self::Ip),
+            Some((kind, name)) if !name.is_empty() && kind.eq_ignore_ascii_case("header") => {
+                Ok(Self::Header(name.to_ascii_lowercase()))
+            }
+            Some((kind, name)) if !name.is_empty() && kind.eq_ignore_ascii_case("jwt") => {
+                Ok(Self::JwtClaim(name.to_string()))
+            }
+            _ => Err(RateLimitParseError::InvalidClientKey(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for RpcClientKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ip => f.write_str("ip"),
+            Self::Header(name) => write!(f, "header:{name}"),
+            Self::JwtClaim(claim) => write!(f, "jwt:{claim}"),
+        }
+    }
+}
+
+/// The cost of a single call to an RPC method, parsed from `<method>=<weight>`.
+///
+/// A method ending with `_` applies to the whole namespace, e.g. `trace_=20`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcMethodWeight {
+    /// The method name or namespace prefix.
+    pub method: String,
+    /// The number of quota units consumed by a call.
+    pub weight: u64,
+}
+
+impl FromStr for RpcMethodWeight {
+    type Err = RateLimitParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (method, weight) = parse_pair(s)?;
+        Ok(Self { method, weight })
+    }
+}
+
+/// The quota of a single client, parsed from `<client>=<units per second>`.
+///
+/// Overrides the default quota for the client identified by the [`RpcClientKey`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcClientQuota {
+    /// The client identifier.
+    pub client: String,
+    /// The number of quota units refilled per second.
+    pub units_per_second: u64,
+}
+
+impl FromStr for RpcClientQuota {
+    type Err = RateLimitParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (client, units_per_second) = parse_pair(s)?;
+        Ok(Self { client, units_per_second })
+    }
+}
+
+/// Parses a `<name>=<value>` pair.
+fn parse_pair(s: &str) -> Result<(String, u64), RateLimitParseError> {
+    let (name, value) = s
+        .split_once('=')
+        .filter(|(name, _)| !name.is_empty())
+        .ok_or_else(|| RateLimitParseError::InvalidPair(s.to_string()))?;
+    let value = value.parse().map_err(|_| RateLimitParseError::InvalidPair(s.to_string()))?;
+    Ok((name.to_string(), value))
+}
+
+/// Errors when parsing rate limit configuration.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RateLimitParseError {
+    /// The client key is not one of `ip`, `header:<name>` or `jwt:<claim>`.
+    #[error("invalid client key {0:?}, expected one of `ip`, `header:<name>` or `jwt:<claim>`")]
+    InvalidClientKey(String),
+    /// The value is not a `<name>=<number>` pair.
+    #[error("invalid value {0:?}, expected `<name>=<number>`")]
+    InvalidPair(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_client_key() {
+        assert_eq!("ip".parse::<RpcClientKey>().unwrap(), RpcClientKey::Ip);
+        assert_eq!(
+            "header:X-Api-Key".parse::<RpcClientKey>().unwrap(),
+            RpcClientKey::Header("x-api-key".to_string())
+        );
+        assert_eq!(
+            "jwt:sub".parse::<RpcClientKey>().unwrap(),
+            RpcClientKey::JwtClaim("sub".to_string())
+        );
+        assert!("header:".parse::<RpcClientKey>().is_err());
+        assert!("cookie:id".parse::<RpcClientKey>().is_err());
+    }
+
+    #[test]
+    fn parse_method_weight() {
+        assert_eq!(
+            "trace_=20".parse::<RpcMethodWeight>().unwrap(),
+            RpcMethodWeight { method: "trace_".to_string(), weight: 20 }
+        );
+        assert!("eth_getLogs".parse::<RpcMethodWeight>().is_err());
+        assert!("=5".parse::<RpcMethodWeight>().is_err());
+        assert!("eth_call=-1".parse::<RpcMethodWeight>().is_err());
+    }
+}
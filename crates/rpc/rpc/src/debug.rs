@@ -14,6 +14,7 @@ use alloy_rpc_types_trace::geth::{
 };
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use parking_lot::Mutex;
 use reth_chainspec::EthereumHardforks;
 use reth_evm::{
     env::EvmEnv,
@@ -22,6 +23,7 @@ use reth_evm::{
 };
 use reth_primitives::{NodePrimitives, ReceiptWithBloom, RecoveredBlock};
 use reth_primitives_traits::{Block as _, BlockBody, SignedTransaction};
+use reth_profiling::ProfilingError;
 use reth_provider::{
     BlockIdReader, BlockReaderIdExt, ChainSpecProvider, HeaderProvider, ProviderBlock,
     ReceiptProviderIdExt, StateProofProvider, TransactionVariant,
@@ -33,7 +35,10 @@ use reth_rpc_eth_api::{
     EthApiTypes, FromEthApiError, RpcNodeCore,
 };
 use reth_rpc_eth_types::{EthApiError, StateCacheDb};
-use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_server_types::{
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    ToRpcResult,
+};
 use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
     db::{CacheDB, State},
@@ -43,7 +48,7 @@ use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
 use revm_primitives::TxEnv;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc, time::Duration};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
 /// `debug` API implementation.
@@ -62,7 +67,12 @@ impl<Eth, BlockExecutor> DebugApi<Eth, BlockExecutor> {
         blocking_task_guard: BlockingTaskGuard,
         block_executor: BlockExecutor,
     ) -> Self {
-        let inner = Arc::new(DebugApiInner { eth_api: eth, blocking_task_guard, block_executor });
+        let inner = Arc::new(DebugApiInner {
+            eth_api: eth,
+            blocking_task_guard,
+            block_executor,
+            cpu_profile_file: Default::default(),
+        });
         Self { inner }
     }
 
@@ -1005,8 +1015,15 @@ where
         Ok(())
     }
 
-    async fn debug_cpu_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_cpuProfile`
+    async fn debug_cpu_profile(&self, file: String, seconds: u64) -> RpcResult<()> {
+        let profile = reth_profiling::profile_cpu(
+            Duration::from_secs(seconds),
+            reth_profiling::DEFAULT_CPU_PROFILE_FREQUENCY,
+        )
+        .await
+        .map_err(profiling_rpc_err)?;
+        write_profile(&file, &profile)
     }
 
     async fn debug_db_ancient(&self, _kind: String, _number: u64) -> RpcResult<()> {
@@ -1133,7 +1150,11 @@ where
         Ok(())
     }
 
-    async fn debug_start_cpu_profile(&self, _file: String) -> RpcResult<()> {
+    /// Handler for `debug_startCPUProfile`
+    async fn debug_start_cpu_profile(&self, file: String) -> RpcResult<()> {
+        reth_profiling::start_cpu_profile(reth_profiling::DEFAULT_CPU_PROFILE_FREQUENCY)
+            .map_err(profiling_rpc_err)?;
+        *self.inner.cpu_profile_file.lock() = Some(PathBuf::from(file));
         Ok(())
     }
 
@@ -1141,8 +1162,13 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_stopCPUProfile`
     async fn debug_stop_cpu_profile(&self) -> RpcResult<()> {
-        Ok(())
+        let Some(file) = self.inner.cpu_profile_file.lock().take() else {
+            return Err(profiling_rpc_err(ProfilingError::NotRunning))
+        };
+        let profile = reth_profiling::stop_cpu_profile().map_err(profiling_rpc_err)?;
+        write_profile(&file, &profile)
     }
 
     async fn debug_stop_go_trace(&self) -> RpcResult<()> {
@@ -1168,20 +1194,27 @@ where
         Ok(())
     }
 
-    async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_verbosity`
+    async fn debug_verbosity(&self, level: usize) -> RpcResult<()> {
+        log_filter_handle()?
+            .set_verbosity(reth_tracing::verbosity_level(level))
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))
     }
 
-    async fn debug_vmodule(&self, _pattern: String) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_vmodule`
+    async fn debug_vmodule(&self, pattern: String) -> RpcResult<()> {
+        log_filter_handle()?
+            .set_vmodule(&pattern)
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))
     }
 
     async fn debug_write_block_profile(&self, _file: String) -> RpcResult<()> {
         Ok(())
     }
 
-    async fn debug_write_mem_profile(&self, _file: String) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_writeMemProfile`
+    async fn debug_write_mem_profile(&self, file: String) -> RpcResult<()> {
+        reth_profiling::write_heap_profile(file.as_ref()).map_err(profiling_rpc_err)
     }
 
     async fn debug_write_mutex_profile(&self, _file: String) -> RpcResult<()> {
@@ -1208,4 +1241,25 @@ struct DebugApiInner<Eth, BlockExecutor> {
     blocking_task_guard: BlockingTaskGuard,
     /// block executor for debug & trace apis
     block_executor: BlockExecutor,
+    /// The file the CPU profile started with `debug_startCPUProfile` is written to
+    cpu_profile_file: Mutex<Option<PathBuf>>,
+}
+
+/// Returns the handle to change the log filters of the node, if its tracer supports it.
+pub(crate) fn log_filter_handle() -> RpcResult<&'static reth_tracing::LogFilterHandle> {
+    reth_tracing::log_filter_handle()
+        .ok_or_else(|| internal_rpc_err("log filters can not be changed at runtime"))
+}
+
+/// Converts a [`ProfilingError`] into an RPC error.
+fn profiling_rpc_err(err: ProfilingError) -> jsonrpsee::types::ErrorObject<'static> {
+    match err {
+        ProfilingError::DurationTooLong(..) => invalid_params_rpc_err(err.to_string()),
+        _ => internal_rpc_err(err.to_string()),
+    }
+}
+
+/// Writes a collected profile to the given file.
+fn write_profile(file: impl AsRef<std::path::Path>, profile: &[u8]) -> RpcResult<()> {
+    std::fs::write(file, profile).map_err(|err| internal_rpc_err(err.to_string()))
 }
@@ -1,7 +1,7 @@
 //! `Eth` bundle implementation and helpers.
 
 use alloy_consensus::{BlockHeader, EnvKzgSettings, Transaction as _};
-use alloy_eips::eip4844::MAX_DATA_GAS_PER_BLOCK;
+use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
 use alloy_primitives::{Keccak256, U256};
 use alloy_rpc_types_mev::{EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult};
 use jsonrpsee::core::RpcResult;
@@ -88,17 +88,6 @@ where
             .into_iter()
             .collect::<Vec<_>>();
 
-        // Validate that the bundle does not contain more than MAX_BLOB_NUMBER_PER_BLOCK blob
-        // transactions.
-        if transactions.iter().filter_map(|tx| tx.blob_gas_used()).sum::<u64>() >
-            MAX_DATA_GAS_PER_BLOCK
-        {
-            return Err(EthApiError::InvalidParams(
-                EthBundleError::Eip4844BlobGasExceeded.to_string(),
-            )
-            .into())
-        }
-
         let block_id: alloy_rpc_types_eth::BlockId = state_block_number.into();
         // Note: the block number is considered the `parent` block: <https://github.com/flashbots/mev-geth/blob/fddf97beec5877483f879a77b7dea2e58a58d653/internal/ethapi/api.go#L2104>
         let (mut evm_env, at) = self.eth_api().evm_env_at(block_id).await?;
@@ -114,6 +103,21 @@ where
             evm_env.block_env.timestamp += U256::from(12);
         }
 
+        // Validate that the bundle does not contain more blob gas than the max blob gas per block
+        // of the fork active at the simulated block's timestamp.
+        let max_blob_gas_per_block = RpcNodeCore::provider(self.eth_api())
+            .chain_spec()
+            .blob_params_at_timestamp(evm_env.block_env.timestamp.saturating_to())
+            .map_or(0, |params| params.max_blob_count * DATA_GAS_PER_BLOB);
+        if transactions.iter().filter_map(|tx| tx.blob_gas_used()).sum::<u64>() >
+            max_blob_gas_per_block
+        {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::Eip4844BlobGasExceeded(max_blob_gas_per_block).to_string(),
+            )
+            .into())
+        }
+
         if let Some(difficulty) = difficulty {
             evm_env.block_env.difficulty = U256::from(difficulty);
         }
@@ -314,6 +318,6 @@ pub enum EthBundleError {
     #[error("bundle missing blockNumber")]
     BundleMissingBlockNumber,
     /// Thrown when the blob gas usage of the blob transactions in a bundle exceed the maximum.
-    #[error("blob gas usage exceeds the limit of {MAX_DATA_GAS_PER_BLOCK} gas per block.")]
-    Eip4844BlobGasExceeded,
+    #[error("blob gas usage exceeds the limit of {0} gas per block.")]
+    Eip4844BlobGasExceeded(u64),
 }
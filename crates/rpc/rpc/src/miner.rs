@@ -1,25 +1,52 @@
+use alloy_consensus::constants::MAXIMUM_EXTRA_DATA_SIZE;
 use alloy_primitives::{Bytes, U128};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use reth_payload_util::MinerConfig;
 use reth_rpc_api::MinerApiServer;
+use reth_rpc_server_types::result::invalid_params_rpc_err;
 
 /// `miner` API implementation.
 ///
 /// This type provides the functionality for handling `miner` related requests.
+///
+/// Updates are applied to the shared [`MinerConfig`] and take effect for the next payload job.
+/// Without a [`MinerConfig`] all updates are rejected.
 #[derive(Clone, Debug, Default)]
-pub struct MinerApi {}
+pub struct MinerApi {
+    miner_config: Option<MinerConfig>,
+}
+
+impl MinerApi {
+    /// Creates a new instance of `MinerApi` that updates the given [`MinerConfig`].
+    pub const fn new(miner_config: MinerConfig) -> Self {
+        Self { miner_config: Some(miner_config) }
+    }
+}
 
 #[async_trait]
 impl MinerApiServer for MinerApi {
-    fn set_extra(&self, _record: Bytes) -> RpcResult<bool> {
-        Ok(false)
+    fn set_extra(&self, record: Bytes) -> RpcResult<bool> {
+        let Some(miner_config) = &self.miner_config else { return Ok(false) };
+        if record.len() > MAXIMUM_EXTRA_DATA_SIZE {
+            return Err(invalid_params_rpc_err(format!(
+                "extra data size {} exceeds {MAXIMUM_EXTRA_DATA_SIZE}-byte limit",
+                record.len()
+            )))
+        }
+        miner_config.set_extra_data(record);
+        Ok(true)
     }
 
-    fn set_gas_price(&self, _gas_price: U128) -> RpcResult<bool> {
-        Ok(false)
+    fn set_gas_price(&self, gas_price: U128) -> RpcResult<bool> {
+        let Some(miner_config) = &self.miner_config else { return Ok(false) };
+        miner_config.set_minimum_priority_fee(gas_price.to());
+        Ok(true)
     }
 
-    fn set_gas_limit(&self, _gas_price: U128) -> RpcResult<bool> {
-        Ok(false)
+    fn set_gas_limit(&self, gas_limit: U128) -> RpcResult<bool> {
+        let Some(miner_config) = &self.miner_config else { return Ok(false) };
+        miner_config.set_gas_limit(gas_limit.saturating_to());
+        Ok(true)
     }
 }
@@ -8,6 +8,7 @@ use reth_errors::RethResult;
 use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
 use reth_rpc_api::RethApiServer;
 use reth_rpc_eth_types::{EthApiError, EthResult};
+use reth_rpc_server_types::result::invalid_params_rpc_err;
 use reth_tasks::TaskSpawner;
 use tokio::sync::oneshot;
 
@@ -97,6 +98,13 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_setLogFilter`
+    async fn reth_set_log_filter(&self, filter: String) -> RpcResult<()> {
+        crate::debug::log_filter_handle()?
+            .set_filter(&filter)
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))
+    }
 }
 
 impl<Provider> std::fmt::Debug for RethApi<Provider> {
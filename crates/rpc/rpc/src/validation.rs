@@ -347,6 +347,7 @@ where
                     parent_beacon_block_root: request.parent_beacon_block_root,
                     versioned_hashes: self.validate_blobs_bundle(request.request.blobs_bundle)?,
                 }),
+                None,
             )?
             .try_recover()
             .map_err(|_| ValidationApiError::InvalidTransactionSignature)?;
@@ -380,6 +381,7 @@ where
                         ),
                     },
                 ),
+                None,
             )?
             .try_recover()
             .map_err(|_| ValidationApiError::InvalidTransactionSignature)?;
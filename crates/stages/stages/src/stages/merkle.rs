@@ -251,7 +251,7 @@ where
         } else {
             debug!(target: "sync::stages::merkle::exec", current = ?current_block_number, target = ?to_block, "Updating trie");
             let (root, updates) =
-                StateRoot::incremental_root_with_updates(provider.tx_ref(), range)
+                StateRoot::incremental_root_with_updates(provider.tx_ref(), range.clone())
                     .map_err(|e| {
                         error!(target: "sync::stages::merkle", %e, ?current_block_number, ?to_block, "Incremental state root failed! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
                         StageError::Fatal(Box::new(e))
@@ -259,6 +259,9 @@ where
 
             provider.write_trie_updates(&updates)?;
 
+            // Record the trie changesets of the most recent blocks, if enabled.
+            let root = provider.write_trie_changesets_range(range)?.unwrap_or(root);
+
             let total_hashed_entries = (provider.count_entries::<tables::HashedAccounts>()? +
                 provider.count_entries::<tables::HashedStorages>()?)
                 as u64;
@@ -299,6 +302,9 @@ where
             return Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
         }
 
+        // Trie changesets of the unwound blocks are no longer valid.
+        provider.remove_trie_changesets_from(input.unwind_to + 1)?;
+
         let mut entities_checkpoint =
             input.checkpoint.entities_stage_checkpoint().unwrap_or(EntitiesCheckpoint {
                 processed: 0,
@@ -379,7 +385,13 @@ mod tests {
         self, random_block, random_block_range, random_changeset_range,
         random_contract_account_range, BlockParams, BlockRangeParams,
     };
-    use reth_trie::test_utils::{state_root, state_root_prehashed};
+    use reth_trie::{
+        prefix_set::TriePrefixSetsMut,
+        test_utils::{state_root, state_root_prehashed},
+        updates::TrieUpdates,
+        HashedPostState, KeccakKeyHasher, TrieInput,
+    };
+    use reth_trie_db::{DatabaseHashedPostState, DatabaseTrieChangeSets, DatabaseTrieUpdates};
     use std::collections::BTreeMap;
 
     stage_test_suite_ext!(MerkleTestRunner, merkle);
@@ -465,6 +477,46 @@ mod tests {
         assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
     }
 
+    /// Update small trie and record the trie changesets of every block
+    #[tokio::test]
+    async fn execute_small_merkle_with_trie_changesets() {
+        let (previous_stage, stage_progress) = (4, 1);
+
+        // Set up the runner
+        let mut runner = MerkleTestRunner::default();
+        runner.db.factory = runner.db.factory.clone().with_trie_changesets(Some(2));
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+
+        runner.seed_execution(input).expect("failed to seed execution");
+
+        let rx = runner.execute(input);
+
+        // Assert the successful result
+        let result = rx.await.unwrap();
+        assert_matches!(result, Ok(ExecOutput { done: true, .. }));
+
+        // Only the changesets of the last two blocks are recorded, and the nodes they restore
+        // yield the same state root before them as recomputing it from the reverted state.
+        let provider = runner.db.factory.provider().unwrap();
+        let tx = provider.tx_ref();
+        let changesets = DatabaseTrieChangeSets::new(tx);
+        assert!(changesets.is_available(previous_stage - 1..=previous_stage).unwrap());
+        assert!(!changesets.is_available(previous_stage - 2..=previous_stage).unwrap());
+
+        let revert_state =
+            HashedPostState::from_reverts::<KeccakKeyHasher>(tx, previous_stage - 1).unwrap();
+        let expected = StateRoot::overlay_root(tx, revert_state.clone()).unwrap();
+        let input = TrieInput::new(
+            TrieUpdates::from_reverts(tx, previous_stage - 1).unwrap(),
+            revert_state,
+            TriePrefixSetsMut::default(),
+        );
+        assert_eq!(StateRoot::overlay_root_from_nodes(tx, input).unwrap(), expected);
+    }
+
     struct MerkleTestRunner {
         db: TestStageDB,
         clean_threshold: u64,
@@ -0,0 +1,77 @@
+//! Pure-Rust embedded database backend, an alternative to MDBX.
+//!
+//! The functions in this module mirror the ones in the `mdbx` module, so the backend is selected by
+//! the function used to initialize the database.
+//!
+//! The whole database is kept in memory and only the commit log is stored on disk, so the backend
+//! is suited for tests and small databases. It's not enabled by default, see the `btree` feature.
+
+use crate::{is_database_empty, TableSet, Tables};
+use eyre::Context;
+use std::path::Path;
+
+pub use crate::implementation::btree::*;
+
+/// Creates a new database at the specified path if it doesn't exist. Does NOT create tables. Check
+/// [`init_db`].
+pub fn create_db<P: AsRef<Path>>(
+    path: P,
+    args: BTreeDatabaseArguments,
+) -> eyre::Result<BTreeDatabaseEnv> {
+    use crate::version::{check_db_version_file, create_db_version_file, DatabaseVersionError};
+
+    let rpath = path.as_ref();
+    if is_database_empty(rpath) {
+        reth_fs_util::create_dir_all(rpath)
+            .wrap_err_with(|| format!("Could not create database directory {}", rpath.display()))?;
+        create_db_version_file(rpath)?;
+    } else {
+        match check_db_version_file(rpath) {
+            Ok(_) => (),
+            Err(DatabaseVersionError::MissingFile) => create_db_version_file(rpath)?,
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Ok(BTreeDatabaseEnv::open(rpath, args)?)
+}
+
+/// Opens up an existing database or creates a new one at the specified path. Creates tables defined
+/// in [`Tables`] if necessary. Read/Write mode.
+pub fn init_db<P: AsRef<Path>>(
+    path: P,
+    args: BTreeDatabaseArguments,
+) -> eyre::Result<BTreeDatabaseEnv> {
+    init_db_for::<P, Tables>(path, args)
+}
+
+/// Opens up an existing database or creates a new one at the specified path. Creates tables defined
+/// in the given [`TableSet`] if necessary. Read/Write mode.
+pub fn init_db_for<P: AsRef<Path>, TS: TableSet>(
+    path: P,
+    args: BTreeDatabaseArguments,
+) -> eyre::Result<BTreeDatabaseEnv> {
+    let client_version = args.client_version().clone();
+    let db = create_db(path, args)?;
+    db.create_tables_for::<TS>()?;
+    db.record_client_version(client_version)?;
+    Ok(db)
+}
+
+/// Opens up an existing database. Read only mode. It doesn't create it or create tables if missing.
+pub fn open_db_read_only(
+    path: &Path,
+    args: BTreeDatabaseArguments,
+) -> eyre::Result<BTreeDatabaseEnv> {
+    BTreeDatabaseEnv::open_read_only(path, args)
+        .with_context(|| format!("Could not open database at path: {}", path.display()))
+}
+
+/// Opens up an existing database. Read/Write mode. It doesn't create it or create tables if
+/// missing.
+pub fn open_db(path: &Path, args: BTreeDatabaseArguments) -> eyre::Result<BTreeDatabaseEnv> {
+    let db = BTreeDatabaseEnv::open(path, args.clone())
+        .with_context(|| format!("Could not open database at path: {}", path.display()))?;
+    db.record_client_version(args.client_version().clone())?;
+    Ok(db)
+}
@@ -0,0 +1,398 @@
+//! Cursors of the B-tree database.
+
+use super::{
+    table::{Entry, TableData, TableView},
+    tx::{TransactionKind, WriteState, RW},
+    BTreeError,
+};
+use crate::{
+    tables::utils::{decode_one, decoder},
+    DatabaseError,
+};
+use parking_lot::Mutex;
+use reth_db_api::{
+    common::{PairResult, ValueOnlyResult},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
+        ReverseWalker, Walker,
+    },
+    table::{Compress, DupSort, Encode, Table, TableRow},
+};
+use reth_storage_errors::db::{DatabaseWriteError, DatabaseWriteOperation};
+use std::{
+    borrow::Cow,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    sync::Arc,
+};
+
+/// Read only Cursor.
+pub type CursorRO<T> = Cursor<super::tx::RO, T>;
+/// Read write cursor.
+pub type CursorRW<T> = Cursor<RW, T>;
+
+/// Table contents a cursor operates on.
+#[derive(Debug)]
+pub(crate) enum CursorSource {
+    /// Committed table data of a read-only transaction.
+    ReadOnly(Arc<TableData>),
+    /// Uncommitted state of a read-write transaction.
+    ReadWrite(Arc<Mutex<WriteState>>),
+}
+
+/// Position of a cursor.
+#[derive(Debug, Clone)]
+enum Position {
+    /// The cursor wasn't positioned yet, or moved before the first entry.
+    Unset,
+    /// The cursor points at the entry. The entry might have been deleted since, in which case the
+    /// cursor behaves as if it pointed at the following entry.
+    At(Entry),
+    /// The cursor moved past the last entry.
+    End,
+}
+
+/// Cursor over a table of the B-tree database.
+#[derive(Debug)]
+pub struct Cursor<K: TransactionKind, T: Table> {
+    source: CursorSource,
+    position: Position,
+    _marker: PhantomData<(K, T)>,
+}
+
+/// Compresses a table value into an owned buffer.
+pub(crate) fn compress_value<T: Table>(value: &T::Value) -> Vec<u8> {
+    value.uncompressable_ref().map(<[u8]>::to_vec).unwrap_or_else(|| {
+        let mut buf = Vec::new();
+        value.compress_to_buf(&mut buf);
+        buf
+    })
+}
+
+/// Decodes an encoded `(key, value)` pair.
+fn decode_entry<T: Table>((key, value): Entry) -> Result<TableRow<T>, DatabaseError> {
+    decoder::<T>((Cow::Owned(key), Cow::Owned(value)))
+}
+
+impl<K: TransactionKind, T: Table> Cursor<K, T> {
+    pub(crate) const fn new(source: CursorSource) -> Self {
+        Self { source, position: Position::Unset, _marker: PhantomData }
+    }
+
+    /// Executes the closure with a read view of the table.
+    fn read<R>(&self, f: impl FnOnce(TableView<'_>) -> R) -> Result<R, DatabaseError> {
+        match &self.source {
+            CursorSource::ReadOnly(data) => Ok(f(TableView::new(Some(data), None))),
+            CursorSource::ReadWrite(state) => Ok(f(state.lock().view(T::NAME)?)),
+        }
+    }
+
+    /// Returns the entry the cursor currently points at.
+    fn current_entry(&self) -> Result<Option<Entry>, DatabaseError> {
+        match &self.position {
+            Position::At(entry) => self.read(|view| view.next(Bound::Included(entry))),
+            Position::Unset | Position::End => Ok(None),
+        }
+    }
+
+    /// Moves the cursor to the entry, or to `fallback` if there is none.
+    fn move_to(&mut self, entry: Option<Entry>, fallback: Position) -> Option<Entry> {
+        self.position = entry.clone().map_or(fallback, Position::At);
+        entry
+    }
+
+    /// Moves the cursor to the entry returned by the closure and decodes it. If the closure returns
+    /// [`None`], the cursor is moved to `fallback`.
+    fn move_with(
+        &mut self,
+        fallback: Position,
+        f: impl FnOnce(TableView<'_>, &Position) -> Option<Entry>,
+    ) -> PairResult<T> {
+        let entry = self.read(|view| f(view, &self.position))?;
+        self.move_to(entry, fallback).map(decode_entry::<T>).transpose()
+    }
+}
+
+impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
+    fn first(&mut self) -> PairResult<T> {
+        self.move_with(Position::End, |view, _| view.first())
+    }
+
+    fn seek_exact(&mut self, key: T::Key) -> PairResult<T> {
+        let key: Vec<u8> = key.encode().into();
+        // Like MDBX, position the cursor at the next key if the exact key doesn't exist.
+        let entry = self.read(|view| view.seek(&key))?;
+        self.move_to(entry, Position::End)
+            .filter(|(k, _)| *k == key)
+            .map(decode_entry::<T>)
+            .transpose()
+    }
+
+    fn seek(&mut self, key: T::Key) -> PairResult<T> {
+        let key: Vec<u8> = key.encode().into();
+        self.move_with(Position::End, |view, _| view.seek(&key))
+    }
+
+    fn next(&mut self) -> PairResult<T> {
+        // Like MDBX, stay at the last entry if there are no more entries.
+        self.move_with(self.position.clone(), |view, position| match position {
+            Position::Unset => view.first(),
+            Position::At(entry) => view.next(Bound::Excluded(entry)),
+            Position::End => None,
+        })
+    }
+
+    fn prev(&mut self) -> PairResult<T> {
+        self.move_with(self.position.clone(), |view, position| match position {
+            Position::Unset | Position::End => view.last(),
+            Position::At(entry) => view.prev(Bound::Excluded(entry)),
+        })
+    }
+
+    fn last(&mut self) -> PairResult<T> {
+        self.move_with(Position::Unset, |view, _| view.last())
+    }
+
+    fn current(&mut self) -> PairResult<T> {
+        self.current_entry()?.map(decode_entry::<T>).transpose()
+    }
+
+    fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
+        let start = if let Some(start_key) = start_key {
+            self.seek(start_key).transpose()
+        } else {
+            self.first().transpose()
+        };
+
+        Ok(Walker::new(self, start))
+    }
+
+    fn walk_range(
+        &mut self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<RangeWalker<'_, T, Self>, DatabaseError> {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => self.seek(key),
+            Bound::Excluded(_key) => {
+                unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
+            }
+            Bound::Unbounded => self.first(),
+        }
+        .transpose();
+
+        Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
+    }
+
+    fn walk_back(
+        &mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
+        let start =
+            if let Some(start_key) = start_key { self.seek(start_key) } else { self.last() }
+                .transpose();
+
+        Ok(ReverseWalker::new(self, start))
+    }
+}
+
+impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
+    /// Returns the next `(key, value)` pair of a DUPSORT table.
+    fn next_dup(&mut self) -> PairResult<T> {
+        self.move_with(self.position.clone(), |view, position| match position {
+            Position::Unset => view.first(),
+            Position::At(current) => {
+                view.next(Bound::Excluded(current)).filter(|(key, _)| *key == current.0)
+            }
+            Position::End => None,
+        })
+    }
+
+    /// Returns the next `(key, value)` pair skipping the duplicates.
+    fn next_no_dup(&mut self) -> PairResult<T> {
+        self.move_with(self.position.clone(), |view, position| match position {
+            Position::Unset => view.first(),
+            Position::At((key, _)) => view.next_key(key),
+            Position::End => None,
+        })
+    }
+
+    /// Returns the next `value` of a duplicate `key`.
+    fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        Ok(self.next_dup()?.map(|(_, value)| value))
+    }
+
+    fn seek_by_key_subkey(&mut self, key: T::Key, subkey: T::SubKey) -> ValueOnlyResult<T> {
+        let key: Vec<u8> = key.encode().into();
+        let subkey: Vec<u8> = subkey.encode().into();
+        let Some(entry) = self.read(|view| view.seek_by_key_subkey(&key, &subkey))? else {
+            return Ok(None)
+        };
+        self.position = Position::At(entry.clone());
+        decode_one::<T>(Cow::Owned(entry.1)).map(Some)
+    }
+
+    /// Depending on its arguments, returns an iterator starting at:
+    /// - Some(key), Some(subkey): a `key` item whose data is >= than `subkey`
+    /// - Some(key), None: first item of a specified `key`
+    /// - None, Some(subkey): like first case, but in the first key
+    /// - None, None: first item in the table of a DUPSORT table.
+    fn walk_dup(
+        &mut self,
+        key: Option<T::Key>,
+        subkey: Option<T::SubKey>,
+    ) -> Result<DupWalker<'_, T, Self>, DatabaseError> {
+        let key: Option<Vec<u8>> = match key {
+            Some(key) => Some(key.encode().into()),
+            None => self.read(|view| view.first())?.map(|(key, _)| key),
+        };
+        let subkey: Vec<u8> = subkey.map(|subkey| subkey.encode().into()).unwrap_or_default();
+
+        let entry = match key {
+            Some(key) => self.read(|view| view.seek_by_key_subkey(&key, &subkey))?,
+            None => None,
+        };
+        if let Some(entry) = &entry {
+            self.position = Position::At(entry.clone());
+        }
+        let start = entry.map(decode_entry::<T>);
+
+        Ok(DupWalker::<'_, T, Self> { cursor: self, start })
+    }
+}
+
+impl<T: Table> Cursor<RW, T> {
+    /// Executes the closure with the uncommitted state of the transaction.
+    fn write<R>(
+        &self,
+        f: impl FnOnce(&mut WriteState) -> Result<R, DatabaseError>,
+    ) -> Result<R, DatabaseError> {
+        match &self.source {
+            CursorSource::ReadWrite(state) => f(&mut state.lock()),
+            CursorSource::ReadOnly(_) => {
+                Err(DatabaseError::InitCursor(BTreeError::ReadOnly.into()))
+            }
+        }
+    }
+
+    /// Inserts the entry if `check` passes for the last entry of the table, returning
+    /// [`BTreeError::KeyMismatch`] otherwise.
+    fn append_checked(
+        &mut self,
+        entry: Entry,
+        operation: DatabaseWriteOperation,
+        check: impl FnOnce(&Entry, &Entry, bool) -> bool,
+    ) -> Result<(), DatabaseError> {
+        let last = self.write(|state| {
+            let view = state.view(T::NAME)?;
+            let last = view.last();
+            if last.as_ref().is_none_or(|last| check(last, &entry, view.is_dupsort())) {
+                state.insert(T::NAME, entry.clone())?;
+                Ok(None)
+            } else {
+                Ok(last)
+            }
+        })?;
+
+        match last {
+            None => {
+                self.position = Position::At(entry);
+                Ok(())
+            }
+            Some(last) => {
+                self.position = Position::At(last);
+                Err(DatabaseWriteError {
+                    info: BTreeError::KeyMismatch.into(),
+                    operation,
+                    table_name: T::NAME,
+                    key: entry.0,
+                }
+                .into())
+            }
+        }
+    }
+}
+
+impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
+    /// Database operation that will update an existing row if a specified value already
+    /// exists in a table, and insert a new row if the specified value doesn't already exist
+    ///
+    /// For a DUPSORT table, `upsert` will not actually update-or-insert. If the key already exists,
+    /// it will append the value to the subkey, even if the subkeys are the same. So if you want
+    /// to properly upsert, you'll need to `seek_exact` & `delete_current` if the key+subkey was
+    /// found, before calling `upsert`.
+    fn upsert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        let entry = (key.encode().into(), compress_value::<T>(value));
+        self.write(|state| state.upsert(T::NAME, entry.clone()))?;
+        self.position = Position::At(entry);
+        Ok(())
+    }
+
+    fn insert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        let entry: Entry = (key.encode().into(), compress_value::<T>(value));
+        let existing = self.write(|state| {
+            let existing = state.view(T::NAME)?.seek_exact(&entry.0);
+            if existing.is_none() {
+                state.insert(T::NAME, entry.clone())?;
+            }
+            Ok(existing)
+        })?;
+
+        match existing {
+            None => {
+                self.position = Position::At(entry);
+                Ok(())
+            }
+            Some(existing) => {
+                self.position = Position::At(existing);
+                Err(DatabaseWriteError {
+                    info: BTreeError::KeyExist.into(),
+                    operation: DatabaseWriteOperation::CursorInsert,
+                    table_name: T::NAME,
+                    key: entry.0,
+                }
+                .into())
+            }
+        }
+    }
+
+    /// Appends the data to the end of the table. Consequently, the append operation
+    /// will fail if the inserted key is less than the last table key
+    fn append(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        let entry = (key.encode().into(), compress_value::<T>(value));
+        self.append_checked(entry, DatabaseWriteOperation::CursorAppend, |last, entry, dupsort| {
+            if dupsort {
+                last.0 <= entry.0
+            } else {
+                last.0 < entry.0
+            }
+        })
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        let Some(current) = self.current_entry()? else { return Ok(()) };
+        self.write(|state| state.remove(T::NAME, &current))?;
+        self.position = Position::At(current);
+        Ok(())
+    }
+}
+
+impl<T: DupSort> DbDupCursorRW<T> for Cursor<RW, T> {
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        let Some(current) = self.current_entry()? else { return Ok(()) };
+        self.write(|state| {
+            for entry in state.view(T::NAME)?.duplicates(&current.0) {
+                state.remove(T::NAME, &entry)?;
+            }
+            Ok(())
+        })?;
+        self.position = Position::At(current);
+        Ok(())
+    }
+
+    fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let entry = (key.encode().into(), compress_value::<T>(&value));
+        self.append_checked(entry, DatabaseWriteOperation::CursorAppendDup, |last, entry, _| {
+            last < entry
+        })
+    }
+}
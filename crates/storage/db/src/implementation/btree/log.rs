@@ -0,0 +1,372 @@
+//! Append-only commit log used to persist the B-tree database.
+//!
+//! The log starts with [`LOG_MAGIC`], followed by a sequence of frames. Every committed read-write
+//! transaction is written as a single frame:
+//!
+//! ```text
+//! frame   := payload_len: u64 LE | checksum: u64 LE | payload
+//! payload := op*
+//! op      := TABLE name dupsort: u8 | CLEAR | INSERT key value | REMOVE key value
+//! bytes   := len: u32 LE | data
+//! ```
+//!
+//! `TABLE` selects the table the following operations apply to, creating it if necessary. A frame
+//! whose length or checksum doesn't match is considered to be a torn write and marks the end of the
+//! log.
+
+use super::table::{Entry, Snapshot, TableData};
+use reth_tracing::tracing::warn;
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Seek, SeekFrom, Write},
+    path::Path,
+    sync::Arc,
+};
+
+/// Magic bytes at the beginning of the log file.
+pub(crate) const LOG_MAGIC: &[u8; 8] = b"RETHBT01";
+
+/// Size of the frame header: payload length and checksum.
+const FRAME_HEADER_SIZE: usize = 8 + 8;
+
+/// Maximum size of a single frame written during compaction.
+const MAX_COMPACTION_FRAME_SIZE: usize = 64 * 1024 * 1024;
+
+const OP_TABLE: u8 = 0;
+const OP_CLEAR: u8 = 1;
+const OP_INSERT: u8 = 2;
+const OP_REMOVE: u8 = 3;
+
+/// Builder for the payload of a single log frame.
+#[derive(Debug, Default)]
+pub(crate) struct FrameBuilder {
+    payload: Vec<u8>,
+}
+
+impl FrameBuilder {
+    /// Returns `true` if no operations were recorded.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.payload.is_empty()
+    }
+
+    /// Returns the size of the payload recorded so far.
+    pub(crate) fn len(&self) -> usize {
+        self.payload.len()
+    }
+
+    /// Selects the table that the following operations apply to.
+    pub(crate) fn table(&mut self, name: &str, dupsort: bool) {
+        self.payload.push(OP_TABLE);
+        self.bytes(name.as_bytes());
+        self.payload.push(dupsort as u8);
+    }
+
+    /// Records the removal of all entries of the selected table.
+    pub(crate) fn clear(&mut self) {
+        self.payload.push(OP_CLEAR);
+    }
+
+    /// Records the insertion of an entry into the selected table.
+    pub(crate) fn insert(&mut self, (key, value): &Entry) {
+        self.payload.push(OP_INSERT);
+        self.bytes(key);
+        self.bytes(value);
+    }
+
+    /// Records the removal of an entry from the selected table.
+    pub(crate) fn remove(&mut self, (key, value): &Entry) {
+        self.payload.push(OP_REMOVE);
+        self.bytes(key);
+        self.bytes(value);
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.payload.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        self.payload.extend_from_slice(bytes);
+    }
+
+    /// Encodes the frame, including its header.
+    pub(crate) fn finish(self) -> Vec<u8> {
+        let mut frame = Vec::with_capacity(FRAME_HEADER_SIZE + self.payload.len());
+        frame.extend_from_slice(&(self.payload.len() as u64).to_le_bytes());
+        frame.extend_from_slice(&checksum(&self.payload).to_le_bytes());
+        frame.extend_from_slice(&self.payload);
+        frame
+    }
+}
+
+/// Writer appending frames to the log file.
+#[derive(Debug)]
+pub(crate) struct LogWriter {
+    file: File,
+    /// Whether the file should be synced to disk after every frame.
+    sync: bool,
+}
+
+impl LogWriter {
+    /// Creates a new writer appending to the given file.
+    pub(crate) const fn new(file: File, sync: bool) -> Self {
+        Self { file, sync }
+    }
+
+    /// Appends an encoded frame to the log.
+    ///
+    /// If the frame can't be written completely, the log is truncated back to its previous length,
+    /// so that the following frames aren't hidden behind a torn one.
+    pub(crate) fn append(&mut self, frame: &[u8]) -> io::Result<()> {
+        let len = self.file.stream_position()?;
+        let result = self.file.write_all(frame).and_then(|_| {
+            if self.sync {
+                self.file.sync_data()?;
+            }
+            Ok(())
+        });
+        if result.is_err() {
+            self.file.set_len(len)?;
+            self.file.seek(SeekFrom::Start(len))?;
+        }
+        result
+    }
+
+    /// Replaces the underlying file, e.g. after compaction.
+    pub(crate) fn set_file(&mut self, file: File) {
+        self.file = file;
+    }
+}
+
+/// Result of replaying a log file.
+#[derive(Debug)]
+pub(crate) struct Replay {
+    /// Database contents after applying all valid frames.
+    pub(crate) snapshot: Snapshot,
+    /// Offset right after the last valid frame.
+    pub(crate) valid_len: u64,
+}
+
+/// Replays the log at the given path. A missing file is treated as an empty log.
+pub(crate) fn replay(path: &Path) -> io::Result<Replay> {
+    let mut snapshot = Snapshot::default();
+
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Ok(Replay { snapshot, valid_len: 0 })
+        }
+        Err(err) => return Err(err),
+    };
+    let file_len = file.metadata()?.len();
+    let mut reader = BufReader::new(file);
+
+    let mut magic = [0u8; LOG_MAGIC.len()];
+    if file_len < LOG_MAGIC.len() as u64 {
+        return Ok(Replay { snapshot, valid_len: 0 })
+    }
+    reader.read_exact(&mut magic)?;
+    if &magic != LOG_MAGIC {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid database log header"))
+    }
+
+    let mut valid_len = LOG_MAGIC.len() as u64;
+    let mut header = [0u8; FRAME_HEADER_SIZE];
+    let mut payload = Vec::new();
+    while valid_len < file_len {
+        if file_len - valid_len < FRAME_HEADER_SIZE as u64 {
+            break
+        }
+        reader.read_exact(&mut header)?;
+        let payload_len = u64::from_le_bytes(header[..8].try_into().unwrap());
+        let expected_checksum = u64::from_le_bytes(header[8..].try_into().unwrap());
+        if file_len - valid_len - (FRAME_HEADER_SIZE as u64) < payload_len {
+            break
+        }
+
+        payload.resize(payload_len as usize, 0);
+        reader.read_exact(&mut payload)?;
+        if checksum(&payload) != expected_checksum || apply(&mut snapshot, &payload).is_none() {
+            break
+        }
+
+        valid_len += FRAME_HEADER_SIZE as u64 + payload_len;
+    }
+
+    if valid_len < file_len {
+        warn!(
+            target: "storage::db::btree",
+            ?path,
+            valid_len,
+            file_len,
+            "Database log contains an incomplete or corrupted frame, ignoring the rest of the log"
+        );
+    }
+
+    Ok(Replay { snapshot, valid_len })
+}
+
+/// Opens the log for appending, creating it if necessary and discarding everything after
+/// `valid_len`.
+pub(crate) fn open_for_append(path: &Path, valid_len: u64) -> io::Result<File> {
+    let mut file =
+        File::options().read(true).write(true).create(true).truncate(false).open(path)?;
+    if valid_len == 0 {
+        file.set_len(0)?;
+        file.write_all(LOG_MAGIC)?;
+        file.sync_all()?;
+    } else if file.metadata()?.len() != valid_len {
+        file.set_len(valid_len)?;
+        file.sync_all()?;
+    }
+    file.seek(SeekFrom::End(0))?;
+    Ok(file)
+}
+
+/// Writes the full contents of the snapshot into a new log file at the given path.
+pub(crate) fn write_snapshot(path: &Path, snapshot: &Snapshot) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(LOG_MAGIC)?;
+
+    for (name, table) in snapshot {
+        let mut frame = FrameBuilder::default();
+        frame.table(name, table.dupsort);
+        for entry in table.entries.iter() {
+            if frame.len() >= MAX_COMPACTION_FRAME_SIZE {
+                file.write_all(&std::mem::take(&mut frame).finish())?;
+                frame.table(name, table.dupsort);
+            }
+            frame.insert(entry);
+        }
+        file.write_all(&frame.finish())?;
+    }
+
+    file.sync_all()
+}
+
+/// Applies the operations of a single frame to the snapshot. Returns [`None`] if the payload is
+/// malformed.
+fn apply(snapshot: &mut Snapshot, mut payload: &[u8]) -> Option<()> {
+    let mut table: Option<&mut TableData> = None;
+
+    while let Some((&op, rest)) = payload.split_first() {
+        payload = rest;
+        match op {
+            OP_TABLE => {
+                let name = std::str::from_utf8(read_bytes(&mut payload)?).ok()?;
+                let (&dupsort, rest) = payload.split_first()?;
+                payload = rest;
+                let data = snapshot
+                    .entry(name.to_string())
+                    .or_insert_with(|| Arc::new(TableData::new(dupsort != 0)));
+                table = Some(Arc::make_mut(data));
+            }
+            OP_CLEAR => table.as_mut()?.entries.clear(),
+            OP_INSERT | OP_REMOVE => {
+                let key = read_bytes(&mut payload)?.to_vec();
+                let value = read_bytes(&mut payload)?.to_vec();
+                let entries = &mut table.as_mut()?.entries;
+                if op == OP_INSERT {
+                    entries.insert((key, value));
+                } else {
+                    entries.remove(&(key, value));
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    Some(())
+}
+
+/// Reads length-prefixed bytes, advancing the buffer.
+fn read_bytes<'a>(buf: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let len = u32::from_le_bytes(buf.get(..4)?.try_into().ok()?) as usize;
+    let bytes = buf.get(4..4 + len)?;
+    *buf = &buf[4 + len..];
+    Some(bytes)
+}
+
+/// FNV-1a hash of the frame payload.
+fn checksum(payload: &[u8]) -> u64 {
+    payload
+        .iter()
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn frame(ops: impl FnOnce(&mut FrameBuilder)) -> Vec<u8> {
+        let mut builder = FrameBuilder::default();
+        ops(&mut builder);
+        builder.finish()
+    }
+
+    #[test]
+    fn replay_ignores_torn_frame() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log");
+
+        let mut file = open_for_append(&path, 0).unwrap();
+        file.write_all(&frame(|f| {
+            f.table("Table", false);
+            f.insert(&(vec![1], vec![2]));
+        }))
+        .unwrap();
+        let valid_len = file.metadata().unwrap().len();
+
+        let torn = frame(|f| {
+            f.table("Table", false);
+            f.insert(&(vec![3], vec![4]));
+        });
+        file.write_all(&torn[..torn.len() - 1]).unwrap();
+
+        let replay = replay(&path).unwrap();
+        assert_eq!(replay.valid_len, valid_len);
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), valid_len + torn.len() as u64 - 1);
+        let table = &replay.snapshot["Table"];
+        assert_eq!(table.entries.iter().collect::<Vec<_>>(), vec![&(vec![1], vec![2])]);
+
+        // Reopening for append truncates the torn frame.
+        let file = open_for_append(&path, replay.valid_len).unwrap();
+        assert_eq!(file.metadata().unwrap().len(), valid_len);
+    }
+
+    #[test]
+    fn replay_rejects_corrupted_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log");
+
+        let mut file = open_for_append(&path, 0).unwrap();
+        let mut corrupted = frame(|f| {
+            f.table("Table", true);
+            f.insert(&(vec![1], vec![2]));
+        });
+        *corrupted.last_mut().unwrap() ^= 0xff;
+        file.write_all(&corrupted).unwrap();
+
+        let replay = replay(&path).unwrap();
+        assert_eq!(replay.valid_len, LOG_MAGIC.len() as u64);
+        assert!(replay.snapshot.is_empty());
+    }
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log");
+
+        let mut snapshot = Snapshot::default();
+        let mut table = TableData::new(true);
+        table.entries.insert((vec![1], vec![1]));
+        table.entries.insert((vec![1], vec![2]));
+        snapshot.insert("Table".to_string(), Arc::new(table));
+        snapshot.insert("Empty".to_string(), Arc::new(TableData::new(false)));
+
+        write_snapshot(&path, &snapshot).unwrap();
+        let replay = replay(&path).unwrap();
+        assert_eq!(replay.valid_len, std::fs::metadata(&path).unwrap().len());
+        assert_eq!(replay.snapshot.len(), 2);
+        assert!(replay.snapshot["Table"].dupsort);
+        assert_eq!(replay.snapshot["Table"].entries, snapshot["Table"].entries);
+        assert_eq!(replay.snapshot["Empty"].entries.len(), 0);
+    }
+}
@@ -0,0 +1,552 @@
+//! Pure-Rust embedded database backend.
+//!
+//! All tables are kept in memory as ordered B-trees of encoded `(key, value)` pairs, and every
+//! committed read-write transaction is appended to a commit log in the database directory, see
+//! [`LOG_FILE_NAME`]. On open, the log is replayed to restore the committed state, and compacted
+//! if it grew considerably larger than the data it describes.
+//!
+//! Read-only transactions operate on an immutable snapshot of the committed state, so they never
+//! block and are never blocked by the writer. Only one read-write transaction can be open at a
+//! time, others wait until it is committed or aborted. Uncommitted changes are kept as an overlay
+//! on top of the committed state and merged into it on commit. Tables are persistent B-trees whose
+//! nodes are shared between versions, so a commit only copies the nodes it modifies that are still
+//! referenced by open read-only transactions.
+
+use self::log::{FrameBuilder, LogWriter};
+use crate::{
+    lockfile::StorageLock,
+    tables::{self, Tables},
+    DatabaseError, TableSet,
+};
+use metrics::Label;
+use parking_lot::{Condvar, Mutex, RwLock};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    database::Database,
+    database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
+    models::ClientVersion,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_storage_errors::db::DatabaseErrorInfo;
+use reth_tracing::tracing::{debug, info};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use table::{Snapshot, TableData};
+use tx::{Tx, RO, RW};
+
+pub mod cursor;
+mod log;
+mod table;
+mod tree;
+pub mod tx;
+
+/// Name of the commit log file in the database directory.
+pub const LOG_FILE_NAME: &str = "btree.log";
+
+/// The log is compacted on open only if it's larger than this size.
+const MIN_COMPACTION_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Errors specific to the B-tree database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum BTreeError {
+    /// The table doesn't exist.
+    #[error("table not found")]
+    TableNotFound,
+    /// The key already exists in the table.
+    #[error("key already exists")]
+    KeyExist,
+    /// The appended key or value is not greater than the last one in the table.
+    #[error("appended key or value is out of order")]
+    KeyMismatch,
+    /// The database was opened in read-only mode.
+    #[error("database is opened in read-only mode")]
+    ReadOnly,
+}
+
+impl From<BTreeError> for i32 {
+    fn from(error: BTreeError) -> Self {
+        match error {
+            BTreeError::TableNotFound => -1,
+            BTreeError::KeyExist => -2,
+            BTreeError::KeyMismatch => -3,
+            BTreeError::ReadOnly => -4,
+        }
+    }
+}
+
+/// Converts an I/O error into the database error info.
+fn io_error_info(error: io::Error) -> DatabaseErrorInfo {
+    DatabaseErrorInfo {
+        code: error.raw_os_error().unwrap_or(-1),
+        message: error.to_string().into(),
+    }
+}
+
+/// Arguments for B-tree database initialization.
+#[derive(Clone, Debug)]
+pub struct BTreeDatabaseArguments {
+    /// Client version that accesses the database.
+    client_version: ClientVersion,
+    /// Whether the commit log is synced to disk on every commit.
+    sync: bool,
+}
+
+impl Default for BTreeDatabaseArguments {
+    fn default() -> Self {
+        Self::new(ClientVersion::default())
+    }
+}
+
+impl BTreeDatabaseArguments {
+    /// Create new database arguments with given client version.
+    pub const fn new(client_version: ClientVersion) -> Self {
+        Self { client_version, sync: true }
+    }
+
+    /// Sets whether the commit log is synced to disk on every commit. Enabled by default.
+    ///
+    /// Disabling it makes commits cheaper, but the most recent commits may be lost on a system
+    /// crash.
+    pub const fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Returns the client version if any.
+    pub const fn client_version(&self) -> &ClientVersion {
+        &self.client_version
+    }
+}
+
+/// State shared between the environment and its transactions.
+#[derive(Debug)]
+pub(crate) struct EnvInner {
+    /// Committed state of all tables.
+    committed: RwLock<Arc<Snapshot>>,
+    /// Whether a read-write transaction is currently open.
+    writer_busy: Mutex<bool>,
+    /// Notified when the read-write transaction is closed.
+    writer_released: Condvar,
+    /// Commit log writer. [`None`] if the database is opened in read-only mode.
+    log: Option<Mutex<LogWriter>>,
+}
+
+impl EnvInner {
+    /// Returns the committed snapshot.
+    pub(crate) fn snapshot(&self) -> Arc<Snapshot> {
+        self.committed.read().clone()
+    }
+
+    /// Writes the uncommitted changes to the log and merges them into the committed state.
+    pub(crate) fn commit(&self, state: tx::WriteState) -> Result<(), DatabaseError> {
+        let tx::WriteState { base, overlays } = state;
+
+        let mut frame = FrameBuilder::default();
+        for (name, overlay) in &overlays {
+            // Skip tables that were only read, or created while already existing.
+            if overlay.changes.is_empty() && !overlay.cleared && base.contains_key(name) {
+                continue
+            }
+            frame.table(name, overlay.dupsort);
+            if overlay.cleared {
+                frame.clear();
+            }
+            for (entry, present) in &overlay.changes {
+                if *present {
+                    frame.insert(entry);
+                } else {
+                    frame.remove(entry);
+                }
+            }
+        }
+        if frame.is_empty() {
+            return Ok(())
+        }
+
+        let log =
+            self.log.as_ref().ok_or_else(|| DatabaseError::Commit(BTreeError::ReadOnly.into()))?;
+        log.lock()
+            .append(&frame.finish())
+            .map_err(|err| DatabaseError::Commit(io_error_info(err)))?;
+
+        // Release the reference to the committed state, so that it can be modified in place.
+        drop(base);
+        let mut committed = self.committed.write();
+        // Tables are modified in place unless there are open read transactions referencing them, in
+        // which case only the modified nodes are copied.
+        let mut snapshot =
+            Arc::try_unwrap(std::mem::take(&mut *committed)).unwrap_or_else(|s| (*s).clone());
+        for (name, overlay) in overlays {
+            let data =
+                snapshot.entry(name).or_insert_with(|| Arc::new(TableData::new(overlay.dupsort)));
+            overlay.apply_to(Arc::make_mut(data));
+        }
+        *committed = Arc::new(snapshot);
+
+        Ok(())
+    }
+}
+
+/// Exclusive writer lock of the environment, released on drop.
+#[derive(Debug)]
+pub(crate) struct WriterGuard(Arc<EnvInner>);
+
+impl WriterGuard {
+    /// Waits until no other read-write transaction is open and acquires the lock.
+    fn acquire(env: Arc<EnvInner>) -> Self {
+        let mut busy = env.writer_busy.lock();
+        while *busy {
+            env.writer_released.wait(&mut busy);
+        }
+        *busy = true;
+        drop(busy);
+        Self(env)
+    }
+
+    /// Returns the environment the lock belongs to.
+    pub(crate) fn env(&self) -> &EnvInner {
+        &self.0
+    }
+}
+
+impl Drop for WriterGuard {
+    fn drop(&mut self) {
+        *self.0.writer_busy.lock() = false;
+        self.0.writer_released.notify_one();
+    }
+}
+
+/// Pure-Rust embedded database environment.
+#[derive(Debug)]
+pub struct BTreeDatabaseEnv {
+    /// Shared state of the environment.
+    inner: Arc<EnvInner>,
+    /// Path to the commit log.
+    log_path: PathBuf,
+    /// Write lock for when dealing with a read-write environment.
+    _lock_file: Option<StorageLock>,
+}
+
+impl Database for BTreeDatabaseEnv {
+    type TX = Tx<RO>;
+    type TXMut = Tx<RW>;
+
+    fn tx(&self) -> Result<Self::TX, DatabaseError> {
+        Ok(Tx::new_ro(self.inner.snapshot()))
+    }
+
+    fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
+        if self.inner.log.is_none() {
+            return Err(DatabaseError::InitTx(BTreeError::ReadOnly.into()))
+        }
+        Ok(Tx::new_rw(WriterGuard::acquire(self.inner.clone())))
+    }
+}
+
+impl DatabaseMetrics for BTreeDatabaseEnv {
+    fn gauge_metrics(&self) -> Vec<(&'static str, f64, Vec<Label>)> {
+        let snapshot = self.inner.snapshot();
+        let mut metrics = Tables::ALL
+            .iter()
+            .filter_map(|table| {
+                let data = snapshot.get(table.name())?;
+                Some((
+                    "db.table_entries",
+                    data.entries.len() as f64,
+                    vec![Label::new("table", table.name())],
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        if let Ok(metadata) = reth_fs_util::metadata(&self.log_path) {
+            metrics.push(("db.log_size", metadata.len() as f64, vec![]));
+        }
+
+        metrics
+    }
+}
+
+impl DatabaseMetadata for BTreeDatabaseEnv {
+    fn metadata(&self) -> DatabaseMetadataValue {
+        DatabaseMetadataValue::new(None)
+    }
+}
+
+impl BTreeDatabaseEnv {
+    /// Opens the database at the specified path in read-write mode, replaying the commit log.
+    ///
+    /// It does not create the tables, for that call [`BTreeDatabaseEnv::create_tables`].
+    pub fn open(path: &Path, args: BTreeDatabaseArguments) -> Result<Self, DatabaseError> {
+        let _lock_file =
+            StorageLock::try_acquire(path).map_err(|err| DatabaseError::Other(err.to_string()))?;
+
+        let log_path = path.join(LOG_FILE_NAME);
+        let replay =
+            log::replay(&log_path).map_err(|err| DatabaseError::Open(io_error_info(err)))?;
+
+        let live_size = replay.snapshot.values().map(|table| table.size() as u64).sum::<u64>();
+        let file = if replay.valid_len > MIN_COMPACTION_SIZE && replay.valid_len > 2 * live_size {
+            info!(
+                target: "storage::db::btree",
+                log_size = replay.valid_len,
+                live_size,
+                "Compacting database log"
+            );
+            compact_log(&log_path, &replay.snapshot)
+        } else {
+            log::open_for_append(&log_path, replay.valid_len)
+        }
+        .map_err(|err| DatabaseError::Open(io_error_info(err)))?;
+
+        Ok(Self {
+            inner: Arc::new(EnvInner {
+                committed: RwLock::new(Arc::new(replay.snapshot)),
+                writer_busy: Mutex::new(false),
+                writer_released: Condvar::new(),
+                log: Some(Mutex::new(LogWriter::new(file, args.sync))),
+            }),
+            log_path,
+            _lock_file: Some(_lock_file),
+        })
+    }
+
+    /// Opens the database at the specified path in read-only mode.
+    ///
+    /// Read-only environments observe the state committed at the moment of opening.
+    pub fn open_read_only(
+        path: &Path,
+        _args: BTreeDatabaseArguments,
+    ) -> Result<Self, DatabaseError> {
+        let log_path = path.join(LOG_FILE_NAME);
+        let replay =
+            log::replay(&log_path).map_err(|err| DatabaseError::Open(io_error_info(err)))?;
+
+        Ok(Self {
+            inner: Arc::new(EnvInner {
+                committed: RwLock::new(Arc::new(replay.snapshot)),
+                writer_busy: Mutex::new(false),
+                writer_released: Condvar::new(),
+                log: None,
+            }),
+            log_path,
+            _lock_file: None,
+        })
+    }
+
+    /// Creates all the tables defined in [`Tables`], if necessary.
+    pub fn create_tables(&self) -> Result<(), DatabaseError> {
+        self.create_tables_for::<Tables>()
+    }
+
+    /// Creates all the tables defined in the given [`TableSet`], if necessary.
+    pub fn create_tables_for<TS: TableSet>(&self) -> Result<(), DatabaseError> {
+        let tx = self.tx_mut()?;
+        tx.write(|state| {
+            for table in TS::tables() {
+                state.create(table.name(), table.is_dupsort());
+            }
+            Ok(())
+        })?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Records version that accesses the database with write privileges.
+    pub fn record_client_version(&self, version: ClientVersion) -> Result<(), DatabaseError> {
+        if version.is_empty() {
+            return Ok(())
+        }
+
+        let tx = self.tx_mut()?;
+        let mut version_cursor = tx.cursor_write::<tables::VersionHistory>()?;
+
+        let last_version = version_cursor.last()?.map(|(_, v)| v);
+        if Some(&version) != last_version.as_ref() {
+            version_cursor.upsert(
+                SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+                &version,
+            )?;
+            tx.commit()?;
+        }
+
+        Ok(())
+    }
+
+    /// Rewrites the commit log so that it only contains the currently committed state.
+    ///
+    /// Waits for the open read-write transaction, if any, to finish.
+    pub fn compact(&self) -> Result<(), DatabaseError> {
+        let log = self
+            .inner
+            .log
+            .as_ref()
+            .ok_or_else(|| DatabaseError::Other(BTreeError::ReadOnly.to_string()))?;
+
+        let _guard = WriterGuard::acquire(self.inner.clone());
+        let file = compact_log(&self.log_path, &self.inner.snapshot())
+            .map_err(|err| DatabaseError::Other(err.to_string()))?;
+        log.lock().set_file(file);
+
+        Ok(())
+    }
+}
+
+/// Writes the snapshot into a fresh log, atomically replaces the log at the given path with it and
+/// returns the file opened for appending.
+fn compact_log(path: &Path, snapshot: &Snapshot) -> io::Result<std::fs::File> {
+    let tmp_path = path.with_extension("tmp");
+    log::write_snapshot(&tmp_path, snapshot)?;
+    std::fs::rename(&tmp_path, path)?;
+
+    let len = std::fs::metadata(path)?.len();
+    debug!(target: "storage::db::btree", ?path, len, "Compacted database log");
+    log::open_for_append(path, len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    database_test_suite!();
+
+    /// Create database for testing
+    fn create_test_db() -> Arc<BTreeDatabaseEnv> {
+        Arc::new(create_test_db_with_path(
+            &tempfile::TempDir::new().expect(ERROR_TEMPDIR).into_path(),
+        ))
+    }
+
+    /// Create database for testing with specified path
+    fn create_test_db_with_path(path: &Path) -> BTreeDatabaseEnv {
+        let env = BTreeDatabaseEnv::open(path, BTreeDatabaseArguments::default().with_sync(false))
+            .expect(ERROR_DB_CREATION);
+        env.create_tables().expect(ERROR_TABLE_CREATION);
+        env
+    }
+
+    /// Open existing database for testing in read-only mode
+    fn open_test_db_read_only(path: &Path) -> BTreeDatabaseEnv {
+        BTreeDatabaseEnv::open_read_only(path, BTreeDatabaseArguments::default())
+            .expect(ERROR_DB_CREATION)
+    }
+
+    fn key_exist_error() -> DatabaseErrorInfo {
+        BTreeError::KeyExist.into()
+    }
+
+    fn key_mismatch_error() -> DatabaseErrorInfo {
+        BTreeError::KeyMismatch.into()
+    }
+
+    #[test]
+    fn db_reopen_restores_committed_state() {
+        let path = TempDir::new().expect(ERROR_TEMPDIR).into_path();
+
+        {
+            let env = create_test_db_with_path(&path);
+            let tx = env.tx_mut().expect(ERROR_INIT_TX);
+            for key in 0..10 {
+                tx.put::<CanonicalHeaders>(key, B256::with_last_byte(key as u8)).expect(ERROR_PUT);
+            }
+            tx.commit().expect(ERROR_COMMIT);
+
+            let tx = env.tx_mut().expect(ERROR_INIT_TX);
+            tx.delete::<CanonicalHeaders>(3, None).expect(ERROR_DEL);
+            tx.clear::<Headers>().expect(ERROR_DEL);
+            tx.commit().expect(ERROR_COMMIT);
+
+            // Aborted changes must not be persisted.
+            let tx = env.tx_mut().expect(ERROR_INIT_TX);
+            tx.put::<CanonicalHeaders>(100, B256::ZERO).expect(ERROR_PUT);
+            tx.abort();
+        }
+
+        let env = create_test_db_with_path(&path);
+        let tx = env.tx().expect(ERROR_INIT_TX);
+        let keys = tx
+            .cursor_read::<CanonicalHeaders>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|res| res.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec![0, 1, 2, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(tx.get::<CanonicalHeaders>(9), Ok(Some(B256::with_last_byte(9))));
+        assert_eq!(tx.entries::<Headers>(), Ok(0));
+    }
+
+    #[test]
+    fn db_read_tx_isolation() {
+        let env = create_test_db();
+
+        let read_tx = env.tx().expect(ERROR_INIT_TX);
+        let write_tx = env.tx_mut().expect(ERROR_INIT_TX);
+        write_tx.put::<CanonicalHeaders>(1, B256::ZERO).expect(ERROR_PUT);
+
+        // Uncommitted changes are visible only to the writer.
+        assert_eq!(write_tx.get::<CanonicalHeaders>(1), Ok(Some(B256::ZERO)));
+        assert_eq!(read_tx.get::<CanonicalHeaders>(1), Ok(None));
+        write_tx.commit().expect(ERROR_COMMIT);
+
+        // Read transactions keep observing the state at the moment they were opened.
+        assert_eq!(read_tx.get::<CanonicalHeaders>(1), Ok(None));
+        assert_eq!(env.tx().expect(ERROR_INIT_TX).get::<CanonicalHeaders>(1), Ok(Some(B256::ZERO)));
+    }
+
+    #[test]
+    fn db_read_only_rejects_writes() {
+        let path = TempDir::new().expect(ERROR_TEMPDIR).into_path();
+        drop(create_test_db_with_path(&path));
+
+        let env = BTreeDatabaseEnv::open_read_only(&path, BTreeDatabaseArguments::default())
+            .expect(ERROR_DB_CREATION);
+        assert!(env.tx_mut().is_err());
+        assert!(env.compact().is_err());
+        assert_eq!(env.tx().expect(ERROR_INIT_TX).entries::<CanonicalHeaders>(), Ok(0));
+    }
+
+    #[test]
+    fn db_missing_table() {
+        let path = TempDir::new().expect(ERROR_TEMPDIR).into_path();
+        let env = BTreeDatabaseEnv::open(&path, BTreeDatabaseArguments::default())
+            .expect(ERROR_DB_CREATION);
+
+        let tx = env.tx().expect(ERROR_INIT_TX);
+        assert!(matches!(tx.get::<CanonicalHeaders>(0), Err(DatabaseError::Open(_))));
+        let tx = env.tx_mut().expect(ERROR_INIT_TX);
+        assert!(matches!(tx.put::<CanonicalHeaders>(0, B256::ZERO), Err(DatabaseError::Open(_))));
+    }
+
+    #[test]
+    fn db_compaction() {
+        let path = TempDir::new().expect(ERROR_TEMPDIR).into_path();
+
+        {
+            let env = create_test_db_with_path(&path);
+            for value in 0..10u8 {
+                env.update(|tx| {
+                    tx.put::<CanonicalHeaders>(1, B256::with_last_byte(value)).expect(ERROR_PUT)
+                })
+                .expect(ERROR_COMMIT);
+            }
+
+            let log_size = std::fs::metadata(path.join(LOG_FILE_NAME)).unwrap().len();
+            env.compact().unwrap();
+            assert!(std::fs::metadata(path.join(LOG_FILE_NAME)).unwrap().len() < log_size);
+
+            // Commits after the compaction are appended to the new log.
+            env.update(|tx| tx.put::<CanonicalHeaders>(2, B256::ZERO).expect(ERROR_PUT))
+                .expect(ERROR_COMMIT);
+        }
+
+        let env = create_test_db_with_path(&path);
+        let tx = env.tx().expect(ERROR_INIT_TX);
+        assert_eq!(tx.get::<CanonicalHeaders>(1), Ok(Some(B256::with_last_byte(9))));
+        assert_eq!(tx.get::<CanonicalHeaders>(2), Ok(Some(B256::ZERO)));
+    }
+}
@@ -0,0 +1,277 @@
+//! In-memory representation of tables and uncommitted changes.
+
+use super::tree::Tree;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    ops::Bound,
+    sync::Arc,
+};
+
+/// Encoded `(key, value)` pair.
+///
+/// Entries are ordered by key first and by value second, which gives `DUPSORT` semantics for free:
+/// all values of a key are adjacent and sorted. Non-`DUPSORT` tables hold at most one entry per
+/// key.
+pub(crate) type Entry = (Vec<u8>, Vec<u8>);
+
+/// Committed state of all tables, keyed by table name.
+pub(crate) type Snapshot = HashMap<String, Arc<TableData>>;
+
+/// Committed contents of a single table.
+#[derive(Clone, Default)]
+pub(crate) struct TableData {
+    /// Whether the table is a `DUPSORT` table.
+    pub(crate) dupsort: bool,
+    /// Table entries. Cloning the table is cheap, because the nodes of the tree are shared until
+    /// they're modified.
+    pub(crate) entries: Tree,
+}
+
+impl TableData {
+    /// Creates a new empty table.
+    pub(crate) fn new(dupsort: bool) -> Self {
+        Self { dupsort, entries: Tree::default() }
+    }
+
+    /// Approximate number of bytes occupied by the keys and values of the table.
+    pub(crate) fn size(&self) -> usize {
+        self.entries.iter().map(|(key, value)| key.len() + value.len()).sum()
+    }
+}
+
+impl fmt::Debug for TableData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TableData")
+            .field("dupsort", &self.dupsort)
+            .field("entries", &self.entries.len())
+            .finish()
+    }
+}
+
+/// Uncommitted changes to a single table made by a read-write transaction.
+#[derive(Debug)]
+pub(crate) struct TableOverlay {
+    /// Whether the table is a `DUPSORT` table.
+    pub(crate) dupsort: bool,
+    /// Whether all committed entries were removed.
+    pub(crate) cleared: bool,
+    /// Entries inserted (`true`) or removed (`false`) by the transaction.
+    pub(crate) changes: BTreeMap<Entry, bool>,
+}
+
+impl TableOverlay {
+    /// Creates a new overlay without any changes.
+    pub(crate) const fn new(dupsort: bool) -> Self {
+        Self { dupsort, cleared: false, changes: BTreeMap::new() }
+    }
+
+    /// Applies the changes to the committed table data.
+    pub(crate) fn apply_to(&self, data: &mut TableData) {
+        if self.cleared {
+            data.entries.clear();
+        }
+        for (entry, present) in &self.changes {
+            if *present {
+                data.entries.insert(entry.clone());
+            } else {
+                data.entries.remove(entry);
+            }
+        }
+    }
+}
+
+/// Read view over a table: committed data with the uncommitted changes of the current
+/// transaction on top.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TableView<'a> {
+    base: Option<&'a TableData>,
+    overlay: Option<&'a TableOverlay>,
+}
+
+impl<'a> TableView<'a> {
+    /// Creates a new view. At least one of `base` and `overlay` is expected to be set.
+    pub(crate) const fn new(
+        base: Option<&'a TableData>,
+        overlay: Option<&'a TableOverlay>,
+    ) -> Self {
+        Self { base, overlay }
+    }
+
+    /// Whether the table is a `DUPSORT` table.
+    pub(crate) fn is_dupsort(&self) -> bool {
+        self.overlay.map(|overlay| overlay.dupsort).or_else(|| self.base.map(|base| base.dupsort)) ==
+            Some(true)
+    }
+
+    /// Returns committed data that is still visible through the overlay.
+    fn visible_base(&self) -> Option<&'a TableData> {
+        self.base.filter(|_| !self.overlay.is_some_and(|overlay| overlay.cleared))
+    }
+
+    /// Returns `true` if the committed entry was removed by the overlay.
+    fn is_removed(&self, entry: &Entry) -> bool {
+        self.overlay.and_then(|overlay| overlay.changes.get(entry)) == Some(&false)
+    }
+
+    /// Returns `true` if the entry is present in the table.
+    pub(crate) fn contains(&self, entry: &Entry) -> bool {
+        match self.overlay.and_then(|overlay| overlay.changes.get(entry)) {
+            Some(present) => *present,
+            None => self.visible_base().is_some_and(|base| base.entries.contains(entry)),
+        }
+    }
+
+    /// Returns the smallest entry that is after the given bound.
+    pub(crate) fn next(&self, start: Bound<&Entry>) -> Option<Entry> {
+        let range = (start, Bound::Unbounded);
+        let base = self.visible_base().and_then(|base| {
+            let mut entry = base.entries.next(start)?;
+            while self.is_removed(entry) {
+                entry = base.entries.next(Bound::Excluded(entry))?;
+            }
+            Some(entry)
+        });
+        let overlay = self.overlay.and_then(|overlay| {
+            overlay.changes.range::<Entry, _>(range).find(|(_, present)| **present).map(|(e, _)| e)
+        });
+
+        match (base, overlay) {
+            (Some(base), Some(overlay)) => Some(base.min(overlay)),
+            (base, overlay) => base.or(overlay),
+        }
+        .cloned()
+    }
+
+    /// Returns the largest entry that is before the given bound.
+    pub(crate) fn prev(&self, end: Bound<&Entry>) -> Option<Entry> {
+        let range = (Bound::Unbounded, end);
+        let base = self.visible_base().and_then(|base| {
+            let mut entry = base.entries.prev(end)?;
+            while self.is_removed(entry) {
+                entry = base.entries.prev(Bound::Excluded(entry))?;
+            }
+            Some(entry)
+        });
+        let overlay = self.overlay.and_then(|overlay| {
+            overlay
+                .changes
+                .range::<Entry, _>(range)
+                .rev()
+                .find(|(_, present)| **present)
+                .map(|(e, _)| e)
+        });
+
+        match (base, overlay) {
+            (Some(base), Some(overlay)) => Some(base.max(overlay)),
+            (base, overlay) => base.or(overlay),
+        }
+        .cloned()
+    }
+
+    /// Returns the first entry of the table.
+    pub(crate) fn first(&self) -> Option<Entry> {
+        self.next(Bound::Unbounded)
+    }
+
+    /// Returns the last entry of the table.
+    pub(crate) fn last(&self) -> Option<Entry> {
+        self.prev(Bound::Unbounded)
+    }
+
+    /// Returns the first entry with a key greater than or equal to `key`.
+    pub(crate) fn seek(&self, key: &[u8]) -> Option<Entry> {
+        self.next(Bound::Included(&(key.to_vec(), Vec::new())))
+    }
+
+    /// Returns the first entry of the given key.
+    pub(crate) fn seek_exact(&self, key: &[u8]) -> Option<Entry> {
+        self.seek(key).filter(|(k, _)| k == key)
+    }
+
+    /// Returns the first entry of the given key with a value greater than or equal to `subkey`.
+    pub(crate) fn seek_by_key_subkey(&self, key: &[u8], subkey: &[u8]) -> Option<Entry> {
+        self.next(Bound::Included(&(key.to_vec(), subkey.to_vec()))).filter(|(k, _)| k == key)
+    }
+
+    /// Returns the first entry with a key strictly greater than `key`.
+    pub(crate) fn next_key(&self, key: &[u8]) -> Option<Entry> {
+        self.next(Bound::Included(&(key_successor(key), Vec::new())))
+    }
+
+    /// Returns all entries of the given key.
+    pub(crate) fn duplicates(&self, key: &[u8]) -> Vec<Entry> {
+        let mut entries = Vec::new();
+        let mut next = self.seek_exact(key);
+        while let Some(entry) = next {
+            next = self.next(Bound::Excluded(&entry)).filter(|(k, _)| k == key);
+            entries.push(entry);
+        }
+        entries
+    }
+
+    /// Returns the number of entries in the table.
+    pub(crate) fn len(&self) -> usize {
+        let base = self.visible_base();
+        let mut len = base.map_or(0, |base| base.entries.len());
+        if let Some(overlay) = self.overlay {
+            for (entry, present) in &overlay.changes {
+                match (*present, base.is_some_and(|base| base.entries.contains(entry))) {
+                    (true, false) => len += 1,
+                    (false, true) => len -= 1,
+                    _ => {}
+                }
+            }
+        }
+        len
+    }
+}
+
+/// Returns the smallest byte string that is greater than `key`.
+fn key_successor(key: &[u8]) -> Vec<u8> {
+    let mut successor = Vec::with_capacity(key.len() + 1);
+    successor.extend_from_slice(key);
+    successor.push(0);
+    successor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: u8, value: u8) -> Entry {
+        (vec![key], vec![value])
+    }
+
+    #[test]
+    fn overlay_view() {
+        let mut base = TableData::new(true);
+        base.entries.extend([entry(1, 1), entry(1, 2), entry(2, 1), entry(3, 1)]);
+
+        let mut overlay = TableOverlay::new(true);
+        overlay.changes.insert(entry(1, 2), false);
+        overlay.changes.insert(entry(2, 0), true);
+        overlay.changes.insert(entry(4, 1), true);
+
+        let view = TableView::new(Some(&base), Some(&overlay));
+        assert_eq!(view.len(), 5);
+        assert_eq!(view.first(), Some(entry(1, 1)));
+        assert_eq!(view.next(Bound::Excluded(&entry(1, 1))), Some(entry(2, 0)));
+        assert_eq!(view.prev(Bound::Excluded(&entry(2, 0))), Some(entry(1, 1)));
+        assert_eq!(view.last(), Some(entry(4, 1)));
+        assert_eq!(view.next_key(&[2]), Some(entry(3, 1)));
+        assert_eq!(view.duplicates(&[2]), vec![entry(2, 0), entry(2, 1)]);
+        assert!(!view.contains(&entry(1, 2)));
+
+        let mut data = base.clone();
+        overlay.apply_to(&mut data);
+        let committed = TableView::new(Some(&data), None);
+        assert_eq!(committed.len(), view.len());
+        assert_eq!(committed.duplicates(&[1]), vec![entry(1, 1)]);
+
+        overlay.cleared = true;
+        let view = TableView::new(Some(&base), Some(&overlay));
+        assert_eq!(view.len(), 2);
+        assert_eq!(view.first(), Some(entry(2, 0)));
+    }
+}
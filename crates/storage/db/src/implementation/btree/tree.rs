@@ -0,0 +1,315 @@
+//! Persistent B+ tree of encoded entries.
+//!
+//! Nodes are reference counted and shared between versions of a table. Modifying a tree only
+//! copies the nodes on the path to the modified entry that are still referenced by another
+//! version, e.g. by the snapshot of an open read-only transaction, instead of the whole table.
+
+use super::table::Entry;
+use std::{fmt, ops::Bound, sync::Arc};
+
+/// Maximum number of entries in a leaf node before it's split.
+const MAX_LEAF_ENTRIES: usize = 128;
+
+/// Maximum number of children of an internal node before it's split.
+const MAX_CHILDREN: usize = 64;
+
+/// Ordered set of entries, cheap to clone.
+#[derive(Clone, Default)]
+pub(crate) struct Tree {
+    root: Option<Arc<Node>>,
+    len: usize,
+}
+
+#[derive(Clone)]
+enum Node {
+    /// Sorted entries.
+    Leaf(Vec<Entry>),
+    /// Children with the lower bounds of all of them but the first one: entries of
+    /// `children[i + 1]` are greater than or equal to `keys[i]`, and entries of `children[i]` are
+    /// less than it.
+    Internal { keys: Vec<Entry>, children: Vec<Arc<Node>> },
+}
+
+/// Right sibling created by splitting a node, with its lower bound.
+type Split = (Entry, Arc<Node>);
+
+impl Tree {
+    /// Returns the number of entries.
+    pub(crate) const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Removes all entries.
+    pub(crate) fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Returns `true` if the tree contains the entry.
+    pub(crate) fn contains(&self, entry: &Entry) -> bool {
+        self.next(Bound::Included(entry)) == Some(entry)
+    }
+
+    /// Returns the smallest entry that is after the given bound.
+    pub(crate) fn next(&self, start: Bound<&Entry>) -> Option<&Entry> {
+        self.root.as_deref().and_then(|root| root.next(start))
+    }
+
+    /// Returns the largest entry that is before the given bound.
+    pub(crate) fn prev(&self, end: Bound<&Entry>) -> Option<&Entry> {
+        self.root.as_deref().and_then(|root| root.prev(end))
+    }
+
+    /// Returns an iterator over all entries in order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Entry> {
+        std::iter::successors(self.next(Bound::Unbounded), |entry| {
+            self.next(Bound::Excluded(entry))
+        })
+    }
+
+    /// Inserts the entry. Returns `true` if it wasn't present yet.
+    pub(crate) fn insert(&mut self, entry: Entry) -> bool {
+        // Avoid copying shared nodes if there is nothing to change.
+        if self.contains(&entry) {
+            return false
+        }
+
+        let Some(root) = self.root.as_mut() else {
+            self.root = Some(Arc::new(Node::Leaf(vec![entry])));
+            self.len = 1;
+            return true
+        };
+        if let Some((key, right)) = Node::insert(root, entry) {
+            let left = self.root.take().expect("root exists");
+            self.root =
+                Some(Arc::new(Node::Internal { keys: vec![key], children: vec![left, right] }));
+        }
+        self.len += 1;
+        true
+    }
+
+    /// Removes the entry. Returns `true` if it was present.
+    pub(crate) fn remove(&mut self, entry: &Entry) -> bool {
+        if !self.contains(entry) {
+            return false
+        }
+
+        let root = self.root.as_mut().expect("tree is not empty");
+        Node::remove(root, entry);
+        self.len -= 1;
+
+        // Collapse the root if it's empty or has a single child.
+        loop {
+            match self.root.as_deref() {
+                Some(root) if root.is_empty() => self.root = None,
+                Some(Node::Internal { children, .. }) if children.len() == 1 => {
+                    self.root = Some(children[0].clone())
+                }
+                _ => break,
+            }
+        }
+        true
+    }
+}
+
+impl Node {
+    fn next(&self, start: Bound<&Entry>) -> Option<&Entry> {
+        match self {
+            Self::Leaf(entries) => {
+                let index = match start {
+                    Bound::Unbounded => 0,
+                    Bound::Included(start) => entries.partition_point(|entry| entry < start),
+                    Bound::Excluded(start) => entries.partition_point(|entry| entry <= start),
+                };
+                entries.get(index)
+            }
+            Self::Internal { keys, children } => {
+                let index = match start {
+                    Bound::Unbounded => 0,
+                    Bound::Included(start) | Bound::Excluded(start) => {
+                        keys.partition_point(|key| key <= start)
+                    }
+                };
+                children[index..].iter().find_map(|child| child.next(start))
+            }
+        }
+    }
+
+    fn prev(&self, end: Bound<&Entry>) -> Option<&Entry> {
+        match self {
+            Self::Leaf(entries) => {
+                let index = match end {
+                    Bound::Unbounded => entries.len(),
+                    Bound::Included(end) => entries.partition_point(|entry| entry <= end),
+                    Bound::Excluded(end) => entries.partition_point(|entry| entry < end),
+                };
+                index.checked_sub(1).map(|index| &entries[index])
+            }
+            Self::Internal { keys, children } => {
+                let index = match end {
+                    Bound::Unbounded => children.len() - 1,
+                    Bound::Included(end) | Bound::Excluded(end) => {
+                        keys.partition_point(|key| key <= end)
+                    }
+                };
+                children[..=index].iter().rev().find_map(|child| child.prev(end))
+            }
+        }
+    }
+
+    /// Inserts an entry that is not present in the subtree, copying the node if it's shared.
+    /// Returns the new right sibling if the node had to be split.
+    fn insert(node: &mut Arc<Self>, entry: Entry) -> Option<Split> {
+        match Arc::make_mut(node) {
+            Self::Leaf(entries) => {
+                let index = entries.partition_point(|existing| existing < &entry);
+                entries.insert(index, entry);
+                if entries.len() <= MAX_LEAF_ENTRIES {
+                    return None
+                }
+
+                let right = entries.split_off(entries.len() / 2);
+                Some((right[0].clone(), Arc::new(Self::Leaf(right))))
+            }
+            Self::Internal { keys, children } => {
+                let index = keys.partition_point(|key| key <= &entry);
+                let (key, right) = Self::insert(&mut children[index], entry)?;
+                keys.insert(index, key);
+                children.insert(index + 1, right);
+                if children.len() <= MAX_CHILDREN {
+                    return None
+                }
+
+                let mid = children.len() / 2;
+                let right_children = children.split_off(mid);
+                let mut right_keys = keys.split_off(mid - 1);
+                let key = right_keys.remove(0);
+                Some((key, Arc::new(Self::Internal { keys: right_keys, children: right_children })))
+            }
+        }
+    }
+
+    /// Removes an entry that is present in the subtree, copying the node if it's shared. Children
+    /// that become empty are removed.
+    fn remove(node: &mut Arc<Self>, entry: &Entry) {
+        match Arc::make_mut(node) {
+            Self::Leaf(entries) => {
+                if let Ok(index) = entries.binary_search(entry) {
+                    entries.remove(index);
+                }
+            }
+            Self::Internal { keys, children } => {
+                let index = keys.partition_point(|key| key <= entry);
+                Self::remove(&mut children[index], entry);
+                if children[index].is_empty() {
+                    children.remove(index);
+                    if !keys.is_empty() {
+                        keys.remove(index.saturating_sub(1));
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Leaf(entries) => entries.is_empty(),
+            Self::Internal { children, .. } => children.is_empty(),
+        }
+    }
+}
+
+impl fmt::Debug for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for Tree {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for Tree {}
+
+impl Extend<Entry> for Tree {
+    fn extend<T: IntoIterator<Item = Entry>>(&mut self, iter: T) {
+        for entry in iter {
+            self.insert(entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn entry(key: u32, value: u8) -> Entry {
+        (key.to_be_bytes().to_vec(), vec![value])
+    }
+
+    #[test]
+    fn matches_btree_set() {
+        let mut tree = Tree::default();
+        let mut expected = BTreeSet::new();
+
+        // Interleave insertions and removals to split and shrink nodes on every level.
+        for i in 0..20_000u32 {
+            let key = i.wrapping_mul(7919) % 10_000;
+            let entry = entry(key, (i % 3) as u8);
+            if i % 5 == 4 {
+                assert_eq!(tree.remove(&entry), expected.remove(&entry));
+            } else {
+                assert_eq!(tree.insert(entry.clone()), expected.insert(entry));
+            }
+        }
+
+        assert_eq!(tree.len(), expected.len());
+        assert!(tree.iter().eq(expected.iter()));
+
+        for probe in [entry(0, 0), entry(4_999, 1), entry(9_999, 2), entry(10_000, 0)] {
+            assert_eq!(
+                tree.next(Bound::Included(&probe)),
+                expected.range(&probe..).next(),
+                "{probe:?}"
+            );
+            assert_eq!(
+                tree.next(Bound::Excluded(&probe)),
+                expected.range((Bound::Excluded(&probe), Bound::Unbounded)).next()
+            );
+            assert_eq!(tree.prev(Bound::Included(&probe)), expected.range(..=&probe).next_back());
+            assert_eq!(tree.prev(Bound::Excluded(&probe)), expected.range(..&probe).next_back());
+        }
+
+        for entry in expected.clone() {
+            assert!(tree.remove(&entry));
+        }
+        assert_eq!(tree.len(), 0);
+        assert!(tree.root.is_none());
+    }
+
+    #[test]
+    fn clone_shares_unmodified_nodes() {
+        let mut tree = Tree::default();
+        tree.extend((0..10_000).map(|i| entry(i, 0)));
+
+        let snapshot = tree.clone();
+        tree.insert(entry(10_000, 0));
+        tree.remove(&entry(0, 0));
+
+        // The snapshot is unaffected by the modifications.
+        assert_eq!(snapshot.len(), 10_000);
+        assert!(snapshot.contains(&entry(0, 0)));
+        assert!(!snapshot.contains(&entry(10_000, 0)));
+
+        // Only the nodes on the modified paths were copied.
+        let (Some(Node::Internal { children, .. }), Some(Node::Internal { children: old, .. })) =
+            (tree.root.as_deref(), snapshot.root.as_deref())
+        else {
+            panic!("expected internal root nodes")
+        };
+        let shared = children.iter().filter(|child| old.iter().any(|o| Arc::ptr_eq(child, o)));
+        assert_eq!(shared.count(), children.len() - 2);
+    }
+}
@@ -0,0 +1,310 @@
+//! Transactions of the B-tree database.
+
+use super::{
+    cursor::{compress_value, Cursor, CursorSource},
+    table::{Entry, Snapshot, TableData, TableOverlay, TableView},
+    BTreeError, WriterGuard,
+};
+use crate::{tables::utils::decode_one, DatabaseError};
+use parking_lot::Mutex;
+use reth_db_api::{
+    table::{DupSort, Encode, Table, TableImporter},
+    transaction::{DbTx, DbTxMut},
+};
+use std::{borrow::Cow, collections::HashMap, fmt::Debug, marker::PhantomData, sync::Arc};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Marker trait for the kind of a transaction.
+pub trait TransactionKind: private::Sealed + Send + Sync + Debug + 'static {
+    /// Whether the transaction is read-only.
+    const IS_READ_ONLY: bool;
+}
+
+/// Marker for read-only transactions.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RO;
+
+/// Marker for read-write transactions.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RW;
+
+impl private::Sealed for RO {}
+impl private::Sealed for RW {}
+
+impl TransactionKind for RO {
+    const IS_READ_ONLY: bool = true;
+}
+
+impl TransactionKind for RW {
+    const IS_READ_ONLY: bool = false;
+}
+
+/// Uncommitted state of a read-write transaction.
+#[derive(Debug, Default)]
+pub(crate) struct WriteState {
+    /// Committed state at the moment the transaction was opened.
+    pub(crate) base: Arc<Snapshot>,
+    /// Changes made by the transaction, keyed by table name.
+    pub(crate) overlays: HashMap<String, TableOverlay>,
+}
+
+impl WriteState {
+    /// Creates a new state on top of the committed snapshot.
+    pub(crate) fn new(base: Arc<Snapshot>) -> Self {
+        Self { base, overlays: HashMap::new() }
+    }
+
+    /// Returns a read view of the table.
+    pub(crate) fn view(&self, table: &str) -> Result<TableView<'_>, DatabaseError> {
+        let base = self.base.get(table).map(Arc::as_ref);
+        let overlay = self.overlays.get(table);
+        if base.is_none() && overlay.is_none() {
+            return Err(DatabaseError::Open(BTreeError::TableNotFound.into()))
+        }
+        Ok(TableView::new(base, overlay))
+    }
+
+    /// Returns the committed table data and the overlay of the table, creating the latter if
+    /// necessary.
+    fn overlay_mut(
+        &mut self,
+        table: &str,
+    ) -> Result<(Option<&TableData>, &mut TableOverlay), DatabaseError> {
+        let base = self.base.get(table).map(Arc::as_ref);
+        if !self.overlays.contains_key(table) {
+            let dupsort =
+                base.ok_or_else(|| DatabaseError::Open(BTreeError::TableNotFound.into()))?.dupsort;
+            self.overlays.insert(table.to_string(), TableOverlay::new(dupsort));
+        }
+        let overlay = self.overlays.get_mut(table).expect("overlay is inserted above");
+        let cleared = overlay.cleared;
+        Ok((base.filter(|_| !cleared), overlay))
+    }
+
+    /// Creates the table if it doesn't exist.
+    pub(crate) fn create(&mut self, table: &str, dupsort: bool) {
+        if !self.base.contains_key(table) && !self.overlays.contains_key(table) {
+            self.overlays.insert(table.to_string(), TableOverlay::new(dupsort));
+        }
+    }
+
+    /// Inserts the entry into the table.
+    pub(crate) fn insert(&mut self, table: &str, entry: Entry) -> Result<(), DatabaseError> {
+        let (base, overlay) = self.overlay_mut(table)?;
+        if base.is_some_and(|base| base.entries.contains(&entry)) {
+            overlay.changes.remove(&entry);
+        } else {
+            overlay.changes.insert(entry, true);
+        }
+        Ok(())
+    }
+
+    /// Removes the entry from the table.
+    pub(crate) fn remove(&mut self, table: &str, entry: &Entry) -> Result<(), DatabaseError> {
+        let (base, overlay) = self.overlay_mut(table)?;
+        if base.is_some_and(|base| base.entries.contains(entry)) {
+            overlay.changes.insert(entry.clone(), false);
+        } else {
+            overlay.changes.remove(entry);
+        }
+        Ok(())
+    }
+
+    /// Removes all entries from the table.
+    pub(crate) fn clear(&mut self, table: &str) -> Result<(), DatabaseError> {
+        let (_, overlay) = self.overlay_mut(table)?;
+        overlay.cleared = true;
+        overlay.changes.clear();
+        Ok(())
+    }
+
+    /// Inserts the entry, replacing the existing value of the key for non-`DUPSORT` tables.
+    pub(crate) fn upsert(&mut self, table: &str, entry: Entry) -> Result<(), DatabaseError> {
+        let view = self.view(table)?;
+        let replaced = if view.is_dupsort() { Vec::new() } else { view.duplicates(&entry.0) };
+        for old in replaced.iter().filter(|old| **old != entry) {
+            self.remove(table, old)?;
+        }
+        self.insert(table, entry)
+    }
+}
+
+/// Inner state of a transaction.
+#[derive(Debug)]
+enum TxInner {
+    /// Read-only transaction over a committed snapshot.
+    ReadOnly(Arc<Snapshot>),
+    /// Read-write transaction holding the writer lock.
+    ReadWrite {
+        /// Uncommitted changes, shared with the cursors of the transaction.
+        state: Arc<Mutex<WriteState>>,
+        /// Writer lock of the environment, released when the transaction is closed.
+        guard: WriterGuard,
+    },
+}
+
+/// B-tree database transaction.
+#[derive(Debug)]
+pub struct Tx<K: TransactionKind> {
+    inner: TxInner,
+    _kind: PhantomData<K>,
+}
+
+impl Tx<RO> {
+    /// Creates a new read-only transaction over the committed snapshot.
+    pub(crate) const fn new_ro(snapshot: Arc<Snapshot>) -> Self {
+        Self { inner: TxInner::ReadOnly(snapshot), _kind: PhantomData }
+    }
+}
+
+impl Tx<RW> {
+    /// Creates a new read-write transaction holding the writer lock.
+    pub(crate) fn new_rw(guard: WriterGuard) -> Self {
+        let state = Arc::new(Mutex::new(WriteState::new(guard.env().snapshot())));
+        Self { inner: TxInner::ReadWrite { state, guard }, _kind: PhantomData }
+    }
+
+    /// Executes the closure with the uncommitted state of the transaction.
+    pub(crate) fn write<R>(
+        &self,
+        f: impl FnOnce(&mut WriteState) -> Result<R, DatabaseError>,
+    ) -> Result<R, DatabaseError> {
+        match &self.inner {
+            TxInner::ReadWrite { state, .. } => f(&mut state.lock()),
+            TxInner::ReadOnly(_) => Err(DatabaseError::InitTx(BTreeError::ReadOnly.into())),
+        }
+    }
+}
+
+impl<K: TransactionKind> Tx<K> {
+    /// Executes the closure with a read view of the table.
+    fn read<T: Table, R>(&self, f: impl FnOnce(TableView<'_>) -> R) -> Result<R, DatabaseError> {
+        match &self.inner {
+            TxInner::ReadOnly(snapshot) => {
+                let data = snapshot
+                    .get(T::NAME)
+                    .ok_or_else(|| DatabaseError::Open(BTreeError::TableNotFound.into()))?;
+                Ok(f(TableView::new(Some(data), None)))
+            }
+            TxInner::ReadWrite { state, .. } => Ok(f(state.lock().view(T::NAME)?)),
+        }
+    }
+
+    /// Creates a new cursor over the table.
+    pub fn new_cursor<T: Table>(&self) -> Result<Cursor<K, T>, DatabaseError> {
+        let source = match &self.inner {
+            TxInner::ReadOnly(snapshot) => CursorSource::ReadOnly(
+                snapshot
+                    .get(T::NAME)
+                    .cloned()
+                    .ok_or_else(|| DatabaseError::Open(BTreeError::TableNotFound.into()))?,
+            ),
+            TxInner::ReadWrite { state, .. } => {
+                state.lock().view(T::NAME)?;
+                CursorSource::ReadWrite(state.clone())
+            }
+        };
+        Ok(Cursor::new(source))
+    }
+}
+
+impl TableImporter for Tx<RW> {}
+
+impl<K: TransactionKind> DbTx for Tx<K> {
+    type Cursor<T: Table> = Cursor<K, T>;
+    type DupCursor<T: DupSort> = Cursor<K, T>;
+
+    fn get<T: Table>(&self, key: T::Key) -> Result<Option<T::Value>, DatabaseError> {
+        self.get_by_encoded_key::<T>(&key.encode())
+    }
+
+    fn get_by_encoded_key<T: Table>(
+        &self,
+        key: &<T::Key as Encode>::Encoded,
+    ) -> Result<Option<T::Value>, DatabaseError> {
+        self.read::<T, _>(|view| view.seek_exact(key.as_ref()))?
+            .map(|(_, value)| decode_one::<T>(Cow::Owned(value)))
+            .transpose()
+    }
+
+    fn commit(self) -> Result<bool, DatabaseError> {
+        match self.inner {
+            TxInner::ReadOnly(_) => {}
+            TxInner::ReadWrite { state, guard } => {
+                let state = std::mem::take(&mut *state.lock());
+                guard.env().commit(state)?;
+            }
+        }
+        Ok(false)
+    }
+
+    fn abort(self) {}
+
+    fn cursor_read<T: Table>(&self) -> Result<Self::Cursor<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    fn cursor_dup_read<T: DupSort>(&self) -> Result<Self::DupCursor<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
+        self.read::<T, _>(|view| view.len())
+    }
+
+    /// Read transactions hold a snapshot of the database and never time out, so there is nothing
+    /// to disable.
+    fn disable_long_read_transaction_safety(&mut self) {}
+}
+
+impl DbTxMut for Tx<RW> {
+    type CursorMut<T: Table> = Cursor<RW, T>;
+    type DupCursorMut<T: DupSort> = Cursor<RW, T>;
+
+    fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let entry = (key.encode().into(), compress_value::<T>(&value));
+        self.write(|state| state.upsert(T::NAME, entry))
+    }
+
+    fn delete<T: Table>(
+        &self,
+        key: T::Key,
+        value: Option<T::Value>,
+    ) -> Result<bool, DatabaseError> {
+        let key: Vec<u8> = key.encode().into();
+        self.write(|state| {
+            let removed = match value {
+                Some(value) => {
+                    let entry = (key, compress_value::<T>(&value));
+                    if state.view(T::NAME)?.contains(&entry) {
+                        vec![entry]
+                    } else {
+                        Vec::new()
+                    }
+                }
+                None => state.view(T::NAME)?.duplicates(&key),
+            };
+            for entry in &removed {
+                state.remove(T::NAME, entry)?;
+            }
+            Ok(!removed.is_empty())
+        })
+    }
+
+    fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
+        self.write(|state| state.clear(T::NAME))
+    }
+
+    fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    fn cursor_dup_write<T: DupSort>(&self) -> Result<Self::DupCursorMut<T>, DatabaseError> {
+        self.new_cursor()
+    }
+}
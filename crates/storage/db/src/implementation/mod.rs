@@ -1,2 +1,8 @@
+#[cfg(test)]
+#[macro_use]
+mod test_suite;
+
+#[cfg(feature = "btree")]
+pub(crate) mod btree;
 #[cfg(feature = "mdbx")]
 pub(crate) mod mdbx;
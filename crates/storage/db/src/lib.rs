@@ -3,6 +3,8 @@
 //! This crate is an implementation of [`reth-db-api`] for MDBX, as well as a few other common
 //! database types.
 //!
+//! An alternative pure-Rust backend is available in the `btree` module, behind the `btree` feature.
+//!
 //! # Overview
 //!
 //! An overview of the current data model of reth can be found in the [`mod@tables`] module.
@@ -21,16 +23,18 @@ pub mod lockfile;
 mod metrics;
 pub mod static_file;
 pub mod tables;
-#[cfg(feature = "mdbx")]
+#[cfg(any(feature = "mdbx", feature = "btree"))]
 mod utils;
 pub mod version;
 
+#[cfg(feature = "btree")]
+pub mod btree;
 #[cfg(feature = "mdbx")]
 pub mod mdbx;
 
 pub use reth_storage_errors::db::{DatabaseError, DatabaseWriteOperation};
 pub use tables::*;
-#[cfg(feature = "mdbx")]
+#[cfg(any(feature = "mdbx", feature = "btree"))]
 pub use utils::is_database_empty;
 
 #[cfg(feature = "mdbx")]
@@ -204,6 +208,22 @@ pub mod test_utils {
         Arc::new(TempDatabase::new(db, path))
     }
 
+    /// Create read/write database using the pure-Rust [`btree`](crate::btree) backend for testing
+    #[cfg(feature = "btree")]
+    #[track_caller]
+    pub fn create_test_rw_btree_db() -> Arc<TempDatabase<crate::btree::BTreeDatabaseEnv>> {
+        let path = tempdir_path();
+        let emsg = format!("{ERROR_DB_CREATION}: {path:?}");
+
+        let db = crate::btree::init_db(
+            &path,
+            crate::btree::BTreeDatabaseArguments::new(ClientVersion::default()).with_sync(false),
+        )
+        .expect(&emsg);
+
+        Arc::new(TempDatabase::new(db, path))
+    }
+
     /// Create read only database for testing
     #[track_caller]
     pub fn create_test_ro_db() -> Arc<TempDatabase<DatabaseEnv>> {
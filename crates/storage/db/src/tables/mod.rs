@@ -16,14 +16,14 @@ pub mod codecs;
 mod raw;
 pub use raw::{RawDupSort, RawKey, RawTable, RawValue, TableRawRow};
 
-#[cfg(feature = "mdbx")]
+#[cfg(any(feature = "mdbx", feature = "btree"))]
 pub(crate) mod utils;
 
 use alloy_consensus::Header;
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
 use reth_db_api::{
     models::{
-        accounts::BlockNumberAddress,
+        accounts::{BlockNumberAddress, BlockNumberHashedAddress},
         blocks::{HeaderHash, StoredBlockOmmers},
         storage_sharded_key::StorageShardedKey,
         AccountBeforeTx, ClientVersion, CompactU256, IntegerList, ShardedKey,
@@ -35,7 +35,9 @@ use reth_primitives::{Receipt, StorageEntry, TransactionSigned};
 use reth_primitives_traits::{Account, Bytecode};
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::StageCheckpoint;
-use reth_trie_common::{BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey};
+use reth_trie_common::{
+    BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey, TrieChangeSetsEntry,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -493,6 +495,25 @@ tables! {
         type SubKey = StoredNibblesSubKey;
     }
 
+    /// Stores the state of an account trie node before a certain block changed it.
+    /// If [`TrieChangeSetsEntry::node`] is `None`, the node did not exist before the block.
+    ///
+    /// Only populated when historical proofs are enabled. The root node is recorded for every
+    /// block, so the presence of a block key marks the changesets for that block as available.
+    table AccountsTrieChangeSets {
+        type Key = BlockNumber;
+        type Value = TrieChangeSetsEntry;
+        type SubKey = StoredNibblesSubKey;
+    }
+
+    /// Stores the state of a storage trie node before a certain block changed it.
+    /// If [`TrieChangeSetsEntry::node`] is `None`, the node did not exist before the block.
+    table StoragesTrieChangeSets {
+        type Key = BlockNumberHashedAddress;
+        type Value = TrieChangeSetsEntry;
+        type SubKey = StoredNibblesSubKey;
+    }
+
     /// Stores the transaction sender for each canonical transaction.
     /// It is needed to speed up execution stage and allows fetching signer without doing
     /// transaction signed recovery
@@ -26,6 +26,7 @@ where
 }
 
 /// Helper function to decode only a value from a `(key, value)` pair.
+#[cfg(feature = "mdbx")]
 pub(crate) fn decode_value<'a, T>(
     kv: (Cow<'a, [u8]>, Cow<'a, [u8]>),
 ) -> Result<T::Value, DatabaseError>
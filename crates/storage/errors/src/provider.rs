@@ -28,6 +28,9 @@ pub enum ProviderError {
     /// Nippy jar error.
     #[error("nippy jar error: {_0}")]
     NippyJar(String),
+    /// Error encountered when serving expired history from era files.
+    #[error("era history error: {_0}")]
+    EraHistory(String),
     /// Trie witness error.
     #[error("trie witness error: {_0}")]
     TrieWitnessError(String),
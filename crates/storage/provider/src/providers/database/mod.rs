@@ -57,6 +57,8 @@ pub struct ProviderFactory<N: NodeTypesWithDB> {
     static_file_provider: StaticFileProvider<N::Primitives>,
     /// Optional pruning configuration
     prune_modes: PruneModes,
+    /// Number of most recent blocks trie changesets are retained for, if they're recorded.
+    trie_changesets: Option<u64>,
     /// The node storage handler.
     storage: Arc<N::Storage>,
 }
@@ -66,12 +68,14 @@ where
     N: NodeTypesWithDB<DB: fmt::Debug, ChainSpec: fmt::Debug, Storage: fmt::Debug>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self { db, chain_spec, static_file_provider, prune_modes, storage } = self;
+        let Self { db, chain_spec, static_file_provider, prune_modes, trie_changesets, storage } =
+            self;
         f.debug_struct("ProviderFactory")
             .field("db", &db)
             .field("chain_spec", &chain_spec)
             .field("static_file_provider", &static_file_provider)
             .field("prune_modes", &prune_modes)
+            .field("trie_changesets", &trie_changesets)
             .field("storage", &storage)
             .finish()
     }
@@ -89,6 +93,7 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
             chain_spec,
             static_file_provider,
             prune_modes: PruneModes::none(),
+            trie_changesets: None,
             storage: Default::default(),
         }
     }
@@ -105,6 +110,16 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
         self
     }
 
+    /// Sets the number of most recent blocks trie changesets are recorded and retained for in an
+    /// existing [`ProviderFactory`], or disables them if [`None`].
+    ///
+    /// Trie changesets allow historical state proofs to be generated without recomputing the
+    /// intermediate trie nodes of the requested block.
+    pub const fn with_trie_changesets(mut self, distance: Option<u64>) -> Self {
+        self.trie_changesets = distance;
+        self
+    }
+
     /// Returns reference to the underlying database.
     pub const fn db_ref(&self) -> &N::DB {
         &self.db
@@ -131,6 +146,7 @@ impl<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>> ProviderFactory<N> {
             chain_spec,
             static_file_provider,
             prune_modes: PruneModes::none(),
+            trie_changesets: None,
             storage: Default::default(),
         })
     }
@@ -160,13 +176,16 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
     /// open.
     #[track_caller]
     pub fn provider_rw(&self) -> ProviderResult<DatabaseProviderRW<N::DB, N>> {
-        Ok(DatabaseProviderRW(DatabaseProvider::new_rw(
-            self.db.tx_mut()?,
-            self.chain_spec.clone(),
-            self.static_file_provider.clone(),
-            self.prune_modes.clone(),
-            self.storage.clone(),
-        )))
+        Ok(DatabaseProviderRW(
+            DatabaseProvider::new_rw(
+                self.db.tx_mut()?,
+                self.chain_spec.clone(),
+                self.static_file_provider.clone(),
+                self.prune_modes.clone(),
+                self.storage.clone(),
+            )
+            .with_trie_changesets(self.trie_changesets),
+        ))
     }
 
     /// State provider for latest block
@@ -360,6 +379,10 @@ impl<N: ProviderNodeTypes> BlockNumReader for ProviderFactory<N> {
         self.provider()?.last_block_number()
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        Ok(self.static_file_provider.earliest_history_height())
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         self.provider()?.block_number(hash)
     }
@@ -637,6 +660,7 @@ impl<N: NodeTypesWithDB> Clone for ProviderFactory<N> {
             chain_spec: self.chain_spec.clone(),
             static_file_provider: self.static_file_provider.clone(),
             prune_modes: self.prune_modes.clone(),
+            trie_changesets: self.trie_changesets,
             storage: self.storage.clone(),
         }
     }
@@ -647,25 +671,35 @@ mod tests {
     use super::*;
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
-        test_utils::{blocks::TEST_BLOCK, create_test_provider_factory, MockNodeTypesWithDB},
-        BlockHashReader, BlockNumReader, BlockWriter, DBProvider, HeaderSyncGapProvider,
-        StorageLocation, TransactionsProvider,
+        test_utils::{
+            blocks::TEST_BLOCK, create_test_provider_factory,
+            create_test_provider_factory_with_btree, MockNodeTypesWithDB,
+        },
+        writer::UnifiedStorageWriter,
+        BlockHashReader, BlockNumReader, BlockReader, BlockWriter, DBProvider, ExecutionOutcome,
+        HeaderSyncGapProvider, OriginalValuesKnown, ReceiptProvider, StateWriter, StorageLocation,
+        TransactionVariant, TransactionsProvider,
     };
+    use alloy_consensus::ReceiptWithBloom;
     use alloy_primitives::{TxNumber, B256, U256};
     use assert_matches::assert_matches;
     use rand::Rng;
-    use reth_chainspec::ChainSpecBuilder;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
     use reth_db::{
         mdbx::DatabaseArguments,
         tables,
-        test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
+        test_utils::{create_test_rw_db, create_test_static_files_dir, ERROR_TEMPDIR},
     };
-    use reth_primitives::StaticFileSegment;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_era::era1::{self, BlockTuple, Era1Writer};
+    use reth_primitives::{Receipt, Receipts, SealedBlock, StaticFileSegment};
     use reth_primitives_traits::SignedTransaction;
     use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_errors::provider::ProviderError;
-    use reth_testing_utils::generators::{self, random_block, random_header, BlockParams};
-    use std::{ops::RangeInclusive, sync::Arc};
+    use reth_testing_utils::generators::{
+        self, random_block, random_header, random_receipt, BlockParams,
+    };
+    use std::{fs, ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
 
     #[test]
@@ -755,6 +789,105 @@ mod tests {
         }
     }
 
+    #[test]
+    fn insert_block_btree() {
+        let factory = create_test_provider_factory_with_btree();
+
+        let block = TEST_BLOCK.clone();
+        {
+            let provider = factory.provider_rw().unwrap();
+            assert_matches!(
+                provider
+                    .insert_block(block.clone().try_recover().unwrap(), StorageLocation::Database),
+                Ok(_)
+            );
+            provider.commit().unwrap();
+        }
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.block_hash(block.number).unwrap(), Some(block.hash()));
+        assert_matches!(
+            provider.transaction_sender(0), Ok(Some(sender))
+            if sender == block.body().transactions[0].recover_signer().unwrap()
+        );
+        assert_matches!(
+            provider.transaction_id(*block.body().transactions[0].tx_hash()),
+            Ok(Some(0))
+        );
+    }
+
+    #[test]
+    fn remove_blocks_above_btree() {
+        let factory = create_test_provider_factory_with_btree();
+
+        let mut rng = generators::rng();
+        let mut parent = None;
+        let blocks = (0..4)
+            .map(|number| {
+                let block = random_block(
+                    &mut rng,
+                    number,
+                    BlockParams { parent, tx_count: Some(2), ..Default::default() },
+                );
+                parent = Some(block.hash());
+                block
+            })
+            .collect::<Vec<_>>();
+
+        let provider = factory.provider_rw().unwrap();
+        for block in &blocks {
+            provider
+                .insert_block(block.clone().try_recover().unwrap(), StorageLocation::Database)
+                .unwrap();
+        }
+        provider.commit().unwrap();
+
+        // Readers opened before the removal keep observing all blocks.
+        let reader = factory.provider().unwrap();
+
+        let provider = factory.provider_rw().unwrap();
+        provider.remove_blocks_above(1, StorageLocation::Database).unwrap();
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        for block in &blocks {
+            let expected = (block.number <= 1).then(|| block.hash());
+            assert_eq!(provider.block_hash(block.number).unwrap(), expected);
+            assert_eq!(reader.block_hash(block.number).unwrap(), Some(block.hash()));
+
+            let tx_hash = *block.body().transactions[0].tx_hash();
+            let expected = (block.number <= 1).then_some(block.number * 2);
+            assert_eq!(provider.transaction_id(tx_hash).unwrap(), expected);
+            assert_eq!(reader.transaction_id(tx_hash).unwrap(), Some(block.number * 2));
+        }
+    }
+
+    #[test]
+    fn take_block_transaction_range_recover_senders_btree() {
+        let factory = create_test_provider_factory_with_btree();
+
+        let mut rng = generators::rng();
+        let block =
+            random_block(&mut rng, 0, BlockParams { tx_count: Some(3), ..Default::default() });
+
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .insert_block(block.clone().try_recover().unwrap(), StorageLocation::Database)
+            .unwrap();
+
+        let senders = provider.take::<tables::TransactionSenders>(1..=2);
+        assert_eq!(
+            senders,
+            Ok((1..=2)
+                .map(|tx_number| (
+                    tx_number,
+                    block.body().transactions[tx_number as usize].recover_signer().unwrap()
+                ))
+                .collect())
+        );
+        assert_eq!(provider.senders_by_tx_range(0..=2).unwrap().len(), 1);
+    }
+
     #[test]
     fn take_block_transaction_range_recover_senders() {
         let factory = create_test_provider_factory();
@@ -790,6 +923,156 @@ mod tests {
         }
     }
 
+    #[test]
+    fn expired_history_from_era_files() {
+        let mut rng = generators::rng();
+
+        // blocks 0..=5 in static files of two blocks each, with receipts committed to by their
+        // headers
+        let mut parent = B256::ZERO;
+        let mut blocks = Vec::new();
+        let mut receipts = Vec::new();
+        for number in 0..=5 {
+            let block = random_block(
+                &mut rng,
+                number,
+                BlockParams {
+                    parent: Some(parent),
+                    tx_count: Some(2),
+                    ommers_count: Some(0),
+                    ..Default::default()
+                },
+            );
+            let block_receipts = block
+                .body()
+                .transactions
+                .iter()
+                .map(|tx| random_receipt(&mut rng, tx, Some(1)))
+                .collect::<Vec<_>>();
+            let (mut header, body) = block.split_header_body();
+            header.receipts_root =
+                Receipt::calculate_receipt_root_no_memo(&block_receipts.iter().collect::<Vec<_>>());
+            let block = SealedBlock::<reth_primitives::Block>::seal_parts(header, body);
+            parent = block.hash();
+            blocks.push(block);
+            receipts.push(block_receipts);
+        }
+
+        let (static_dir, _) = create_test_static_files_dir();
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::<MockNodeTypesWithDB>::new(
+            db.clone(),
+            MAINNET.clone(),
+            StaticFileProvider::read_write(&static_dir).unwrap().with_custom_blocks_per_file(2),
+        );
+        let provider = factory.database_provider_rw().unwrap();
+        for block in &blocks {
+            provider
+                .insert_block(block.clone().try_recover().unwrap(), StorageLocation::StaticFiles)
+                .unwrap();
+        }
+        let execution_outcome = ExecutionOutcome::new(
+            Default::default(),
+            Receipts {
+                receipt_vec: receipts
+                    .iter()
+                    .map(|receipts| receipts.iter().cloned().map(Some).collect())
+                    .collect(),
+            },
+            0,
+            Default::default(),
+        );
+        provider
+            .write_state(&execution_outcome, OriginalValuesKnown::Yes, StorageLocation::StaticFiles)
+            .unwrap();
+        UnifiedStorageWriter::commit(provider).unwrap();
+
+        // expire blocks 0..=3 like the bodies prune segment does
+        let static_file_provider = factory.static_file_provider();
+        for segment in [StaticFileSegment::Transactions, StaticFileSegment::Receipts] {
+            assert_eq!(
+                static_file_provider.delete_segment_below_block(segment, 4).unwrap().len(),
+                2
+            );
+        }
+        let provider = factory.provider_rw().unwrap();
+        for number in 0..=3 {
+            provider.tx_ref().delete::<tables::BlockBodyIndices>(number, None).unwrap();
+        }
+        provider.commit().unwrap();
+        drop((static_file_provider, factory));
+
+        // expired history isn't available without era files
+        let factory = ProviderFactory::<MockNodeTypesWithDB>::new(
+            db.clone(),
+            MAINNET.clone(),
+            StaticFileProvider::read_write(&static_dir).unwrap().with_custom_blocks_per_file(2),
+        );
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.earliest_block_number().unwrap(), 4);
+        assert_eq!(provider.block(1.into()).unwrap(), None);
+        assert_eq!(provider.transaction_by_id(2).unwrap(), None);
+        assert_eq!(provider.receipt(2).unwrap(), None);
+        assert_eq!(provider.block_range(0..=5).unwrap(), vec![]);
+        drop((provider, factory));
+
+        // write blocks 0..=3 to an era file
+        let era_dir = tempfile::tempdir().unwrap();
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        for (block, receipts) in blocks.iter().zip(&receipts).take(4) {
+            let tuple = BlockTuple {
+                header: alloy_rlp::encode(block.header()),
+                body: alloy_rlp::encode(block.body()),
+                receipts: alloy_rlp::encode(
+                    receipts.iter().cloned().map(ReceiptWithBloom::from).collect::<Vec<_>>(),
+                ),
+                total_difficulty: U256::ZERO,
+            };
+            writer.push(block.number, &tuple).unwrap();
+        }
+        let (bytes, accumulator) = writer.finish().unwrap();
+        fs::write(era_dir.path().join(era1::file_name("mainnet", 0, accumulator)), bytes).unwrap();
+
+        let factory = ProviderFactory::<MockNodeTypesWithDB>::new(
+            db,
+            MAINNET.clone(),
+            StaticFileProvider::read_write(&static_dir)
+                .unwrap()
+                .with_custom_blocks_per_file(2)
+                .with_era_history(era_dir.path())
+                .unwrap(),
+        );
+        let provider = factory.provider().unwrap();
+
+        let block = &blocks[1];
+        assert_eq!(provider.block(1.into()).unwrap(), Some(block.clone().into_block()));
+        assert_eq!(
+            provider.transaction_by_id(3).unwrap().as_ref(),
+            Some(&block.body().transactions[1])
+        );
+        assert_eq!(provider.receipt(3).unwrap().as_ref(), Some(&receipts[1][1]));
+        assert_eq!(provider.receipts_by_block(1.into()).unwrap().as_ref(), Some(&receipts[1]));
+
+        let tx_hash = *block.body().transactions[1].tx_hash();
+        assert_eq!(
+            provider.transaction_by_hash(tx_hash).unwrap().as_ref(),
+            Some(&block.body().transactions[1])
+        );
+        let (_, meta) = provider.transaction_by_hash_with_meta(tx_hash).unwrap().unwrap();
+        assert_eq!((meta.block_number, meta.index), (1, 1));
+
+        let recovered =
+            provider.sealed_block_with_senders(2.into(), TransactionVariant::NoHash).unwrap();
+        assert_eq!(recovered, Some(blocks[2].clone().try_recover().unwrap()));
+
+        // ranges spanning expired and available history are contiguous
+        assert_eq!(
+            provider.block_range(0..=5).unwrap(),
+            blocks.iter().cloned().map(SealedBlock::into_block).collect::<Vec<_>>()
+        );
+        assert_eq!(provider.block_with_senders_range(2..=4).unwrap().len(), 3);
+    }
+
     #[test]
     fn header_sync_gap_lookup() {
         let factory = create_test_provider_factory();
@@ -65,7 +65,7 @@ use reth_trie::{
     updates::{StorageTrieUpdates, TrieUpdates},
     HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles,
 };
-use reth_trie_db::{DatabaseStateRoot, DatabaseStorageTrieCursor};
+use reth_trie_db::{DatabaseStateRoot, DatabaseStorageTrieCursor, DatabaseTrieChangeSets};
 use revm::db::states::{
     PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset,
 };
@@ -145,6 +145,8 @@ pub struct DatabaseProvider<TX, N: NodeTypes> {
     static_file_provider: StaticFileProvider<N::Primitives>,
     /// Pruning configuration
     prune_modes: PruneModes,
+    /// Number of most recent blocks trie changesets are retained for, if they're recorded.
+    trie_changesets: Option<u64>,
     /// Node storage handler.
     storage: Arc<N::Storage>,
 }
@@ -154,6 +156,19 @@ impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
     pub const fn prune_modes_ref(&self) -> &PruneModes {
         &self.prune_modes
     }
+
+    /// Sets the number of most recent blocks trie changesets are recorded and retained for, or
+    /// disables them if [`None`].
+    pub const fn with_trie_changesets(mut self, distance: Option<u64>) -> Self {
+        self.trie_changesets = distance;
+        self
+    }
+
+    /// Returns the number of most recent blocks trie changesets are retained for, if they're
+    /// recorded.
+    pub const fn trie_changesets_distance(&self) -> Option<u64> {
+        self.trie_changesets
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -243,7 +258,7 @@ impl<TX: DbTxMut, N: NodeTypes> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self { tx, chain_spec, static_file_provider, prune_modes, trie_changesets: None, storage }
     }
 }
 
@@ -337,6 +352,7 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
             })))
         }
         self.write_trie_updates(&trie_updates)?;
+        self.remove_trie_changesets_from(*range.start())?;
 
         Ok(())
     }
@@ -510,7 +526,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self { tx, chain_spec, static_file_provider, prune_modes, trie_changesets: None, storage }
     }
 
     /// Consume `DbTx` or `DbTxMut`.
@@ -552,6 +568,73 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         )
     }
 
+    /// Returns `true` if the body and receipts of the block have been expired from static files.
+    fn is_history_expired(&self, block_number: BlockNumber) -> bool {
+        block_number < self.static_file_provider.earliest_history_height()
+    }
+
+    /// Returns the body of a block whose history has been expired, if the era files that contain
+    /// it are configured.
+    fn expired_block_body(&self, block_number: BlockNumber) -> ProviderResult<Option<BodyTy<N>>> {
+        let Some(era_history) = self.static_file_provider.era_history() else { return Ok(None) };
+        let Some(header) = self.sealed_header(block_number)? else { return Ok(None) };
+        era_history.body(&header)
+    }
+
+    /// Returns the receipts of a block whose history has been expired, if the era files that
+    /// contain it are configured.
+    fn expired_block_receipts(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Option<Vec<ReceiptTy<N>>>> {
+        let Some(era_history) = self.static_file_provider.era_history() else { return Ok(None) };
+        let Some(header) = self.sealed_header(block_number)? else { return Ok(None) };
+        era_history.receipts(&header)
+    }
+
+    /// Returns `true` if the transaction and its receipt have been expired from static files.
+    fn is_transaction_expired(&self, id: TxNumber) -> bool {
+        id < self.static_file_provider.earliest_history_tx()
+    }
+
+    /// Returns the block of a transaction whose history has been expired and the index of the
+    /// transaction in it.
+    ///
+    /// The block body indices of expired blocks are pruned, so the block is found using the
+    /// [`tables::TransactionBlocks`] entries of the block and the one before it.
+    fn expired_transaction_block(
+        &self,
+        id: TxNumber,
+    ) -> ProviderResult<Option<(BlockNumber, usize)>> {
+        let mut cursor = self.tx.cursor_read::<tables::TransactionBlocks>()?;
+        let Some((_, block_number)) = cursor.seek(id)? else { return Ok(None) };
+        let first_tx_num =
+            cursor.prev()?.map(|(last_tx_num, _)| last_tx_num + 1).unwrap_or_default();
+        Ok(Some((block_number, (id - first_tx_num) as usize)))
+    }
+
+    /// Returns a transaction whose history has been expired, if the era files that contain it are
+    /// configured.
+    fn expired_transaction(&self, id: TxNumber) -> ProviderResult<Option<TxTy<N>>> {
+        let Some((block_number, index)) = self.expired_transaction_block(id)? else {
+            return Ok(None)
+        };
+        Ok(self
+            .expired_block_body(block_number)?
+            .and_then(|body| body.into_transactions().into_iter().nth(index)))
+    }
+
+    /// Returns a receipt whose history has been expired, if the era files that contain it are
+    /// configured.
+    fn expired_receipt(&self, id: TxNumber) -> ProviderResult<Option<ReceiptTy<N>>> {
+        let Some((block_number, index)) = self.expired_transaction_block(id)? else {
+            return Ok(None)
+        };
+        Ok(self
+            .expired_block_receipts(block_number)?
+            .and_then(|receipts| receipts.into_iter().nth(index)))
+    }
+
     fn block_with_senders<H, HF, B, BF>(
         &self,
         id: BlockHashOrNumber,
@@ -567,6 +650,12 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         let Some(block_number) = self.convert_hash_or_number(id)? else { return Ok(None) };
         let Some(header) = header_by_number(block_number)? else { return Ok(None) };
 
+        if self.is_history_expired(block_number) {
+            let Some(body) = self.expired_block_body(block_number)? else { return Ok(None) };
+            let senders = body.recover_signers_unchecked()?;
+            return construct_block(header, body, senders)
+        }
+
         // Get the block body
         //
         // If the body indices are not found, this means that the transactions either do not exist
@@ -598,7 +687,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
     /// Uses the provided `headers_range` to get the headers for the range, and `assemble_block` to
     /// construct blocks from the following inputs:
     ///     – Header
-    ///     - Range of transaction numbers
+    ///     - Range of transaction numbers, `None` if the history of the block has been expired
     ///     – Ommers
     ///     – Withdrawals
     ///     – Senders
@@ -610,8 +699,8 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
     ) -> ProviderResult<Vec<R>>
     where
         H: AsRef<HeaderTy<N>>,
-        HF: FnOnce(RangeInclusive<BlockNumber>) -> ProviderResult<Vec<H>>,
-        F: FnMut(H, BodyTy<N>, Range<TxNumber>) -> ProviderResult<R>,
+        HF: Fn(RangeInclusive<BlockNumber>) -> ProviderResult<Vec<H>>,
+        F: FnMut(H, BodyTy<N>, Option<Range<TxNumber>>) -> ProviderResult<R>,
     {
         if range.is_empty() {
             return Ok(Vec::new())
@@ -620,6 +709,25 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         let len = range.end().saturating_sub(*range.start()) as usize;
         let mut blocks = Vec::with_capacity(len);
 
+        // Expired blocks are read from era files, stopping at the first one that isn't available
+        // to return a contiguous range.
+        let earliest_history_height = self.static_file_provider.earliest_history_height();
+        let range = if *range.start() < earliest_history_height {
+            let expired = *range.start()..=(*range.end()).min(earliest_history_height - 1);
+            for (number, header) in expired.clone().zip(headers_range(expired)?) {
+                let Some(body) = self.expired_block_body(number)? else { return Ok(blocks) };
+                blocks.push(assemble_block(header, body, None)?);
+            }
+
+            let range = earliest_history_height..=*range.end();
+            if range.is_empty() {
+                return Ok(blocks)
+            }
+            range
+        } else {
+            range
+        };
+
         let headers = headers_range(range.clone())?;
         let mut tx_cursor = self.tx.cursor_read::<tables::Transactions<TxTy<N>>>()?;
 
@@ -649,7 +757,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         let bodies = self.storage.reader().read_block_bodies(self, inputs)?;
 
         for ((tx_range, header), body) in present_headers.into_iter().zip(bodies) {
-            blocks.push(assemble_block(header, body, tx_range)?);
+            blocks.push(assemble_block(header, body, Some(tx_range))?);
         }
 
         Ok(blocks)
@@ -679,9 +787,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         let mut senders_cursor = self.tx.cursor_read::<tables::TransactionSenders>()?;
 
         self.block_range(range, headers_range, |header, body, tx_range| {
-            let senders = if tx_range.is_empty() {
-                Vec::new()
-            } else {
+            let senders = if let Some(tx_range) = tx_range {
                 // fetch senders from the senders table
                 let known_senders =
                     senders_cursor
@@ -701,6 +807,9 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
                 }
 
                 senders
+            } else {
+                // the senders of expired blocks are pruned with their history
+                body.recover_signers_unchecked()?
             };
 
             assemble_block(header, body, senders)
@@ -1143,6 +1252,10 @@ impl<TX: DbTx + 'static, N: NodeTypes> BlockNumReader for DatabaseProvider<TX, N
             .unwrap_or_default())
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        Ok(self.static_file_provider.earliest_history_height())
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         Ok(self.tx.get::<tables::HeaderNumbers>(hash)?)
     }
@@ -1170,6 +1283,13 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> BlockReader for DatabaseProvid
     /// will return None.
     fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Self::Block>> {
         if let Some(number) = self.convert_hash_or_number(id)? {
+            if self.is_history_expired(number) {
+                return Ok(self
+                    .expired_block_body(number)?
+                    .zip(self.header_by_number(number)?)
+                    .map(|(body, header)| Self::Block::new(header, body)))
+            }
+
             if let Some(header) = self.header_by_number(number)? {
                 // If the body indices are not found, this means that the transactions either do not
                 // exist in the database yet, or they do exit but are not indexed.
@@ -1377,6 +1497,10 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
     }
 
     fn transaction_by_id(&self, id: TxNumber) -> ProviderResult<Option<Self::Transaction>> {
+        if self.is_transaction_expired(id) {
+            return self.expired_transaction(id)
+        }
+
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::Transactions,
             id,
@@ -1389,6 +1513,10 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
         &self,
         id: TxNumber,
     ) -> ProviderResult<Option<Self::Transaction>> {
+        if self.is_transaction_expired(id) {
+            return self.expired_transaction(id)
+        }
+
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::Transactions,
             id,
@@ -1417,13 +1545,18 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
                 {
                     if let Some(sealed_header) = self.sealed_header(block_number)? {
                         let (header, block_hash) = sealed_header.split();
-                        if let Some(block_body) = self.block_body_indices(block_number)? {
+                        let index = if self.is_transaction_expired(transaction_id) {
+                            self.expired_transaction_block(transaction_id)?
+                                .map(|(_, index)| index as u64)
+                        } else {
                             // the index of the tx in the block is the offset:
                             // len([start..tx_id])
                             // NOTE: `transaction_id` is always `>=` the block's first
                             // index
-                            let index = transaction_id - block_body.first_tx_num();
-
+                            self.block_body_indices(block_number)?
+                                .map(|block_body| transaction_id - block_body.first_tx_num())
+                        };
+                        if let Some(index) = index {
                             let meta = TransactionMeta {
                                 tx_hash,
                                 index,
@@ -1456,6 +1589,12 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
         let mut tx_cursor = self.tx.cursor_read::<tables::Transactions<Self::Transaction>>()?;
 
         if let Some(block_number) = self.convert_hash_or_number(id)? {
+            if self.is_history_expired(block_number) {
+                return Ok(self
+                    .expired_block_body(block_number)?
+                    .map(|body| body.into_transactions()))
+            }
+
             if let Some(body) = self.block_body_indices(block_number)? {
                 let tx_range = body.tx_num_range();
                 return if tx_range.is_empty() {
@@ -1517,6 +1656,10 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
     type Receipt = ReceiptTy<N>;
 
     fn receipt(&self, id: TxNumber) -> ProviderResult<Option<Self::Receipt>> {
+        if self.is_transaction_expired(id) {
+            return self.expired_receipt(id)
+        }
+
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::Receipts,
             id,
@@ -1538,6 +1681,10 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
         block: BlockHashOrNumber,
     ) -> ProviderResult<Option<Vec<Self::Receipt>>> {
         if let Some(number) = self.convert_hash_or_number(block)? {
+            if self.is_history_expired(number) {
+                return self.expired_block_receipts(number)
+            }
+
             if let Some(body) = self.block_body_indices(number)? {
                 let tx_range = body.tx_num_range();
                 return if tx_range.is_empty() {
@@ -2352,6 +2499,69 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> TrieWriter for DatabaseProvider
 
         Ok(num_entries)
     }
+
+    fn write_trie_changesets(
+        &self,
+        block_number: BlockNumber,
+        trie_updates: &TrieUpdates,
+    ) -> ProviderResult<usize> {
+        let Some(distance) = self.trie_changesets else { return Ok(0) };
+
+        let changesets = DatabaseTrieChangeSets::new(self.tx_ref());
+        changesets.ensure_contiguous(block_number)?;
+        let num_entries = changesets.write(block_number, trie_updates)?;
+        changesets.prune((block_number + 1).saturating_sub(distance))?;
+
+        Ok(num_entries)
+    }
+
+    fn write_trie_changesets_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<B256>> {
+        let Some(distance) = self.trie_changesets else { return Ok(None) };
+        let end = *range.end();
+        let start = (*range.start()).max((end + 1).saturating_sub(distance));
+        if range.is_empty() || start > end {
+            return Ok(None)
+        }
+
+        let changesets = DatabaseTrieChangeSets::new(self.tx_ref());
+        changesets.ensure_contiguous(start)?;
+
+        // Revert the hashed state and the trie one block at a time, like the unwind does. The
+        // trie updates of every reverted block restore the nodes it changed to their state before
+        // the block.
+        for block_number in (start..=end).rev() {
+            self.unwind_account_hashing_range(block_number..=block_number)?;
+            self.unwind_storage_hashing_range(BlockNumberAddress::range(
+                block_number..=block_number,
+            ))?;
+            let (_, trie_updates) =
+                StateRoot::incremental_root_with_updates(&self.tx, block_number..=block_number)
+                    .map_err(reth_db::DatabaseError::from)?;
+            changesets.write_reverted(block_number, &trie_updates)?;
+            self.write_trie_updates(&trie_updates)?;
+        }
+
+        // Restore the hashed state and the trie at the end of the range.
+        let storages = self.plain_state_storages(self.changed_storages_with_range(start..=end)?)?;
+        self.insert_storage_for_hashing(storages)?;
+        let accounts = self.basic_accounts(self.changed_accounts_with_range(start..=end)?)?;
+        self.insert_account_for_hashing(accounts)?;
+        let (state_root, trie_updates) =
+            StateRoot::incremental_root_with_updates(&self.tx, start..=end)
+                .map_err(reth_db::DatabaseError::from)?;
+        self.write_trie_updates(&trie_updates)?;
+
+        changesets.prune((end + 1).saturating_sub(distance))?;
+
+        Ok(Some(state_root))
+    }
+
+    fn remove_trie_changesets_from(&self, from: BlockNumber) -> ProviderResult<()> {
+        Ok(DatabaseTrieChangeSets::new(self.tx_ref()).unwind(from)?)
+    }
 }
 
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> StorageTrieWriter for DatabaseProvider<TX, N> {
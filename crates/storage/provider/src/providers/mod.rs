@@ -9,7 +9,7 @@ pub use database::*;
 
 mod static_file;
 pub use static_file::{
-    StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderRW,
+    EraHistory, StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderRW,
     StaticFileProviderRWRefMut, StaticFileWriter,
 };
 
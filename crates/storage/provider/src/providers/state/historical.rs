@@ -27,7 +27,8 @@ use reth_trie::{
 };
 use reth_trie_db::{
     DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
-    DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
+    DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieChangeSets, DatabaseTrieUpdates,
+    DatabaseTrieWitness, StateCommitment,
 };
 use std::fmt::Debug;
 
@@ -42,6 +43,8 @@ use std::fmt::Debug;
 /// - [`tables::StoragesHistory`]
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
+/// - [`tables::AccountsTrieChangeSets`]
+/// - [`tables::StoragesTrieChangeSets`]
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, Provider> {
     /// Database provider
@@ -140,6 +143,35 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
         >(self.tx(), self.block_number)?)
     }
 
+    /// Retrieve the intermediate trie nodes as they were at the start of this block.
+    ///
+    /// Returns [`None`] if trie changesets are missing for any block between this block and the
+    /// tip, in which case the nodes have to be recomputed from the revert state.
+    fn revert_trie_nodes(&self) -> ProviderResult<Option<TrieUpdates>> {
+        if !self.trie_changesets_available()? {
+            return Ok(None)
+        }
+
+        Ok(Some(TrieUpdates::from_reverts(self.tx(), self.block_number)?))
+    }
+
+    /// Returns `true` if trie changesets exist for every block between this block and the tip.
+    fn trie_changesets_available(&self) -> ProviderResult<bool> {
+        let tip = self.provider.last_block_number()?;
+        Ok(DatabaseTrieChangeSets::new(self.tx()).is_available(self.block_number..=tip)?)
+    }
+
+    /// Prepend the revert state to the trie input. If trie changesets are available, the reverted
+    /// intermediate nodes are prepended as well, so that only the targets need to be recomputed.
+    fn prepend_reverts(&self, input: &mut TrieInput) -> ProviderResult<()> {
+        let revert_state = self.revert_state()?;
+        match self.revert_trie_nodes()? {
+            Some(nodes) => input.prepend_cached(nodes, revert_state),
+            None => input.prepend(revert_state),
+        }
+        Ok(())
+    }
+
     /// Retrieve revert hashed storage for this history provider and target address.
     fn revert_storage(&self, address: Address) -> ProviderResult<HashedStorage> {
         if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
@@ -297,7 +329,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
     }
 
     fn state_root_from_nodes(&self, mut input: TrieInput) -> ProviderResult<B256> {
-        input.prepend(self.revert_state()?);
+        self.prepend_reverts(&mut input)?;
         StateRoot::overlay_root_from_nodes(self.tx(), input)
             .map_err(|err| ProviderError::Database(err.into()))
     }
@@ -316,7 +348,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
         &self,
         mut input: TrieInput,
     ) -> ProviderResult<(B256, TrieUpdates)> {
-        input.prepend(self.revert_state()?);
+        self.prepend_reverts(&mut input)?;
         StateRoot::overlay_root_from_nodes_with_updates(self.tx(), input)
             .map_err(|err| ProviderError::Database(err.into()))
     }
@@ -371,7 +403,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         address: Address,
         slots: &[B256],
     ) -> ProviderResult<AccountProof> {
-        input.prepend(self.revert_state()?);
+        self.prepend_reverts(&mut input)?;
         Proof::overlay_account_proof(self.tx(), input, address, slots).map_err(ProviderError::from)
     }
 
@@ -380,7 +412,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         mut input: TrieInput,
         targets: MultiProofTargets,
     ) -> ProviderResult<MultiProof> {
-        input.prepend(self.revert_state()?);
+        self.prepend_reverts(&mut input)?;
         Proof::overlay_multiproof(self.tx(), input, targets).map_err(ProviderError::from)
     }
 
@@ -389,9 +421,13 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         mut input: TrieInput,
         target: HashedPostState,
     ) -> ProviderResult<B256HashMap<Bytes>> {
-        input.prepend(self.revert_state()?);
+        self.prepend_reverts(&mut input)?;
         TrieWitness::overlay_witness(self.tx(), input, target).map_err(ProviderError::from)
     }
+
+    fn has_trie_changesets(&self) -> ProviderResult<bool> {
+        self.trie_changesets_available()
+    }
 }
 
 impl<Provider: StateCommitmentProvider> HashedPostStateProvider
@@ -0,0 +1,178 @@
+use alloy_consensus::ReceiptWithBloom;
+use alloy_rlp::Decodable;
+use parking_lot::Mutex;
+use reth_era::era1::{BlockTuple, Era1File, ERA1_EXTENSION, MAX_BLOCKS_PER_ERA1};
+use reth_primitives::SealedHeader;
+use reth_primitives_traits::{
+    proofs::ordered_trie_root_with_encoder, BlockBody, BlockHeader, Receipt,
+};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::debug;
+
+/// Directory of ERA1 files that expired static file history is served from.
+///
+/// The directory is indexed by epoch once on creation, following the
+/// `<network>-<epoch>-<short-root>.era1` naming convention, and every block read from the files is
+/// verified against its canonical header before it's returned.
+#[derive(Debug)]
+pub struct EraHistory {
+    /// Directory where the ERA1 files are located.
+    dir: PathBuf,
+    /// Paths of the ERA1 files in the directory, by epoch.
+    files: BTreeMap<u64, PathBuf>,
+    /// Most recently read file. Requests for expired history are usually close to each other.
+    cache: Mutex<Option<Arc<Era1File>>>,
+}
+
+impl EraHistory {
+    /// Creates a new [`EraHistory`] reading from the ERA1 files in the given directory.
+    pub fn new(dir: impl Into<PathBuf>) -> ProviderResult<Self> {
+        let dir = dir.into();
+        let mut files = BTreeMap::new();
+        let entries = reth_fs_util::read_dir(&dir)
+            .map_err(|err| ProviderError::FsPathError(err.to_string()))?;
+        for entry in entries {
+            let path = entry.map_err(|err| ProviderError::EraHistory(err.to_string()))?.path();
+            if path.extension().is_none_or(|extension| extension != ERA1_EXTENSION) {
+                continue
+            }
+            let Some(epoch) =
+                path.file_stem().and_then(|stem| parse_epoch(&stem.to_string_lossy()))
+            else {
+                debug!(target: "provider::static_file", ?path, "Skipping era file with invalid name");
+                continue
+            };
+            if let Some(other) = files.insert(epoch, path.clone()) {
+                return Err(ProviderError::EraHistory(format!(
+                    "multiple era files for epoch {epoch}: {} and {}",
+                    other.display(),
+                    path.display()
+                )))
+            }
+        }
+
+        Ok(Self { dir, files, cache: Mutex::new(None) })
+    }
+
+    /// Returns the directory where the ERA1 files are located.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the body of the block with the given header, if its ERA1 file is available.
+    pub fn body<H, B>(&self, header: &SealedHeader<H>) -> ProviderResult<Option<B>>
+    where
+        H: BlockHeader,
+        B: BlockBody,
+    {
+        let Some(tuple) = self.block_tuple(header)? else { return Ok(None) };
+        let body = tuple.decode_body::<B>().map_err(era_error)?;
+
+        let transactions_root = body.calculate_tx_root();
+        if transactions_root != header.transactions_root() {
+            return Err(ProviderError::EraHistory(format!(
+                "transactions root mismatch for block {}: computed {transactions_root}, expected {}",
+                header.number(),
+                header.transactions_root()
+            )))
+        }
+
+        if body
+            .calculate_ommers_root()
+            .is_some_and(|ommers_hash| ommers_hash != header.ommers_hash())
+        {
+            return Err(ProviderError::EraHistory(format!(
+                "ommers hash mismatch for block {}",
+                header.number()
+            )))
+        }
+
+        Ok(Some(body))
+    }
+
+    /// Returns the receipts of the block with the given header, if its ERA1 file is available.
+    pub fn receipts<H, R>(&self, header: &SealedHeader<H>) -> ProviderResult<Option<Vec<R>>>
+    where
+        H: BlockHeader,
+        R: Receipt,
+    {
+        let Some(tuple) = self.block_tuple(header)? else { return Ok(None) };
+        let receipts = Vec::<ReceiptWithBloom<R>>::decode(&mut tuple.receipts.as_slice())?;
+
+        let receipts_root = ordered_trie_root_with_encoder(&receipts, |receipt, buf| {
+            receipt.receipt.eip2718_encode_with_bloom(&receipt.logs_bloom, buf)
+        });
+        if receipts_root != header.receipts_root() {
+            return Err(ProviderError::EraHistory(format!(
+                "receipts root mismatch for block {}: computed {receipts_root}, expected {}",
+                header.number(),
+                header.receipts_root()
+            )))
+        }
+
+        Ok(Some(receipts.into_iter().map(|receipt| receipt.receipt).collect()))
+    }
+
+    /// Returns the block tuple of the block with the given header, if its ERA1 file is available.
+    fn block_tuple<H: BlockHeader>(
+        &self,
+        header: &SealedHeader<H>,
+    ) -> ProviderResult<Option<BlockTuple>> {
+        let number = header.number();
+        let Some(file) = self.file(number / MAX_BLOCKS_PER_ERA1 as u64)? else { return Ok(None) };
+        let Some(tuple) =
+            number.checked_sub(file.start_block).and_then(|index| file.blocks.get(index as usize))
+        else {
+            return Ok(None)
+        };
+
+        if tuple.hash() != header.hash() {
+            return Err(ProviderError::EraHistory(format!(
+                "block hash mismatch for block {number}: era file has {}, expected {}",
+                tuple.hash(),
+                header.hash()
+            )))
+        }
+
+        Ok(Some(tuple.clone()))
+    }
+
+    /// Returns the ERA1 file of the given epoch, if it exists in the directory.
+    fn file(&self, epoch: u64) -> ProviderResult<Option<Arc<Era1File>>> {
+        if let Some(file) = self.cache.lock().as_ref().filter(|file| file.epoch() == epoch) {
+            return Ok(Some(file.clone()))
+        }
+
+        let Some(path) = self.files.get(&epoch) else { return Ok(None) };
+        debug!(target: "provider::static_file", ?path, %epoch, "Loading era file");
+        let file = Era1File::open(path).map_err(era_error)?;
+        file.verify_accumulator().map_err(era_error)?;
+        if let Some(name) = path.file_name() {
+            file.verify_file_name(&name.to_string_lossy()).map_err(era_error)?;
+        }
+
+        // the lock isn't held while reading, concurrent readers of other epochs aren't blocked
+        let file = Arc::new(file);
+        *self.cache.lock() = Some(file.clone());
+        Ok(Some(file))
+    }
+}
+
+/// Parses the epoch of an ERA1 file from its name without extension,
+/// `<network>-<epoch>-<short-root>`.
+fn parse_epoch(stem: &str) -> Option<u64> {
+    let mut parts = stem.rsplitn(3, '-');
+    let _short_root = parts.next()?;
+    let epoch = parts.next()?;
+    let _network = parts.next()?;
+    epoch.parse().ok()
+}
+
+fn era_error(err: reth_era::EraError) -> ProviderError {
+    ProviderError::EraHistory(err.to_string())
+}
@@ -1,5 +1,5 @@
 use super::{
-    metrics::StaticFileProviderMetrics, writer::StaticFileWriters, LoadedJar,
+    metrics::StaticFileProviderMetrics, writer::StaticFileWriters, EraHistory, LoadedJar,
     StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
 };
 use crate::{
@@ -213,6 +213,11 @@ pub struct StaticFileProviderInner<N> {
     map: DashMap<(BlockNumber, StaticFileSegment), LoadedJar>,
     /// Max static file block for each segment
     static_files_max_block: RwLock<HashMap<StaticFileSegment, u64>>,
+    /// Min static file block for each segment. Higher than zero if the history of the segment
+    /// has been expired.
+    static_files_min_block: RwLock<HashMap<StaticFileSegment, u64>>,
+    /// Min static file transaction for each transaction based segment.
+    static_files_min_tx: RwLock<HashMap<StaticFileSegment, TxNumber>>,
     /// Available static file block ranges on disk indexed by max transactions.
     static_files_tx_index: RwLock<SegmentRanges>,
     /// Directory where `static_files` are located
@@ -221,6 +226,8 @@ pub struct StaticFileProviderInner<N> {
     writers: StaticFileWriters<N>,
     /// Metrics for the static files.
     metrics: Option<Arc<StaticFileProviderMetrics>>,
+    /// Era files to serve expired history from.
+    era_history: Option<EraHistory>,
     /// Access rights of the provider.
     access: StaticFileAccess,
     /// Number of blocks per file.
@@ -244,9 +251,12 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
             map: Default::default(),
             writers: Default::default(),
             static_files_max_block: Default::default(),
+            static_files_min_block: Default::default(),
+            static_files_min_tx: Default::default(),
             static_files_tx_index: Default::default(),
             path: path.as_ref().to_path_buf(),
             metrics: None,
+            era_history: None,
             access,
             blocks_per_file: DEFAULT_BLOCKS_PER_STATIC_FILE,
             _lock_file,
@@ -285,6 +295,21 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Self(Arc::new(provider))
     }
 
+    /// Serves expired history from the ERA1 files in the given directory.
+    ///
+    /// Returns an error if the directory can't be indexed.
+    pub fn with_era_history(self, dir: impl Into<PathBuf>) -> ProviderResult<Self> {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.era_history = Some(EraHistory::new(dir)?);
+        Ok(Self(Arc::new(provider)))
+    }
+
+    /// Returns the era files that expired history is served from, if configured.
+    pub fn era_history(&self) -> Option<&EraHistory> {
+        self.era_history.as_ref()
+    }
+
     /// Reports metrics for the static files.
     pub fn report_metrics(&self) -> ProviderResult<()> {
         let Some(metrics) = &self.metrics else { return Ok(()) };
@@ -420,8 +445,11 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
 
         jar.delete().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
 
+        // If the lowest jar was deleted, there are no static files left for this segment.
         let mut segment_max_block = None;
-        if fixed_block_range.start() > 0 {
+        if fixed_block_range.start() >
+            self.get_lowest_static_file_block(segment).unwrap_or_default()
+        {
             segment_max_block = Some(fixed_block_range.start() - 1)
         };
         self.update_index(segment, segment_max_block)?;
@@ -429,6 +457,82 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(())
     }
 
+    /// Returns the highest block of the jars that [`Self::delete_segment_below_block`] would delete
+    /// for the same arguments, if any.
+    pub fn highest_expirable_block(
+        &self,
+        segment: StaticFileSegment,
+        block: BlockNumber,
+    ) -> Option<BlockNumber> {
+        let lowest_block = self.get_lowest_static_file_block(segment)?;
+        let highest_block = self.get_highest_static_file_block(segment)?;
+        let bound =
+            self.find_fixed_range(block).start().min(self.find_fixed_range(highest_block).start());
+        bound.checked_sub(1).filter(|last_block| *last_block >= lowest_block)
+    }
+
+    /// Given a segment, it deletes all jars whose block range ends before `block`, expiring their
+    /// history. The jar holding the highest block of the segment is never deleted.
+    ///
+    /// Returns the segment headers of the deleted jars.
+    ///
+    /// CAUTION: destructive. Deletes files on disk.
+    pub fn delete_segment_below_block(
+        &self,
+        segment: StaticFileSegment,
+        block: BlockNumber,
+    ) -> ProviderResult<Vec<SegmentHeader>> {
+        let (Some(lowest_block), Some(highest_block)) = (
+            self.get_lowest_static_file_block(segment),
+            self.get_highest_static_file_block(segment),
+        ) else {
+            return Ok(Vec::new())
+        };
+        let highest_range = self.find_fixed_range(highest_block);
+
+        let mut deleted = Vec::new();
+        let mut last_deleted = None;
+        let mut range = self.find_fixed_range(lowest_block);
+        while range.end() < block && range.end() < highest_range.start() {
+            let key = (range.end(), segment);
+            let jar = if let Some((_, jar)) = self.map.remove(&key) {
+                jar.jar
+            } else {
+                NippyJar::<SegmentHeader>::load(&self.path.join(segment.filename(&range)))
+                    .map_err(|e| ProviderError::NippyJar(e.to_string()))?
+            };
+            deleted.push(jar.user_header().clone());
+            jar.delete().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+            trace!(target: "provider::static_file", ?segment, ?range, "Deleted expired static file");
+
+            last_deleted = Some(range);
+            range = self.find_fixed_range(range.end() + 1);
+        }
+
+        if let Some(last_deleted) = last_deleted {
+            let mut min_block = self.static_files_min_block.write();
+            let mut min_tx = self.static_files_min_tx.write();
+            let mut tx_index = self.static_files_tx_index.write();
+
+            min_block.insert(segment, last_deleted.end() + 1);
+            if let Some(index) = tx_index.get_mut(&segment) {
+                let retained = index.split_off(
+                    &index
+                        .iter()
+                        .find(|(_, block_range)| block_range.end() > last_deleted.end())
+                        .map(|(tx_end, _)| *tx_end)
+                        .unwrap_or(TxNumber::MAX),
+                );
+                if let Some((expired_tx_end, _)) = index.last_key_value() {
+                    min_tx.insert(segment, expired_tx_end + 1);
+                }
+                *index = retained;
+            }
+        }
+
+        Ok(deleted)
+    }
+
     /// Given a segment and block range it returns a cached
     /// [`StaticFileJarProvider`]. TODO(joshie): we should check the size and pop N if there's too
     /// many.
@@ -464,6 +568,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment: StaticFileSegment,
         block: u64,
     ) -> Option<SegmentRangeInclusive> {
+        if self.get_lowest_static_file_block(segment).is_some_and(|min| min > block) {
+            return None
+        }
         self.static_files_max_block
             .read()
             .get(&segment)
@@ -490,7 +597,10 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 // request tx is higher than highest static file tx
                 return None
             }
-            let tx_start = static_files_rev_iter.peek().map(|(tx_end, _)| *tx_end + 1).unwrap_or(0);
+            let tx_start =
+                static_files_rev_iter.peek().map(|(tx_end, _)| *tx_end + 1).unwrap_or_else(|| {
+                    self.static_files_min_tx.read().get(&segment).copied().unwrap_or_default()
+                });
             if tx_start <= tx {
                 return Some(self.find_fixed_range(block_range.end()))
             }
@@ -510,6 +620,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment_max_block: Option<BlockNumber>,
     ) -> ProviderResult<()> {
         let mut max_block = self.static_files_max_block.write();
+        let mut min_block = self.static_files_min_block.write();
+        let mut min_tx = self.static_files_min_tx.write();
         let mut tx_index = self.static_files_tx_index.write();
 
         match segment_max_block {
@@ -518,6 +630,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 max_block.insert(segment, segment_max_block);
                 let fixed_range = self.find_fixed_range(segment_max_block);
 
+                // The first static file of the segment sets its min block.
+                min_block.entry(segment).or_insert_with(|| fixed_range.start());
+
                 let jar = NippyJar::<SegmentHeader>::load(
                     &self.path.join(segment.filename(&fixed_range)),
                 )
@@ -572,6 +687,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             None => {
                 tx_index.remove(&segment);
                 max_block.remove(&segment);
+                min_block.remove(&segment);
+                min_tx.remove(&segment);
             }
         };
 
@@ -581,9 +698,13 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     /// Initializes the inner transaction and block index
     pub fn initialize_index(&self) -> ProviderResult<()> {
         let mut max_block = self.static_files_max_block.write();
+        let mut min_block = self.static_files_min_block.write();
+        let mut min_tx = self.static_files_min_tx.write();
         let mut tx_index = self.static_files_tx_index.write();
 
         max_block.clear();
+        min_block.clear();
+        min_tx.clear();
         tx_index.clear();
 
         for (segment, ranges) in
@@ -594,6 +715,15 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 max_block.insert(segment, block_range.end());
             }
 
+            // Update first block and transaction for each segment, which are higher than zero if
+            // its history has been expired
+            if let Some((block_range, _)) = ranges.first() {
+                min_block.insert(segment, block_range.start());
+            }
+            if let Some(tx_range) = ranges.iter().find_map(|(_, tx_range)| tx_range.as_ref()) {
+                min_tx.insert(segment, tx_range.start());
+            }
+
             // Update tx -> block_range index
             for (block_range, tx_range) in ranges {
                 if let Some(tx_range) = tx_range {
@@ -930,6 +1060,30 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         self.static_files_max_block.read().get(&segment).copied()
     }
 
+    /// Gets the lowest static file block if it exists for a static file segment.
+    ///
+    /// This is higher than zero if the history of the segment has been expired. If there is
+    /// nothing on disk for the given segment, this will return [`None`].
+    pub fn get_lowest_static_file_block(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
+        self.static_files_min_block.read().get(&segment).copied()
+    }
+
+    /// Returns the lowest block whose transactions are still available in static files, meaning
+    /// that bodies and receipts of all blocks below it have been expired.
+    pub fn earliest_history_height(&self) -> BlockNumber {
+        self.get_lowest_static_file_block(StaticFileSegment::Transactions).unwrap_or_default()
+    }
+
+    /// Returns the lowest transaction that is still available in static files, meaning that all
+    /// transactions and receipts below it have been expired.
+    pub fn earliest_history_tx(&self) -> TxNumber {
+        self.static_files_min_tx
+            .read()
+            .get(&StaticFileSegment::Transactions)
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// Gets the highest static file transaction.
     ///
     /// If there is nothing on disk for the given segment, this will return [`None`].
@@ -958,8 +1112,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         func: impl Fn(StaticFileJarProvider<'_, N>) -> ProviderResult<Option<T>>,
     ) -> ProviderResult<Option<T>> {
         if let Some(highest_block) = self.get_highest_static_file_block(segment) {
+            let lowest_block = self.get_lowest_static_file_block(segment).unwrap_or_default();
             let mut range = self.find_fixed_range(highest_block);
-            while range.end() > 0 {
+            while range.end() > lowest_block {
                 if let Some(res) = func(self.get_or_create_jar_provider(segment, &range)?)? {
                     return Ok(Some(res))
                 }
@@ -1597,6 +1752,10 @@ impl<N: NodePrimitives> BlockNumReader for StaticFileProvider<N> {
         Err(ProviderError::UnsupportedProvider)
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        Ok(self.earliest_history_height())
+    }
+
     fn block_number(&self, _hash: B256) -> ProviderResult<Option<BlockNumber>> {
         // Required data not present in static_files
         Err(ProviderError::UnsupportedProvider)
@@ -9,6 +9,9 @@ pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
 mod metrics;
 
+mod era;
+pub use era::EraHistory;
+
 use reth_nippy_jar::NippyJar;
 use reth_primitives::{static_file::SegmentHeader, StaticFileSegment};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -550,6 +553,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tx_based_expiry() {
+        let segments = [StaticFileSegment::Transactions, StaticFileSegment::Receipts];
+        let blocks_per_file = 10; // Number of blocks per file
+        let files_per_range = 3; // Number of files per range (data/conf/offset files)
+
+        fn assert_tx(
+            sf_rw: &StaticFileProvider<EthPrimitives>,
+            segment: StaticFileSegment,
+            id: TxNumber,
+            expected: Option<u64>,
+        ) {
+            // Uses cumulative_gas_used & nonce as ids.
+            if segment.is_receipts() {
+                assert_eq!(sf_rw.receipt(id).unwrap().map(|r| r.cumulative_gas_used), expected);
+            } else {
+                assert_eq!(sf_rw.transaction_by_id(id).unwrap().map(|t| t.nonce()), expected);
+            }
+        }
+
+        for segment in segments {
+            let (static_dir, _) = create_test_static_files_dir();
+
+            let sf_rw = StaticFileProvider::read_write(&static_dir)
+                .expect("Failed to create static file provider")
+                .with_custom_blocks_per_file(blocks_per_file);
+
+            setup_tx_based_scenario(&sf_rw, segment, blocks_per_file);
+            assert_eq!(sf_rw.get_lowest_static_file_block(segment), Some(0));
+
+            // Only the files whose whole block range is below the block are deleted.
+            let deleted = sf_rw.delete_segment_below_block(segment, blocks_per_file + 5).unwrap();
+            assert_eq!(
+                deleted.iter().map(|header| header.block_range().copied()).collect::<Vec<_>>(),
+                vec![Some(SegmentRangeInclusive::new(0, 9))]
+            );
+            assert_eq!(sf_rw.get_lowest_static_file_block(segment), Some(blocks_per_file));
+            assert_eq!(count_files_without_lockfile(&static_dir).unwrap(), files_per_range * 2);
+            assert_eq!(
+                sf_rw.tx_index().read().get(&segment).map(|index| index.len()),
+                Some(1),
+                "tx index mismatch"
+            );
+            assert_tx(&sf_rw, segment, 0, None);
+            assert_tx(&sf_rw, segment, 9, Some(9));
+
+            // The file holding the highest block is never deleted.
+            let deleted = sf_rw.delete_segment_below_block(segment, u64::MAX).unwrap();
+            assert_eq!(deleted.len(), 1);
+            assert_eq!(sf_rw.get_lowest_static_file_block(segment), Some(blocks_per_file * 2));
+            assert_eq!(count_files_without_lockfile(&static_dir).unwrap(), files_per_range);
+
+            // Expired history is detected from the files on disk.
+            let sf_rw = StaticFileProvider::read_write(&static_dir)
+                .expect("Failed to create static file provider")
+                .with_custom_blocks_per_file(blocks_per_file);
+            assert_eq!(sf_rw.get_lowest_static_file_block(segment), Some(blocks_per_file * 2));
+            assert_eq!(sf_rw.get_highest_static_file_tx(segment), Some(9));
+            assert_tx(&sf_rw, segment, 8, None);
+            assert_tx(&sf_rw, segment, 9, Some(9));
+        }
+    }
+
     /// Returns the number of files in the provided path, excluding ".lock" files.
     fn count_files_without_lockfile(path: impl AsRef<Path>) -> eyre::Result<usize> {
         let is_lockfile = |entry: &fs::DirEntry| {
@@ -53,6 +53,8 @@ pub struct MockEthProvider<T = TransactionSigned> {
     pub chain_spec: Arc<ChainSpec>,
     /// Local state roots
     pub state_roots: Arc<Mutex<Vec<B256>>>,
+    /// Earliest block whose body and receipts are available
+    pub earliest_block_number: Arc<Mutex<BlockNumber>>,
 }
 
 impl<T> MockEthProvider<T> {
@@ -98,6 +100,12 @@ impl<T> MockEthProvider<T> {
     pub fn add_state_root(&self, state_root: B256) {
         self.state_roots.lock().push(state_root);
     }
+
+    /// Set the earliest block whose body and receipts are available, expiring the history before
+    /// it
+    pub fn set_earliest_block_number(&self, number: BlockNumber) {
+        *self.earliest_block_number.lock() = number;
+    }
 }
 
 impl<T> Default for MockEthProvider<T> {
@@ -108,6 +116,7 @@ impl<T> Default for MockEthProvider<T> {
             accounts: Default::default(),
             chain_spec: Arc::new(reth_chainspec::ChainSpecBuilder::mainnet().build()),
             state_roots: Default::default(),
+            earliest_block_number: Default::default(),
         }
     }
 }
@@ -457,6 +466,10 @@ impl BlockNumReader for MockEthProvider {
         self.best_block_number()
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        Ok(*self.earliest_block_number.lock())
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<alloy_primitives::BlockNumber>> {
         let lock = self.blocks.lock();
         let num = lock.iter().find_map(|(h, b)| (*h == hash).then_some(b.number));
@@ -5,7 +5,10 @@ use crate::{
 use alloy_primitives::B256;
 use reth_chainspec::{ChainSpec, MAINNET};
 use reth_db::{
-    test_utils::{create_test_rw_db, create_test_static_files_dir, TempDatabase},
+    btree::BTreeDatabaseEnv,
+    test_utils::{
+        create_test_rw_btree_db, create_test_rw_db, create_test_static_files_dir, TempDatabase,
+    },
     DatabaseEnv,
 };
 use reth_errors::ProviderResult;
@@ -61,6 +64,19 @@ pub fn create_test_provider_factory_with_node_types<N: NodeTypes>(
     )
 }
 
+/// Creates test provider factory with mainnet chain spec, backed by the pure-Rust
+/// [`btree`](reth_db::btree) database.
+pub fn create_test_provider_factory_with_btree(
+) -> ProviderFactory<MockNodeTypesWithDB<TempDatabase<BTreeDatabaseEnv>>> {
+    let (static_dir, _) = create_test_static_files_dir();
+    let db = create_test_rw_btree_db();
+    ProviderFactory::new(
+        db,
+        MAINNET.clone(),
+        StaticFileProvider::read_write(static_dir.into_path()).expect("static file provider"),
+    )
+}
+
 /// Inserts the genesis alloc from the provided chain spec into the trie.
 pub fn insert_genesis<N: ProviderNodeTypes<ChainSpec = ChainSpec>>(
     provider_factory: &ProviderFactory<N>,
@@ -161,6 +161,7 @@ where
         //  * indices (already done basically)
         // Insert the blocks
         for ExecutedBlock { recovered_block, execution_output, hashed_state, trie } in blocks {
+            let block_number = recovered_block.number();
             self.database()
                 .insert_block(Arc::unwrap_or_clone(recovered_block), StorageLocation::Both)?;
 
@@ -175,6 +176,7 @@ where
             // insert hashes and intermediate merkle nodes
             self.database()
                 .write_hashed_state(&Arc::unwrap_or_clone(hashed_state).into_sorted())?;
+            self.database().write_trie_changesets(block_number, &trie)?;
             self.database().write_trie_updates(&trie)?;
         }
 
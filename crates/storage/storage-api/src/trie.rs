@@ -1,10 +1,11 @@
-use alloy_primitives::{map::B256HashMap, Address, Bytes, B256};
+use alloy_primitives::{map::B256HashMap, Address, BlockNumber, Bytes, B256};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     updates::{StorageTrieUpdates, TrieUpdates},
     AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StorageMultiProof,
     StorageProof, TrieInput,
 };
+use std::ops::RangeInclusive;
 
 /// A type that can compute the state root of a given post state.
 #[auto_impl::auto_impl(&, Box, Arc)]
@@ -90,6 +91,12 @@ pub trait StateProofProvider: Send + Sync {
         input: TrieInput,
         target: HashedPostState,
     ) -> ProviderResult<B256HashMap<Bytes>>;
+
+    /// Returns `true` if proofs of this historical state are generated from recorded trie
+    /// changesets, instead of recomputing the intermediate nodes of all reverted blocks.
+    fn has_trie_changesets(&self) -> ProviderResult<bool> {
+        Ok(false)
+    }
 }
 
 /// Trie Writer
@@ -99,6 +106,31 @@ pub trait TrieWriter: Send + Sync {
     ///
     /// Returns the number of entries modified.
     fn write_trie_updates(&self, trie_updates: &TrieUpdates) -> ProviderResult<usize>;
+
+    /// Records the current state of all trie nodes touched by the trie updates as the trie
+    /// changesets of the given block. This is a no-op if trie changesets are disabled.
+    ///
+    /// Must be called before the trie updates are written. Returns the number of entries written.
+    fn write_trie_changesets(
+        &self,
+        block_number: BlockNumber,
+        trie_updates: &TrieUpdates,
+    ) -> ProviderResult<usize>;
+
+    /// Records the trie changesets of a range of blocks whose trie updates were written at once,
+    /// e.g. by the pipeline. This is a no-op if trie changesets are disabled.
+    ///
+    /// Must be called after the hashed state and the trie updates of the range are written. Only
+    /// the blocks within the changesets retention distance of the range end are recorded. Returns
+    /// the state root of the trie at the range end, which is rebuilt in the process, if any
+    /// changesets were recorded.
+    fn write_trie_changesets_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<B256>>;
+
+    /// Removes the trie changesets of all blocks starting from `from` (inclusive).
+    fn remove_trie_changesets_from(&self, from: BlockNumber) -> ProviderResult<()>;
 }
 
 /// Storage Trie Writer
@@ -0,0 +1,217 @@
+use std::sync::{Arc, Mutex, OnceLock};
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{filter::Directive, reload, EnvFilter, Registry};
+
+use crate::layers::build_env_filter;
+
+/// The handle of the installed global tracer, see [`log_filter_handle`].
+static LOG_FILTER_HANDLE: OnceLock<LogFilterHandle> = OnceLock::new();
+
+/// Returns the [`LogFilterHandle`] of the global tracer, if it was installed by
+/// [`RethTracer`](crate::RethTracer).
+pub fn log_filter_handle() -> Option<&'static LogFilterHandle> {
+    LOG_FILTER_HANDLE.get()
+}
+
+/// Installs the handle of the global tracer.
+pub(crate) fn install_log_filter_handle(handle: LogFilterHandle) {
+    let _ = LOG_FILTER_HANDLE.set(handle);
+}
+
+/// Converts a geth style verbosity into a [`LevelFilter`].
+///
+/// The verbosity matches the `-v` flags of the CLI: `0` disables logging, `1` enables errors and
+/// `5` or above enables traces.
+pub const fn verbosity_level(verbosity: usize) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::OFF,
+        1 => LevelFilter::ERROR,
+        2 => LevelFilter::WARN,
+        3 => LevelFilter::INFO,
+        4 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+/// The filter of a single layer, as configured at startup.
+#[derive(Debug)]
+pub(crate) struct LayerFilter {
+    default_directive: Option<Directive>,
+    filters: String,
+    handle: reload::Handle<EnvFilter, Registry>,
+}
+
+impl LayerFilter {
+    /// Creates a new reloadable [`EnvFilter`] for a layer, returning the filter to attach to the
+    /// layer and the configuration needed to rebuild it.
+    pub(crate) fn new(
+        default_directive: Option<Directive>,
+        filters: &str,
+    ) -> eyre::Result<(reload::Layer<EnvFilter, Registry>, Self)> {
+        let (filter, handle) =
+            reload::Layer::new(build_env_filter(default_directive.clone(), filters)?);
+        Ok((filter, Self { default_directive, filters: filters.to_string(), handle }))
+    }
+
+    /// Builds the filter of this layer with the runtime overrides applied on top of its startup
+    /// configuration.
+    fn build(&self, overrides: &FilterOverrides) -> eyre::Result<EnvFilter> {
+        let mut filter = build_env_filter(self.default_directive.clone(), &self.filters)?;
+        if let Some(level) = overrides.verbosity {
+            filter = filter.add_directive(level.into());
+        }
+        for directive in &overrides.directives {
+            filter = filter.add_directive(directive.clone());
+        }
+        Ok(filter)
+    }
+}
+
+/// Runtime overrides of the log filters.
+#[derive(Debug, Clone, Default)]
+struct FilterOverrides {
+    /// Overrides the default level of all layers.
+    verbosity: Option<LevelFilter>,
+    /// Additional directives, e.g. `engine::tree=debug`.
+    directives: Vec<Directive>,
+}
+
+/// A handle to change the log filters of the stdout, file and journald layers at runtime.
+///
+/// Changes are applied on top of the filters the layers were configured with at startup, so the
+/// startup configuration can always be restored with [`LogFilterHandle::reset`].
+#[derive(Debug, Clone)]
+pub struct LogFilterHandle {
+    layers: Arc<Vec<LayerFilter>>,
+    overrides: Arc<Mutex<FilterOverrides>>,
+}
+
+impl LogFilterHandle {
+    /// Creates a new handle for the given layer filters.
+    pub(crate) fn new(layers: Vec<LayerFilter>) -> Self {
+        Self { layers: Arc::new(layers), overrides: Default::default() }
+    }
+
+    /// Sets the default level of all layers.
+    pub fn set_verbosity(&self, level: LevelFilter) -> eyre::Result<()> {
+        self.update(|overrides| {
+            overrides.verbosity = Some(level);
+            Ok(())
+        })
+    }
+
+    /// Sets per target levels, replacing the ones set previously.
+    ///
+    /// The pattern is a comma separated list of `target=level` pairs, e.g.
+    /// `engine::tree=debug,net::session=4`. Levels can be given by name or as a verbosity as
+    /// accepted by [`verbosity_level`]. An empty pattern removes all per target levels.
+    pub fn set_vmodule(&self, pattern: &str) -> eyre::Result<()> {
+        let directives = pattern
+            .split(',')
+            .map(str::trim)
+            .filter(|directive| !directive.is_empty())
+            .map(|directive| {
+                let directive = match directive.rsplit_once('=') {
+                    Some((target, level)) => match level.parse::<usize>() {
+                        Ok(verbosity) => format!("{target}={}", verbosity_level(verbosity)),
+                        Err(_) => directive.to_string(),
+                    },
+                    None => eyre::bail!("missing level in `{directive}`"),
+                };
+                Ok(directive.parse()?)
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        self.update(|overrides| {
+            overrides.directives = directives;
+            Ok(())
+        })
+    }
+
+    /// Replaces all runtime overrides with the given filter directives, e.g.
+    /// `info,engine::tree=debug`.
+    ///
+    /// An empty filter restores the filters the layers were configured with at startup.
+    pub fn set_filter(&self, filter: &str) -> eyre::Result<()> {
+        let directives = filter
+            .split(',')
+            .map(str::trim)
+            .filter(|directive| !directive.is_empty())
+            .map(|directive| Ok(directive.parse()?))
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        self.update(|overrides| {
+            *overrides = FilterOverrides { verbosity: None, directives };
+            Ok(())
+        })
+    }
+
+    /// Restores the filters the layers were configured with at startup.
+    pub fn reset(&self) -> eyre::Result<()> {
+        self.set_filter("")
+    }
+
+    /// Applies the update to the overrides and reloads the filters of all layers.
+    ///
+    /// The overrides are left unchanged if any of the filters could not be built.
+    fn update(&self, f: impl FnOnce(&mut FilterOverrides) -> eyre::Result<()>) -> eyre::Result<()> {
+        let mut overrides = self.overrides.lock().unwrap_or_else(|err| err.into_inner());
+
+        let mut updated = overrides.clone();
+        f(&mut updated)?;
+        let filters = self
+            .layers
+            .iter()
+            .map(|layer| layer.build(&updated))
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        for (layer, filter) in self.layers.iter().zip(filters) {
+            layer.handle.reload(filter)?;
+        }
+        *overrides = updated;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::Level;
+    use tracing_subscriber::{layer::SubscriberExt, Layer};
+
+    #[test]
+    fn reloads_layer_filters() {
+        let (filter, layer_filter) = LayerFilter::new(Some(LevelFilter::INFO.into()), "").unwrap();
+        let handle = LogFilterHandle::new(vec![layer_filter]);
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_subscriber::fmt::layer().with_writer(std::io::sink).with_filter(filter));
+
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(tracing::enabled!(Level::ERROR));
+            assert!(!tracing::enabled!(Level::DEBUG));
+            assert!(!tracing::enabled!(target: "net::session", Level::DEBUG));
+
+            handle.set_vmodule("net::session=4").unwrap();
+            assert!(!tracing::enabled!(Level::DEBUG));
+            assert!(tracing::enabled!(target: "net::session", Level::DEBUG));
+
+            handle.set_verbosity(verbosity_level(0)).unwrap();
+            assert!(!tracing::enabled!(Level::ERROR));
+            assert!(tracing::enabled!(target: "net::session", Level::DEBUG));
+
+            // invalid directives leave the filters untouched
+            assert!(handle.set_vmodule("net::session").is_err());
+            assert!(handle.set_filter("net::session=loud").is_err());
+            assert!(tracing::enabled!(target: "net::session", Level::DEBUG));
+
+            handle.set_filter("debug").unwrap();
+            assert!(tracing::enabled!(Level::DEBUG));
+
+            handle.reset().unwrap();
+            assert!(tracing::enabled!(Level::ERROR));
+            assert!(!tracing::enabled!(Level::DEBUG));
+            assert!(!tracing::enabled!(target: "net::session", Level::DEBUG));
+        });
+    }
+}
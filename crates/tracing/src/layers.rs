@@ -4,7 +4,11 @@ use rolling_file::{RollingConditionBasic, RollingFileAppender};
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::{filter::Directive, EnvFilter, Layer, Registry};
 
-use crate::formatter::LogFormat;
+use crate::{
+    filter::{LayerFilter, LogFilterHandle},
+    formatter::LogFormat,
+    otlp::OtlpConfig,
+};
 
 /// A worker guard returned by the file layer.
 ///
@@ -31,19 +35,24 @@ const DEFAULT_ENV_FILTER_DIRECTIVES: [&str; 5] = [
 ///
 /// `Layers` acts as a container for different logging layers such as stdout, file, or journald.
 /// Each layer can be configured separately and then combined into a tracing subscriber.
+///
+/// The filters of all layers are reloadable through the [`LogFilterHandle`] returned by
+/// [`Layers::into_inner`].
 pub(crate) struct Layers {
     inner: Vec<BoxedLayer<Registry>>,
+    filters: Vec<LayerFilter>,
 }
 
 impl Layers {
     /// Creates a new `Layers` instance.
     pub(crate) fn new() -> Self {
-        Self { inner: vec![] }
+        Self { inner: vec![], filters: vec![] }
     }
 
-    /// Consumes the `Layers` instance, returning the inner vector of layers.
-    pub(crate) fn into_inner(self) -> Vec<BoxedLayer<Registry>> {
-        self.inner
+    /// Consumes the `Layers` instance, returning the inner vector of layers and the handle to
+    /// reload their filters.
+    pub(crate) fn into_inner(self) -> (Vec<BoxedLayer<Registry>>, LogFilterHandle) {
+        (self.inner, LogFilterHandle::new(self.filters))
     }
 
     /// Adds a journald layer to the layers collection.
@@ -54,9 +63,10 @@ impl Layers {
     /// # Returns
     /// An `eyre::Result<()>` indicating the success or failure of the operation.
     pub(crate) fn journald(&mut self, filter: &str) -> eyre::Result<()> {
-        let journald_filter = build_env_filter(None, filter)?;
+        let (journald_filter, layer_filter) = LayerFilter::new(None, filter)?;
         let layer = tracing_journald::layer()?.with_filter(journald_filter).boxed();
         self.inner.push(layer);
+        self.filters.push(layer_filter);
         Ok(())
     }
 
@@ -80,9 +90,10 @@ impl Layers {
         filters: &str,
         color: Option<String>,
     ) -> eyre::Result<()> {
-        let filter = build_env_filter(Some(default_directive), filters)?;
+        let (filter, layer_filter) = LayerFilter::new(Some(default_directive), filters)?;
         let layer = format.apply(filter, color, None);
         self.inner.push(layer.boxed());
+        self.filters.push(layer_filter);
         Ok(())
     }
 
@@ -102,11 +113,26 @@ impl Layers {
         file_info: FileInfo,
     ) -> eyre::Result<FileWorkerGuard> {
         let (writer, guard) = file_info.create_log_writer();
-        let file_filter = build_env_filter(None, filter)?;
+        let (file_filter, layer_filter) = LayerFilter::new(None, filter)?;
         let layer = format.apply(file_filter, None, Some(writer));
         self.inner.push(layer);
+        self.filters.push(layer_filter);
         Ok(guard)
     }
+
+    /// Adds a layer exporting spans to an OTLP collector.
+    ///
+    /// # Arguments
+    /// * `config` - The configuration of the exporter, including its filter directives.
+    ///
+    /// # Returns
+    /// An `eyre::Result<()>` indicating the success or failure of the operation.
+    pub(crate) fn otlp(&mut self, config: OtlpConfig) -> eyre::Result<()> {
+        let filter = build_env_filter(None, config.filter())?;
+        let layer = config.build()?.with_filter(filter).boxed();
+        self.inner.push(layer);
+        Ok(())
+    }
 }
 
 /// Holds configuration information for file logging.
@@ -166,7 +192,7 @@ impl FileInfo {
 ///
 /// # Returns
 /// An `eyre::Result<EnvFilter>` that can be used to configure a tracing subscriber.
-fn build_env_filter(
+pub(crate) fn build_env_filter(
     default_directive: Option<Directive>,
     directives: &str,
 ) -> eyre::Result<EnvFilter> {
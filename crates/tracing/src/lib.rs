@@ -1,9 +1,9 @@
 //!  The `tracing` module provides functionalities for setting up and configuring logging.
 //!
 //!  It includes structures and functions to create and manage various logging layers: stdout,
-//!  file, or journald. The module's primary entry point is the `Tracer` struct, which can be
-//!  configured to use different logging formats and destinations. If no layer is specified, it will
-//!  default to stdout.
+//!  file, or journald, and a layer exporting spans to an OTLP collector. The module's
+//! primary entry point is the `Tracer` struct, which can be  configured to use different logging
+//! formats and destinations. If no layer is specified, it will  default to stdout.
 //!
 //!  # Examples
 //!
@@ -49,12 +49,16 @@ pub use tracing_appender;
 pub use tracing_subscriber;
 
 // Re-export our types
+pub use filter::{log_filter_handle, verbosity_level, LogFilterHandle};
 pub use formatter::LogFormat;
 pub use layers::{FileInfo, FileWorkerGuard};
+pub use otlp::{OtlpConfig, OtlpLayer, DEFAULT_OTLP_ENDPOINT};
 pub use test_tracer::TestTracer;
 
+mod filter;
 mod formatter;
 mod layers;
+mod otlp;
 mod test_tracer;
 
 use crate::layers::Layers;
@@ -71,15 +75,16 @@ pub struct RethTracer {
     stdout: LayerInfo,
     journald: Option<String>,
     file: Option<(LayerInfo, FileInfo)>,
+    otlp: Option<OtlpConfig>,
 }
 
 impl RethTracer {
     ///  Constructs a new `Tracer` with default settings.
     ///
     ///  Initializes with default stdout layer configuration.
-    ///  Journald and file layers are not set by default.
+    ///  Journald, file and OTLP layers are not set by default.
     pub fn new() -> Self {
-        Self { stdout: LayerInfo::default(), journald: None, file: None }
+        Self { stdout: LayerInfo::default(), journald: None, file: None, otlp: None }
     }
 
     ///  Sets a custom configuration for the stdout layer.
@@ -109,6 +114,15 @@ impl RethTracer {
         self.file = Some((config, file_info));
         self
     }
+
+    ///  Sets the configuration of the layer exporting spans to an OTLP collector.
+    ///
+    ///  # Arguments
+    ///  * `config` - The `OtlpConfig` to use for the OTLP layer.
+    pub fn with_otlp(mut self, config: OtlpConfig) -> Self {
+        self.otlp = Some(config);
+        self
+    }
 }
 
 impl Default for RethTracer {
@@ -183,10 +197,13 @@ impl Tracer for RethTracer {
     ///  Initializes the logging system based on the configured layers.
     ///
     ///  This method sets up the global tracing subscriber with the specified
-    ///  stdout, journald, and file layers.
+    ///  stdout, journald, file and OTLP layers.
     ///
     ///  The default layer is stdout.
     ///
+    ///  Once installed, the filters of the layers can be changed at runtime through the handle
+    ///  returned by [`log_filter_handle`].
+    ///
     ///  # Returns
     ///  An `eyre::Result` which is `Ok` with an optional `WorkerGuard` if a file layer is used,
     ///  or an `Err` in case of an error during initialization.
@@ -210,9 +227,16 @@ impl Tracer for RethTracer {
             None
         };
 
+        if let Some(config) = self.otlp {
+            layers.otlp(config)?;
+        }
+
         // The error is returned if the global default subscriber is already set,
         // so it's safe to ignore it
-        let _ = tracing_subscriber::registry().with(layers.into_inner()).try_init();
+        let (layers, filter_handle) = layers.into_inner();
+        if tracing_subscriber::registry().with(layers).try_init().is_ok() {
+            filter::install_log_filter_handle(filter_handle);
+        }
         Ok(file_guard)
     }
 }
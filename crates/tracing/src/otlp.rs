@@ -0,0 +1,255 @@
+//! Export of `tracing` spans to an OTLP collector.
+//!
+//! Spans are recorded by [`tracing_opentelemetry`] and sent to the collector over OTLP/HTTP in
+//! batches by the `OpenTelemetry` SDK, see [`OtlpConfig`].
+
+use std::time::Duration;
+
+use opentelemetry::{trace::TracerProvider, KeyValue};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    trace::{Sampler, SdkTracerProvider, Tracer},
+    Resource,
+};
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+/// The default OTLP/HTTP endpoint of a local collector.
+pub const DEFAULT_OTLP_ENDPOINT: &str = "http://localhost:4318";
+
+/// The path spans are exported to, relative to the endpoint.
+const TRACES_PATH: &str = "/v1/traces";
+
+/// The timeout of a request to the collector.
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A [`Layer`](tracing_subscriber::Layer) that records spans and sends them to an OTLP collector
+/// once they are closed.
+///
+/// Events emitted inside of a span are recorded as span events. Exporting happens on a background
+/// thread that exits once the layer is dropped, after exporting the remaining spans.
+pub type OtlpLayer<S> = OpenTelemetryLayer<S, Tracer>;
+
+/// Configuration of the OTLP span exporter.
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+    endpoint: String,
+    sample_ratio: f64,
+    resource_attributes: Vec<(String, String)>,
+    filter: String,
+}
+
+impl OtlpConfig {
+    /// Creates a new configuration exporting all spans to the given endpoint.
+    ///
+    /// The endpoint is the base URL of an OTLP/HTTP collector, e.g. `http://localhost:4318`, spans
+    /// are sent to its `/v1/traces` path.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            sample_ratio: 1.0,
+            resource_attributes: vec![("service.name".to_string(), "reth".to_string())],
+            filter: String::new(),
+        }
+    }
+
+    /// Sets the ratio of traces to export, between `0.0` and `1.0`.
+    ///
+    /// The decision is made for the root span of a trace and inherited by all of its children.
+    pub const fn with_sample_ratio(mut self, sample_ratio: f64) -> Self {
+        self.sample_ratio = sample_ratio;
+        self
+    }
+
+    /// Adds an attribute describing the node to all exported spans, e.g. `service.name=reth`.
+    ///
+    /// Overrides a previously set attribute with the same key.
+    pub fn with_resource_attribute(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        let key = key.into();
+        self.resource_attributes.retain(|(k, _)| *k != key);
+        self.resource_attributes.push((key, value.into()));
+        self
+    }
+
+    /// Sets the filter directives selecting the spans to export, e.g. `info,engine::tree=trace`.
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = filter.into();
+        self
+    }
+
+    /// Returns the filter directives selecting the spans to export.
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Returns the URL spans are posted to.
+    fn traces_url(&self) -> String {
+        let endpoint = self.endpoint.trim_end_matches('/');
+        if endpoint.ends_with(TRACES_PATH) {
+            endpoint.to_string()
+        } else {
+            format!("{endpoint}{TRACES_PATH}")
+        }
+    }
+
+    /// Creates the layer recording spans and the exporter sending them to the collector.
+    pub fn build<S>(self) -> eyre::Result<OtlpLayer<S>>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        if !(0.0..=1.0).contains(&self.sample_ratio) {
+            eyre::bail!("sample ratio must be between 0 and 1, got {}", self.sample_ratio)
+        }
+
+        let exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(self.traces_url())
+            .with_timeout(EXPORT_TIMEOUT)
+            .build()?;
+        let resource = Resource::builder()
+            .with_attributes(
+                self.resource_attributes.into_iter().map(|(key, value)| KeyValue::new(key, value)),
+            )
+            .build();
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+                self.sample_ratio,
+            ))))
+            .with_resource(resource)
+            .build();
+
+        // the tracer keeps the provider alive, which exports the remaining spans once dropped
+        let tracer = provider.tracer("reth");
+        Ok(tracing_opentelemetry::layer().with_tracer(tracer).with_level(true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry_proto::tonic::{
+        collector::trace::v1::ExportTraceServiceRequest,
+        common::v1::{any_value::Value, AnyValue},
+    };
+    use prost::Message;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+    };
+    use tracing_subscriber::{layer::SubscriberExt, Registry};
+
+    /// Accepts one request like a collector would and returns its request line and body.
+    fn receive_request(listener: &TcpListener) -> (String, ExportTraceServiceRequest) {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            if header.trim_end().is_empty() {
+                break
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        reader.get_mut().write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+
+        (request_line, ExportTraceServiceRequest::decode(body.as_slice()).unwrap())
+    }
+
+    fn string_value(value: &str) -> Option<AnyValue> {
+        Some(AnyValue { value: Some(Value::StringValue(value.to_string())) })
+    }
+
+    #[test]
+    fn traces_url() {
+        assert_eq!(
+            OtlpConfig::new("http://localhost:4318").traces_url(),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            OtlpConfig::new("http://collector/otlp/").traces_url(),
+            "http://collector/otlp/v1/traces"
+        );
+        assert_eq!(
+            OtlpConfig::new("https://collector:4318/v1/traces").traces_url(),
+            "https://collector:4318/v1/traces"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_sample_ratio() {
+        assert!(OtlpConfig::new(DEFAULT_OTLP_ENDPOINT)
+            .with_sample_ratio(1.5)
+            .build::<Registry>()
+            .is_err());
+    }
+
+    #[test]
+    fn exports_spans_to_collector() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let collector = std::thread::spawn(move || receive_request(&listener));
+
+        let layer = OtlpConfig::new(endpoint)
+            .with_resource_attribute("service.instance.id", "node-1")
+            .build()
+            .unwrap();
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), || {
+            let outer = tracing::info_span!(target: "engine::tree", "new_payload", block = 1i64);
+            let _outer = outer.enter();
+            let inner = tracing::info_span!("execute", gas_used = tracing::field::Empty);
+            let _inner = inner.enter();
+            inner.record("gas_used", 21_000i64);
+            tracing::info!(valid = true, "Executed block");
+        });
+
+        let (request_line, request) = collector.join().unwrap();
+        assert_eq!(request_line.trim_end(), "POST /v1/traces HTTP/1.1");
+
+        let resource_spans = &request.resource_spans[0];
+        let resource = resource_spans.resource.as_ref().unwrap();
+        let resource_attribute = |key: &str| {
+            resource.attributes.iter().find(|kv| kv.key == key).and_then(|kv| kv.value.clone())
+        };
+        assert_eq!(resource_attribute("service.name"), string_value("reth"));
+        assert_eq!(resource_attribute("service.instance.id"), string_value("node-1"));
+
+        let spans = &resource_spans.scope_spans[0].spans;
+        assert_eq!(spans.len(), 2);
+        let (inner, outer) = (&spans[0], &spans[1]);
+        assert_eq!(inner.name, "execute");
+        assert_eq!(outer.name, "new_payload");
+        assert_eq!(inner.trace_id, outer.trace_id);
+        assert_eq!(inner.parent_span_id, outer.span_id);
+        assert!(outer.parent_span_id.is_empty());
+
+        let attribute = |span: &opentelemetry_proto::tonic::trace::v1::Span, key: &str| {
+            span.attributes.iter().find(|kv| kv.key == key).and_then(|kv| kv.value.clone())
+        };
+        assert_eq!(attribute(outer, "block"), Some(AnyValue { value: Some(Value::IntValue(1)) }));
+        assert_eq!(
+            attribute(inner, "gas_used"),
+            Some(AnyValue { value: Some(Value::IntValue(21_000)) })
+        );
+
+        let event = &inner.events[0];
+        assert_eq!(event.name, "Executed block");
+        assert!(event.attributes.iter().any(|kv| kv.key == "valid" &&
+            kv.value == Some(AnyValue { value: Some(Value::BoolValue(true)) })));
+    }
+}
@@ -19,10 +19,10 @@ use alloy_consensus::{
     BlockHeader,
 };
 use alloy_eips::{
-    eip1559::ETHEREUM_BLOCK_GAS_LIMIT,
-    eip4844::{env_settings::EnvKzgSettings, MAX_BLOBS_PER_BLOCK},
+    eip1559::ETHEREUM_BLOCK_GAS_LIMIT, eip4844::env_settings::EnvKzgSettings, eip7840::BlobParams,
 };
 use reth_chainspec::{ChainSpec, EthereumHardforks};
+use reth_payload_util::MinerConfig;
 use reth_primitives::{InvalidTransactionError, SealedBlock};
 use reth_primitives_traits::{Block, GotExpected};
 use reth_storage_api::{StateProvider, StateProviderFactory};
@@ -148,8 +148,12 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     eip7702: bool,
     /// The current max gas limit
     block_gas_limit: AtomicU64,
+    /// The max number of blobs per block of the active fork
+    max_blob_count: AtomicU64,
     /// Minimum priority fee to enforce for acceptance into the pool.
     minimum_priority_fee: Option<u128>,
+    /// Runtime updates of the minimum priority fee, taking precedence over the configured one.
+    miner_config: MinerConfig,
     /// Stores the setup and parameters needed for validating KZG proofs.
     kzg_settings: EnvKzgSettings,
     /// How to handle [`TransactionOrigin::Local`](TransactionOrigin) transactions.
@@ -274,7 +278,7 @@ where
         // the pool.
         if !self.local_transactions_config.is_local(origin, transaction.sender_ref()) &&
             transaction.is_eip1559() &&
-            transaction.max_priority_fee_per_gas() < self.minimum_priority_fee
+            transaction.max_priority_fee_per_gas() < self.minimum_priority_fee()
         {
             return TransactionValidationOutcome::Invalid(
                 transaction,
@@ -334,14 +338,15 @@ where
                 )
             }
 
-            if blob_count > MAX_BLOBS_PER_BLOCK {
+            let max_blob_count = self.max_blob_count();
+            if blob_count > max_blob_count {
                 // too many blobs
                 return TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidPoolTransactionError::Eip4844(
                         Eip4844PoolTransactionError::TooManyEip4844Blobs {
                             have: blob_count,
-                            permitted: MAX_BLOBS_PER_BLOCK,
+                            permitted: max_blob_count,
                         },
                     ),
                 )
@@ -521,12 +526,29 @@ where
             self.fork_tracker.prague.store(true, std::sync::atomic::Ordering::Relaxed);
         }
 
+        if let Some(blob_params) =
+            self.chain_spec.blob_params_at_timestamp(new_tip_block.timestamp())
+        {
+            self.max_blob_count
+                .store(blob_params.max_blob_count, std::sync::atomic::Ordering::Relaxed);
+        }
+
         self.block_gas_limit.store(new_tip_block.gas_limit(), std::sync::atomic::Ordering::Relaxed);
     }
 
     fn max_gas_limit(&self) -> u64 {
         self.block_gas_limit.load(std::sync::atomic::Ordering::Relaxed)
     }
+
+    /// Returns the max number of blobs per block of the active fork.
+    fn max_blob_count(&self) -> usize {
+        self.max_blob_count.load(std::sync::atomic::Ordering::Relaxed) as usize
+    }
+
+    /// Returns the minimum priority fee, preferring the one set at runtime.
+    fn minimum_priority_fee(&self) -> Option<u128> {
+        self.miner_config.minimum_priority_fee().or(self.minimum_priority_fee)
+    }
 }
 
 /// A builder for [`TransactionValidationTaskExecutor`]
@@ -549,8 +571,12 @@ pub struct EthTransactionValidatorBuilder {
     eip7702: bool,
     /// The current max gas limit
     block_gas_limit: AtomicU64,
+    /// The max number of blobs per block of the active fork
+    max_blob_count: AtomicU64,
     /// Minimum priority fee to enforce for acceptance into the pool.
     minimum_priority_fee: Option<u128>,
+    /// Runtime updates of the minimum priority fee.
+    miner_config: MinerConfig,
     /// Determines how many additional tasks to spawn
     ///
     /// Default is 1
@@ -576,8 +602,10 @@ impl EthTransactionValidatorBuilder {
     pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
         Self {
             block_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT.into(),
+            max_blob_count: BlobParams::cancun().max_blob_count.into(),
             chain_spec,
             minimum_priority_fee: None,
+            miner_config: MinerConfig::default(),
             additional_tasks: 1,
             kzg_settings: EnvKzgSettings::Default,
             local_transactions_config: Default::default(),
@@ -687,6 +715,15 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Sets the [`MinerConfig`] through which the minimum priority fee can be updated at runtime.
+    ///
+    /// A minimum priority fee set via the [`MinerConfig`] takes precedence over
+    /// [`Self::with_minimum_priority_fee`].
+    pub fn with_miner_config(mut self, miner_config: MinerConfig) -> Self {
+        self.miner_config = miner_config;
+        self
+    }
+
     /// Sets the number of additional tasks to spawn.
     pub const fn with_additional_tasks(mut self, additional_tasks: usize) -> Self {
         self.additional_tasks = additional_tasks;
@@ -700,6 +737,9 @@ impl EthTransactionValidatorBuilder {
         self.cancun = self.chain_spec.is_cancun_active_at_timestamp(timestamp);
         self.shanghai = self.chain_spec.is_shanghai_active_at_timestamp(timestamp);
         self.prague = self.chain_spec.is_prague_active_at_timestamp(timestamp);
+        if let Some(blob_params) = self.chain_spec.blob_params_at_timestamp(timestamp) {
+            self.max_blob_count = blob_params.max_blob_count.into();
+        }
         self
     }
 
@@ -736,7 +776,9 @@ impl EthTransactionValidatorBuilder {
             eip4844,
             eip7702,
             block_gas_limit,
+            max_blob_count,
             minimum_priority_fee,
+            miner_config,
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
@@ -758,7 +800,9 @@ impl EthTransactionValidatorBuilder {
             eip4844,
             eip7702,
             block_gas_limit,
+            max_blob_count,
             minimum_priority_fee,
+            miner_config,
             blob_store: Box::new(blob_store),
             kzg_settings,
             local_transactions_config,
@@ -965,4 +1009,35 @@ mod tests {
         let tx = pool.get(transaction.hash());
         assert!(tx.is_none());
     }
+
+    #[tokio::test]
+    async fn minimum_priority_fee_updated_at_runtime() {
+        let transaction = get_transaction();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+
+        let miner_config = MinerConfig::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .with_minimum_priority_fee(1) // tx priority fee is 3 gwei
+            .with_miner_config(miner_config.clone())
+            .build(provider, InMemoryBlobStore::default());
+
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(outcome.is_valid());
+
+        miner_config.set_minimum_priority_fee(4_000_000_000);
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(_, InvalidPoolTransactionError::Underpriced)
+        ));
+
+        miner_config.set_minimum_priority_fee(3_000_000_000);
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction);
+        assert!(outcome.is_valid());
+    }
 }
@@ -31,3 +31,68 @@ impl reth_codecs::Compact for StorageTrieEntry {
         (this, buf)
     }
 }
+
+/// Value of an intermediate trie node before it was changed by a block.
+///
+/// Used as the value of the trie changeset tables, where `node` is [`None`] if the node didn't
+/// exist before the block.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+pub struct TrieChangeSetsEntry {
+    /// The nibbles of the intermediate node
+    pub nibbles: StoredNibblesSubKey,
+    /// Encoded node, if it existed.
+    pub node: Option<BranchNodeCompact>,
+}
+
+// NOTE: Like with `StorageTrieEntry`, the subkey is encoded manually so that the entries can be
+// fetched with `seek_by_key_subkey`.
+#[cfg(any(test, feature = "reth-codec"))]
+impl reth_codecs::Compact for TrieChangeSetsEntry {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        let nibbles_len = self.nibbles.to_compact(buf);
+        let node_len = self.node.as_ref().map(|node| node.to_compact(buf)).unwrap_or_default();
+        nibbles_len + node_len
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (nibbles, buf) = StoredNibblesSubKey::from_compact(buf, 65);
+        if len == 65 {
+            return (Self { nibbles, node: None }, buf)
+        }
+        let (node, buf) = BranchNodeCompact::from_compact(buf, len - 65);
+        (Self { nibbles, node: Some(node) }, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Nibbles, TrieMask};
+    use alloy_primitives::B256;
+    use reth_codecs::Compact;
+
+    #[test]
+    fn trie_changesets_entry_roundtrip() {
+        let nibbles = StoredNibblesSubKey(Nibbles::from_nibbles_unchecked([0x1, 0x2, 0x3]));
+        let node = BranchNodeCompact::new(
+            TrieMask::new(0b11),
+            TrieMask::new(0b01),
+            TrieMask::new(0b10),
+            vec![B256::repeat_byte(0xaa)],
+            None,
+        );
+
+        for entry in [
+            TrieChangeSetsEntry { nibbles: nibbles.clone(), node: None },
+            TrieChangeSetsEntry { nibbles, node: Some(node) },
+        ] {
+            let mut buf = Vec::new();
+            let len = entry.to_compact(&mut buf);
+            assert_eq!(TrieChangeSetsEntry::from_compact(&buf, len).0, entry);
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use alloy_primitives::{
+    map::{B256HashMap, HashMap},
+    BlockNumber, B256,
+};
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    models::BlockNumberHashedAddress,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_storage_errors::db::DatabaseError;
+use reth_trie::{
+    updates::{StorageTrieUpdates, TrieUpdates},
+    BranchNodeCompact, Nibbles, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey,
+    TrieChangeSetsEntry,
+};
+use std::ops::RangeInclusive;
+
+/// Extends [`TrieUpdates`] with operations specific for working with a database transaction.
+pub trait DatabaseTrieUpdates<TX>: Sized {
+    /// Initializes [`TrieUpdates`] from trie changesets. Iterates over trie changesets from the
+    /// specified block up to the current tip and aggregates them into trie updates that restore
+    /// the intermediate nodes as they were before `from`.
+    ///
+    /// The result is only correct if [`DatabaseTrieChangeSets::is_available`] holds for the range
+    /// from `from` up to the tip.
+    fn from_reverts(tx: &TX, from: BlockNumber) -> Result<Self, DatabaseError>;
+}
+
+impl<TX: DbTx> DatabaseTrieUpdates<TX> for TrieUpdates {
+    fn from_reverts(tx: &TX, from: BlockNumber) -> Result<Self, DatabaseError> {
+        // Iterate over account trie changesets and record the node before its first change.
+        let mut account_nodes = HashMap::<Nibbles, Option<BranchNodeCompact>>::default();
+        let mut account_cursor = tx.cursor_read::<tables::AccountsTrieChangeSets>()?;
+        for entry in account_cursor.walk_range(from..)? {
+            let (_, TrieChangeSetsEntry { nibbles, node }) = entry?;
+            // The root node is never persisted, its entry only marks the block as available.
+            if !nibbles.0.is_empty() {
+                account_nodes.entry(nibbles.0).or_insert(node);
+            }
+        }
+
+        // Iterate over storage trie changesets and record the node before its first change.
+        let mut storage_nodes =
+            B256HashMap::<HashMap<Nibbles, Option<BranchNodeCompact>>>::default();
+        let mut storage_cursor = tx.cursor_read::<tables::StoragesTrieChangeSets>()?;
+        for entry in storage_cursor.walk_range(BlockNumberHashedAddress((from, B256::ZERO))..)? {
+            let (key, TrieChangeSetsEntry { nibbles, node }) = entry?;
+            storage_nodes.entry(key.hashed_address()).or_default().entry(nibbles.0).or_insert(node);
+        }
+
+        let mut updates = Self::default();
+        for (nibbles, node) in account_nodes {
+            match node {
+                Some(node) => {
+                    updates.account_nodes.insert(nibbles, node);
+                }
+                None => {
+                    updates.removed_nodes.insert(nibbles);
+                }
+            }
+        }
+        for (hashed_address, nodes) in storage_nodes {
+            let mut storage_updates = StorageTrieUpdates::default();
+            for (nibbles, node) in nodes {
+                match node {
+                    Some(node) => {
+                        storage_updates.storage_nodes.insert(nibbles, node);
+                    }
+                    None => {
+                        storage_updates.removed_nodes.insert(nibbles);
+                    }
+                }
+            }
+            updates.storage_tries.insert(hashed_address, storage_updates);
+        }
+
+        Ok(updates)
+    }
+}
+
+/// A wrapper around a database transaction for reading and writing trie changesets.
+///
+/// Trie changesets record the intermediate trie nodes as they were before a block modified them.
+/// Every block with changesets has an entry for the root path in
+/// [`tables::AccountsTrieChangeSets`], even if its trie updates were empty.
+#[derive(Debug)]
+pub struct DatabaseTrieChangeSets<'a, TX>(&'a TX);
+
+impl<'a, TX> DatabaseTrieChangeSets<'a, TX> {
+    /// Create new [`DatabaseTrieChangeSets`].
+    pub const fn new(tx: &'a TX) -> Self {
+        Self(tx)
+    }
+}
+
+impl<TX: DbTx> DatabaseTrieChangeSets<'_, TX> {
+    /// Returns `true` if trie changesets exist for every block in the given range.
+    ///
+    /// Changesets are always kept contiguous, see [`Self::ensure_contiguous`], so only the first
+    /// and the last recorded blocks are checked.
+    pub fn is_available(&self, range: RangeInclusive<BlockNumber>) -> Result<bool, DatabaseError> {
+        if range.is_empty() {
+            return Ok(true)
+        }
+
+        let mut cursor = self.0.cursor_read::<tables::AccountsTrieChangeSets>()?;
+        let Some((first, _)) = cursor.first()? else { return Ok(false) };
+        let Some((last, _)) = cursor.last()? else { return Ok(false) };
+        Ok(first <= *range.start() && last >= *range.end())
+    }
+}
+
+impl<TX: DbTxMut + DbTx> DatabaseTrieChangeSets<'_, TX> {
+    /// Records the current state of all trie nodes touched by the given trie updates as the
+    /// changesets of `block_number`.
+    ///
+    /// Must be called before the trie updates are written to the database. Returns the number of
+    /// entries written.
+    pub fn write(
+        &self,
+        block_number: BlockNumber,
+        trie_updates: &TrieUpdates,
+    ) -> Result<usize, DatabaseError> {
+        let mut num_entries = 0;
+
+        // Collect the touched account nodes, always including the root path.
+        let mut account_paths = trie_updates
+            .account_nodes_ref()
+            .keys()
+            .chain(trie_updates.removed_nodes_ref())
+            .filter(|nibbles| !nibbles.is_empty())
+            .cloned()
+            .collect::<Vec<_>>();
+        account_paths.push(Nibbles::default());
+        account_paths.sort_unstable();
+        account_paths.dedup();
+
+        let mut account_trie_cursor = self.0.cursor_read::<tables::AccountsTrie>()?;
+        let mut account_changesets_cursor =
+            self.0.cursor_dup_write::<tables::AccountsTrieChangeSets>()?;
+        for nibbles in account_paths {
+            let node = if nibbles.is_empty() {
+                None
+            } else {
+                account_trie_cursor
+                    .seek_exact(StoredNibbles(nibbles.clone()))?
+                    .map(|(_, node)| node)
+            };
+            account_changesets_cursor.upsert(
+                block_number,
+                &TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(nibbles), node },
+            )?;
+            num_entries += 1;
+        }
+
+        let mut storage_tries = Vec::from_iter(trie_updates.storage_tries_ref());
+        storage_tries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        let mut storage_trie_cursor = self.0.cursor_dup_read::<tables::StoragesTrie>()?;
+        let mut storage_changesets_cursor =
+            self.0.cursor_dup_write::<tables::StoragesTrieChangeSets>()?;
+        for (hashed_address, storage_trie_updates) in storage_tries {
+            let mut entries = HashMap::<Nibbles, Option<BranchNodeCompact>>::default();
+
+            // If the storage trie is wiped, every existing node is touched.
+            if storage_trie_updates.is_deleted() {
+                let mut entry = storage_trie_cursor.seek_exact(*hashed_address)?;
+                while let Some((_, StorageTrieEntry { nibbles, node })) = entry {
+                    entries.insert(nibbles.0, Some(node));
+                    entry = storage_trie_cursor.next_dup()?;
+                }
+            }
+
+            for nibbles in storage_trie_updates
+                .storage_nodes_ref()
+                .keys()
+                .chain(storage_trie_updates.removed_nodes_ref())
+                .filter(|nibbles| !nibbles.is_empty())
+            {
+                if entries.contains_key(nibbles) {
+                    continue
+                }
+                let subkey = StoredNibblesSubKey(nibbles.clone());
+                let node = storage_trie_cursor
+                    .seek_by_key_subkey(*hashed_address, subkey.clone())?
+                    .filter(|entry| entry.nibbles == subkey)
+                    .map(|entry| entry.node);
+                entries.insert(nibbles.clone(), node);
+            }
+
+            let mut entries = Vec::from_iter(entries);
+            entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+            let key = BlockNumberHashedAddress((block_number, *hashed_address));
+            for (nibbles, node) in entries {
+                storage_changesets_cursor.upsert(
+                    key,
+                    &TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(nibbles), node },
+                )?;
+                num_entries += 1;
+            }
+        }
+
+        Ok(num_entries)
+    }
+
+    /// Records trie updates that restore the intermediate nodes changed by a block to their state
+    /// before it, e.g. as computed when unwinding the block, as the changesets of `block_number`.
+    ///
+    /// Must be called before the trie updates are written to the database. Returns the number of
+    /// entries written.
+    pub fn write_reverted(
+        &self,
+        block_number: BlockNumber,
+        reverted: &TrieUpdates,
+    ) -> Result<usize, DatabaseError> {
+        let mut num_entries = 0;
+
+        // Nodes that are removed by the reverted updates didn't exist before the block.
+        let mut account_nodes = reverted
+            .removed_nodes_ref()
+            .iter()
+            .filter(|nibbles| !nibbles.is_empty())
+            .map(|nibbles| (nibbles.clone(), None))
+            .collect::<HashMap<_, _>>();
+        account_nodes.extend(
+            reverted
+                .account_nodes_ref()
+                .iter()
+                .filter(|(nibbles, _)| !nibbles.is_empty())
+                .map(|(nibbles, node)| (nibbles.clone(), Some(node.clone()))),
+        );
+        account_nodes.insert(Nibbles::default(), None);
+        let mut account_nodes = Vec::from_iter(account_nodes);
+        account_nodes.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+        let mut account_changesets_cursor =
+            self.0.cursor_dup_write::<tables::AccountsTrieChangeSets>()?;
+        for (nibbles, node) in account_nodes {
+            account_changesets_cursor.upsert(
+                block_number,
+                &TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(nibbles), node },
+            )?;
+            num_entries += 1;
+        }
+
+        let mut storage_tries = Vec::from_iter(reverted.storage_tries_ref());
+        storage_tries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        let mut storage_trie_cursor = self.0.cursor_dup_read::<tables::StoragesTrie>()?;
+        let mut storage_changesets_cursor =
+            self.0.cursor_dup_write::<tables::StoragesTrieChangeSets>()?;
+        for (hashed_address, storage_trie_updates) in storage_tries {
+            let mut entries = HashMap::<Nibbles, Option<BranchNodeCompact>>::default();
+
+            // If the storage trie is wiped, none of the current nodes existed before the block.
+            if storage_trie_updates.is_deleted() {
+                let mut entry = storage_trie_cursor.seek_exact(*hashed_address)?;
+                while let Some((_, StorageTrieEntry { nibbles, .. })) = entry {
+                    entries.insert(nibbles.0, None);
+                    entry = storage_trie_cursor.next_dup()?;
+                }
+            }
+            for nibbles in storage_trie_updates.removed_nodes_ref() {
+                entries.insert(nibbles.clone(), None);
+            }
+            for (nibbles, node) in storage_trie_updates.storage_nodes_ref() {
+                entries.insert(nibbles.clone(), Some(node.clone()));
+            }
+
+            let mut entries = Vec::from_iter(entries);
+            entries.retain(|(nibbles, _)| !nibbles.is_empty());
+            entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+            let key = BlockNumberHashedAddress((block_number, *hashed_address));
+            for (nibbles, node) in entries {
+                storage_changesets_cursor.upsert(
+                    key,
+                    &TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(nibbles), node },
+                )?;
+                num_entries += 1;
+            }
+        }
+
+        Ok(num_entries)
+    }
+
+    /// Prepares recording the changesets of `next`: removes all trie changesets if they don't end
+    /// right before it.
+    ///
+    /// Changesets of a block are only usable together with the changesets of every later block up
+    /// to the tip, so recorded changesets followed by a gap can never be used again.
+    pub fn ensure_contiguous(&self, next: BlockNumber) -> Result<(), DatabaseError> {
+        let last = self.0.cursor_read::<tables::AccountsTrieChangeSets>()?.last()?;
+        if last.is_some_and(|(last, _)| last.checked_add(1) != Some(next)) {
+            self.0.clear::<tables::AccountsTrieChangeSets>()?;
+            self.0.clear::<tables::StoragesTrieChangeSets>()?;
+        }
+        Ok(())
+    }
+
+    /// Removes the trie changesets of all blocks before `below`.
+    pub fn prune(&self, below: BlockNumber) -> Result<(), DatabaseError> {
+        let mut account_cursor = self.0.cursor_dup_write::<tables::AccountsTrieChangeSets>()?;
+        while account_cursor.first()?.is_some_and(|(block_number, _)| block_number < below) {
+            account_cursor.delete_current_duplicates()?;
+        }
+
+        let mut storage_cursor = self.0.cursor_dup_write::<tables::StoragesTrieChangeSets>()?;
+        while storage_cursor.first()?.is_some_and(|(key, _)| key.block_number() < below) {
+            storage_cursor.delete_current_duplicates()?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the trie changesets of all blocks starting from `from`.
+    pub fn unwind(&self, from: BlockNumber) -> Result<(), DatabaseError> {
+        let mut account_cursor = self.0.cursor_dup_write::<tables::AccountsTrieChangeSets>()?;
+        while account_cursor.seek(from)?.is_some() {
+            account_cursor.delete_current_duplicates()?;
+        }
+
+        let mut storage_cursor = self.0.cursor_dup_write::<tables::StoragesTrieChangeSets>()?;
+        while storage_cursor.seek(BlockNumberHashedAddress((from, B256::ZERO)))?.is_some() {
+            storage_cursor.delete_current_duplicates()?;
+        }
+
+        Ok(())
+    }
+}
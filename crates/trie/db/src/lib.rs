@@ -1,5 +1,6 @@
 //! An integration of [`reth-trie`] with [`reth-db`].
 
+mod changesets;
 mod commitment;
 mod hashed_cursor;
 mod prefix_set;
@@ -9,6 +10,7 @@ mod storage;
 mod trie_cursor;
 mod witness;
 
+pub use changesets::{DatabaseTrieChangeSets, DatabaseTrieUpdates};
 pub use commitment::{MerklePatriciaTrie, StateCommitment};
 pub use hashed_cursor::{
     DatabaseHashedAccountCursor, DatabaseHashedCursorFactory, DatabaseHashedStorageCursor,
@@ -0,0 +1,216 @@
+#![allow(missing_docs)]
+
+use alloy_primitives::{keccak256, B256, U256};
+use reth_db::tables;
+use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
+use reth_primitives::Account;
+use reth_provider::{test_utils::create_test_provider_factory, StateWriter, TrieWriter};
+use reth_trie::{
+    prefix_set::TriePrefixSetsMut,
+    trie_cursor::{InMemoryTrieCursorFactory, TrieCursor, TrieCursorFactory},
+    updates::TrieUpdates,
+    BranchNodeCompact, HashedPostState, HashedStorage, Nibbles, StateRoot, TrieInput,
+};
+use reth_trie_db::{
+    DatabaseStateRoot, DatabaseTrieChangeSets, DatabaseTrieCursorFactory, DatabaseTrieUpdates,
+};
+
+fn hashed(i: u64) -> B256 {
+    keccak256(B256::from(U256::from(i)))
+}
+
+fn account(balance: u64) -> Account {
+    Account { nonce: 1, balance: U256::from(balance), bytecode_hash: None }
+}
+
+fn collect_nodes(mut cursor: impl TrieCursor) -> Vec<(Nibbles, BranchNodeCompact)> {
+    let mut nodes = Vec::new();
+    let mut entry = cursor.seek(Nibbles::default()).unwrap();
+    while let Some(node) = entry {
+        nodes.push(node);
+        entry = cursor.next().unwrap();
+    }
+    nodes
+}
+
+/// Hashed addresses of an account whose storage is modified and of one that is destroyed.
+fn storage_accounts() -> (B256, B256) {
+    (hashed(0), hashed(1))
+}
+
+/// Returns the state changes of block 1 and block 2, and the state that reverts block 2.
+fn block_states() -> (HashedPostState, HashedPostState, HashedPostState) {
+    let (modified, wiped) = storage_accounts();
+
+    // Block 1 creates accounts, two of which have storage.
+    let mut state_1 = HashedPostState::default();
+    for i in 0..500 {
+        state_1.accounts.insert(hashed(i), Some(account(i)));
+    }
+    for address in [modified, wiped] {
+        state_1.storages.insert(
+            address,
+            HashedStorage::from_iter(false, (1..200).map(|slot| (hashed(slot), U256::from(slot)))),
+        );
+    }
+
+    // Block 2 modifies and creates accounts, changes storage and destroys an account.
+    let mut state_2 = HashedPostState::default();
+    let mut revert_state = HashedPostState::default();
+    for i in 2..50 {
+        state_2.accounts.insert(hashed(i), Some(account(i + 1000)));
+        revert_state.accounts.insert(hashed(i), Some(account(i)));
+    }
+    for i in 500..600 {
+        state_2.accounts.insert(hashed(i), Some(account(i)));
+        revert_state.accounts.insert(hashed(i), None);
+    }
+    state_2.storages.insert(
+        modified,
+        HashedStorage::from_iter(
+            false,
+            (1..50).chain(200..260).map(|slot| (hashed(slot), U256::from(slot + 1000))),
+        ),
+    );
+    revert_state.storages.insert(
+        modified,
+        HashedStorage::from_iter(
+            false,
+            (1..50)
+                .map(|slot| (hashed(slot), U256::from(slot)))
+                .chain((200..260).map(|slot| (hashed(slot), U256::ZERO))),
+        ),
+    );
+    state_2.accounts.insert(wiped, None);
+    state_2.storages.insert(wiped, HashedStorage::new(true));
+    revert_state.accounts.insert(wiped, Some(account(1)));
+    revert_state.storages.insert(
+        wiped,
+        HashedStorage::from_iter(false, (1..200).map(|slot| (hashed(slot), U256::from(slot)))),
+    );
+
+    (state_1, state_2, revert_state)
+}
+
+#[test]
+fn reverts_restore_trie_nodes() {
+    let factory = create_test_provider_factory();
+    let provider = factory.provider_rw().unwrap();
+    let tx = provider.tx_ref();
+
+    let (modified, wiped) = storage_accounts();
+    let (state_1, state_2, revert_state) = block_states();
+
+    let (root_1, updates_1) = StateRoot::overlay_root_with_updates(tx, state_1.clone()).unwrap();
+    DatabaseTrieChangeSets::new(tx).write(1, &updates_1).unwrap();
+    provider.write_hashed_state(&state_1.clone().into_sorted()).unwrap();
+    provider.write_trie_updates(&updates_1).unwrap();
+
+    let factory_1 = DatabaseTrieCursorFactory::new(tx);
+    let accounts_1 = collect_nodes(factory_1.account_trie_cursor().unwrap());
+    let modified_1 = collect_nodes(factory_1.storage_trie_cursor(modified).unwrap());
+    let wiped_1 = collect_nodes(factory_1.storage_trie_cursor(wiped).unwrap());
+    assert!(!accounts_1.is_empty());
+    assert!(!modified_1.is_empty());
+    assert!(!wiped_1.is_empty());
+
+    let (root_2, updates_2) = StateRoot::overlay_root_with_updates(tx, state_2.clone()).unwrap();
+    assert_ne!(root_1, root_2);
+    DatabaseTrieChangeSets::new(tx).write(2, &updates_2).unwrap();
+    provider.write_hashed_state(&state_2.into_sorted()).unwrap();
+    provider.write_trie_updates(&updates_2).unwrap();
+
+    let changesets = DatabaseTrieChangeSets::new(tx);
+    assert!(changesets.is_available(1..=2).unwrap());
+    assert!(!changesets.is_available(0..=2).unwrap());
+    assert!(!changesets.is_available(2..=3).unwrap());
+
+    // Reverting block 2 restores the intermediate nodes of block 1.
+    let reverts = TrieUpdates::from_reverts(tx, 2).unwrap();
+    let reverts_sorted = reverts.clone().into_sorted();
+    let factory_1 =
+        InMemoryTrieCursorFactory::new(DatabaseTrieCursorFactory::new(tx), &reverts_sorted);
+    assert_eq!(collect_nodes(factory_1.account_trie_cursor().unwrap()), accounts_1);
+    assert_eq!(collect_nodes(factory_1.storage_trie_cursor(modified).unwrap()), modified_1);
+    assert_eq!(collect_nodes(factory_1.storage_trie_cursor(wiped).unwrap()), wiped_1);
+
+    // The restored nodes yield the historical root without any prefix sets.
+    let input = TrieInput::new(reverts, revert_state, TriePrefixSetsMut::default());
+    assert_eq!(StateRoot::overlay_root_from_nodes(tx, input).unwrap(), root_1);
+
+    // Unwinding removes the changesets of block 2 only.
+    changesets.unwind(2).unwrap();
+    assert!(changesets.is_available(1..=1).unwrap());
+    assert!(!changesets.is_available(2..=2).unwrap());
+    assert_eq!(TrieUpdates::from_reverts(tx, 2).unwrap(), TrieUpdates::default());
+    assert!(tx
+        .cursor_read::<tables::StoragesTrieChangeSets>()
+        .unwrap()
+        .walk(None)
+        .unwrap()
+        .all(|entry| entry.unwrap().0.block_number() == 1));
+}
+
+#[test]
+fn reverted_updates_restore_trie_nodes() {
+    let factory = create_test_provider_factory();
+    let provider = factory.provider_rw().unwrap();
+    let tx = provider.tx_ref();
+
+    let (modified, wiped) = storage_accounts();
+    let (state_1, state_2, revert_state) = block_states();
+
+    let (root_1, updates_1) = StateRoot::overlay_root_with_updates(tx, state_1.clone()).unwrap();
+    provider.write_hashed_state(&state_1.into_sorted()).unwrap();
+    provider.write_trie_updates(&updates_1).unwrap();
+
+    let factory_1 = DatabaseTrieCursorFactory::new(tx);
+    let accounts_1 = collect_nodes(factory_1.account_trie_cursor().unwrap());
+    let modified_1 = collect_nodes(factory_1.storage_trie_cursor(modified).unwrap());
+    let wiped_1 = collect_nodes(factory_1.storage_trie_cursor(wiped).unwrap());
+
+    let (_, updates_2) = StateRoot::overlay_root_with_updates(tx, state_2.clone()).unwrap();
+    provider.write_hashed_state(&state_2.into_sorted()).unwrap();
+    provider.write_trie_updates(&updates_2).unwrap();
+
+    // Record the changesets of block 2 from the updates that revert it, as the pipeline does.
+    let (reverted_root, reverted) =
+        StateRoot::overlay_root_with_updates(tx, revert_state.clone()).unwrap();
+    assert_eq!(reverted_root, root_1);
+    let changesets = DatabaseTrieChangeSets::new(tx);
+    changesets.write_reverted(2, &reverted).unwrap();
+    assert!(changesets.is_available(2..=2).unwrap());
+
+    let reverts = TrieUpdates::from_reverts(tx, 2).unwrap();
+    let reverts_sorted = reverts.clone().into_sorted();
+    let factory_1 =
+        InMemoryTrieCursorFactory::new(DatabaseTrieCursorFactory::new(tx), &reverts_sorted);
+    assert_eq!(collect_nodes(factory_1.account_trie_cursor().unwrap()), accounts_1);
+    assert_eq!(collect_nodes(factory_1.storage_trie_cursor(modified).unwrap()), modified_1);
+    assert_eq!(collect_nodes(factory_1.storage_trie_cursor(wiped).unwrap()), wiped_1);
+
+    let input = TrieInput::new(reverts, revert_state, TriePrefixSetsMut::default());
+    assert_eq!(StateRoot::overlay_root_from_nodes(tx, input).unwrap(), root_1);
+}
+
+#[test]
+fn changesets_are_contiguous_and_pruned() {
+    let factory = create_test_provider_factory().with_trie_changesets(Some(2));
+    let provider = factory.provider_rw().unwrap();
+    let tx = provider.tx_ref();
+    let changesets = DatabaseTrieChangeSets::new(tx);
+
+    for block_number in 1..=3 {
+        provider.write_trie_changesets(block_number, &TrieUpdates::default()).unwrap();
+    }
+
+    // Only the changesets within the retention distance of the last block are kept.
+    assert!(changesets.is_available(2..=3).unwrap());
+    assert!(!changesets.is_available(1..=3).unwrap());
+
+    // A gap makes the recorded changesets unusable, so they are removed.
+    provider.write_trie_changesets(5, &TrieUpdates::default()).unwrap();
+    assert!(changesets.is_available(5..=5).unwrap());
+    assert!(!changesets.is_available(3..=5).unwrap());
+    assert_eq!(tx.entries::<tables::AccountsTrieChangeSets>().unwrap(), 1);
+}
@@ -100,11 +100,11 @@ impl<'a, C: TrieCursor> InMemoryAccountTrieCursor<'a, C> {
     ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
         let in_memory = self.in_memory_cursor.first_after(&last);
 
-        // Reposition the cursor to the first greater or equal node that wasn't removed.
+        // Reposition the cursor to the first greater node that wasn't removed.
         let mut db_entry = self.cursor.seek(last.clone())?;
         while db_entry
             .as_ref()
-            .is_some_and(|entry| entry.0 < last || self.removed_nodes.contains(&entry.0))
+            .is_some_and(|entry| entry.0 <= last || self.removed_nodes.contains(&entry.0))
         {
             db_entry = self.cursor.next()?;
         }
@@ -230,10 +230,10 @@ impl<C: TrieCursor> InMemoryStorageTrieCursor<'_, C> {
             return Ok(in_memory)
         }
 
-        // Reposition the cursor to the first greater or equal node that wasn't removed.
+        // Reposition the cursor to the first greater node that wasn't removed.
         let mut db_entry = self.cursor.seek(last.clone())?;
         while db_entry.as_ref().is_some_and(|entry| {
-            entry.0 < last || self.removed_nodes.as_ref().is_some_and(|r| r.contains(&entry.0))
+            entry.0 <= last || self.removed_nodes.as_ref().is_some_and(|r| r.contains(&entry.0))
         }) {
             db_entry = self.cursor.next()?;
         }
@@ -211,6 +211,7 @@ impl PayloadValidator for CustomEngineValidator {
         &self,
         payload: ExecutionPayload,
         sidecar: ExecutionPayloadSidecar,
+        _withdrawals_root: Option<B256>,
     ) -> Result<SealedBlock<Self::Block>, PayloadError> {
         self.inner.ensure_well_formed_payload(payload, sidecar)
     }